@@ -0,0 +1,143 @@
+//! Bootstrap confidence intervals
+//!
+//! Estimates the uncertainty of a metric by resampling the
+//! `(output, target)` pairs with replacement and recomputing the
+//! metric on each resample. The confidence interval is given by the
+//! percentiles of the resampled scores.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::analysis::bootstrap::Bootstrap;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! let outputs = Vector::new(vec![1.0, 0.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.0]);
+//! let targets = Vector::new(vec![1.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0]);
+//!
+//! let accuracy = |outputs: &Vector<f64>, targets: &Vector<f64>| {
+//!     let correct = outputs.data().iter().zip(targets.data().iter())
+//!                          .filter(|&(o, t)| o == t)
+//!                          .count();
+//!     correct as f64 / targets.size() as f64
+//! };
+//!
+//! let ci = Bootstrap::new(1000, 1).interval(&outputs, &targets, accuracy);
+//!
+//! assert_eq!(ci.estimate, 0.75);
+//! assert!(ci.lower <= ci.estimate && ci.estimate <= ci.upper);
+//! ```
+
+use learning::toolkit::rand_utils::seeded_rng;
+use linalg::vector::Vector;
+
+use rand::Rng;
+
+/// A percentile confidence interval for a metric.
+#[derive(Clone, Debug)]
+pub struct ConfidenceInterval {
+    /// The metric computed on all of the samples.
+    pub estimate: f64,
+    /// The lower bound of the interval.
+    pub lower: f64,
+    /// The upper bound of the interval.
+    pub upper: f64,
+    /// The metric computed on each resample, in increasing order.
+    pub scores: Vec<f64>,
+}
+
+/// Bootstrap resampling of a metric.
+#[derive(Clone, Copy, Debug)]
+pub struct Bootstrap {
+    n_resamples: usize,
+    confidence: f64,
+    seed: u64,
+}
+
+impl Bootstrap {
+    /// Constructs a bootstrap drawing `n_resamples` resamples.
+    ///
+    /// Defaults to a confidence level of 0.95. The same resamples are
+    /// drawn for the same seed.
+    ///
+    /// # Panics
+    ///
+    /// - n_resamples is zero.
+    pub fn new(n_resamples: usize, seed: u64) -> Bootstrap {
+        assert!(n_resamples > 0, "Must draw at least one resample.");
+
+        Bootstrap {
+            n_resamples: n_resamples,
+            confidence: 0.95,
+            seed: seed,
+        }
+    }
+
+    /// Sets the confidence level of the interval.
+    ///
+    /// # Panics
+    ///
+    /// - The confidence is not in the interval (0, 1).
+    pub fn confidence(mut self, confidence: f64) -> Bootstrap {
+        assert!(confidence > 0f64 && confidence < 1f64,
+                "Confidence must be in the interval (0, 1).");
+        self.confidence = confidence;
+        self
+    }
+
+    /// Computes the confidence interval of the metric.
+    ///
+    /// The metric is called with the outputs and targets of each
+    /// resample. Resamples for which the metric is NaN, for example
+    /// because a class is missing, are left out of the interval.
+    ///
+    /// # Panics
+    ///
+    /// - The outputs and targets have different lengths.
+    /// - There are no samples.
+    /// - The metric is NaN on every resample.
+    pub fn interval<T, S>(&self,
+                          outputs: &Vector<T>,
+                          targets: &Vector<T>,
+                          metric: S)
+                          -> ConfidenceInterval
+        where T: Copy,
+              S: Fn(&Vector<T>, &Vector<T>) -> f64
+    {
+        assert!(outputs.size() == targets.size(),
+                "Outputs and targets have different lengths.");
+        assert!(outputs.size() > 0, "Cannot resample empty outputs.");
+
+        let mut rng = seeded_rng(self.seed);
+        let n = outputs.size();
+
+        let mut scores: Vec<f64> = (0..self.n_resamples)
+                                       .map(|_| {
+                                           let idxs: Vec<usize> =
+                                               (0..n).map(|_| rng.gen_range(0, n)).collect();
+                                           metric(&outputs.select(&idxs), &targets.select(&idxs))
+                                       })
+                                       .filter(|s| !s.is_nan())
+                                       .collect();
+        assert!(!scores.is_empty(), "Metric is NaN on every resample.");
+        scores.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let alpha = 1f64 - self.confidence;
+
+        ConfidenceInterval {
+            estimate: metric(outputs, targets),
+            lower: percentile(&scores, alpha / 2f64),
+            upper: percentile(&scores, 1f64 - alpha / 2f64),
+            scores: scores,
+        }
+    }
+}
+
+/// The `q` quantile of sorted values, interpolating linearly between
+/// neighbouring values.
+fn percentile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let lo = pos.floor() as usize;
+    let hi = pos.ceil() as usize;
+
+    sorted[lo] + (pos - lo as f64) * (sorted[hi] - sorted[lo])
+}
@@ -0,0 +1,207 @@
+//! Confusion matrices
+//!
+//! Counts how often each actual class is predicted as each class,
+//! and derives per-class rates such as precision and recall from the
+//! counts.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::analysis::confusion_matrix::confusion_matrix;
+//!
+//! let predicted = vec![0.0, 1.0, 1.0, 2.0, 2.0];
+//! let actual = vec![0.0, 1.0, 2.0, 2.0, 2.0];
+//!
+//! let cm = confusion_matrix(&predicted, &actual);
+//!
+//! assert_eq!(cm.labels(), &[0.0, 1.0, 2.0]);
+//!
+//! // Rows are the actual classes and columns the predicted classes.
+//! assert_eq!(*cm.counts().data(), vec![1, 0, 0,
+//!                                      0, 1, 0,
+//!                                      0, 1, 2]);
+//!
+//! assert_eq!(cm.accuracy(), 0.8);
+//! assert_eq!(cm.precisions(), vec![1.0, 0.5, 1.0]);
+//! ```
+
+use linalg::matrix::Matrix;
+
+/// A confusion matrix with its class labels.
+///
+/// The entry at `[i, j]` counts the samples of class `labels[i]`
+/// which were predicted as class `labels[j]`.
+#[derive(Clone)]
+pub struct ConfusionMatrix<T> {
+    labels: Vec<T>,
+    counts: Matrix<usize>,
+}
+
+/// Computes the confusion matrix of the predicted classes.
+///
+/// The labels are every class which appears in either the predicted
+/// or the actual classes, in increasing order.
+///
+/// # Panics
+///
+/// - The predicted and actual classes have different lengths.
+pub fn confusion_matrix<T: Copy + PartialOrd>(predicted: &[T], actual: &[T]) -> ConfusionMatrix<T> {
+    assert!(predicted.len() == actual.len(),
+            "Predicted and actual classes have different lengths.");
+
+    let mut labels: Vec<T> = Vec::new();
+    for x in predicted.iter().chain(actual.iter()) {
+        if !labels.iter().any(|l| l == x) {
+            labels.push(*x);
+        }
+    }
+    labels.sort_by(|a, b| a.partial_cmp(b).expect("Class labels must be comparable."));
+
+    ConfusionMatrix::with_labels(predicted, actual, labels)
+}
+
+impl<T: Copy + PartialOrd> ConfusionMatrix<T> {
+    /// Computes the confusion matrix over the given labels.
+    ///
+    /// This keeps classes which never occur in the matrix.
+    ///
+    /// # Panics
+    ///
+    /// - The predicted and actual classes have different lengths.
+    /// - A class is not one of the labels.
+    pub fn with_labels(predicted: &[T], actual: &[T], labels: Vec<T>) -> ConfusionMatrix<T> {
+        assert!(predicted.len() == actual.len(),
+                "Predicted and actual classes have different lengths.");
+
+        let n = labels.len();
+        let mut counts = vec![0usize; n * n];
+
+        for (p, a) in predicted.iter().zip(actual.iter()) {
+            let i = find_label(&labels, a);
+            let j = find_label(&labels, p);
+            counts[i * n + j] += 1;
+        }
+
+        ConfusionMatrix {
+            labels: labels,
+            counts: Matrix::new(n, n, counts),
+        }
+    }
+
+    /// The index of the label.
+    pub fn label_index(&self, label: &T) -> Option<usize> {
+        self.labels.iter().position(|l| l == label)
+    }
+}
+
+impl<T> ConfusionMatrix<T> {
+    /// The class labels of the rows and columns.
+    pub fn labels(&self) -> &[T] {
+        &self.labels
+    }
+
+    /// The counts of actual (rows) against predicted (columns) classes.
+    pub fn counts(&self) -> &Matrix<usize> {
+        &self.counts
+    }
+
+    /// The total number of samples.
+    pub fn total(&self) -> usize {
+        self.counts.data().iter().sum()
+    }
+
+    /// The fraction of samples which were predicted correctly.
+    pub fn accuracy(&self) -> f64 {
+        let correct: usize = (0..self.labels.len()).map(|i| self.counts[[i, i]]).sum();
+        ratio(correct, self.total())
+    }
+
+    /// The number of samples of the class at `idx` predicted correctly.
+    pub fn true_positives(&self, idx: usize) -> usize {
+        self.counts[[idx, idx]]
+    }
+
+    /// The number of samples wrongly predicted as the class at `idx`.
+    pub fn false_positives(&self, idx: usize) -> usize {
+        self.predicted_count(idx) - self.true_positives(idx)
+    }
+
+    /// The number of samples of the class at `idx` predicted as
+    /// another class.
+    pub fn false_negatives(&self, idx: usize) -> usize {
+        self.support(idx) - self.true_positives(idx)
+    }
+
+    /// The number of samples of other classes not predicted as the
+    /// class at `idx`.
+    pub fn true_negatives(&self, idx: usize) -> usize {
+        self.total() - self.support(idx) - self.false_positives(idx)
+    }
+
+    /// The number of samples of the class at `idx`.
+    pub fn support(&self, idx: usize) -> usize {
+        let n = self.labels.len();
+        self.counts.data()[idx * n..(idx + 1) * n].iter().sum()
+    }
+
+    /// The number of samples predicted as the class at `idx`.
+    pub fn predicted_count(&self, idx: usize) -> usize {
+        (0..self.labels.len()).map(|i| self.counts[[i, idx]]).sum()
+    }
+
+    /// The precision of each class.
+    ///
+    /// The fraction of samples predicted as the class which belong to
+    /// it. Classes which are never predicted have a precision of zero.
+    pub fn precisions(&self) -> Vec<f64> {
+        self.per_class(|i| ratio(self.true_positives(i), self.predicted_count(i)))
+    }
+
+    /// The recall (true positive rate) of each class.
+    ///
+    /// The fraction of samples of the class which are predicted
+    /// correctly. Classes with no samples have a recall of zero.
+    pub fn recalls(&self) -> Vec<f64> {
+        self.per_class(|i| ratio(self.true_positives(i), self.support(i)))
+    }
+
+    /// The specificity (true negative rate) of each class.
+    pub fn specificities(&self) -> Vec<f64> {
+        self.per_class(|i| {
+            let tn = self.true_negatives(i);
+            ratio(tn, tn + self.false_positives(i))
+        })
+    }
+
+    /// The false positive rate of each class.
+    pub fn false_positive_rates(&self) -> Vec<f64> {
+        self.per_class(|i| {
+            let fp = self.false_positives(i);
+            ratio(fp, fp + self.true_negatives(i))
+        })
+    }
+
+    /// The F1 score (harmonic mean of precision and recall) of each
+    /// class.
+    pub fn f1_scores(&self) -> Vec<f64> {
+        self.per_class(|i| {
+            let tp = self.true_positives(i);
+            ratio(2 * tp, self.support(i) + self.predicted_count(i))
+        })
+    }
+
+    fn per_class<F: Fn(usize) -> f64>(&self, f: F) -> Vec<f64> {
+        (0..self.labels.len()).map(f).collect()
+    }
+}
+
+fn find_label<T: PartialOrd>(labels: &[T], x: &T) -> usize {
+    match labels.iter().position(|l| l == x) {
+        Some(i) => i,
+        None => panic!("Class is not one of the labels."),
+    }
+}
+
+fn ratio(num: usize, denom: usize) -> f64 {
+    if denom == 0 { 0f64 } else { num as f64 / denom as f64 }
+}
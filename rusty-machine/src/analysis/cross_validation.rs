@@ -0,0 +1,359 @@
+//! Cross validation
+//!
+//! Provides iterators over the train and test indices of k-fold
+//! splits of a data set, and a helper for scoring a model on each fold.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::analysis::cross_validation::cross_val_score;
+//! use rusty_machine::learning::lin_reg::LinRegressor;
+//! use rusty_machine::linalg::matrix::Matrix;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! let inputs = Matrix::new(6, 1, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+//! let targets = Vector::new(vec![3.0, 5.0, 7.0, 9.0, 11.0, 13.0]);
+//!
+//! // The mean absolute error of the predictions.
+//! let mae = |outputs: &Vector<f64>, targets: &Vector<f64>| {
+//!     (outputs - targets).apply(&|x: f64| x.abs()).mean()
+//! };
+//!
+//! let scores = cross_val_score(LinRegressor::default, &inputs, &targets, 3, mae);
+//!
+//! assert_eq!(scores.len(), 3);
+//! assert!(scores.iter().all(|s| *s < 1e-8));
+//! ```
+
+use learning::SupModel;
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+use data::split::class_indices;
+#[cfg(feature = "rand")]
+use data::split::shuffled_indices;
+#[cfg(feature = "rand")]
+use learning::toolkit::rand_utils::with_task;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// K-fold cross validation.
+///
+/// Splits the samples into k folds of (almost) equal size. Each
+/// iteration yields the `(train, test)` indices with one fold used as
+/// the test set.
+pub struct KFold {
+    folds: Folds,
+}
+
+impl KFold {
+    /// Constructs k contiguous folds over the samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::analysis::cross_validation::KFold;
+    ///
+    /// let mut folds = KFold::new(5, 2);
+    ///
+    /// assert_eq!(folds.next(), Some((vec![3, 4], vec![0, 1, 2])));
+    /// assert_eq!(folds.next(), Some((vec![0, 1, 2], vec![3, 4])));
+    /// assert_eq!(folds.next(), None);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - k is less than 2 or greater than the number of samples.
+    pub fn new(n_samples: usize, k: usize) -> KFold {
+        KFold { folds: Folds::new(vec![(0..n_samples).collect()], k) }
+    }
+
+    /// Constructs k folds over the shuffled samples.
+    ///
+    /// Requires the `rand` feature.
+    ///
+    /// # Panics
+    ///
+    /// - k is less than 2 or greater than the number of samples.
+    #[cfg(feature = "rand")]
+    pub fn shuffled(n_samples: usize, k: usize, seed: u64) -> KFold {
+        KFold { folds: Folds::new(vec![shuffled_indices(n_samples, seed)], k) }
+    }
+}
+
+impl Iterator for KFold {
+    type Item = (Vec<usize>, Vec<usize>);
+
+    fn next(&mut self) -> Option<(Vec<usize>, Vec<usize>)> {
+        self.folds.next()
+    }
+}
+
+/// Stratified k-fold cross validation.
+///
+/// Splits the samples into k folds which preserve the proportion of
+/// each class in the targets.
+pub struct StratifiedKFold {
+    folds: Folds,
+}
+
+impl StratifiedKFold {
+    /// Constructs k stratified folds using the targets as class labels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::analysis::cross_validation::StratifiedKFold;
+    ///
+    /// let targets = vec![0.0, 0.0, 1.0, 1.0];
+    ///
+    /// for (train, test) in StratifiedKFold::new(&targets, 2) {
+    ///     // Each test fold holds one sample of each class.
+    ///     assert_eq!(test.len(), 2);
+    ///     assert!(targets[test[0]] != targets[test[1]]);
+    ///     assert_eq!(train.len(), 2);
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - k is less than 2 or greater than the number of samples.
+    pub fn new<U: PartialEq>(targets: &[U], k: usize) -> StratifiedKFold {
+        StratifiedKFold { folds: Folds::new(class_indices(targets), k) }
+    }
+
+    /// Constructs k stratified folds, shuffling the samples within each class.
+    ///
+    /// Requires the `rand` feature.
+    ///
+    /// # Panics
+    ///
+    /// - k is less than 2 or greater than the number of samples.
+    #[cfg(feature = "rand")]
+    pub fn shuffled<U: PartialEq>(targets: &[U], k: usize, seed: u64) -> StratifiedKFold {
+        let groups = class_indices(targets)
+                         .into_iter()
+                         .enumerate()
+                         .map(|(i, g)| {
+                             let order = shuffled_indices(g.len(), seed.wrapping_add(i as u64));
+                             order.into_iter().map(|j| g[j]).collect()
+                         })
+                         .collect();
+
+        StratifiedKFold { folds: Folds::new(groups, k) }
+    }
+}
+
+impl Iterator for StratifiedKFold {
+    type Item = (Vec<usize>, Vec<usize>);
+
+    fn next(&mut self) -> Option<(Vec<usize>, Vec<usize>)> {
+        self.folds.next()
+    }
+}
+
+/// The test indices of each fold and the current position.
+struct Folds {
+    folds: Vec<Vec<usize>>,
+    current: usize,
+}
+
+impl Folds {
+    /// Distributes each group of indices across k folds.
+    ///
+    /// The groups are dealt out in turn so that the folds have
+    /// almost equal size and each group is spread evenly.
+    fn new(groups: Vec<Vec<usize>>, k: usize) -> Folds {
+        let n: usize = groups.iter().map(|g| g.len()).sum();
+        assert!(k > 1, "Number of folds must be at least 2.");
+        assert!(k <= n, "Number of folds cannot exceed the number of samples.");
+
+        let mut folds = vec![Vec::new(); k];
+
+        if groups.len() == 1 {
+            // Contiguous folds, with the larger folds first.
+            let mut start = 0;
+            for (i, fold) in folds.iter_mut().enumerate() {
+                let size = n / k + if i < n % k { 1 } else { 0 };
+                fold.extend_from_slice(&groups[0][start..start + size]);
+                start += size;
+            }
+        } else {
+            let mut next_fold = 0;
+            for group in groups {
+                for idx in group {
+                    folds[next_fold].push(idx);
+                    next_fold = (next_fold + 1) % k;
+                }
+            }
+        }
+
+        Folds {
+            folds: folds,
+            current: 0,
+        }
+    }
+}
+
+impl Iterator for Folds {
+    type Item = (Vec<usize>, Vec<usize>);
+
+    fn next(&mut self) -> Option<(Vec<usize>, Vec<usize>)> {
+        if self.current == self.folds.len() {
+            return None;
+        }
+
+        let test = self.folds[self.current].clone();
+        let train = self.folds
+                        .iter()
+                        .enumerate()
+                        .filter(|&(i, _)| i != self.current)
+                        .flat_map(|(_, f)| f.iter().cloned())
+                        .collect();
+
+        self.current += 1;
+        Some((train, test))
+    }
+}
+
+/// Scores a model on each fold of a k-fold split.
+///
+/// For each fold a new model is created using `model_builder`, trained
+/// on the remaining folds and used to predict the targets of the fold.
+/// The `metric` is called with the predicted outputs and the true
+/// targets and the score for each fold is returned.
+///
+/// # Panics
+///
+/// - The number of targets does not match the number of input rows.
+/// - k is less than 2 or greater than the number of samples.
+pub fn cross_val_score<M, B, S>(model_builder: B,
+                                inputs: &Matrix<f64>,
+                                targets: &Vector<f64>,
+                                k: usize,
+                                metric: S)
+                                -> Vec<f64>
+    where M: SupModel<Matrix<f64>, Vector<f64>>,
+          B: Fn() -> M,
+          S: Fn(&Vector<f64>, &Vector<f64>) -> f64
+{
+    cross_val_score_folds(model_builder,
+                          inputs,
+                          targets,
+                          KFold::new(inputs.rows(), k),
+                          metric)
+}
+
+/// Scores a model on each of the given `(train, test)` index splits.
+///
+/// This allows `cross_val_score` to be used with shuffled or
+/// stratified folds.
+///
+/// The fold with index `i` is trained as the `rand_utils::with_task`
+/// task `i`, so seeded models give the same scores as
+/// `cross_val_score_parallel`.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::analysis::cross_validation::{cross_val_score_folds, StratifiedKFold};
+/// use rusty_machine::learning::logistic_reg::LogisticRegressor;
+/// use rusty_machine::linalg::matrix::Matrix;
+/// use rusty_machine::linalg::vector::Vector;
+///
+/// let inputs = Matrix::new(6, 1, vec![-3.0, -2.0, -1.0, 1.0, 2.0, 3.0]);
+/// let targets = Vector::new(vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+///
+/// let accuracy = |outputs: &Vector<f64>, targets: &Vector<f64>| {
+///     let correct = outputs.data().iter().zip(targets.data().iter())
+///                          .filter(|&(o, t)| (o.round() - t).abs() < 1e-8)
+///                          .count();
+///     correct as f64 / targets.size() as f64
+/// };
+///
+/// let folds = StratifiedKFold::shuffled(targets.data(), 3, 1);
+/// let scores = cross_val_score_folds(LogisticRegressor::default, &inputs, &targets,
+///                                    folds, accuracy);
+///
+/// assert_eq!(scores.len(), 3);
+/// ```
+///
+/// # Panics
+///
+/// - The number of targets does not match the number of input rows.
+pub fn cross_val_score_folds<M, B, I, S>(model_builder: B,
+                                         inputs: &Matrix<f64>,
+                                         targets: &Vector<f64>,
+                                         folds: I,
+                                         metric: S)
+                                         -> Vec<f64>
+    where M: SupModel<Matrix<f64>, Vector<f64>>,
+          B: Fn() -> M,
+          I: IntoIterator<Item = (Vec<usize>, Vec<usize>)>,
+          S: Fn(&Vector<f64>, &Vector<f64>) -> f64
+{
+    assert!(inputs.rows() == targets.size(),
+            "Inputs and targets have different lengths.");
+
+    folds.into_iter()
+         .enumerate()
+         .map(|(i, (train, test))| {
+             with_task(i, || {
+                 let mut model = model_builder();
+                 model.train(&inputs.select_rows(&train), &targets.select(&train));
+
+                 let outputs = model.predict(&inputs.select_rows(&test));
+                 metric(&outputs, &targets.select(&test))
+             })
+         })
+         .collect()
+}
+
+/// Scores a model on each of the given folds using the rayon thread
+/// pool.
+///
+/// Gives the same scores, in the same order, as
+/// `cross_val_score_folds`. The folds are drawn before any model is
+/// trained, so shuffled folds are reproducible for a given seed. Each
+/// fold is trained in its own `rand_utils::with_task` task, so after
+/// `rand_utils::set_seed` the models are reproducible too, whichever
+/// thread trains them.
+///
+/// Requires the `parallel` feature.
+///
+/// # Panics
+///
+/// - The number of targets does not match the number of input rows.
+#[cfg(feature = "parallel")]
+pub fn cross_val_score_parallel<M, B, S>(model_builder: B,
+                                         inputs: &Matrix<f64>,
+                                         targets: &Vector<f64>,
+                                         folds: &[(Vec<usize>, Vec<usize>)],
+                                         metric: S)
+                                         -> Vec<f64>
+    where M: SupModel<Matrix<f64>, Vector<f64>>,
+          B: Fn() -> M + Sync,
+          S: Fn(&Vector<f64>, &Vector<f64>) -> f64 + Sync
+{
+    assert!(inputs.rows() == targets.size(),
+            "Inputs and targets have different lengths.");
+
+    folds.par_iter()
+         .enumerate()
+         .map(|(i, (train, test))| {
+             with_task(i, || {
+                 let mut model = model_builder();
+                 model.train(&inputs.select_rows(train), &targets.select(train));
+
+                 let outputs = model.predict(&inputs.select_rows(test));
+                 metric(&outputs, &targets.select(test))
+             })
+         })
+         .collect()
+}
+
+// Without the `rand` feature the models draw no random numbers.
+#[cfg(not(feature = "rand"))]
+fn with_task<T, F: FnOnce() -> T>(_index: usize, f: F) -> T {
+    f()
+}
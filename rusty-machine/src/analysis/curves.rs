@@ -0,0 +1,449 @@
+//! ROC, precision-recall and calibration curves
+//!
+//! Evaluates binary classifiers which output continuous scores, such
+//! as the probabilities from logistic regression. The ROC and
+//! precision-recall curves consider every decision threshold, while
+//! the calibration curve checks whether predicted probabilities match
+//! the observed frequency of the positive class. The `tune_threshold`
+//! function picks the threshold which maximizes a chosen objective on
+//! validation scores.
+//!
+//! The targets must be `1.0` for the positive class and `0.0` for the
+//! negative class. Higher scores indicate the positive class.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::analysis::curves::{roc_curve, roc_auc_score};
+//!
+//! let scores = vec![0.1, 0.4, 0.35, 0.8];
+//! let targets = vec![0.0, 0.0, 1.0, 1.0];
+//!
+//! let roc = roc_curve(&scores, &targets);
+//!
+//! assert_eq!(roc.false_positive_rates, vec![0.0, 0.0, 0.5, 0.5, 1.0]);
+//! assert_eq!(roc.true_positive_rates, vec![0.0, 0.5, 0.5, 1.0, 1.0]);
+//!
+//! assert_eq!(roc_auc_score(&scores, &targets), 0.75);
+//! ```
+
+use std::f64;
+
+/// The points of a receiver operating characteristic curve.
+///
+/// Point `i` is the result of predicting the positive class for every
+/// score at least `thresholds[i]`. The first threshold is infinite so
+/// that the curve starts at `(0, 0)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RocCurve {
+    /// The false positive rate at each threshold.
+    pub false_positive_rates: Vec<f64>,
+    /// The true positive rate at each threshold.
+    pub true_positive_rates: Vec<f64>,
+    /// The decreasing thresholds.
+    pub thresholds: Vec<f64>,
+}
+
+/// The points of a precision-recall curve.
+///
+/// Point `i` is the result of predicting the positive class for every
+/// score at least `thresholds[i]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrecisionRecallCurve {
+    /// The precision at each threshold.
+    pub precisions: Vec<f64>,
+    /// The (increasing) recall at each threshold.
+    pub recalls: Vec<f64>,
+    /// The decreasing thresholds.
+    pub thresholds: Vec<f64>,
+}
+
+/// The points of a calibration (reliability) curve.
+///
+/// Only bins which contain at least one prediction are included. A
+/// perfectly calibrated classifier has `mean_predicted` equal to
+/// `observed_frequency` in every bin.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalibrationCurve {
+    /// The mean predicted probability in each bin.
+    pub mean_predicted: Vec<f64>,
+    /// The fraction of positive samples in each bin.
+    pub observed_frequency: Vec<f64>,
+    /// The number of samples in each bin.
+    pub counts: Vec<usize>,
+}
+
+/// The objective maximized by `tune_threshold`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ThresholdObjective {
+    /// The F1 score, the harmonic mean of precision and recall.
+    F1,
+    /// Youden's J statistic, the true positive rate minus the false
+    /// positive rate.
+    YoudenJ,
+    /// The precision, among thresholds with at least the given recall.
+    PrecisionAtRecall(f64),
+}
+
+/// A decision threshold chosen by `tune_threshold`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TunedThreshold {
+    /// The threshold, the positive class is predicted for every score
+    /// at least this value.
+    pub threshold: f64,
+    /// The value of the objective at the threshold.
+    pub score: f64,
+}
+
+/// Finds the decision threshold which maximizes the objective.
+///
+/// Every distinct score is tried as a threshold. Ties are broken by
+/// the highest threshold. The threshold can be used with classifiers
+/// which predict the positive class for scores at least the threshold,
+/// such as `NeuralNet::predict_classes`.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::analysis::curves::{tune_threshold, ThresholdObjective};
+///
+/// let scores = vec![0.1, 0.3, 0.35, 0.4, 0.8];
+/// let targets = vec![0.0, 0.0, 1.0, 1.0, 1.0];
+///
+/// let tuned = tune_threshold(&scores, &targets, ThresholdObjective::F1);
+///
+/// assert_eq!(tuned.threshold, 0.35);
+/// assert_eq!(tuned.score, 1.0);
+/// ```
+///
+/// # Panics
+///
+/// - The scores and targets have different lengths.
+/// - The targets are not all `0.0` or `1.0`.
+/// - The targets contain no positive samples.
+/// - The objective is Youden's J and the targets contain no negative
+///   samples.
+/// - The objective is precision at a recall which is not in [0, 1].
+pub fn tune_threshold(scores: &[f64],
+                      targets: &[f64],
+                      objective: ThresholdObjective)
+                      -> TunedThreshold {
+    let counts = threshold_counts(scores, targets);
+    let (pos, neg) = class_totals(&counts);
+    assert!(pos > 0, "Targets must contain a positive sample.");
+
+    if let ThresholdObjective::YoudenJ = objective {
+        assert!(neg > 0, "Targets must contain both classes.");
+    }
+    if let ThresholdObjective::PrecisionAtRecall(r) = objective {
+        assert!((0f64..=1f64).contains(&r), "Recall must be in the interval [0, 1].");
+    }
+
+    let mut best: Option<TunedThreshold> = None;
+
+    for &(threshold, tp, fp) in &counts {
+        let (tp, fp) = (tp as f64, fp as f64);
+        let recall = tp / pos as f64;
+
+        let score = match objective {
+            ThresholdObjective::F1 => 2f64 * tp / (tp + fp + pos as f64),
+            ThresholdObjective::YoudenJ => recall - fp / neg as f64,
+            ThresholdObjective::PrecisionAtRecall(r) => {
+                if recall < r {
+                    continue;
+                }
+                tp / (tp + fp)
+            }
+        };
+
+        let better = match best {
+            Some(b) => score > b.score,
+            None => true,
+        };
+
+        if better {
+            best = Some(TunedThreshold {
+                threshold: threshold,
+                score: score,
+            });
+        }
+    }
+
+    // The lowest threshold has a recall of one, so a threshold is
+    // always found.
+    best.unwrap()
+}
+
+/// Computes the ROC curve of the scores.
+///
+/// # Panics
+///
+/// - The scores and targets have different lengths.
+/// - The targets are not all `0.0` or `1.0`.
+/// - The targets do not contain both classes.
+pub fn roc_curve(scores: &[f64], targets: &[f64]) -> RocCurve {
+    let counts = threshold_counts(scores, targets);
+    let (pos, neg) = class_totals(&counts);
+    assert!(pos > 0 && neg > 0, "Targets must contain both classes.");
+
+    let mut curve = RocCurve {
+        false_positive_rates: vec![0f64],
+        true_positive_rates: vec![0f64],
+        thresholds: vec![f64::INFINITY],
+    };
+
+    for &(threshold, tp, fp) in &counts {
+        curve.false_positive_rates.push(fp as f64 / neg as f64);
+        curve.true_positive_rates.push(tp as f64 / pos as f64);
+        curve.thresholds.push(threshold);
+    }
+
+    curve
+}
+
+/// Computes the area under the ROC curve of the scores.
+///
+/// This is the probability that a random positive sample is scored
+/// higher than a random negative sample.
+///
+/// # Panics
+///
+/// - The scores and targets have different lengths.
+/// - The targets are not all `0.0` or `1.0`.
+/// - The targets do not contain both classes.
+pub fn roc_auc_score(scores: &[f64], targets: &[f64]) -> f64 {
+    let roc = roc_curve(scores, targets);
+    auc(&roc.false_positive_rates, &roc.true_positive_rates)
+}
+
+/// Computes the precision-recall curve of the scores.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::analysis::curves::precision_recall_curve;
+///
+/// let scores = vec![0.1, 0.4, 0.35, 0.8];
+/// let targets = vec![0.0, 0.0, 1.0, 1.0];
+///
+/// let pr = precision_recall_curve(&scores, &targets);
+///
+/// assert_eq!(pr.thresholds, vec![0.8, 0.4, 0.35, 0.1]);
+/// assert_eq!(pr.recalls, vec![0.5, 0.5, 1.0, 1.0]);
+/// assert_eq!(pr.precisions, vec![1.0, 0.5, 2.0 / 3.0, 0.5]);
+/// ```
+///
+/// # Panics
+///
+/// - The scores and targets have different lengths.
+/// - The targets are not all `0.0` or `1.0`.
+/// - The targets contain no positive samples.
+pub fn precision_recall_curve(scores: &[f64], targets: &[f64]) -> PrecisionRecallCurve {
+    let counts = threshold_counts(scores, targets);
+    let (pos, _) = class_totals(&counts);
+    assert!(pos > 0, "Targets must contain a positive sample.");
+
+    let mut curve = PrecisionRecallCurve {
+        precisions: Vec::with_capacity(counts.len()),
+        recalls: Vec::with_capacity(counts.len()),
+        thresholds: Vec::with_capacity(counts.len()),
+    };
+
+    for &(threshold, tp, fp) in &counts {
+        curve.precisions.push(tp as f64 / (tp + fp) as f64);
+        curve.recalls.push(tp as f64 / pos as f64);
+        curve.thresholds.push(threshold);
+    }
+
+    curve
+}
+
+/// Computes the average precision of the scores.
+///
+/// The precision at each threshold is weighted by the increase in
+/// recall from the previous threshold. This summarizes the
+/// precision-recall curve without interpolation.
+///
+/// # Panics
+///
+/// - The scores and targets have different lengths.
+/// - The targets are not all `0.0` or `1.0`.
+/// - The targets contain no positive samples.
+pub fn average_precision(scores: &[f64], targets: &[f64]) -> f64 {
+    let pr = precision_recall_curve(scores, targets);
+
+    let mut last_recall = 0f64;
+    let mut total = 0f64;
+    for (p, r) in pr.precisions.iter().zip(pr.recalls.iter()) {
+        total += (r - last_recall) * p;
+        last_recall = *r;
+    }
+
+    total
+}
+
+/// Computes the calibration curve of predicted probabilities.
+///
+/// The interval [0, 1] is split into `n_bins` bins of equal width.
+/// The probabilities in each bin are averaged and compared with the
+/// fraction of positive targets in the bin.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::analysis::curves::calibration_curve;
+///
+/// let probs = vec![0.1, 0.2, 0.3, 0.7, 0.9, 0.9];
+/// let targets = vec![0.0, 0.0, 1.0, 1.0, 1.0, 0.0];
+///
+/// let curve = calibration_curve(&probs, &targets, 2);
+///
+/// assert_eq!(curve.counts, vec![3, 3]);
+/// assert!((curve.mean_predicted[0] - 0.2).abs() < 1e-12);
+/// assert!((curve.observed_frequency[1] - 2.0 / 3.0).abs() < 1e-12);
+/// ```
+///
+/// # Panics
+///
+/// - The probabilities and targets have different lengths.
+/// - The targets are not all `0.0` or `1.0`.
+/// - A probability is not in the interval [0, 1].
+/// - n_bins is zero.
+pub fn calibration_curve(probabilities: &[f64], targets: &[f64], n_bins: usize) -> CalibrationCurve {
+    check_probabilities(probabilities, targets);
+    assert!(n_bins > 0, "Must use at least one bin.");
+
+    let mut sums = vec![0f64; n_bins];
+    let mut positives = vec![0usize; n_bins];
+    let mut counts = vec![0usize; n_bins];
+
+    for (&p, &t) in probabilities.iter().zip(targets.iter()) {
+        // The last bin includes a probability of one.
+        let bin = ((p * n_bins as f64) as usize).min(n_bins - 1);
+        sums[bin] += p;
+        counts[bin] += 1;
+        if t == 1f64 {
+            positives[bin] += 1;
+        }
+    }
+
+    let mut curve = CalibrationCurve {
+        mean_predicted: Vec::new(),
+        observed_frequency: Vec::new(),
+        counts: Vec::new(),
+    };
+
+    for b in (0..n_bins).filter(|&b| counts[b] > 0) {
+        curve.mean_predicted.push(sums[b] / counts[b] as f64);
+        curve.observed_frequency.push(positives[b] as f64 / counts[b] as f64);
+        curve.counts.push(counts[b]);
+    }
+
+    curve
+}
+
+/// Computes the Brier score of predicted probabilities.
+///
+/// This is the mean squared difference between the probabilities and
+/// the targets. Lower is better, and a perfect classifier scores zero.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::analysis::curves::brier_score;
+///
+/// assert_eq!(brier_score(&[0.5, 1.0], &[1.0, 1.0]), 0.125);
+/// ```
+///
+/// # Panics
+///
+/// - The probabilities and targets have different lengths.
+/// - The targets are not all `0.0` or `1.0`.
+/// - A probability is not in the interval [0, 1].
+/// - There are no samples.
+pub fn brier_score(probabilities: &[f64], targets: &[f64]) -> f64 {
+    check_probabilities(probabilities, targets);
+    assert!(!targets.is_empty(), "Cannot score empty probabilities.");
+
+    let total: f64 = probabilities.iter()
+                                  .zip(targets.iter())
+                                  .map(|(p, t)| (p - t) * (p - t))
+                                  .sum();
+    total / targets.len() as f64
+}
+
+/// Computes the area under a curve using the trapezoid rule.
+///
+/// The x values must be either increasing or decreasing.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::analysis::curves::auc;
+///
+/// assert_eq!(auc(&[0.0, 1.0, 2.0], &[0.0, 1.0, 1.0]), 1.5);
+/// ```
+///
+/// # Panics
+///
+/// - The x and y values have different lengths.
+/// - The x values are not monotonic.
+pub fn auc(x: &[f64], y: &[f64]) -> f64 {
+    assert!(x.len() == y.len(), "x and y have different lengths.");
+
+    let increasing = x.windows(2).all(|w| w[0] <= w[1]);
+    let decreasing = x.windows(2).all(|w| w[0] >= w[1]);
+    assert!(increasing || decreasing, "x values must be monotonic.");
+
+    let area: f64 = x.windows(2)
+                     .zip(y.windows(2))
+                     .map(|(xs, ys)| (xs[1] - xs[0]) * (ys[0] + ys[1]) / 2f64)
+                     .sum();
+
+    if increasing { area } else { -area }
+}
+
+fn check_targets(scores: &[f64], targets: &[f64]) {
+    assert!(scores.len() == targets.len(),
+            "Scores and targets have different lengths.");
+    assert!(targets.iter().all(|&t| t == 0f64 || t == 1f64),
+            "Targets must be 0 or 1.");
+}
+
+fn check_probabilities(probabilities: &[f64], targets: &[f64]) {
+    check_targets(probabilities, targets);
+    assert!(probabilities.iter().all(|p| (0f64..=1f64).contains(p)),
+            "Probabilities must be in the interval [0, 1].");
+}
+
+/// The cumulative true and false positives at each distinct score,
+/// in decreasing order of score.
+fn threshold_counts(scores: &[f64], targets: &[f64]) -> Vec<(f64, usize, usize)> {
+    check_targets(scores, targets);
+
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    order.sort_by(|&a, &b| scores[b].partial_cmp(&scores[a]).expect("Scores must not be NaN."));
+
+    let mut counts: Vec<(f64, usize, usize)> = Vec::new();
+    let (mut tp, mut fp) = (0, 0);
+
+    for (k, &i) in order.iter().enumerate() {
+        if targets[i] == 1f64 {
+            tp += 1;
+        } else {
+            fp += 1;
+        }
+
+        // Only record a point once every sample with this score is counted.
+        let last = k + 1 == order.len() || scores[order[k + 1]] != scores[i];
+        if last {
+            counts.push((scores[i], tp, fp));
+        }
+    }
+
+    counts
+}
+
+fn class_totals(counts: &[(f64, usize, usize)]) -> (usize, usize) {
+    counts.last().map(|&(_, tp, fp)| (tp, fp)).unwrap_or((0, 0))
+}
@@ -0,0 +1,261 @@
+//! Learning and validation curves
+//!
+//! Diagnoses overfitting and underfitting by comparing the scores of a
+//! model on its training data and on held out data.
+//!
+//! - `learning_curve` trains on increasing fractions of the training
+//!   folds. Test scores which keep improving suggest more data would
+//!   help, while a persistent gap between the train and test scores
+//!   suggests overfitting.
+//! - `validation_curve` sweeps the values of a single hyperparameter.
+//!
+//! Both use the same `(train, test)` folds and metric as the cross
+//! validation functions, and return the score on every fold.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::analysis::cross_validation::KFold;
+//! use rusty_machine::analysis::learning_curve::learning_curve;
+//! use rusty_machine::learning::lin_reg::LinRegressor;
+//! use rusty_machine::linalg::matrix::Matrix;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! let inputs = Matrix::new(8, 1, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]);
+//! let targets = Vector::new(vec![0.1, 2.0, 4.1, 5.9, 8.0, 10.1, 12.0, 13.9]);
+//!
+//! // The negative mean squared error.
+//! let neg_mse = |outputs: &Vector<f64>, targets: &Vector<f64>| {
+//!     let diff = outputs - targets;
+//!     -diff.dot(&diff) / diff.size() as f64
+//! };
+//!
+//! let folds: Vec<_> = KFold::shuffled(inputs.rows(), 4, 1).collect();
+//! let curve = learning_curve(LinRegressor::default, &inputs, &targets,
+//!                            &folds, &[0.5, 1.0], neg_mse);
+//!
+//! assert_eq!(curve.train_sizes, vec![3, 6]);
+//! assert_eq!(curve.test_scores[0].len(), 4);
+//! ```
+
+use learning::SupModel;
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+
+/// The scores of a model trained on increasing amounts of data.
+#[derive(Clone, Debug)]
+pub struct LearningCurve {
+    /// The number of training samples used for each point.
+    ///
+    /// This is the size within the first fold, later folds may differ
+    /// by one sample.
+    pub train_sizes: Vec<usize>,
+    /// The score on the training samples, for each size and fold.
+    pub train_scores: Vec<Vec<f64>>,
+    /// The score on the test fold, for each size and fold.
+    pub test_scores: Vec<Vec<f64>>,
+}
+
+impl LearningCurve {
+    /// The mean training score over the folds for each size.
+    pub fn mean_train_scores(&self) -> Vec<f64> {
+        fold_means(&self.train_scores)
+    }
+
+    /// The mean test score over the folds for each size.
+    pub fn mean_test_scores(&self) -> Vec<f64> {
+        fold_means(&self.test_scores)
+    }
+}
+
+/// The scores of a model across values of a hyperparameter.
+#[derive(Clone, Debug)]
+pub struct ValidationCurve {
+    /// The hyperparameter values.
+    pub values: Vec<f64>,
+    /// The score on the training folds, for each value and fold.
+    pub train_scores: Vec<Vec<f64>>,
+    /// The score on the test fold, for each value and fold.
+    pub test_scores: Vec<Vec<f64>>,
+}
+
+impl ValidationCurve {
+    /// The mean training score over the folds for each value.
+    pub fn mean_train_scores(&self) -> Vec<f64> {
+        fold_means(&self.train_scores)
+    }
+
+    /// The mean test score over the folds for each value.
+    pub fn mean_test_scores(&self) -> Vec<f64> {
+        fold_means(&self.test_scores)
+    }
+}
+
+/// Scores a model trained on fractions of each training fold.
+///
+/// For every fraction and fold a new model is created using
+/// `model_builder` and trained on the first samples of the training
+/// fold. It is then scored on those samples and on the test fold.
+///
+/// The training samples are taken in the order of the fold, so the
+/// folds should be shuffled (for example with `KFold::shuffled`) when
+/// the data is ordered.
+///
+/// # Panics
+///
+/// - The number of targets does not match the number of input rows.
+/// - No folds are given.
+/// - A fraction is not in the interval (0, 1].
+pub fn learning_curve<M, B, S>(model_builder: B,
+                               inputs: &Matrix<f64>,
+                               targets: &Vector<f64>,
+                               folds: &[(Vec<usize>, Vec<usize>)],
+                               fractions: &[f64],
+                               metric: S)
+                               -> LearningCurve
+    where M: SupModel<Matrix<f64>, Vector<f64>>,
+          B: Fn() -> M,
+          S: Fn(&Vector<f64>, &Vector<f64>) -> f64
+{
+    assert!(inputs.rows() == targets.size(),
+            "Inputs and targets have different lengths.");
+    assert!(!folds.is_empty(), "Must use at least one fold.");
+    assert!(fractions.iter().all(|&f| f > 0f64 && f <= 1f64),
+            "Fractions must be in the interval (0, 1].");
+
+    let mut curve = LearningCurve {
+        train_sizes: Vec::with_capacity(fractions.len()),
+        train_scores: Vec::with_capacity(fractions.len()),
+        test_scores: Vec::with_capacity(fractions.len()),
+    };
+
+    for &f in fractions {
+        let (train_scores, test_scores) = folds.iter()
+                                               .map(|(train, test)| {
+                                                   let n = subset_size(train.len(), f);
+                                                   let mut model = model_builder();
+                                                   score_train_test(&mut model,
+                                                                    inputs,
+                                                                    targets,
+                                                                    &train[..n],
+                                                                    test,
+                                                                    &metric)
+                                               })
+                                               .unzip();
+
+        curve.train_sizes.push(subset_size(folds[0].0.len(), f));
+        curve.train_scores.push(train_scores);
+        curve.test_scores.push(test_scores);
+    }
+
+    curve
+}
+
+/// Scores a model for each value of a hyperparameter.
+///
+/// For every value and fold a new model is created by passing the
+/// value to `model_builder`. It is trained on the training fold and
+/// scored on both the training and the test fold.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::analysis::cross_validation::KFold;
+/// use rusty_machine::analysis::learning_curve::validation_curve;
+/// use rusty_machine::learning::gp::{GaussianProcess, ConstMean};
+/// use rusty_machine::learning::toolkit::kernel::SquaredExp;
+/// use rusty_machine::linalg::matrix::Matrix;
+/// use rusty_machine::linalg::vector::Vector;
+///
+/// let inputs = Matrix::new(6, 1, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+/// let targets = Vector::new(vec![0.0, 0.8, 0.9, 0.1, -0.8, -1.0]);
+///
+/// let builder = |ls: f64| GaussianProcess::new(SquaredExp::new(ls, 1.0), ConstMean::default(), 0.1);
+///
+/// let neg_mse = |outputs: &Vector<f64>, targets: &Vector<f64>| {
+///     let diff = outputs - targets;
+///     -diff.dot(&diff) / diff.size() as f64
+/// };
+///
+/// let folds: Vec<_> = KFold::new(inputs.rows(), 3).collect();
+/// let curve = validation_curve(builder, &inputs, &targets, &folds, &[0.5, 1.0, 2.0], neg_mse);
+///
+/// assert_eq!(curve.mean_test_scores().len(), 3);
+/// ```
+///
+/// # Panics
+///
+/// - The number of targets does not match the number of input rows.
+/// - No folds are given.
+pub fn validation_curve<M, B, S>(model_builder: B,
+                                 inputs: &Matrix<f64>,
+                                 targets: &Vector<f64>,
+                                 folds: &[(Vec<usize>, Vec<usize>)],
+                                 values: &[f64],
+                                 metric: S)
+                                 -> ValidationCurve
+    where M: SupModel<Matrix<f64>, Vector<f64>>,
+          B: Fn(f64) -> M,
+          S: Fn(&Vector<f64>, &Vector<f64>) -> f64
+{
+    assert!(inputs.rows() == targets.size(),
+            "Inputs and targets have different lengths.");
+    assert!(!folds.is_empty(), "Must use at least one fold.");
+
+    let mut curve = ValidationCurve {
+        values: values.to_vec(),
+        train_scores: Vec::with_capacity(values.len()),
+        test_scores: Vec::with_capacity(values.len()),
+    };
+
+    for &v in values {
+        let (train_scores, test_scores) = folds.iter()
+                                               .map(|(train, test)| {
+                                                   let mut model = model_builder(v);
+                                                   score_train_test(&mut model,
+                                                                    inputs,
+                                                                    targets,
+                                                                    train,
+                                                                    test,
+                                                                    &metric)
+                                               })
+                                               .unzip();
+
+        curve.train_scores.push(train_scores);
+        curve.test_scores.push(test_scores);
+    }
+
+    curve
+}
+
+/// Trains the model on the train indices and returns its train and
+/// test scores.
+fn score_train_test<M, S>(model: &mut M,
+                          inputs: &Matrix<f64>,
+                          targets: &Vector<f64>,
+                          train: &[usize],
+                          test: &[usize],
+                          metric: &S)
+                          -> (f64, f64)
+    where M: SupModel<Matrix<f64>, Vector<f64>>,
+          S: Fn(&Vector<f64>, &Vector<f64>) -> f64
+{
+    let train_inputs = inputs.select_rows(train);
+    let train_targets = targets.select(train);
+    model.train(&train_inputs, &train_targets);
+
+    let train_score = metric(&model.predict(&train_inputs), &train_targets);
+    let test_score = metric(&model.predict(&inputs.select_rows(test)),
+                            &targets.select(test));
+
+    (train_score, test_score)
+}
+
+/// The number of samples in the fraction of `n`, at least one.
+fn subset_size(n: usize, fraction: f64) -> usize {
+    ((n as f64 * fraction).ceil() as usize).max(1).min(n)
+}
+
+fn fold_means(scores: &[Vec<f64>]) -> Vec<f64> {
+    scores.iter().map(|s| s.iter().sum::<f64>() / s.len() as f64).collect()
+}
@@ -0,0 +1,809 @@
+//! Hyperparameter search
+//!
+//! Provides grid search, random search and Bayesian optimization over
+//! named model parameters. Every candidate set of parameters is scored
+//! by cross validation on the same folds, and the candidate with the
+//! highest mean score is reported as the best.
+//!
+//! Metrics for which lower is better (such as the mean squared error)
+//! should be negated.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::analysis::cross_validation::KFold;
+//! use rusty_machine::analysis::model_selection::{GridSearch, ParamGrid, Params};
+//! use rusty_machine::learning::gp::{GaussianProcess, ConstMean};
+//! use rusty_machine::learning::toolkit::kernel::SquaredExp;
+//! use rusty_machine::linalg::matrix::Matrix;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! let inputs = Matrix::new(6, 1, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+//! let targets = Vector::new(vec![0.0, 0.8, 0.9, 0.1, -0.8, -1.0]);
+//!
+//! let grid = ParamGrid::new().add("ls", vec![0.5, 1.0, 2.0])
+//!                            .add("noise", vec![0.01, 0.1]);
+//!
+//! let builder = |p: &Params| {
+//!     let ker = SquaredExp::new(p.get("ls"), 1.0);
+//!     GaussianProcess::new(ker, ConstMean::default(), p.get("noise"))
+//! };
+//!
+//! // The negative mean squared error.
+//! let neg_mse = |outputs: &Vector<f64>, targets: &Vector<f64>| {
+//!     let diff = outputs - targets;
+//!     -diff.dot(&diff) / diff.size() as f64
+//! };
+//!
+//! let folds: Vec<_> = KFold::new(inputs.rows(), 3).collect();
+//! let report = GridSearch::new(grid).fit(builder, &inputs, &targets, &folds, neg_mse);
+//!
+//! assert_eq!(report.results().len(), 6);
+//! println!("Best parameters: {:?}", report.best().params);
+//! ```
+//!
+//! The `fit_parallel` methods score the candidates on multiple
+//! threads. With the `parallel` feature they use the rayon thread
+//! pool, otherwise the candidates are split between one thread per
+//! core. Random candidates are sampled before scoring, so the results
+//! are the same as `fit` in either case.
+//!
+//! The `BayesSearch` chooses each candidate from the scores of the
+//! previous ones, and usually needs far fewer candidates than a random
+//! search to find good parameters. As each candidate depends on the
+//! last, it is only run on a single thread.
+
+#[cfg(feature = "rand")]
+use std::f64::consts::{PI, SQRT_2};
+#[cfg(not(feature = "parallel"))]
+use std::thread;
+
+use analysis::cross_validation::cross_val_score_folds;
+use learning::SupModel;
+#[cfg(feature = "rand")]
+use learning::gp::{ConstMean, GaussianProcess};
+#[cfg(feature = "rand")]
+use learning::optim::fmincg::ConjugateGD;
+#[cfg(feature = "rand")]
+use learning::toolkit::kernel::SquaredExp;
+#[cfg(feature = "rand")]
+use learning::toolkit::rand_utils::seeded_rng;
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+
+#[cfg(feature = "rand")]
+use rand::Rng;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// A set of named parameter values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Params {
+    values: Vec<(String, f64)>,
+}
+
+impl Params {
+    /// Returns the value of the named parameter.
+    ///
+    /// # Panics
+    ///
+    /// - The parameter does not exist.
+    pub fn get(&self, name: &str) -> f64 {
+        match self.values.iter().find(|&(n, _)| n == name) {
+            Some(&(_, v)) => v,
+            None => panic!("Parameter {} does not exist.", name),
+        }
+    }
+
+    /// The names and values of the parameters.
+    pub fn values(&self) -> &[(String, f64)] {
+        &self.values
+    }
+}
+
+/// A grid of parameter values.
+///
+/// The candidates are every combination of the given values.
+pub struct ParamGrid {
+    params: Vec<(String, Vec<f64>)>,
+}
+
+impl Default for ParamGrid {
+    fn default() -> ParamGrid {
+        ParamGrid::new()
+    }
+}
+
+impl ParamGrid {
+    /// Constructs an empty parameter grid.
+    pub fn new() -> ParamGrid {
+        ParamGrid { params: Vec::new() }
+    }
+
+    /// Adds a parameter with the values to search over.
+    ///
+    /// # Panics
+    ///
+    /// - No values are given.
+    pub fn add(mut self, name: &str, values: Vec<f64>) -> ParamGrid {
+        assert!(!values.is_empty(), "Parameter must have at least one value.");
+        self.params.push((name.to_string(), values));
+        self
+    }
+
+    /// Returns every combination of the parameter values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::analysis::model_selection::ParamGrid;
+    ///
+    /// let grid = ParamGrid::new().add("a", vec![1.0, 2.0])
+    ///                            .add("b", vec![3.0, 4.0, 5.0]);
+    ///
+    /// let candidates = grid.candidates();
+    ///
+    /// assert_eq!(candidates.len(), 6);
+    /// assert_eq!(candidates[1].get("a"), 1.0);
+    /// assert_eq!(candidates[1].get("b"), 4.0);
+    /// ```
+    pub fn candidates(&self) -> Vec<Params> {
+        let mut candidates = vec![Params { values: Vec::new() }];
+
+        for (name, values) in &self.params {
+            candidates = candidates.into_iter()
+                                   .flat_map(|c| {
+                                       values.iter().map(move |v| {
+                                           let mut c = c.clone();
+                                           c.values.push((name.clone(), *v));
+                                           c
+                                       })
+                                   })
+                                   .collect();
+        }
+
+        candidates
+    }
+}
+
+/// A distribution to sample parameter values from.
+#[derive(Clone, Debug)]
+pub enum ParamDist {
+    /// Uniform on the interval [low, high).
+    Uniform(f64, f64),
+    /// Log-uniform on the interval [low, high).
+    ///
+    /// Suited to scale parameters such as learning rates.
+    LogUniform(f64, f64),
+    /// A uniform choice from the values.
+    Choice(Vec<f64>),
+}
+
+#[cfg(feature = "rand")]
+impl ParamDist {
+    fn sample<R: Rng>(&self, rng: &mut R) -> f64 {
+        match *self {
+            ParamDist::Uniform(low, high) => low + (high - low) * rng.gen::<f64>(),
+            ParamDist::LogUniform(low, high) => {
+                (low.ln() + (high.ln() - low.ln()) * rng.gen::<f64>()).exp()
+            }
+            ParamDist::Choice(ref values) => values[rng.gen_range(0, values.len())],
+        }
+    }
+
+    /// Maps a value onto the unit interval, on a log scale for the
+    /// log-uniform distribution.
+    fn unit(&self, value: f64) -> f64 {
+        match *self {
+            ParamDist::Uniform(low, high) => (value - low) / (high - low),
+            ParamDist::LogUniform(low, high) => {
+                (value.ln() - low.ln()) / (high.ln() - low.ln())
+            }
+            ParamDist::Choice(ref values) => {
+                let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+                let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                if max > min { (value - min) / (max - min) } else { 0f64 }
+            }
+        }
+    }
+}
+
+/// A space of parameter distributions.
+pub struct ParamSpace {
+    params: Vec<(String, ParamDist)>,
+}
+
+impl Default for ParamSpace {
+    fn default() -> ParamSpace {
+        ParamSpace::new()
+    }
+}
+
+impl ParamSpace {
+    /// Constructs an empty parameter space.
+    pub fn new() -> ParamSpace {
+        ParamSpace { params: Vec::new() }
+    }
+
+    /// Adds a parameter with the distribution to sample it from.
+    ///
+    /// # Panics
+    ///
+    /// - The distribution is empty or its bounds are invalid.
+    pub fn add(mut self, name: &str, dist: ParamDist) -> ParamSpace {
+        match dist {
+            ParamDist::Uniform(low, high) => {
+                assert!(low < high, "Lower bound must be less than upper bound.")
+            }
+            ParamDist::LogUniform(low, high) => {
+                assert!(low > 0f64 && low < high,
+                        "Log-uniform bounds must be positive and increasing.")
+            }
+            ParamDist::Choice(ref values) => {
+                assert!(!values.is_empty(), "Choice must have at least one value.")
+            }
+        }
+        self.params.push((name.to_string(), dist));
+        self
+    }
+
+    /// Samples a set of parameters.
+    ///
+    /// Requires the `rand` feature.
+    #[cfg(feature = "rand")]
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Params {
+        Params {
+            values: self.params
+                        .iter()
+                        .map(|(name, dist)| (name.clone(), dist.sample(rng)))
+                        .collect(),
+        }
+    }
+
+    /// The coordinates of a set of parameters in the unit cube.
+    #[cfg(feature = "rand")]
+    fn unit(&self, params: &Params) -> Vec<f64> {
+        self.params
+            .iter()
+            .zip(params.values())
+            .map(|((_, dist), (_, v))| dist.unit(*v))
+            .collect()
+    }
+}
+
+/// The cross validation scores of a single candidate.
+#[derive(Clone, Debug)]
+pub struct SearchResult {
+    /// The candidate parameters.
+    pub params: Params,
+    /// The score on each fold.
+    pub fold_scores: Vec<f64>,
+    /// The mean score over the folds.
+    pub mean_score: f64,
+}
+
+/// The results of a hyperparameter search.
+#[derive(Clone, Debug)]
+pub struct SearchReport {
+    results: Vec<SearchResult>,
+    best: usize,
+}
+
+impl SearchReport {
+    fn new(candidates: Vec<Params>, scores: Vec<Vec<f64>>) -> SearchReport {
+        assert!(!candidates.is_empty(), "No candidates to search.");
+
+        let mut results = Vec::with_capacity(candidates.len());
+
+        for (params, fold_scores) in candidates.into_iter().zip(scores) {
+            let mean = fold_scores.iter().sum::<f64>() / fold_scores.len() as f64;
+            results.push(SearchResult {
+                params: params,
+                fold_scores: fold_scores,
+                mean_score: mean,
+            });
+        }
+
+        let mut best = 0;
+        for (i, r) in results.iter().enumerate() {
+            if r.mean_score > results[best].mean_score {
+                best = i;
+            }
+        }
+
+        SearchReport {
+            results: results,
+            best: best,
+        }
+    }
+
+    /// The results for every candidate, in the order they were searched.
+    pub fn results(&self) -> &[SearchResult] {
+        &self.results
+    }
+
+    /// The result with the highest mean score.
+    pub fn best(&self) -> &SearchResult {
+        &self.results[self.best]
+    }
+}
+
+/// Exhaustive search over a parameter grid.
+pub struct GridSearch {
+    grid: ParamGrid,
+}
+
+impl GridSearch {
+    /// Constructs a grid search over the parameter grid.
+    pub fn new(grid: ParamGrid) -> GridSearch {
+        GridSearch { grid: grid }
+    }
+
+    /// Scores every candidate in the grid.
+    ///
+    /// The `model_builder` constructs an untrained model from a
+    /// candidate set of parameters. Each model is trained and scored
+    /// on the `(train, test)` folds using the metric.
+    ///
+    /// # Panics
+    ///
+    /// - The grid is empty.
+    /// - The number of targets does not match the number of input rows.
+    pub fn fit<M, B, S>(&self,
+                        model_builder: B,
+                        inputs: &Matrix<f64>,
+                        targets: &Vector<f64>,
+                        folds: &[(Vec<usize>, Vec<usize>)],
+                        metric: S)
+                        -> SearchReport
+        where M: SupModel<Matrix<f64>, Vector<f64>>,
+              B: Fn(&Params) -> M,
+              S: Fn(&Vector<f64>, &Vector<f64>) -> f64
+    {
+        let candidates = self.grid.candidates();
+        let scores = candidates.iter()
+                               .map(|p| score_candidate(&model_builder, p, inputs, targets, folds, &metric))
+                               .collect();
+
+        SearchReport::new(candidates, scores)
+    }
+
+    /// Scores every candidate in the grid using multiple threads.
+    ///
+    /// The candidates are shared between the available cores, or run
+    /// on the rayon thread pool with the `parallel` feature.
+    ///
+    /// # Panics
+    ///
+    /// - The grid is empty.
+    /// - The number of targets does not match the number of input rows.
+    pub fn fit_parallel<M, B, S>(&self,
+                                 model_builder: B,
+                                 inputs: &Matrix<f64>,
+                                 targets: &Vector<f64>,
+                                 folds: &[(Vec<usize>, Vec<usize>)],
+                                 metric: S)
+                                 -> SearchReport
+        where M: SupModel<Matrix<f64>, Vector<f64>>,
+              B: Fn(&Params) -> M + Sync,
+              S: Fn(&Vector<f64>, &Vector<f64>) -> f64 + Sync
+    {
+        let candidates = self.grid.candidates();
+        let scores = score_parallel(&candidates, &model_builder, inputs, targets, folds, &metric);
+
+        SearchReport::new(candidates, scores)
+    }
+}
+
+/// Search over parameters sampled at random.
+///
+/// Requires the `rand` feature.
+#[cfg(feature = "rand")]
+pub struct RandomSearch {
+    space: ParamSpace,
+    iters: usize,
+    seed: u64,
+}
+
+#[cfg(feature = "rand")]
+impl RandomSearch {
+    /// Constructs a random search drawing `iters` candidates from the
+    /// parameter space.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::analysis::model_selection::{RandomSearch, ParamSpace, ParamDist};
+    ///
+    /// let space = ParamSpace::new().add("alpha", ParamDist::LogUniform(1e-4, 1.0))
+    ///                              .add("iters", ParamDist::Choice(vec![50.0, 100.0]));
+    ///
+    /// let search = RandomSearch::new(space, 20, 1);
+    /// ```
+    pub fn new(space: ParamSpace, iters: usize, seed: u64) -> RandomSearch {
+        RandomSearch {
+            space: space,
+            iters: iters,
+            seed: seed,
+        }
+    }
+
+    /// Returns the sampled candidates.
+    ///
+    /// The same candidates are returned for the same seed.
+    pub fn candidates(&self) -> Vec<Params> {
+        let mut rng = seeded_rng(self.seed);
+
+        (0..self.iters).map(|_| self.space.sample(&mut rng)).collect()
+    }
+
+    /// Scores every sampled candidate.
+    ///
+    /// See `GridSearch::fit` for details.
+    ///
+    /// # Panics
+    ///
+    /// - The number of iterations is zero.
+    /// - The number of targets does not match the number of input rows.
+    pub fn fit<M, B, S>(&self,
+                        model_builder: B,
+                        inputs: &Matrix<f64>,
+                        targets: &Vector<f64>,
+                        folds: &[(Vec<usize>, Vec<usize>)],
+                        metric: S)
+                        -> SearchReport
+        where M: SupModel<Matrix<f64>, Vector<f64>>,
+              B: Fn(&Params) -> M,
+              S: Fn(&Vector<f64>, &Vector<f64>) -> f64
+    {
+        let candidates = self.candidates();
+        let scores = candidates.iter()
+                               .map(|p| score_candidate(&model_builder, p, inputs, targets, folds, &metric))
+                               .collect();
+
+        SearchReport::new(candidates, scores)
+    }
+
+    /// Scores every sampled candidate using multiple threads.
+    ///
+    /// # Panics
+    ///
+    /// - The number of iterations is zero.
+    /// - The number of targets does not match the number of input rows.
+    pub fn fit_parallel<M, B, S>(&self,
+                                 model_builder: B,
+                                 inputs: &Matrix<f64>,
+                                 targets: &Vector<f64>,
+                                 folds: &[(Vec<usize>, Vec<usize>)],
+                                 metric: S)
+                                 -> SearchReport
+        where M: SupModel<Matrix<f64>, Vector<f64>>,
+              B: Fn(&Params) -> M + Sync,
+              S: Fn(&Vector<f64>, &Vector<f64>) -> f64 + Sync
+    {
+        let candidates = self.candidates();
+        let scores = score_parallel(&candidates, &model_builder, inputs, targets, folds, &metric);
+
+        SearchReport::new(candidates, scores)
+    }
+}
+
+/// Bayesian optimization of the parameters.
+///
+/// The first candidates are sampled at random from the parameter
+/// space. Each later candidate maximizes the expected improvement over
+/// the best mean score so far, under a gaussian process model of the
+/// scores. The gaussian process has a squared exponential kernel on
+/// the parameters mapped to the unit cube, with its hyperparameters
+/// fitted to the scores at each step.
+///
+/// The expected improvement is maximized over a set of proposals
+/// sampled from the parameter space.
+///
+/// Requires the `rand` feature.
+#[cfg(feature = "rand")]
+pub struct BayesSearch {
+    space: ParamSpace,
+    iters: usize,
+    seed: u64,
+    init_iters: usize,
+    proposals: usize,
+    xi: f64,
+}
+
+#[cfg(feature = "rand")]
+impl BayesSearch {
+    /// Constructs a Bayesian optimization scoring `iters` candidates
+    /// from the parameter space.
+    ///
+    /// The defaults are:
+    ///
+    /// - init_iters = 5
+    /// - proposals = 500
+    /// - exploration = 0.01
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::analysis::cross_validation::KFold;
+    /// use rusty_machine::analysis::model_selection::{BayesSearch, ParamSpace, ParamDist, Params};
+    /// use rusty_machine::learning::gp::{GaussianProcess, ConstMean};
+    /// use rusty_machine::learning::toolkit::kernel::SquaredExp;
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::vector::Vector;
+    ///
+    /// let inputs = Matrix::new(6, 1, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+    /// let targets = Vector::new(vec![0.0, 0.8, 0.9, 0.1, -0.8, -1.0]);
+    ///
+    /// let space = ParamSpace::new().add("ls", ParamDist::LogUniform(0.1, 10.0))
+    ///                              .add("noise", ParamDist::LogUniform(1e-3, 1.0));
+    ///
+    /// let builder = |p: &Params| {
+    ///     let ker = SquaredExp::new(p.get("ls"), 1.0);
+    ///     GaussianProcess::new(ker, ConstMean::default(), p.get("noise"))
+    /// };
+    ///
+    /// let neg_mse = |outputs: &Vector<f64>, targets: &Vector<f64>| {
+    ///     let diff = outputs - targets;
+    ///     -diff.dot(&diff) / diff.size() as f64
+    /// };
+    ///
+    /// let folds: Vec<_> = KFold::new(inputs.rows(), 3).collect();
+    /// let search = BayesSearch::new(space, 10, 1).init_iters(4);
+    /// let report = search.fit(builder, &inputs, &targets, &folds, neg_mse);
+    ///
+    /// assert_eq!(report.results().len(), 10);
+    /// println!("Best parameters: {:?}", report.best().params);
+    /// ```
+    pub fn new(space: ParamSpace, iters: usize, seed: u64) -> BayesSearch {
+        BayesSearch {
+            space: space,
+            iters: iters,
+            seed: seed,
+            init_iters: 5,
+            proposals: 500,
+            xi: 0.01,
+        }
+    }
+
+    /// Sets the number of random candidates scored before the
+    /// gaussian process is used.
+    ///
+    /// # Panics
+    ///
+    /// - The number is zero.
+    pub fn init_iters(mut self, init_iters: usize) -> BayesSearch {
+        assert!(init_iters > 0, "At least one initial candidate is required.");
+        self.init_iters = init_iters;
+        self
+    }
+
+    /// Sets the number of proposals the expected improvement is
+    /// maximized over.
+    ///
+    /// # Panics
+    ///
+    /// - The number is zero.
+    pub fn proposals(mut self, proposals: usize) -> BayesSearch {
+        assert!(proposals > 0, "At least one proposal is required.");
+        self.proposals = proposals;
+        self
+    }
+
+    /// Sets the improvement over the best score, in standard
+    /// deviations of the scores, below which candidates are not
+    /// counted as improvements.
+    ///
+    /// Larger values favour exploring uncertain parameters.
+    pub fn exploration(mut self, xi: f64) -> BayesSearch {
+        self.xi = xi;
+        self
+    }
+
+    /// Scores the candidates in the order they are chosen.
+    ///
+    /// See `GridSearch::fit` for details. The same candidates are
+    /// chosen for the same seed and data.
+    ///
+    /// # Panics
+    ///
+    /// - The number of iterations is zero.
+    /// - The number of targets does not match the number of input rows.
+    pub fn fit<M, B, S>(&self,
+                        model_builder: B,
+                        inputs: &Matrix<f64>,
+                        targets: &Vector<f64>,
+                        folds: &[(Vec<usize>, Vec<usize>)],
+                        metric: S)
+                        -> SearchReport
+        where M: SupModel<Matrix<f64>, Vector<f64>>,
+              B: Fn(&Params) -> M,
+              S: Fn(&Vector<f64>, &Vector<f64>) -> f64
+    {
+        let mut rng = seeded_rng(self.seed);
+
+        let mut candidates = Vec::with_capacity(self.iters);
+        let mut scores = Vec::with_capacity(self.iters);
+        let mut means = Vec::with_capacity(self.iters);
+
+        for i in 0..self.iters {
+            let params = if i < self.init_iters {
+                self.space.sample(&mut rng)
+            } else {
+                self.propose(&candidates, &means, &mut rng)
+            };
+
+            let fold_scores = score_candidate(&model_builder, &params, inputs, targets, folds, &metric);
+            means.push(fold_scores.iter().sum::<f64>() / fold_scores.len() as f64);
+            candidates.push(params);
+            scores.push(fold_scores);
+        }
+
+        SearchReport::new(candidates, scores)
+    }
+
+    /// Chooses the proposal with the largest expected improvement.
+    ///
+    /// Candidates with scores which are not finite are left out of the
+    /// gaussian process.
+    fn propose<R: Rng>(&self, observed: &[Params], means: &[f64], rng: &mut R) -> Params {
+        let mut proposals: Vec<Params> = (0..self.proposals)
+            .map(|_| self.space.sample(rng))
+            .collect();
+
+        let finite: Vec<usize> = (0..means.len()).filter(|&i| means[i].is_finite()).collect();
+        if finite.is_empty() {
+            return proposals.swap_remove(0);
+        }
+
+        // Standardize the scores so the default kernel amplitude suits them.
+        let n = finite.len() as f64;
+        let mean = finite.iter().map(|&i| means[i]).sum::<f64>() / n;
+        let var = finite.iter().map(|&i| (means[i] - mean) * (means[i] - mean)).sum::<f64>() / n;
+        let std_dev = if var > 0f64 { var.sqrt() } else { 1f64 };
+        let y = finite.iter().map(|&i| (means[i] - mean) / std_dev).collect::<Vector<f64>>();
+        let best = y.data().iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+
+        let dim = self.space.params.len();
+        let train = Matrix::new(finite.len(),
+                                dim,
+                                finite.iter().flat_map(|&i| self.space.unit(&observed[i])).collect::<Vec<_>>());
+        let test = Matrix::new(proposals.len(),
+                               dim,
+                               proposals.iter().flat_map(|p| self.space.unit(p)).collect::<Vec<_>>());
+
+        let surrogate = || GaussianProcess::new(SquaredExp::new(0.5, 1f64), ConstMean::default(), 1e-2);
+        let mut gp = surrogate();
+        gp.optimize_hyperparams(&train, &y, &ConjugateGD::default());
+        let mut posterior = gp.get_posterior(&test);
+
+        // Fall back to the starting hyperparameters if the fit failed.
+        if !posterior.0.data().iter().all(|m| m.is_finite()) {
+            gp = surrogate();
+            gp.train(&train, &y);
+            posterior = gp.get_posterior(&test);
+        }
+
+        let (post_mean, post_cov) = posterior;
+        let mut best_idx = 0;
+        let mut best_ei = f64::NEG_INFINITY;
+        for i in 0..proposals.len() {
+            let improvement = post_mean[i] - best - self.xi;
+            let sd = post_cov[[i, i]].max(0f64).sqrt();
+            let ei = if sd > 0f64 {
+                let z = improvement / sd;
+                improvement * normal_cdf(z) + sd * normal_pdf(z)
+            } else {
+                improvement.max(0f64)
+            };
+
+            if ei > best_ei {
+                best_ei = ei;
+                best_idx = i;
+            }
+        }
+
+        proposals.swap_remove(best_idx)
+    }
+}
+
+/// The standard normal density.
+#[cfg(feature = "rand")]
+fn normal_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2f64 * PI).sqrt()
+}
+
+/// The standard normal distribution function.
+///
+/// Uses the approximation of the complementary error function from
+/// Abramowitz and Stegun (7.1.26), accurate to about 1e-7.
+#[cfg(feature = "rand")]
+fn normal_cdf(x: f64) -> f64 {
+    let z = x.abs() / SQRT_2;
+    let t = 1f64 / (1f64 + 0.3275911 * z);
+    let poly = t *
+               (0.254829592 +
+                t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let tail = 0.5 * poly * (-z * z).exp();
+
+    if x >= 0f64 { 1f64 - tail } else { tail }
+}
+
+fn score_candidate<M, B, S>(model_builder: &B,
+                            params: &Params,
+                            inputs: &Matrix<f64>,
+                            targets: &Vector<f64>,
+                            folds: &[(Vec<usize>, Vec<usize>)],
+                            metric: &S)
+                            -> Vec<f64>
+    where M: SupModel<Matrix<f64>, Vector<f64>>,
+          B: Fn(&Params) -> M,
+          S: Fn(&Vector<f64>, &Vector<f64>) -> f64
+{
+    cross_val_score_folds(|| model_builder(params),
+                          inputs,
+                          targets,
+                          folds.iter().cloned(),
+                          metric)
+}
+
+#[cfg(feature = "parallel")]
+fn score_parallel<M, B, S>(candidates: &[Params],
+                           model_builder: &B,
+                           inputs: &Matrix<f64>,
+                           targets: &Vector<f64>,
+                           folds: &[(Vec<usize>, Vec<usize>)],
+                           metric: &S)
+                           -> Vec<Vec<f64>>
+    where M: SupModel<Matrix<f64>, Vector<f64>>,
+          B: Fn(&Params) -> M + Sync,
+          S: Fn(&Vector<f64>, &Vector<f64>) -> f64 + Sync
+{
+    candidates.par_iter()
+              .map(|p| score_candidate(model_builder, p, inputs, targets, folds, metric))
+              .collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+fn score_parallel<M, B, S>(candidates: &[Params],
+                           model_builder: &B,
+                           inputs: &Matrix<f64>,
+                           targets: &Vector<f64>,
+                           folds: &[(Vec<usize>, Vec<usize>)],
+                           metric: &S)
+                           -> Vec<Vec<f64>>
+    where M: SupModel<Matrix<f64>, Vector<f64>>,
+          B: Fn(&Params) -> M + Sync,
+          S: Fn(&Vector<f64>, &Vector<f64>) -> f64 + Sync
+{
+    let n_threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let chunk_size = candidates.len().div_ceil(n_threads);
+
+    if chunk_size == 0 {
+        return Vec::new();
+    }
+
+    thread::scope(|s| {
+        let handles: Vec<_> = candidates.chunks(chunk_size)
+                                        .map(|chunk| {
+                                            s.spawn(move || {
+                                                chunk.iter()
+                                                     .map(|p| {
+                                                         score_candidate(model_builder,
+                                                                         p,
+                                                                         inputs,
+                                                                         targets,
+                                                                         folds,
+                                                                         metric)
+                                                     })
+                                                     .collect::<Vec<_>>()
+                                            })
+                                        })
+                                        .collect();
+
+        handles.into_iter()
+               .flat_map(|h| h.join().expect("Search thread panicked."))
+               .collect()
+    })
+}
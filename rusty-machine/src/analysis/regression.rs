@@ -0,0 +1,205 @@
+//! Regression metrics
+//!
+//! Scores for comparing predicted outputs of a regression model with
+//! the true targets.
+//!
+//! Each metric takes the outputs followed by the targets, so it can be
+//! passed directly to the cross validation functions. The metrics
+//! accept either a `Vector` for a single output or a `Matrix` with
+//! an output in each column. For multiple outputs the metric is
+//! computed for each column and the results are averaged.
+//!
+//! Note that for the error metrics lower is better, whereas model
+//! selection in this crate picks the highest score. Negate the error
+//! when searching for the best model.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::analysis::regression::{mean_squared_error, r2_score};
+//! use rusty_machine::linalg::matrix::Matrix;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! let outputs = Vector::new(vec![1.0, 2.0, 4.0]);
+//! let targets = Vector::new(vec![1.0, 3.0, 5.0]);
+//!
+//! assert!((mean_squared_error(&outputs, &targets) - 2.0 / 3.0).abs() < 1e-12);
+//! assert_eq!(r2_score(&outputs, &targets), 0.75);
+//!
+//! // The mean over two outputs.
+//! let outputs = Matrix::new(2, 2, vec![1.0, 0.0, 2.0, 0.0]);
+//! let targets = Matrix::new(2, 2, vec![1.0, 1.0, 2.0, 1.0]);
+//!
+//! assert_eq!(mean_squared_error(&outputs, &targets), 0.5);
+//! ```
+
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+
+use std::f64;
+
+/// Types which hold the outputs or targets of a regression model.
+pub trait RegressionData {
+    /// The number of samples.
+    fn samples(&self) -> usize;
+
+    /// The values of each output, one `Vec` per output.
+    fn outputs(&self) -> Vec<Vec<f64>>;
+}
+
+impl RegressionData for Vector<f64> {
+    fn samples(&self) -> usize {
+        self.size()
+    }
+
+    fn outputs(&self) -> Vec<Vec<f64>> {
+        vec![self.data().clone()]
+    }
+}
+
+impl RegressionData for Matrix<f64> {
+    fn samples(&self) -> usize {
+        self.rows()
+    }
+
+    fn outputs(&self) -> Vec<Vec<f64>> {
+        let cols = self.cols();
+        (0..cols)
+            .map(|j| self.data().iter().skip(j).step_by(cols).cloned().collect())
+            .collect()
+    }
+}
+
+/// The mean squared error of the outputs.
+///
+/// # Panics
+///
+/// - The outputs and targets have different shapes.
+/// - There are no samples.
+pub fn mean_squared_error<T: RegressionData>(outputs: &T, targets: &T) -> f64 {
+    average_outputs(outputs, targets, |o, t| {
+        mean(o.iter().zip(t.iter()).map(|(x, y)| (x - y) * (x - y)))
+    })
+}
+
+/// The root of the mean squared error of the outputs.
+///
+/// For multiple outputs this is the mean of the root mean squared
+/// error of each output.
+///
+/// # Panics
+///
+/// - The outputs and targets have different shapes.
+/// - There are no samples.
+pub fn root_mean_squared_error<T: RegressionData>(outputs: &T, targets: &T) -> f64 {
+    average_outputs(outputs, targets, |o, t| {
+        mean(o.iter().zip(t.iter()).map(|(x, y)| (x - y) * (x - y))).sqrt()
+    })
+}
+
+/// The mean absolute error of the outputs.
+///
+/// # Panics
+///
+/// - The outputs and targets have different shapes.
+/// - There are no samples.
+pub fn mean_absolute_error<T: RegressionData>(outputs: &T, targets: &T) -> f64 {
+    average_outputs(outputs, targets, |o, t| {
+        mean(o.iter().zip(t.iter()).map(|(x, y)| (x - y).abs()))
+    })
+}
+
+/// The mean absolute percentage error of the outputs.
+///
+/// The error is returned as a fraction, so `0.1` is ten percent.
+/// Targets of zero are replaced by the machine epsilon to avoid
+/// dividing by zero.
+///
+/// # Panics
+///
+/// - The outputs and targets have different shapes.
+/// - There are no samples.
+pub fn mean_absolute_percentage_error<T: RegressionData>(outputs: &T, targets: &T) -> f64 {
+    average_outputs(outputs, targets, |o, t| {
+        mean(o.iter().zip(t.iter()).map(|(x, y)| (x - y).abs() / y.abs().max(f64::EPSILON)))
+    })
+}
+
+/// The coefficient of determination (R²) of the outputs.
+///
+/// This is one minus the ratio of the residual sum of squares to the
+/// total sum of squares of the targets. The best score is 1 and
+/// always predicting the mean of the targets scores 0.
+///
+/// Constant targets score 1 if they are predicted exactly and 0
+/// otherwise.
+///
+/// # Panics
+///
+/// - The outputs and targets have different shapes.
+/// - There are no samples.
+pub fn r2_score<T: RegressionData>(outputs: &T, targets: &T) -> f64 {
+    average_outputs(outputs, targets, |o, t| {
+        let t_mean = mean(t.iter().cloned());
+        let ss_res: f64 = o.iter().zip(t.iter()).map(|(x, y)| (y - x) * (y - x)).sum();
+        let ss_tot: f64 = t.iter().map(|y| (y - t_mean) * (y - t_mean)).sum();
+
+        one_minus_ratio(ss_res, ss_tot)
+    })
+}
+
+/// The explained variance score of the outputs.
+///
+/// This is one minus the ratio of the variance of the residuals to the
+/// variance of the targets. Unlike R² it ignores a constant bias in
+/// the outputs.
+///
+/// Constant targets score 1 if the residuals are constant and 0
+/// otherwise.
+///
+/// # Panics
+///
+/// - The outputs and targets have different shapes.
+/// - There are no samples.
+pub fn explained_variance<T: RegressionData>(outputs: &T, targets: &T) -> f64 {
+    average_outputs(outputs, targets, |o, t| {
+        let residuals: Vec<f64> = o.iter().zip(t.iter()).map(|(x, y)| y - x).collect();
+        one_minus_ratio(variance(&residuals), variance(t))
+    })
+}
+
+/// Applies the metric to each output and averages the results.
+fn average_outputs<T, F>(outputs: &T, targets: &T, metric: F) -> f64
+    where T: RegressionData,
+          F: Fn(&[f64], &[f64]) -> f64
+{
+    assert!(outputs.samples() == targets.samples(),
+            "Outputs and targets have different lengths.");
+    assert!(targets.samples() > 0, "Cannot score empty outputs.");
+
+    let outputs = outputs.outputs();
+    let targets = targets.outputs();
+    assert!(outputs.len() == targets.len(),
+            "Outputs and targets have a different number of columns.");
+
+    let total: f64 = outputs.iter().zip(targets.iter()).map(|(o, t)| metric(o, t)).sum();
+    total / targets.len() as f64
+}
+
+fn mean<I: ExactSizeIterator<Item = f64>>(values: I) -> f64 {
+    let n = values.len();
+    values.sum::<f64>() / n as f64
+}
+
+fn variance(values: &[f64]) -> f64 {
+    let m = mean(values.iter().cloned());
+    mean(values.iter().map(|x| (x - m) * (x - m)))
+}
+
+fn one_minus_ratio(num: f64, denom: f64) -> f64 {
+    if denom == 0f64 {
+        if num == 0f64 { 1f64 } else { 0f64 }
+    } else {
+        1f64 - num / denom
+    }
+}
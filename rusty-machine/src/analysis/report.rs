@@ -0,0 +1,203 @@
+//! Classification reports
+//!
+//! Summarizes the per-class precision, recall, F1 score and support
+//! of a classifier, along with the accuracy and averages over the
+//! classes. The report can be printed for quick evaluation.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::analysis::report::classification_report;
+//!
+//! let predicted = vec![0.0, 1.0, 1.0, 2.0, 2.0];
+//! let actual = vec![0.0, 1.0, 2.0, 2.0, 2.0];
+//!
+//! let report = classification_report(&predicted, &actual);
+//!
+//! assert_eq!(report.classes()[1].precision, 0.5);
+//! assert_eq!(report.classes()[2].support, 3);
+//! assert_eq!(report.accuracy(), 0.8);
+//!
+//! println!("{}", report);
+//! ```
+
+use std::fmt;
+
+use analysis::confusion_matrix::{confusion_matrix, ConfusionMatrix};
+
+/// The metrics of a single class.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClassMetrics<T> {
+    /// The class label.
+    pub label: T,
+    /// The fraction of samples predicted as the class which belong to it.
+    pub precision: f64,
+    /// The fraction of samples of the class which are predicted correctly.
+    pub recall: f64,
+    /// The harmonic mean of the precision and recall.
+    pub f1_score: f64,
+    /// The number of samples of the class.
+    pub support: usize,
+}
+
+/// Precision, recall and F1 score averaged over the classes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AverageMetrics {
+    /// The averaged precision.
+    pub precision: f64,
+    /// The averaged recall.
+    pub recall: f64,
+    /// The averaged F1 score.
+    pub f1_score: f64,
+}
+
+/// The per-class metrics of a classifier.
+#[derive(Clone, Debug)]
+pub struct ClassificationReport<T> {
+    classes: Vec<ClassMetrics<T>>,
+    accuracy: f64,
+    total: usize,
+}
+
+/// Computes the classification report of the predicted classes.
+///
+/// The classes are every label which appears in either the predicted
+/// or the actual classes, in increasing order.
+///
+/// # Panics
+///
+/// - The predicted and actual classes have different lengths.
+pub fn classification_report<T: Copy + PartialOrd>(predicted: &[T],
+                                                   actual: &[T])
+                                                   -> ClassificationReport<T> {
+    ClassificationReport::from_confusion_matrix(&confusion_matrix(predicted, actual))
+}
+
+impl<T: Copy> ClassificationReport<T> {
+    /// Computes the classification report from a confusion matrix.
+    pub fn from_confusion_matrix(cm: &ConfusionMatrix<T>) -> ClassificationReport<T> {
+        let precisions = cm.precisions();
+        let recalls = cm.recalls();
+        let f1_scores = cm.f1_scores();
+
+        let classes = cm.labels()
+                        .iter()
+                        .enumerate()
+                        .map(|(i, &label)| {
+                            ClassMetrics {
+                                label: label,
+                                precision: precisions[i],
+                                recall: recalls[i],
+                                f1_score: f1_scores[i],
+                                support: cm.support(i),
+                            }
+                        })
+                        .collect();
+
+        ClassificationReport {
+            classes: classes,
+            accuracy: cm.accuracy(),
+            total: cm.total(),
+        }
+    }
+}
+
+impl<T> ClassificationReport<T> {
+    /// The metrics of each class.
+    pub fn classes(&self) -> &[ClassMetrics<T>] {
+        &self.classes
+    }
+
+    /// The fraction of samples which were predicted correctly.
+    pub fn accuracy(&self) -> f64 {
+        self.accuracy
+    }
+
+    /// The total number of samples.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// The unweighted mean of the metrics over the classes.
+    pub fn macro_average(&self) -> AverageMetrics {
+        let n = self.classes.len() as f64;
+        self.average(|_| 1f64 / n)
+    }
+
+    /// The mean of the metrics over the classes, weighted by support.
+    pub fn weighted_average(&self) -> AverageMetrics {
+        let total = self.total as f64;
+        self.average(|c| if total > 0f64 { c.support as f64 / total } else { 0f64 })
+    }
+
+    fn average<F: Fn(&ClassMetrics<T>) -> f64>(&self, weight: F) -> AverageMetrics {
+        let mut avg = AverageMetrics {
+            precision: 0f64,
+            recall: 0f64,
+            f1_score: 0f64,
+        };
+
+        for c in &self.classes {
+            let w = weight(c);
+            avg.precision += w * c.precision;
+            avg.recall += w * c.recall;
+            avg.f1_score += w * c.f1_score;
+        }
+
+        avg
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for ClassificationReport<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let labels: Vec<String> = self.classes.iter().map(|c| c.label.to_string()).collect();
+        let width = labels.iter().map(|l| l.len()).max().unwrap_or(0).max("weighted avg".len());
+
+        writeln!(f,
+                 "{:>w$} {:>9} {:>9} {:>9} {:>9}",
+                 "",
+                 "precision",
+                 "recall",
+                 "f1-score",
+                 "support",
+                 w = width)?;
+        writeln!(f)?;
+
+        for (label, c) in labels.iter().zip(self.classes.iter()) {
+            writeln!(f,
+                     "{:>w$} {:>9.2} {:>9.2} {:>9.2} {:>9}",
+                     label,
+                     c.precision,
+                     c.recall,
+                     c.f1_score,
+                     c.support,
+                     w = width)?;
+        }
+        writeln!(f)?;
+
+        writeln!(f,
+                 "{:>w$} {:>9} {:>9} {:>9.2} {:>9}",
+                 "accuracy",
+                 "",
+                 "",
+                 self.accuracy,
+                 self.total,
+                 w = width)?;
+
+        let averages = [("macro avg", self.macro_average()),
+                        ("weighted avg", self.weighted_average())];
+
+        for &(name, ref avg) in &averages {
+            writeln!(f,
+                     "{:>w$} {:>9.2} {:>9.2} {:>9.2} {:>9}",
+                     name,
+                     avg.precision,
+                     avg.recall,
+                     avg.f1_score,
+                     self.total,
+                     w = width)?;
+        }
+
+        Ok(())
+    }
+}
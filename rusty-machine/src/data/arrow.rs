@@ -0,0 +1,301 @@
+//! Arrow and Parquet loading
+//!
+//! Converts numeric columns of Apache Arrow record batches into a
+//! `Matrix`, with an optional target column read into a `Vector`.
+//! With the `parquet` feature Parquet files can be loaded directly,
+//! reading only the selected columns.
+//!
+//! Floating point, integer and boolean columns are converted to
+//! `f64`. Columns containing nulls cannot be read.
+//!
+//! Requires the `arrow` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate arrow_array;
+//! # extern crate rusty_machine;
+//! # fn main() {
+//! use std::sync::Arc;
+//!
+//! use arrow_array::{ArrayRef, Float64Array, Int32Array, RecordBatch};
+//! use rusty_machine::data::arrow::{read_record_batches, ArrowOptions};
+//!
+//! let batch = RecordBatch::try_from_iter(vec![
+//!     ("x1", Arc::new(Float64Array::from(vec![1.0, 3.0])) as ArrayRef),
+//!     ("x2", Arc::new(Int32Array::from(vec![2, 4])) as ArrayRef),
+//!     ("y", Arc::new(Int32Array::from(vec![0, 1])) as ArrayRef),
+//! ]).unwrap();
+//!
+//! let options = ArrowOptions::default().target("y");
+//! let data = read_record_batches(&[batch], &options).unwrap();
+//!
+//! assert_eq!(*data.inputs.data(), vec![1.0, 2.0, 3.0, 4.0]);
+//! assert_eq!(*data.targets.unwrap().data(), vec![0.0, 1.0]);
+//! assert_eq!(data.columns, vec!["x1", "x2"]);
+//! # }
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::slice;
+#[cfg(feature = "parquet")]
+use std::fs::File;
+#[cfg(feature = "parquet")]
+use std::path::Path;
+
+use arrow_array::{Array, ArrowPrimitiveType, BooleanArray, PrimitiveArray, RecordBatch};
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type,
+                         UInt16Type, UInt32Type, UInt64Type, UInt8Type};
+use arrow_schema::{ArrowError, DataType, Schema};
+
+#[cfg(feature = "parquet")]
+use parquet::arrow::ProjectionMask;
+#[cfg(feature = "parquet")]
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+#[cfg(feature = "parquet")]
+use parquet::errors::ParquetError;
+
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+
+/// Options for reading Arrow data.
+#[derive(Clone, Debug, Default)]
+pub struct ArrowOptions {
+    columns: Option<Vec<String>>,
+    target: Option<String>,
+}
+
+impl ArrowOptions {
+    /// Selects the columns used as inputs, in the given order.
+    ///
+    /// By default every column other than the target is used.
+    pub fn columns(mut self, columns: Vec<&str>) -> ArrowOptions {
+        self.columns = Some(columns.into_iter().map(|c| c.to_string()).collect());
+        self
+    }
+
+    /// Sets the column which is read into the targets.
+    pub fn target(mut self, target: &str) -> ArrowOptions {
+        self.target = Some(target.to_string());
+        self
+    }
+}
+
+/// The data read from Arrow record batches.
+pub struct ArrowData {
+    /// The input columns.
+    pub inputs: Matrix<f64>,
+    /// The target column, if one was chosen.
+    pub targets: Option<Vector<f64>>,
+    /// The names of the input columns.
+    pub columns: Vec<String>,
+}
+
+/// Errors from reading Arrow data.
+#[derive(Debug)]
+pub enum ArrowDataError {
+    /// An error from the Arrow library.
+    Arrow(ArrowError),
+    /// An error reading a Parquet file.
+    #[cfg(feature = "parquet")]
+    Parquet(ParquetError),
+    /// A selected column does not exist.
+    MissingColumn(String),
+    /// A selected column is not numeric.
+    UnsupportedType {
+        /// The column name.
+        column: String,
+        /// The Arrow data type of the column.
+        data_type: DataType,
+    },
+    /// A selected column contains nulls.
+    Nulls(String),
+    /// The record batches have different schemas.
+    SchemaMismatch,
+}
+
+impl fmt::Display for ArrowDataError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ArrowDataError::Arrow(ref e) => write!(f, "Arrow error: {}", e),
+            #[cfg(feature = "parquet")]
+            ArrowDataError::Parquet(ref e) => write!(f, "Parquet error: {}", e),
+            ArrowDataError::MissingColumn(ref c) => write!(f, "Column '{}' does not exist.", c),
+            ArrowDataError::UnsupportedType { ref column, ref data_type } => {
+                write!(f, "Column '{}' has non-numeric type {}.", column, data_type)
+            }
+            ArrowDataError::Nulls(ref c) => write!(f, "Column '{}' contains nulls.", c),
+            ArrowDataError::SchemaMismatch => write!(f, "Record batches have different schemas."),
+        }
+    }
+}
+
+impl Error for ArrowDataError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            ArrowDataError::Arrow(ref e) => Some(e),
+            #[cfg(feature = "parquet")]
+            ArrowDataError::Parquet(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<ArrowError> for ArrowDataError {
+    fn from(e: ArrowError) -> ArrowDataError {
+        ArrowDataError::Arrow(e)
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl From<ParquetError> for ArrowDataError {
+    fn from(e: ParquetError) -> ArrowDataError {
+        ArrowDataError::Parquet(e)
+    }
+}
+
+/// Reads the selected columns of record batches into a matrix.
+///
+/// The batches are concatenated in order and must share a schema.
+pub fn read_record_batches(batches: &[RecordBatch],
+                           options: &ArrowOptions)
+                           -> Result<ArrowData, ArrowDataError> {
+    let schema = match batches.first() {
+        Some(b) => b.schema(),
+        None => {
+            return Ok(ArrowData {
+                inputs: Matrix::new(0, options.columns.as_ref().map_or(0, |c| c.len()), vec![]),
+                targets: options.target.as_ref().map(|_| Vector::new(vec![])),
+                columns: options.columns.clone().unwrap_or_default(),
+            })
+        }
+    };
+
+    if batches.iter().any(|b| b.schema() != schema) {
+        return Err(ArrowDataError::SchemaMismatch);
+    }
+
+    let columns = input_columns(&schema, options)?;
+    let col_idxs = column_indices(&schema, &columns)?;
+    let target_idx = match options.target {
+        Some(ref t) => Some(column_indices(&schema, slice::from_ref(t))?[0]),
+        None => None,
+    };
+
+    let n_rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+    let n_cols = col_idxs.len();
+    let mut data = vec![0f64; n_rows * n_cols];
+    let mut targets = Vec::with_capacity(if target_idx.is_some() { n_rows } else { 0 });
+
+    let mut row_start = 0;
+    for batch in batches {
+        for (j, &idx) in col_idxs.iter().enumerate() {
+            let values = column_values(batch.column(idx).as_ref(), schema.field(idx).name())?;
+            for (i, x) in values.into_iter().enumerate() {
+                data[(row_start + i) * n_cols + j] = x;
+            }
+        }
+
+        if let Some(idx) = target_idx {
+            targets.extend(column_values(batch.column(idx).as_ref(), schema.field(idx).name())?);
+        }
+
+        row_start += batch.num_rows();
+    }
+
+    Ok(ArrowData {
+        inputs: Matrix::new(n_rows, n_cols, data),
+        targets: target_idx.map(|_| Vector::new(targets)),
+        columns: columns,
+    })
+}
+
+/// Loads the selected columns of a Parquet file into a matrix.
+///
+/// Only the input and target columns are read from the file.
+///
+/// Requires the `parquet` feature.
+#[cfg(feature = "parquet")]
+pub fn load_parquet<P: AsRef<Path>>(path: P,
+                                    options: &ArrowOptions)
+                                    -> Result<ArrowData, ArrowDataError> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(path)
+                                                               .map_err(ParquetError::from)?)?;
+
+    let schema = builder.schema().clone();
+    let mut needed = column_indices(&schema, &input_columns(&schema, options)?)?;
+    if let Some(ref t) = options.target {
+        needed.extend(column_indices(&schema, slice::from_ref(t))?);
+    }
+    needed.sort();
+    needed.dedup();
+
+    let mask = ProjectionMask::roots(builder.parquet_schema(), needed);
+    let batches = builder.with_projection(mask)
+                         .build()?
+                         .collect::<Result<Vec<_>, _>>()?;
+
+    read_record_batches(&batches, options)
+}
+
+/// The names of the input columns.
+fn input_columns(schema: &Schema, options: &ArrowOptions) -> Result<Vec<String>, ArrowDataError> {
+    match options.columns {
+        Some(ref c) => Ok(c.clone()),
+        None => {
+            Ok(schema.fields()
+                     .iter()
+                     .map(|f| f.name().clone())
+                     .filter(|n| options.target.as_ref() != Some(n))
+                     .collect())
+        }
+    }
+}
+
+fn column_indices(schema: &Schema, columns: &[String]) -> Result<Vec<usize>, ArrowDataError> {
+    columns.iter()
+           .map(|c| schema.index_of(c).map_err(|_| ArrowDataError::MissingColumn(c.clone())))
+           .collect()
+}
+
+/// Converts a numeric column to doubles.
+fn column_values(array: &dyn Array, name: &str) -> Result<Vec<f64>, ArrowDataError> {
+    if array.null_count() > 0 {
+        return Err(ArrowDataError::Nulls(name.to_string()));
+    }
+
+    let values = match *array.data_type() {
+        DataType::Float64 => primitive_values::<Float64Type, _>(array, |x| x),
+        DataType::Float32 => primitive_values::<Float32Type, _>(array, |x| x as f64),
+        DataType::Int8 => primitive_values::<Int8Type, _>(array, |x| x as f64),
+        DataType::Int16 => primitive_values::<Int16Type, _>(array, |x| x as f64),
+        DataType::Int32 => primitive_values::<Int32Type, _>(array, |x| x as f64),
+        DataType::Int64 => primitive_values::<Int64Type, _>(array, |x| x as f64),
+        DataType::UInt8 => primitive_values::<UInt8Type, _>(array, |x| x as f64),
+        DataType::UInt16 => primitive_values::<UInt16Type, _>(array, |x| x as f64),
+        DataType::UInt32 => primitive_values::<UInt32Type, _>(array, |x| x as f64),
+        DataType::UInt64 => primitive_values::<UInt64Type, _>(array, |x| x as f64),
+        DataType::Boolean => {
+            let bools: &BooleanArray = array.as_boolean();
+            bools.values().iter().map(|b| if b { 1f64 } else { 0f64 }).collect()
+        }
+        ref data_type => {
+            return Err(ArrowDataError::UnsupportedType {
+                column: name.to_string(),
+                data_type: data_type.clone(),
+            })
+        }
+    };
+
+    Ok(values)
+}
+
+fn primitive_values<T, F>(array: &dyn Array, convert: F) -> Vec<f64>
+    where T: ArrowPrimitiveType,
+          F: Fn(T::Native) -> f64
+{
+    let array: &PrimitiveArray<T> = array.as_primitive();
+    array.values().iter().map(|&x| convert(x)).collect()
+}
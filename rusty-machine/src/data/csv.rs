@@ -0,0 +1,533 @@
+//! CSV loading
+//!
+//! Reads delimited text files into matrices.
+//!
+//! Fields may be quoted with double quotes, in which case they can
+//! contain the delimiter. Every selected field must parse as a number.
+//!
+//! `CsvSource` reads the data a mini-batch at a time, for training on
+//! files which do not fit in memory.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::data::csv::{read_matrix, CsvOptions};
+//!
+//! let text = "x1,x2,y\n1.0,2.0,0\n3.0,4.0,1\n";
+//!
+//! let options = CsvOptions::default().header(true).target(2);
+//! let data = read_matrix(text.as_bytes(), &options).unwrap();
+//!
+//! assert_eq!(*data.inputs.data(), vec![1.0, 2.0, 3.0, 4.0]);
+//! assert_eq!(*data.targets.unwrap().data(), vec![0.0, 1.0]);
+//! assert_eq!(data.header.unwrap(), vec!["x1", "x2"]);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+#[cfg(feature = "io")]
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
+use std::marker::PhantomData;
+use std::mem;
+#[cfg(feature = "io")]
+use std::path::Path;
+
+use data::source::DataSource;
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+
+/// Options for reading delimited files.
+#[derive(Clone, Debug)]
+pub struct CsvOptions {
+    delimiter: char,
+    header: bool,
+    columns: Option<Vec<usize>>,
+    target: Option<usize>,
+}
+
+/// Comma delimited, with no header, using every column as an input.
+impl Default for CsvOptions {
+    fn default() -> CsvOptions {
+        CsvOptions {
+            delimiter: ',',
+            header: false,
+            columns: None,
+            target: None,
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Sets the field delimiter.
+    pub fn delimiter(mut self, delimiter: char) -> CsvOptions {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets whether the first line is a header of column names.
+    pub fn header(mut self, header: bool) -> CsvOptions {
+        self.header = header;
+        self
+    }
+
+    /// Selects the columns used as inputs, in the given order.
+    ///
+    /// By default every column other than the target is used.
+    pub fn columns(mut self, columns: Vec<usize>) -> CsvOptions {
+        self.columns = Some(columns);
+        self
+    }
+
+    /// Sets the column which is read into the targets.
+    pub fn target(mut self, target: usize) -> CsvOptions {
+        self.target = Some(target);
+        self
+    }
+}
+
+/// The data read from a delimited file.
+pub struct CsvData {
+    /// The input columns.
+    pub inputs: Matrix<f64>,
+    /// The target column, if one was chosen.
+    pub targets: Option<Vector<f64>>,
+    /// The names of the input columns, if the file has a header.
+    pub header: Option<Vec<String>>,
+}
+
+/// Errors from reading delimited files.
+#[derive(Debug)]
+pub enum CsvError {
+    /// An error reading the file.
+    Io(io::Error),
+    /// A field could not be parsed as a number.
+    Parse {
+        /// The line number, starting from 1.
+        line: usize,
+        /// The column index.
+        column: usize,
+        /// The field which failed to parse.
+        value: String,
+    },
+    /// A line has a different number of fields to the first line.
+    FieldCount {
+        /// The line number, starting from 1.
+        line: usize,
+        /// The expected number of fields.
+        expected: usize,
+        /// The number of fields found.
+        found: usize,
+    },
+    /// A selected column does not exist.
+    MissingColumn(usize),
+    /// The file contains no data.
+    Empty,
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CsvError::Io(ref e) => write!(f, "IO error: {}", e),
+            CsvError::Parse { line, column, ref value } => {
+                write!(f,
+                       "Could not parse '{}' as a number on line {}, column {}.",
+                       value,
+                       line,
+                       column)
+            }
+            CsvError::FieldCount { line, expected, found } => {
+                write!(f,
+                       "Expected {} fields but found {} on line {}.",
+                       expected,
+                       found,
+                       line)
+            }
+            CsvError::MissingColumn(c) => write!(f, "Column {} does not exist.", c),
+            CsvError::Empty => write!(f, "No data found."),
+        }
+    }
+}
+
+impl Error for CsvError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            CsvError::Io(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CsvError {
+    fn from(e: io::Error) -> CsvError {
+        CsvError::Io(e)
+    }
+}
+
+/// Loads a delimited file into a matrix.
+///
+/// See `read_matrix` for details.
+///
+/// Requires the `io` feature.
+#[cfg(feature = "io")]
+pub fn load_matrix<P: AsRef<Path>>(path: P, options: &CsvOptions) -> Result<CsvData, CsvError> {
+    let file = File::open(path)?;
+    read_matrix(file, options)
+}
+
+/// Reads delimited data into a matrix.
+///
+/// Blank lines are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::data::csv::{read_matrix, CsvOptions};
+///
+/// let text = "1;2;3\n4;5;6\n";
+///
+/// let options = CsvOptions::default().delimiter(';').columns(vec![2, 0]);
+/// let data = read_matrix(text.as_bytes(), &options).unwrap();
+///
+/// assert_eq!(*data.inputs.data(), vec![3.0, 1.0, 6.0, 4.0]);
+/// assert!(data.targets.is_none());
+/// ```
+pub fn read_matrix<R: Read>(reader: R, options: &CsvOptions) -> Result<CsvData, CsvError> {
+    let mut lines = Vec::new();
+    for (i, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            lines.push((i + 1, split_line(&line, options.delimiter)));
+        }
+    }
+
+    let mut lines = lines.into_iter();
+
+    let header = if options.header { lines.next() } else { None };
+    let body: Vec<(usize, Vec<String>)> = lines.collect();
+
+    let n_fields = match (body.first(), header.as_ref()) {
+        (Some((_, fields)), _) => fields.len(),
+        (None, Some((_, h))) => h.len(),
+        (None, None) => return Err(CsvError::Empty),
+    };
+
+    if let Some((line, ref h)) = header {
+        if h.len() != n_fields {
+            return Err(CsvError::FieldCount {
+                line: line,
+                expected: n_fields,
+                found: h.len(),
+            });
+        }
+    }
+
+    let columns = select_columns(options, n_fields)?;
+
+    let mut data = Vec::with_capacity(body.len() * columns.len());
+    let mut targets = Vec::with_capacity(body.len());
+
+    for &(line, ref fields) in &body {
+        parse_line(fields,
+                   line,
+                   n_fields,
+                   &columns,
+                   options.target,
+                   &mut data,
+                   &mut targets)?;
+    }
+
+    Ok(CsvData {
+        inputs: Matrix::new(body.len(), columns.len(), data),
+        targets: options.target.map(|_| Vector::new(targets)),
+        header: header.map(|(_, h)| columns.iter().map(|&c| h[c].trim().to_string()).collect()),
+    })
+}
+
+/// A `DataSource` which reads mini-batches of delimited data.
+///
+/// Only one mini-batch is parsed at a time, and each pass reads the
+/// data again from the start, so files larger than memory can be used
+/// for training. The target column is read as a single column matrix
+/// or, for a `CsvSource<R, Vector<f64>>`, as a vector. Without a target
+/// the targets have no columns, or no entries.
+///
+/// Blank lines are skipped. The number of fields is taken from the
+/// first line, which is the header if there is one.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::data::csv::{CsvOptions, CsvSource};
+/// use rusty_machine::data::source::DataSource;
+/// use rusty_machine::linalg::matrix::Matrix;
+/// use std::io::Cursor;
+///
+/// let text = "x,y\n1.0,0\n2.0,1\n3.0,1\n";
+/// let options = CsvOptions::default().header(true).target(1);
+/// let mut source = CsvSource::new(Cursor::new(text), options, 2);
+///
+/// let mut inputs = Matrix::new(0, 0, vec![]);
+/// let mut targets = Matrix::new(0, 0, vec![]);
+///
+/// source.reset().unwrap();
+/// assert!(source.next_batch(&mut inputs, &mut targets).unwrap());
+/// assert_eq!(*inputs.data(), vec![1.0, 2.0]);
+/// assert!(source.next_batch(&mut inputs, &mut targets).unwrap());
+/// assert_eq!(*targets.data(), vec![1.0]);
+/// assert!(!source.next_batch(&mut inputs, &mut targets).unwrap());
+/// ```
+pub struct CsvSource<R, T = Matrix<f64>> {
+    reader: R,
+    options: CsvOptions,
+    batch_size: usize,
+    /// The number of lines read in the current pass.
+    line: usize,
+    /// The number of fields and the input columns, from the first line.
+    layout: Option<(usize, Vec<usize>)>,
+    buf: String,
+    targets: PhantomData<T>,
+}
+
+impl<R: BufRead + Seek, T> CsvSource<R, T> {
+    /// Constructs a source of batches of the given size.
+    ///
+    /// The final batch of each pass holds the remaining rows and may
+    /// be smaller.
+    ///
+    /// # Panics
+    ///
+    /// - The batch size is zero.
+    pub fn new(reader: R, options: CsvOptions, batch_size: usize) -> CsvSource<R, T> {
+        assert!(batch_size > 0, "Batch size must be greater than zero.");
+
+        CsvSource {
+            reader: reader,
+            options: options,
+            batch_size: batch_size,
+            line: 0,
+            layout: None,
+            buf: String::new(),
+            targets: PhantomData,
+        }
+    }
+
+    /// Reads the next line which is not blank, split into fields.
+    fn read_fields(&mut self) -> Result<Option<Vec<String>>, CsvError> {
+        loop {
+            self.buf.clear();
+            if self.reader.read_line(&mut self.buf)? == 0 {
+                return Ok(None);
+            }
+            self.line += 1;
+
+            let line = self.buf.trim_end_matches(['\n', '\r']);
+            if !line.trim().is_empty() {
+                return Ok(Some(split_line(line, self.options.delimiter)));
+            }
+        }
+    }
+
+    /// Sets the layout from the first line, if it is not yet known.
+    fn check_layout(&mut self, n_fields: usize) -> Result<(), CsvError> {
+        if self.layout.is_none() {
+            self.layout = Some((n_fields, select_columns(&self.options, n_fields)?));
+        }
+        Ok(())
+    }
+
+    /// Starts a new pass through the data, reading any header.
+    fn rewind(&mut self) -> Result<(), CsvError> {
+        self.reader.seek(SeekFrom::Start(0))?;
+        self.line = 0;
+
+        if self.options.header {
+            if let Some(header) = self.read_fields()? {
+                self.check_layout(header.len())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the next mini-batch into the inputs and the target data,
+    /// returning the number of rows read.
+    fn read_batch(&mut self,
+                  inputs: &mut Matrix<f64>,
+                  target_data: &mut Vec<f64>)
+                  -> Result<usize, CsvError> {
+        let mut input_data = mem::replace(inputs, Matrix::new(0, 0, vec![])).into_vec();
+        input_data.clear();
+        target_data.clear();
+
+        let mut rows = 0;
+        while rows < self.batch_size {
+            let fields = match self.read_fields()? {
+                Some(fields) => fields,
+                None => break,
+            };
+            self.check_layout(fields.len())?;
+
+            if let Some((n_fields, ref columns)) = self.layout {
+                parse_line(&fields,
+                           self.line,
+                           n_fields,
+                           columns,
+                           self.options.target,
+                           &mut input_data,
+                           target_data)?;
+            }
+            rows += 1;
+        }
+
+        let cols = self.layout.as_ref().map_or(0, |l| l.1.len());
+        *inputs = Matrix::new(rows, cols, input_data);
+        Ok(rows)
+    }
+}
+
+#[cfg(feature = "io")]
+impl<T> CsvSource<BufReader<File>, T> {
+    /// Opens a delimited file as a source of batches of the given size.
+    ///
+    /// Requires the `io` feature.
+    ///
+    /// # Panics
+    ///
+    /// - The batch size is zero.
+    pub fn open<P: AsRef<Path>>(path: P,
+                                options: CsvOptions,
+                                batch_size: usize)
+                                -> Result<CsvSource<BufReader<File>, T>, CsvError> {
+        let file = File::open(path)?;
+        Ok(CsvSource::new(BufReader::new(file), options, batch_size))
+    }
+}
+
+impl<R: BufRead + Seek> DataSource<Matrix<f64>> for CsvSource<R, Matrix<f64>> {
+    type Error = CsvError;
+
+    fn reset(&mut self) -> Result<(), CsvError> {
+        self.rewind()
+    }
+
+    fn next_batch(&mut self,
+                  inputs: &mut Matrix<f64>,
+                  targets: &mut Matrix<f64>)
+                  -> Result<bool, CsvError> {
+        let mut target_data = mem::replace(targets, Matrix::new(0, 0, vec![])).into_vec();
+        let rows = self.read_batch(inputs, &mut target_data)?;
+
+        let target_cols = if self.options.target.is_some() { 1 } else { 0 };
+        *targets = Matrix::new(rows, target_cols, target_data);
+
+        Ok(rows > 0)
+    }
+}
+
+impl<R: BufRead + Seek> DataSource<Vector<f64>> for CsvSource<R, Vector<f64>> {
+    type Error = CsvError;
+
+    fn reset(&mut self) -> Result<(), CsvError> {
+        self.rewind()
+    }
+
+    fn next_batch(&mut self,
+                  inputs: &mut Matrix<f64>,
+                  targets: &mut Vector<f64>)
+                  -> Result<bool, CsvError> {
+        let mut target_data = mem::replace(targets, Vector::new(vec![])).into_vec();
+        let rows = self.read_batch(inputs, &mut target_data)?;
+        *targets = Vector::new(target_data);
+
+        Ok(rows > 0)
+    }
+}
+
+/// The input columns to read, checking that the selected columns exist.
+fn select_columns(options: &CsvOptions, n_fields: usize) -> Result<Vec<usize>, CsvError> {
+    let columns: Vec<usize> = match options.columns {
+        Some(ref c) => c.clone(),
+        None => (0..n_fields).filter(|&c| Some(c) != options.target).collect(),
+    };
+
+    for &c in columns.iter().chain(options.target.iter()) {
+        if c >= n_fields {
+            return Err(CsvError::MissingColumn(c));
+        }
+    }
+
+    Ok(columns)
+}
+
+/// Parses the selected fields of a line onto the inputs and targets.
+fn parse_line(fields: &[String],
+              line: usize,
+              n_fields: usize,
+              columns: &[usize],
+              target: Option<usize>,
+              inputs: &mut Vec<f64>,
+              targets: &mut Vec<f64>)
+              -> Result<(), CsvError> {
+    if fields.len() != n_fields {
+        return Err(CsvError::FieldCount {
+            line: line,
+            expected: n_fields,
+            found: fields.len(),
+        });
+    }
+
+    for &c in columns {
+        inputs.push(parse_field(fields, line, c)?);
+    }
+
+    if let Some(t) = target {
+        targets.push(parse_field(fields, line, t)?);
+    }
+
+    Ok(())
+}
+
+fn parse_field(fields: &[String], line: usize, column: usize) -> Result<f64, CsvError> {
+    let field = fields[column].trim();
+    field.parse().map_err(|_| {
+        CsvError::Parse {
+            line: line,
+            column: column,
+            value: field.to_string(),
+        }
+    })
+}
+
+/// Splits a line into fields, handling double quoted fields.
+fn split_line(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    // An escaped quote.
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(c);
+            }
+        } else if c == '"' {
+            in_quotes = true;
+        } else if c == delimiter {
+            fields.push(field);
+            field = String::new();
+        } else {
+            field.push(c);
+        }
+    }
+
+    fields.push(field);
+    fields
+}
@@ -0,0 +1,274 @@
+//! Datasets
+//!
+//! Bundles inputs with their targets so that they are always
+//! shuffled, batched and split together.
+//!
+//! A `Dataset` keeps an ordering of its rows. Shuffling only
+//! changes this ordering, and the rows are copied out in order
+//! when batches or splits are taken.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::data::dataset::Dataset;
+//! use rusty_machine::linalg::matrix::Matrix;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! let inputs = Matrix::new(5, 1, vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+//! let targets = Vector::new(vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+//!
+//! let mut data = Dataset::new(inputs, targets);
+//! data.shuffle(42);
+//!
+//! for (batch_inputs, batch_targets) in data.batches(2) {
+//!     // The rows stay paired with their targets.
+//!     assert_eq!(batch_inputs.data(), batch_targets.data());
+//! }
+//! ```
+
+use std::vec::Vec;
+
+#[cfg(not(feature = "std"))]
+use libnum::Float;
+
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+#[cfg(feature = "rand")]
+use data::split::shuffled_indices;
+
+/// Data made up of rows which can be selected.
+pub trait RowData: Sized {
+    /// The number of rows.
+    fn n_rows(&self) -> usize;
+
+    /// Creates new data from the given rows.
+    fn select_rows(&self, rows: &[usize]) -> Self;
+
+    /// Copies the given rows into existing data, reusing its allocation.
+    fn select_rows_into(&self, rows: &[usize], out: &mut Self);
+
+    /// Data with no rows, used as a buffer for `select_rows_into`.
+    fn empty() -> Self;
+}
+
+impl<T: Copy> RowData for Matrix<T> {
+    fn n_rows(&self) -> usize {
+        self.rows()
+    }
+
+    fn select_rows(&self, rows: &[usize]) -> Matrix<T> {
+        Matrix::select_rows(self, rows)
+    }
+
+    fn select_rows_into(&self, rows: &[usize], out: &mut Matrix<T>) {
+        Matrix::select_rows_into(self, rows, out)
+    }
+
+    fn empty() -> Matrix<T> {
+        Matrix::new(0, 0, Vec::new())
+    }
+}
+
+impl<T: Copy> RowData for Vector<T> {
+    fn n_rows(&self) -> usize {
+        self.size()
+    }
+
+    fn select_rows(&self, rows: &[usize]) -> Vector<T> {
+        self.select(rows)
+    }
+
+    fn select_rows_into(&self, rows: &[usize], out: &mut Vector<T>) {
+        self.select_into(rows, out)
+    }
+
+    fn empty() -> Vector<T> {
+        Vector::new(Vec::new())
+    }
+}
+
+/// A set of inputs and their targets.
+pub struct Dataset<I, T> {
+    inputs: I,
+    targets: T,
+    order: Vec<usize>,
+}
+
+impl<I: RowData, T: RowData> Dataset<I, T> {
+    /// Constructs a dataset from inputs and targets.
+    ///
+    /// # Panics
+    ///
+    /// - The inputs and targets have a different number of rows.
+    pub fn new(inputs: I, targets: T) -> Dataset<I, T> {
+        assert!(inputs.n_rows() == targets.n_rows(),
+                "Inputs and targets have different lengths.");
+
+        let order = (0..inputs.n_rows()).collect();
+
+        Dataset {
+            inputs: inputs,
+            targets: targets,
+            order: order,
+        }
+    }
+
+    /// The number of rows in the dataset.
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Returns true if the dataset has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// The inputs in their original order.
+    pub fn inputs(&self) -> &I {
+        &self.inputs
+    }
+
+    /// The targets in their original order.
+    pub fn targets(&self) -> &T {
+        &self.targets
+    }
+
+    /// The current ordering of the rows.
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Randomly reorders the rows.
+    ///
+    /// The same seed always gives the same order.
+    ///
+    /// Requires the `rand` feature.
+    #[cfg(feature = "rand")]
+    pub fn shuffle(&mut self, seed: u64) {
+        let perm = shuffled_indices(self.order.len(), seed);
+        self.order = perm.into_iter().map(|i| self.order[i]).collect();
+    }
+
+    /// Iterates over batches of rows in the current order.
+    ///
+    /// The final batch holds the remaining rows and may be smaller.
+    ///
+    /// # Panics
+    ///
+    /// - The batch size is zero.
+    pub fn batches(&self, size: usize) -> Batches<'_, I, T> {
+        assert!(size > 0, "Batch size must be greater than zero.");
+
+        Batches {
+            dataset: self,
+            size: size,
+            pos: 0,
+        }
+    }
+
+    /// Splits the dataset in its current order.
+    ///
+    /// The first dataset holds a `fraction` proportion of the
+    /// rows, rounded to the nearest row, and the second holds
+    /// the rest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::data::dataset::Dataset;
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::vector::Vector;
+    ///
+    /// let data = Dataset::new(Matrix::new(4, 1, vec![1.0, 2.0, 3.0, 4.0]),
+    ///                         Vector::new(vec![0.0, 0.0, 1.0, 1.0]));
+    ///
+    /// let (train, test) = data.split(0.75);
+    ///
+    /// assert_eq!(train.len(), 3);
+    /// assert_eq!(*test.inputs().data(), vec![4.0]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The fraction is not between 0 and 1.
+    pub fn split(&self, fraction: f64) -> (Dataset<I, T>, Dataset<I, T>) {
+        assert!((0f64..=1f64).contains(&fraction),
+                "Fraction must be between 0 and 1.");
+
+        let n = (self.order.len() as f64 * fraction).round() as usize;
+        let (first, second) = self.order.split_at(n);
+
+        (self.select(first), self.select(second))
+    }
+
+    fn select(&self, rows: &[usize]) -> Dataset<I, T> {
+        Dataset::new(self.inputs.select_rows(rows), self.targets.select_rows(rows))
+    }
+}
+
+/// Iterator over the batches of a dataset.
+pub struct Batches<'a, I: 'a, T: 'a> {
+    dataset: &'a Dataset<I, T>,
+    size: usize,
+    pos: usize,
+}
+
+impl<'a, I: RowData, T: RowData> Batches<'a, I, T> {
+    /// Copies the next batch into existing buffers.
+    ///
+    /// Returns false when there are no batches left. This avoids
+    /// allocating new data for every batch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::data::dataset::{Dataset, RowData};
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::vector::Vector;
+    ///
+    /// let data = Dataset::new(Matrix::new(3, 1, vec![1.0, 2.0, 3.0]),
+    ///                         Vector::new(vec![1.0, 2.0, 3.0]));
+    ///
+    /// let mut inputs = Matrix::empty();
+    /// let mut targets = Vector::empty();
+    /// let mut batches = data.batches(2);
+    ///
+    /// assert!(batches.next_into(&mut inputs, &mut targets));
+    /// assert_eq!(inputs.rows(), 2);
+    /// assert!(batches.next_into(&mut inputs, &mut targets));
+    /// assert_eq!(*targets.data(), vec![3.0]);
+    /// assert!(!batches.next_into(&mut inputs, &mut targets));
+    /// ```
+    pub fn next_into(&mut self, inputs: &mut I, targets: &mut T) -> bool {
+        match self.next_range() {
+            Some((start, end)) => {
+                let rows = &self.dataset.order[start..end];
+                self.dataset.inputs.select_rows_into(rows, inputs);
+                self.dataset.targets.select_rows_into(rows, targets);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn next_range(&mut self) -> Option<(usize, usize)> {
+        if self.pos >= self.dataset.len() {
+            return None;
+        }
+
+        let start = self.pos;
+        self.pos = (start + self.size).min(self.dataset.len());
+        Some((start, self.pos))
+    }
+}
+
+impl<'a, I: RowData, T: RowData> Iterator for Batches<'a, I, T> {
+    type Item = (I, T);
+
+    fn next(&mut self) -> Option<(I, T)> {
+        self.next_range().map(|(start, end)| {
+            let rows = &self.dataset.order[start..end];
+            (self.dataset.inputs.select_rows(rows), self.dataset.targets.select_rows(rows))
+        })
+    }
+}
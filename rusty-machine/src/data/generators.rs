@@ -0,0 +1,202 @@
+//! Synthetic data generators
+//!
+//! Generates simple data sets for testing and benchmarking models.
+//!
+//! Every generator takes a seed for the random number generator so
+//! that the data sets are reproducible.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::data::generators::make_moons;
+//! use rusty_machine::learning::k_means::KMeansClassifier;
+//! use rusty_machine::learning::UnSupModel;
+//!
+//! let (inputs, labels) = make_moons(100, 0.05, 1);
+//!
+//! assert_eq!(inputs.rows(), 100);
+//! assert_eq!(labels.size(), 100);
+//!
+//! let mut model = KMeansClassifier::new(2);
+//! model.train(&inputs);
+//! ```
+
+use std::f64::consts::PI;
+
+use learning::toolkit::rand_utils::{seeded_rng, standard_normal};
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+
+use rand::Rng;
+
+/// Generates isotropic gaussian blobs around the given centers.
+///
+/// Each row of `centers` is the center of a blob. The samples are
+/// shared between the blobs as evenly as possible, and the label of
+/// each sample is the index of its blob.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::data::generators::make_blobs;
+/// use rusty_machine::linalg::matrix::Matrix;
+///
+/// let centers = Matrix::new(2, 2, vec![0.0, 0.0, 10.0, 10.0]);
+/// let (inputs, labels) = make_blobs(10, &centers, 0.5, 3);
+///
+/// assert_eq!(inputs.cols(), 2);
+/// assert_eq!(labels.data().iter().filter(|&&l| l == 1.0).count(), 5);
+/// ```
+///
+/// # Panics
+///
+/// - There are no centers.
+/// - The standard deviation is negative.
+pub fn make_blobs(n_samples: usize,
+                  centers: &Matrix<f64>,
+                  std_dev: f64,
+                  seed: u64)
+                  -> (Matrix<f64>, Vector<f64>) {
+    assert!(centers.rows() > 0, "There must be at least one center.");
+    assert!(std_dev >= 0f64, "Standard deviation must be non-negative.");
+
+    let mut rng = seeded_rng(seed);
+    let cols = centers.cols();
+    let mut data = Vec::with_capacity(n_samples * cols);
+    let mut labels = Vec::with_capacity(n_samples);
+
+    for i in 0..n_samples {
+        let c = i % centers.rows();
+        for x in &centers.data()[c * cols..(c + 1) * cols] {
+            data.push(x + std_dev * standard_normal(&mut rng));
+        }
+        labels.push(c as f64);
+    }
+
+    (Matrix::new(n_samples, cols, data), Vector::new(labels))
+}
+
+/// Generates two interleaving half circles.
+///
+/// Half of the samples lie on each moon, labelled 0 and 1. Gaussian
+/// noise with standard deviation `noise` is added to each point.
+///
+/// # Panics
+///
+/// - The noise is negative.
+pub fn make_moons(n_samples: usize, noise: f64, seed: u64) -> (Matrix<f64>, Vector<f64>) {
+    assert!(noise >= 0f64, "Noise must be non-negative.");
+
+    let mut rng = seeded_rng(seed);
+    let n_outer = n_samples - n_samples / 2;
+    let mut data = Vec::with_capacity(n_samples * 2);
+    let mut labels = Vec::with_capacity(n_samples);
+
+    for i in 0..n_samples {
+        let (x, y, label) = if i < n_outer {
+            let t = PI * i as f64 / (n_outer.max(2) - 1) as f64;
+            (t.cos(), t.sin(), 0f64)
+        } else {
+            let t = PI * (i - n_outer) as f64 / ((n_samples - n_outer).max(2) - 1) as f64;
+            (1f64 - t.cos(), 0.5 - t.sin(), 1f64)
+        };
+
+        data.push(x + noise * standard_normal(&mut rng));
+        data.push(y + noise * standard_normal(&mut rng));
+        labels.push(label);
+    }
+
+    (Matrix::new(n_samples, 2, data), Vector::new(labels))
+}
+
+/// Generates a small circle inside a larger circle.
+///
+/// The outer circle has radius 1 and label 0, the inner circle has
+/// radius `factor` and label 1. Gaussian noise with standard deviation
+/// `noise` is added to each point.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::data::generators::make_circles;
+///
+/// let (inputs, labels) = make_circles(8, 0.5, 0.0, 1);
+///
+/// // The first point lies on the outer circle.
+/// assert!((inputs[[0, 0]] - 1.0).abs() < 1e-12);
+/// assert_eq!(labels[0], 0.0);
+/// ```
+///
+/// # Panics
+///
+/// - The factor is not between 0 and 1.
+/// - The noise is negative.
+pub fn make_circles(n_samples: usize,
+                    factor: f64,
+                    noise: f64,
+                    seed: u64)
+                    -> (Matrix<f64>, Vector<f64>) {
+    assert!(factor > 0f64 && factor < 1f64, "Factor must be between 0 and 1.");
+    assert!(noise >= 0f64, "Noise must be non-negative.");
+
+    let mut rng = seeded_rng(seed);
+    let n_outer = n_samples - n_samples / 2;
+    let mut data = Vec::with_capacity(n_samples * 2);
+    let mut labels = Vec::with_capacity(n_samples);
+
+    for i in 0..n_samples {
+        let (t, radius, label) = if i < n_outer {
+            (2f64 * PI * i as f64 / n_outer as f64, 1f64, 0f64)
+        } else {
+            let n_inner = n_samples - n_outer;
+            (2f64 * PI * (i - n_outer) as f64 / n_inner as f64, factor, 1f64)
+        };
+
+        data.push(radius * t.cos() + noise * standard_normal(&mut rng));
+        data.push(radius * t.sin() + noise * standard_normal(&mut rng));
+        labels.push(label);
+    }
+
+    (Matrix::new(n_samples, 2, data), Vector::new(labels))
+}
+
+/// Generates a random linear regression problem.
+///
+/// The inputs are drawn from a standard normal distribution and the
+/// targets are a random linear combination of the inputs plus gaussian
+/// noise. Returns `(inputs, targets, coefficients)`.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::data::generators::make_regression;
+///
+/// let (inputs, targets, coefs) = make_regression(20, 3, 0.0, 5);
+///
+/// let outputs = &inputs * &coefs;
+/// assert!((outputs[0] - targets[0]).abs() < 1e-12);
+/// ```
+///
+/// # Panics
+///
+/// - The noise is negative.
+pub fn make_regression(n_samples: usize,
+                       n_features: usize,
+                       noise: f64,
+                       seed: u64)
+                       -> (Matrix<f64>, Vector<f64>, Vector<f64>) {
+    assert!(noise >= 0f64, "Noise must be non-negative.");
+
+    let mut rng = seeded_rng(seed);
+
+    let coefs: Vector<f64> = (0..n_features).map(|_| 100f64 * rng.gen::<f64>()).collect();
+    let inputs = Matrix::rand_normal(n_samples, n_features, 0f64, 1f64, &mut rng);
+
+    let mut targets = &inputs * &coefs;
+    for t in targets.mut_data() {
+        *t += noise * standard_normal(&mut rng);
+    }
+
+    (inputs, targets, coefs)
+}
+
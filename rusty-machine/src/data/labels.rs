@@ -0,0 +1,175 @@
+//! Label utilities
+//!
+//! Converts class labels to and from the forms used by the models.
+//!
+//! Multi-class neural networks are trained on one-hot encoded targets,
+//! with a column for each class, and predict a row of class scores.
+//! The `one_hot` and `argmax_decode` functions convert between class
+//! indices and these matrices. The `LabelEncoder` maps labels of any
+//! comparable type, such as strings, to class indices.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::data::labels::{argmax_decode, one_hot, LabelEncoder};
+//! use rusty_machine::linalg::matrix::BaseMatrix;
+//!
+//! let labels = vec!["cat", "dog", "bird", "dog"];
+//!
+//! let mut encoder = LabelEncoder::default();
+//! let classes = encoder.fit_transform(&labels);
+//!
+//! // The classes are sorted: [bird, cat, dog].
+//! assert_eq!(*classes.data(), vec![1.0, 2.0, 0.0, 2.0]);
+//!
+//! let targets = one_hot(&classes, 3);
+//! assert_eq!(targets.row(0), &[0.0, 1.0, 0.0]);
+//!
+//! // Network outputs would be decoded in the same way.
+//! let decoded = argmax_decode(&targets);
+//! assert_eq!(encoder.inverse_transform(&decoded), labels);
+//! ```
+
+use std::cmp::Ordering;
+use std::vec::Vec;
+
+use linalg::matrix::{BaseMatrix, Matrix};
+use linalg::utils;
+use linalg::vector::Vector;
+
+#[cfg(not(feature = "std"))]
+use libnum::Float;
+
+/// One-hot encodes class indices.
+///
+/// Each label is the index of a class, and is encoded as a row with a
+/// one in the column of the class and zeros elsewhere.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::data::labels::one_hot;
+/// use rusty_machine::linalg::vector::Vector;
+///
+/// let targets = one_hot(&Vector::new(vec![2.0, 0.0]), 3);
+///
+/// assert_eq!(*targets.data(), vec![0.0, 0.0, 1.0, 1.0, 0.0, 0.0]);
+/// ```
+///
+/// # Panics
+///
+/// - A label is not an integer less than the number of classes.
+pub fn one_hot(labels: &Vector<f64>, n_classes: usize) -> Matrix<f64> {
+    let mut data = vec![0f64; labels.size() * n_classes];
+
+    for (i, &label) in labels.data().iter().enumerate() {
+        assert!(label >= 0f64 && label.fract() == 0f64 && (label as usize) < n_classes,
+                "Labels must be class indices less than the number of classes.");
+        data[i * n_classes + label as usize] = 1f64;
+    }
+
+    Matrix::new(labels.size(), n_classes, data)
+}
+
+/// Decodes each row of scores to the index of its largest column.
+///
+/// Ties are broken by the first largest column.
+///
+/// # Panics
+///
+/// - The matrix has no columns.
+pub fn argmax_decode(outputs: &Matrix<f64>) -> Vector<f64> {
+    assert!(outputs.cols() > 0, "Outputs must have at least one column.");
+
+    (0..outputs.rows()).map(|i| utils::argmax(outputs.row(i)).0 as f64).collect()
+}
+
+/// The Label Encoder
+///
+/// Maps labels of any comparable type to class indices. The classes
+/// are learned by `fit` and stored in sorted order.
+#[derive(Clone, Debug)]
+pub struct LabelEncoder<T> {
+    classes: Option<Vec<T>>,
+}
+
+impl<T> Default for LabelEncoder<T> {
+    fn default() -> LabelEncoder<T> {
+        LabelEncoder { classes: None }
+    }
+}
+
+impl<T> LabelEncoder<T> {
+    /// The sorted classes learned by `fit`.
+    ///
+    /// Returns None if the encoder has not been fitted.
+    pub fn classes(&self) -> Option<&[T]> {
+        self.classes.as_ref().map(|c| &c[..])
+    }
+
+    fn fitted_classes(&self) -> &[T] {
+        match self.classes {
+            Some(ref c) => c,
+            None => panic!("Encoder has not been fitted."),
+        }
+    }
+}
+
+impl<T: Clone + PartialOrd> LabelEncoder<T> {
+    /// Learns the classes of the labels.
+    ///
+    /// # Panics
+    ///
+    /// - Two of the labels cannot be compared.
+    pub fn fit(&mut self, labels: &[T]) {
+        let mut classes = labels.to_vec();
+        classes.sort_by(|a, b| a.partial_cmp(b).expect("Labels must be comparable."));
+        classes.dedup_by(|a, b| a == b);
+
+        self.classes = Some(classes);
+    }
+
+    /// Maps each label to the index of its class.
+    ///
+    /// # Panics
+    ///
+    /// - The encoder has not been fitted.
+    /// - A label is not one of the fitted classes.
+    pub fn transform(&self, labels: &[T]) -> Vector<f64> {
+        let classes = self.fitted_classes();
+
+        labels.iter()
+            .map(|x| {
+                match classes.binary_search_by(|c| c.partial_cmp(x).unwrap_or(Ordering::Less)) {
+                    Ok(idx) => idx as f64,
+                    Err(_) => panic!("Unknown label found."),
+                }
+            })
+            .collect()
+    }
+
+    /// Fits the encoder to the labels and encodes them.
+    pub fn fit_transform(&mut self, labels: &[T]) -> Vector<f64> {
+        self.fit(labels);
+        self.transform(labels)
+    }
+
+    /// Maps class indices back to their labels.
+    ///
+    /// # Panics
+    ///
+    /// - The encoder has not been fitted.
+    /// - A value is not the index of a class.
+    pub fn inverse_transform(&self, indices: &Vector<f64>) -> Vec<T> {
+        let classes = self.fitted_classes();
+
+        indices.data()
+            .iter()
+            .map(|&x| {
+                assert!(x >= 0f64 && x.fract() == 0f64 && (x as usize) < classes.len(),
+                        "Value is not the index of a class.");
+                classes[x as usize].clone()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,247 @@
+//! libsvm format
+//!
+//! Reads and writes the sparse text format used by libsvm and
+//! svmlight. Each line holds a target followed by `index:value` pairs
+//! for the non-zero features, with indices starting from 1:
+//!
+//! ```text
+//! 1 1:0.5 3:2.0
+//! -1 2:1.5
+//! ```
+//!
+//! The crate does not have a sparse matrix type, so the features are
+//! read into a dense `Matrix` with missing features set to zero.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::data::libsvm::{read_libsvm, write_libsvm};
+//!
+//! let text = "1 1:0.5 3:2\n-1 2:1.5\n";
+//! let (inputs, targets) = read_libsvm(text.as_bytes(), None).unwrap();
+//!
+//! assert_eq!(*inputs.data(), vec![0.5, 0.0, 2.0, 0.0, 1.5, 0.0]);
+//! assert_eq!(*targets.data(), vec![1.0, -1.0]);
+//!
+//! let mut out = Vec::new();
+//! write_libsvm(&mut out, &inputs, &targets).unwrap();
+//!
+//! assert_eq!(String::from_utf8(out).unwrap(), text);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+#[cfg(feature = "io")]
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read, Write};
+#[cfg(feature = "io")]
+use std::io::BufWriter;
+#[cfg(feature = "io")]
+use std::path::Path;
+
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+
+/// Errors from reading libsvm files.
+#[derive(Debug)]
+pub enum LibSvmError {
+    /// An error reading the file.
+    Io(io::Error),
+    /// A token could not be parsed.
+    Parse {
+        /// The line number, starting from 1.
+        line: usize,
+        /// The token which failed to parse.
+        token: String,
+    },
+    /// A feature index is zero or exceeds the number of features.
+    InvalidIndex {
+        /// The line number, starting from 1.
+        line: usize,
+        /// The feature index.
+        index: usize,
+    },
+}
+
+impl fmt::Display for LibSvmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LibSvmError::Io(ref e) => write!(f, "IO error: {}", e),
+            LibSvmError::Parse { line, ref token } => {
+                write!(f, "Could not parse '{}' on line {}.", token, line)
+            }
+            LibSvmError::InvalidIndex { line, index } => {
+                write!(f, "Invalid feature index {} on line {}.", index, line)
+            }
+        }
+    }
+}
+
+impl Error for LibSvmError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            LibSvmError::Io(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for LibSvmError {
+    fn from(e: io::Error) -> LibSvmError {
+        LibSvmError::Io(e)
+    }
+}
+
+/// Loads a libsvm file.
+///
+/// See `read_libsvm` for details.
+///
+/// Requires the `io` feature.
+#[cfg(feature = "io")]
+pub fn load_libsvm<P: AsRef<Path>>(path: P,
+                                   n_features: Option<usize>)
+                                   -> Result<(Matrix<f64>, Vector<f64>), LibSvmError> {
+    let file = File::open(path)?;
+    read_libsvm(file, n_features)
+}
+
+/// Reads libsvm formatted data into inputs and targets.
+///
+/// The number of features is the largest index found, unless
+/// `n_features` is given. Blank lines, comments starting with `#`
+/// and `qid:` tokens are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::data::libsvm::read_libsvm;
+///
+/// let text = "0 2:1 # a comment\n1 qid:3 1:4\n";
+/// let (inputs, targets) = read_libsvm(text.as_bytes(), Some(3)).unwrap();
+///
+/// assert_eq!(inputs.cols(), 3);
+/// assert_eq!(*inputs.data(), vec![0.0, 1.0, 0.0, 4.0, 0.0, 0.0]);
+/// ```
+pub fn read_libsvm<R: Read>(reader: R,
+                            n_features: Option<usize>)
+                            -> Result<(Matrix<f64>, Vector<f64>), LibSvmError> {
+    let mut targets = Vec::new();
+    let mut rows = Vec::new();
+    let mut max_index = 0;
+
+    for (i, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line?;
+        let line_num = i + 1;
+        let content = line.split('#').next().unwrap_or("");
+
+        let mut tokens = content.split_whitespace();
+        let target = match tokens.next() {
+            Some(t) => parse_token(t, line_num)?,
+            None => continue,
+        };
+
+        let mut features = Vec::new();
+        for token in tokens {
+            if token.starts_with("qid:") {
+                continue;
+            }
+
+            let mut parts = token.splitn(2, ':');
+            let (idx, value) = match (parts.next(), parts.next()) {
+                (Some(idx), Some(value)) => (idx, value),
+                _ => {
+                    return Err(LibSvmError::Parse {
+                        line: line_num,
+                        token: token.to_string(),
+                    })
+                }
+            };
+
+            let index: usize = idx.parse().map_err(|_| {
+                LibSvmError::Parse {
+                    line: line_num,
+                    token: token.to_string(),
+                }
+            })?;
+
+            if index == 0 || n_features.is_some_and(|n| index > n) {
+                return Err(LibSvmError::InvalidIndex {
+                    line: line_num,
+                    index: index,
+                });
+            }
+
+            max_index = max_index.max(index);
+            features.push((index - 1, parse_token(value, line_num)?));
+        }
+
+        targets.push(target);
+        rows.push(features);
+    }
+
+    let cols = n_features.unwrap_or(max_index);
+    let mut data = vec![0f64; rows.len() * cols];
+
+    for (i, row) in rows.iter().enumerate() {
+        for &(j, x) in row {
+            data[i * cols + j] = x;
+        }
+    }
+
+    Ok((Matrix::new(rows.len(), cols, data), Vector::new(targets)))
+}
+
+/// Saves inputs and targets to a libsvm file.
+///
+/// See `write_libsvm` for details.
+///
+/// Requires the `io` feature.
+#[cfg(feature = "io")]
+pub fn save_libsvm<P: AsRef<Path>>(path: P,
+                                   inputs: &Matrix<f64>,
+                                   targets: &Vector<f64>)
+                                   -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_libsvm(&mut writer, inputs, targets)?;
+    writer.flush()
+}
+
+/// Writes inputs and targets in the libsvm format.
+///
+/// Only the non-zero features are written.
+///
+/// # Panics
+///
+/// - The number of targets does not match the number of input rows.
+pub fn write_libsvm<W: Write>(writer: &mut W,
+                              inputs: &Matrix<f64>,
+                              targets: &Vector<f64>)
+                              -> io::Result<()> {
+    assert!(inputs.rows() == targets.size(),
+            "Inputs and targets have different lengths.");
+
+    let cols = inputs.cols();
+
+    for (i, t) in targets.data().iter().enumerate() {
+        write!(writer, "{}", t)?;
+
+        for (j, x) in inputs.data()[i * cols..(i + 1) * cols].iter().enumerate() {
+            if *x != 0f64 {
+                write!(writer, " {}:{}", j + 1, x)?;
+            }
+        }
+
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+fn parse_token(token: &str, line: usize) -> Result<f64, LibSvmError> {
+    token.parse().map_err(|_| {
+        LibSvmError::Parse {
+            line: line,
+            token: token.to_string(),
+        }
+    })
+}
@@ -0,0 +1,692 @@
+//! NumPy files
+//!
+//! Reads and writes the `.npy` binary format used by NumPy to store a
+//! single array, and `.npz` archives which bundle several arrays, such
+//! as inputs and targets, in one file.
+//!
+//! Arrays of floating point, integer or boolean values are read and
+//! converted to `f64`. One dimensional arrays are read as a `Vector`,
+//! or as a single column `Matrix`. Arrays are always written as
+//! little-endian doubles in row-major order.
+//!
+//! Archives created by `numpy.savez` are supported. Compressed
+//! archives from `numpy.savez_compressed` are not.
+//!
+//! `NpyChunks` reads a data set split over several files a chunk at a
+//! time, for training on data which does not fit in memory.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::data::npy::{read_npy, write_npy};
+//! use rusty_machine::linalg::matrix::Matrix;
+//!
+//! let mat = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+//!
+//! let mut bytes = Vec::new();
+//! write_npy(&mut bytes, &mat).unwrap();
+//!
+//! let loaded: Matrix<f64> = read_npy(&bytes[..]).unwrap();
+//! assert_eq!(loaded.rows(), 2);
+//! assert_eq!(*loaded.data(), *mat.data());
+//! ```
+
+use std::error::Error;
+use std::fmt;
+#[cfg(feature = "io")]
+use std::fs::File;
+use std::io::{self, Read, Write};
+#[cfg(feature = "io")]
+use std::io::BufWriter;
+#[cfg(feature = "io")]
+use std::mem;
+#[cfg(feature = "io")]
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "io")]
+use data::dataset::RowData;
+#[cfg(feature = "io")]
+use data::source::DataSource;
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+/// Errors from reading NumPy files.
+#[derive(Debug)]
+pub enum NpyError {
+    /// An error reading or writing the file.
+    Io(io::Error),
+    /// The file is not a valid `.npy` file or `.npz` archive.
+    Format(String),
+    /// The array has a data type which cannot be read.
+    UnsupportedDtype(String),
+    /// The array shape does not fit the requested type.
+    Shape(Vec<usize>),
+    /// An archive entry is compressed.
+    Compressed(String),
+    /// The archive has no array with the given name.
+    MissingArray(String),
+}
+
+impl fmt::Display for NpyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            NpyError::Io(ref e) => write!(f, "IO error: {}", e),
+            NpyError::Format(ref msg) => write!(f, "Invalid NumPy file: {}", msg),
+            NpyError::UnsupportedDtype(ref d) => write!(f, "Unsupported data type '{}'.", d),
+            NpyError::Shape(ref s) => write!(f, "Cannot read an array of shape {:?}.", s),
+            NpyError::Compressed(ref name) => {
+                write!(f, "Archive entry '{}' is compressed.", name)
+            }
+            NpyError::MissingArray(ref name) => write!(f, "No array named '{}'.", name),
+        }
+    }
+}
+
+impl Error for NpyError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            NpyError::Io(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for NpyError {
+    fn from(e: io::Error) -> NpyError {
+        NpyError::Io(e)
+    }
+}
+
+fn format_error<T>(msg: &str) -> Result<T, NpyError> {
+    Err(NpyError::Format(msg.to_string()))
+}
+
+/// Types which can be stored as NumPy arrays.
+pub trait NpyArray {
+    /// The shape of the array.
+    fn npy_shape(&self) -> Vec<usize>;
+
+    /// The values of the array in row-major order.
+    fn npy_data(&self) -> &[f64];
+
+    /// Constructs the array from its shape and row-major values.
+    fn from_npy(shape: Vec<usize>, data: Vec<f64>) -> Result<Self, NpyError> where Self: Sized;
+}
+
+impl NpyArray for Matrix<f64> {
+    fn npy_shape(&self) -> Vec<usize> {
+        vec![self.rows(), self.cols()]
+    }
+
+    fn npy_data(&self) -> &[f64] {
+        self.data()
+    }
+
+    /// Two dimensional arrays keep their shape and one dimensional
+    /// arrays become a single column.
+    fn from_npy(shape: Vec<usize>, data: Vec<f64>) -> Result<Matrix<f64>, NpyError> {
+        match shape.len() {
+            1 => Ok(Matrix::new(shape[0], 1, data)),
+            2 => Ok(Matrix::new(shape[0], shape[1], data)),
+            _ => Err(NpyError::Shape(shape)),
+        }
+    }
+}
+
+impl NpyArray for Vector<f64> {
+    fn npy_shape(&self) -> Vec<usize> {
+        vec![self.size()]
+    }
+
+    fn npy_data(&self) -> &[f64] {
+        self.data()
+    }
+
+    /// One dimensional arrays and two dimensional arrays with a single
+    /// row or column can be read.
+    fn from_npy(shape: Vec<usize>, data: Vec<f64>) -> Result<Vector<f64>, NpyError> {
+        match shape.len() {
+            1 => Ok(Vector::new(data)),
+            2 if shape[0] == 1 || shape[1] == 1 => Ok(Vector::new(data)),
+            _ => Err(NpyError::Shape(shape)),
+        }
+    }
+}
+
+/// Loads an array from a `.npy` file.
+///
+/// See `read_npy` for details.
+///
+/// Requires the `io` feature.
+#[cfg(feature = "io")]
+pub fn load_npy<T: NpyArray, P: AsRef<Path>>(path: P) -> Result<T, NpyError> {
+    read_npy(File::open(path)?)
+}
+
+/// Reads an array in the `.npy` format.
+///
+/// Fortran ordered arrays are converted to row-major order.
+pub fn read_npy<T: NpyArray, R: Read>(mut reader: R) -> Result<T, NpyError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+
+    let (shape, data) = parse_npy(&bytes)?;
+    T::from_npy(shape, data)
+}
+
+/// Saves an array to a `.npy` file.
+///
+/// Requires the `io` feature.
+#[cfg(feature = "io")]
+pub fn save_npy<T: NpyArray, P: AsRef<Path>>(path: P, array: &T) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_npy(&mut writer, array)?;
+    writer.flush()
+}
+
+/// Writes an array in the `.npy` format.
+pub fn write_npy<T: NpyArray + ?Sized, W: Write>(writer: &mut W, array: &T) -> io::Result<()> {
+    let shape = array.npy_shape();
+    let shape_str = if shape.len() == 1 {
+        format!("({},)", shape[0])
+    } else {
+        format!("({})",
+                shape.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(", "))
+    };
+
+    let mut header = format!("{{'descr': '<f8', 'fortran_order': False, 'shape': {}, }}",
+                             shape_str);
+    // The header is padded with spaces so that the data is aligned to
+    // 64 bytes, and ends with a newline.
+    let unpadded = MAGIC.len() + 4 + header.len() + 1;
+    header.push_str(&" ".repeat((64 - unpadded % 64) % 64));
+    header.push('\n');
+
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[1, 0])?;
+    writer.write_all(&(header.len() as u16).to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+
+    for x in array.npy_data() {
+        writer.write_all(&x.to_le_bytes())?;
+    }
+
+    Ok(())
+}
+
+/// Parses a `.npy` file into its shape and row-major values.
+fn parse_npy(bytes: &[u8]) -> Result<(Vec<usize>, Vec<f64>), NpyError> {
+    if bytes.len() < 10 || &bytes[..6] != MAGIC {
+        return format_error("Missing magic string.");
+    }
+
+    let (header_len, header_start) = match bytes[6] {
+        1 => (u16::from_le_bytes([bytes[8], bytes[9]]) as usize, 10),
+        2 | 3 if bytes.len() >= 12 => {
+            (u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]) as usize, 12)
+        }
+        _ => return format_error("Unsupported format version."),
+    };
+
+    if bytes.len() < header_start + header_len {
+        return format_error("Truncated header.");
+    }
+
+    let header = match ::std::str::from_utf8(&bytes[header_start..header_start + header_len]) {
+        Ok(h) => h,
+        Err(_) => return format_error("Header is not valid text."),
+    };
+
+    let descr = header_value(header, "descr")?;
+    let descr = descr.trim_matches(|c| c == '\'' || c == '"');
+    let fortran_order = match header_value(header, "fortran_order")? {
+        "True" => true,
+        "False" => false,
+        _ => return format_error("Invalid fortran_order."),
+    };
+    let shape = parse_shape(header_value(header, "shape")?)?;
+
+    let count = shape.iter().product::<usize>();
+    let data = parse_data(descr, &bytes[header_start + header_len..], count)?;
+
+    if fortran_order && shape.len() == 2 {
+        let (rows, cols) = (shape[0], shape[1]);
+        let transposed = (0..rows * cols).map(|i| data[(i % cols) * rows + i / cols]).collect();
+        return Ok((shape, transposed));
+    } else if fortran_order && shape.len() > 2 {
+        return Err(NpyError::Shape(shape));
+    }
+
+    Ok((shape, data))
+}
+
+/// Finds the value of a key in the header dictionary.
+fn header_value<'a>(header: &'a str, key: &str) -> Result<&'a str, NpyError> {
+    let start = match header.find(&format!("'{}'", key)) {
+        Some(i) => i + key.len() + 2,
+        None => return format_error(&format!("Missing '{}' in header.", key)),
+    };
+
+    let rest = header[start..].trim_start();
+    if !rest.starts_with(':') {
+        return format_error("Invalid header.");
+    }
+    let rest = rest[1..].trim_start();
+
+    // The shape is a tuple, the other values do not contain commas.
+    let end = if rest.starts_with('(') {
+        rest.find(')').map(|i| i + 1)
+    } else {
+        rest.find(&[',', '}'][..])
+    };
+
+    match end {
+        Some(end) => Ok(rest[..end].trim()),
+        None => format_error("Invalid header."),
+    }
+}
+
+fn parse_shape(shape: &str) -> Result<Vec<usize>, NpyError> {
+    shape.trim_matches(|c| c == '(' || c == ')')
+         .split(',')
+         .map(|d| d.trim())
+         .filter(|d| !d.is_empty())
+         .map(|d| d.parse().map_err(|_| NpyError::Format(format!("Invalid shape {}.", shape))))
+         .collect()
+}
+
+/// Converts the raw array data to doubles.
+fn parse_data(descr: &str, bytes: &[u8], count: usize) -> Result<Vec<f64>, NpyError> {
+    let unsupported = || NpyError::UnsupportedDtype(descr.to_string());
+
+    if descr.len() < 3 {
+        return Err(unsupported());
+    }
+    let big_endian = match &descr[..1] {
+        "<" | "|" | "=" => false,
+        ">" => true,
+        _ => return Err(unsupported()),
+    };
+    let kind = &descr[1..2];
+    let size: usize = descr[2..].parse().map_err(|_| unsupported())?;
+
+    let convert: fn(&[u8]) -> f64 = match (kind, size) {
+        ("f", 4) => |b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+        ("f", 8) => |b| f64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]),
+        ("i", 1) => |b| b[0] as i8 as f64,
+        ("i", 2) => |b| i16::from_le_bytes([b[0], b[1]]) as f64,
+        ("i", 4) => |b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+        ("i", 8) => |b| i64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]) as f64,
+        ("u", 1) | ("b", 1) => |b| b[0] as f64,
+        ("u", 2) => |b| u16::from_le_bytes([b[0], b[1]]) as f64,
+        ("u", 4) => |b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64,
+        ("u", 8) => |b| u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]) as f64,
+        _ => return Err(unsupported()),
+    };
+
+    if bytes.len() < count * size {
+        return format_error("Truncated data.");
+    }
+
+    let mut buf = vec![0u8; size];
+    Ok(bytes[..count * size]
+           .chunks(size)
+           .map(|chunk| {
+               buf.copy_from_slice(chunk);
+               if big_endian {
+                   buf.reverse();
+               }
+               convert(&buf)
+           })
+           .collect())
+}
+
+/// The arrays of a `.npz` archive.
+#[derive(Clone, Debug)]
+pub struct NpzArchive {
+    arrays: Vec<(String, Vec<u8>)>,
+}
+
+impl NpzArchive {
+    /// The names of the arrays in the archive.
+    pub fn names(&self) -> Vec<&str> {
+        self.arrays.iter().map(|a| &a.0[..]).collect()
+    }
+
+    /// Reads the array with the given name.
+    ///
+    /// The name does not include the `.npy` extension.
+    pub fn array<T: NpyArray>(&self, name: &str) -> Result<T, NpyError> {
+        match self.arrays.iter().find(|a| a.0 == name) {
+            Some(a) => read_npy(&a.1[..]),
+            None => Err(NpyError::MissingArray(name.to_string())),
+        }
+    }
+
+    /// Reads a pair of input and target arrays.
+    pub fn inputs_targets(&self,
+                          inputs: &str,
+                          targets: &str)
+                          -> Result<(Matrix<f64>, Vector<f64>), NpyError> {
+        Ok((self.array(inputs)?, self.array(targets)?))
+    }
+}
+
+/// A `DataSource` which reads mini-batches from a sequence of chunks
+/// stored as `.npy` files.
+///
+/// Each chunk is a pair of files holding the inputs and targets of
+/// some of the rows. Only one chunk is loaded at a time, so data sets
+/// larger than memory can be split into chunks for training. Batches
+/// do not span chunks, so the last batch of each chunk may be smaller.
+///
+/// The targets are a matrix by default, and a vector, read from
+/// one-dimensional arrays, for a `NpyChunks<Vector<f64>>`.
+///
+/// Requires the `io` feature.
+#[cfg(feature = "io")]
+pub struct NpyChunks<T = Matrix<f64>> {
+    chunks: Vec<(PathBuf, PathBuf)>,
+    batch_size: usize,
+    next_chunk: usize,
+    current: Option<(Matrix<f64>, T)>,
+    pos: usize,
+}
+
+#[cfg(feature = "io")]
+impl<T> NpyChunks<T> {
+    /// Constructs a source from the paths of the input and target
+    /// files of each chunk.
+    ///
+    /// # Panics
+    ///
+    /// - The batch size is zero.
+    pub fn new<P: AsRef<Path>>(chunks: &[(P, P)], batch_size: usize) -> NpyChunks<T> {
+        assert!(batch_size > 0, "Batch size must be greater than zero.");
+
+        NpyChunks {
+            chunks: chunks.iter()
+                .map(|c| (c.0.as_ref().to_path_buf(), c.1.as_ref().to_path_buf()))
+                .collect(),
+            batch_size: batch_size,
+            next_chunk: 0,
+            current: None,
+            pos: 0,
+        }
+    }
+}
+
+#[cfg(feature = "io")]
+impl<T: NpyArray + RowData> DataSource<T> for NpyChunks<T> {
+    type Error = NpyError;
+
+    fn reset(&mut self) -> Result<(), NpyError> {
+        self.next_chunk = 0;
+        self.current = None;
+        self.pos = 0;
+        Ok(())
+    }
+
+    fn next_batch(&mut self,
+                  inputs: &mut Matrix<f64>,
+                  targets: &mut T)
+                  -> Result<bool, NpyError> {
+        loop {
+            if let Some((ref chunk_inputs, ref chunk_targets)) = self.current {
+                if self.pos < chunk_inputs.rows() {
+                    let end = (self.pos + self.batch_size).min(chunk_inputs.rows());
+                    copy_rows(chunk_inputs, self.pos, end, inputs);
+                    let rows = (self.pos..end).collect::<Vec<_>>();
+                    chunk_targets.select_rows_into(&rows, targets);
+                    self.pos = end;
+                    return Ok(true);
+                }
+            }
+
+            if self.next_chunk == self.chunks.len() {
+                return Ok(false);
+            }
+
+            let (chunk_inputs, chunk_targets): (Matrix<f64>, T) = {
+                let paths = &self.chunks[self.next_chunk];
+                (load_npy(&paths.0)?, load_npy(&paths.1)?)
+            };
+            if chunk_inputs.rows() != chunk_targets.n_rows() {
+                return Err(NpyError::Format(format!("Chunk {} has {} input rows but {} target rows.",
+                                                    self.next_chunk,
+                                                    chunk_inputs.rows(),
+                                                    chunk_targets.n_rows())));
+            }
+
+            self.current = Some((chunk_inputs, chunk_targets));
+            self.next_chunk += 1;
+            self.pos = 0;
+        }
+    }
+}
+
+/// Copies the rows from `start` to `end` into `out`, reusing its
+/// allocation.
+#[cfg(feature = "io")]
+fn copy_rows(mat: &Matrix<f64>, start: usize, end: usize, out: &mut Matrix<f64>) {
+    let cols = mat.cols();
+    let mut data = mem::replace(out, Matrix::new(0, 0, vec![])).into_vec();
+    data.clear();
+    data.extend_from_slice(&mat.data()[start * cols..end * cols]);
+    *out = Matrix::new(end - start, cols, data);
+}
+
+/// Loads a `.npz` archive.
+///
+/// Requires the `io` feature.
+#[cfg(feature = "io")]
+pub fn load_npz<P: AsRef<Path>>(path: P) -> Result<NpzArchive, NpyError> {
+    read_npz(File::open(path)?)
+}
+
+/// Reads a `.npz` archive.
+///
+/// The entries are found using the central directory of the zip
+/// archive, including the zip64 extensions written by NumPy.
+pub fn read_npz<R: Read>(mut reader: R) -> Result<NpzArchive, NpyError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+
+    // The end of central directory record is at least 22 bytes.
+    let eocd = match (0..bytes.len().saturating_sub(21))
+                         .rev()
+                         .find(|&i| bytes[i..i + 4] == [0x50, 0x4b, 0x05, 0x06]) {
+        Some(i) => i,
+        None => return format_error("Missing zip directory."),
+    };
+
+    let n_entries = read_u16(&bytes, eocd + 10) as usize;
+    let mut pos = read_u32(&bytes, eocd + 16) as usize;
+
+    let mut arrays = Vec::with_capacity(n_entries);
+    for _ in 0..n_entries {
+        if pos + 46 > bytes.len() || read_u32(&bytes, pos) != 0x0201_4b50 {
+            return format_error("Invalid zip directory entry.");
+        }
+
+        let method = read_u16(&bytes, pos + 10);
+        let mut size = read_u32(&bytes, pos + 20) as u64;
+        let name_len = read_u16(&bytes, pos + 28) as usize;
+        let extra_len = read_u16(&bytes, pos + 30) as usize;
+        let comment_len = read_u16(&bytes, pos + 32) as usize;
+        let mut offset = read_u32(&bytes, pos + 42) as u64;
+
+        let name_end = pos + 46 + name_len;
+        if name_end + extra_len > bytes.len() {
+            return format_error("Invalid zip directory entry.");
+        }
+        let name = String::from_utf8_lossy(&bytes[pos + 46..name_end]).into_owned();
+
+        // Sizes and offsets which do not fit are stored in the zip64
+        // extra field, in this order.
+        let uncompressed = read_u32(&bytes, pos + 24);
+        let mut extra = &bytes[name_end..name_end + extra_len];
+        while extra.len() >= 4 {
+            let id = read_u16(extra, 0);
+            let len = read_u16(extra, 2) as usize;
+            if id == 1 {
+                let mut field = 4;
+                if uncompressed == 0xffff_ffff {
+                    field += 8;
+                }
+                if size == 0xffff_ffff && field + 8 <= extra.len() {
+                    size = read_u64(extra, field);
+                    field += 8;
+                }
+                if offset == 0xffff_ffff && field + 8 <= extra.len() {
+                    offset = read_u64(extra, field);
+                }
+            }
+            extra = &extra[(4 + len).min(extra.len())..];
+        }
+
+        if method != 0 {
+            return Err(NpyError::Compressed(name));
+        }
+
+        let local = offset as usize;
+        if local + 30 > bytes.len() || read_u32(&bytes, local) != 0x0403_4b50 {
+            return format_error("Invalid zip entry.");
+        }
+        let data_start = local + 30 + read_u16(&bytes, local + 26) as usize +
+                         read_u16(&bytes, local + 28) as usize;
+        let data_end = data_start + size as usize;
+        if data_end > bytes.len() {
+            return format_error("Truncated zip entry.");
+        }
+
+        let name = name.trim_end_matches(".npy").to_string();
+        arrays.push((name, bytes[data_start..data_end].to_vec()));
+
+        pos = name_end + extra_len + comment_len;
+    }
+
+    Ok(NpzArchive { arrays: arrays })
+}
+
+/// Saves arrays to a `.npz` archive.
+///
+/// See `write_npz` for details.
+///
+/// Requires the `io` feature.
+#[cfg(feature = "io")]
+pub fn save_npz<P: AsRef<Path>>(path: P, arrays: &[(&str, &dyn NpyArray)]) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_npz(&mut writer, arrays)?;
+    writer.flush()
+}
+
+/// Writes arrays to a `.npz` archive.
+///
+/// Each array is stored uncompressed under its name with a `.npy`
+/// extension, as with `numpy.savez`.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::data::npy::{read_npz, write_npz};
+/// use rusty_machine::linalg::matrix::Matrix;
+/// use rusty_machine::linalg::vector::Vector;
+///
+/// let inputs = Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+/// let targets = Vector::new(vec![0.0, 1.0]);
+///
+/// let mut bytes = Vec::new();
+/// write_npz(&mut bytes, &[("inputs", &inputs), ("targets", &targets)]).unwrap();
+///
+/// let archive = read_npz(&bytes[..]).unwrap();
+/// let (x, y) = archive.inputs_targets("inputs", "targets").unwrap();
+///
+/// assert_eq!(*x.data(), *inputs.data());
+/// assert_eq!(*y.data(), *targets.data());
+/// ```
+///
+/// # Failures
+///
+/// - An array is larger than 4GB.
+pub fn write_npz<W: Write>(writer: &mut W, arrays: &[(&str, &dyn NpyArray)]) -> io::Result<()> {
+    let mut directory = Vec::new();
+    let mut offset = 0usize;
+
+    for &(name, array) in arrays {
+        let mut data = Vec::new();
+        write_npy(&mut data, array)?;
+        if data.len() > u32::MAX as usize || offset > u32::MAX as usize {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      "Arrays larger than 4GB are not supported."));
+        }
+
+        let file_name = format!("{}.npy", name);
+        let crc = crc32(&data);
+
+        // The fields shared by the local header and directory entry:
+        // version needed, flags, method, time, date, crc and sizes.
+        let mut common = Vec::with_capacity(26);
+        common.extend_from_slice(&20u16.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        common.extend_from_slice(&0x21u16.to_le_bytes());
+        common.extend_from_slice(&crc.to_le_bytes());
+        common.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        common.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        common.extend_from_slice(&(file_name.len() as u16).to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+
+        writer.write_all(&0x0403_4b50u32.to_le_bytes())?;
+        writer.write_all(&common)?;
+        writer.write_all(file_name.as_bytes())?;
+        writer.write_all(&data)?;
+
+        directory.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        directory.extend_from_slice(&20u16.to_le_bytes());
+        directory.extend_from_slice(&common);
+        // Comment length, disk number and attributes.
+        directory.extend_from_slice(&[0u8; 10]);
+        directory.extend_from_slice(&(offset as u32).to_le_bytes());
+        directory.extend_from_slice(file_name.as_bytes());
+
+        offset += 30 + file_name.len() + data.len();
+    }
+
+    writer.write_all(&directory)?;
+
+    writer.write_all(&0x0605_4b50u32.to_le_bytes())?;
+    writer.write_all(&[0u8; 4])?;
+    writer.write_all(&(arrays.len() as u16).to_le_bytes())?;
+    writer.write_all(&(arrays.len() as u16).to_le_bytes())?;
+    writer.write_all(&(directory.len() as u32).to_le_bytes())?;
+    writer.write_all(&(offset as u32).to_le_bytes())?;
+    writer.write_all(&0u16.to_le_bytes())
+}
+
+fn read_u16(bytes: &[u8], pos: usize) -> u16 {
+    u16::from_le_bytes([bytes[pos], bytes[pos + 1]])
+}
+
+fn read_u32(bytes: &[u8], pos: usize) -> u32 {
+    u32::from_le_bytes([bytes[pos], bytes[pos + 1], bytes[pos + 2], bytes[pos + 3]])
+}
+
+fn read_u64(bytes: &[u8], pos: usize) -> u64 {
+    read_u32(bytes, pos) as u64 | (read_u32(bytes, pos + 4) as u64) << 32
+}
+
+/// The CRC-32 checksum used by zip archives.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for &b in data {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
@@ -0,0 +1,281 @@
+//! Polars DataFrame conversion
+//!
+//! Converts the columns of a polars `DataFrame` into model inputs.
+//! Numeric and boolean columns are converted to `f64` and string or
+//! categorical columns are one-hot encoded with a `OneHotEncoder`.
+//!
+//! The categories are learned by `fit`, so data transformed later,
+//! such as a test set, is encoded into the same columns.
+//!
+//! Requires the `polars` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! # extern crate polars;
+//! # extern crate rusty_machine;
+//! # fn main() {
+//! use polars::prelude::*;
+//! use rusty_machine::data::polars::{target_vector, DataFrameEncoder};
+//!
+//! let df = df!("size" => [1.0, 2.0, 3.0],
+//!              "color" => ["red", "green", "red"],
+//!              "price" => [10.0, 20.0, 30.0]).unwrap();
+//!
+//! let mut encoder = DataFrameEncoder::new().columns(vec!["size", "color"]);
+//! let inputs = encoder.fit_transform(&df).unwrap();
+//! let targets = target_vector(&df, "price").unwrap();
+//!
+//! assert_eq!(encoder.feature_names().unwrap(), vec!["size", "color=green", "color=red"]);
+//! assert_eq!(*inputs.data(), vec![1.0, 0.0, 1.0,
+//!                                 2.0, 1.0, 0.0,
+//!                                 3.0, 0.0, 1.0]);
+//! assert_eq!(*targets.data(), vec![10.0, 20.0, 30.0]);
+//! # }
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use polars::prelude::{Column, DataFrame, DataType, PolarsError};
+
+use data::transforms::encode::{OneHotEncoder, UnknownCategory};
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+
+/// Errors from converting a `DataFrame`.
+#[derive(Debug)]
+pub enum PolarsDataError {
+    /// An error from polars.
+    Polars(PolarsError),
+    /// A selected column does not exist.
+    MissingColumn(String),
+    /// A selected column is neither numeric nor categorical.
+    UnsupportedType {
+        /// The column name.
+        column: String,
+        /// The polars data type of the column.
+        dtype: DataType,
+    },
+    /// A selected column contains nulls.
+    Nulls(String),
+}
+
+impl fmt::Display for PolarsDataError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PolarsDataError::Polars(ref e) => write!(f, "Polars error: {}", e),
+            PolarsDataError::MissingColumn(ref c) => write!(f, "Column '{}' does not exist.", c),
+            PolarsDataError::UnsupportedType { ref column, ref dtype } => {
+                write!(f, "Column '{}' has unsupported type {}.", column, dtype)
+            }
+            PolarsDataError::Nulls(ref c) => write!(f, "Column '{}' contains nulls.", c),
+        }
+    }
+}
+
+impl Error for PolarsDataError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            PolarsDataError::Polars(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<PolarsError> for PolarsDataError {
+    fn from(e: PolarsError) -> PolarsDataError {
+        PolarsDataError::Polars(e)
+    }
+}
+
+/// How a column is converted.
+enum ColumnEncoding {
+    /// Converted directly to `f64`.
+    Numeric,
+    /// One-hot encoded.
+    Categorical(OneHotEncoder<String>),
+}
+
+/// The DataFrame Encoder
+///
+/// Converts the selected columns of a `DataFrame` into a matrix,
+/// one-hot encoding the string and categorical columns.
+pub struct DataFrameEncoder {
+    columns: Option<Vec<String>>,
+    unknown: UnknownCategory,
+    fitted: Option<Vec<(String, ColumnEncoding)>>,
+}
+
+impl Default for DataFrameEncoder {
+    fn default() -> DataFrameEncoder {
+        DataFrameEncoder::new()
+    }
+}
+
+impl DataFrameEncoder {
+    /// Constructs an encoder which uses every column of the frame
+    /// and panics on unknown categories.
+    pub fn new() -> DataFrameEncoder {
+        DataFrameEncoder {
+            columns: None,
+            unknown: UnknownCategory::Panic,
+            fitted: None,
+        }
+    }
+
+    /// Selects the columns used as inputs, in the given order.
+    pub fn columns(mut self, columns: Vec<&str>) -> DataFrameEncoder {
+        self.columns = Some(columns.into_iter().map(|c| c.to_string()).collect());
+        self
+    }
+
+    /// Sets how categories which were not seen during `fit` are encoded.
+    pub fn unknown(mut self, unknown: UnknownCategory) -> DataFrameEncoder {
+        self.unknown = unknown;
+        self
+    }
+
+    /// The names of the columns produced by `transform`.
+    ///
+    /// Encoded columns are named `column=category`.
+    ///
+    /// Returns None if the encoder has not been fitted.
+    pub fn feature_names(&self) -> Option<Vec<String>> {
+        self.fitted.as_ref().map(|fitted| {
+            let mut names = Vec::new();
+            for (name, encoding) in fitted {
+                match *encoding {
+                    ColumnEncoding::Numeric => names.push(name.clone()),
+                    ColumnEncoding::Categorical(ref encoder) => {
+                        let cats = &encoder.categories().expect("Encoder is fitted.")[0];
+                        names.extend(cats.iter().map(|c| format!("{}={}", name, c)));
+                    }
+                }
+            }
+            names
+        })
+    }
+
+    /// Learns how to encode each column of the frame.
+    pub fn fit(&mut self, df: &DataFrame) -> Result<(), PolarsDataError> {
+        let names = match self.columns {
+            Some(ref c) => c.clone(),
+            None => df.get_column_names().iter().map(|n| n.to_string()).collect(),
+        };
+
+        let mut fitted = Vec::with_capacity(names.len());
+        for name in names {
+            let column = get_column(df, &name)?;
+            let encoding = if is_categorical(column.dtype()) {
+                let mut encoder = OneHotEncoder::new(self.unknown);
+                encoder.fit(&string_values(column, &name)?);
+                ColumnEncoding::Categorical(encoder)
+            } else {
+                check_numeric(column, &name)?;
+                ColumnEncoding::Numeric
+            };
+            fitted.push((name, encoding));
+        }
+
+        self.fitted = Some(fitted);
+        Ok(())
+    }
+
+    /// Converts the columns of the frame into a matrix.
+    ///
+    /// # Panics
+    ///
+    /// - The encoder has not been fitted.
+    /// - An unknown category is found and unknown categories are not ignored.
+    pub fn transform(&self, df: &DataFrame) -> Result<Matrix<f64>, PolarsDataError> {
+        let fitted = match self.fitted {
+            Some(ref f) => f,
+            None => panic!("Transformer has not been fitted."),
+        };
+
+        let rows = df.height();
+        let mut blocks = Vec::with_capacity(fitted.len());
+        for (name, encoding) in fitted {
+            let column = get_column(df, name)?;
+            let block = match *encoding {
+                ColumnEncoding::Numeric => Matrix::new(rows, 1, numeric_values(column, name)?),
+                ColumnEncoding::Categorical(ref encoder) => {
+                    if !is_categorical(column.dtype()) {
+                        return Err(unsupported(column, name));
+                    }
+                    encoder.transform(&string_values(column, name)?)
+                }
+            };
+            blocks.push(block);
+        }
+
+        let cols = blocks.iter().map(|b| b.cols()).sum();
+        let mut data = Vec::with_capacity(rows * cols);
+        for i in 0..rows {
+            for block in &blocks {
+                let c = block.cols();
+                data.extend_from_slice(&block.data()[i * c..(i + 1) * c]);
+            }
+        }
+
+        Ok(Matrix::new(rows, cols, data))
+    }
+
+    /// Fits the encoder and converts the frame.
+    pub fn fit_transform(&mut self, df: &DataFrame) -> Result<Matrix<f64>, PolarsDataError> {
+        self.fit(df)?;
+        self.transform(df)
+    }
+}
+
+/// Reads a numeric or boolean column of the frame into a vector.
+pub fn target_vector(df: &DataFrame, column: &str) -> Result<Vector<f64>, PolarsDataError> {
+    let values = numeric_values(get_column(df, column)?, column)?;
+    Ok(Vector::new(values))
+}
+
+fn get_column<'a>(df: &'a DataFrame, name: &str) -> Result<&'a Column, PolarsDataError> {
+    df.column(name).map_err(|_| PolarsDataError::MissingColumn(name.to_string()))
+}
+
+fn is_categorical(dtype: &DataType) -> bool {
+    dtype.is_string() || dtype.is_categorical() || dtype.is_enum()
+}
+
+fn unsupported(column: &Column, name: &str) -> PolarsDataError {
+    PolarsDataError::UnsupportedType {
+        column: name.to_string(),
+        dtype: column.dtype().clone(),
+    }
+}
+
+fn check_numeric(column: &Column, name: &str) -> Result<(), PolarsDataError> {
+    let dtype = column.dtype();
+    if !(dtype.is_primitive_numeric() || dtype.is_bool()) {
+        return Err(unsupported(column, name));
+    }
+    if column.null_count() > 0 {
+        return Err(PolarsDataError::Nulls(name.to_string()));
+    }
+    Ok(())
+}
+
+/// Converts a numeric or boolean column to doubles.
+fn numeric_values(column: &Column, name: &str) -> Result<Vec<f64>, PolarsDataError> {
+    check_numeric(column, name)?;
+    let doubles = column.cast(&DataType::Float64)?;
+    let values = doubles.f64()?.into_no_null_iter().collect();
+    Ok(values)
+}
+
+/// Reads a string or categorical column into a one column matrix.
+fn string_values(column: &Column, name: &str) -> Result<Matrix<String>, PolarsDataError> {
+    if column.null_count() > 0 {
+        return Err(PolarsDataError::Nulls(name.to_string()));
+    }
+
+    let strings = column.cast(&DataType::String)?;
+    let values: Vec<String> = strings.str()?.into_no_null_iter().map(|s| s.to_string()).collect();
+    Ok(Matrix::new(values.len(), 1, values))
+}
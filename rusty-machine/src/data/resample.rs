@@ -0,0 +1,222 @@
+//! Resampling for imbalanced classes
+//!
+//! Utilities which rebalance the classes of a data set before
+//! training.
+//!
+//! - `random_oversample` repeats samples of the smaller classes.
+//! - `random_undersample` drops samples of the larger classes.
+//! - `smote` creates synthetic samples of the smaller classes by
+//!   interpolating between neighbouring samples.
+//!
+//! All functions take a seed for the random number generator so
+//! that the resampling is reproducible.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::data::resample::random_oversample;
+//! use rusty_machine::linalg::matrix::Matrix;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! let inputs = Matrix::new(4, 1, vec![1.0, 2.0, 3.0, 4.0]);
+//! let targets = Vector::new(vec![0.0, 0.0, 0.0, 1.0]);
+//!
+//! let (inputs, targets) = random_oversample(&inputs, &targets, 1);
+//!
+//! assert_eq!(inputs.rows(), 6);
+//! assert_eq!(targets.data().iter().filter(|&&t| t == 1.0).count(), 3);
+//! ```
+
+use data::split::class_indices;
+use learning::toolkit::rand_utils::seeded_rng;
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+
+use rand::Rng;
+
+/// Repeats randomly chosen samples of each class until every class
+/// is as large as the largest class.
+///
+/// The original samples come first, followed by the repeated samples.
+///
+/// # Panics
+///
+/// - The number of targets does not match the number of input rows.
+pub fn random_oversample<T, U>(inputs: &Matrix<T>,
+                               targets: &Vector<U>,
+                               seed: u64)
+                               -> (Matrix<T>, Vector<U>)
+    where T: Copy,
+          U: Copy + PartialEq
+{
+    assert!(inputs.rows() == targets.size(),
+            "Inputs and targets have different lengths.");
+
+    let mut rng = seeded_rng(seed);
+    let groups = class_indices(targets.data());
+    let largest = groups.iter().map(|g| g.len()).max().unwrap_or(0);
+
+    let mut idxs: Vec<usize> = (0..inputs.rows()).collect();
+    for g in &groups {
+        for _ in g.len()..largest {
+            idxs.push(g[rng.gen_range(0, g.len())]);
+        }
+    }
+
+    (inputs.select_rows(&idxs), targets.select(&idxs))
+}
+
+/// Keeps a random subset of each class so that every class is as
+/// small as the smallest class.
+///
+/// The kept samples remain in their original order.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::data::resample::random_undersample;
+/// use rusty_machine::linalg::matrix::Matrix;
+/// use rusty_machine::linalg::vector::Vector;
+///
+/// let inputs = Matrix::new(4, 1, vec![1.0, 2.0, 3.0, 4.0]);
+/// let targets = Vector::new(vec![0.0, 0.0, 0.0, 1.0]);
+///
+/// let (inputs, targets) = random_undersample(&inputs, &targets, 1);
+///
+/// assert_eq!(inputs.rows(), 2);
+/// assert_eq!(targets[1], 1.0);
+/// ```
+///
+/// # Panics
+///
+/// - The number of targets does not match the number of input rows.
+pub fn random_undersample<T, U>(inputs: &Matrix<T>,
+                                targets: &Vector<U>,
+                                seed: u64)
+                                -> (Matrix<T>, Vector<U>)
+    where T: Copy,
+          U: Copy + PartialEq
+{
+    assert!(inputs.rows() == targets.size(),
+            "Inputs and targets have different lengths.");
+
+    let mut rng = seeded_rng(seed);
+    let groups = class_indices(targets.data());
+    let smallest = groups.iter().map(|g| g.len()).min().unwrap_or(0);
+
+    let mut idxs = Vec::with_capacity(smallest * groups.len());
+    for g in &groups {
+        let mut g = g.clone();
+        rng.shuffle(&mut g);
+        idxs.extend_from_slice(&g[..smallest]);
+    }
+    idxs.sort();
+
+    (inputs.select_rows(&idxs), targets.select(&idxs))
+}
+
+/// Creates synthetic samples of each class until every class is as
+/// large as the largest class.
+///
+/// Each synthetic sample lies at a random point on the line between a
+/// random sample of the class and one of its `k` nearest neighbours
+/// (by euclidean distance) within the same class. This is the SMOTE
+/// algorithm.
+///
+/// The original samples come first, followed by the synthetic samples.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::data::resample::smote;
+/// use rusty_machine::linalg::matrix::Matrix;
+/// use rusty_machine::linalg::vector::Vector;
+///
+/// let inputs = Matrix::new(5, 1, vec![0.0, 1.0, 2.0, 10.0, 11.0]);
+/// let targets = Vector::new(vec![0.0, 0.0, 0.0, 1.0, 1.0]);
+///
+/// let (inputs, targets) = smote(&inputs, &targets, 1, 2);
+///
+/// assert_eq!(inputs.rows(), 6);
+/// assert_eq!(targets[5], 1.0);
+/// assert!((10.0..=11.0).contains(&inputs[[5, 0]]));
+/// ```
+///
+/// # Panics
+///
+/// - The number of targets does not match the number of input rows.
+/// - k is zero.
+/// - The inputs contain NaN.
+/// - A class which needs new samples has only one sample.
+pub fn smote<U>(inputs: &Matrix<f64>,
+                targets: &Vector<U>,
+                k: usize,
+                seed: u64)
+                -> (Matrix<f64>, Vector<U>)
+    where U: Copy + PartialEq
+{
+    assert!(inputs.rows() == targets.size(),
+            "Inputs and targets have different lengths.");
+    assert!(k > 0, "Must use at least one neighbour.");
+    assert!(!inputs.data().iter().any(|x| x.is_nan()),
+            "Inputs must not contain NaN.");
+
+    let mut rng = seeded_rng(seed);
+    let groups = class_indices(targets.data());
+    let largest = groups.iter().map(|g| g.len()).max().unwrap_or(0);
+    let cols = inputs.cols();
+
+    let mut data = inputs.data().clone();
+    let mut labels = targets.data().clone();
+
+    for g in &groups {
+        if g.len() == largest {
+            continue;
+        }
+        assert!(g.len() > 1, "Cannot create synthetic samples of a class with one sample.");
+
+        let neighbours: Vec<Vec<usize>> = g.iter()
+                                           .map(|&i| nearest_neighbours(inputs, i, g, k))
+                                           .collect();
+
+        for _ in g.len()..largest {
+            let s = rng.gen_range(0, g.len());
+            let n = neighbours[s][rng.gen_range(0, neighbours[s].len())];
+            let gap: f64 = rng.gen();
+
+            let a = &inputs.data()[g[s] * cols..(g[s] + 1) * cols];
+            let b = &inputs.data()[n * cols..(n + 1) * cols];
+            data.extend(a.iter().zip(b.iter()).map(|(x, y)| x + gap * (y - x)));
+            labels.push(targets[g[s]]);
+        }
+    }
+
+    (Matrix::new(labels.len(), cols, data), Vector::new(labels))
+}
+
+/// The (at most) `k` rows among the candidates which are closest to
+/// the given row, excluding the row itself.
+fn nearest_neighbours(inputs: &Matrix<f64>,
+                      row: usize,
+                      candidates: &[usize],
+                      k: usize)
+                      -> Vec<usize> {
+    let cols = inputs.cols();
+    let x = &inputs.data()[row * cols..(row + 1) * cols];
+
+    let mut dists: Vec<(usize, f64)> = candidates.iter()
+                                                 .filter(|&&c| c != row)
+                                                 .map(|&c| {
+                                                     let y = &inputs.data()[c * cols..];
+                                                     let d = x.iter()
+                                                              .zip(y.iter())
+                                                              .map(|(a, b)| (a - b) * (a - b))
+                                                              .sum::<f64>();
+                                                     (c, d)
+                                                 })
+                                                 .collect();
+
+    dists.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+    dists.truncate(k);
+    dists.into_iter().map(|(c, _)| c).collect()
+}
@@ -0,0 +1,153 @@
+//! Data sources
+//!
+//! A `DataSource` yields mini-batches of inputs and targets one at a
+//! time, so that training only holds one batch in memory. The
+//! stochastic optimizers can train any model from a data source with
+//! `optimize_source`, which allows data sets larger than memory to be
+//! read from disk during training.
+//!
+//! The targets of a source are a `Matrix` by default, as a neural
+//! network takes, and may be any `RowData`, such as the `Vector`
+//! targets of the linear and logistic regressors.
+//!
+//! `MemorySource` batches data which is already in memory. The `csv`
+//! and `npy` modules provide sources which read from files.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::data::source::{DataSource, MemorySource};
+//! use rusty_machine::linalg::matrix::Matrix;
+//!
+//! let inputs = Matrix::new(3, 1, vec![1.0, 2.0, 3.0]);
+//! let targets = Matrix::new(3, 1, vec![2.0, 4.0, 6.0]);
+//! let mut source = MemorySource::new(&inputs, &targets, 2);
+//!
+//! let mut batch_inputs = Matrix::new(0, 0, vec![]);
+//! let mut batch_targets = Matrix::new(0, 0, vec![]);
+//!
+//! source.reset().unwrap();
+//! while source.next_batch(&mut batch_inputs, &mut batch_targets).unwrap() {
+//!     assert!(batch_inputs.rows() <= 2);
+//! }
+//! ```
+
+use std::convert::Infallible;
+use std::vec::Vec;
+
+use data::dataset::RowData;
+use linalg::matrix::Matrix;
+#[cfg(feature = "rand")]
+use learning::toolkit::rand_utils;
+#[cfg(feature = "rand")]
+use rand::{Rng, StdRng};
+
+/// Trait for sources of mini-batches with targets of type `T`.
+///
+/// Each pass through the data starts with a call to `reset`, followed
+/// by calls to `next_batch` until it returns false.
+pub trait DataSource<T = Matrix<f64>> {
+    /// The error from reading the data.
+    type Error;
+
+    /// Starts a new pass through the data.
+    fn reset(&mut self) -> Result<(), Self::Error>;
+
+    /// Reads the next mini-batch into the buffers, reusing their
+    /// allocations.
+    ///
+    /// Returns false when there are no batches left in the pass.
+    fn next_batch(&mut self,
+                  inputs: &mut Matrix<f64>,
+                  targets: &mut T)
+                  -> Result<bool, Self::Error>;
+}
+
+/// Mini-batches of data held in memory.
+///
+/// The batches are copied from the inputs and targets, which are
+/// borrowed and never copied as a whole.
+pub struct MemorySource<'a, T: 'a = Matrix<f64>> {
+    inputs: &'a Matrix<f64>,
+    targets: &'a T,
+    batch_size: usize,
+    order: Vec<usize>,
+    pos: usize,
+    #[cfg(feature = "rand")]
+    rng: Option<StdRng>,
+}
+
+impl<'a, T: RowData> MemorySource<'a, T> {
+    /// Constructs a source of batches of the given size.
+    ///
+    /// The final batch of each pass holds the remaining rows and may
+    /// be smaller.
+    ///
+    /// # Panics
+    ///
+    /// - The inputs and targets have a different number of rows.
+    /// - The batch size is zero.
+    pub fn new(inputs: &'a Matrix<f64>,
+               targets: &'a T,
+               batch_size: usize)
+               -> MemorySource<'a, T> {
+        assert!(inputs.rows() == targets.n_rows(),
+                "Inputs and targets have different lengths.");
+        assert!(batch_size > 0, "Batch size must be greater than zero.");
+
+        MemorySource {
+            inputs: inputs,
+            targets: targets,
+            batch_size: batch_size,
+            order: (0..inputs.rows()).collect(),
+            pos: 0,
+            #[cfg(feature = "rand")]
+            rng: None,
+        }
+    }
+
+    /// Sets whether the rows are shuffled at the start of each pass.
+    ///
+    /// The order is drawn from `rand_utils::rng`, so it is reproducible
+    /// after calling `rand_utils::set_seed`.
+    ///
+    /// Requires the `rand` feature.
+    #[cfg(feature = "rand")]
+    pub fn shuffle(mut self, shuffle: bool) -> MemorySource<'a, T> {
+        self.rng = if shuffle { Some(rand_utils::rng()) } else { None };
+        self
+    }
+}
+
+impl<'a, T: RowData> DataSource<T> for MemorySource<'a, T> {
+    type Error = Infallible;
+
+    fn reset(&mut self) -> Result<(), Infallible> {
+        #[cfg(feature = "rand")]
+        {
+            if let Some(ref mut rng) = self.rng {
+                rng.shuffle(&mut self.order);
+            }
+        }
+
+        self.pos = 0;
+        Ok(())
+    }
+
+    fn next_batch(&mut self,
+                  inputs: &mut Matrix<f64>,
+                  targets: &mut T)
+                  -> Result<bool, Infallible> {
+        if self.pos >= self.order.len() {
+            return Ok(false);
+        }
+
+        let end = (self.pos + self.batch_size).min(self.order.len());
+        let rows = &self.order[self.pos..end];
+        self.inputs.select_rows_into(rows, inputs);
+        self.targets.select_rows_into(rows, targets);
+
+        self.pos = end;
+        Ok(true)
+    }
+}
@@ -0,0 +1,199 @@
+//! Data splitting
+//!
+//! Utilities for shuffling data and partitioning it into
+//! training and test sets.
+//!
+//! All functions take a seed for the random number generator so
+//! that the splits are reproducible. Everything except `class_indices`
+//! requires the `rand` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::data::split::train_test_split;
+//! use rusty_machine::linalg::matrix::Matrix;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! let inputs = Matrix::new(4, 1, vec![1.0, 2.0, 3.0, 4.0]);
+//! let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+//!
+//! let (train_inputs, test_inputs, train_targets, test_targets) =
+//!     train_test_split(&inputs, &targets, 0.25, 42, false);
+//!
+//! assert_eq!(train_inputs.rows(), 3);
+//! assert_eq!(test_inputs.rows(), 1);
+//! assert_eq!(train_targets.size(), 3);
+//! assert_eq!(test_targets.size(), 1);
+//! ```
+
+use std::vec::Vec;
+
+#[cfg(feature = "rand")]
+use learning::toolkit::rand_utils::seeded_rng;
+#[cfg(feature = "rand")]
+use linalg::matrix::Matrix;
+#[cfg(feature = "rand")]
+use linalg::vector::Vector;
+
+#[cfg(feature = "rand")]
+use rand::Rng;
+
+/// Returns the indices `0..n` in a random order.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::data::split::shuffled_indices;
+///
+/// let mut idxs = shuffled_indices(5, 1);
+/// idxs.sort();
+///
+/// assert_eq!(idxs, vec![0, 1, 2, 3, 4]);
+/// ```
+#[cfg(feature = "rand")]
+pub fn shuffled_indices(n: usize, seed: u64) -> Vec<usize> {
+    let mut rng = seeded_rng(seed);
+    let mut idxs: Vec<usize> = (0..n).collect();
+    rng.shuffle(&mut idxs);
+    idxs
+}
+
+/// Shuffles the rows of the inputs along with the targets.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::data::split::shuffle;
+/// use rusty_machine::linalg::matrix::Matrix;
+/// use rusty_machine::linalg::vector::Vector;
+///
+/// let inputs = Matrix::new(3, 1, vec![1.0, 2.0, 3.0]);
+/// let targets = Vector::new(vec![1.0, 2.0, 3.0]);
+///
+/// let (inputs, targets) = shuffle(&inputs, &targets, 7);
+///
+/// // The rows stay paired with their targets.
+/// assert_eq!(inputs.data(), targets.data());
+/// ```
+///
+/// # Panics
+///
+/// - The number of targets does not match the number of input rows.
+#[cfg(feature = "rand")]
+pub fn shuffle<T: Copy, U: Copy>(inputs: &Matrix<T>,
+                                 targets: &Vector<U>,
+                                 seed: u64)
+                                 -> (Matrix<T>, Vector<U>) {
+    assert!(inputs.rows() == targets.size(),
+            "Inputs and targets have different lengths.");
+
+    let idxs = shuffled_indices(inputs.rows(), seed);
+    (inputs.select_rows(&idxs), targets.select(&idxs))
+}
+
+/// Splits the inputs and targets into shuffled training and test sets.
+///
+/// The test set contains a `test_fraction` proportion of the rows,
+/// rounded to the nearest row. The results are returned in the order
+/// `(train_inputs, test_inputs, train_targets, test_targets)`.
+///
+/// If `stratify` is true the targets are treated as class labels and
+/// each class is split separately, so that the class proportions are
+/// preserved in both sets.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::data::split::train_test_split;
+/// use rusty_machine::linalg::matrix::Matrix;
+/// use rusty_machine::linalg::vector::Vector;
+///
+/// let inputs = Matrix::new(6, 1, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+/// let targets = Vector::new(vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0]);
+///
+/// let (_, _, train_targets, test_targets) =
+///     train_test_split(&inputs, &targets, 0.5, 3, true);
+///
+/// // Each set holds half of each class.
+/// assert_eq!(train_targets.sum(), 1.0);
+/// assert_eq!(test_targets.sum(), 1.0);
+/// ```
+///
+/// # Panics
+///
+/// - The number of targets does not match the number of input rows.
+/// - The test fraction is not between 0 and 1.
+#[cfg(feature = "rand")]
+pub fn train_test_split<T, U>(inputs: &Matrix<T>,
+                              targets: &Vector<U>,
+                              test_fraction: f64,
+                              seed: u64,
+                              stratify: bool)
+                              -> (Matrix<T>, Matrix<T>, Vector<U>, Vector<U>)
+    where T: Copy,
+          U: Copy + PartialEq
+{
+    assert!(inputs.rows() == targets.size(),
+            "Inputs and targets have different lengths.");
+    assert!(test_fraction > 0f64 && test_fraction < 1f64,
+            "Test fraction must be between 0 and 1.");
+
+    let mut rng = seeded_rng(seed);
+    let mut train_idxs = Vec::new();
+    let mut test_idxs = Vec::new();
+
+    let groups = if stratify {
+        class_indices(targets.data())
+    } else {
+        vec![(0..targets.size()).collect()]
+    };
+
+    for mut group in groups {
+        rng.shuffle(&mut group);
+
+        let n_test = (group.len() as f64 * test_fraction).round() as usize;
+        test_idxs.extend_from_slice(&group[..n_test]);
+        train_idxs.extend_from_slice(&group[n_test..]);
+    }
+
+    if stratify {
+        // Mix the classes back together.
+        rng.shuffle(&mut train_idxs);
+        rng.shuffle(&mut test_idxs);
+    }
+
+    (inputs.select_rows(&train_idxs),
+     inputs.select_rows(&test_idxs),
+     targets.select(&train_idxs),
+     targets.select(&test_idxs))
+}
+
+/// Groups the indices of the targets by their class.
+///
+/// Classes are ordered by their first occurrence.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::data::split::class_indices;
+///
+/// let groups = class_indices(&[1.0, 0.0, 1.0]);
+///
+/// assert_eq!(groups, vec![vec![0, 2], vec![1]]);
+/// ```
+pub fn class_indices<U: PartialEq>(targets: &[U]) -> Vec<Vec<usize>> {
+    let mut classes: Vec<&U> = Vec::new();
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+
+    for (i, t) in targets.iter().enumerate() {
+        match classes.iter().position(|c| *c == t) {
+            Some(c) => groups[c].push(i),
+            None => {
+                classes.push(t);
+                groups.push(vec![i]);
+            }
+        }
+    }
+
+    groups
+}
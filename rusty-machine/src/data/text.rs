@@ -0,0 +1,500 @@
+//! Text vectorizers
+//!
+//! Converts documents into numeric features which can be used by
+//! the models in this crate.
+//!
+//! Documents are split into lowercase tokens made up of alphanumeric
+//! characters. Terms are n-grams of these tokens, joined by a single
+//! space. The crate does not have a sparse matrix type, so the
+//! features are returned as a dense `Matrix` with one row per
+//! document and one column per term in the vocabulary.
+//!
+//! The `HashingVectorizer` does not store a vocabulary, and instead
+//! hashes each term into a fixed number of columns.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::data::text::CountVectorizer;
+//!
+//! let docs = ["the cat sat", "the cat ran", "a dog ran"];
+//!
+//! let mut vectorizer = CountVectorizer::default();
+//! let counts = vectorizer.fit_transform(&docs);
+//!
+//! assert_eq!(vectorizer.vocabulary(), ["a", "cat", "dog", "ran", "sat", "the"]);
+//! assert_eq!(counts.rows(), 3);
+//! assert_eq!(counts[[0, 5]], 1.0);
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+
+/// The Count Vectorizer
+///
+/// Counts the occurrences of each term of a fitted vocabulary.
+#[derive(Clone, Debug)]
+pub struct CountVectorizer {
+    ngram_range: (usize, usize),
+    min_df: usize,
+    max_df: f64,
+    lowercase: bool,
+    vocabulary: Option<Vocabulary>,
+}
+
+#[derive(Clone, Debug)]
+struct Vocabulary {
+    terms: Vec<String>,
+    index: HashMap<String, usize>,
+}
+
+/// Unigrams only, keeping every term and lowercasing the text.
+impl Default for CountVectorizer {
+    fn default() -> CountVectorizer {
+        CountVectorizer {
+            ngram_range: (1, 1),
+            min_df: 1,
+            max_df: 1f64,
+            lowercase: true,
+            vocabulary: None,
+        }
+    }
+}
+
+impl CountVectorizer {
+    /// Sets the smallest and largest n-grams used as terms.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::data::text::CountVectorizer;
+    ///
+    /// let mut vectorizer = CountVectorizer::default().ngram_range(1, 2);
+    /// vectorizer.fit(&["red fox"]);
+    ///
+    /// assert_eq!(vectorizer.vocabulary(), ["fox", "red", "red fox"]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - min is zero or greater than max.
+    pub fn ngram_range(mut self, min: usize, max: usize) -> CountVectorizer {
+        assert!(min > 0 && min <= max, "Invalid n-gram range.");
+        self.ngram_range = (min, max);
+        self
+    }
+
+    /// Drops terms which appear in fewer than `min_df` documents.
+    pub fn min_df(mut self, min_df: usize) -> CountVectorizer {
+        self.min_df = min_df;
+        self
+    }
+
+    /// Drops terms which appear in more than a `max_df` proportion
+    /// of the documents.
+    ///
+    /// # Panics
+    ///
+    /// - max_df is not between 0 and 1.
+    pub fn max_df(mut self, max_df: f64) -> CountVectorizer {
+        assert!((0f64..=1f64).contains(&max_df),
+                "Maximum document frequency must be between 0 and 1.");
+        self.max_df = max_df;
+        self
+    }
+
+    /// Sets whether the text is lowercased before tokenizing.
+    pub fn lowercase(mut self, lowercase: bool) -> CountVectorizer {
+        self.lowercase = lowercase;
+        self
+    }
+
+    /// The terms of the fitted vocabulary, in column order.
+    ///
+    /// # Panics
+    ///
+    /// - The vectorizer has not been fitted.
+    pub fn vocabulary(&self) -> &[String] {
+        &self.fitted().terms
+    }
+
+    /// Splits a document into its terms.
+    pub fn terms(&self, doc: &str) -> Vec<String> {
+        tokenize(doc, self.lowercase, self.ngram_range)
+    }
+
+    /// Builds the vocabulary from the documents.
+    ///
+    /// Terms are sorted alphabetically.
+    ///
+    /// # Panics
+    ///
+    /// - There are no documents.
+    pub fn fit<S: AsRef<str>>(&mut self, docs: &[S]) {
+        assert!(!docs.is_empty(), "Cannot fit to no documents.");
+
+        let mut doc_freqs = BTreeMap::new();
+        for doc in docs {
+            let mut terms = self.terms(doc.as_ref());
+            terms.sort();
+            terms.dedup();
+
+            for term in terms {
+                *doc_freqs.entry(term).or_insert(0usize) += 1;
+            }
+        }
+
+        let max_count = self.max_df * docs.len() as f64;
+        let terms: Vec<String> = doc_freqs.into_iter()
+                                          .filter(|&(_, df)| {
+                                              df >= self.min_df && df as f64 <= max_count
+                                          })
+                                          .map(|(term, _)| term)
+                                          .collect();
+
+        let index = terms.iter().cloned().enumerate().map(|(i, t)| (t, i)).collect();
+
+        self.vocabulary = Some(Vocabulary {
+            terms: terms,
+            index: index,
+        });
+    }
+
+    /// Counts the vocabulary terms in each document.
+    ///
+    /// Terms which are not in the vocabulary are ignored.
+    ///
+    /// # Panics
+    ///
+    /// - The vectorizer has not been fitted.
+    pub fn transform<S: AsRef<str>>(&self, docs: &[S]) -> Matrix<f64> {
+        let vocab = self.fitted();
+        let cols = vocab.terms.len();
+        let mut data = vec![0f64; docs.len() * cols];
+
+        for (i, doc) in docs.iter().enumerate() {
+            for term in self.terms(doc.as_ref()) {
+                if let Some(&j) = vocab.index.get(&term) {
+                    data[i * cols + j] += 1f64;
+                }
+            }
+        }
+
+        Matrix::new(docs.len(), cols, data)
+    }
+
+    /// Builds the vocabulary and then counts the terms.
+    pub fn fit_transform<S: AsRef<str>>(&mut self, docs: &[S]) -> Matrix<f64> {
+        self.fit(docs);
+        self.transform(docs)
+    }
+
+    fn fitted(&self) -> &Vocabulary {
+        match self.vocabulary {
+            Some(ref v) => v,
+            None => panic!("Transformer has not been fitted."),
+        }
+    }
+}
+
+/// The TF-IDF Vectorizer
+///
+/// Weights the term counts by the inverse document frequency,
+/// `ln((1 + n) / (1 + df)) + 1`, so that terms which appear in
+/// many documents are less important. Each row is then scaled
+/// to have unit euclidean norm unless normalization is disabled.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::data::text::{CountVectorizer, TfIdfVectorizer};
+///
+/// let docs = ["sunny day", "rainy day", "sunny morning"];
+///
+/// let mut vectorizer = TfIdfVectorizer::new(CountVectorizer::default());
+/// let features = vectorizer.fit_transform(&docs);
+///
+/// // "day" appears in more documents than "rainy".
+/// assert!(features[[1, 0]] < features[[1, 2]]);
+/// ```
+#[derive(Clone)]
+pub struct TfIdfVectorizer {
+    counts: CountVectorizer,
+    normalize: bool,
+    idf: Option<Vector<f64>>,
+}
+
+impl fmt::Debug for TfIdfVectorizer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TfIdfVectorizer")
+            .field("counts", &self.counts)
+            .field("normalize", &self.normalize)
+            .field("fitted", &self.idf.is_some())
+            .finish()
+    }
+}
+
+/// Uses the default `CountVectorizer` with normalization.
+impl Default for TfIdfVectorizer {
+    fn default() -> TfIdfVectorizer {
+        TfIdfVectorizer::new(CountVectorizer::default())
+    }
+}
+
+impl TfIdfVectorizer {
+    /// Constructs a TF-IDF vectorizer from a count vectorizer.
+    ///
+    /// The count vectorizer determines the tokenizing and vocabulary.
+    pub fn new(counts: CountVectorizer) -> TfIdfVectorizer {
+        TfIdfVectorizer {
+            counts: counts,
+            normalize: true,
+            idf: None,
+        }
+    }
+
+    /// Sets whether each row is scaled to unit norm.
+    pub fn normalize(mut self, normalize: bool) -> TfIdfVectorizer {
+        self.normalize = normalize;
+        self
+    }
+
+    /// The terms of the fitted vocabulary, in column order.
+    ///
+    /// # Panics
+    ///
+    /// - The vectorizer has not been fitted.
+    pub fn vocabulary(&self) -> &[String] {
+        self.counts.vocabulary()
+    }
+
+    /// The inverse document frequency of each term.
+    ///
+    /// # Panics
+    ///
+    /// - The vectorizer has not been fitted.
+    pub fn idf(&self) -> &Vector<f64> {
+        match self.idf {
+            Some(ref idf) => idf,
+            None => panic!("Transformer has not been fitted."),
+        }
+    }
+
+    /// Builds the vocabulary and document frequencies.
+    ///
+    /// # Panics
+    ///
+    /// - There are no documents.
+    pub fn fit<S: AsRef<str>>(&mut self, docs: &[S]) {
+        let counts = self.counts.fit_transform(docs);
+        let n = docs.len() as f64;
+
+        let cols = counts.cols();
+        let mut doc_freqs = vec![0f64; cols];
+        for i in 0..counts.rows() {
+            for (df, c) in doc_freqs.iter_mut().zip(&counts.data()[i * cols..(i + 1) * cols]) {
+                if *c > 0f64 {
+                    *df += 1f64;
+                }
+            }
+        }
+
+        let idf = doc_freqs.into_iter().map(|df| ((1f64 + n) / (1f64 + df)).ln() + 1f64).collect();
+        self.idf = Some(idf);
+    }
+
+    /// Computes the TF-IDF features of each document.
+    ///
+    /// # Panics
+    ///
+    /// - The vectorizer has not been fitted.
+    pub fn transform<S: AsRef<str>>(&self, docs: &[S]) -> Matrix<f64> {
+        let idf = self.idf();
+        let mut features = self.counts.transform(docs);
+        let cols = features.cols();
+
+        for row in features.mut_data().chunks_mut(cols.max(1)) {
+            for (x, w) in row.iter_mut().zip(idf.data().iter()) {
+                *x *= *w;
+            }
+
+            if self.normalize {
+                let norm = row.iter().map(|x| x * x).sum::<f64>().sqrt();
+                if norm > 0f64 {
+                    for x in row.iter_mut() {
+                        *x /= norm;
+                    }
+                }
+            }
+        }
+
+        features
+    }
+
+    /// Fits the vectorizer and then transforms the documents.
+    pub fn fit_transform<S: AsRef<str>>(&mut self, docs: &[S]) -> Matrix<f64> {
+        self.fit(docs);
+        self.transform(docs)
+    }
+}
+
+/// The Hashing Vectorizer
+///
+/// Maps terms to a fixed number of columns using a hash function,
+/// so that no vocabulary needs to be stored or fitted. This suits
+/// streaming data where the set of terms is unbounded, at the cost
+/// of occasional collisions between terms.
+///
+/// Terms are hashed with 64-bit FNV-1a, so the mapping is the same
+/// on every platform and run. By default the high bit of the same
+/// hash chooses the sign of each term, so that collisions tend to
+/// cancel out.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::data::text::HashingVectorizer;
+///
+/// let vectorizer = HashingVectorizer::new(16);
+/// let features = vectorizer.transform(&["the quick brown fox", "the lazy dog"]);
+///
+/// assert_eq!(features.rows(), 2);
+/// assert_eq!(features.cols(), 16);
+/// ```
+#[derive(Clone, Debug)]
+pub struct HashingVectorizer {
+    n_features: usize,
+    ngram_range: (usize, usize),
+    lowercase: bool,
+    alternate_sign: bool,
+}
+
+impl HashingVectorizer {
+    /// Constructs a hashing vectorizer with the given number of columns.
+    ///
+    /// # Panics
+    ///
+    /// - The number of features is zero.
+    pub fn new(n_features: usize) -> HashingVectorizer {
+        assert!(n_features > 0, "The number of features must be greater than zero.");
+
+        HashingVectorizer {
+            n_features: n_features,
+            ngram_range: (1, 1),
+            lowercase: true,
+            alternate_sign: true,
+        }
+    }
+
+    /// Sets the smallest and largest n-grams used as terms.
+    ///
+    /// # Panics
+    ///
+    /// - min is zero or greater than max.
+    pub fn ngram_range(mut self, min: usize, max: usize) -> HashingVectorizer {
+        assert!(min > 0 && min <= max, "Invalid n-gram range.");
+        self.ngram_range = (min, max);
+        self
+    }
+
+    /// Sets whether the text is lowercased before tokenizing.
+    pub fn lowercase(mut self, lowercase: bool) -> HashingVectorizer {
+        self.lowercase = lowercase;
+        self
+    }
+
+    /// Sets whether the sign of each term is chosen by its hash.
+    ///
+    /// If disabled every term adds a positive count.
+    pub fn alternate_sign(mut self, alternate_sign: bool) -> HashingVectorizer {
+        self.alternate_sign = alternate_sign;
+        self
+    }
+
+    /// The number of output columns.
+    pub fn n_features(&self) -> usize {
+        self.n_features
+    }
+
+    /// Hashes the terms of each document.
+    pub fn transform<S: AsRef<str>>(&self, docs: &[S]) -> Matrix<f64> {
+        let rows: Vec<Vec<String>> = docs.iter()
+                                         .map(|d| tokenize(d.as_ref(), self.lowercase, self.ngram_range))
+                                         .collect();
+        self.transform_features(&rows)
+    }
+
+    /// Hashes rows of string features directly.
+    ///
+    /// Each row is a list of features, such as pre-tokenized terms or
+    /// categorical values. Features are used exactly as given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::data::text::HashingVectorizer;
+    ///
+    /// let vectorizer = HashingVectorizer::new(8).alternate_sign(false);
+    /// let rows = vec![vec!["color=red", "size=small"], vec!["color=red"]];
+    ///
+    /// let features = vectorizer.transform_features(&rows);
+    ///
+    /// assert_eq!(features.data()[..8].iter().sum::<f64>(), 2.0);
+    /// assert_eq!(features.data()[8..].iter().sum::<f64>(), 1.0);
+    /// ```
+    pub fn transform_features<S: AsRef<str>>(&self, rows: &[Vec<S>]) -> Matrix<f64> {
+        let mut data = vec![0f64; rows.len() * self.n_features];
+
+        for (i, row) in rows.iter().enumerate() {
+            for feature in row {
+                let hash = fnv1a(feature.as_ref().as_bytes());
+                let j = (hash % self.n_features as u64) as usize;
+
+                // Use the high bits for the sign, as the low bits pick the column.
+                let sign = if self.alternate_sign && hash >> 63 == 1 {
+                    -1f64
+                } else {
+                    1f64
+                };
+
+                data[i * self.n_features + j] += sign;
+            }
+        }
+
+        Matrix::new(rows.len(), self.n_features, data)
+    }
+}
+
+/// Splits text into alphanumeric tokens and joins them into n-grams.
+fn tokenize(doc: &str, lowercase: bool, ngram_range: (usize, usize)) -> Vec<String> {
+    let text = if lowercase {
+        doc.to_lowercase()
+    } else {
+        doc.to_string()
+    };
+
+    let tokens: Vec<&str> = text.split(|c: char| !c.is_alphanumeric())
+                                .filter(|t| !t.is_empty())
+                                .collect();
+
+    let mut terms = Vec::new();
+    for n in ngram_range.0..ngram_range.1 + 1 {
+        for window in tokens.windows(n) {
+            terms.push(window.join(" "));
+        }
+    }
+    terms
+}
+
+/// The 64-bit FNV-1a hash.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash = 0xcbf29ce484222325u64;
+    for &b in bytes {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
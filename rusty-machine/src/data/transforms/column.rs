@@ -0,0 +1,247 @@
+//! Column transforms
+//!
+//! Applies different transforms to different subsets of the input
+//! columns and concatenates the results.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::data::transforms::Transformer;
+//! use rusty_machine::data::transforms::column::ColumnTransformer;
+//! use rusty_machine::data::transforms::encode::OneHotEncoder;
+//! use rusty_machine::data::transforms::minmax::MinMaxScaler;
+//! use rusty_machine::linalg::matrix::Matrix;
+//!
+//! // A numeric column and a categorical column.
+//! let inputs = Matrix::new(3, 2, vec![10.0, 1.0,
+//!                                     20.0, 2.0,
+//!                                     30.0, 1.0]);
+//!
+//! let mut transformer = ColumnTransformer::new()
+//!                           .add(&[0], MinMaxScaler::default())
+//!                           .add(&[1], OneHotEncoder::default());
+//!
+//! let outputs = transformer.fit_transform(&inputs);
+//!
+//! assert_eq!(*outputs.data(), vec![0.0, 1.0, 0.0,
+//!                                  0.5, 0.0, 1.0,
+//!                                  1.0, 1.0, 0.0]);
+//! ```
+
+use data::transforms::Transformer;
+use linalg::matrix::Matrix;
+
+type BoxedTransformer = Box<dyn Transformer<Matrix<f64>> + Send + Sync>;
+
+/// The Column Transformer
+///
+/// Each transform is applied to its own subset of the columns. The
+/// outputs are concatenated in the order the transforms were added,
+/// followed by any passed through columns.
+///
+/// Columns which are not used by any transform are dropped unless
+/// `passthrough` is set.
+pub struct ColumnTransformer {
+    transforms: Vec<(Vec<usize>, BoxedTransformer)>,
+    passthrough: bool,
+    fitted: Option<FittedLayout>,
+}
+
+/// The column layout learned by `fit`.
+struct FittedLayout {
+    input_cols: usize,
+    output_widths: Vec<usize>,
+    remainder: Vec<usize>,
+}
+
+impl Default for ColumnTransformer {
+    fn default() -> ColumnTransformer {
+        ColumnTransformer::new()
+    }
+}
+
+impl ColumnTransformer {
+    /// Constructs a column transformer with no transforms.
+    pub fn new() -> ColumnTransformer {
+        ColumnTransformer {
+            transforms: Vec::new(),
+            passthrough: false,
+            fitted: None,
+        }
+    }
+
+    /// Adds a transform applied to the given columns.
+    ///
+    /// # Panics
+    ///
+    /// - No columns are given.
+    pub fn add<T>(mut self, cols: &[usize], transform: T) -> ColumnTransformer
+        where T: Transformer<Matrix<f64>> + Send + Sync + 'static
+    {
+        assert!(!cols.is_empty(), "A transform must use at least one column.");
+        self.transforms.push((cols.to_vec(), Box::new(transform)));
+        self
+    }
+
+    /// Keeps the columns which are not used by any transform.
+    ///
+    /// These columns are appended unchanged after the transformed columns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::data::transforms::Transformer;
+    /// use rusty_machine::data::transforms::column::ColumnTransformer;
+    /// use rusty_machine::data::transforms::minmax::MinMaxScaler;
+    /// use rusty_machine::linalg::matrix::Matrix;
+    ///
+    /// let inputs = Matrix::new(2, 2, vec![7.0, 1.0, 8.0, 3.0]);
+    ///
+    /// let mut transformer = ColumnTransformer::new()
+    ///                           .add(&[1], MinMaxScaler::default())
+    ///                           .passthrough();
+    ///
+    /// let outputs = transformer.fit_transform(&inputs);
+    /// assert_eq!(*outputs.data(), vec![0.0, 7.0, 1.0, 8.0]);
+    /// ```
+    pub fn passthrough(mut self) -> ColumnTransformer {
+        self.passthrough = true;
+        self
+    }
+
+    fn layout(&self, input_cols: usize) -> &FittedLayout {
+        match self.fitted {
+            Some(ref l) => {
+                assert!(l.input_cols == input_cols,
+                        "Input columns do not match fitted columns.");
+                l
+            }
+            None => panic!("Transformer has not been fitted."),
+        }
+    }
+}
+
+impl Transformer<Matrix<f64>> for ColumnTransformer {
+    /// Fits each transform to its columns.
+    ///
+    /// # Panics
+    ///
+    /// - A column index is out of bounds.
+    fn fit(&mut self, inputs: &Matrix<f64>) {
+        let mut output_widths = Vec::with_capacity(self.transforms.len());
+        let mut used = vec![false; inputs.cols()];
+
+        for (cols, t) in &mut self.transforms {
+            for &c in cols.iter() {
+                assert!(c < inputs.cols(), "Column index is out of bounds.");
+                used[c] = true;
+            }
+
+            let outputs = t.fit_transform(&inputs.select_cols(cols));
+            output_widths.push(outputs.cols());
+        }
+
+        let remainder = if self.passthrough {
+            (0..inputs.cols()).filter(|&c| !used[c]).collect()
+        } else {
+            Vec::new()
+        };
+
+        self.fitted = Some(FittedLayout {
+            input_cols: inputs.cols(),
+            output_widths: output_widths,
+            remainder: remainder,
+        });
+    }
+
+    /// Applies each transform to its columns and concatenates the outputs.
+    ///
+    /// # Panics
+    ///
+    /// - The transformer has not been fitted.
+    /// - The inputs have a different number of columns to the fitted data.
+    fn transform(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        let layout = self.layout(inputs.cols());
+
+        let mut parts: Vec<Matrix<f64>> = self.transforms
+                                              .iter()
+                                              .map(|(cols, t)| {
+                                                  t.transform(&inputs.select_cols(cols))
+                                              })
+                                              .collect();
+
+        if !layout.remainder.is_empty() {
+            parts.push(inputs.select_cols(&layout.remainder));
+        }
+
+        concat_cols(inputs.rows(), &parts)
+    }
+
+    /// Inverts each transform and places the columns back in
+    /// their original positions.
+    ///
+    /// If a column is used by multiple transforms, the last
+    /// transform determines its value.
+    ///
+    /// # Panics
+    ///
+    /// - The transformer has not been fitted.
+    /// - The inputs do not have the number of transformed columns.
+    /// - Some columns were dropped by the transform.
+    fn inverse_transform(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        let layout = match self.fitted {
+            Some(ref l) => l,
+            None => panic!("Transformer has not been fitted."),
+        };
+
+        let total = layout.output_widths.iter().sum::<usize>() + layout.remainder.len();
+        assert!(inputs.cols() == total,
+                "Input columns do not match transformed columns.");
+
+        let rows = inputs.rows();
+        let mut data = vec![0f64; rows * layout.input_cols];
+        let mut filled = vec![false; layout.input_cols];
+        let mut offset = 0;
+
+        for ((cols, t), width) in self.transforms.iter().zip(layout.output_widths.iter()) {
+            let out_idxs: Vec<usize> = (offset..offset + width).collect();
+            let original = t.inverse_transform(&inputs.select_cols(&out_idxs));
+
+            for i in 0..rows {
+                for (k, &c) in cols.iter().enumerate() {
+                    data[i * layout.input_cols + c] = original[[i, k]];
+                }
+            }
+            for &c in cols {
+                filled[c] = true;
+            }
+            offset += *width;
+        }
+
+        for (k, &c) in layout.remainder.iter().enumerate() {
+            for i in 0..rows {
+                data[i * layout.input_cols + c] = inputs[[i, offset + k]];
+            }
+            filled[c] = true;
+        }
+
+        assert!(filled.iter().all(|f| *f),
+                "Cannot invert a transform which drops columns.");
+
+        Matrix::new(rows, layout.input_cols, data)
+    }
+}
+
+/// Concatenates the columns of matrices with the same number of rows.
+fn concat_cols(rows: usize, parts: &[Matrix<f64>]) -> Matrix<f64> {
+    let cols = parts.iter().map(|p| p.cols()).sum();
+    let mut data = Vec::with_capacity(rows * cols);
+
+    for i in 0..rows {
+        for p in parts {
+            data.extend_from_slice(&p.data()[i * p.cols()..(i + 1) * p.cols()]);
+        }
+    }
+
+    Matrix::new(rows, cols, data)
+}
@@ -0,0 +1,337 @@
+//! Discretization transform
+//!
+//! Sorts the values of each column into bins. The bins can be output
+//! as ordinal bin indices or one-hot encoded.
+//!
+//! Discretized features are useful for models over categorical data
+//! such as Naive Bayes, and for building interpretable scorecards.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::data::transforms::Transformer;
+//! use rusty_machine::data::transforms::discretize::{KBinsDiscretizer, BinStrategy};
+//! use rusty_machine::linalg::matrix::Matrix;
+//!
+//! let inputs = Matrix::new(4, 1, vec![0.0, 1.0, 2.0, 4.0]);
+//!
+//! let mut bins = KBinsDiscretizer::new(2).strategy(BinStrategy::Uniform);
+//! let outputs = bins.fit_transform(&inputs);
+//!
+//! // The bins are [0, 2) and [2, 4].
+//! assert_eq!(*outputs.data(), vec![0.0, 0.0, 1.0, 1.0]);
+//!
+//! // The inverse gives the bin centers.
+//! assert_eq!(*bins.inverse_transform(&outputs).data(), vec![1.0, 1.0, 3.0, 3.0]);
+//! ```
+
+use std::f64;
+
+use data::transforms::Transformer;
+use linalg::matrix::Matrix;
+
+/// The strategy used to place the bin edges.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BinStrategy {
+    /// Bins of equal width over the range of the column.
+    Uniform,
+    /// Bins holding (roughly) the same number of samples.
+    Quantile,
+    /// Bins around the centroids of a one dimensional k-means
+    /// clustering of the column.
+    KMeans,
+}
+
+/// The encoding of the output bins.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BinEncoding {
+    /// Each column is replaced by its bin index.
+    Ordinal,
+    /// Each column is replaced by a one-hot encoding of its bin.
+    OneHot,
+}
+
+/// The K Bins Discretizer
+///
+/// Learns the edges of up to `n_bins` bins for each column. Bins
+/// which would be empty, such as repeated quantiles, are merged so
+/// some columns may have fewer bins.
+///
+/// Values outside of the fitted range are placed in the first or last
+/// bin.
+#[derive(Clone, Debug)]
+pub struct KBinsDiscretizer {
+    n_bins: usize,
+    strategy: BinStrategy,
+    encoding: BinEncoding,
+    edges: Option<Vec<Vec<f64>>>,
+}
+
+impl KBinsDiscretizer {
+    /// Constructs a discretizer with the given number of bins.
+    ///
+    /// Defaults to quantile bins with ordinal encoding.
+    ///
+    /// # Panics
+    ///
+    /// - n_bins is less than two.
+    pub fn new(n_bins: usize) -> KBinsDiscretizer {
+        assert!(n_bins > 1, "Must use at least two bins.");
+
+        KBinsDiscretizer {
+            n_bins: n_bins,
+            strategy: BinStrategy::Quantile,
+            encoding: BinEncoding::Ordinal,
+            edges: None,
+        }
+    }
+
+    /// Sets the strategy used to place the bin edges.
+    pub fn strategy(mut self, strategy: BinStrategy) -> KBinsDiscretizer {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Sets the encoding of the output bins.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::data::transforms::Transformer;
+    /// use rusty_machine::data::transforms::discretize::{KBinsDiscretizer, BinEncoding};
+    /// use rusty_machine::linalg::matrix::Matrix;
+    ///
+    /// let inputs = Matrix::new(3, 1, vec![1.0, 2.0, 3.0]);
+    ///
+    /// let mut bins = KBinsDiscretizer::new(3).encoding(BinEncoding::OneHot);
+    /// let outputs = bins.fit_transform(&inputs);
+    ///
+    /// assert_eq!(*outputs.data(), vec![1.0, 0.0, 0.0,
+    ///                                  0.0, 1.0, 0.0,
+    ///                                  0.0, 0.0, 1.0]);
+    /// ```
+    pub fn encoding(mut self, encoding: BinEncoding) -> KBinsDiscretizer {
+        self.encoding = encoding;
+        self
+    }
+
+    /// The bin edges of each column learned by `fit`.
+    ///
+    /// The edges of a column with `n` bins contain `n + 1` increasing
+    /// values, from the column minimum to the column maximum.
+    pub fn edges(&self) -> Option<&Vec<Vec<f64>>> {
+        self.edges.as_ref()
+    }
+
+    fn fitted_edges(&self, cols: usize) -> &Vec<Vec<f64>> {
+        match self.edges {
+            Some(ref e) => {
+                assert!(e.len() == cols, "Input columns do not match fitted columns.");
+                e
+            }
+            None => panic!("Transformer has not been fitted."),
+        }
+    }
+
+    fn column_edges(&self, col: &mut [f64]) -> Vec<f64> {
+        col.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let min = col[0];
+        let max = col[col.len() - 1];
+        let n = self.n_bins;
+
+        let mut edges: Vec<f64> = match self.strategy {
+            BinStrategy::Uniform => uniform_edges(min, max, n),
+            BinStrategy::Quantile => (0..n + 1).map(|i| quantile(col, i as f64 / n as f64)).collect(),
+            BinStrategy::KMeans => kmeans_edges(col, n),
+        };
+
+        edges.dedup_by(|a, b| (*a - *b).abs() <= 1e-8 * b.abs().max(1f64));
+        if edges.len() == 1 {
+            // A constant column is placed in a single bin.
+            edges.push(edges[0]);
+        }
+        edges
+    }
+}
+
+impl Transformer<Matrix<f64>> for KBinsDiscretizer {
+    /// Learns the bin edges of each column.
+    ///
+    /// # Panics
+    ///
+    /// - The inputs have no rows.
+    fn fit(&mut self, inputs: &Matrix<f64>) {
+        assert!(inputs.rows() > 0, "Cannot fit bins to empty inputs.");
+
+        let cols = inputs.cols();
+        let edges = (0..cols)
+                        .map(|j| {
+                            let mut col: Vec<f64> = inputs.data()
+                                                          .iter()
+                                                          .skip(j)
+                                                          .step_by(cols)
+                                                          .cloned()
+                                                          .collect();
+                            self.column_edges(&mut col)
+                        })
+                        .collect();
+
+        self.edges = Some(edges);
+    }
+
+    /// Replaces each value by its encoded bin.
+    ///
+    /// # Panics
+    ///
+    /// - The transformer has not been fitted.
+    /// - The inputs have a different number of columns to the fitted data.
+    fn transform(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        let edges = self.fitted_edges(inputs.cols());
+        let cols = inputs.cols();
+
+        let out_cols = match self.encoding {
+            BinEncoding::Ordinal => cols,
+            BinEncoding::OneHot => edges.iter().map(|e| e.len() - 1).sum(),
+        };
+        let mut data = vec![0f64; inputs.rows() * out_cols];
+
+        for i in 0..inputs.rows() {
+            let mut offset = i * out_cols;
+            for (j, e) in edges.iter().enumerate() {
+                let b = find_bin(e, inputs.data()[i * cols + j]);
+                match self.encoding {
+                    BinEncoding::Ordinal => data[offset] = b as f64,
+                    BinEncoding::OneHot => data[offset + b] = 1f64,
+                }
+
+                offset += match self.encoding {
+                    BinEncoding::Ordinal => 1,
+                    BinEncoding::OneHot => e.len() - 1,
+                };
+            }
+        }
+
+        Matrix::new(inputs.rows(), out_cols, data)
+    }
+
+    /// Replaces each encoded bin by the center of the bin.
+    ///
+    /// One-hot encoded rows use the bin with the largest value.
+    ///
+    /// # Panics
+    ///
+    /// - The transformer has not been fitted.
+    /// - The inputs do not have the number of transformed columns.
+    /// - An ordinal bin index is out of range.
+    fn inverse_transform(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        let edges = match self.edges {
+            Some(ref e) => e,
+            None => panic!("Transformer has not been fitted."),
+        };
+        let widths: Vec<usize> = match self.encoding {
+            BinEncoding::Ordinal => vec![1; edges.len()],
+            BinEncoding::OneHot => edges.iter().map(|e| e.len() - 1).collect(),
+        };
+        assert!(inputs.cols() == widths.iter().sum::<usize>(),
+                "Input columns do not match transformed columns.");
+
+        let in_cols = inputs.cols();
+        let mut data = Vec::with_capacity(inputs.rows() * edges.len());
+
+        for i in 0..inputs.rows() {
+            let mut offset = i * in_cols;
+            for (e, w) in edges.iter().zip(widths.iter()) {
+                let row = &inputs.data()[offset..offset + w];
+                let b = match self.encoding {
+                    BinEncoding::Ordinal => {
+                        let b = row[0].round();
+                        assert!(b >= 0f64 && (b as usize) < e.len() - 1,
+                                "Bin index is out of range.");
+                        b as usize
+                    }
+                    BinEncoding::OneHot => {
+                        row.iter()
+                           .enumerate()
+                           .fold((0, f64::NEG_INFINITY),
+                                 |(bi, bv), (k, &v)| if v > bv { (k, v) } else { (bi, bv) })
+                           .0
+                    }
+                };
+
+                data.push((e[b] + e[b + 1]) / 2f64);
+                offset += w;
+            }
+        }
+
+        Matrix::new(inputs.rows(), edges.len(), data)
+    }
+}
+
+/// Finds the bin containing the value, clamping to the end bins.
+fn find_bin(edges: &[f64], x: f64) -> usize {
+    let inner = &edges[1..edges.len() - 1];
+    inner.iter().take_while(|e| x >= **e).count()
+}
+
+fn uniform_edges(min: f64, max: f64, n: usize) -> Vec<f64> {
+    let width = (max - min) / n as f64;
+    (0..n + 1).map(|i| if i == n { max } else { min + i as f64 * width }).collect()
+}
+
+/// The linearly interpolated quantile of the sorted values.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let lower = pos.floor() as usize;
+    let upper = pos.ceil() as usize;
+    sorted[lower] + (pos - lower as f64) * (sorted[upper] - sorted[lower])
+}
+
+/// Edges halfway between the centroids of a one dimensional k-means.
+///
+/// The centroids start at the centers of the uniform bins, so the
+/// result is deterministic.
+fn kmeans_edges(sorted: &[f64], n: usize) -> Vec<f64> {
+    let min = sorted[0];
+    let max = sorted[sorted.len() - 1];
+
+    let uniform = uniform_edges(min, max, n);
+    let mut centers: Vec<f64> = uniform.windows(2).map(|w| (w[0] + w[1]) / 2f64).collect();
+
+    for _ in 0..100 {
+        let mut sums = vec![0f64; n];
+        let mut counts = vec![0usize; n];
+
+        for &x in sorted {
+            let c = centers.iter()
+                           .enumerate()
+                           .fold((0, f64::INFINITY), |(bc, bd), (k, c)| {
+                               let d = (x - c).abs();
+                               if d < bd { (k, d) } else { (bc, bd) }
+                           })
+                           .0;
+            sums[c] += x;
+            counts[c] += 1;
+        }
+
+        let next: Vec<f64> = centers.iter()
+                                    .enumerate()
+                                    .map(|(k, c)| {
+                                        if counts[k] > 0 { sums[k] / counts[k] as f64 } else { *c }
+                                    })
+                                    .collect();
+
+        let converged = next.iter().zip(centers.iter()).all(|(a, b)| (a - b).abs() < 1e-12);
+        centers = next;
+        if converged {
+            break;
+        }
+    }
+
+    centers.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let mut edges = vec![min];
+    edges.extend(centers.windows(2).map(|w| (w[0] + w[1]) / 2f64));
+    edges.push(max);
+    edges
+}
@@ -0,0 +1,358 @@
+//! Categorical encoders
+//!
+//! Maps columns of categorical values into numeric columns.
+//!
+//! The encoders accept matrices of any comparable category type,
+//! such as `String` or integer labels. The categories of each column
+//! are learned by `fit` and stored in sorted order.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::data::transforms::encode::OneHotEncoder;
+//! use rusty_machine::linalg::matrix::Matrix;
+//!
+//! let inputs = Matrix::new(3, 2, vec!["red", "small",
+//!                                     "green", "large",
+//!                                     "red", "large"]);
+//!
+//! let mut encoder = OneHotEncoder::default();
+//! encoder.fit(&inputs);
+//!
+//! // The columns are [green, red, large, small].
+//! let encoded = encoder.transform(&inputs);
+//!
+//! assert_eq!(*encoded.data(), vec![0.0, 1.0, 0.0, 1.0,
+//!                                  1.0, 0.0, 1.0, 0.0,
+//!                                  0.0, 1.0, 1.0, 0.0]);
+//! ```
+
+use std::cmp::Ordering;
+
+use data::transforms::Transformer;
+use linalg::matrix::Matrix;
+
+/// How to encode categories which were not seen during `fit`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UnknownCategory {
+    /// Panic when an unknown category is transformed.
+    Panic,
+    /// Encode unknown categories as all zeros in the one-hot encoding
+    /// and as -1 in the ordinal encoding.
+    Ignore,
+}
+
+/// The One-Hot Encoder
+///
+/// Replaces each categorical column with one indicator column per
+/// category.
+///
+/// Inputs with no columns are encoded as matrices with no columns.
+pub struct OneHotEncoder<T> {
+    unknown: UnknownCategory,
+    categories: Option<Vec<Vec<T>>>,
+}
+
+/// Constructs an encoder which panics on unknown categories.
+impl<T> Default for OneHotEncoder<T> {
+    fn default() -> OneHotEncoder<T> {
+        OneHotEncoder::new(UnknownCategory::Panic)
+    }
+}
+
+impl<T> OneHotEncoder<T> {
+    /// Constructs a new encoder with the given handling of unknown categories.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::data::transforms::encode::{OneHotEncoder, UnknownCategory};
+    /// use rusty_machine::linalg::matrix::Matrix;
+    ///
+    /// let mut encoder = OneHotEncoder::new(UnknownCategory::Ignore);
+    /// encoder.fit(&Matrix::new(2, 1, vec![1, 2]));
+    ///
+    /// let encoded = encoder.transform(&Matrix::new(2, 1, vec![2, 3]));
+    /// assert_eq!(*encoded.data(), vec![0.0, 1.0, 0.0, 0.0]);
+    /// ```
+    pub fn new(unknown: UnknownCategory) -> OneHotEncoder<T> {
+        OneHotEncoder {
+            unknown: unknown,
+            categories: None,
+        }
+    }
+
+    /// The sorted categories of each column learned by `fit`.
+    pub fn categories(&self) -> Option<&Vec<Vec<T>>> {
+        self.categories.as_ref()
+    }
+
+    /// The number of columns produced by `transform`.
+    ///
+    /// Returns None if the encoder has not been fitted.
+    pub fn encoded_cols(&self) -> Option<usize> {
+        self.categories.as_ref().map(|c| c.iter().map(|cats| cats.len()).sum())
+    }
+
+    fn fitted_categories(&self, cols: usize) -> &Vec<Vec<T>> {
+        match self.categories {
+            Some(ref c) => {
+                assert!(c.len() == cols, "Input columns do not match fitted columns.");
+                c
+            }
+            None => panic!("Transformer has not been fitted."),
+        }
+    }
+}
+
+impl<T: Clone + PartialOrd> OneHotEncoder<T> {
+    /// Learns the categories of each column.
+    ///
+    /// # Panics
+    ///
+    /// - Two of the categories cannot be compared.
+    pub fn fit(&mut self, inputs: &Matrix<T>) {
+        self.categories = Some(fit_categories(inputs));
+    }
+
+    /// Encodes the categorical inputs.
+    ///
+    /// # Panics
+    ///
+    /// - The transformer has not been fitted.
+    /// - The inputs have a different number of columns to the fitted data.
+    /// - An unknown category is found and unknown categories are not ignored.
+    pub fn transform(&self, inputs: &Matrix<T>) -> Matrix<f64> {
+        let categories = self.fitted_categories(inputs.cols());
+        let out_cols = categories.iter().map(|c| c.len()).sum();
+        let cols = inputs.cols();
+        let mut data = vec![0.0; inputs.rows() * out_cols];
+
+        // Rows are sliced by index as `chunks` rejects zero widths.
+        for i in 0..inputs.rows() {
+            let in_row = &inputs.data()[i * cols..(i + 1) * cols];
+            let out_row = &mut data[i * out_cols..(i + 1) * out_cols];
+            let mut offset = 0;
+            for (x, cats) in in_row.iter().zip(categories.iter()) {
+                if let Some(idx) = find_category(cats, x, self.unknown) {
+                    out_row[offset + idx] = 1.0;
+                }
+                offset += cats.len();
+            }
+        }
+
+        Matrix::new(inputs.rows(), out_cols, data)
+    }
+
+    /// Maps one-hot encoded columns back to their categories.
+    ///
+    /// The category with the largest value in each block of
+    /// columns is chosen.
+    ///
+    /// # Panics
+    ///
+    /// - The transformer has not been fitted.
+    /// - The inputs do not have the number of encoded columns.
+    /// - A block of columns is all zeros (an ignored unknown category).
+    pub fn inverse_transform(&self, inputs: &Matrix<f64>) -> Matrix<T> {
+        let categories = match self.categories {
+            Some(ref c) => c,
+            None => panic!("Transformer has not been fitted."),
+        };
+        assert!(Some(inputs.cols()) == self.encoded_cols(),
+                "Input columns do not match encoded columns.");
+
+        let cols = inputs.cols();
+        let mut data = Vec::with_capacity(inputs.rows() * categories.len());
+
+        for i in 0..inputs.rows() {
+            let row = &inputs.data()[i * cols..(i + 1) * cols];
+            let mut offset = 0;
+            for cats in categories {
+                let block = &row[offset..offset + cats.len()];
+                let (idx, max) = block.iter()
+                                      .enumerate()
+                                      .fold((0, 0.0), |(i, m), (j, x)| {
+                                          if *x > m { (j, *x) } else { (i, m) }
+                                      });
+                assert!(max > 0.0, "Cannot invert an unknown category.");
+
+                data.push(cats[idx].clone());
+                offset += cats.len();
+            }
+        }
+
+        Matrix::new(inputs.rows(), categories.len(), data)
+    }
+}
+
+/// Allows the encoding of numeric categorical columns alongside
+/// other transforms on `Matrix<f64>`.
+impl Transformer<Matrix<f64>> for OneHotEncoder<f64> {
+    fn fit(&mut self, inputs: &Matrix<f64>) {
+        OneHotEncoder::fit(self, inputs)
+    }
+
+    fn transform(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        OneHotEncoder::transform(self, inputs)
+    }
+
+    fn inverse_transform(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        OneHotEncoder::inverse_transform(self, inputs)
+    }
+}
+
+/// The Ordinal Encoder
+///
+/// Replaces each category with its index in the sorted
+/// categories of the column.
+pub struct OrdinalEncoder<T> {
+    unknown: UnknownCategory,
+    categories: Option<Vec<Vec<T>>>,
+}
+
+/// Constructs an encoder which panics on unknown categories.
+impl<T> Default for OrdinalEncoder<T> {
+    fn default() -> OrdinalEncoder<T> {
+        OrdinalEncoder::new(UnknownCategory::Panic)
+    }
+}
+
+impl<T> OrdinalEncoder<T> {
+    /// Constructs a new encoder with the given handling of unknown categories.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::data::transforms::encode::{OrdinalEncoder, UnknownCategory};
+    /// use rusty_machine::linalg::matrix::Matrix;
+    ///
+    /// let mut encoder = OrdinalEncoder::new(UnknownCategory::Ignore);
+    /// encoder.fit(&Matrix::new(3, 1, vec!["b", "a", "b"]));
+    ///
+    /// let encoded = encoder.transform(&Matrix::new(3, 1, vec!["a", "b", "c"]));
+    /// assert_eq!(*encoded.data(), vec![0.0, 1.0, -1.0]);
+    /// ```
+    pub fn new(unknown: UnknownCategory) -> OrdinalEncoder<T> {
+        OrdinalEncoder {
+            unknown: unknown,
+            categories: None,
+        }
+    }
+
+    /// The sorted categories of each column learned by `fit`.
+    pub fn categories(&self) -> Option<&Vec<Vec<T>>> {
+        self.categories.as_ref()
+    }
+
+    fn fitted_categories(&self, cols: usize) -> &Vec<Vec<T>> {
+        match self.categories {
+            Some(ref c) => {
+                assert!(c.len() == cols, "Input columns do not match fitted columns.");
+                c
+            }
+            None => panic!("Transformer has not been fitted."),
+        }
+    }
+}
+
+impl<T: Clone + PartialOrd> OrdinalEncoder<T> {
+    /// Learns the categories of each column.
+    ///
+    /// # Panics
+    ///
+    /// - Two of the categories cannot be compared.
+    pub fn fit(&mut self, inputs: &Matrix<T>) {
+        self.categories = Some(fit_categories(inputs));
+    }
+
+    /// Encodes the categorical inputs.
+    ///
+    /// # Panics
+    ///
+    /// - The transformer has not been fitted.
+    /// - The inputs have a different number of columns to the fitted data.
+    /// - An unknown category is found and unknown categories are not ignored.
+    pub fn transform(&self, inputs: &Matrix<T>) -> Matrix<f64> {
+        let categories = self.fitted_categories(inputs.cols());
+        let cols = inputs.cols();
+        let data = inputs.data();
+
+        Matrix::from_fn(inputs.rows(), cols, |i, j| {
+            match find_category(&categories[j], &data[i * cols + j], self.unknown) {
+                Some(idx) => idx as f64,
+                None => -1.0,
+            }
+        })
+    }
+
+    /// Maps ordinal encoded columns back to their categories.
+    ///
+    /// # Panics
+    ///
+    /// - The transformer has not been fitted.
+    /// - The inputs have a different number of columns to the fitted data.
+    /// - A value is not the index of a category.
+    pub fn inverse_transform(&self, inputs: &Matrix<f64>) -> Matrix<T> {
+        let categories = self.fitted_categories(inputs.cols());
+        let cols = inputs.cols();
+        let data = inputs.data();
+
+        Matrix::from_fn(inputs.rows(), cols, |i, j| {
+            let x = data[i * cols + j];
+            assert!(x >= 0.0 && x.fract() == 0.0 && (x as usize) < categories[j].len(),
+                    "Value is not the index of a category.");
+            categories[j][x as usize].clone()
+        })
+    }
+}
+
+/// Allows the encoding of numeric categorical columns alongside
+/// other transforms on `Matrix<f64>`.
+impl Transformer<Matrix<f64>> for OrdinalEncoder<f64> {
+    fn fit(&mut self, inputs: &Matrix<f64>) {
+        OrdinalEncoder::fit(self, inputs)
+    }
+
+    fn transform(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        OrdinalEncoder::transform(self, inputs)
+    }
+
+    fn inverse_transform(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        OrdinalEncoder::inverse_transform(self, inputs)
+    }
+}
+
+/// Collects the sorted unique categories of each column.
+fn fit_categories<T: Clone + PartialOrd>(inputs: &Matrix<T>) -> Vec<Vec<T>> {
+    let cols = inputs.cols();
+    let mut categories = vec![Vec::new(); cols];
+
+    for i in 0..inputs.rows() {
+        let row = &inputs.data()[i * cols..(i + 1) * cols];
+        for (x, cats) in row.iter().zip(categories.iter_mut()) {
+            cats.push(x.clone());
+        }
+    }
+
+    for cats in &mut categories {
+        cats.sort_by(|a, b| a.partial_cmp(b).expect("Categories must be comparable."));
+        cats.dedup_by(|a, b| a == b);
+    }
+
+    categories
+}
+
+/// Finds the index of the category, handling unknown categories.
+fn find_category<T: PartialOrd>(categories: &[T],
+                                x: &T,
+                                unknown: UnknownCategory)
+                                -> Option<usize> {
+    let idx = categories.binary_search_by(|c| c.partial_cmp(x).unwrap_or(Ordering::Less));
+
+    match (idx, unknown) {
+        (Ok(i), _) => Some(i),
+        (Err(_), UnknownCategory::Ignore) => None,
+        (Err(_), UnknownCategory::Panic) => panic!("Unknown category found in inputs."),
+    }
+}
@@ -0,0 +1,158 @@
+//! Min-max scaling transform
+//!
+//! Scales each column of the inputs linearly into a given range.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::data::transforms::Transformer;
+//! use rusty_machine::data::transforms::minmax::MinMaxScaler;
+//! use rusty_machine::linalg::matrix::Matrix;
+//!
+//! let inputs = Matrix::new(3, 2, vec![1.0, 10.0, 2.0, 20.0, 3.0, 40.0]);
+//!
+//! // Scale into the default range [0, 1].
+//! let mut scaler = MinMaxScaler::default();
+//! let scaled = scaler.fit_transform(&inputs);
+//!
+//! assert_eq!(*scaled.data(), vec![0.0, 0.0, 0.5, 1.0 / 3.0, 1.0, 1.0]);
+//! ```
+
+use data::transforms::Transformer;
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+
+use libnum::Float;
+
+/// The Min-Max Scaler
+///
+/// Maps the minimum of each column to the lower bound of the
+/// range and the maximum of each column to the upper bound.
+///
+/// Constant columns are mapped to the lower bound.
+pub struct MinMaxScaler<T: Float> {
+    min: T,
+    max: T,
+    data_mins: Option<Vector<T>>,
+    scales: Option<Vector<T>>,
+}
+
+/// Scales into the range [0, 1].
+impl<T: Float> Default for MinMaxScaler<T> {
+    fn default() -> MinMaxScaler<T> {
+        MinMaxScaler::new(T::zero(), T::one())
+    }
+}
+
+impl<T: Float> MinMaxScaler<T> {
+    /// Constructs a new scaler mapping columns into the range [min, max].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::data::transforms::minmax::MinMaxScaler;
+    ///
+    /// let scaler = MinMaxScaler::new(-1.0, 1.0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - min is not less than max.
+    pub fn new(min: T, max: T) -> MinMaxScaler<T> {
+        assert!(min < max, "The scaled minimum must be less than the maximum.");
+
+        MinMaxScaler {
+            min: min,
+            max: max,
+            data_mins: None,
+            scales: None,
+        }
+    }
+
+    fn params(&self, cols: usize) -> (&Vector<T>, &Vector<T>) {
+        match (self.data_mins.as_ref(), self.scales.as_ref()) {
+            (Some(m), Some(s)) => {
+                assert!(m.size() == cols,
+                        "Input columns do not match fitted columns.");
+                (m, s)
+            }
+            _ => panic!("Transformer has not been fitted."),
+        }
+    }
+}
+
+impl<T: Float> Transformer<Matrix<T>> for MinMaxScaler<T> {
+    /// Computes the minimum and maximum of each column.
+    ///
+    /// # Panics
+    ///
+    /// - The inputs have no rows.
+    /// - The inputs have no columns.
+    fn fit(&mut self, inputs: &Matrix<T>) {
+        assert!(inputs.rows() > 0, "Cannot scale inputs with no rows.");
+        assert!(inputs.cols() > 0, "Cannot scale inputs with no columns.");
+
+        let cols = inputs.cols();
+        let mut mins = inputs.data()[..cols].to_vec();
+        let mut maxs = mins.clone();
+
+        for row in inputs.data().chunks(cols).skip(1) {
+            for (j, x) in row.iter().enumerate() {
+                mins[j] = mins[j].min(*x);
+                maxs[j] = maxs[j].max(*x);
+            }
+        }
+
+        let scales = mins.iter()
+                         .zip(maxs.iter())
+                         .map(|(a, b)| {
+                             if b > a {
+                                 (self.max - self.min) / (*b - *a)
+                             } else {
+                                 T::zero()
+                             }
+                         })
+                         .collect();
+
+        self.data_mins = Some(Vector::new(mins));
+        self.scales = Some(scales);
+    }
+
+    /// Scales the inputs into the range.
+    ///
+    /// # Panics
+    ///
+    /// - The transformer has not been fitted.
+    /// - The inputs have a different number of columns to the fitted data.
+    fn transform(&self, inputs: &Matrix<T>) -> Matrix<T> {
+        let (mins, scales) = self.params(inputs.cols());
+        let data = inputs.data();
+        let cols = inputs.cols();
+
+        Matrix::from_fn(inputs.rows(), cols, |i, j| {
+            (data[i * cols + j] - mins[j]) * scales[j] + self.min
+        })
+    }
+
+    /// Maps scaled inputs back to the original range.
+    ///
+    /// Constant columns are mapped back to their fitted value.
+    ///
+    /// # Panics
+    ///
+    /// - The transformer has not been fitted.
+    /// - The inputs have a different number of columns to the fitted data.
+    fn inverse_transform(&self, inputs: &Matrix<T>) -> Matrix<T> {
+        let (mins, scales) = self.params(inputs.cols());
+        let data = inputs.data();
+        let cols = inputs.cols();
+
+        Matrix::from_fn(inputs.rows(), cols, |i, j| {
+            if scales[j] == T::zero() {
+                mins[j]
+            } else {
+                (data[i * cols + j] - self.min) / scales[j] + mins[j]
+            }
+        })
+    }
+}
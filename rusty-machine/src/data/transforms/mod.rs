@@ -0,0 +1,57 @@
+//! Data transforms
+//!
+//! Contains the `Transformer` trait and implementations of common
+//! preprocessing transforms.
+//!
+//! Transformers learn their parameters from a set of inputs using `fit`
+//! and can then be applied to any inputs with the same structure. Most
+//! models in this crate train far better on features which have been
+//! transformed to a common scale.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::data::transforms::Transformer;
+//! use rusty_machine::data::transforms::standardize::StandardScaler;
+//! use rusty_machine::linalg::matrix::Matrix;
+//!
+//! let inputs = Matrix::new(3, 2, vec![1.0, 10.0, 2.0, 20.0, 3.0, 30.0]);
+//!
+//! let mut scaler = StandardScaler::default();
+//! let scaled = scaler.fit_transform(&inputs);
+//!
+//! // Each column now has zero mean.
+//! assert_eq!(*scaled.mean(0).data(), vec![0.0, 0.0]);
+//!
+//! // And the transform can be reversed.
+//! let original = scaler.inverse_transform(&scaled);
+//! assert_eq!(original.data(), inputs.data());
+//! ```
+
+pub mod standardize;
+pub mod minmax;
+pub mod encode;
+pub mod column;
+pub mod poly;
+pub mod select;
+pub mod discretize;
+pub mod whiten;
+pub mod target;
+
+/// Trait for data transformers.
+pub trait Transformer<T> {
+    /// Learns the transform parameters from the inputs.
+    fn fit(&mut self, inputs: &T);
+
+    /// Applies the fitted transform to the inputs.
+    fn transform(&self, inputs: &T) -> T;
+
+    /// Reverses the fitted transform.
+    fn inverse_transform(&self, inputs: &T) -> T;
+
+    /// Fits the transform to the inputs and then transforms them.
+    fn fit_transform(&mut self, inputs: &T) -> T {
+        self.fit(inputs);
+        self.transform(inputs)
+    }
+}
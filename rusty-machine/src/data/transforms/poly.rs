@@ -0,0 +1,191 @@
+//! Polynomial feature expansion
+//!
+//! Generates polynomial and interaction terms from the input columns.
+//! Linear models trained on these features can fit nonlinear
+//! decision boundaries.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::data::transforms::Transformer;
+//! use rusty_machine::data::transforms::poly::PolynomialFeatures;
+//! use rusty_machine::linalg::matrix::Matrix;
+//!
+//! let inputs = Matrix::new(2, 2, vec![2.0, 3.0, 4.0, 5.0]);
+//!
+//! let mut poly = PolynomialFeatures::new(2);
+//! let outputs = poly.fit_transform(&inputs);
+//!
+//! // 1, a, b, a^2, ab, b^2
+//! assert_eq!(*outputs.data(), vec![1.0, 2.0, 3.0, 4.0, 6.0, 9.0,
+//!                                  1.0, 4.0, 5.0, 16.0, 20.0, 25.0]);
+//! ```
+
+use data::transforms::Transformer;
+use linalg::matrix::Matrix;
+
+/// The Polynomial Features transform
+///
+/// Produces every product of at most `degree` input columns. The
+/// terms are ordered by degree, and then lexicographically by the
+/// columns used.
+#[derive(Clone, Debug)]
+pub struct PolynomialFeatures {
+    degree: usize,
+    interaction_only: bool,
+    include_bias: bool,
+    input_cols: Option<usize>,
+    terms: Vec<Vec<usize>>,
+}
+
+impl PolynomialFeatures {
+    /// Constructs a transform with terms up to the given degree.
+    ///
+    /// The bias column of ones is included.
+    ///
+    /// # Panics
+    ///
+    /// - The degree is zero.
+    pub fn new(degree: usize) -> PolynomialFeatures {
+        assert!(degree > 0, "Degree must be greater than zero.");
+
+        PolynomialFeatures {
+            degree: degree,
+            interaction_only: false,
+            include_bias: true,
+            input_cols: None,
+            terms: Vec::new(),
+        }
+    }
+
+    /// Only produces products of distinct columns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::data::transforms::Transformer;
+    /// use rusty_machine::data::transforms::poly::PolynomialFeatures;
+    /// use rusty_machine::linalg::matrix::Matrix;
+    ///
+    /// let inputs = Matrix::new(1, 3, vec![2.0, 3.0, 5.0]);
+    ///
+    /// let mut poly = PolynomialFeatures::new(3).interaction_only().include_bias(false);
+    /// let outputs = poly.fit_transform(&inputs);
+    ///
+    /// // a, b, c, ab, ac, bc, abc
+    /// assert_eq!(*outputs.data(), vec![2.0, 3.0, 5.0, 6.0, 10.0, 15.0, 30.0]);
+    /// ```
+    pub fn interaction_only(mut self) -> PolynomialFeatures {
+        self.interaction_only = true;
+        self
+    }
+
+    /// Sets whether the bias column of ones is included.
+    pub fn include_bias(mut self, include_bias: bool) -> PolynomialFeatures {
+        self.include_bias = include_bias;
+        self
+    }
+
+    /// The input columns multiplied to produce each output column.
+    ///
+    /// The bias term has no columns.
+    ///
+    /// # Panics
+    ///
+    /// - The transformer has not been fitted.
+    pub fn terms(&self) -> &[Vec<usize>] {
+        self.check_fitted(None);
+        &self.terms
+    }
+
+    fn check_fitted(&self, cols: Option<usize>) {
+        match self.input_cols {
+            Some(c) => {
+                if let Some(cols) = cols {
+                    assert!(c == cols, "Input columns do not match fitted columns.");
+                }
+            }
+            None => panic!("Transformer has not been fitted."),
+        }
+    }
+}
+
+impl Transformer<Matrix<f64>> for PolynomialFeatures {
+    /// Generates the terms for the number of input columns.
+    fn fit(&mut self, inputs: &Matrix<f64>) {
+        let cols = inputs.cols();
+        let mut terms = Vec::new();
+
+        if self.include_bias {
+            terms.push(Vec::new());
+        }
+
+        // The terms of the previous degree, extended by one column each time.
+        let mut last: Vec<Vec<usize>> = vec![Vec::new()];
+        for _ in 0..self.degree {
+            let mut next = Vec::new();
+            for term in &last {
+                let start = match term.last() {
+                    Some(&c) if self.interaction_only => c + 1,
+                    Some(&c) => c,
+                    None => 0,
+                };
+
+                for c in start..cols {
+                    let mut t = term.clone();
+                    t.push(c);
+                    next.push(t);
+                }
+            }
+
+            terms.extend(next.iter().cloned());
+            last = next;
+        }
+
+        self.input_cols = Some(cols);
+        self.terms = terms;
+    }
+
+    /// Computes the polynomial terms of the inputs.
+    ///
+    /// # Panics
+    ///
+    /// - The transformer has not been fitted.
+    /// - The inputs have a different number of columns to the fitted data.
+    fn transform(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        self.check_fitted(Some(inputs.cols()));
+
+        let cols = inputs.cols();
+        let mut data = Vec::with_capacity(inputs.rows() * self.terms.len());
+
+        for i in 0..inputs.rows() {
+            let row = &inputs.data()[i * cols..(i + 1) * cols];
+            for term in &self.terms {
+                data.push(term.iter().fold(1f64, |acc, &c| acc * row[c]));
+            }
+        }
+
+        Matrix::new(inputs.rows(), self.terms.len(), data)
+    }
+
+    /// Recovers the inputs from the degree one terms.
+    ///
+    /// # Panics
+    ///
+    /// - The transformer has not been fitted.
+    /// - The inputs do not have the number of transformed columns.
+    fn inverse_transform(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        self.check_fitted(None);
+        assert!(inputs.cols() == self.terms.len(),
+                "Input columns do not match transformed columns.");
+
+        let linear: Vec<usize> = self.terms
+                                     .iter()
+                                     .enumerate()
+                                     .filter(|&(_, t)| t.len() == 1)
+                                     .map(|(i, _)| i)
+                                     .collect();
+
+        inputs.select_cols(&linear)
+    }
+}
@@ -0,0 +1,653 @@
+//! Feature selection
+//!
+//! Transforms which keep a subset of the input columns.
+//!
+//! - `VarianceThreshold` drops columns with low variance.
+//! - `SelectKBest` keeps the columns with the highest univariate
+//!   scores against class targets.
+//! - `Rfe` recursively eliminates the columns with the smallest
+//!   coefficients in a trained model.
+//!
+//! Each selector exposes the indices of the kept columns through
+//! `selected`. The inverse transforms place the kept columns back in
+//! their original position and fill the removed columns with zeros.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::data::transforms::select::{SelectKBest, ScoreFunc};
+//! use rusty_machine::linalg::matrix::Matrix;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! // Only the second column separates the classes.
+//! let inputs = Matrix::new(4, 3, vec![1.0, 0.0, 5.0,
+//!                                     2.0, 0.1, 5.0,
+//!                                     1.0, 3.0, 5.0,
+//!                                     2.0, 3.1, 5.0]);
+//! let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+//!
+//! let mut select = SelectKBest::new(1, ScoreFunc::FClassif);
+//! let outputs = select.fit_transform(&inputs, &targets);
+//!
+//! assert_eq!(select.selected(), &[1]);
+//! assert_eq!(*outputs.data(), vec![0.0, 0.1, 3.0, 3.1]);
+//! ```
+
+use std::cmp;
+use std::f64;
+
+use data::split::class_indices;
+use data::transforms::Transformer;
+use learning::{ModelParams, SupModel};
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+
+/// The Variance Threshold selector
+///
+/// Keeps the columns whose (sample) variance is strictly greater
+/// than the threshold. The default threshold of zero removes constant
+/// columns.
+#[derive(Clone)]
+pub struct VarianceThreshold {
+    threshold: f64,
+    variances: Option<Vector<f64>>,
+    selection: Selection,
+}
+
+impl Default for VarianceThreshold {
+    fn default() -> VarianceThreshold {
+        VarianceThreshold::new(0f64)
+    }
+}
+
+impl VarianceThreshold {
+    /// Constructs a selector with the given variance threshold.
+    ///
+    /// # Panics
+    ///
+    /// - The threshold is negative.
+    pub fn new(threshold: f64) -> VarianceThreshold {
+        assert!(threshold >= 0f64, "Threshold must be non-negative.");
+
+        VarianceThreshold {
+            threshold: threshold,
+            variances: None,
+            selection: Selection::default(),
+        }
+    }
+
+    /// The column variances learned by `fit`.
+    pub fn variances(&self) -> Option<&Vector<f64>> {
+        self.variances.as_ref()
+    }
+
+    /// The indices of the kept columns.
+    ///
+    /// # Panics
+    ///
+    /// - The transformer has not been fitted.
+    pub fn selected(&self) -> &[usize] {
+        self.selection.selected()
+    }
+}
+
+impl Transformer<Matrix<f64>> for VarianceThreshold {
+    /// Computes the column variances and selects the columns above
+    /// the threshold.
+    ///
+    /// # Panics
+    ///
+    /// - The inputs have fewer than two rows.
+    /// - No column has a variance above the threshold.
+    fn fit(&mut self, inputs: &Matrix<f64>) {
+        assert!(inputs.rows() > 1,
+                "Cannot compute variance of inputs with fewer than two rows.");
+
+        let variances = inputs.variance(0);
+        let selected: Vec<usize> = variances.data()
+                                            .iter()
+                                            .enumerate()
+                                            .filter(|&(_, v)| *v > self.threshold)
+                                            .map(|(i, _)| i)
+                                            .collect();
+
+        assert!(!selected.is_empty(), "No column has a variance above the threshold.");
+
+        self.selection = Selection::new(inputs.cols(), selected);
+        self.variances = Some(variances);
+    }
+
+    /// Keeps the selected columns of the inputs.
+    ///
+    /// # Panics
+    ///
+    /// - The transformer has not been fitted.
+    /// - The inputs have a different number of columns to the fitted data.
+    fn transform(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        self.selection.transform(inputs)
+    }
+
+    /// Restores the removed columns as zeros.
+    ///
+    /// # Panics
+    ///
+    /// - The transformer has not been fitted.
+    /// - The inputs do not have the number of selected columns.
+    fn inverse_transform(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        self.selection.inverse_transform(inputs)
+    }
+}
+
+/// Univariate scores of the input columns against class targets.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScoreFunc {
+    /// The chi-squared statistic. See `chi2`.
+    ChiSquared,
+    /// The ANOVA F-value. See `f_classif`.
+    FClassif,
+    /// The mutual information using the given number of bins. See
+    /// `mutual_info`.
+    MutualInfo(usize),
+}
+
+impl ScoreFunc {
+    /// Scores each column of the inputs against the targets.
+    pub fn score(&self, inputs: &Matrix<f64>, targets: &Vector<f64>) -> Vector<f64> {
+        match *self {
+            ScoreFunc::ChiSquared => chi2(inputs, targets),
+            ScoreFunc::FClassif => f_classif(inputs, targets),
+            ScoreFunc::MutualInfo(bins) => mutual_info(inputs, targets, bins),
+        }
+    }
+}
+
+/// The Select K Best selector
+///
+/// Keeps the `k` columns with the highest scores against the
+/// targets. Ties are broken in favour of the earlier column.
+///
+/// As the selector needs the targets to be fitted it does not
+/// implement `Transformer`.
+#[derive(Clone)]
+pub struct SelectKBest {
+    k: usize,
+    score_func: ScoreFunc,
+    scores: Option<Vector<f64>>,
+    selection: Selection,
+}
+
+impl SelectKBest {
+    /// Constructs a selector keeping `k` columns ranked by the score
+    /// function.
+    ///
+    /// # Panics
+    ///
+    /// - k is zero.
+    pub fn new(k: usize, score_func: ScoreFunc) -> SelectKBest {
+        assert!(k > 0, "Must select at least one column.");
+
+        SelectKBest {
+            k: k,
+            score_func: score_func,
+            scores: None,
+            selection: Selection::default(),
+        }
+    }
+
+    /// The column scores learned by `fit`.
+    pub fn scores(&self) -> Option<&Vector<f64>> {
+        self.scores.as_ref()
+    }
+
+    /// The indices of the kept columns in ascending order.
+    ///
+    /// # Panics
+    ///
+    /// - The selector has not been fitted.
+    pub fn selected(&self) -> &[usize] {
+        self.selection.selected()
+    }
+
+    /// Scores the columns and selects the `k` best.
+    ///
+    /// # Panics
+    ///
+    /// - The number of targets does not match the number of input rows.
+    /// - k is greater than the number of columns.
+    pub fn fit(&mut self, inputs: &Matrix<f64>, targets: &Vector<f64>) {
+        assert!(self.k <= inputs.cols(),
+                "Cannot select more columns than the inputs have.");
+
+        let scores = self.score_func.score(inputs, targets);
+
+        let mut ranked: Vec<usize> = (0..inputs.cols()).collect();
+        ranked.sort_by(|&a, &b| {
+            let (a, b) = (nan_to_min(scores[a]), nan_to_min(scores[b]));
+            b.partial_cmp(&a).unwrap()
+        });
+        ranked.truncate(self.k);
+        ranked.sort();
+
+        self.selection = Selection::new(inputs.cols(), ranked);
+        self.scores = Some(scores);
+    }
+
+    /// Keeps the selected columns of the inputs.
+    ///
+    /// # Panics
+    ///
+    /// - The selector has not been fitted.
+    /// - The inputs have a different number of columns to the fitted data.
+    pub fn transform(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        self.selection.transform(inputs)
+    }
+
+    /// Restores the removed columns as zeros.
+    ///
+    /// # Panics
+    ///
+    /// - The selector has not been fitted.
+    /// - The inputs do not have the number of selected columns.
+    pub fn inverse_transform(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        self.selection.inverse_transform(inputs)
+    }
+
+    /// Fits the selector and then transforms the inputs.
+    pub fn fit_transform(&mut self, inputs: &Matrix<f64>, targets: &Vector<f64>) -> Matrix<f64> {
+        self.fit(inputs, targets);
+        self.transform(inputs)
+    }
+}
+
+/// The Recursive Feature Elimination selector
+///
+/// Starting from every column, repeatedly trains a model on the
+/// remaining columns and removes the `step` columns with the smallest
+/// coefficient magnitudes until `n_features` columns remain.
+///
+/// Any `SupModel` whose `ModelParams` expose a `"coefficients"` group
+/// with one row per input column can be used, such as the linear and
+/// logistic regressors. Multi-output coefficients are ranked by the
+/// sum of their magnitudes in each row. The magnitudes depend on the
+/// scale of the columns, so the inputs should usually be standardized
+/// first.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::data::transforms::select::Rfe;
+/// use rusty_machine::learning::lin_reg::LinRegressor;
+/// use rusty_machine::linalg::matrix::Matrix;
+/// use rusty_machine::linalg::vector::Vector;
+///
+/// // The targets only depend on the first column.
+/// let inputs = Matrix::new(6, 2, vec![1.0, 0.3, 2.0, -0.2, 3.0, 0.8,
+///                                     4.0, 0.1, 5.0, -0.5, 6.0, 0.4]);
+/// let targets = Vector::new(vec![2.0, 4.0, 6.0, 8.0, 10.0, 12.0]);
+///
+/// let mut rfe = Rfe::new(1);
+/// rfe.fit(LinRegressor::default, &inputs, &targets);
+///
+/// assert_eq!(rfe.selected(), &[0]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Rfe {
+    n_features: usize,
+    step: usize,
+    ranking: Option<Vec<usize>>,
+    selection: Selection,
+}
+
+impl Rfe {
+    /// Constructs a selector which keeps `n_features` columns,
+    /// removing one column at a time.
+    ///
+    /// # Panics
+    ///
+    /// - n_features is zero.
+    pub fn new(n_features: usize) -> Rfe {
+        assert!(n_features > 0, "Must select at least one column.");
+
+        Rfe {
+            n_features: n_features,
+            step: 1,
+            ranking: None,
+            selection: Selection::default(),
+        }
+    }
+
+    /// Sets the number of columns removed after each training.
+    ///
+    /// The last elimination removes fewer columns if needed to keep
+    /// exactly `n_features`.
+    ///
+    /// # Panics
+    ///
+    /// - step is zero.
+    pub fn step(mut self, step: usize) -> Rfe {
+        assert!(step > 0, "Must remove at least one column per step.");
+        self.step = step;
+        self
+    }
+
+    /// The ranking of each column learned by `fit`.
+    ///
+    /// Selected columns have rank 1, the columns removed by the last
+    /// elimination have rank 2 and so on.
+    pub fn ranking(&self) -> Option<&Vec<usize>> {
+        self.ranking.as_ref()
+    }
+
+    /// The indices of the kept columns in ascending order.
+    ///
+    /// # Panics
+    ///
+    /// - The selector has not been fitted.
+    pub fn selected(&self) -> &[usize] {
+        self.selection.selected()
+    }
+
+    /// Eliminates columns until `n_features` remain.
+    ///
+    /// Each elimination trains a new model created by `model_builder`
+    /// on the remaining columns.
+    ///
+    /// # Panics
+    ///
+    /// - The number of targets does not match the number of input rows.
+    /// - n_features is greater than the number of columns.
+    /// - The trained model has no `"coefficients"` group with one row
+    ///   per remaining column.
+    pub fn fit<M, B>(&mut self, model_builder: B, inputs: &Matrix<f64>, targets: &Vector<f64>)
+        where M: SupModel<Matrix<f64>, Vector<f64>> + ModelParams,
+              B: Fn() -> M
+    {
+        assert!(inputs.rows() == targets.size(),
+                "Inputs and targets have different lengths.");
+        assert!(self.n_features <= inputs.cols(),
+                "Cannot select more columns than the inputs have.");
+
+        let mut remaining: Vec<usize> = (0..inputs.cols()).collect();
+        let mut eliminated = Vec::new();
+
+        while remaining.len() > self.n_features {
+            let mut model = model_builder();
+            model.train(&inputs.select_cols(&remaining), targets);
+
+            let importances = coef_importances(&model, remaining.len());
+            let mut order: Vec<usize> = (0..remaining.len()).collect();
+            order.sort_by(|&a, &b| importances[a].partial_cmp(&importances[b]).unwrap());
+
+            let n_remove = cmp::min(self.step, remaining.len() - self.n_features);
+            let mut removed: Vec<usize> = order[..n_remove].iter().map(|&i| remaining[i]).collect();
+            removed.sort();
+
+            remaining.retain(|c| removed.binary_search(c).is_err());
+            eliminated.push(removed);
+        }
+
+        let mut ranking = vec![1; inputs.cols()];
+        for (rank, removed) in eliminated.iter().rev().enumerate() {
+            for &c in removed {
+                ranking[c] = rank + 2;
+            }
+        }
+
+        self.selection = Selection::new(inputs.cols(), remaining);
+        self.ranking = Some(ranking);
+    }
+
+    /// Keeps the selected columns of the inputs.
+    ///
+    /// # Panics
+    ///
+    /// - The selector has not been fitted.
+    /// - The inputs have a different number of columns to the fitted data.
+    pub fn transform(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        self.selection.transform(inputs)
+    }
+
+    /// Restores the removed columns as zeros.
+    ///
+    /// # Panics
+    ///
+    /// - The selector has not been fitted.
+    /// - The inputs do not have the number of selected columns.
+    pub fn inverse_transform(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        self.selection.inverse_transform(inputs)
+    }
+}
+
+/// Computes the chi-squared statistic of each column against the
+/// class targets.
+///
+/// The columns are treated as counts or frequencies, such as the
+/// outputs of the text vectorizers.
+///
+/// # Panics
+///
+/// - The number of targets does not match the number of input rows.
+/// - The inputs contain negative values.
+pub fn chi2(inputs: &Matrix<f64>, targets: &Vector<f64>) -> Vector<f64> {
+    assert!(inputs.rows() == targets.size(),
+            "Inputs and targets have different lengths.");
+    assert!(inputs.data().iter().all(|x| *x >= 0f64),
+            "Chi-squared scores require non-negative inputs.");
+
+    let groups = class_indices(targets.data());
+    let totals = inputs.sum_rows();
+    let n = inputs.rows() as f64;
+
+    let mut scores = vec![0f64; inputs.cols()];
+    for g in &groups {
+        let observed = inputs.select_rows(g).sum_rows();
+        let frac = g.len() as f64 / n;
+
+        for (j, s) in scores.iter_mut().enumerate() {
+            let expected = frac * totals[j];
+            if expected > 0f64 {
+                *s += (observed[j] - expected).powi(2) / expected;
+            }
+        }
+    }
+
+    Vector::new(scores)
+}
+
+/// Computes the one-way ANOVA F-value of each column against the
+/// class targets.
+///
+/// Columns which are constant within every class but differ between
+/// classes have an infinite score.
+///
+/// # Panics
+///
+/// - The number of targets does not match the number of input rows.
+/// - The targets have fewer than two classes.
+/// - There are no more samples than classes.
+pub fn f_classif(inputs: &Matrix<f64>, targets: &Vector<f64>) -> Vector<f64> {
+    assert!(inputs.rows() == targets.size(),
+            "Inputs and targets have different lengths.");
+
+    let groups = class_indices(targets.data());
+    let n_classes = groups.len();
+    assert!(n_classes > 1, "Targets must have at least two classes.");
+    assert!(inputs.rows() > n_classes, "Must have more samples than classes.");
+
+    let means = inputs.mean(0);
+    let mut between = vec![0f64; inputs.cols()];
+    let mut within = vec![0f64; inputs.cols()];
+
+    for g in &groups {
+        let class_inputs = inputs.select_rows(g);
+        let class_means = class_inputs.mean(0);
+        let cols = inputs.cols();
+
+        for j in 0..cols {
+            between[j] += g.len() as f64 * (class_means[j] - means[j]).powi(2);
+            within[j] += class_inputs.data()
+                                     .iter()
+                                     .skip(j)
+                                     .step_by(cols)
+                                     .map(|x| (x - class_means[j]).powi(2))
+                                     .sum::<f64>();
+        }
+    }
+
+    let df_between = (n_classes - 1) as f64;
+    let df_within = (inputs.rows() - n_classes) as f64;
+
+    let scores = between.iter()
+                        .zip(within.iter())
+                        .map(|(b, w)| {
+                            if *w > 0f64 {
+                                (b / df_between) / (w / df_within)
+                            } else if *b > 0f64 {
+                                f64::INFINITY
+                            } else {
+                                0f64
+                            }
+                        })
+                        .collect();
+
+    Vector::new(scores)
+}
+
+/// Computes the mutual information (in nats) between each column and
+/// the class targets.
+///
+/// Each column is discretized into `bins` bins of equal width over its
+/// range before the information is computed.
+///
+/// # Panics
+///
+/// - The number of targets does not match the number of input rows.
+/// - bins is zero.
+pub fn mutual_info(inputs: &Matrix<f64>, targets: &Vector<f64>, bins: usize) -> Vector<f64> {
+    assert!(inputs.rows() == targets.size(),
+            "Inputs and targets have different lengths.");
+    assert!(bins > 0, "Must use at least one bin.");
+
+    let groups = class_indices(targets.data());
+    let mut labels = vec![0; inputs.rows()];
+    for (c, g) in groups.iter().enumerate() {
+        for &i in g {
+            labels[i] = c;
+        }
+    }
+
+    let n = inputs.rows() as f64;
+    let cols = inputs.cols();
+
+    let scores = (0..cols)
+                     .map(|j| {
+                         let col: Vec<f64> = inputs.data()
+                                                   .iter()
+                                                   .skip(j)
+                                                   .step_by(cols)
+                                                   .cloned()
+                                                   .collect();
+                         let min = col.iter().cloned().fold(f64::INFINITY, f64::min);
+                         let max = col.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                         let width = (max - min) / bins as f64;
+
+                         let mut joint = vec![vec![0f64; groups.len()]; bins];
+                         for (x, &c) in col.iter().zip(labels.iter()) {
+                             let b = if width > 0f64 {
+                                 (((x - min) / width) as usize).min(bins - 1)
+                             } else {
+                                 0
+                             };
+                             joint[b][c] += 1f64;
+                         }
+
+                         let mut info = 0f64;
+                         for row in &joint {
+                             let p_bin = row.iter().sum::<f64>() / n;
+                             for (c, count) in row.iter().enumerate() {
+                                 if *count > 0f64 {
+                                     let p_joint = count / n;
+                                     let p_class = groups[c].len() as f64 / n;
+                                     info += p_joint * (p_joint / (p_bin * p_class)).ln();
+                                 }
+                             }
+                         }
+                         info
+                     })
+                     .collect();
+
+    Vector::new(scores)
+}
+
+/// The columns kept by a fitted selector.
+#[derive(Clone, Debug, Default)]
+struct Selection {
+    input_cols: Option<usize>,
+    selected: Vec<usize>,
+}
+
+impl Selection {
+    fn new(input_cols: usize, selected: Vec<usize>) -> Selection {
+        Selection {
+            input_cols: Some(input_cols),
+            selected: selected,
+        }
+    }
+
+    fn selected(&self) -> &[usize] {
+        self.input_cols();
+        &self.selected
+    }
+
+    fn input_cols(&self) -> usize {
+        match self.input_cols {
+            Some(c) => c,
+            None => panic!("Transformer has not been fitted."),
+        }
+    }
+
+    fn transform(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        assert!(self.input_cols() == inputs.cols(),
+                "Input columns do not match fitted columns.");
+        inputs.select_cols(&self.selected)
+    }
+
+    fn inverse_transform(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        let cols = self.input_cols();
+        assert!(inputs.cols() == self.selected.len(),
+                "Input columns do not match selected columns.");
+
+        let mut data = vec![0f64; inputs.rows() * cols];
+        for i in 0..inputs.rows() {
+            for (k, &j) in self.selected.iter().enumerate() {
+                data[i * cols + j] = inputs[[i, k]];
+            }
+        }
+
+        Matrix::new(inputs.rows(), cols, data)
+    }
+}
+
+/// The summed coefficient magnitudes of each input column.
+///
+/// NaN coefficients rank below every other column.
+fn coef_importances<M: ModelParams>(model: &M, cols: usize) -> Vec<f64> {
+    let groups = model.param_groups();
+    let coefs = match groups.iter().find(|g| g.name == "coefficients") {
+        Some(g) => &g.values,
+        None => panic!("Model does not expose coefficients."),
+    };
+    assert!(coefs.rows() == cols,
+            "Coefficients do not match the input columns.");
+
+    (0..cols)
+        .map(|i| {
+            let row = &coefs.data()[i * coefs.cols()..(i + 1) * coefs.cols()];
+            nan_to_min(row.iter().map(|x| x.abs()).sum())
+        })
+        .collect()
+}
+
+fn nan_to_min(x: f64) -> f64 {
+    if x.is_nan() { f64::NEG_INFINITY } else { x }
+}
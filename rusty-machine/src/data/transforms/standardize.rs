@@ -0,0 +1,126 @@
+//! Standardization transform
+//!
+//! Scales each column of the inputs to have zero mean and
+//! unit variance.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::data::transforms::Transformer;
+//! use rusty_machine::data::transforms::standardize::StandardScaler;
+//! use rusty_machine::linalg::matrix::Matrix;
+//!
+//! let inputs = Matrix::new(2, 2, vec![-1.0, 2.0, 1.0, 4.0]);
+//!
+//! let mut scaler = StandardScaler::default();
+//! scaler.fit(&inputs);
+//!
+//! let scaled = scaler.transform(&inputs);
+//! ```
+
+use data::transforms::Transformer;
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+
+use libnum::{Float, FromPrimitive};
+
+/// The Standard Scaler
+///
+/// Subtracts the column means and divides by the column
+/// (sample) standard deviations.
+///
+/// Columns with zero variance are only centered.
+pub struct StandardScaler<T: Float> {
+    means: Option<Vector<T>>,
+    std_devs: Option<Vector<T>>,
+}
+
+impl<T: Float> Default for StandardScaler<T> {
+    fn default() -> StandardScaler<T> {
+        StandardScaler {
+            means: None,
+            std_devs: None,
+        }
+    }
+}
+
+impl<T: Float> StandardScaler<T> {
+    /// The column means learned by `fit`.
+    pub fn means(&self) -> Option<&Vector<T>> {
+        self.means.as_ref()
+    }
+
+    /// The column standard deviations learned by `fit`.
+    ///
+    /// Zero standard deviations are stored as one.
+    pub fn std_devs(&self) -> Option<&Vector<T>> {
+        self.std_devs.as_ref()
+    }
+
+    fn params(&self, cols: usize) -> (&Vector<T>, &Vector<T>) {
+        match (self.means.as_ref(), self.std_devs.as_ref()) {
+            (Some(m), Some(s)) => {
+                assert!(m.size() == cols,
+                        "Input columns do not match fitted columns.");
+                (m, s)
+            }
+            _ => panic!("Transformer has not been fitted."),
+        }
+    }
+}
+
+impl<T: Float + FromPrimitive> Transformer<Matrix<T>> for StandardScaler<T> {
+    /// Computes the column means and standard deviations.
+    ///
+    /// # Panics
+    ///
+    /// - The inputs have fewer than two rows.
+    fn fit(&mut self, inputs: &Matrix<T>) {
+        assert!(inputs.rows() > 1,
+                "Cannot standardize inputs with fewer than two rows.");
+
+        let std_devs = inputs.variance(0)
+                             .data()
+                             .iter()
+                             .map(|v| {
+                                 let s = v.sqrt();
+                                 if s > T::zero() { s } else { T::one() }
+                             })
+                             .collect();
+
+        self.means = Some(inputs.mean(0));
+        self.std_devs = Some(std_devs);
+    }
+
+    /// Standardizes the inputs.
+    ///
+    /// # Panics
+    ///
+    /// - The transformer has not been fitted.
+    /// - The inputs have a different number of columns to the fitted data.
+    fn transform(&self, inputs: &Matrix<T>) -> Matrix<T> {
+        let (means, std_devs) = self.params(inputs.cols());
+        let data = inputs.data();
+        let cols = inputs.cols();
+
+        Matrix::from_fn(inputs.rows(), cols, |i, j| {
+            (data[i * cols + j] - means[j]) / std_devs[j]
+        })
+    }
+
+    /// Reverses the standardization.
+    ///
+    /// # Panics
+    ///
+    /// - The transformer has not been fitted.
+    /// - The inputs have a different number of columns to the fitted data.
+    fn inverse_transform(&self, inputs: &Matrix<T>) -> Matrix<T> {
+        let (means, std_devs) = self.params(inputs.cols());
+        let data = inputs.data();
+        let cols = inputs.cols();
+
+        Matrix::from_fn(inputs.rows(), cols, |i, j| {
+            data[i * cols + j] * std_devs[j] + means[j]
+        })
+    }
+}
@@ -0,0 +1,254 @@
+//! Target transforms
+//!
+//! Invertible transforms of regression targets.
+//!
+//! Skewed targets, such as prices or counts, are often better modelled
+//! after a log or power transform. These transforms act on a `Vector`
+//! of targets and are used with the `TransformedTargetRegressor` in
+//! the `learning::transformed_target` module, which inverts the
+//! transform on the predictions.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::data::transforms::Transformer;
+//! use rusty_machine::data::transforms::target::BoxCox;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! let targets = Vector::new(vec![1.0, 2.0, 4.0, 8.0, 16.0]);
+//!
+//! let mut box_cox = BoxCox::default();
+//! let transformed = box_cox.fit_transform(&targets);
+//!
+//! // The targets are powers of two, so the fitted transform is close
+//! // to the logarithm.
+//! assert!(box_cox.lambda().unwrap().abs() < 0.1);
+//!
+//! let original = box_cox.inverse_transform(&transformed);
+//! for (x, y) in original.data().iter().zip(targets.data()) {
+//!     assert!((x - y).abs() < 1e-8);
+//! }
+//! ```
+
+use std::f64;
+use std::vec::Vec;
+
+use data::transforms::Transformer;
+use linalg::vector::Vector;
+
+/// The logarithm transform.
+///
+/// Maps each target y to ln(y + offset). The offset allows targets
+/// which are zero, such as counts.
+#[derive(Clone, Copy, Debug)]
+pub struct LogTransform {
+    /// Added to the targets before taking the logarithm.
+    pub offset: f64,
+}
+
+/// The logarithm without an offset.
+impl Default for LogTransform {
+    fn default() -> LogTransform {
+        LogTransform { offset: 0f64 }
+    }
+}
+
+impl LogTransform {
+    /// Constructs the transform ln(y + offset).
+    pub fn new(offset: f64) -> LogTransform {
+        LogTransform { offset: offset }
+    }
+}
+
+/// The transform has no parameters to fit.
+impl Transformer<Vector<f64>> for LogTransform {
+    fn fit(&mut self, _inputs: &Vector<f64>) {}
+
+    /// # Panics
+    ///
+    /// - A target plus the offset is not positive.
+    fn transform(&self, inputs: &Vector<f64>) -> Vector<f64> {
+        inputs.data()
+            .iter()
+            .map(|y| {
+                let y = y + self.offset;
+                assert!(y > 0f64, "Targets plus the offset must be positive.");
+                y.ln()
+            })
+            .collect()
+    }
+
+    fn inverse_transform(&self, inputs: &Vector<f64>) -> Vector<f64> {
+        inputs.data().iter().map(|z| z.exp() - self.offset).collect()
+    }
+}
+
+/// The Box-Cox power transform.
+///
+/// Maps each positive target y to (y<sup>λ</sup> - 1) / λ, or to ln(y)
+/// when λ is zero. By default λ is fitted by maximum likelihood, which
+/// makes the transformed targets as close to normally distributed as
+/// the transform allows.
+#[derive(Clone, Copy, Debug)]
+pub struct BoxCox {
+    lambda: Option<f64>,
+    fit_lambda: bool,
+    min_lambda: f64,
+    max_lambda: f64,
+}
+
+/// Fits λ in the range [-2, 2].
+impl Default for BoxCox {
+    fn default() -> BoxCox {
+        BoxCox::new(-2f64, 2f64)
+    }
+}
+
+impl BoxCox {
+    /// Constructs a transform which fits λ within the range.
+    ///
+    /// # Panics
+    ///
+    /// - min is greater than max.
+    pub fn new(min: f64, max: f64) -> BoxCox {
+        assert!(min <= max, "The minimum lambda must not exceed the maximum.");
+
+        BoxCox {
+            lambda: None,
+            fit_lambda: true,
+            min_lambda: min,
+            max_lambda: max,
+        }
+    }
+
+    /// Constructs a transform with a fixed λ.
+    ///
+    /// Fitting the transform leaves λ unchanged.
+    pub fn with_lambda(lambda: f64) -> BoxCox {
+        BoxCox {
+            lambda: Some(lambda),
+            fit_lambda: false,
+            min_lambda: lambda,
+            max_lambda: lambda,
+        }
+    }
+
+    /// The value of λ.
+    ///
+    /// Returns None if λ has not been fitted.
+    pub fn lambda(&self) -> Option<f64> {
+        self.lambda
+    }
+
+    fn fitted_lambda(&self) -> f64 {
+        self.lambda.expect("Transformer has not been fitted.")
+    }
+}
+
+impl Transformer<Vector<f64>> for BoxCox {
+    /// Fits λ by maximizing the profile log-likelihood with a golden
+    /// section search.
+    ///
+    /// # Panics
+    ///
+    /// - A target is not positive.
+    /// - There are fewer than two targets.
+    fn fit(&mut self, inputs: &Vector<f64>) {
+        if !self.fit_lambda {
+            return;
+        }
+
+        assert!(inputs.size() > 1, "At least two targets are needed to fit lambda.");
+        let logs = inputs.data()
+            .iter()
+            .map(|&y| {
+                assert!(y > 0f64, "Box-Cox targets must be positive.");
+                y.ln()
+            })
+            .collect::<Vec<_>>();
+
+        // Golden section search for the maximum of the likelihood.
+        let ratio = (5f64.sqrt() - 1f64) / 2f64;
+        let (mut a, mut b) = (self.min_lambda, self.max_lambda);
+        let mut c = b - ratio * (b - a);
+        let mut d = a + ratio * (b - a);
+        let (mut fc, mut fd) = (log_likelihood(&logs, c), log_likelihood(&logs, d));
+
+        while b - a > 1e-8 {
+            if fc > fd {
+                b = d;
+                d = c;
+                fd = fc;
+                c = b - ratio * (b - a);
+                fc = log_likelihood(&logs, c);
+            } else {
+                a = c;
+                c = d;
+                fc = fd;
+                d = a + ratio * (b - a);
+                fd = log_likelihood(&logs, d);
+            }
+        }
+
+        self.lambda = Some((a + b) / 2f64);
+    }
+
+    /// # Panics
+    ///
+    /// - The transform has not been fitted.
+    /// - A target is not positive.
+    fn transform(&self, inputs: &Vector<f64>) -> Vector<f64> {
+        let lambda = self.fitted_lambda();
+        inputs.data()
+            .iter()
+            .map(|&y| {
+                assert!(y > 0f64, "Box-Cox targets must be positive.");
+                power(y.ln(), lambda)
+            })
+            .collect()
+    }
+
+    /// Values outside the range of the transform are mapped to its
+    /// limit, zero for positive λ and infinity for negative λ.
+    ///
+    /// # Panics
+    ///
+    /// - The transform has not been fitted.
+    fn inverse_transform(&self, inputs: &Vector<f64>) -> Vector<f64> {
+        let lambda = self.fitted_lambda();
+        inputs.data()
+            .iter()
+            .map(|&z| {
+                if lambda == 0f64 {
+                    z.exp()
+                } else if lambda * z <= -1f64 {
+                    if lambda > 0f64 { 0f64 } else { f64::INFINITY }
+                } else {
+                    ((lambda * z).ln_1p() / lambda).exp()
+                }
+            })
+            .collect()
+    }
+}
+
+/// The Box-Cox transform of a target from its logarithm.
+fn power(log_y: f64, lambda: f64) -> f64 {
+    if lambda == 0f64 {
+        log_y
+    } else {
+        (lambda * log_y).exp_m1() / lambda
+    }
+}
+
+/// The profile log-likelihood of λ, up to a constant.
+///
+/// -n/2 ln(σ<sup>2</sup>) + (λ - 1) Σ ln(y), where σ<sup>2</sup> is the
+/// variance of the transformed targets.
+fn log_likelihood(logs: &[f64], lambda: f64) -> f64 {
+    let n = logs.len() as f64;
+    let transformed = logs.iter().map(|&l| power(l, lambda)).collect::<Vec<_>>();
+    let mean = transformed.iter().sum::<f64>() / n;
+    let var = transformed.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / n;
+
+    -n / 2f64 * var.ln() + (lambda - 1f64) * logs.iter().sum::<f64>()
+}
@@ -0,0 +1,259 @@
+//! Whitening transform
+//!
+//! Decorrelates the columns of the inputs and scales them to unit
+//! variance, so that the covariance of the outputs is (close to) the
+//! identity.
+//!
+//! Two variants are provided:
+//!
+//! - PCA whitening rotates the inputs onto the principal components,
+//!   ordered by decreasing variance.
+//! - ZCA whitening additionally rotates back, so the outputs stay as
+//!   close as possible to the original inputs.
+//!
+//! A small epsilon is added to the variances before they are inverted
+//! to avoid amplifying noise in directions with almost no variance.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::data::transforms::Transformer;
+//! use rusty_machine::data::transforms::whiten::{Whitening, WhitenMethod};
+//! use rusty_machine::linalg::matrix::Matrix;
+//!
+//! let inputs = Matrix::new(4, 2, vec![1.0, 2.0, 2.0, 3.5, 3.0, 6.5, 4.0, 8.0]);
+//!
+//! let mut whiten = Whitening::new(WhitenMethod::Zca).epsilon(0.0);
+//! let outputs = whiten.fit_transform(&inputs);
+//!
+//! // The outputs are uncorrelated with unit variance.
+//! let centered = &outputs - &Matrix::new(4, 2, outputs.mean(0).into_vec().repeat(4));
+//! let cov = (centered.transpose() * centered) / 3.0;
+//!
+//! assert!((cov[[0, 0]] - 1.0).abs() < 1e-8);
+//! assert!(cov[[0, 1]].abs() < 1e-8);
+//! ```
+
+use data::transforms::Transformer;
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+
+/// The whitening variant.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WhitenMethod {
+    /// Projects onto the scaled principal components.
+    Pca,
+    /// Projects onto the scaled principal components and rotates back
+    /// to the original axes.
+    Zca,
+}
+
+/// The Whitening transform
+///
+/// Learns the column means and the eigendecomposition of the sample
+/// covariance of the inputs.
+pub struct Whitening {
+    method: WhitenMethod,
+    epsilon: f64,
+    means: Option<Vector<f64>>,
+    eigenvalues: Option<Vector<f64>>,
+    whiten: Option<Matrix<f64>>,
+    unwhiten: Option<Matrix<f64>>,
+}
+
+impl Whitening {
+    /// Constructs a whitening transform with the given method.
+    ///
+    /// Defaults to an epsilon of `1e-5`.
+    pub fn new(method: WhitenMethod) -> Whitening {
+        Whitening {
+            method: method,
+            epsilon: 1e-5,
+            means: None,
+            eigenvalues: None,
+            whiten: None,
+            unwhiten: None,
+        }
+    }
+
+    /// Sets the epsilon added to the variances.
+    ///
+    /// # Panics
+    ///
+    /// - epsilon is negative.
+    pub fn epsilon(mut self, epsilon: f64) -> Whitening {
+        assert!(epsilon >= 0f64, "Epsilon must be non-negative.");
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// The column means learned by `fit`.
+    pub fn means(&self) -> Option<&Vector<f64>> {
+        self.means.as_ref()
+    }
+
+    /// The eigenvalues of the covariance learned by `fit`, in
+    /// decreasing order.
+    pub fn eigenvalues(&self) -> Option<&Vector<f64>> {
+        self.eigenvalues.as_ref()
+    }
+
+    /// The matrix which multiplies the centered inputs on the right to
+    /// produce the whitened outputs.
+    pub fn whitening_matrix(&self) -> Option<&Matrix<f64>> {
+        self.whiten.as_ref()
+    }
+
+    fn params(&self, cols: usize) -> (&Vector<f64>, &Matrix<f64>, &Matrix<f64>) {
+        match (self.means.as_ref(), self.whiten.as_ref(), self.unwhiten.as_ref()) {
+            (Some(m), Some(w), Some(u)) => {
+                assert!(m.size() == cols, "Input columns do not match fitted columns.");
+                (m, w, u)
+            }
+            _ => panic!("Transformer has not been fitted."),
+        }
+    }
+}
+
+impl Transformer<Matrix<f64>> for Whitening {
+    /// Computes the whitening matrix from the sample covariance.
+    ///
+    /// # Panics
+    ///
+    /// - The inputs have fewer than two rows.
+    /// - The covariance is singular and epsilon is zero.
+    fn fit(&mut self, inputs: &Matrix<f64>) {
+        assert!(inputs.rows() > 1,
+                "Cannot whiten inputs with fewer than two rows.");
+
+        let means = inputs.mean(0);
+        let centered = center(inputs, &means);
+        let cov = (centered.transpose() * &centered) / (inputs.rows() - 1) as f64;
+
+        let (values, vectors) = symmetric_eigen(&cov);
+        let n = values.len();
+
+        // Eigenvalues which are zero up to rounding are treated as zero.
+        let tol = 1e-12 * values[0].abs();
+        let scales: Vec<f64> = values.iter()
+                                     .map(|&v| {
+                                         let v = if v > tol { v } else { 0f64 };
+                                         (v + self.epsilon).sqrt()
+                                     })
+                                     .collect();
+        assert!(scales.iter().all(|s| *s > 0f64),
+                "Covariance is singular, use a positive epsilon.");
+
+        // Columns of `vectors` scaled by the inverse and forward scales.
+        let inv_scaled = Matrix::from_fn(n, n, |i, j| vectors[[i, j]] / scales[j]);
+        let fwd_scaled = Matrix::from_fn(n, n, |i, j| vectors[[j, i]] * scales[i]);
+
+        let (whiten, unwhiten) = match self.method {
+            WhitenMethod::Pca => (inv_scaled, fwd_scaled),
+            WhitenMethod::Zca => {
+                (inv_scaled * vectors.transpose(), &vectors * fwd_scaled)
+            }
+        };
+
+        self.means = Some(means);
+        self.eigenvalues = Some(Vector::new(values));
+        self.whiten = Some(whiten);
+        self.unwhiten = Some(unwhiten);
+    }
+
+    /// Centers and whitens the inputs.
+    ///
+    /// # Panics
+    ///
+    /// - The transformer has not been fitted.
+    /// - The inputs have a different number of columns to the fitted data.
+    fn transform(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        let (means, whiten, _) = self.params(inputs.cols());
+        center(inputs, means) * whiten
+    }
+
+    /// Reverses the whitening.
+    ///
+    /// # Panics
+    ///
+    /// - The transformer has not been fitted.
+    /// - The inputs have a different number of columns to the fitted data.
+    fn inverse_transform(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        let (means, _, unwhiten) = self.params(inputs.cols());
+        let outputs = inputs * unwhiten;
+        let cols = outputs.cols();
+
+        Matrix::from_fn(outputs.rows(), cols, |i, j| outputs.data()[i * cols + j] + means[j])
+    }
+}
+
+fn center(inputs: &Matrix<f64>, means: &Vector<f64>) -> Matrix<f64> {
+    let cols = inputs.cols();
+    Matrix::from_fn(inputs.rows(), cols, |i, j| inputs.data()[i * cols + j] - means[j])
+}
+
+/// Eigendecomposition of a symmetric matrix by cyclic Jacobi rotations.
+///
+/// Returns the eigenvalues in decreasing order and a matrix with the
+/// corresponding eigenvectors as columns.
+fn symmetric_eigen(m: &Matrix<f64>) -> (Vec<f64>, Matrix<f64>) {
+    let n = m.rows();
+    let mut a = m.clone();
+    let mut v = Matrix::<f64>::identity(n);
+
+    let total: f64 = m.data().iter().map(|x| x * x).sum();
+
+    for _ in 0..100 {
+        let off: f64 = (0..n)
+                           .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+                           .map(|(i, j)| a[[i, j]] * a[[i, j]])
+                           .sum();
+        if off <= 1e-30 * total {
+            break;
+        }
+
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = a[[p, q]];
+                if apq.abs() < 1e-300 {
+                    continue;
+                }
+
+                let theta = (a[[q, q]] - a[[p, p]]) / (2f64 * apq);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1f64).sqrt());
+                let c = 1f64 / (t * t + 1f64).sqrt();
+                let s = t * c;
+
+                {
+                    let data = a.mut_data();
+                    for k in 0..n {
+                        let akp = data[k * n + p];
+                        let akq = data[k * n + q];
+                        data[k * n + p] = c * akp - s * akq;
+                        data[k * n + q] = s * akp + c * akq;
+                    }
+                    for k in 0..n {
+                        let apk = data[p * n + k];
+                        let aqk = data[q * n + k];
+                        data[p * n + k] = c * apk - s * aqk;
+                        data[q * n + k] = s * apk + c * aqk;
+                    }
+                }
+
+                let data = v.mut_data();
+                for k in 0..n {
+                    let vkp = data[k * n + p];
+                    let vkq = data[k * n + q];
+                    data[k * n + p] = c * vkp - s * vkq;
+                    data[k * n + q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| a[[j, j]].partial_cmp(&a[[i, i]]).unwrap());
+
+    let values = order.iter().map(|&i| a[[i, i]]).collect();
+    (values, v.select_cols(&order))
+}
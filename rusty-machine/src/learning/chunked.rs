@@ -0,0 +1,119 @@
+//! Chunked prediction
+//!
+//! Predicts from a stream of input rows in fixed-size blocks. Only one
+//! block of inputs is held in memory at a time, so large data sets can
+//! be scored without building one matrix of all the inputs.
+//!
+//! The `predict_chunked` method is available on every supervised model
+//! which predicts from a `Matrix<f64>`.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::learning::SupModel;
+//! use rusty_machine::learning::chunked::ChunkedPredict;
+//! use rusty_machine::learning::lin_reg::LinRegressor;
+//! use rusty_machine::linalg::matrix::Matrix;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! let inputs = Matrix::new(3, 1, vec![1.0, 2.0, 3.0]);
+//! let targets = Vector::new(vec![2.0, 4.0, 6.0]);
+//!
+//! let mut model = LinRegressor::default();
+//! model.train(&inputs, &targets);
+//!
+//! // The rows can come from any iterator, for example a file reader.
+//! let rows = (0..10).map(|x| vec![x as f64]);
+//!
+//! let mut total = 0;
+//! for outputs in model.predict_chunked(rows, 4) {
+//!     total += outputs.size();
+//! }
+//!
+//! assert_eq!(total, 10);
+//! ```
+
+use std::marker::PhantomData;
+use std::vec::Vec;
+
+use learning::SupModel;
+use linalg::matrix::Matrix;
+
+/// Trait for models which can predict from a stream of rows.
+///
+/// Implemented for every model which predicts from a `Matrix<f64>`.
+pub trait ChunkedPredict<U>: SupModel<Matrix<f64>, U> + Sized {
+    /// Predicts the outputs of the rows `chunk_size` rows at a time.
+    ///
+    /// Returns an iterator over the outputs of each chunk. The last
+    /// chunk holds the remaining rows and may be smaller.
+    ///
+    /// # Panics
+    ///
+    /// - chunk_size is zero.
+    /// - The iterator panics if the rows have different lengths.
+    fn predict_chunked<I>(&self, rows: I, chunk_size: usize) -> PredictChunks<'_, Self, U, I::IntoIter>
+        where I: IntoIterator,
+              I::Item: AsRef<[f64]>
+    {
+        assert!(chunk_size > 0, "Chunk size must be greater than zero.");
+
+        PredictChunks {
+            model: self,
+            rows: rows.into_iter(),
+            chunk_size: chunk_size,
+            cols: None,
+            _outputs: PhantomData,
+        }
+    }
+}
+
+impl<U, M: SupModel<Matrix<f64>, U>> ChunkedPredict<U> for M {}
+
+/// Iterator over the outputs of each chunk of rows.
+///
+/// Created by `ChunkedPredict::predict_chunked`.
+pub struct PredictChunks<'a, M: 'a, U, I> {
+    model: &'a M,
+    rows: I,
+    chunk_size: usize,
+    cols: Option<usize>,
+    _outputs: PhantomData<U>,
+}
+
+impl<'a, M, U, I> Iterator for PredictChunks<'a, M, U, I>
+    where M: SupModel<Matrix<f64>, U>,
+          I: Iterator,
+          I::Item: AsRef<[f64]>
+{
+    type Item = U;
+
+    fn next(&mut self) -> Option<U> {
+        let mut data = Vec::new();
+        let mut n_rows = 0;
+
+        while n_rows < self.chunk_size {
+            let row = match self.rows.next() {
+                Some(row) => row,
+                None => break,
+            };
+            let row = row.as_ref();
+
+            let cols = *self.cols.get_or_insert(row.len());
+            assert!(row.len() == cols, "Rows have different lengths.");
+
+            if data.is_empty() {
+                data.reserve(self.chunk_size * cols);
+            }
+            data.extend_from_slice(row);
+            n_rows += 1;
+        }
+
+        if n_rows == 0 {
+            return None;
+        }
+
+        let cols = self.cols.unwrap_or(0);
+        Some(self.model.predict(&Matrix::new(n_rows, cols, data)))
+    }
+}
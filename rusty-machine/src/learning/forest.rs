@@ -0,0 +1,547 @@
+//! Random Forest module
+//!
+//! Contains random forests of decision trees for classification and
+//! regression. Each tree is grown on a bootstrap sample of the rows,
+//! and each split considers a random subset of the features. The
+//! forest averages the predictions of its trees: the class proportions
+//! for the `RandomForestClassifier` and the targets for the
+//! `RandomForestRegressor`.
+//!
+//! Extremely randomized trees, built with `extra_trees`, draw one
+//! random threshold for each feature considered rather than searching
+//! for the best, and grow every tree on all of the rows. They train
+//! faster than a random forest, and the extra randomness lowers the
+//! variance on noisy data for a little more bias.
+//!
+//! When the trees are grown on bootstrap samples, each row is left out
+//! of the samples of about a third of the trees. Averaging only those
+//! trees gives out-of-bag predictions for the training rows, and the
+//! out-of-bag accuracy or R² of these predictions estimates how well
+//! the forest generalizes without holding out a validation set.
+//!
+//! Both forests can be trained with a weight for each row through
+//! `WeightedSupModel`. Each tree is grown with the weights, and the
+//! bootstrap samples are drawn from the rows of positive weight. The
+//! out-of-bag scores are unweighted.
+//!
+//! The trees are grown with the `TreeParams` of the `tree` module. The
+//! random numbers are drawn from `rand_utils::rng`, so a forest is
+//! reproducible after `rand_utils::set_seed`.
+//!
+//! With the `parallel` feature the trees are grown on the rayon thread
+//! pool. Each tree is grown as a `rand_utils::with_task` task, so a
+//! seeded forest is the same whichever threads grow its trees, and the
+//! same as without the feature.
+//!
+//! Requires the `rand` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::learning::SupModel;
+//! use rusty_machine::learning::forest::RandomForestClassifier;
+//! use rusty_machine::linalg::matrix::Matrix;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! let inputs = Matrix::new(6, 2, vec![1.0, 5.0, 2.0, 4.0, 3.0, 6.0,
+//!                                     7.0, 5.0, 8.0, 4.0, 9.0, 6.0]);
+//! let targets = Vector::new(vec![0, 0, 0, 1, 1, 1]);
+//!
+//! let mut forest = RandomForestClassifier::default();
+//! forest.train(&inputs, &targets);
+//!
+//! let proba = forest.predict_proba(&Matrix::new(1, 2, vec![1.5, 5.0]));
+//! assert!(proba[[0, 0]] > proba[[0, 1]]);
+//!
+//! // The out-of-bag accuracy estimates the accuracy on new data.
+//! println!("{:?}", forest.oob_score());
+//! ```
+
+use std::fmt;
+
+use rand::Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use analysis::regression::r2_score;
+use learning::{SupModel, WeightedSupModel};
+use learning::toolkit::rand_utils;
+use learning::tree::{self, MaxFeatures, Splitter, Tree, TreeParams};
+use linalg::matrix::{BaseMatrix, Matrix};
+use linalg::vector::Vector;
+use linalg::utils;
+
+/// A random forest for classification.
+///
+/// The targets are class labels `0..k`.
+#[derive(Clone)]
+pub struct RandomForestClassifier {
+    n_trees: usize,
+    params: TreeParams,
+    bootstrap: bool,
+    n_classes: usize,
+    trees: Vec<Tree>,
+    oob_proba: Option<Matrix<f64>>,
+    oob_score: Option<f64>,
+}
+
+/// The default random forest classifier.
+///
+/// The forest has 100 trees grown on bootstrap samples, with the
+/// default `TreeParams` except that each split considers the square
+/// root of the number of features.
+impl Default for RandomForestClassifier {
+    fn default() -> RandomForestClassifier {
+        RandomForestClassifier::new(100, TreeParams::default().max_features(MaxFeatures::Sqrt))
+    }
+}
+
+impl fmt::Debug for RandomForestClassifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RandomForestClassifier")
+            .field("n_trees", &self.n_trees)
+            .field("params", &self.params)
+            .field("bootstrap", &self.bootstrap)
+            .field("trained", &!self.trees.is_empty())
+            .finish()
+    }
+}
+
+impl RandomForestClassifier {
+    /// Constructs an untrained forest of trees grown with the settings
+    /// on bootstrap samples.
+    ///
+    /// # Panics
+    ///
+    /// - The number of trees is zero.
+    pub fn new(n_trees: usize, params: TreeParams) -> RandomForestClassifier {
+        assert!(n_trees > 0, "A forest needs at least one tree.");
+
+        RandomForestClassifier {
+            n_trees: n_trees,
+            params: params,
+            bootstrap: true,
+            n_classes: 0,
+            trees: Vec::new(),
+            oob_proba: None,
+            oob_score: None,
+        }
+    }
+
+    /// Constructs an untrained forest of extremely randomized trees.
+    ///
+    /// The splitter of the settings is set to `Splitter::Random`, and
+    /// the trees are grown on all of the rows.
+    ///
+    /// # Panics
+    ///
+    /// - The number of trees is zero.
+    pub fn extra_trees(n_trees: usize, params: TreeParams) -> RandomForestClassifier {
+        RandomForestClassifier::new(n_trees, params.splitter(Splitter::Random)).bootstrap(false)
+    }
+
+    /// Sets whether each tree is grown on a bootstrap sample of the
+    /// rows, rather than all of them.
+    pub fn bootstrap(mut self, bootstrap: bool) -> RandomForestClassifier {
+        self.bootstrap = bootstrap;
+        self
+    }
+
+    /// The trained trees.
+    ///
+    /// Empty if the model has not been trained.
+    pub fn trees(&self) -> &[Tree] {
+        &self.trees
+    }
+
+    /// The out-of-bag class probabilities of the training rows.
+    ///
+    /// Each row averages the class proportions of the trees whose
+    /// bootstrap sample left it out, and is NaN if there are none.
+    /// Returns `None` if the trees were not grown on bootstrap samples
+    /// or the model has not been trained.
+    pub fn oob_proba(&self) -> Option<&Matrix<f64>> {
+        self.oob_proba.as_ref()
+    }
+
+    /// The accuracy of the out-of-bag predictions of the training rows.
+    ///
+    /// Rows which were in the sample of every tree are left out.
+    /// Returns `None` if there are no out-of-bag predictions.
+    pub fn oob_score(&self) -> Option<f64> {
+        self.oob_score
+    }
+
+    /// Predicts the probability of each class, one row per input row.
+    ///
+    /// The probabilities are the mean class proportions of the leaves
+    /// the input reaches in each tree.
+    ///
+    /// # Panics
+    ///
+    /// - The model has not been trained.
+    /// - The inputs do not have the columns the model was trained on.
+    pub fn predict_proba(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        check_inputs(&self.trees, inputs);
+
+        let k = self.n_classes;
+        let mut data = vec![0f64; inputs.rows() * k];
+        for (i, proba) in data.chunks_mut(k).enumerate() {
+            for tree in &self.trees {
+                for (p, v) in proba.iter_mut().zip(tree.value(inputs.row(i))) {
+                    *p += v;
+                }
+            }
+        }
+
+        let n_trees = self.trees.len() as f64;
+        Matrix::new(inputs.rows(), k, data) / n_trees
+    }
+}
+
+impl SupModel<Matrix<f64>, Vector<usize>> for RandomForestClassifier {
+    /// Grows the trees on the inputs and class labels.
+    ///
+    /// # Panics
+    ///
+    /// - The inputs and targets have different lengths.
+    /// - There are no rows.
+    fn train(&mut self, inputs: &Matrix<f64>, targets: &Vector<usize>) {
+        self.fit(inputs, targets, None);
+    }
+
+    /// Predicts the most probable class of each input row.
+    ///
+    /// # Panics
+    ///
+    /// - The model has not been trained.
+    /// - The inputs do not have the columns the model was trained on.
+    fn predict(&self, inputs: &Matrix<f64>) -> Vector<usize> {
+        let proba = self.predict_proba(inputs);
+        Vector::new((0..proba.rows()).map(|i| utils::argmax(proba.row(i)).0).collect::<Vec<_>>())
+    }
+}
+
+impl WeightedSupModel<Matrix<f64>, Vector<usize>> for RandomForestClassifier {
+    /// Grows the trees on the inputs and class labels, weighting each
+    /// tree's class proportions and impurities by the sample weights.
+    ///
+    /// # Panics
+    ///
+    /// - The inputs and targets have different lengths.
+    /// - There are no rows.
+    /// - The weights are invalid, as for `tree::check_weights`.
+    fn train_weighted(&mut self, inputs: &Matrix<f64>, targets: &Vector<usize>, weights: &Vector<f64>) {
+        self.fit(inputs, targets, Some(weights.data()));
+    }
+}
+
+impl RandomForestClassifier {
+    fn fit(&mut self, inputs: &Matrix<f64>, targets: &Vector<usize>, weights: Option<&[f64]>) {
+        check_training_data(inputs, targets.size(), weights);
+
+        let n_classes = targets.data().iter().max().unwrap() + 1;
+        let samples = draw_samples(inputs.rows(), weights, self.n_trees, self.bootstrap);
+
+        self.trees = grow_trees(&samples, |rows| {
+            Tree::grow_classes(inputs, targets.data(), n_classes, rows, weights, &self.params)
+        });
+        self.n_classes = n_classes;
+
+        self.oob_proba = None;
+        self.oob_score = None;
+        if self.bootstrap {
+            let (sums, counts) = oob_sums(&self.trees, &samples, inputs, n_classes);
+            let mut proba = Matrix::new(inputs.rows(), n_classes, sums);
+            let mut correct = 0;
+            let mut scored = 0;
+            for (i, &count) in counts.iter().enumerate() {
+                let row = &mut proba.mut_data()[i * n_classes..(i + 1) * n_classes];
+                for p in row.iter_mut() {
+                    *p /= count as f64;
+                }
+                if count > 0 {
+                    scored += 1;
+                    if utils::argmax(row).0 == targets[i] {
+                        correct += 1;
+                    }
+                }
+            }
+
+            self.oob_proba = Some(proba);
+            if scored > 0 {
+                self.oob_score = Some(correct as f64 / scored as f64);
+            }
+        }
+    }
+}
+
+/// A random forest for regression.
+#[derive(Clone)]
+pub struct RandomForestRegressor {
+    n_trees: usize,
+    params: TreeParams,
+    bootstrap: bool,
+    trees: Vec<Tree>,
+    oob_prediction: Option<Vector<f64>>,
+    oob_score: Option<f64>,
+}
+
+/// The default random forest regressor.
+///
+/// The forest has 100 trees grown on bootstrap samples with the
+/// default `TreeParams`, so each split considers every feature.
+impl Default for RandomForestRegressor {
+    fn default() -> RandomForestRegressor {
+        RandomForestRegressor::new(100, TreeParams::default())
+    }
+}
+
+impl fmt::Debug for RandomForestRegressor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RandomForestRegressor")
+            .field("n_trees", &self.n_trees)
+            .field("params", &self.params)
+            .field("bootstrap", &self.bootstrap)
+            .field("trained", &!self.trees.is_empty())
+            .finish()
+    }
+}
+
+impl RandomForestRegressor {
+    /// Constructs an untrained forest of trees grown with the settings
+    /// on bootstrap samples.
+    ///
+    /// # Panics
+    ///
+    /// - The number of trees is zero.
+    pub fn new(n_trees: usize, params: TreeParams) -> RandomForestRegressor {
+        assert!(n_trees > 0, "A forest needs at least one tree.");
+
+        RandomForestRegressor {
+            n_trees: n_trees,
+            params: params,
+            bootstrap: true,
+            trees: Vec::new(),
+            oob_prediction: None,
+            oob_score: None,
+        }
+    }
+
+    /// Constructs an untrained forest of extremely randomized trees.
+    ///
+    /// The splitter of the settings is set to `Splitter::Random`, and
+    /// the trees are grown on all of the rows.
+    ///
+    /// # Panics
+    ///
+    /// - The number of trees is zero.
+    pub fn extra_trees(n_trees: usize, params: TreeParams) -> RandomForestRegressor {
+        RandomForestRegressor::new(n_trees, params.splitter(Splitter::Random)).bootstrap(false)
+    }
+
+    /// Sets whether each tree is grown on a bootstrap sample of the
+    /// rows, rather than all of them.
+    pub fn bootstrap(mut self, bootstrap: bool) -> RandomForestRegressor {
+        self.bootstrap = bootstrap;
+        self
+    }
+
+    /// The trained trees.
+    ///
+    /// Empty if the model has not been trained.
+    pub fn trees(&self) -> &[Tree] {
+        &self.trees
+    }
+
+    /// The out-of-bag predictions of the training rows.
+    ///
+    /// Each prediction averages the trees whose bootstrap sample left
+    /// the row out, and is NaN if there are none. Returns `None` if the
+    /// trees were not grown on bootstrap samples or the model has not
+    /// been trained.
+    pub fn oob_prediction(&self) -> Option<&Vector<f64>> {
+        self.oob_prediction.as_ref()
+    }
+
+    /// The R² score of the out-of-bag predictions of the training rows.
+    ///
+    /// Rows which were in the sample of every tree are left out.
+    /// Returns `None` if there are no out-of-bag predictions.
+    pub fn oob_score(&self) -> Option<f64> {
+        self.oob_score
+    }
+}
+
+impl SupModel<Matrix<f64>, Vector<f64>> for RandomForestRegressor {
+    /// Grows the trees on the inputs and targets.
+    ///
+    /// # Panics
+    ///
+    /// - The inputs and targets have different lengths.
+    /// - There are no rows.
+    fn train(&mut self, inputs: &Matrix<f64>, targets: &Vector<f64>) {
+        self.fit(inputs, targets, None);
+    }
+
+    /// Predicts the target of each input row, the mean prediction of
+    /// the trees.
+    ///
+    /// # Panics
+    ///
+    /// - The model has not been trained.
+    /// - The inputs do not have the columns the model was trained on.
+    fn predict(&self, inputs: &Matrix<f64>) -> Vector<f64> {
+        check_inputs(&self.trees, inputs);
+
+        let n_trees = self.trees.len() as f64;
+        Vector::new((0..inputs.rows())
+                        .map(|i| {
+                            let row = inputs.row(i);
+                            self.trees.iter().map(|t| t.value(row)[0]).sum::<f64>() / n_trees
+                        })
+                        .collect::<Vec<_>>())
+    }
+}
+
+impl WeightedSupModel<Matrix<f64>, Vector<f64>> for RandomForestRegressor {
+    /// Grows the trees on the inputs and targets, weighting each tree's
+    /// leaf means and impurities by the sample weights.
+    ///
+    /// # Panics
+    ///
+    /// - The inputs and targets have different lengths.
+    /// - There are no rows.
+    /// - The weights are invalid, as for `tree::check_weights`.
+    fn train_weighted(&mut self, inputs: &Matrix<f64>, targets: &Vector<f64>, weights: &Vector<f64>) {
+        self.fit(inputs, targets, Some(weights.data()));
+    }
+}
+
+impl RandomForestRegressor {
+    fn fit(&mut self, inputs: &Matrix<f64>, targets: &Vector<f64>, weights: Option<&[f64]>) {
+        check_training_data(inputs, targets.size(), weights);
+
+        let samples = draw_samples(inputs.rows(), weights, self.n_trees, self.bootstrap);
+        self.trees = grow_trees(&samples, |rows| {
+            Tree::grow_values(inputs, targets.data(), rows, weights, &self.params)
+        });
+
+        self.oob_prediction = None;
+        self.oob_score = None;
+        if self.bootstrap {
+            let (sums, counts) = oob_sums(&self.trees, &samples, inputs, 1);
+            let prediction = sums.iter()
+                .zip(&counts)
+                .map(|(s, &c)| s / c as f64)
+                .collect::<Vector<f64>>();
+
+            let scored = (0..counts.len()).filter(|&i| counts[i] > 0).collect::<Vec<_>>();
+            if !scored.is_empty() {
+                self.oob_score = Some(r2_score(&prediction.select(&scored), &targets.select(&scored)));
+            }
+            self.oob_prediction = Some(prediction);
+        }
+    }
+}
+
+/// The rows each tree is grown on, a bootstrap sample or all of them.
+///
+/// A bootstrap sample is drawn from the rows of positive weight, so no
+/// tree is grown on rows which all have zero weight.
+fn draw_samples(n: usize, weights: Option<&[f64]>, n_trees: usize, bootstrap: bool) -> Vec<Vec<usize>> {
+    if !bootstrap {
+        return vec![(0..n).collect(); n_trees];
+    }
+
+    let rows = match weights {
+        Some(w) => (0..n).filter(|&i| w[i] > 0f64).collect::<Vec<_>>(),
+        None => (0..n).collect(),
+    };
+    let mut rng = rand_utils::rng();
+    (0..n_trees).map(|_| (0..rows.len()).map(|_| rows[rng.gen_range(0, rows.len())]).collect()).collect()
+}
+
+/// The first task index of the trees of a forest.
+///
+/// It is drawn from the generator of the caller, so the trees of
+/// forests grown one after another run as different tasks.
+fn first_task() -> usize {
+    rand_utils::rng().gen_range(0, usize::MAX / 2)
+}
+
+/// Grows a tree on each sample using the rayon thread pool.
+///
+/// Each tree is grown in its own `rand_utils::with_task` task, so the
+/// trees do not depend on the threads which grow them.
+#[cfg(feature = "parallel")]
+fn grow_trees<F>(samples: &[Vec<usize>], grow: F) -> Vec<Tree>
+    where F: Fn(&[usize]) -> Tree + Sync
+{
+    let first = first_task();
+    samples.par_iter()
+        .enumerate()
+        .map(|(i, rows)| rand_utils::with_task(first + i, || grow(rows)))
+        .collect()
+}
+
+/// Grows a tree on each sample in turn.
+///
+/// Each tree is grown in its own `rand_utils::with_task` task, as with
+/// the `parallel` feature.
+#[cfg(not(feature = "parallel"))]
+fn grow_trees<F>(samples: &[Vec<usize>], grow: F) -> Vec<Tree>
+    where F: Fn(&[usize]) -> Tree + Sync
+{
+    let first = first_task();
+    samples.iter()
+        .enumerate()
+        .map(|(i, rows)| rand_utils::with_task(first + i, || grow(rows)))
+        .collect()
+}
+
+/// The sums of the predictions of the trees for the training rows out
+/// of their samples, `width` values per row, and the number of trees
+/// each row is out of.
+fn oob_sums(trees: &[Tree],
+            samples: &[Vec<usize>],
+            inputs: &Matrix<f64>,
+            width: usize)
+            -> (Vec<f64>, Vec<usize>) {
+    let n = inputs.rows();
+    let mut sums = vec![0f64; n * width];
+    let mut counts = vec![0; n];
+
+    for (tree, rows) in trees.iter().zip(samples) {
+        let mut in_bag = vec![false; n];
+        for &r in rows {
+            in_bag[r] = true;
+        }
+
+        for i in (0..n).filter(|&i| !in_bag[i]) {
+            for (s, v) in sums[i * width..(i + 1) * width].iter_mut().zip(tree.value(inputs.row(i))) {
+                *s += v;
+            }
+            counts[i] += 1;
+        }
+    }
+
+    (sums, counts)
+}
+
+/// Checks the inputs, targets and weights before training.
+fn check_training_data(inputs: &Matrix<f64>, n_targets: usize, weights: Option<&[f64]>) {
+    assert!(inputs.rows() == n_targets,
+            "Inputs and targets have different lengths.");
+    assert!(inputs.rows() > 0, "Cannot train on no data.");
+    if let Some(weights) = weights {
+        tree::check_weights(inputs, weights);
+    }
+}
+
+/// Checks the inputs match the trees before prediction.
+fn check_inputs(trees: &[Tree], inputs: &Matrix<f64>) {
+    assert!(!trees.is_empty(), "The model has not been trained.");
+    assert!(inputs.cols() == trees[0].n_features(),
+            "Input columns do not match the trained forest.");
+}
@@ -0,0 +1,900 @@
+//! Gradient Boosting module
+//!
+//! Contains a gradient boosted regression tree model. Each tree is
+//! grown on the gradient of the loss of the current predictions, and
+//! its predictions, scaled by the learning rate, are added to the
+//! model.
+//!
+//! The loss is given by an `Objective`, its gradient and hessian with
+//! respect to each output. The squared error is the default, and the
+//! `Quantile` and `Tweedie` objectives are provided for prediction
+//! intervals and for insurance-style targets with a mass at zero. Any
+//! closure from an output and its target to the gradient and hessian
+//! is also an objective.
+//!
+//! As in XGBoost, the trees are grown on the gradient and hessian of
+//! the loss with an L2 penalty `lambda` on the leaf values. A leaf
+//! whose rows have gradient sum G and hessian sum H has the value
+//! `-G / (H + lambda)`, and a split is taken if it increases the total
+//! of `G^2 / (H + lambda)` over the leaves.
+//!
+//! Splits are found from histograms. Before boosting, each column is
+//! cut into at most `max_bins` bins at quantiles of its values, and
+//! every input is replaced by the index of its bin. The bin indices are
+//! computed once and reused by every tree. A node then sums the
+//! gradients of its rows in each bin and scans the bins, rather than
+//! sorting its rows on every column, which keeps training on millions
+//! of rows feasible. A column with at most `max_bins` distinct values
+//! has a bin for each value, so its splits are the same as an exact
+//! search.
+//!
+//! Missing values, given as NaN, are kept in a bin of their own. Each
+//! split sends them down the branch which gives the larger gain, or
+//! down the branch with more rows if none of its training rows were
+//! missing the feature.
+//!
+//! Each column can be constrained so the predictions only increase,
+//! or only decrease, with it while the other columns are held fixed,
+//! as regulated domains such as credit scoring often require. A split
+//! on a constrained column is only taken if its leaf values are in
+//! the required order. The midpoint of the two values then bounds the
+//! values of every leaf below, so deeper splits on other columns
+//! cannot break the order.
+//!
+//! The model can be trained with a weight for each row through
+//! `WeightedSupModel`. The gradient and hessian of each row are scaled
+//! by its weight, and the objective's base score and leaf values are
+//! weighted too. `min_samples_leaf` still counts rows.
+//!
+//! The trees are `Tree`s of the `tree` module, so they can be exported
+//! in the same way. The impurity of each node is the variance of the
+//! gradients of its rows.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::learning::SupModel;
+//! use rusty_machine::learning::gbm::GradientBoostingRegressor;
+//! use rusty_machine::linalg::matrix::Matrix;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! let inputs = Matrix::new(6, 1, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+//! let targets = Vector::new(vec![1.0, 1.0, 1.0, 5.0, 5.0, 5.0]);
+//!
+//! let mut model = GradientBoostingRegressor::new(200, 0.1).max_bins(16);
+//! model.train(&inputs, &targets);
+//!
+//! let outputs = model.predict(&Matrix::new(2, 1, vec![2.0, 5.0]));
+//! assert!((outputs[0] - 1.0).abs() < 1e-3);
+//! assert!((outputs[1] - 5.0).abs() < 1e-3);
+//! ```
+
+use std::fmt;
+
+use learning::{SupModel, WeightedSupModel};
+use learning::toolkit::cost_fn::{QuantileLoss, TweedieLoss};
+use learning::tree::{self, Branch, Node, Split, Tree};
+use linalg::matrix::{BaseMatrix, Matrix};
+use linalg::vector::Vector;
+use linalg::utils;
+
+/// A loss for gradient boosting.
+///
+/// The boosting only needs the first and second derivatives of the
+/// loss of each output. The hessians must be positive. The model
+/// scales them by the sample weights.
+///
+/// Closures taking an output and its target, and returning the
+/// gradient and hessian, implement this trait.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::learning::SupModel;
+/// use rusty_machine::learning::gbm::GradientBoostingRegressor;
+/// use rusty_machine::linalg::matrix::Matrix;
+/// use rusty_machine::linalg::vector::Vector;
+///
+/// let inputs = Matrix::new(4, 1, vec![1.0, 2.0, 3.0, 4.0]);
+/// let targets = Vector::new(vec![1.0, 1.0, 3.0, 3.0]);
+///
+/// // The squared error weighted four times as heavily above the targets.
+/// let asymmetric = |output: f64, target: f64| if output > target {
+///     (4.0 * (output - target), 4.0)
+/// } else {
+///     (output - target, 1.0)
+/// };
+///
+/// let mut model = GradientBoostingRegressor::default().objective(asymmetric);
+/// model.train(&inputs, &targets);
+/// ```
+pub trait Objective {
+    /// The gradient and hessian of the loss with respect to each output.
+    fn gradients(&self, outputs: &Vector<f64>, targets: &Vector<f64>) -> (Vector<f64>, Vector<f64>);
+
+    /// The output the boosting starts from, given the weight of each
+    /// target.
+    ///
+    /// The weighted mean target by default.
+    fn base_score(&self, targets: &Vector<f64>, weights: &Vector<f64>) -> f64 {
+        weighted_mean(targets, weights)
+    }
+
+    /// Maps an output, the base score plus the trees, to a prediction.
+    ///
+    /// The identity by default, and the inverse of the link function
+    /// for objectives with one.
+    fn transform(&self, output: f64) -> f64 {
+        output
+    }
+
+    /// Replaces the value of a leaf once its tree is grown.
+    ///
+    /// The rows are those of the leaf. Returns `None`, by default, to
+    /// keep the Newton step `-G / (H + lambda)`. Objectives whose
+    /// hessian does not describe the loss well, such as the `Quantile`,
+    /// can instead give the best step for the weighted rows.
+    fn leaf_value(&self,
+                  _outputs: &Vector<f64>,
+                  _targets: &Vector<f64>,
+                  _weights: &Vector<f64>,
+                  _rows: &[usize])
+                  -> Option<f64> {
+        None
+    }
+}
+
+impl<F: Fn(f64, f64) -> (f64, f64)> Objective for F {
+    fn gradients(&self, outputs: &Vector<f64>, targets: &Vector<f64>) -> (Vector<f64>, Vector<f64>) {
+        let (grad, hess): (Vec<f64>, Vec<f64>) = outputs.data()
+            .iter()
+            .zip(targets.data())
+            .map(|(&f, &y)| self(f, y))
+            .unzip();
+        (Vector::new(grad), Vector::new(hess))
+    }
+}
+
+/// The squared error, halved so its hessian is one.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SquaredError;
+
+impl Objective for SquaredError {
+    fn gradients(&self, outputs: &Vector<f64>, targets: &Vector<f64>) -> (Vector<f64>, Vector<f64>) {
+        (outputs - targets, Vector::ones(outputs.size()))
+    }
+}
+
+/// The quantile (pinball) loss, for predicting a quantile of the
+/// targets.
+///
+/// The loss is linear, so its hessian is taken to be one to grow the
+/// trees. The value of each leaf is then set to the quantile of the
+/// residuals of its rows, as in scikit-learn. The boosting starts from
+/// the quantile of the targets.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::learning::SupModel;
+/// use rusty_machine::learning::gbm::{GradientBoostingRegressor, Quantile};
+/// use rusty_machine::linalg::matrix::Matrix;
+/// use rusty_machine::linalg::vector::Vector;
+///
+/// let inputs = Matrix::new(5, 1, vec![1.0, 1.0, 1.0, 1.0, 1.0]);
+/// let targets = Vector::new(vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+///
+/// // An upper bound of a prediction interval.
+/// let mut upper = GradientBoostingRegressor::default().objective(Quantile::new(0.9));
+/// upper.train(&inputs, &targets);
+/// assert_eq!(upper.base_score(), 5.0);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Quantile {
+    loss: QuantileLoss,
+}
+
+impl Quantile {
+    /// Constructs the objective for the quantile.
+    ///
+    /// # Panics
+    ///
+    /// - The quantile is not strictly between 0 and 1.
+    pub fn new(quantile: f64) -> Quantile {
+        Quantile { loss: QuantileLoss::new(quantile) }
+    }
+}
+
+impl Objective for Quantile {
+    fn gradients(&self, outputs: &Vector<f64>, targets: &Vector<f64>) -> (Vector<f64>, Vector<f64>) {
+        (self.loss.grad_cost(outputs, targets), Vector::ones(outputs.size()))
+    }
+
+    /// The weighted quantile of the targets.
+    fn base_score(&self, targets: &Vector<f64>, weights: &Vector<f64>) -> f64 {
+        let values = targets.data().iter().cloned().zip(weights.data().iter().cloned()).collect();
+        lower_quantile(values, self.loss.quantile())
+    }
+
+    /// The weighted quantile of the residuals of the rows.
+    fn leaf_value(&self,
+                  outputs: &Vector<f64>,
+                  targets: &Vector<f64>,
+                  weights: &Vector<f64>,
+                  rows: &[usize])
+                  -> Option<f64> {
+        let residuals = rows.iter().map(|&r| (targets[r] - outputs[r], weights[r])).collect();
+        Some(lower_quantile(residuals, self.loss.quantile()))
+    }
+}
+
+/// The smallest value which values of at least the quantile of the
+/// total weight do not exceed, given `(value, weight)` pairs.
+fn lower_quantile(mut values: Vec<(f64, f64)>, quantile: f64) -> f64 {
+    values.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+    let target = quantile * values.iter().map(|v| v.1).sum::<f64>();
+    let mut total = 0f64;
+    for &(x, w) in &values {
+        total += w;
+        if w > 0f64 && total >= target {
+            return x;
+        }
+    }
+    values.last().unwrap().0
+}
+
+/// The mean of the targets weighted by the weights.
+fn weighted_mean(targets: &Vector<f64>, weights: &Vector<f64>) -> f64 {
+    utils::dot(targets.data(), weights.data()) / weights.sum()
+}
+
+/// The Tweedie loss with a log link, for non-negative targets with a
+/// mass at zero such as insurance claims.
+///
+/// The outputs are the logarithms of the predicted means, and the
+/// predictions are the means.
+#[derive(Clone, Copy, Debug)]
+pub struct Tweedie {
+    loss: TweedieLoss,
+}
+
+impl Tweedie {
+    /// Constructs the objective with the variance power.
+    ///
+    /// # Panics
+    ///
+    /// - The power is not strictly between 1 and 2.
+    pub fn new(power: f64) -> Tweedie {
+        Tweedie { loss: TweedieLoss::new(power) }
+    }
+}
+
+impl Objective for Tweedie {
+    /// # Panics
+    ///
+    /// - A target is negative.
+    fn gradients(&self, outputs: &Vector<f64>, targets: &Vector<f64>) -> (Vector<f64>, Vector<f64>) {
+        (self.loss.grad_cost(outputs, targets), self.loss.hessian(outputs, targets))
+    }
+
+    /// The logarithm of the weighted mean target.
+    ///
+    /// # Panics
+    ///
+    /// - The mean target is not positive.
+    fn base_score(&self, targets: &Vector<f64>, weights: &Vector<f64>) -> f64 {
+        let mean = weighted_mean(targets, weights);
+        assert!(mean > 0f64, "The mean target must be positive.");
+        mean.ln()
+    }
+
+    fn transform(&self, output: f64) -> f64 {
+        output.exp()
+    }
+}
+
+/// A constraint on how the predictions change with a column.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Monotone {
+    /// The predictions never decrease as the column increases.
+    Increasing,
+    /// The predictions never increase as the column increases.
+    Decreasing,
+    /// The predictions may change in either direction.
+    Unconstrained,
+}
+
+/// A gradient boosted tree model for regression.
+///
+/// The model minimizes the squared error unless another objective is
+/// set with `objective`.
+#[derive(Clone)]
+pub struct GradientBoostingRegressor<O: Objective = SquaredError> {
+    objective: O,
+    n_estimators: usize,
+    learning_rate: f64,
+    max_depth: usize,
+    min_samples_leaf: usize,
+    max_bins: usize,
+    lambda: f64,
+    monotone: Vec<Monotone>,
+    base_score: f64,
+    trees: Vec<Tree>,
+}
+
+/// The default gradient boosting model.
+///
+/// The defaults are:
+///
+/// - n_estimators = 100
+/// - learning_rate = 0.1
+/// - max_depth = 3
+/// - min_samples_leaf = 1
+/// - max_bins = 255
+/// - lambda = 1
+/// - monotone constraints = [] (all columns unconstrained)
+impl Default for GradientBoostingRegressor {
+    fn default() -> GradientBoostingRegressor {
+        GradientBoostingRegressor::new(100, 0.1)
+    }
+}
+
+impl<O: Objective> fmt::Debug for GradientBoostingRegressor<O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GradientBoostingRegressor")
+            .field("n_estimators", &self.n_estimators)
+            .field("learning_rate", &self.learning_rate)
+            .field("max_depth", &self.max_depth)
+            .field("min_samples_leaf", &self.min_samples_leaf)
+            .field("max_bins", &self.max_bins)
+            .field("lambda", &self.lambda)
+            .field("monotone", &self.monotone)
+            .field("trained", &!self.trees.is_empty())
+            .finish()
+    }
+}
+
+impl GradientBoostingRegressor {
+    /// Constructs an untrained model with the number of trees and the
+    /// learning rate.
+    ///
+    /// The other settings take their default values.
+    ///
+    /// # Panics
+    ///
+    /// - The number of trees is zero.
+    /// - The learning rate is not positive.
+    pub fn new(n_estimators: usize, learning_rate: f64) -> GradientBoostingRegressor {
+        assert!(n_estimators > 0, "There must be at least one tree.");
+        assert!(learning_rate > 0f64, "The learning rate must be positive.");
+
+        GradientBoostingRegressor {
+            objective: SquaredError,
+            n_estimators: n_estimators,
+            learning_rate: learning_rate,
+            max_depth: 3,
+            min_samples_leaf: 1,
+            max_bins: 255,
+            lambda: 1f64,
+            monotone: Vec::new(),
+            base_score: 0f64,
+            trees: Vec::new(),
+        }
+    }
+}
+
+impl<O: Objective> GradientBoostingRegressor<O> {
+    /// Sets the objective the trees minimize.
+    ///
+    /// The other settings are kept.
+    pub fn objective<P: Objective>(self, objective: P) -> GradientBoostingRegressor<P> {
+        GradientBoostingRegressor {
+            objective: objective,
+            n_estimators: self.n_estimators,
+            learning_rate: self.learning_rate,
+            max_depth: self.max_depth,
+            min_samples_leaf: self.min_samples_leaf,
+            max_bins: self.max_bins,
+            lambda: self.lambda,
+            monotone: self.monotone,
+            base_score: 0f64,
+            trees: Vec::new(),
+        }
+    }
+
+    /// Sets the greatest depth of a leaf of each tree.
+    pub fn max_depth(mut self, max_depth: usize) -> GradientBoostingRegressor<O> {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets the fewest rows in each child of a split.
+    ///
+    /// # Panics
+    ///
+    /// - The number is zero.
+    pub fn min_samples_leaf(mut self, min_samples_leaf: usize) -> GradientBoostingRegressor<O> {
+        assert!(min_samples_leaf > 0, "A leaf needs at least one row.");
+        self.min_samples_leaf = min_samples_leaf;
+        self
+    }
+
+    /// Sets the greatest number of bins of each column.
+    ///
+    /// # Panics
+    ///
+    /// - The number is less than 2 or more than 255.
+    pub fn max_bins(mut self, max_bins: usize) -> GradientBoostingRegressor<O> {
+        assert!((2..=255).contains(&max_bins),
+                "The number of bins must be between 2 and 255.");
+        self.max_bins = max_bins;
+        self
+    }
+
+    /// Sets the L2 penalty on the leaf values.
+    ///
+    /// # Panics
+    ///
+    /// - The penalty is negative.
+    pub fn lambda(mut self, lambda: f64) -> GradientBoostingRegressor<O> {
+        assert!(lambda >= 0f64, "The penalty must be non-negative.");
+        self.lambda = lambda;
+        self
+    }
+
+    /// Sets the monotone constraint of each column.
+    ///
+    /// The constraints must have one entry per input column when the
+    /// model is trained. Empty constraints leave every column
+    /// unconstrained.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::gbm::{GradientBoostingRegressor, Monotone};
+    ///
+    /// // The predictions increase with the first column only.
+    /// let model = GradientBoostingRegressor::default()
+    ///     .monotone_constraints(&[Monotone::Increasing, Monotone::Unconstrained]);
+    /// ```
+    pub fn monotone_constraints(mut self, monotone: &[Monotone]) -> GradientBoostingRegressor<O> {
+        self.monotone = monotone.to_vec();
+        self
+    }
+
+    /// The trained trees, in the order they were grown.
+    ///
+    /// Empty if the model has not been trained.
+    pub fn trees(&self) -> &[Tree] {
+        &self.trees
+    }
+
+    /// The output before any tree is added, given by the objective.
+    pub fn base_score(&self) -> f64 {
+        self.base_score
+    }
+}
+
+impl<O: Objective> SupModel<Matrix<f64>, Vector<f64>> for GradientBoostingRegressor<O> {
+    /// Grows the trees on the inputs and targets.
+    ///
+    /// # Panics
+    ///
+    /// - The inputs and targets have different lengths.
+    /// - There are no rows.
+    /// - The monotone constraints are set, but not for each column.
+    fn train(&mut self, inputs: &Matrix<f64>, targets: &Vector<f64>) {
+        self.fit(inputs, targets, &Vector::ones(targets.size()));
+    }
+
+    /// Predicts the target of each input row.
+    ///
+    /// # Panics
+    ///
+    /// - The model has not been trained.
+    /// - The inputs do not have the columns the model was trained on.
+    fn predict(&self, inputs: &Matrix<f64>) -> Vector<f64> {
+        assert!(!self.trees.is_empty(), "The model has not been trained.");
+        assert!(inputs.cols() == self.trees[0].n_features(),
+                "Input columns do not match the trained model.");
+
+        Vector::new((0..inputs.rows())
+                        .map(|i| {
+                            let row = inputs.row(i);
+                            let output = self.base_score +
+                                         self.trees.iter().map(|t| t.value(row)[0]).sum::<f64>();
+                            self.objective.transform(output)
+                        })
+                        .collect::<Vec<_>>())
+    }
+}
+
+impl<O: Objective> WeightedSupModel<Matrix<f64>, Vector<f64>> for GradientBoostingRegressor<O> {
+    /// Grows the trees on the inputs and targets, with the gradients
+    /// and hessians of each row scaled by its weight.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::{SupModel, WeightedSupModel};
+    /// use rusty_machine::learning::gbm::GradientBoostingRegressor;
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::vector::Vector;
+    ///
+    /// let inputs = Matrix::new(4, 1, vec![1.0, 2.0, 3.0, 4.0]);
+    /// let targets = Vector::new(vec![1.0, 1.0, 5.0, 5.0]);
+    /// let weights = Vector::new(vec![1.0, 1.0, 3.0, 3.0]);
+    ///
+    /// let mut model = GradientBoostingRegressor::default();
+    /// model.train_weighted(&inputs, &targets, &weights);
+    ///
+    /// // The heavier rows pull the starting output towards them.
+    /// assert_eq!(model.base_score(), 4.0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The inputs and targets have different lengths.
+    /// - There are no rows.
+    /// - The monotone constraints are set, but not for each column.
+    /// - The weights are invalid, as for `tree::check_weights`.
+    fn train_weighted(&mut self, inputs: &Matrix<f64>, targets: &Vector<f64>, weights: &Vector<f64>) {
+        self.fit(inputs, targets, weights);
+    }
+}
+
+impl<O: Objective> GradientBoostingRegressor<O> {
+    fn fit(&mut self, inputs: &Matrix<f64>, targets: &Vector<f64>, weights: &Vector<f64>) {
+        assert!(inputs.rows() == targets.size(),
+                "Inputs and targets have different lengths.");
+        assert!(inputs.rows() > 0, "Cannot train on no data.");
+        assert!(self.monotone.is_empty() || self.monotone.len() == inputs.cols(),
+                "There must be a monotone constraint for each column.");
+        tree::check_weights(inputs, weights.data());
+
+        let n = inputs.rows();
+        let binned = Binned::new(inputs, self.max_bins);
+
+        self.base_score = self.objective.base_score(targets, weights);
+        let mut outputs = Vector::new(vec![self.base_score; n]);
+        let rows = (0..n).collect::<Vec<_>>();
+
+        let mut trees = Vec::with_capacity(self.n_estimators);
+        for _ in 0..self.n_estimators {
+            let tree = Grower::new(&binned, &outputs, targets, weights, self).grow_tree(rows.clone());
+            for (i, output) in outputs.mut_data().iter_mut().enumerate() {
+                *output += tree.value(inputs.row(i))[0];
+            }
+            trees.push(tree);
+        }
+        self.trees = trees;
+    }
+}
+
+/// The bin of missing values.
+const MISSING_BIN: u8 = u8::MAX;
+
+/// The inputs replaced by the indices of their bins.
+struct Binned {
+    /// The upper edge of each bin of each column, but the last.
+    edges: Vec<Vec<f64>>,
+    /// The bin of each input, by row.
+    bins: Vec<u8>,
+    cols: usize,
+    has_missing: bool,
+}
+
+impl Binned {
+    fn new(inputs: &Matrix<f64>, max_bins: usize) -> Binned {
+        let cols = inputs.cols();
+        let edges = (0..cols)
+            .map(|j| {
+                let values = (0..inputs.rows())
+                    .map(|i| inputs[[i, j]])
+                    .filter(|x| !x.is_nan())
+                    .collect::<Vec<_>>();
+                bin_edges(values, max_bins)
+            })
+            .collect::<Vec<_>>();
+
+        let bins = inputs.data()
+            .iter()
+            .enumerate()
+            .map(|(k, &x)| if x.is_nan() {
+                MISSING_BIN
+            } else {
+                edges[k % cols].partition_point(|&e| e < x) as u8
+            })
+            .collect::<Vec<_>>();
+
+        Binned {
+            has_missing: bins.contains(&MISSING_BIN),
+            edges: edges,
+            bins: bins,
+            cols: cols,
+        }
+    }
+
+    fn bin(&self, row: usize, col: usize) -> u8 {
+        self.bins[row * self.cols + col]
+    }
+}
+
+/// The upper edges of at most `max_bins` bins of the values.
+///
+/// A value goes in the first bin whose edge it does not exceed.
+fn bin_edges(mut values: Vec<f64>, max_bins: usize) -> Vec<f64> {
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut distinct = values.clone();
+    distinct.dedup();
+
+    if distinct.len() <= max_bins {
+        // A bin for each value, with the edges halfway between them.
+        distinct.windows(2)
+            .map(|w| {
+                let mid = w[0] + (w[1] - w[0]) / 2f64;
+                if mid < w[1] { mid } else { w[0] }
+            })
+            .collect()
+    } else {
+        // Edges at evenly spaced quantiles of the values.
+        let n = values.len();
+        let mut edges = (1..max_bins).map(|i| values[i * n / max_bins - 1]).collect::<Vec<_>>();
+        edges.dedup();
+        if edges.last() == values.last() {
+            edges.pop();
+        }
+        edges
+    }
+}
+
+/// The sums of the weighted gradients and hessians of a set of rows.
+#[derive(Clone, Copy, Default)]
+struct GradStats {
+    n: usize,
+    /// The total weight of the rows.
+    total_weight: f64,
+    grad: f64,
+    grad_sq: f64,
+    hess: f64,
+}
+
+impl GradStats {
+    fn add(&mut self, weight: f64, grad: f64, hess: f64) {
+        self.n += 1;
+        self.total_weight += weight;
+        self.grad += grad;
+        self.grad_sq += grad * grad;
+        self.hess += hess;
+    }
+
+    fn plus(self, other: GradStats) -> GradStats {
+        GradStats {
+            n: self.n + other.n,
+            total_weight: self.total_weight + other.total_weight,
+            grad: self.grad + other.grad,
+            grad_sq: self.grad_sq + other.grad_sq,
+            hess: self.hess + other.hess,
+        }
+    }
+
+    fn minus(self, other: GradStats) -> GradStats {
+        GradStats {
+            n: self.n - other.n,
+            total_weight: self.total_weight - other.total_weight,
+            grad: self.grad - other.grad,
+            grad_sq: self.grad_sq - other.grad_sq,
+            hess: self.hess - other.hess,
+        }
+    }
+
+    /// The leaf value within the bounds which minimizes the penalized
+    /// second-order loss.
+    fn weight(&self, lambda: f64, bounds: (f64, f64)) -> f64 {
+        (-self.grad / (self.hess + lambda)).max(bounds.0).min(bounds.1)
+    }
+
+    /// The decrease in the penalized second-order loss of a leaf with
+    /// the value.
+    fn score(&self, lambda: f64, weight: f64) -> f64 {
+        -(2f64 * self.grad * weight + (self.hess + lambda) * weight * weight)
+    }
+
+    fn variance(&self) -> f64 {
+        let n = self.n as f64;
+        let mean = self.grad / n;
+        (self.grad_sq / n - mean * mean).max(0f64)
+    }
+}
+
+/// The best split found for a node.
+struct Candidate {
+    feature: usize,
+    /// The last bin on the left.
+    bin: usize,
+    missing_left: bool,
+    /// The values of the left and right leaves.
+    weights: (f64, f64),
+    gain: f64,
+}
+
+/// Grows one tree on the gradients and hessians of the rows.
+struct Grower<'a, O: Objective + 'a> {
+    binned: &'a Binned,
+    outputs: &'a Vector<f64>,
+    targets: &'a Vector<f64>,
+    weights: &'a Vector<f64>,
+    grad: Vec<f64>,
+    hess: Vec<f64>,
+    model: &'a GradientBoostingRegressor<O>,
+    nodes: Vec<Node>,
+}
+
+impl<'a, O: Objective> Grower<'a, O> {
+    fn new(binned: &'a Binned,
+           outputs: &'a Vector<f64>,
+           targets: &'a Vector<f64>,
+           weights: &'a Vector<f64>,
+           model: &'a GradientBoostingRegressor<O>)
+           -> Grower<'a, O> {
+        let (grad, hess) = model.objective.gradients(outputs, targets);
+
+        Grower {
+            binned: binned,
+            outputs: outputs,
+            targets: targets,
+            weights: weights,
+            grad: grad.elemul(weights).into_vec(),
+            hess: hess.elemul(weights).into_vec(),
+            model: model,
+            nodes: Vec::new(),
+        }
+    }
+
+    fn grow_tree(mut self, rows: Vec<usize>) -> Tree {
+        self.grow(rows, 0, (f64::NEG_INFINITY, f64::INFINITY));
+        Tree::from_nodes(self.nodes, self.binned.cols, self.binned.has_missing)
+    }
+
+    /// Adds the row to the stats.
+    fn add(&self, stats: &mut GradStats, row: usize) {
+        stats.add(self.weights[row], self.grad[row], self.hess[row]);
+    }
+
+    /// Adds the node for the rows and its subtree, returning its index.
+    ///
+    /// The values of the nodes are kept within the bounds.
+    fn grow(&mut self, rows: Vec<usize>, depth: usize, bounds: (f64, f64)) -> usize {
+        let mut stats = GradStats::default();
+        for &r in &rows {
+            self.add(&mut stats, r);
+        }
+
+        let idx = self.nodes.len();
+        self.nodes.push(Node {
+            value: vec![self.model.learning_rate * stats.weight(self.model.lambda, bounds)],
+            samples: rows.len(),
+            weight: stats.total_weight,
+            impurity: stats.variance(),
+            branch: None,
+        });
+
+        let candidate = if depth < self.model.max_depth &&
+                           rows.len() >= 2 * self.model.min_samples_leaf {
+            self.best_split(&rows, stats, bounds)
+        } else {
+            None
+        };
+
+        if let Some(candidate) = candidate {
+            let (left_rows, right_rows): (Vec<usize>, Vec<usize>) = rows.iter().partition(|&&r| {
+                match self.binned.bin(r, candidate.feature) {
+                    MISSING_BIN => candidate.missing_left,
+                    bin => bin as usize <= candidate.bin,
+                }
+            });
+
+            // A constrained split bounds the values on each side by
+            // the midpoint of its leaf values.
+            let mid = (candidate.weights.0 + candidate.weights.1) / 2f64;
+            let (left_bounds, right_bounds) = match self.constraint(candidate.feature) {
+                Monotone::Increasing => ((bounds.0, mid), (mid, bounds.1)),
+                Monotone::Decreasing => ((mid, bounds.1), (bounds.0, mid)),
+                Monotone::Unconstrained => (bounds, bounds),
+            };
+
+            let threshold = self.binned.edges[candidate.feature][candidate.bin];
+            let left = self.grow(left_rows, depth + 1, left_bounds);
+            let right = self.grow(right_rows, depth + 1, right_bounds);
+            self.nodes[idx].branch = Some(Branch {
+                feature: candidate.feature,
+                split: Split::Threshold(threshold),
+                left: left,
+                right: right,
+                missing_left: candidate.missing_left,
+            });
+        } else if let Some(value) = self.model.objective.leaf_value(self.outputs, self.targets, self.weights, &rows) {
+            let value = value.max(bounds.0).min(bounds.1);
+            self.nodes[idx].value = vec![self.model.learning_rate * value];
+        }
+
+        idx
+    }
+
+    fn constraint(&self, feature: usize) -> Monotone {
+        self.model.monotone.get(feature).cloned().unwrap_or(Monotone::Unconstrained)
+    }
+
+    /// The split of the rows with the largest positive gain which
+    /// keeps the leaf values within the bounds and in the order of any
+    /// constraint on its feature.
+    fn best_split(&self,
+                  rows: &[usize],
+                  stats: GradStats,
+                  bounds: (f64, f64))
+                  -> Option<Candidate> {
+        let lambda = self.model.lambda;
+        let min_leaf = self.model.min_samples_leaf;
+        let parent = stats.score(lambda, stats.weight(lambda, bounds));
+        let mut best: Option<Candidate> = None;
+
+        for (feature, edges) in self.binned.edges.iter().enumerate() {
+            let constraint = self.constraint(feature);
+
+            // The stats of the rows in each bin.
+            let mut hist = vec![GradStats::default(); edges.len() + 1];
+            let mut missing = GradStats::default();
+            for &r in rows {
+                match self.binned.bin(r, feature) {
+                    MISSING_BIN => self.add(&mut missing, r),
+                    bin => self.add(&mut hist[bin as usize], r),
+                }
+            }
+
+            let present = stats.minus(missing);
+            let mut left = GradStats::default();
+            for (bin, bin_stats) in hist[..edges.len()].iter().enumerate() {
+                left = left.plus(*bin_stats);
+                let right = present.minus(left);
+
+                // Try the missing rows on each side.
+                let sides = if missing.n == 0 {
+                    [Some((left, right, left.n >= right.n)), None]
+                } else {
+                    [Some((left.plus(missing), right, true)),
+                     Some((left, right.plus(missing), false))]
+                };
+
+                for &(l, r, missing_left) in sides.iter().flatten() {
+                    if l.n < min_leaf || r.n < min_leaf || l.total_weight <= 0f64 || r.total_weight <= 0f64 {
+                        continue;
+                    }
+
+                    let weights = (l.weight(lambda, bounds), r.weight(lambda, bounds));
+                    let ordered = match constraint {
+                        Monotone::Increasing => weights.0 <= weights.1,
+                        Monotone::Decreasing => weights.0 >= weights.1,
+                        Monotone::Unconstrained => true,
+                    };
+                    if !ordered {
+                        continue;
+                    }
+
+                    let gain = l.score(lambda, weights.0) + r.score(lambda, weights.1) - parent;
+                    if gain > 1e-12 && best.as_ref().is_none_or(|b| gain > b.gain) {
+                        best = Some(Candidate {
+                            feature: feature,
+                            bin: bin,
+                            missing_left: missing_left,
+                            weights: weights,
+                            gain: gain,
+                        });
+                    }
+                }
+            }
+        }
+
+        best
+    }
+}
@@ -1,8 +1,12 @@
+use std::fmt;
+
 use linalg::vector::Vector;
 use linalg::matrix::Matrix;
 
-use learning::SupModel;
+use learning::{ApproxEq, ModelParams, ParamGroup, SupModel};
+use learning::{linear_param_groups, params_approx_eq};
 
+#[derive(Clone)]
 pub struct GenLinearModel<C: Criterion> {
     parameters: Option<Vector<f64>>,
     criterion: C,
@@ -17,6 +21,30 @@ impl<C: Criterion> GenLinearModel<C> {
 	}
 }
 
+impl<C: Criterion + fmt::Debug> fmt::Debug for GenLinearModel<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GenLinearModel")
+            .field("criterion", &self.criterion)
+            .field("trained", &self.parameters.is_some())
+            .finish()
+    }
+}
+
+impl<C: Criterion> ApproxEq for GenLinearModel<C> {
+    fn approx_eq(&self, other: &GenLinearModel<C>, tol: f64) -> bool {
+        params_approx_eq(self.parameters.as_ref().map(|p| p.data().as_slice()),
+                         other.parameters.as_ref().map(|p| p.data().as_slice()),
+                         tol)
+    }
+}
+
+/// The `"intercept"` and the `"coefficients"` of the inputs.
+impl<C: Criterion> ModelParams for GenLinearModel<C> {
+    fn param_groups(&self) -> Vec<ParamGroup> {
+        linear_param_groups(self.parameters.as_ref())
+    }
+}
+
 impl<C: Criterion> SupModel<Matrix<f64>, Vector<f64>> for GenLinearModel<C> {
     /// Predict output from inputs.
     fn predict(&self, inputs: &Matrix<f64>) -> Vector<f64> {
@@ -126,6 +154,7 @@ pub trait LinkFunc {
 }
 
 /// Sigmoid activation function.
+#[derive(Clone, Copy, Debug)]
 pub struct Logit;
 
 impl LinkFunc for Logit {
@@ -148,6 +177,7 @@ impl LinkFunc for Logit {
     }
 }
 
+#[derive(Clone, Copy, Debug)]
 pub struct Bernoulli;
 
 impl Criterion for Bernoulli {
@@ -1,6 +1,7 @@
 //! Gaussian Processes
 //!
-//! Provides implementation of gaussian process regression.
+//! Provides implementation of gaussian process regression and binary
+//! gaussian process classification.
 //!
 //! # Usage
 //!
@@ -25,12 +26,46 @@
 //! Alternatively one could use gaussp.get_posterior() which would return both
 //! the predictive mean and covariance. However, this is likely to change in
 //! a future release.
+//!
+//! Rather than setting the kernel by hand, its hyperparameters and the
+//! noise can be fitted to the training data by maximizing the marginal
+//! likelihood with `optimize_hyperparams`.
+//!
+//! The `GaussianProcessClassifier` predicts the probability of the
+//! positive class for targets of 0 or 1.
+//!
+//! ```
+//! use rusty_machine::learning::gp::GaussianProcessClassifier;
+//! use rusty_machine::learning::SupModel;
+//! use rusty_machine::linalg::matrix::Matrix;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! let inputs = Matrix::new(6, 1, vec![-3., -2., -1., 1., 2., 3.]);
+//! let targets = Vector::new(vec![0., 0., 0., 1., 1., 1.]);
+//!
+//! let mut gpc = GaussianProcessClassifier::default();
+//! gpc.train(&inputs, &targets);
+//!
+//! let probs = gpc.predict(&Matrix::new(2, 1, vec![-2.5, 2.5]));
+//! assert!(probs[0] < 0.5 && probs[1] > 0.5);
+//! ```
+
+use std::f64;
+use std::fmt;
+use std::vec::Vec;
 
-use learning::toolkit::kernel::{Kernel, SquaredExp};
-use learning::SupModel;
-use linalg::matrix::Matrix;
+use std::f64::consts::PI;
+
+use learning::optim::{OptimAlgorithm, Optimizable, TrainingReport};
+use learning::toolkit::kernel::{self, Hyperparameters, Kernel, SquaredExp};
+use learning::toolkit::stable;
+use learning::{ApproxEq, ModelParams, ParamGroup, SupModel, params_approx_eq};
+use linalg::matrix::{Matrix, BaseMatrix};
 use linalg::vector::Vector;
 
+#[cfg(not(feature = "std"))]
+use libnum::Float;
+
 /// Trait for GP mean functions.
 pub trait MeanFunc {
     /// Compute the mean function applied elementwise to a matrix.
@@ -38,6 +73,7 @@ pub trait MeanFunc {
 }
 
 /// Constant mean function
+#[derive(Clone, Copy, Debug)]
 pub struct ConstMean {
     a: f64,
 }
@@ -59,7 +95,8 @@ impl MeanFunc for ConstMean {
 ///
 /// Gaussian process with generic kernel and deterministic mean function.
 /// Can be used for gaussian process regression with noise.
-/// Currently does not support classification.
+/// See `GaussianProcessClassifier` for classification.
+#[derive(Clone)]
 pub struct GaussianProcess<T: Kernel, U: MeanFunc> {
     ker: T,
     mean: U,
@@ -118,24 +155,52 @@ impl<T: Kernel, U: MeanFunc> GaussianProcess<T, U> {
         }
     }
 
+    /// The kernel of the GP.
+    pub fn kernel(&self) -> &T {
+        &self.ker
+    }
+
     /// Construct a kernel matrix
     fn ker_mat(&self, m1: &Matrix<f64>, m2: &Matrix<f64>) -> Matrix<f64> {
-        assert_eq!(m1.cols(), m2.cols());
-        let cols = m1.cols();
+        kernel::gram_matrix(&self.ker, m1, m2)
+    }
+}
 
-        let dim1 = m1.rows();
-        let dim2 = m2.rows();
+impl<T: Kernel + fmt::Debug, U: MeanFunc + fmt::Debug> fmt::Debug for GaussianProcess<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GaussianProcess")
+            .field("ker", &self.ker)
+            .field("mean", &self.mean)
+            .field("noise", &self.noise)
+            .field("trained", &self.alpha.is_some())
+            .finish()
+    }
+}
 
-        let mut ker_data = Vec::with_capacity(dim1 * dim2);
+/// Compares the training data and the fitted weights of the data.
+impl<T: Kernel, U: MeanFunc> ApproxEq for GaussianProcess<T, U> {
+    fn approx_eq(&self, other: &GaussianProcess<T, U>, tol: f64) -> bool {
+        params_approx_eq(self.alpha.as_ref().map(|a| a.data().as_slice()),
+                         other.alpha.as_ref().map(|a| a.data().as_slice()),
+                         tol) &&
+        params_approx_eq(self.train_data.as_ref().map(|x| x.data().as_slice()),
+                         other.train_data.as_ref().map(|x| x.data().as_slice()),
+                         tol)
+    }
+}
 
-        for i in 0..dim1 {
-            for j in 0..dim2 {
-                ker_data.push(self.ker.kernel(&m1.data()[i * cols..(i + 1) * cols],
-                                              &m2.data()[j * cols..(j + 1) * cols]));
+/// The `"train_inputs"` and the weight of each, the
+/// `"dual_coefficients"`.
+impl<T: Kernel, U: MeanFunc> ModelParams for GaussianProcess<T, U> {
+    fn param_groups(&self) -> Vec<ParamGroup> {
+        match (&self.alpha, &self.train_data) {
+            (Some(alpha), Some(train_data)) => {
+                vec![ParamGroup::new("dual_coefficients",
+                                     Matrix::new(alpha.size(), 1, alpha.data().clone())),
+                     ParamGroup::new("train_inputs", train_data.clone())]
             }
+            _ => Vec::new(),
         }
-
-        Matrix::new(dim1, dim2, ker_data)
     }
 }
 
@@ -144,7 +209,7 @@ impl<T: Kernel, U: MeanFunc> SupModel<Matrix<f64>, Vector<f64>> for GaussianProc
     fn predict(&self, inputs: &Matrix<f64>) -> Vector<f64> {
 
         // Messy referencing for succint syntax
-        if let (&Some(ref alpha), &Some(ref t_data)) = (&self.alpha, &self.train_data) {
+        if let (Some(alpha), Some(t_data)) = (&self.alpha, &self.train_data) {
             let mean = self.mean.func(inputs.clone());
 
             let post_mean = self.ker_mat(inputs, t_data) * alpha;
@@ -160,7 +225,7 @@ impl<T: Kernel, U: MeanFunc> SupModel<Matrix<f64>, Vector<f64>> for GaussianProc
     fn train(&mut self, inputs: &Matrix<f64>, targets: &Vector<f64>) {
         let noise_mat = Matrix::identity(inputs.rows()) * self.noise;
 
-        let ker_mat = self.ker_mat(inputs, inputs);
+        let ker_mat = kernel::symmetric_gram_matrix(&self.ker, inputs);
 
         let train_mat = (ker_mat + noise_mat).cholesky();
 
@@ -180,9 +245,9 @@ impl<T: Kernel, U: MeanFunc> GaussianProcess<T, U> {
     ///
     /// Outputs the posterior mean and covariance matrix.
     pub fn get_posterior(&self, inputs: &Matrix<f64>) -> (Vector<f64>, Matrix<f64>) {
-        if let (&Some(ref t_mat), &Some(ref alpha), &Some(ref t_data)) = (&self.train_mat,
-                                                                          &self.alpha,
-                                                                          &self.train_data) {
+        if let (Some(t_mat), Some(alpha), Some(t_data)) = (&self.train_mat,
+                                                           &self.alpha,
+                                                           &self.train_data) {
             let mean = self.mean.func(inputs.clone());
 
             let post_mean = mean + self.ker_mat(inputs, t_data) * alpha;
@@ -205,6 +270,409 @@ impl<T: Kernel, U: MeanFunc> GaussianProcess<T, U> {
     }
 }
 
+impl<T: Hyperparameters, U: MeanFunc> GaussianProcess<T, U> {
+    /// Fit the kernel hyperparameters and the noise, then train the model.
+    ///
+    /// The hyperparameters maximize the log marginal likelihood of the
+    /// targets, found by the given optimization algorithm starting from
+    /// the current kernel and noise. Noise below 1e-6 is raised to 1e-6
+    /// at the start, as the noise is optimized on a log scale.
+    ///
+    /// Returns the report from the optimization.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::gp::{ConstMean, GaussianProcess};
+    /// use rusty_machine::learning::optim::fmincg::ConjugateGD;
+    /// use rusty_machine::learning::toolkit::kernel::SquaredExp;
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::vector::Vector;
+    ///
+    /// let inputs = Matrix::new(8, 1, vec![0., 1., 2., 3., 4., 5., 6., 7.]);
+    /// let targets = Vector::new(vec![0., 0.8, 0.9, 0.1, -0.8, -1., -0.3, 0.7]);
+    ///
+    /// let mut gaussp = GaussianProcess::new(SquaredExp::default(), ConstMean::default(), 0.1);
+    /// let report = gaussp.optimize_hyperparams(&inputs, &targets, &ConjugateGD::default());
+    ///
+    /// println!("{:?} with noise {}", gaussp.kernel(), gaussp.noise);
+    /// assert!(report.cost.is_finite());
+    /// ```
+    pub fn optimize_hyperparams<A>(&mut self,
+                                   inputs: &Matrix<f64>,
+                                   targets: &Vector<f64>,
+                                   alg: &A)
+                                   -> TrainingReport
+        where A: OptimAlgorithm<MarginalLikelihood<T>>
+    {
+        let mut start = self.ker.log_params();
+        start.push(self.noise.max(1e-6).ln());
+
+        let centered = targets - self.mean.func(inputs.clone());
+        let objective = MarginalLikelihood::new(self.ker.with_log_params(&start[..start.len() - 1]));
+        let result = alg.optimize(&objective, &start, inputs, &centered);
+
+        let n_params = result.params.len() - 1;
+        self.ker = self.ker.with_log_params(&result.params[..n_params]);
+        self.noise = result.params[n_params].exp();
+        self.train(inputs, targets);
+
+        result.report
+    }
+}
+
+/// The negative log marginal likelihood of a zero mean GP.
+///
+/// The parameters are the logarithms of the kernel hyperparameters
+/// followed by the logarithm of the noise. Subtract the mean function
+/// from the targets to use it with another mean.
+///
+/// The cost is infinite for parameters whose kernel matrix is not
+/// numerically positive definite.
+///
+/// The cost is
+///
+/// y<sup>T</sup>K<sup>-1</sup>y / 2 + ln|K| / 2 + n ln(2π) / 2
+///
+/// where K is the kernel matrix of the inputs plus the noise.
+#[derive(Clone, Copy, Debug)]
+pub struct MarginalLikelihood<T: Hyperparameters> {
+    ker: T,
+}
+
+impl<T: Hyperparameters> MarginalLikelihood<T> {
+    /// Constructs the objective for the kernel.
+    ///
+    /// Only the type of the kernel and its fixed settings are used, the
+    /// hyperparameters are taken from the parameters being optimized.
+    pub fn new(ker: T) -> MarginalLikelihood<T> {
+        MarginalLikelihood { ker: ker }
+    }
+}
+
+impl<T: Hyperparameters> Optimizable for MarginalLikelihood<T> {
+    type Inputs = Matrix<f64>;
+    type Targets = Vector<f64>;
+
+    fn compute_grad(&self,
+                    params: &[f64],
+                    inputs: &Matrix<f64>,
+                    targets: &Vector<f64>)
+                    -> (f64, Vec<f64>) {
+        assert!(inputs.rows() == targets.size(),
+                "Inputs and targets have different lengths.");
+
+        let n = inputs.rows();
+        let n_params = params.len() - 1;
+        let ker = self.ker.with_log_params(&params[..n_params]);
+        let noise = params[n_params].exp();
+
+        let ker_mat = kernel::symmetric_gram_matrix(&ker, inputs) +
+                      Matrix::identity(n) * noise;
+
+        // Hyperparameters which are numerically singular are infinitely
+        // unlikely, which makes line searches step back.
+        let chol = match checked_cholesky(&ker_mat) {
+            Some(chol) => chol,
+            None => return (f64::INFINITY, vec![0f64; params.len()]),
+        };
+        let chol_t = chol.transpose();
+        let alpha = solve_u_triangular(&chol_t, &solve_l_triangular(&chol, targets));
+
+        let log_det = (0..n).map(|i| chol[[i, i]].ln()).sum::<f64>();
+        let cost = 0.5 * targets.dot(&alpha) + log_det + 0.5 * n as f64 * (2f64 * PI).ln();
+
+        // The columns of the inverse kernel matrix.
+        let mut inv_data = Vec::with_capacity(n * n);
+        for i in 0..n {
+            let unit = (0..n).map(|j| if i == j { 1f64 } else { 0f64 }).collect::<Vector<f64>>();
+            inv_data.append(&mut solve_u_triangular(&chol_t, &solve_l_triangular(&chol, &unit))
+                .into_vec());
+        }
+        let ker_inv = Matrix::new(n, n, inv_data);
+
+        // d cost / d theta = tr((K^-1 - alpha alpha^T) dK / d theta) / 2
+        let mut grad = vec![0f64; params.len()];
+        for i in 0..n {
+            for j in 0..n {
+                let weight = 0.5 * (ker_inv[[i, j]] - alpha[i] * alpha[j]);
+                let ker_grad = ker.log_param_grad(inputs.row(i), inputs.row(j));
+                for (g, d) in grad.iter_mut().zip(ker_grad) {
+                    *g += weight * d;
+                }
+            }
+            grad[n_params] += 0.5 * noise * (ker_inv[[i, i]] - alpha[i] * alpha[i]);
+        }
+
+        (cost, grad)
+    }
+}
+
+/// Gaussian Process Classifier
+///
+/// Binary classification with a zero mean latent gaussian process and
+/// a logistic likelihood. The posterior of the latent function is
+/// approximated by a gaussian at its mode, the Laplace approximation,
+/// which is found by Newton's method.
+///
+/// The targets must be 0 or 1, and the predictions are the
+/// probabilities of class 1. The probabilities average the logistic
+/// function over the latent uncertainty, so they move towards 0.5 far
+/// from the training data.
+#[derive(Clone)]
+pub struct GaussianProcessClassifier<T: Kernel> {
+    ker: T,
+    /// The maximum number of Newton iterations used to find the mode.
+    pub max_iters: usize,
+    /// The change in the objective at which the mode is found.
+    pub tol: f64,
+    fit: Option<LaplaceFit>,
+}
+
+/// The Laplace approximation at the posterior mode.
+#[derive(Clone)]
+struct LaplaceFit {
+    train_data: Matrix<f64>,
+    /// The gradient of the log likelihood, `targets - sigmoid(f)`.
+    grad: Vector<f64>,
+    /// The square roots of the negative Hessian of the log likelihood.
+    sqrt_w: Vector<f64>,
+    /// The Cholesky factor of `I + sqrt(W) K sqrt(W)`.
+    chol: Matrix<f64>,
+    log_marginal: f64,
+}
+
+/// Construct a default Gaussian Process Classifier
+///
+/// The defaults are:
+///
+/// - Squared Exponential kernel.
+/// - max_iters = 100
+/// - tol = 1e-10
+impl Default for GaussianProcessClassifier<SquaredExp> {
+    fn default() -> GaussianProcessClassifier<SquaredExp> {
+        GaussianProcessClassifier::new(SquaredExp::default())
+    }
+}
+
+impl<T: Kernel> GaussianProcessClassifier<T> {
+    /// Construct a new Gaussian Process Classifier with the kernel of
+    /// the latent function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::gp::GaussianProcessClassifier;
+    /// use rusty_machine::learning::toolkit::kernel::SquaredExp;
+    ///
+    /// let gpc = GaussianProcessClassifier::new(SquaredExp::new(2., 5.));
+    /// ```
+    pub fn new(ker: T) -> GaussianProcessClassifier<T> {
+        GaussianProcessClassifier {
+            ker: ker,
+            max_iters: 100,
+            tol: 1e-10,
+            fit: None,
+        }
+    }
+
+    /// The approximate log marginal likelihood of the training targets.
+    ///
+    /// Higher values indicate a better kernel for the data. Returns
+    /// None if the model has not been trained.
+    pub fn log_marginal_likelihood(&self) -> Option<f64> {
+        self.fit.as_ref().map(|f| f.log_marginal)
+    }
+
+    /// Predicts the mean and variance of the latent function.
+    ///
+    /// The probabilities are found by squashing the latent function,
+    /// so its variance measures the uncertainty of the predictions.
+    ///
+    /// # Panics
+    ///
+    /// - The model has not been trained.
+    pub fn predict_latent(&self, inputs: &Matrix<f64>) -> (Vector<f64>, Vector<f64>) {
+        let fit = match self.fit {
+            Some(ref fit) => fit,
+            None => panic!("The model has not been trained."),
+        };
+
+        let test_mat = kernel::gram_matrix(&self.ker, inputs, &fit.train_data);
+        let mean = &test_mat * &fit.grad;
+
+        let var = (0..inputs.rows())
+            .map(|i| {
+                let k = Vector::new(test_mat.select_rows(&[i]).into_vec()).elemul(&fit.sqrt_w);
+                let v = solve_l_triangular(&fit.chol, &k);
+                let row = &inputs.data()[i * inputs.cols()..(i + 1) * inputs.cols()];
+                (self.ker.kernel(row, row) - v.dot(&v)).max(0f64)
+            })
+            .collect();
+
+        (mean, var)
+    }
+}
+
+impl<T: Kernel + fmt::Debug> fmt::Debug for GaussianProcessClassifier<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GaussianProcessClassifier")
+            .field("ker", &self.ker)
+            .field("max_iters", &self.max_iters)
+            .field("tol", &self.tol)
+            .field("trained", &self.fit.is_some())
+            .finish()
+    }
+}
+
+/// Compares the training data and the fitted weights of the data.
+impl<T: Kernel> ApproxEq for GaussianProcessClassifier<T> {
+    fn approx_eq(&self, other: &GaussianProcessClassifier<T>, tol: f64) -> bool {
+        params_approx_eq(self.fit.as_ref().map(|f| f.grad.data().as_slice()),
+                         other.fit.as_ref().map(|f| f.grad.data().as_slice()),
+                         tol) &&
+        params_approx_eq(self.fit.as_ref().map(|f| f.train_data.data().as_slice()),
+                         other.fit.as_ref().map(|f| f.train_data.data().as_slice()),
+                         tol)
+    }
+}
+
+/// The `"train_inputs"` and the weight of each in the latent mean,
+/// the `"dual_coefficients"`.
+impl<T: Kernel> ModelParams for GaussianProcessClassifier<T> {
+    fn param_groups(&self) -> Vec<ParamGroup> {
+        match self.fit {
+            Some(ref fit) => {
+                vec![ParamGroup::new("dual_coefficients",
+                                     Matrix::new(fit.grad.size(), 1, fit.grad.data().clone())),
+                     ParamGroup::new("train_inputs", fit.train_data.clone())]
+            }
+            None => Vec::new(),
+        }
+    }
+}
+
+impl<T: Kernel> SupModel<Matrix<f64>, Vector<f64>> for GaussianProcessClassifier<T> {
+    /// Predicts the probability of class 1.
+    ///
+    /// The logistic function is averaged over the latent distribution
+    /// with the probit approximation, `sigmoid(mean / sqrt(1 + pi var / 8))`.
+    ///
+    /// # Panics
+    ///
+    /// - The model has not been trained.
+    fn predict(&self, inputs: &Matrix<f64>) -> Vector<f64> {
+        let (mean, var) = self.predict_latent(inputs);
+
+        mean.data()
+            .iter()
+            .zip(var.data())
+            .map(|(m, v)| stable::sigmoid(m / (1f64 + PI * v / 8f64).sqrt()))
+            .collect()
+    }
+
+    /// Finds the Laplace approximation of the latent posterior.
+    ///
+    /// # Panics
+    ///
+    /// - The inputs and targets have different lengths.
+    /// - The targets are not all 0 or 1.
+    fn train(&mut self, inputs: &Matrix<f64>, targets: &Vector<f64>) {
+        assert!(inputs.rows() == targets.size(),
+                "Inputs and targets have different lengths.");
+        assert!(targets.data().iter().all(|&t| t == 0f64 || t == 1f64),
+                "Targets must be 0 or 1.");
+
+        let ker_mat = kernel::symmetric_gram_matrix(&self.ker, inputs);
+        let log_lik = |f: &Vector<f64>| -> f64 {
+            f.data()
+                .iter()
+                .zip(targets.data())
+                .map(|(&f, &t)| -stable::cross_entropy_with_logits(f, t))
+                .sum()
+        };
+
+        let mut f = Vector::zeros(inputs.rows());
+        let mut objective = log_lik(&f);
+
+        // Newton's method for the mode, algorithm 3.1 of Rasmussen and
+        // Williams' Gaussian Processes for Machine Learning.
+        for _ in 0..self.max_iters {
+            let (grad, sqrt_w) = laplace_terms(&f, targets);
+            let chol = laplace_cholesky(&ker_mat, &sqrt_w);
+
+            let b = sqrt_w.elemul(&sqrt_w).elemul(&f) + grad;
+            let c = solve_l_triangular(&chol, &(&ker_mat * &b).elemul(&sqrt_w));
+            let a = b - solve_u_triangular(&chol.transpose(), &c).elemul(&sqrt_w);
+            f = &ker_mat * &a;
+
+            let next = -a.dot(&f) / 2f64 + log_lik(&f);
+            let converged = (next - objective).abs() < self.tol;
+            objective = next;
+
+            if converged {
+                break;
+            }
+        }
+
+        let (grad, sqrt_w) = laplace_terms(&f, targets);
+        let chol = laplace_cholesky(&ker_mat, &sqrt_w);
+        let log_det = (0..chol.rows()).map(|i| chol[[i, i]].ln()).sum::<f64>();
+
+        self.fit = Some(LaplaceFit {
+            train_data: inputs.clone(),
+            grad: grad,
+            sqrt_w: sqrt_w,
+            chol: chol,
+            log_marginal: objective - log_det,
+        });
+    }
+}
+
+/// The gradient of the log likelihood and the square roots of its
+/// negative Hessian at the latent values.
+fn laplace_terms(f: &Vector<f64>, targets: &Vector<f64>) -> (Vector<f64>, Vector<f64>) {
+    let probs: Vec<f64> = f.data().iter().map(|&x| stable::sigmoid(x)).collect();
+
+    (targets.data().iter().zip(&probs).map(|(t, p)| t - p).collect(),
+     probs.iter().map(|p| (p * (1f64 - p)).sqrt()).collect())
+}
+
+/// The Cholesky factor of `I + sqrt(W) K sqrt(W)`.
+fn laplace_cholesky(ker_mat: &Matrix<f64>, sqrt_w: &Vector<f64>) -> Matrix<f64> {
+    let n = ker_mat.rows();
+    Matrix::from_fn(n, n, |i, j| {
+            let b = sqrt_w[i] * ker_mat[[i, j]] * sqrt_w[j];
+            if i == j { b + 1f64 } else { b }
+        })
+        .cholesky()
+}
+
+/// The Cholesky decomposition, or `None` if the matrix is not
+/// numerically positive definite.
+fn checked_cholesky(mat: &Matrix<f64>) -> Option<Matrix<f64>> {
+    let n = mat.rows();
+    let mut data = vec![0f64; n * n];
+
+    for i in 0..n {
+        for j in 0..i + 1 {
+            let sum = (0..j).map(|k| data[i * n + k] * data[j * n + k]).sum::<f64>();
+
+            if i == j {
+                let diag = mat[[i, i]] - sum;
+                if diag <= 0f64 || !diag.is_finite() {
+                    return None;
+                }
+                data[i * n + i] = diag.sqrt();
+            } else {
+                data[i * n + j] = (mat[[i, j]] - sum) / data[j * n + j];
+            }
+        }
+    }
+
+    Some(Matrix::new(n, n, data))
+}
+
 /// Solves an upper triangular linear system.
 fn solve_u_triangular(mat: &Matrix<f64>, y: &Vector<f64>) -> Vector<f64> {
     assert!(mat.cols() == y.size(),
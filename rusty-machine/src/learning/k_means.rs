@@ -26,6 +26,10 @@
 //! Additionally you can control the initialization
 //! algorithm and max number of iterations.
 //!
+//! The model can also be trained on batches of data using mini-batch
+//! k-means through the `IncrementalUnSupModel` trait, or one sample at
+//! a time using online k-means with the `update` method.
+//!
 //! # Initializations
 //!
 //! Three initialization algorithms are supported.
@@ -42,15 +46,31 @@
 //! ## K-means++ initialization
 //!
 //! The [k-means++](https://en.wikipedia.org/wiki/K-means%2B%2B) scheme.
+//!
+//! # Inference only builds
+//!
+//! The initializations need the `rand` feature. Without it a model can
+//! still predict from saved `centroids` and learn with the online
+//! `update`, which takes its first centroids from the data.
 
-use linalg::matrix::Matrix;
+use std::fmt;
+use std::vec::Vec;
+
+use linalg::matrix::{Matrix, BaseMatrix};
 use linalg::vector::Vector;
-use learning::UnSupModel;
-use rand::{Rng, thread_rng};
+use linalg::utils;
+use learning::{ApproxEq, ModelParams, ParamGroup, UnSupModel, IncrementalUnSupModel};
+use learning::params_approx_eq;
+use learning::toolkit::distance::{Metric, SquaredEuclidean};
+#[cfg(feature = "rand")]
+use learning::toolkit::rand_utils;
+#[cfg(feature = "rand")]
+use rand::Rng;
 
 use libnum::abs;
 
 /// Initialization Algorithm enum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum InitAlgorithm {
     /// The Forgy initialization scheme.
     Forgy,
@@ -64,6 +84,7 @@ pub enum InitAlgorithm {
 ///
 /// Contains option for centroids.
 /// Specifies iterations and number of classes.
+#[derive(Clone)]
 pub struct KMeansClassifier {
     /// Max iterations of algorithm to run.
     pub iters: usize,
@@ -73,13 +94,45 @@ pub struct KMeansClassifier {
     pub centroids: Option<Matrix<f64>>,
     /// The initial algorithm to use.
     pub init_algorithm: InitAlgorithm,
+    /// The number of samples assigned to each centroid so far.
+    counts: Vec<usize>,
 }
 
-impl UnSupModel<Matrix<f64>, Vector<usize>> for KMeansClassifier {
+impl fmt::Debug for KMeansClassifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("KMeansClassifier")
+            .field("iters", &self.iters)
+            .field("k", &self.k)
+            .field("init_algorithm", &self.init_algorithm)
+            .field("trained", &self.centroids.is_some())
+            .finish()
+    }
+}
+
+/// Compares the centroids, including the order of the classes.
+impl ApproxEq for KMeansClassifier {
+    fn approx_eq(&self, other: &KMeansClassifier, tol: f64) -> bool {
+        params_approx_eq(self.centroids.as_ref().map(|c| c.data().as_slice()),
+                         other.centroids.as_ref().map(|c| c.data().as_slice()),
+                         tol)
+    }
+}
+
+/// The `"centroids"`, one in each row.
+impl ModelParams for KMeansClassifier {
+    fn param_groups(&self) -> Vec<ParamGroup> {
+        match self.centroids {
+            Some(ref centroids) => vec![ParamGroup::new("centroids", centroids.clone())],
+            None => Vec::new(),
+        }
+    }
+}
+
+impl<M: BaseMatrix<f64>> UnSupModel<M, Vector<usize>> for KMeansClassifier {
     /// Predict classes from data.
     ///
     /// Model must be trained.
-    fn predict(&self, inputs: &Matrix<f64>) -> Vector<usize> {
+    fn predict(&self, inputs: &M) -> Vector<usize> {
         if let Some(ref centroids) = self.centroids {
             return KMeansClassifier::find_closest_centroids(centroids, inputs).0;
         } else {
@@ -88,7 +141,12 @@ impl UnSupModel<Matrix<f64>, Vector<usize>> for KMeansClassifier {
     }
 
     /// Train the classifier using input data.
-    fn train(&mut self, inputs: &Matrix<f64>) {
+    ///
+    /// # Panics
+    ///
+    /// - The crate is built without the `rand` feature.
+    fn train(&mut self, inputs: &M) {
+        let inputs = &inputs.to_matrix();
         self.init_centroids(inputs);
         let mut cost = 0.0;
         let eps = 1e-14;
@@ -104,6 +162,74 @@ impl UnSupModel<Matrix<f64>, Vector<usize>> for KMeansClassifier {
 
             cost = cost_i;
         }
+
+        let (idx, _) = self.get_closest_centroids(inputs);
+        self.counts = vec![0; self.k];
+        for i in idx.data() {
+            self.counts[*i] += 1;
+        }
+    }
+}
+
+impl IncrementalUnSupModel<Matrix<f64>, Vector<usize>> for KMeansClassifier {
+    /// Update the centroids using a batch of input data.
+    ///
+    /// Uses the mini-batch k-means update. Each sample moves its
+    /// closest centroid towards it, with a step size which decreases
+    /// as more samples are assigned to that centroid. An untrained
+    /// model initializes the centroids from the first batch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::k_means::KMeansClassifier;
+    /// use rusty_machine::learning::{UnSupModel, IncrementalUnSupModel};
+    /// use rusty_machine::linalg::matrix::Matrix;
+    ///
+    /// let mut model = KMeansClassifier::new(2);
+    ///
+    /// let batch = Matrix::new(4, 1, vec![0.0, 0.2, 10.0, 10.2]);
+    /// model.train_incremental(&batch);
+    ///
+    /// let batch = Matrix::new(2, 1, vec![0.1, 10.1]);
+    /// model.train_incremental(&batch);
+    ///
+    /// let classes = model.predict(&Matrix::new(2, 1, vec![0.0, 10.0]));
+    /// assert!(classes[0] != classes[1]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The first batch has fewer rows than there are classes.
+    /// - The model is untrained and the crate is built without the
+    ///   `rand` feature.
+    /// - The batch has a different number of columns to the centroids.
+    fn train_incremental(&mut self, inputs: &Matrix<f64>) {
+        if self.centroids.is_none() {
+            self.init_centroids(inputs);
+        }
+        if self.counts.len() != self.k {
+            self.counts = vec![0; self.k];
+        }
+
+        let cols = inputs.cols();
+        let (idx, _) = self.get_closest_centroids(inputs);
+
+        if let Some(ref mut centroids) = self.centroids {
+            assert!(centroids.cols() == cols,
+                    "Batch columns do not match the centroids.");
+
+            let data = centroids.mut_data();
+            for (i, c) in idx.data().iter().enumerate() {
+                self.counts[*c] += 1;
+                let eta = 1f64 / self.counts[*c] as f64;
+
+                for j in 0..cols {
+                    let x = inputs.data()[i * cols + j];
+                    data[c * cols + j] += eta * (x - data[c * cols + j]);
+                }
+            }
+        }
     }
 }
 
@@ -126,12 +252,113 @@ impl KMeansClassifier {
             k: k,
             centroids: None,
             init_algorithm: InitAlgorithm::KPlusPlus,
+            counts: Vec::new(),
+        }
+    }
+
+    /// Sets the maximum number of iterations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::k_means::{KMeansClassifier, InitAlgorithm};
+    ///
+    /// let model = KMeansClassifier::new(5).iters(50)
+    ///                                     .init_algorithm(InitAlgorithm::Forgy);
+    ///
+    /// assert_eq!(model.iters, 50);
+    /// ```
+    pub fn iters(mut self, iters: usize) -> KMeansClassifier {
+        self.iters = iters;
+        self
+    }
+
+    /// Sets the initialization algorithm.
+    pub fn init_algorithm(mut self, init_algorithm: InitAlgorithm) -> KMeansClassifier {
+        self.init_algorithm = init_algorithm;
+        self
+    }
+
+    /// Updates the centroids with a single sample.
+    ///
+    /// Uses online (sequential) k-means. The closest centroid moves
+    /// towards the sample with a step size of one over the number of
+    /// samples assigned to it, so each centroid is the mean of its
+    /// samples. An untrained model takes the first k distinct samples as
+    /// its centroids, so the data is never held in memory.
+    ///
+    /// Returns the class of the sample.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::k_means::KMeansClassifier;
+    /// use rusty_machine::learning::UnSupModel;
+    /// use rusty_machine::linalg::matrix::Matrix;
+    ///
+    /// let mut model = KMeansClassifier::new(2);
+    ///
+    /// // The samples can come from any stream, for example a file reader.
+    /// for x in &[0.0, 10.0, 0.2, 10.2, 0.1, 10.1] {
+    ///     model.update(&[*x]);
+    /// }
+    ///
+    /// let classes = model.predict(&Matrix::new(2, 1, vec![0.0, 10.0]));
+    /// assert!(classes[0] != classes[1]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The sample has a different length to the centroids.
+    pub fn update(&mut self, x: &[f64]) -> usize {
+        if self.centroids.is_none() {
+            self.centroids = Some(Matrix::new(0, x.len(), Vec::new()));
+        }
+        if self.counts.len() != self.k {
+            self.counts = vec![0; self.k];
         }
+
+        let mut centroids = self.centroids.take().unwrap();
+        assert!(centroids.cols() == x.len(),
+                "Sample length does not match the centroids.");
+
+        let closest = if centroids.rows() > 0 {
+            let sample = Matrix::new(1, x.len(), x.to_vec());
+            let (idx, dist) = KMeansClassifier::find_closest_centroids(&centroids, &sample);
+            Some((idx[0], dist[0]))
+        } else {
+            None
+        };
+
+        let class = match closest {
+            Some((c, d)) if d == 0f64 || centroids.rows() == self.k => {
+                self.counts[c] += 1;
+                let eta = 1f64 / self.counts[c] as f64;
+                let cols = x.len();
+                let data = centroids.mut_data();
+                for (j, x_j) in x.iter().enumerate() {
+                    data[c * cols + j] += eta * (x_j - data[c * cols + j]);
+                }
+                c
+            }
+            _ => {
+                let c = centroids.rows();
+                let mut data = centroids.into_vec();
+                data.extend_from_slice(x);
+                centroids = Matrix::new(c + 1, x.len(), data);
+                self.counts[c] = 1;
+                c
+            }
+        };
+
+        self.centroids = Some(centroids);
+        class
     }
 
     /// Initialize the centroids.
     ///
     /// Used internally within model.
+    #[cfg(feature = "rand")]
     fn init_centroids(&mut self, inputs: &Matrix<f64>) {
         match self.init_algorithm {
             InitAlgorithm::Forgy => {
@@ -146,6 +373,12 @@ impl KMeansClassifier {
         }
     }
 
+    /// The initializations draw random samples.
+    #[cfg(not(feature = "rand"))]
+    fn init_centroids(&mut self, _inputs: &Matrix<f64>) {
+        panic!("Initializing the centroids requires the `rand` feature.");
+    }
+
     /// Updated the centroids by computing means of assigned classes.
     ///
     /// Used internally within model.
@@ -179,22 +412,22 @@ impl KMeansClassifier {
     ///
     /// Used internally within model.
     /// Returns the index of the closest centroid and the distance to it.
-    fn find_closest_centroids(centroids: &Matrix<f64>,
-                              inputs: &Matrix<f64>)
-                              -> (Vector<usize>, Vector<f64>) {
+    fn find_closest_centroids<M: BaseMatrix<f64>>(centroids: &Matrix<f64>,
+                                                  inputs: &M)
+                                                  -> (Vector<usize>, Vector<f64>) {
         let mut idx = Vec::with_capacity(inputs.rows());
         let mut distances = Vec::with_capacity(inputs.rows());
 
         for i in 0..inputs.rows() {
-            // This works like repmat pulling out row i repeatedly.
-            let centroid_diff = centroids - inputs.select_rows(&vec![i; centroids.rows()]);
-            let dist = &centroid_diff.elemul(&centroid_diff).sum_cols();
+            let x = inputs.row(i);
+            let dist: Vec<f64> = (0..centroids.rows())
+                                     .map(|c| SquaredEuclidean.distance(centroids.row(c), x))
+                                     .collect();
 
             // Now take argmin and this is the centroid.
-            let (min_idx, min_dist) = dist.argmin();
+            let (min_idx, min_dist) = utils::argmin(&dist);
             idx.push(min_idx);
             distances.push(min_dist);
-
         }
 
         (Vector::new(idx), Vector::new(distances))
@@ -203,11 +436,12 @@ impl KMeansClassifier {
     /// Compute initial centroids using Forgy scheme.
     ///
     /// Selects k random points in data for centroids.
+    #[cfg(feature = "rand")]
     fn forgy_init(k: usize, inputs: &Matrix<f64>) -> Matrix<f64> {
         assert!(k <= inputs.rows());
 
         let mut random_choices = Vec::with_capacity(k);
-        let mut rng = thread_rng();
+        let mut rng = rand_utils::rng();
         while random_choices.len() < k {
             let r = rng.gen_range(0, inputs.rows());
 
@@ -223,6 +457,7 @@ impl KMeansClassifier {
     ///
     /// Selects centroids by assigning each point randomly to a class
     /// and computing the mean of each class.
+    #[cfg(feature = "rand")]
     fn ran_partition_init(k: usize, inputs: &Matrix<f64>) -> Matrix<f64> {
         assert!(k <= inputs.rows());
 
@@ -233,7 +468,7 @@ impl KMeansClassifier {
             random_assignments.push(i);
         }
 
-        let mut rng = thread_rng();
+        let mut rng = rand_utils::rng();
         for _ in k..inputs.rows() {
             random_assignments.push(rng.gen_range(0, k));
         }
@@ -259,10 +494,11 @@ impl KMeansClassifier {
     ///
     /// Selects centroids using weighted probability from
     /// distances.
+    #[cfg(feature = "rand")]
     fn plusplus_init(k: usize, inputs: &Matrix<f64>) -> Matrix<f64> {
         assert!(k <= inputs.rows());
 
-        let mut rng = thread_rng();
+        let mut rng = rand_utils::rng();
 
         let mut init_centroids = Vec::with_capacity(k * inputs.cols());
         let first_cen = rng.gen_range(0usize, inputs.rows());
@@ -271,8 +507,8 @@ impl KMeansClassifier {
 
         for i in 1..k {
             let temp_centroids = Matrix::new(i, inputs.cols(), init_centroids.clone());
-            let (_, dist) = KMeansClassifier::find_closest_centroids(&temp_centroids, &inputs);
-            let next_cen = sample_discretely(dist);
+            let (_, dist) = KMeansClassifier::find_closest_centroids(&temp_centroids, inputs);
+            let next_cen = sample_discretely(dist, &mut rng);
             init_centroids.append(&mut inputs.select_rows(&vec![next_cen]).into_vec())
         }
 
@@ -283,12 +519,13 @@ impl KMeansClassifier {
 /// Sample from an unnormalized distribution.
 ///
 ///
-fn sample_discretely(unnorm_dist: Vector<f64>) -> usize {
+#[cfg(feature = "rand")]
+fn sample_discretely<R: Rng>(unnorm_dist: Vector<f64>, rng: &mut R) -> usize {
     assert!(unnorm_dist.size() > 0);
 
     let sum = unnorm_dist.sum();
 
-    let rand = thread_rng().gen_range(0.0f64, sum);
+    let rand = rng.gen_range(0.0f64, sum);
 
     let mut tempsum = 0.0;
     for (i, p) in unnorm_dist.data().iter().enumerate() {
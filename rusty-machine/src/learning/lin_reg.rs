@@ -30,26 +30,60 @@
 //! assert!(output[0] > 17f64, "Our regressor isn't very good!");
 //! ```
 
-use learning::SupModel;
-use linalg::matrix::Matrix;
+use std::fmt;
+use std::vec::Vec;
+
+use learning::{ApproxEq, ModelParams, ParamGroup, SupModel, WeightedSupModel};
+use learning::{linear_param_groups, params_approx_eq};
+use linalg::matrix::{Matrix, BaseMatrix};
 use linalg::vector::Vector;
+use linalg::utils;
 use learning::toolkit::cost_fn::CostFunc;
 use learning::toolkit::cost_fn::MeanSqError;
 use learning::optim::grad_desc::GradientDesc;
-use learning::optim::OptimAlgorithm;
+use learning::optim::{OptimAlgorithm, TrainingReport};
 use learning::optim::Optimizable;
 
 /// Linear Regression Model.
 ///
 /// Contains option for optimized parameter.
+#[derive(Clone)]
 pub struct LinRegressor {
     /// The parameters for the regression model.
     parameters: Option<Vector<f64>>,
+    report: Option<TrainingReport>,
 }
 
 impl Default for LinRegressor {
     fn default() -> LinRegressor {
-        LinRegressor { parameters: None }
+        LinRegressor {
+            parameters: None,
+            report: None,
+        }
+    }
+}
+
+impl fmt::Debug for LinRegressor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LinRegressor")
+            .field("trained", &self.parameters.is_some())
+            .field("report", &self.report)
+            .finish()
+    }
+}
+
+impl ApproxEq for LinRegressor {
+    fn approx_eq(&self, other: &LinRegressor, tol: f64) -> bool {
+        params_approx_eq(self.parameters.as_ref().map(|p| p.data().as_slice()),
+                         other.parameters.as_ref().map(|p| p.data().as_slice()),
+                         tol)
+    }
+}
+
+/// The `"intercept"` and the `"coefficients"` of the inputs.
+impl ModelParams for LinRegressor {
+    fn param_groups(&self) -> Vec<ParamGroup> {
+        linear_param_groups(self.parameters.as_ref())
     }
 }
 
@@ -64,9 +98,50 @@ impl LinRegressor {
             Some(ref x) => Some(x.clone()),
         }
     }
+
+    /// Get the diagnostics from the last training.
+    ///
+    /// Returns None if the model has not been trained by
+    /// `train_with_optimization`. The closed form training has no
+    /// iterations to report.
+    pub fn training_report(&self) -> Option<TrainingReport> {
+        self.report
+    }
+
+    /// Predicts the output for a single input row.
+    ///
+    /// Avoids constructing a one row matrix for real-time scoring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::lin_reg::LinRegressor;
+    /// use rusty_machine::learning::SupModel;
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::vector::Vector;
+    ///
+    /// let mut lin_mod = LinRegressor::default();
+    /// let inputs = Matrix::new(3,1, vec![2.0, 3.0, 4.0]);
+    /// let targets = Vector::new(vec![5.0, 6.0, 7.0]);
+    /// lin_mod.train(&inputs, &targets);
+    ///
+    /// assert!((lin_mod.predict_row(&[5.0]) - 8.0).abs() < 1e-8);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The model has not been trained.
+    /// - The inputs do not match the model parameters.
+    pub fn predict_row(&self, inputs: &[f64]) -> f64 {
+        if let Some(ref v) = self.parameters {
+            linear_output(v, inputs)
+        } else {
+            panic!("Model has not been trained.");
+        }
+    }
 }
 
-impl SupModel<Matrix<f64>, Vector<f64>> for LinRegressor {
+impl<M: BaseMatrix<f64>> SupModel<M, Vector<f64>> for LinRegressor {
     /// Train the linear regression model.
     ///
     /// Takes training data and output values as input.
@@ -85,23 +160,22 @@ impl SupModel<Matrix<f64>, Vector<f64>> for LinRegressor {
     ///
     /// lin_mod.train(&inputs, &targets);
     /// ```
-    fn train(&mut self, inputs: &Matrix<f64>, targets: &Vector<f64>) {
+    fn train(&mut self, inputs: &M, targets: &Vector<f64>) {
         let ones = Matrix::<f64>::ones(inputs.rows(), 1);
-        let full_inputs = ones.hcat(inputs);
+        let full_inputs = ones.hcat(&inputs.to_matrix());
 
         let xt = full_inputs.transpose();
 
         self.parameters = Some(((&xt * full_inputs).inverse() * &xt) * targets);
+        self.report = None;
     }
 
     /// Predict output value from input data.
     ///
     /// Model must be trained before prediction can be made.
-    fn predict(&self, inputs: &Matrix<f64>) -> Vector<f64> {
+    fn predict(&self, inputs: &M) -> Vector<f64> {
         if let Some(ref v) = self.parameters {
-            let ones = Matrix::<f64>::ones(inputs.rows(), 1);
-            let full_inputs = ones.hcat(inputs);
-            full_inputs * v
+            linear_outputs(v, inputs)
         }
         else {
             panic!("Model has not been trained.");
@@ -109,6 +183,68 @@ impl SupModel<Matrix<f64>, Vector<f64>> for LinRegressor {
     }
 }
 
+/// Computes the intercept plus the dot product with the coefficients
+/// for each input row, without copying the inputs.
+fn linear_outputs<M: BaseMatrix<f64>>(params: &Vector<f64>, inputs: &M) -> Vector<f64> {
+    assert!(params.size() == inputs.cols() + 1,
+            "Input columns do not match the model parameters.");
+
+    Vector::new((0..inputs.rows()).map(|i| linear_output(params, inputs.row(i))).collect())
+}
+
+/// Computes the intercept plus the dot product with the coefficients
+/// for a single input row.
+fn linear_output(params: &Vector<f64>, inputs: &[f64]) -> f64 {
+    assert!(params.size() == inputs.len() + 1,
+            "Input columns do not match the model parameters.");
+
+    let (intercept, coefs) = params.data().split_at(1);
+    intercept[0] + utils::dot(inputs, coefs)
+}
+
+impl WeightedSupModel<Matrix<f64>, Vector<f64>> for LinRegressor {
+    /// Train the linear regression model using weighted least squares.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::lin_reg::LinRegressor;
+    /// use rusty_machine::learning::{SupModel, WeightedSupModel};
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::vector::Vector;
+    ///
+    /// let inputs = Matrix::new(3,1, vec![1.0, 2.0, 3.0]);
+    /// let targets = Vector::new(vec![1.0, 2.0, 10.0]);
+    ///
+    /// // Ignore the outlier.
+    /// let weights = Vector::new(vec![1.0, 1.0, 0.0]);
+    ///
+    /// let mut lin_mod = LinRegressor::default();
+    /// lin_mod.train_weighted(&inputs, &targets, &weights);
+    ///
+    /// let output = lin_mod.predict(&Matrix::new(1,1, vec![3.0]));
+    /// assert!((output[0] - 3.0).abs() < 1e-8);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The number of weights does not match the number of input rows.
+    fn train_weighted(&mut self, inputs: &Matrix<f64>, targets: &Vector<f64>, weights: &Vector<f64>) {
+        assert!(inputs.rows() == weights.size(),
+                "Weights must have one entry per sample.");
+
+        let ones = Matrix::<f64>::ones(inputs.rows(), 1);
+        let full_inputs = ones.hcat(inputs);
+        let cols = full_inputs.cols();
+
+        // The transpose of the inputs with each sample scaled by its weight.
+        let xt_w = Matrix::from_fn(cols, inputs.rows(), |i, j| full_inputs[[j, i]] * weights[j]);
+
+        self.parameters = Some(((&xt_w * full_inputs).inverse() * &xt_w) * targets);
+        self.report = None;
+    }
+}
+
 impl Optimizable for LinRegressor {
     type Inputs = Matrix<f64>;
     type Targets = Vector<f64>;
@@ -123,6 +259,19 @@ impl Optimizable for LinRegressor {
 
         (cost, grad.into_vec())
     }
+
+    /// The intercept is not penalized.
+    fn is_penalized(&self, idx: usize) -> bool {
+        idx != 0
+    }
+
+    fn compute_hessian(&self,
+                       _params: &[f64],
+                       inputs: &Matrix<f64>,
+                       _targets: &Vector<f64>)
+                       -> Option<Matrix<f64>> {
+        Some(inputs.transpose() * inputs / (inputs.rows() as f64))
+    }
 }
 
 impl LinRegressor {
@@ -135,7 +284,8 @@ impl LinRegressor {
         let initial_params = vec![0.; full_inputs.cols()];
 
         let gd = GradientDesc::default();
-        let optimal_w = gd.optimize(self, &initial_params[..], &full_inputs, targets);
-        self.parameters = Some(Vector::new(optimal_w));
+        let result = gd.optimize(self, &initial_params[..], &full_inputs, targets);
+        self.parameters = Some(Vector::new(result.params));
+        self.report = Some(result.report);
     }
 }
\ No newline at end of file
@@ -34,24 +34,32 @@
 //! by using the `new` constructor instead. This allows us to provide
 //! a `GradientDesc` object with custom parameters.
 
-use learning::SupModel;
-use linalg::matrix::Matrix;
+use std::fmt;
+use std::vec::Vec;
+
+use learning::{ApproxEq, ModelParams, ParamGroup, SupModel, WeightedSupModel, IncrementalModel};
+use learning::{linear_param_groups, params_approx_eq};
+use linalg::matrix::{Matrix, BaseMatrix};
 use linalg::vector::Vector;
+use linalg::utils;
 use learning::toolkit::activ_fn::ActivationFunc;
 use learning::toolkit::activ_fn::Sigmoid;
-use learning::toolkit::cost_fn::CostFunc;
+use learning::toolkit::cost_fn::{CostFunc, WeightedCostFunc};
 use learning::toolkit::cost_fn::CrossEntropyError;
 use learning::optim::grad_desc::GradientDesc;
-use learning::optim::OptimAlgorithm;
+use learning::optim::newton::Newton;
+use learning::optim::{OptimAlgorithm, TrainingReport};
 use learning::optim::Optimizable;
 
 /// Logistic Regression Model.
 ///
 /// Contains option for optimized parameter.
+#[derive(Clone)]
 pub struct LogisticRegressor {
     /// The parameters for the regression model.
     parameters: Option<Vector<f64>>,
     gd: GradientDesc,
+    report: Option<TrainingReport>,
 }
 
 impl Default for LogisticRegressor {
@@ -59,9 +67,36 @@ impl Default for LogisticRegressor {
         LogisticRegressor {
             parameters: None,
             gd: GradientDesc::default(),
+            report: None,
         }
     }
 }
+
+impl fmt::Debug for LogisticRegressor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LogisticRegressor")
+            .field("gd", &self.gd)
+            .field("trained", &self.parameters.is_some())
+            .field("report", &self.report)
+            .finish()
+    }
+}
+
+impl ApproxEq for LogisticRegressor {
+    fn approx_eq(&self, other: &LogisticRegressor, tol: f64) -> bool {
+        params_approx_eq(self.parameters.as_ref().map(|p| p.data().as_slice()),
+                         other.parameters.as_ref().map(|p| p.data().as_slice()),
+                         tol)
+    }
+}
+
+/// The `"intercept"` and the `"coefficients"` of the inputs.
+impl ModelParams for LogisticRegressor {
+    fn param_groups(&self) -> Vec<ParamGroup> {
+        linear_param_groups(self.parameters.as_ref())
+    }
+}
+
 impl LogisticRegressor {
     /// Constructs untrained logistic regression model.
     ///
@@ -78,6 +113,7 @@ impl LogisticRegressor {
         LogisticRegressor {
             parameters: None,
             gd: gd,
+            report: None,
         }
     }
 
@@ -90,9 +126,37 @@ impl LogisticRegressor {
             Some(ref x) => Some(x.clone()),
         }
     }
+
+    /// Get the diagnostics from the last training.
+    ///
+    /// Returns None if the model has not been trained.
+    pub fn training_report(&self) -> Option<TrainingReport> {
+        self.report
+    }
+
+    /// Predicts the probability of the positive class for a single
+    /// input row.
+    ///
+    /// Avoids constructing a one row matrix for real-time scoring.
+    ///
+    /// # Panics
+    ///
+    /// - The model has not been trained.
+    /// - The inputs do not match the model parameters.
+    pub fn predict_row(&self, inputs: &[f64]) -> f64 {
+        if let Some(ref v) = self.parameters {
+            assert!(v.size() == inputs.len() + 1,
+                    "Input columns do not match the model parameters.");
+
+            let (intercept, coefs) = v.data().split_at(1);
+            Sigmoid::func(intercept[0] + utils::dot(inputs, coefs))
+        } else {
+            panic!("Model has not been trained.");
+        }
+    }
 }
 
-impl SupModel<Matrix<f64>, Vector<f64>> for LogisticRegressor {
+impl<M: BaseMatrix<f64>> SupModel<M, Vector<f64>> for LogisticRegressor {
     /// Train the logistic regression model.
     ///
     /// Takes training data and output values as input.
@@ -111,30 +175,205 @@ impl SupModel<Matrix<f64>, Vector<f64>> for LogisticRegressor {
     ///
     /// logistic_mod.train(&inputs, &targets);
     /// ```
-    fn train(&mut self, inputs: &Matrix<f64>, targets: &Vector<f64>) {
+    fn train(&mut self, inputs: &M, targets: &Vector<f64>) {
         let ones = Matrix::<f64>::ones(inputs.rows(), 1);
-        let full_inputs = ones.hcat(inputs);
+        let full_inputs = ones.hcat(&inputs.to_matrix());
 
         let initial_params = vec![0.5; full_inputs.cols()];
 
-        let optimal_w = self.gd.optimize(self, &initial_params[..], &full_inputs, targets);
-        self.parameters = Some(Vector::new(optimal_w));
+        let result = self.gd.optimize(self, &initial_params[..], &full_inputs, targets);
+        self.parameters = Some(Vector::new(result.params));
+        self.report = Some(result.report);
     }
 
     /// Predict output value from input data.
     ///
     /// Model must be trained before prediction can be made.
-    fn predict(&self, inputs: &Matrix<f64>) -> Vector<f64> {
+    fn predict(&self, inputs: &M) -> Vector<f64> {
         if let Some(ref v) = self.parameters {
-            let ones = Matrix::<f64>::ones(inputs.rows(), 1);
-            let full_inputs = ones.hcat(inputs);
-            (full_inputs * v).apply(&Sigmoid::func)
+            assert!(v.size() == inputs.cols() + 1,
+                    "Input columns do not match the model parameters.");
+
+            let (intercept, coefs) = v.data().split_at(1);
+            Vector::new((0..inputs.rows())
+                            .map(|i| Sigmoid::func(intercept[0] + utils::dot(inputs.row(i), coefs)))
+                            .collect())
         } else {
             panic!("Model has not been trained.");
         }
     }
 }
 
+impl WeightedSupModel<Matrix<f64>, Vector<f64>> for LogisticRegressor {
+    /// Train the logistic regression model with weighted samples.
+    ///
+    /// The weighted cross entropy is minimized using the model's
+    /// gradient descent settings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::logistic_reg::LogisticRegressor;
+    /// use rusty_machine::learning::{SupModel, WeightedSupModel};
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::vector::Vector;
+    ///
+    /// let inputs = Matrix::new(4,1, vec![1.0, 3.0, 5.0, 7.0]);
+    /// let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+    ///
+    /// // Up-weight the rarer samples of a class.
+    /// let weights = Vector::new(vec![1.0, 1.0, 3.0, 3.0]);
+    ///
+    /// let mut log_mod = LogisticRegressor::default();
+    /// log_mod.train_weighted(&inputs, &targets, &weights);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The number of weights does not match the number of input rows.
+    fn train_weighted(&mut self, inputs: &Matrix<f64>, targets: &Vector<f64>, weights: &Vector<f64>) {
+        assert!(inputs.rows() == weights.size(),
+                "Weights must have one entry per sample.");
+
+        let ones = Matrix::<f64>::ones(inputs.rows(), 1);
+        let full_inputs = ones.hcat(inputs);
+
+        let initial_params = vec![0.5; full_inputs.cols()];
+
+        let weighted = WeightedLogistic { weights: weights };
+        let result = self.gd.optimize(&weighted, &initial_params[..], &full_inputs, targets);
+        self.parameters = Some(Vector::new(result.params));
+        self.report = Some(result.report);
+    }
+}
+
+impl IncrementalModel<Matrix<f64>, Vector<f64>> for LogisticRegressor {
+    /// Update the logistic regression model using a batch of data.
+    ///
+    /// Gradient descent continues from the current parameters using
+    /// only the batch. An untrained model starts from the same
+    /// parameters as `train`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::logistic_reg::LogisticRegressor;
+    /// use rusty_machine::learning::{SupModel, IncrementalModel};
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::vector::Vector;
+    ///
+    /// let mut log_mod = LogisticRegressor::default();
+    ///
+    /// for _ in 0..5 {
+    ///     let inputs = Matrix::new(4,1, vec![1.0, 3.0, 5.0, 7.0]);
+    ///     let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+    ///     log_mod.train_incremental(&inputs, &targets);
+    /// }
+    ///
+    /// let output = log_mod.predict(&Matrix::new(1,1, vec![10.0]));
+    /// assert!(output[0] > 0.5);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The batch has a different number of columns to earlier batches.
+    fn train_incremental(&mut self, inputs: &Matrix<f64>, targets: &Vector<f64>) {
+        let ones = Matrix::<f64>::ones(inputs.rows(), 1);
+        let full_inputs = ones.hcat(inputs);
+
+        let start = match self.parameters {
+            Some(ref p) => {
+                assert!(p.size() == full_inputs.cols(),
+                        "Batch columns do not match the trained model.");
+                p.data().clone()
+            }
+            None => vec![0.5; full_inputs.cols()],
+        };
+
+        let result = self.gd.optimize(self, &start[..], &full_inputs, targets);
+        self.parameters = Some(Vector::new(result.params));
+        self.report = Some(result.report);
+    }
+}
+
+impl LogisticRegressor {
+    /// Train the logistic regression model using Newton's method.
+    ///
+    /// This usually converges in far fewer iterations than the
+    /// gradient descent algorithm the model was constructed with.
+    /// Training starts from zero parameters, and the training report
+    /// records how the optimization ended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::logistic_reg::LogisticRegressor;
+    /// use rusty_machine::learning::optim::newton::Newton;
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::vector::Vector;
+    ///
+    /// let inputs = Matrix::new(4, 1, vec![1.0, 4.0, 3.0, 7.0]);
+    /// let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+    ///
+    /// let mut model = LogisticRegressor::default();
+    /// model.train_with_newton(&inputs, &targets, &Newton::new(20, 1e-6));
+    ///
+    /// assert!(model.training_report().unwrap().iters <= 20);
+    /// ```
+    pub fn train_with_newton(&mut self,
+                             inputs: &Matrix<f64>,
+                             targets: &Vector<f64>,
+                             newton: &Newton) {
+        let ones = Matrix::<f64>::ones(inputs.rows(), 1);
+        let full_inputs = ones.hcat(inputs);
+
+        let initial_params = vec![0.; full_inputs.cols()];
+
+        let result = newton.optimize(self, &initial_params[..], &full_inputs, targets);
+        self.parameters = Some(Vector::new(result.params));
+        self.report = Some(result.report);
+    }
+}
+
+/// The logistic regression cost with fixed sample weights.
+struct WeightedLogistic<'a> {
+    weights: &'a Vector<f64>,
+}
+
+impl<'a> Optimizable for WeightedLogistic<'a> {
+    type Inputs = Matrix<f64>;
+    type Targets = Vector<f64>;
+
+    fn compute_grad(&self,
+                    params: &[f64],
+                    inputs: &Matrix<f64>,
+                    targets: &Vector<f64>)
+                    -> (f64, Vec<f64>) {
+
+        let beta_vec = Vector::new(params.to_vec());
+        let outputs = (inputs * beta_vec).apply(&Sigmoid::func);
+
+        let cost = CrossEntropyError::weighted_cost(&outputs, targets, self.weights);
+        let grad = (inputs.transpose() * (outputs - targets).elemul(self.weights)) /
+                   self.weights.sum();
+
+        (cost, grad.into_vec())
+    }
+
+    /// The intercept is not penalized.
+    fn is_penalized(&self, idx: usize) -> bool {
+        idx != 0
+    }
+
+    fn compute_hessian(&self,
+                       params: &[f64],
+                       inputs: &Matrix<f64>,
+                       _targets: &Vector<f64>)
+                       -> Option<Matrix<f64>> {
+        Some(logistic_hessian(params, inputs, Some(self.weights)))
+    }
+}
+
 impl Optimizable for LogisticRegressor {
     type Inputs = Matrix<f64>;
     type Targets = Vector<f64>;
@@ -153,4 +392,48 @@ impl Optimizable for LogisticRegressor {
 
         (cost, grad.into_vec())
     }
+
+    /// The intercept is not penalized.
+    fn is_penalized(&self, idx: usize) -> bool {
+        idx != 0
+    }
+
+    fn compute_hessian(&self,
+                       params: &[f64],
+                       inputs: &Matrix<f64>,
+                       _targets: &Vector<f64>)
+                       -> Option<Matrix<f64>> {
+        Some(logistic_hessian(params, inputs, None))
+    }
+}
+
+/// The Hessian of the cross entropy cost, X<sup>T</sup>SX / n.
+///
+/// S is the diagonal of p(1 - p) for the predicted probabilities p,
+/// scaled by the sample weights if there are any.
+fn logistic_hessian(params: &[f64],
+                    inputs: &Matrix<f64>,
+                    weights: Option<&Vector<f64>>)
+                    -> Matrix<f64> {
+    let beta_vec = Vector::new(params.to_vec());
+    let outputs = (inputs * beta_vec).apply(&Sigmoid::func);
+
+    let mut scaled = inputs.clone();
+    let cols = scaled.cols();
+    for (i, row) in scaled.mut_data().chunks_mut(cols).enumerate() {
+        let mut s = outputs[i] * (1f64 - outputs[i]);
+        if let Some(w) = weights {
+            s *= w[i];
+        }
+        for x in row {
+            *x *= s;
+        }
+    }
+
+    let total = match weights {
+        Some(w) => w.sum(),
+        None => inputs.rows() as f64,
+    };
+
+    inputs.transpose() * scaled / total
 }
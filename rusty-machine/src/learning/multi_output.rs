@@ -0,0 +1,151 @@
+//! Multi-output regression module
+//!
+//! Fits a single target regressor to each column of a target matrix.
+//!
+//! Many of the regressors, such as the Gaussian process and the linear
+//! models, only accept a `Vector` of targets. The `MultiOutputRegressor`
+//! trains a clone of the regressor for each target column and stacks
+//! their predictions into the columns of the output matrix. The outputs
+//! are modelled independently, so correlations between them are not
+//! used.
+//!
+//! # Usage
+//!
+//! ```
+//! use rusty_machine::learning::lin_reg::LinRegressor;
+//! use rusty_machine::learning::multi_output::MultiOutputRegressor;
+//! use rusty_machine::learning::SupModel;
+//! use rusty_machine::linalg::matrix::Matrix;
+//!
+//! let inputs = Matrix::new(4, 1, vec![1.0, 2.0, 3.0, 4.0]);
+//! // The targets are 2x + 1 and 5 - x.
+//! let targets = Matrix::new(4, 2, vec![3.0, 4.0, 5.0, 3.0, 7.0, 2.0, 9.0, 1.0]);
+//!
+//! let mut model = MultiOutputRegressor::new(LinRegressor::default());
+//! model.train(&inputs, &targets);
+//!
+//! let outputs = model.predict(&Matrix::new(1, 1, vec![5.0]));
+//! assert!((outputs[[0, 0]] - 11.0).abs() < 1e-8);
+//! assert!((outputs[[0, 1]] - 0.0).abs() < 1e-8);
+//! ```
+
+use std::fmt;
+use std::vec::Vec;
+
+use learning::{ApproxEq, SupModel, WeightedSupModel};
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+
+/// A regressor for each column of a target matrix.
+#[derive(Clone)]
+pub struct MultiOutputRegressor<M> {
+    base: M,
+    models: Vec<M>,
+}
+
+impl<M> MultiOutputRegressor<M> {
+    /// Constructs an untrained multi-output regressor.
+    ///
+    /// The model is cloned for each target column during training, so
+    /// its settings apply to every output.
+    pub fn new(model: M) -> MultiOutputRegressor<M> {
+        MultiOutputRegressor {
+            base: model,
+            models: Vec::new(),
+        }
+    }
+
+    /// The trained models, one for each target column.
+    ///
+    /// Empty if the regressor has not been trained.
+    pub fn models(&self) -> &[M] {
+        &self.models
+    }
+}
+
+impl<M: fmt::Debug> fmt::Debug for MultiOutputRegressor<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MultiOutputRegressor")
+            .field("base", &self.base)
+            .field("outputs", &self.models.len())
+            .field("trained", &!self.models.is_empty())
+            .finish()
+    }
+}
+
+/// Compares the models of each output.
+impl<M: ApproxEq> ApproxEq for MultiOutputRegressor<M> {
+    fn approx_eq(&self, other: &MultiOutputRegressor<M>, tol: f64) -> bool {
+        self.models.len() == other.models.len() &&
+        self.models.iter().zip(&other.models).all(|(a, b)| a.approx_eq(b, tol))
+    }
+}
+
+impl<M: SupModel<Matrix<f64>, Vector<f64>> + Clone> MultiOutputRegressor<M> {
+    /// Trains a clone of the base model on each target column with the
+    /// given training function.
+    fn train_each<F>(&mut self, inputs: &Matrix<f64>, targets: &Matrix<f64>, mut train: F)
+        where F: FnMut(&mut M, &Vector<f64>)
+    {
+        assert!(inputs.rows() == targets.rows(),
+                "Inputs and targets have different lengths.");
+
+        let mut models = Vec::with_capacity(targets.cols());
+        for j in 0..targets.cols() {
+            let column = Vector::new((0..targets.rows()).map(|i| targets[[i, j]]).collect());
+            let mut model = self.base.clone();
+            train(&mut model, &column);
+            models.push(model);
+        }
+
+        self.models = models;
+    }
+}
+
+impl<M: SupModel<Matrix<f64>, Vector<f64>> + Clone> SupModel<Matrix<f64>, Matrix<f64>>
+    for MultiOutputRegressor<M> {
+    /// Predicts each output with its model.
+    ///
+    /// The outputs have a column for each target column seen in
+    /// training.
+    ///
+    /// # Panics
+    ///
+    /// - The model has not been trained.
+    fn predict(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        assert!(!self.models.is_empty(), "Model has not been trained.");
+
+        let outputs: Vec<Vector<f64>> = self.models.iter().map(|m| m.predict(inputs)).collect();
+        let k = outputs.len();
+        let mut data = Vec::with_capacity(inputs.rows() * k);
+        for i in 0..inputs.rows() {
+            data.extend(outputs.iter().map(|out| out[i]));
+        }
+
+        Matrix::new(inputs.rows(), k, data)
+    }
+
+    /// Trains a clone of the model on each target column.
+    ///
+    /// # Panics
+    ///
+    /// - The inputs and targets have a different number of rows.
+    fn train(&mut self, inputs: &Matrix<f64>, targets: &Matrix<f64>) {
+        self.train_each(inputs, targets, |model, column| model.train(inputs, column));
+    }
+}
+
+impl<M> WeightedSupModel<Matrix<f64>, Matrix<f64>> for MultiOutputRegressor<M>
+    where M: WeightedSupModel<Matrix<f64>, Vector<f64>> + Clone
+{
+    /// Trains a clone of the model on each target column with the
+    /// same sample weights.
+    fn train_weighted(&mut self,
+                      inputs: &Matrix<f64>,
+                      targets: &Matrix<f64>,
+                      weights: &Vector<f64>) {
+        self.train_each(inputs,
+                        targets,
+                        |model, column| model.train_weighted(inputs, column, weights));
+    }
+}
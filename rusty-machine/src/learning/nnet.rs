@@ -30,23 +30,44 @@
 //! You can define your own criterion by implementing the `Criterion`
 //! trait with a concrete ActivationFunc and CostFunc.
 
-use linalg::matrix::Matrix;
-use learning::SupModel;
+use std::any;
+use std::fmt;
+use std::mem;
+use std::vec::Vec;
+
+use linalg::matrix::{Matrix, MatrixSlice, BaseMatrix};
+use linalg::utils;
+use linalg::vector::Vector;
+use learning::{ApproxEq, ModelParams, ParamGroup, SupModel, WeightedSupModel, IncrementalModel};
+use learning::params_approx_eq;
 use learning::toolkit::activ_fn;
 use learning::toolkit::activ_fn::ActivationFunc;
 use learning::toolkit::cost_fn;
-use learning::toolkit::cost_fn::CostFunc;
-use learning::optim::{Optimizable, OptimAlgorithm};
+use learning::toolkit::cost_fn::{CostFunc, WeightedCostFunc};
+use learning::optim::{Optimizable, OptimAlgorithm, TrainingReport};
 use learning::optim::grad_desc::StochasticGD;
+use learning::toolkit::regularization::{L2, Penalty};
+use learning::toolkit::stable;
+
+#[cfg(feature = "rand")]
+use rand::Rng;
+#[cfg(feature = "rand")]
+use learning::toolkit::rand_utils;
 
-use rand::{Rng, thread_rng};
+#[cfg(feature = "gpu")]
+use linalg::gpu::mul_into;
 
 /// Neural Network struct
+#[derive(Clone)]
 pub struct NeuralNet<'a, T: Criterion> {
     layer_sizes: &'a [usize],
     weights: Vec<f64>,
     gd: StochasticGD,
     criterion: T,
+    l2: f64,
+    residuals: Vec<(usize, usize)>,
+    threshold: f64,
+    report: Option<TrainingReport>,
 }
 
 impl<'a> NeuralNet<'a, BCECriterion> {
@@ -63,12 +84,53 @@ impl<'a> NeuralNet<'a, BCECriterion> {
     /// let layers = &[3; 4];
     /// let mut net = NeuralNet::default(layers);
     /// ```
-    pub fn default(layer_sizes: &[usize]) -> NeuralNet<BCECriterion> {
+    ///
+    /// Requires the `rand` feature.
+    #[cfg(feature = "rand")]
+    pub fn default(layer_sizes: &[usize]) -> NeuralNet<'_, BCECriterion> {
         NeuralNet {
             layer_sizes: layer_sizes,
             weights: NeuralNet::<BCECriterion>::create_weights(layer_sizes),
             gd: StochasticGD::default(),
             criterion: BCECriterion,
+            l2: 0f64,
+            residuals: Vec::new(),
+            threshold: 0.5,
+            report: None,
+        }
+    }
+
+    /// Creates a builder for a neural network.
+    ///
+    /// The builder defaults to the binary cross entropy criterion,
+    /// the default stochastic gradient descent and no regularization.
+    /// The layer sizes must be given before building.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::nnet::{NeuralNet, MSECriterion};
+    /// use rusty_machine::learning::optim::grad_desc::StochasticGD;
+    ///
+    /// let net = NeuralNet::builder().layers(&[3, 5, 1])
+    ///                               .criterion(MSECriterion)
+    ///                               .optimizer(StochasticGD::new(0.1, 0.1, 20))
+    ///                               .l2(1e-4)
+    ///                               .build();
+    ///
+    /// assert_eq!(net.layer_sizes(), &[3, 5, 1]);
+    /// ```
+    ///
+    /// Requires the `rand` feature.
+    #[cfg(feature = "rand")]
+    pub fn builder() -> NeuralNetBuilder<'a, BCECriterion> {
+        NeuralNetBuilder {
+            layer_sizes: None,
+            gd: StochasticGD::default(),
+            criterion: BCECriterion,
+            l2: 0f64,
+            residuals: Vec::new(),
+            threshold: 0.5,
         }
     }
 }
@@ -90,16 +152,289 @@ impl<'a, T: Criterion> NeuralNet<'a, T> {
     /// let layers = &[3; 4];
     /// let mut net = NeuralNet::new(layers, BCECriterion);
     /// ```
-    pub fn new(layer_sizes: &[usize], criterion: T) -> NeuralNet<T> {
+    ///
+    /// Requires the `rand` feature.
+    #[cfg(feature = "rand")]
+    pub fn new(layer_sizes: &[usize], criterion: T) -> NeuralNet<'_, T> {
         NeuralNet {
             layer_sizes: layer_sizes,
             weights: NeuralNet::<T>::create_weights(layer_sizes),
             gd: StochasticGD::default(),
             criterion: criterion,
+            l2: 0f64,
+            residuals: Vec::new(),
+            threshold: 0.5,
+            report: None,
+        }
+    }
+
+    /// Creates a neural network with the given weights.
+    ///
+    /// The weights are laid out as returned by `parameters`. This
+    /// restores a trained network without random initialization, so
+    /// it is available without the `rand` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::nnet::{NeuralNet, MSECriterion};
+    ///
+    /// // One input and one output, with a bias of 1 and a weight of 2.
+    /// let net = NeuralNet::from_parameters(&[1, 1], MSECriterion, vec![1.0, 2.0]);
+    ///
+    /// assert_eq!(net.parameters(), &[1.0, 2.0]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The number of weights does not match the layer sizes.
+    pub fn from_parameters(layer_sizes: &[usize], criterion: T, weights: Vec<f64>) -> NeuralNet<'_, T> {
+        let n_weights: usize = layer_sizes.windows(2).map(|l| (l[0] + 1) * l[1]).sum();
+        assert!(weights.len() == n_weights,
+                "Number of weights does not match the layer sizes.");
+
+        NeuralNet {
+            layer_sizes: layer_sizes,
+            weights: weights,
+            gd: StochasticGD::default(),
+            criterion: criterion,
+            l2: 0f64,
+            residuals: Vec::new(),
+            threshold: 0.5,
+            report: None,
         }
     }
 
+    /// The sizes of the layers, including the input and output layers.
+    pub fn layer_sizes(&self) -> &[usize] {
+        self.layer_sizes
+    }
+
+    /// The criterion of the network.
+    pub fn criterion(&self) -> &T {
+        &self.criterion
+    }
+
+    /// The L2 regularization strength.
+    pub fn l2(&self) -> f64 {
+        self.l2
+    }
+
+    /// Adds a residual block from the `start` layer to the `end` layer.
+    ///
+    /// The activations of the `start` layer, including any residuals
+    /// added to them, are added to the activations of the `end` layer.
+    /// The skip connection lets gradients reach the early layers of
+    /// deep networks undiminished. Layers are indexed as in the layer
+    /// sizes, so the `start` layer may be the input layer but the `end`
+    /// layer must be a hidden layer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::nnet::{NeuralNet, MSECriterion};
+    ///
+    /// let layers = &[2, 4, 4, 4, 1];
+    /// let net = NeuralNet::new(layers, MSECriterion).residual(1, 3);
+    ///
+    /// assert_eq!(net.residual_blocks(), &[(1, 3)]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The `start` layer is not before the `end` layer.
+    /// - The `end` layer is not a hidden layer.
+    /// - The layers have different sizes.
+    pub fn residual(mut self, start: usize, end: usize) -> NeuralNet<'a, T> {
+        assert!(start < end, "A residual block must start before it ends.");
+        assert!(end < self.layer_sizes.len() - 1,
+                "A residual block must end on a hidden layer.");
+        assert!(self.layer_sizes[start] == self.layer_sizes[end],
+                "A residual block must join layers of the same size.");
+
+        self.residuals.push((start, end));
+        self
+    }
+
+    /// The residual blocks of the network, as `(start, end)` layers.
+    pub fn residual_blocks(&self) -> &[(usize, usize)] {
+        &self.residuals
+    }
+
+    /// Summarizes the architecture of the network.
+    ///
+    /// Describes each layer of weights with its shape, activation and
+    /// number of parameters, to check an architecture before a long
+    /// training run.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::nnet::{NeuralNet, MSECriterion};
+    ///
+    /// let net = NeuralNet::from_parameters(&[3, 4, 1], MSECriterion, vec![0.0; 21]);
+    /// let summary = net.summary();
+    ///
+    /// assert_eq!(summary.layers[0].params, 16);
+    /// assert_eq!(summary.layers[1].activation, "Linear");
+    /// assert_eq!(summary.total_params, 21);
+    ///
+    /// println!("{}", summary);
+    /// ```
+    pub fn summary(&self) -> NetworkSummary {
+        let n_layers = self.layer_sizes.len() - 1;
+        let layers = (0..n_layers)
+            .map(|l| {
+                let activation = if l == n_layers - 1 && self.criterion.fused_output() {
+                    short_type_name::<T::Cost>()
+                } else {
+                    short_type_name::<T::ActFunc>()
+                };
+
+                LayerSummary {
+                    inputs: self.layer_sizes[l],
+                    outputs: self.layer_sizes[l + 1],
+                    activation: activation,
+                    params: (self.layer_sizes[l] + 1) * self.layer_sizes[l + 1],
+                    residuals: self.residual_starts(l + 1),
+                }
+            })
+            .collect();
+
+        NetworkSummary {
+            layers: layers,
+            total_params: self.weights.len(),
+            param_bytes: self.weights.len() * mem::size_of::<f64>(),
+        }
+    }
+
+    /// The layers whose activations are added to those of the layer.
+    fn residual_starts(&self, end: usize) -> Vec<usize> {
+        self.residuals.iter().filter(|r| r.1 == end).map(|r| r.0).collect()
+    }
+
+    /// The weights of the network, layer by layer.
+    pub fn parameters(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// The diagnostics from the last training.
+    ///
+    /// Returns None if the network has not been trained.
+    pub fn training_report(&self) -> Option<TrainingReport> {
+        self.report
+    }
+
+    /// Creates a `Predictor` which reuses its buffers across calls.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::nnet::{NeuralNet, MSECriterion};
+    /// use rusty_machine::linalg::matrix::Matrix;
+    ///
+    /// let net = NeuralNet::from_parameters(&[1, 1], MSECriterion, vec![1.0, 2.0]);
+    /// let mut predictor = net.predictor();
+    ///
+    /// for x in 0..3 {
+    ///     let outputs = predictor.predict(&Matrix::new(1, 1, vec![x as f64]));
+    ///     assert_eq!(outputs[[0, 0]], 1.0 + 2.0 * x as f64);
+    /// }
+    /// ```
+    pub fn predictor(&self) -> Predictor<'_, 'a, T> {
+        Predictor {
+            net: self,
+            offsets: self.layer_offsets(),
+            buffers: (0..self.layer_sizes.len() - 1).map(|_| Matrix::new(0, 0, vec![])).collect(),
+        }
+    }
+
+    /// Predicts the network outputs for a single input row.
+    ///
+    /// Avoids constructing a one row `Matrix` for real-time scoring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::nnet::{NeuralNet, MSECriterion};
+    ///
+    /// let net = NeuralNet::from_parameters(&[1, 1], MSECriterion, vec![1.0, 2.0]);
+    ///
+    /// assert_eq!(net.predict_row(&[3.0]), vec![7.0]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The inputs do not match the size of the input layer.
+    pub fn predict_row(&self, inputs: &[f64]) -> Vec<f64> {
+        self.forward_prop(&MatrixSlice::from_slice(inputs, 1, inputs.len())).into_vec()
+    }
+
+    /// The threshold used by `predict_classes` for networks with a
+    /// single output.
+    ///
+    /// Defaults to 0.5.
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Sets the threshold used by `predict_classes` for networks with
+    /// a single output.
+    pub fn set_threshold(&mut self, threshold: f64) {
+        self.threshold = threshold;
+    }
+
+    /// Predicts the class of each input row.
+    ///
+    /// A network with a single output predicts class 1 when the
+    /// output is at least the threshold and class 0 otherwise. A
+    /// network with several outputs predicts the index of the largest
+    /// output, taking the first on ties.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::nnet::{NeuralNet, MSECriterion};
+    /// use rusty_machine::linalg::matrix::Matrix;
+    ///
+    /// // Two outputs, the input and its negation.
+    /// let net = NeuralNet::from_parameters(&[1, 2], MSECriterion, vec![0.0, 0.0, 1.0, -1.0]);
+    ///
+    /// let inputs = Matrix::new(3, 1, vec![-1.0, 2.0, 0.5]);
+    /// assert_eq!(*net.predict_classes(&inputs).data(), vec![1, 0, 0]);
+    /// ```
+    pub fn predict_classes(&self, inputs: &Matrix<f64>) -> Vector<usize> {
+        let outputs = self.forward_prop(inputs);
+        let cols = outputs.cols();
+
+        outputs.data()
+            .chunks(cols)
+            .map(|row| {
+                if cols == 1 {
+                    if row[0] >= self.threshold { 1 } else { 0 }
+                } else {
+                    utils::argmax(row).0
+                }
+            })
+            .collect()
+    }
+
+    /// Replaces the weights of the network.
+    ///
+    /// Used to resume training from saved weights, for example those
+    /// saved by a `Checkpoint`.
+    ///
+    /// # Panics
+    ///
+    /// - The number of weights does not match the layer sizes.
+    pub fn set_parameters(&mut self, weights: Vec<f64>) {
+        assert!(weights.len() == self.weights.len(),
+                "Number of weights does not match the layer sizes.");
+        self.weights = weights;
+    }
+
     /// Creates initial weights for all neurons in the network.
+    #[cfg(feature = "rand")]
     fn create_weights(layer_sizes: &[usize]) -> Vec<f64> {
         let total_layers = layer_sizes.len();
 
@@ -115,11 +450,12 @@ impl<'a, T: Criterion> NeuralNet<'a, T> {
     }
 
     /// Initializes the weights for a single layer in the network.
+    #[cfg(feature = "rand")]
     fn initialize_weights(l_in: usize, l_out: usize) -> Vec<f64> {
         let mut weights = Vec::with_capacity(l_in * l_out);
         let eps_init = (6f64 / (l_in + l_out) as f64).sqrt();
 
-        let mut rng = thread_rng();
+        let mut rng = rand_utils::rng();
 
         for _i in 0..l_in * l_out {
             let w = (rng.gen_range(0f64, 1f64) * 2f64 * eps_init) - eps_init;
@@ -129,41 +465,39 @@ impl<'a, T: Criterion> NeuralNet<'a, T> {
         weights
     }
 
-    /// Gets matrix of weights between specified layer and forward layer for the weights.
-    fn get_layer_weights(&self, weights: &[f64], idx: usize) -> Matrix<f64> {
-        assert!(idx < self.layer_sizes.len() - 1);
-
-        // Check that the weights are the right size.
-        let mut full_size = 0usize;
-        for l in 0..self.layer_sizes.len() - 1 {
-            full_size += (self.layer_sizes[l] + 1) * self.layer_sizes[l + 1];
-        }
-
-        assert_eq!(full_size, weights.len());
-
+    /// The offset of each layer's weights, followed by the total
+    /// number of weights.
+    fn layer_offsets(&self) -> Vec<usize> {
+        let mut offsets = Vec::with_capacity(self.layer_sizes.len());
         let mut start = 0usize;
+        offsets.push(start);
 
-        for l in 0..idx {
-            start += (self.layer_sizes[l] + 1) * self.layer_sizes[l + 1]
-        }
-
-        let capacity = (self.layer_sizes[idx] + 1) * self.layer_sizes[idx + 1];
-
-        let mut layer_weights = Vec::with_capacity((self.layer_sizes[idx] + 1) *
-                                                   self.layer_sizes[idx + 1]);
-        unsafe {
-            for i in start..start + capacity {
-                layer_weights.push(*weights.get_unchecked(i));
-            }
+        for l in 0..self.layer_sizes.len() - 1 {
+            start += (self.layer_sizes[l] + 1) * self.layer_sizes[l + 1];
+            offsets.push(start);
         }
 
-        Matrix::new(self.layer_sizes[idx] + 1,
-                    self.layer_sizes[idx + 1],
-                    layer_weights)
+        offsets
+    }
 
+    /// A view of the weights between the specified layer and the
+    /// forward layer, located using the `layer_offsets`.
+    fn layer_view<'w>(&self,
+                      weights: &'w [f64],
+                      offsets: &[usize],
+                      idx: usize)
+                      -> MatrixSlice<'w, f64> {
+        MatrixSlice::from_slice(&weights[offsets[idx]..offsets[idx + 1]],
+                                self.layer_sizes[idx] + 1,
+                                self.layer_sizes[idx + 1])
     }
 
-    /// Gets matrix of weights between specified layer and forward layer.
+    /// Gets the weights and biases between the specified layer and the
+    /// forward layer.
+    ///
+    /// The weights have a row for each neuron of the layer and a
+    /// column for each neuron of the forward layer. The biases have an
+    /// entry for each neuron of the forward layer.
     ///
     /// # Examples
     ///
@@ -174,14 +508,47 @@ impl<'a, T: Criterion> NeuralNet<'a, T> {
     /// let layers = &[3; 4];
     /// let mut net = NeuralNet::default(layers);
     ///
-    /// let w = &net.get_net_weights(2);
+    /// let (w, b) = net.layer_weights(2);
     ///
-    /// // We add a bias term to the weight matrix
-    /// assert_eq!(w.rows(), 4);
+    /// assert_eq!(w.rows(), 3);
     /// assert_eq!(w.cols(), 3);
+    /// assert_eq!(b.size(), 3);
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The index is not less than the number of layers minus one.
+    pub fn layer_weights(&self, idx: usize) -> (Matrix<f64>, Vector<f64>) {
+        assert!(idx < self.layer_sizes.len() - 1, "Layer index is out of range.");
+
+        let offsets = self.layer_offsets();
+        let (biases, weights) = self.weights[offsets[idx]..offsets[idx + 1]]
+            .split_at(self.layer_sizes[idx + 1]);
+
+        (Matrix::new(self.layer_sizes[idx], self.layer_sizes[idx + 1], weights.to_vec()),
+         Vector::new(biases.to_vec()))
+    }
+
+    /// Gets the matrix of weights between the specified layer and the
+    /// forward layer.
+    ///
+    /// The first row of the matrix holds the biases.
+    ///
+    /// # Panics
+    ///
+    /// - The index is not less than the number of layers minus one.
+    #[deprecated(note = "use `layer_weights`, which returns the weights and biases separately")]
     pub fn get_net_weights(&self, idx: usize) -> Matrix<f64> {
-        self.get_layer_weights(&self.weights[..], idx)
+        assert!(idx < self.layer_sizes.len() - 1, "Layer index is out of range.");
+
+        let offsets = self.layer_offsets();
+        self.layer_view(&self.weights, &offsets, idx).to_matrix()
+    }
+
+    /// Gets the weights and biases of every layer, as returned by
+    /// `layer_weights`.
+    pub fn weights_per_layer(&self) -> Vec<(Matrix<f64>, Vector<f64>)> {
+        (0..self.layer_sizes.len() - 1).map(|l| self.layer_weights(l)).collect()
     }
 
     // Get the matrix of weights without the bias terms.
@@ -211,96 +578,305 @@ impl<'a, T: Criterion> NeuralNet<'a, T> {
                     inputs: &Matrix<f64>,
                     targets: &Matrix<f64>)
                     -> (f64, Vec<f64>) {
+        self.back_prop(weights,
+                       inputs,
+                       inputs.rows() as f64,
+                       |outputs| {
+                           (self.criterion.cost(outputs, targets),
+                            self.criterion.cost_grad(outputs, targets))
+                       })
+    }
+
+    /// The back propagation algorithm.
+    ///
+    /// The `cost` closure returns the cost and cost gradient of the
+    /// network outputs. The gradients are divided by `norm`.
+    fn back_prop<F>(&self,
+                    weights: &[f64],
+                    inputs: &Matrix<f64>,
+                    norm: f64,
+                    cost: F)
+                    -> (f64, Vec<f64>)
+        where F: Fn(&Matrix<f64>) -> (f64, Matrix<f64>)
+    {
         assert_eq!(inputs.cols(), self.layer_sizes[0]);
 
-        let mut forward_weights = Vec::with_capacity(self.layer_sizes.len() - 1);
-        let mut activations = Vec::with_capacity(self.layer_sizes.len());
+        let n_layers = self.layer_sizes.len();
+        let offsets = self.layer_offsets();
+        assert_eq!(offsets[n_layers - 1], weights.len());
 
-        let net_data = Matrix::ones(inputs.rows(), 1).hcat(inputs);
+        // The weighted inputs and activations of each layer after the
+        // input layer.
+        let mut forward_weights = Vec::with_capacity(n_layers - 1);
+        let mut activations: Vec<Matrix<f64>> = Vec::with_capacity(n_layers - 1);
 
-        activations.push(net_data.clone());
+        // A fused cost takes the weighted inputs of the output layer.
+        let fused = self.criterion.fused_output();
 
         // Forward propagation
-        {
-            let mut z = net_data * self.get_layer_weights(weights, 0);
-            forward_weights.push(z.clone());
-
-            for l in 1..self.layer_sizes.len() - 1 {
+        for l in 0..n_layers - 1 {
+            let z = {
+                let a = if l == 0 { inputs } else { &activations[l - 1] };
+                weighted_inputs(a, self.layer_view(weights, &offsets, l))
+            };
+            if fused && l == n_layers - 2 {
+                activations.push(z.clone());
+            } else {
                 let mut a = self.criterion.activate(z.clone());
-                let ones = Matrix::ones(a.rows(), 1);
+                for s in self.residual_starts(l + 1) {
+                    if s == 0 {
+                        add_rows(&mut a, inputs);
+                    } else {
+                        add_rows(&mut a, &activations[s - 1]);
+                    }
+                }
+                activations.push(a);
+            }
+            forward_weights.push(z);
+        }
+
+        // Take GRAD_cost to compute the output delta.
+        let (final_cost, cost_grad) = cost(&activations[n_layers - 2]);
+        let z = forward_weights.pop().unwrap();
+        let mut delta = if fused {
+            cost_grad
+        } else {
+            cost_grad.elemul(&self.criterion.grad_activ(z))
+        };
+
+        let mut gradients = vec![0f64; weights.len()];
+
+        // The activation gradients carried back by the residual blocks.
+        let mut skip_grads: Vec<Option<Matrix<f64>>> = vec![None; n_layers - 1];
 
-                a = ones.hcat(&a);
+        // Backward propagation, writing each layer's gradient in place.
+        for l in (0..n_layers - 1).rev() {
+            let a = if l == 0 { inputs } else { &activations[l - 1] };
+            weight_grad(a, &delta, norm, &mut gradients[offsets[l]..offsets[l + 1]]);
 
-                activations.push(a.clone());
-                z = a * self.get_layer_weights(weights, l);
-                forward_weights.push(z.clone());
+            if l > 0 {
+                let mut back = back_weights(&delta, self.layer_view(weights, &offsets, l));
+                if let Some(skip) = skip_grads[l].take() {
+                    add_rows(&mut back, &skip);
+                }
+                for s in self.residual_starts(l).into_iter().filter(|&s| s > 0) {
+                    skip_grads[s] = Some(match skip_grads[s].take() {
+                        Some(mut skip) => {
+                            add_rows(&mut skip, &back);
+                            skip
+                        }
+                        None => back.clone(),
+                    });
+                }
+
+                let g = self.criterion.grad_activ(forward_weights.pop().unwrap());
+                delta = back.elemul(&g);
             }
+        }
+
+        let penalty = self.add_l2_penalty(weights, &mut gradients);
 
-            activations.push(self.criterion.activate(z));
+        (final_cost + penalty, gradients)
+    }
+
+    /// Adds the gradient of the L2 penalty to the gradients and
+    /// returns the penalty.
+    fn add_l2_penalty(&self, weights: &[f64], gradients: &mut [f64]) -> f64 {
+        if self.l2 == 0f64 {
+            return 0f64;
         }
 
-        let mut deltas = Vec::with_capacity(self.layer_sizes.len() - 1);
-        // Backward propagation
-        {
-            let z = forward_weights[self.layer_sizes.len() - 2].clone();
-            let g = self.criterion.grad_activ(z);
+        let l2 = L2::new(self.l2);
+        let mut penalty = 0f64;
 
-            // Take GRAD_cost to compute this delta.
-            let mut delta = self.criterion
-                                .cost_grad(&activations[self.layer_sizes.len() - 1], targets)
-                                .elemul(&g);
+        for (start, end) in self.penalized_ranges() {
+            penalty += l2.cost(&weights[start..end]);
+            for (g, d) in gradients[start..end].iter_mut().zip(l2.grad(&weights[start..end])) {
+                *g += d;
+            }
+        }
+
+        penalty
+    }
+
+    /// The ranges of the weights which are penalized.
+    ///
+    /// The bias weights, the first row of each layer, are not
+    /// penalized.
+    fn penalized_ranges(&self) -> Vec<(usize, usize)> {
+        let mut ranges = Vec::with_capacity(self.layer_sizes.len() - 1);
+        let mut start = 0usize;
 
-            deltas.push(delta.clone());
+        for l in 0..self.layer_sizes.len() - 1 {
+            let n_bias = self.layer_sizes[l + 1];
+            let end = start + (self.layer_sizes[l] + 1) * n_bias;
+
+            ranges.push((start + n_bias, end));
+            start = end;
+        }
 
-            for l in (1..self.layer_sizes.len() - 1).rev() {
-                let mut z = forward_weights[l - 1].clone();
-                let ones = Matrix::ones(z.rows(), 1);
-                z = ones.hcat(&z);
+        ranges
+    }
 
-                let g = self.criterion.grad_activ(z);
-                delta = (delta * self.get_layer_weights(weights, l).transpose()).elemul(&g);
+    /// Forward propagation of the model weights to get the outputs.
+    fn forward_prop<M: BaseMatrix<f64>>(&self, inputs: &M) -> Matrix<f64> {
+        assert_eq!(inputs.cols(), self.layer_sizes[0]);
 
-                let non_one_rows = &(1..delta.cols()).collect::<Vec<usize>>()[..];
-                delta = delta.select_cols(non_one_rows);
-                deltas.push(delta.clone());
+        let offsets = self.layer_offsets();
+        let mut z = weighted_inputs(inputs, self.layer_view(&self.weights, &offsets, 0));
+        let mut activations: Vec<Matrix<f64>> = Vec::with_capacity(self.layer_sizes.len() - 2);
+
+        for l in 1..self.layer_sizes.len() - 1 {
+            let mut a = self.criterion.activate(z);
+            for s in self.residual_starts(l) {
+                if s == 0 {
+                    add_rows(&mut a, inputs);
+                } else {
+                    add_rows(&mut a, &activations[s - 1]);
+                }
             }
+            z = weighted_inputs(&a, self.layer_view(&self.weights, &offsets, l));
+            activations.push(a);
+        }
+
+        self.criterion.activate_output(z)
+    }
+}
+
+/// Computes the weighted inputs of a layer, `[1, inputs] * weights`.
+fn weighted_inputs<M: BaseMatrix<f64>>(inputs: &M, weights: MatrixSlice<f64>) -> Matrix<f64> {
+    let mut z = Matrix::new(0, 0, vec![]);
+    weighted_inputs_into(inputs, weights, &mut z);
+    z
+}
+
+/// The name of a type without its module path.
+fn short_type_name<U>() -> &'static str {
+    let name = any::type_name::<U>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Adds the rows of `b` to the rows of `a`, for residual blocks.
+fn add_rows<M: BaseMatrix<f64>>(a: &mut Matrix<f64>, b: &M) {
+    let cols = a.cols();
+    for (i, row) in a.mut_data().chunks_mut(cols).enumerate() {
+        for (x, y) in row.iter_mut().zip(b.row(i)) {
+            *x += *y;
         }
+    }
+}
+
+/// Computes the weighted inputs of a layer into `out`, reusing its
+/// allocation.
+///
+/// The first row of the weights is the bias, so the inputs are not
+/// copied to prepend a column of ones.
+#[cfg(not(feature = "gpu"))]
+fn weighted_inputs_into<M>(inputs: &M, weights: MatrixSlice<f64>, out: &mut Matrix<f64>)
+    where M: BaseMatrix<f64>
+{
+    let cols = weights.cols();
+    let mut data = mem::replace(out, Matrix::new(0, 0, vec![])).into_vec();
+    data.clear();
 
-        let mut grad = Vec::with_capacity(self.layer_sizes.len() - 1);
-        let mut capacity = 0;
+    for i in 0..inputs.rows() {
+        data.extend_from_slice(weights.row(0));
+        let z_row = &mut data[i * cols..];
 
-        for (l, activ_item) in activations.iter().enumerate().take(self.layer_sizes.len() - 1) {
-            let g = deltas[self.layer_sizes.len() - 2 - l].transpose() * activ_item;
-            capacity += g.cols() * g.rows();
-            grad.push(g / (inputs.rows() as f64));
+        for (k, x) in inputs.row(i).iter().enumerate() {
+            for (z, w) in z_row.iter_mut().zip(weights.row(k + 1)) {
+                *z += x * w;
+            }
         }
+    }
+
+    *out = Matrix::new(inputs.rows(), cols, data);
+}
 
-        let mut gradients = Vec::with_capacity(capacity);
+/// Computes the back propagated deltas, `delta * weights^T`, without
+/// the bias row of the weights.
+#[cfg(not(feature = "gpu"))]
+fn back_weights(delta: &Matrix<f64>, weights: MatrixSlice<f64>) -> Matrix<f64> {
+    let cols = weights.rows() - 1;
+    let mut data = Vec::with_capacity(delta.rows() * cols);
 
-        for g in grad {
-            gradients.append(&mut g.into_vec());
+    for i in 0..delta.rows() {
+        let d = delta.row(i);
+        for k in 1..weights.rows() {
+            data.push(utils::dot(d, weights.row(k)));
         }
-        (self.criterion.cost(&activations[activations.len() - 1], targets),
-         gradients)
     }
 
-    /// Forward propagation of the model weights to get the outputs.
-    fn forward_prop(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
-        assert_eq!(inputs.cols(), self.layer_sizes[0]);
+    Matrix::new(delta.rows(), cols, data)
+}
 
-        let net_data = Matrix::ones(inputs.rows(), 1).hcat(inputs);
+/// Computes the gradient of the weights of a layer,
+/// `[1, inputs]^T * delta / norm`, into `grad`.
+///
+/// The gradient is laid out like the weights and is accumulated in
+/// place.
+#[cfg(not(feature = "gpu"))]
+fn weight_grad(inputs: &Matrix<f64>, delta: &Matrix<f64>, norm: f64, grad: &mut [f64]) {
+    let cols = delta.cols();
 
-        let mut z = net_data * self.get_net_weights(0);
-        let mut a = self.criterion.activate(z.clone());
+    for i in 0..delta.rows() {
+        let d = delta.row(i);
 
-        for l in 1..self.layer_sizes.len() - 1 {
-            let ones = Matrix::ones(a.rows(), 1);
-            a = ones.hcat(&a);
-            z = a * self.get_net_weights(l);
-            a = self.criterion.activate(z.clone());
+        for (g, x) in grad[..cols].iter_mut().zip(d) {
+            *g += *x;
         }
 
-        a
+        for (k, a) in inputs.row(i).iter().enumerate() {
+            for (g, x) in grad[(k + 1) * cols..(k + 2) * cols].iter_mut().zip(d) {
+                *g += a * x;
+            }
+        }
+    }
+
+    for g in grad {
+        *g /= norm;
+    }
+}
+
+/// Computes the weighted inputs of a layer into `out`.
+///
+/// Large products are computed on the GPU.
+#[cfg(feature = "gpu")]
+fn weighted_inputs_into<M>(inputs: &M, weights: MatrixSlice<f64>, out: &mut Matrix<f64>)
+    where M: BaseMatrix<f64>
+{
+    mul_into(&Matrix::ones(inputs.rows(), 1).hcat(&inputs.to_matrix()),
+             &weights.to_matrix(),
+             out);
+}
+
+/// Computes the back propagated deltas, `delta * weights^T`, without
+/// the bias row of the weights.
+///
+/// Large products are computed on the GPU.
+#[cfg(feature = "gpu")]
+fn back_weights(delta: &Matrix<f64>, weights: MatrixSlice<f64>) -> Matrix<f64> {
+    let cols = weights.cols();
+    let non_bias = Matrix::new(weights.rows() - 1, cols, weights.data()[cols..].to_vec());
+
+    let mut back = Matrix::new(0, 0, vec![]);
+    mul_into(delta, &non_bias.transpose(), &mut back);
+    back
+}
+
+/// Computes the gradient of the weights of a layer,
+/// `[1, inputs]^T * delta / norm`, into `grad`.
+///
+/// Large products are computed on the GPU.
+#[cfg(feature = "gpu")]
+fn weight_grad(inputs: &Matrix<f64>, delta: &Matrix<f64>, norm: f64, grad: &mut [f64]) {
+    let mut g = Matrix::new(0, 0, vec![]);
+    mul_into(&Matrix::ones(inputs.rows(), 1).hcat(inputs).transpose(),
+             delta,
+             &mut g);
+
+    for (w, x) in grad.iter_mut().zip(g.data()) {
+        *w = x / norm;
     }
 }
 
@@ -316,6 +892,77 @@ impl<'a, T: Criterion> Optimizable for NeuralNet<'a, T> {
                     -> (f64, Vec<f64>) {
         self.compute_grad(params, inputs, targets)
     }
+
+    /// The bias weights are not penalized.
+    fn is_penalized(&self, idx: usize) -> bool {
+        let mut start = 0usize;
+
+        for l in 0..self.layer_sizes.len() - 1 {
+            let n_bias = self.layer_sizes[l + 1];
+            let end = start + (self.layer_sizes[l] + 1) * n_bias;
+
+            if idx < end {
+                return idx >= start + n_bias;
+            }
+            start = end;
+        }
+
+        false
+    }
+
+    fn param_layer(&self, idx: usize) -> Option<usize> {
+        let mut end = 0usize;
+
+        for l in 0..self.layer_sizes.len() - 1 {
+            end += (self.layer_sizes[l] + 1) * self.layer_sizes[l + 1];
+            if idx < end {
+                return Some(l);
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a, T: Criterion + fmt::Debug> fmt::Debug for NeuralNet<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NeuralNet")
+            .field("layer_sizes", &self.layer_sizes)
+            .field("gd", &self.gd)
+            .field("criterion", &self.criterion)
+            .field("l2", &self.l2)
+            .field("residuals", &self.residuals)
+            .field("threshold", &self.threshold)
+            .field("trained", &!self.weights.is_empty())
+            .field("report", &self.report)
+            .finish()
+    }
+}
+
+/// Compares the layer sizes, the residual blocks and the weights.
+impl<'a, T: Criterion> ApproxEq for NeuralNet<'a, T> {
+    fn approx_eq(&self, other: &NeuralNet<'a, T>, tol: f64) -> bool {
+        self.layer_sizes == other.layer_sizes && self.residuals == other.residuals &&
+        params_approx_eq(Some(&self.weights), Some(&other.weights), tol)
+    }
+}
+
+/// For each layer but the last, the `"weights"` between the layer and
+/// the forward layer, with a row for each neuron of the layer,
+/// followed by the `"biases"` of the forward layer.
+impl<'a, T: Criterion> ModelParams for NeuralNet<'a, T> {
+    fn param_groups(&self) -> Vec<ParamGroup> {
+        let mut groups = Vec::with_capacity(2 * (self.layer_sizes.len() - 1));
+
+        for (l, (weights, biases)) in self.weights_per_layer().into_iter().enumerate() {
+            groups.push(ParamGroup::in_layer("weights", l, weights));
+            groups.push(ParamGroup::in_layer("biases",
+                                             l,
+                                             Matrix::new(biases.size(), 1, biases.into_vec())));
+        }
+
+        groups
+    }
 }
 
 impl<'a, T: Criterion> SupModel<Matrix<f64>, Matrix<f64>> for NeuralNet<'a, T> {
@@ -327,8 +974,361 @@ impl<'a, T: Criterion> SupModel<Matrix<f64>, Matrix<f64>> for NeuralNet<'a, T> {
     /// Train the model using gradient optimization and back propagation.
     fn train(&mut self, inputs: &Matrix<f64>, targets: &Matrix<f64>) {
         let start = self.weights.clone();
-        let optimal_w = self.gd.optimize(self, &start[..], inputs, targets);
-        self.weights = optimal_w;
+        let result = self.gd.optimize(self, &start[..], inputs, targets);
+        self.weights = result.params;
+        self.report = Some(result.report);
+    }
+}
+
+impl<'a, T: Criterion> IncrementalModel<Matrix<f64>, Matrix<f64>> for NeuralNet<'a, T> {
+    /// Update the network using a batch of data.
+    ///
+    /// Training always continues from the current weights, so this
+    /// runs the same stochastic gradient descent as `train` on the
+    /// batch.
+    fn train_incremental(&mut self, inputs: &Matrix<f64>, targets: &Matrix<f64>) {
+        self.train(inputs, targets);
+    }
+}
+
+impl<'a, T> WeightedSupModel<Matrix<f64>, Matrix<f64>> for NeuralNet<'a, T>
+    where T: Criterion,
+          T::Cost: WeightedCostFunc<Matrix<f64>>
+{
+    /// Train the model with weighted samples.
+    ///
+    /// Each sample's cost is scaled by its weight. The weights stay
+    /// aligned with their samples within the stochastic mini-batches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::nnet::NeuralNet;
+    /// use rusty_machine::learning::WeightedSupModel;
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::vector::Vector;
+    ///
+    /// let inputs = Matrix::new(3,2, vec![1.,1.,2.,2.,3.,3.]);
+    /// let targets = Matrix::new(3,1, vec![0.,1.,1.]);
+    /// let weights = Vector::new(vec![2.,1.,1.]);
+    ///
+    /// let mut model = NeuralNet::default(&[2,3,1]);
+    /// model.train_weighted(&inputs, &targets, &weights);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The number of weights does not match the number of input rows.
+    fn train_weighted(&mut self, inputs: &Matrix<f64>, targets: &Matrix<f64>, weights: &Vector<f64>) {
+        assert!(inputs.rows() == weights.size(),
+                "Weights must have one entry per sample.");
+
+        // The weights are carried as an extra target column so that
+        // they are batched along with their samples.
+        let weighted_targets = targets.hcat(&Matrix::new(weights.size(), 1, weights.data().clone()));
+
+        let start = self.weights.clone();
+        let result = self.gd.optimize(&WeightedNet { net: self },
+                                      &start[..],
+                                      inputs,
+                                      &weighted_targets);
+        self.weights = result.params;
+        self.report = Some(result.report);
+    }
+}
+
+/// A neural network whose targets carry the sample weights as
+/// their last column.
+struct WeightedNet<'b, 'a: 'b, T: Criterion + 'b> {
+    net: &'b NeuralNet<'a, T>,
+}
+
+impl<'b, 'a, T> Optimizable for WeightedNet<'b, 'a, T>
+    where T: Criterion,
+          T::Cost: WeightedCostFunc<Matrix<f64>>
+{
+    type Inputs = Matrix<f64>;
+    type Targets = Matrix<f64>;
+
+    fn compute_grad(&self,
+                    params: &[f64],
+                    inputs: &Matrix<f64>,
+                    targets: &Matrix<f64>)
+                    -> (f64, Vec<f64>) {
+        let cols = targets.cols() - 1;
+        let target_cols = (0..cols).collect::<Vec<usize>>();
+        let sample_targets = targets.select_cols(&target_cols);
+        let weights = Vector::new(targets.select_cols(&[cols]).into_vec());
+
+        // Normalize by the batch size rather than the total weight, so
+        // that the weights still matter for single sample batches. A
+        // batch of zero weight samples costs nothing.
+        let n = inputs.rows() as f64;
+        let total = weights.sum();
+
+        self.net.back_prop(params,
+                           inputs,
+                           n,
+                           |outputs| {
+                               let cost = if total > 0f64 {
+                                   T::Cost::weighted_cost(outputs, &sample_targets, &weights) * total / n
+                               } else {
+                                   0f64
+                               };
+                               (cost, T::Cost::weighted_grad_cost(outputs, &sample_targets, &weights))
+                           })
+    }
+
+    fn is_penalized(&self, idx: usize) -> bool {
+        Optimizable::is_penalized(self.net, idx)
+    }
+
+    fn param_layer(&self, idx: usize) -> Option<usize> {
+        self.net.param_layer(idx)
+    }
+}
+
+/// A layer of weights in a `NetworkSummary`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayerSummary {
+    /// The number of neurons feeding into the layer.
+    pub inputs: usize,
+    /// The number of neurons of the layer.
+    pub outputs: usize,
+    /// The name of the activation function of the layer.
+    ///
+    /// An output layer fused with the cost is named after the cost
+    /// function.
+    pub activation: &'static str,
+    /// The number of weights and biases of the layer.
+    pub params: usize,
+    /// The layers whose activations are added to the activations of
+    /// this layer by residual blocks.
+    pub residuals: Vec<usize>,
+}
+
+/// The architecture of a `NeuralNet`.
+///
+/// Created by `NeuralNet::summary`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NetworkSummary {
+    /// Each layer of weights, from the input layer to the output layer.
+    pub layers: Vec<LayerSummary>,
+    /// The total number of parameters.
+    pub total_params: usize,
+    /// The memory used by the parameters, in bytes.
+    pub param_bytes: usize,
+}
+
+impl NetworkSummary {
+    /// Estimates the memory used to train on batches of the given size,
+    /// in bytes.
+    ///
+    /// Counts the parameters and their gradients along with the
+    /// weighted inputs and activations of each layer for the batch.
+    /// Memory used by the optimizer is not included.
+    pub fn training_bytes(&self, batch_size: usize) -> usize {
+        let units: usize = self.layers.iter().map(|l| l.outputs).sum();
+        2 * self.param_bytes + 2 * batch_size * units * mem::size_of::<f64>()
+    }
+}
+
+impl fmt::Display for NetworkSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self.layers
+            .iter()
+            .map(|l| l.activation.len())
+            .max()
+            .unwrap_or(0)
+            .max("activation".len());
+
+        writeln!(f,
+                 "{:>5} {:>12} {:>w$} {:>10} {:>8}",
+                 "layer",
+                 "shape",
+                 "activation",
+                 "params",
+                 "residual",
+                 w = width)?;
+
+        for (l, layer) in self.layers.iter().enumerate() {
+            let shape = format!("{} -> {}", layer.inputs, layer.outputs);
+            let residuals = layer.residuals
+                .iter()
+                .map(|r| format!("{}", r))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            writeln!(f,
+                     "{:>5} {:>12} {:>w$} {:>10} {:>8}",
+                     l,
+                     shape,
+                     layer.activation,
+                     layer.params,
+                     residuals,
+                     w = width)?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "Total parameters: {}", self.total_params)?;
+        writeln!(f, "Parameter memory: {} bytes", self.param_bytes)
+    }
+}
+
+/// A handle for repeated predictions from a `NeuralNet`.
+///
+/// Keeps the activations of each layer between calls and writes the
+/// next prediction into them, so that predicting batches of the same
+/// size does not allocate.
+///
+/// Created by `NeuralNet::predictor`.
+pub struct Predictor<'n, 'a: 'n, T: Criterion + 'n> {
+    net: &'n NeuralNet<'a, T>,
+    offsets: Vec<usize>,
+    buffers: Vec<Matrix<f64>>,
+}
+
+impl<'n, 'a, T: Criterion> Predictor<'n, 'a, T> {
+    /// Predicts the network outputs for the inputs.
+    ///
+    /// The outputs are borrowed from the predictor and overwritten by
+    /// the next call.
+    ///
+    /// # Panics
+    ///
+    /// - The inputs do not match the size of the input layer.
+    pub fn predict(&mut self, inputs: &Matrix<f64>) -> &Matrix<f64> {
+        assert_eq!(inputs.cols(), self.net.layer_sizes[0]);
+
+        for l in 0..self.buffers.len() {
+            let mut z = mem::replace(&mut self.buffers[l], Matrix::new(0, 0, vec![]));
+            {
+                let a = if l == 0 { inputs } else { &self.buffers[l - 1] };
+                let weights = self.net.layer_view(&self.net.weights, &self.offsets, l);
+                weighted_inputs_into(a, weights, &mut z);
+            }
+            self.buffers[l] = if l == self.buffers.len() - 1 {
+                self.net.criterion.activate_output(z)
+            } else {
+                let mut a = self.net.criterion.activate(z);
+                for s in self.net.residual_starts(l + 1) {
+                    if s == 0 {
+                        add_rows(&mut a, inputs);
+                    } else {
+                        add_rows(&mut a, &self.buffers[s - 1]);
+                    }
+                }
+                a
+            };
+        }
+
+        &self.buffers[self.buffers.len() - 1]
+    }
+}
+
+/// Builder for a `NeuralNet`.
+///
+/// Created by `NeuralNet::builder`.
+#[cfg(feature = "rand")]
+pub struct NeuralNetBuilder<'a, T: Criterion> {
+    layer_sizes: Option<&'a [usize]>,
+    gd: StochasticGD,
+    criterion: T,
+    l2: f64,
+    residuals: Vec<(usize, usize)>,
+    threshold: f64,
+}
+
+#[cfg(feature = "rand")]
+impl<'a, T: Criterion> NeuralNetBuilder<'a, T> {
+    /// Sets the layer sizes, including the input and output layers.
+    ///
+    /// # Panics
+    ///
+    /// - There are fewer than two layers.
+    pub fn layers(mut self, layer_sizes: &'a [usize]) -> NeuralNetBuilder<'a, T> {
+        assert!(layer_sizes.len() > 1,
+                "A network needs at least an input and an output layer.");
+        self.layer_sizes = Some(layer_sizes);
+        self
+    }
+
+    /// Sets the criterion, which combines the activation and cost
+    /// functions.
+    pub fn criterion<U: Criterion>(self, criterion: U) -> NeuralNetBuilder<'a, U> {
+        NeuralNetBuilder {
+            layer_sizes: self.layer_sizes,
+            gd: self.gd,
+            criterion: criterion,
+            l2: self.l2,
+            residuals: self.residuals,
+            threshold: self.threshold,
+        }
+    }
+
+    /// Sets the gradient descent used for training.
+    pub fn optimizer(mut self, gd: StochasticGD) -> NeuralNetBuilder<'a, T> {
+        self.gd = gd;
+        self
+    }
+
+    /// Sets the strength of the L2 penalty on the (non-bias) weights.
+    ///
+    /// The optimizer must not have a penalty of its own, or the
+    /// weights would be penalized twice.
+    ///
+    /// # Panics
+    ///
+    /// - The strength is negative.
+    pub fn l2(mut self, l2: f64) -> NeuralNetBuilder<'a, T> {
+        assert!(l2 >= 0f64, "L2 regularization must be non-negative.");
+        self.l2 = l2;
+        self
+    }
+
+    /// Sets the threshold used by `predict_classes` for networks with
+    /// a single output.
+    pub fn threshold(mut self, threshold: f64) -> NeuralNetBuilder<'a, T> {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Adds a residual block from the `start` layer to the `end` layer.
+    ///
+    /// See `NeuralNet::residual`, which checks the block when the
+    /// network is built.
+    pub fn residual(mut self, start: usize, end: usize) -> NeuralNetBuilder<'a, T> {
+        self.residuals.push((start, end));
+        self
+    }
+
+    /// Builds the untrained neural network.
+    ///
+    /// # Panics
+    ///
+    /// - The layer sizes have not been set.
+    /// - A residual block is invalid for the layer sizes.
+    /// - Both the network and its optimizer have a penalty.
+    pub fn build(self) -> NeuralNet<'a, T> {
+        let layer_sizes = match self.layer_sizes {
+            Some(l) => l,
+            None => panic!("Layer sizes must be set before building."),
+        };
+        assert!(self.l2 == 0f64 || !self.gd.has_penalty(),
+                "Set the penalty on either the network or its optimizer, not both.");
+
+        let net = NeuralNet {
+            layer_sizes: layer_sizes,
+            weights: NeuralNet::<T>::create_weights(layer_sizes),
+            gd: self.gd,
+            criterion: self.criterion,
+            l2: self.l2,
+            residuals: Vec::new(),
+            threshold: self.threshold,
+            report: None,
+        };
+
+        self.residuals.into_iter().fold(net, |net, (start, end)| net.residual(start, end))
     }
 }
 
@@ -351,6 +1351,37 @@ pub trait Criterion {
         mat.apply(&Self::ActFunc::func_grad)
     }
 
+    /// Whether the activation of the output layer is fused with the
+    /// cost.
+    ///
+    /// A fused cost takes the weighted inputs of the output layer, the
+    /// logits, in place of its activations, and its gradient is taken
+    /// with respect to the logits. Combining the activation with the
+    /// cost avoids multiplying by a saturated activation gradient.
+    ///
+    /// Defaults to false.
+    fn fused_output(&self) -> bool {
+        false
+    }
+
+    /// The activation function of the output layer, used for the
+    /// network predictions.
+    ///
+    /// Defaults to `activate`. Training only uses a different output
+    /// activation when it is fused with the cost.
+    fn activate_output(&self, mat: Matrix<f64>) -> Matrix<f64> {
+        self.activate(mat)
+    }
+
+    /// The ONNX operator of the output layer, used when exporting the
+    /// network.
+    ///
+    /// Defaults to `None`, which exports a softmax output layer for a
+    /// fused criterion and the activation function otherwise.
+    fn onnx_output_op(&self) -> Option<&'static str> {
+        None
+    }
+
     /// The cost function.
     ///
     /// Returns a scalar cost.
@@ -370,6 +1401,7 @@ pub trait Criterion {
 ///
 /// Uses the Sigmoid activation function and the
 /// cross entropy error.
+#[derive(Clone, Copy, Debug)]
 pub struct BCECriterion;
 
 impl Criterion for BCECriterion {
@@ -381,9 +1413,116 @@ impl Criterion for BCECriterion {
 ///
 /// Uses the Linear activation function and the
 /// mean squared error.
+#[derive(Clone, Copy, Debug)]
 pub struct MSECriterion;
 
 impl Criterion for MSECriterion {
     type ActFunc = activ_fn::Linear;
     type Cost = cost_fn::MeanSqError;
 }
+
+/// The softmax cross entropy criterion.
+///
+/// Uses the Sigmoid activation function for the hidden layers and a
+/// softmax output layer fused with the cross entropy error. Each row
+/// of the targets holds the class probabilities, usually a one-hot
+/// encoding, and the predictions are the class probabilities.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::learning::nnet::{NeuralNet, SoftmaxCriterion};
+/// use rusty_machine::learning::SupModel;
+/// use rusty_machine::linalg::matrix::Matrix;
+///
+/// let inputs = Matrix::new(3, 2, vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0]);
+/// let targets = Matrix::new(3, 3, vec![1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]);
+///
+/// let mut net = NeuralNet::new(&[2, 4, 3], SoftmaxCriterion);
+/// net.train(&inputs, &targets);
+///
+/// // Each row of predictions sums to one.
+/// let probs = net.predict(&inputs);
+/// assert!((probs.data()[..3].iter().sum::<f64>() - 1.0).abs() < 1e-12);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SoftmaxCriterion;
+
+impl Criterion for SoftmaxCriterion {
+    type ActFunc = activ_fn::Sigmoid;
+    type Cost = cost_fn::SoftmaxCrossEntropy;
+
+    fn fused_output(&self) -> bool {
+        true
+    }
+
+    fn activate_output(&self, mat: Matrix<f64>) -> Matrix<f64> {
+        stable::softmax(&mat)
+    }
+}
+
+/// The softmax Kullback-Leibler divergence criterion.
+///
+/// Like the `SoftmaxCriterion`, with a softmax output layer fused with
+/// the cost, but reports the divergence of the predicted distributions
+/// from the target distributions. The cost is zero when the network
+/// matches soft targets exactly, such as the outputs of another model.
+#[derive(Clone, Copy, Debug)]
+pub struct KLDivCriterion;
+
+impl Criterion for KLDivCriterion {
+    type ActFunc = activ_fn::Sigmoid;
+    type Cost = cost_fn::SoftmaxKLDivergence;
+
+    fn fused_output(&self) -> bool {
+        true
+    }
+
+    fn activate_output(&self, mat: Matrix<f64>) -> Matrix<f64> {
+        stable::softmax(&mat)
+    }
+}
+
+/// The Gaussian negative log-likelihood criterion.
+///
+/// Uses the Sigmoid activation function for the hidden layers and a
+/// linear output layer fused with the Gaussian negative log-likelihood.
+/// For k targets the output layer has 2k units, and the predictions are
+/// the k means followed by the k log variances.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::learning::nnet::{NeuralNet, GaussianNLLCriterion};
+/// use rusty_machine::learning::SupModel;
+/// use rusty_machine::linalg::matrix::{BaseMatrix, Matrix};
+///
+/// let inputs = Matrix::new(4, 1, vec![0.0, 1.0, 2.0, 3.0]);
+/// let targets = Matrix::new(4, 1, vec![0.1, 0.9, 2.2, 2.8]);
+///
+/// // One target, so two outputs: the mean and the log variance.
+/// let mut net = NeuralNet::new(&[1, 3, 2], GaussianNLLCriterion);
+/// net.train(&inputs, &targets);
+///
+/// let outputs = net.predict(&inputs);
+/// assert_eq!(outputs.cols(), 2);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct GaussianNLLCriterion;
+
+impl Criterion for GaussianNLLCriterion {
+    type ActFunc = activ_fn::Sigmoid;
+    type Cost = cost_fn::GaussianNLL;
+
+    fn fused_output(&self) -> bool {
+        true
+    }
+
+    fn activate_output(&self, mat: Matrix<f64>) -> Matrix<f64> {
+        mat
+    }
+
+    fn onnx_output_op(&self) -> Option<&'static str> {
+        Some("Identity")
+    }
+}
@@ -0,0 +1,387 @@
+//! ONNX export
+//!
+//! Serializes trained models into [ONNX](https://onnx.ai) graphs so
+//! they can be run by other inference engines, such as onnxruntime.
+//!
+//! The exported graphs take a single `input` tensor of shape
+//! `[N, features]` and produce a single `output` tensor of shape
+//! `[N, outputs]`. Models which predict a `Vector` produce an output of
+//! shape `[N, 1]`. All tensors are doubles, so the exported model gives
+//! the same outputs as the original.
+//!
+//! Requires the `onnx` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::learning::SupModel;
+//! use rusty_machine::learning::lin_reg::LinRegressor;
+//! use rusty_machine::learning::onnx::ToOnnx;
+//! use rusty_machine::linalg::matrix::Matrix;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! let inputs = Matrix::new(3, 1, vec![1.0, 2.0, 3.0]);
+//! let targets = Vector::new(vec![2.0, 4.0, 6.0]);
+//!
+//! let mut model = LinRegressor::default();
+//! model.train(&inputs, &targets);
+//!
+//! let bytes = model.to_onnx().unwrap();
+//! // The bytes can now be written to a `.onnx` file.
+//! ```
+
+use std::error::Error;
+use std::fmt;
+#[cfg(feature = "io")]
+use std::fs::File;
+use std::io;
+#[cfg(feature = "io")]
+use std::io::Write;
+#[cfg(feature = "io")]
+use std::path::Path;
+
+use learning::lin_reg::LinRegressor;
+use learning::logistic_reg::LogisticRegressor;
+use learning::nnet::{Criterion, NeuralNet};
+use learning::toolkit::activ_fn::{Exp, Linear, Sigmoid, Softplus};
+
+/// The ONNX IR version of the exported models.
+const IR_VERSION: u64 = 7;
+/// The version of the default operator set used.
+const OPSET_VERSION: u64 = 13;
+/// The ONNX `DOUBLE` element type.
+const DOUBLE: u64 = 11;
+
+/// Errors from exporting a model.
+#[derive(Debug)]
+pub enum OnnxError {
+    /// The model has not been trained.
+    Untrained,
+    /// An error writing the model.
+    Io(io::Error),
+}
+
+impl fmt::Display for OnnxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            OnnxError::Untrained => write!(f, "Model has not been trained."),
+            OnnxError::Io(ref e) => write!(f, "IO error: {}", e),
+        }
+    }
+}
+
+impl Error for OnnxError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            OnnxError::Io(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for OnnxError {
+    fn from(e: io::Error) -> OnnxError {
+        OnnxError::Io(e)
+    }
+}
+
+/// Trait for models which can be exported as ONNX graphs.
+pub trait ToOnnx {
+    /// Serializes the model as an ONNX `ModelProto`.
+    fn to_onnx(&self) -> Result<Vec<u8>, OnnxError>;
+
+    /// Writes the serialized model to a file.
+    ///
+    /// Requires the `io` feature.
+    #[cfg(feature = "io")]
+    fn save_onnx<P: AsRef<Path>>(&self, path: P) -> Result<(), OnnxError> {
+        let bytes = self.to_onnx()?;
+        File::create(path)?.write_all(&bytes)?;
+        Ok(())
+    }
+}
+
+/// Activation functions which have an ONNX operator.
+pub trait OnnxActivation {
+    /// The ONNX operator type, or `None` for the identity.
+    fn op_type() -> Option<&'static str>;
+}
+
+impl OnnxActivation for Sigmoid {
+    fn op_type() -> Option<&'static str> {
+        Some("Sigmoid")
+    }
+}
+
+impl OnnxActivation for Linear {
+    fn op_type() -> Option<&'static str> {
+        None
+    }
+}
+
+impl OnnxActivation for Exp {
+    fn op_type() -> Option<&'static str> {
+        Some("Exp")
+    }
+}
+
+impl OnnxActivation for Softplus {
+    fn op_type() -> Option<&'static str> {
+        Some("Softplus")
+    }
+}
+
+impl ToOnnx for LinRegressor {
+    fn to_onnx(&self) -> Result<Vec<u8>, OnnxError> {
+        let params = self.parameters().ok_or(OnnxError::Untrained)?;
+        Ok(linear_graph(params.data(), None).encode("rusty_machine_lin_reg"))
+    }
+}
+
+impl ToOnnx for LogisticRegressor {
+    fn to_onnx(&self) -> Result<Vec<u8>, OnnxError> {
+        let params = self.parameters().ok_or(OnnxError::Untrained)?;
+        Ok(linear_graph(params.data(), Some("Sigmoid")).encode("rusty_machine_logistic_reg"))
+    }
+}
+
+/// Criteria with a fused output, such as the `SoftmaxCriterion`, are
+/// exported with a softmax output layer, unless they name another
+/// operator with `Criterion::onnx_output_op`. Residual blocks are
+/// exported as `Add` operators.
+impl<'a, T> ToOnnx for NeuralNet<'a, T>
+    where T: Criterion,
+          T::ActFunc: OnnxActivation
+{
+    fn to_onnx(&self) -> Result<Vec<u8>, OnnxError> {
+        let sizes = self.layer_sizes();
+        let mut graph = Graph::new(sizes[0], sizes[sizes.len() - 1]);
+        let mut current = "input".to_string();
+        let n_layers = sizes.len() - 1;
+        let residuals = self.residual_blocks();
+        // The output of each layer, for the residual blocks.
+        let mut outputs = vec![current.clone()];
+
+        for (l, (w, bias)) in self.weights_per_layer().iter().enumerate() {
+            let w_name = format!("layer{}_weights", l);
+            let b_name = format!("layer{}_bias", l);
+            graph.initializer(&w_name, &[w.rows(), w.cols()], w.data());
+            graph.initializer(&b_name, &[bias.size()], bias.data());
+
+            current = graph.node("MatMul", &[&current, &w_name], &format!("layer{}_matmul", l));
+            current = graph.node("Add", &[&current, &b_name], &format!("layer{}_linear", l));
+            let op = if l < n_layers - 1 {
+                T::ActFunc::op_type()
+            } else if let Some(op) = self.criterion().onnx_output_op() {
+                Some(op)
+            } else if self.criterion().fused_output() {
+                Some("Softmax")
+            } else {
+                T::ActFunc::op_type()
+            };
+            if let Some(op) = op {
+                current = graph.node(op, &[&current], &format!("layer{}_activation", l));
+            }
+            for (k, &(start, _)) in residuals.iter().filter(|r| r.1 == l + 1).enumerate() {
+                current = graph.node("Add",
+                                     &[&current, &outputs[start]],
+                                     &format!("layer{}_residual{}", l, k));
+            }
+            outputs.push(current.clone());
+        }
+
+        graph.finish(&current);
+        Ok(graph.encode("rusty_machine_nnet"))
+    }
+}
+
+/// A graph computing `activation(input * weights + intercept)`.
+///
+/// The parameters hold the intercept followed by the weights.
+fn linear_graph(params: &[f64], activation: Option<&str>) -> Graph {
+    let (intercept, weights) = params.split_at(1);
+    let mut graph = Graph::new(weights.len(), 1);
+
+    graph.initializer("weights", &[weights.len(), 1], weights);
+    graph.initializer("intercept", &[1], intercept);
+
+    let mut current = graph.node("MatMul", &["input", "weights"], "matmul");
+    current = graph.node("Add", &[&current, "intercept"], "linear");
+    if let Some(op) = activation {
+        current = graph.node(op, &[&current], "activation");
+    }
+
+    graph.finish(&current);
+    graph
+}
+
+/// A node of the graph.
+struct Node {
+    op_type: String,
+    inputs: Vec<String>,
+    output: String,
+}
+
+/// A constant tensor of the graph.
+struct Initializer {
+    name: String,
+    dims: Vec<usize>,
+    data: Vec<f64>,
+}
+
+/// An ONNX graph with one input and one output.
+struct Graph {
+    n_inputs: usize,
+    n_outputs: usize,
+    nodes: Vec<Node>,
+    initializers: Vec<Initializer>,
+}
+
+impl Graph {
+    fn new(n_inputs: usize, n_outputs: usize) -> Graph {
+        Graph {
+            n_inputs: n_inputs,
+            n_outputs: n_outputs,
+            nodes: Vec::new(),
+            initializers: Vec::new(),
+        }
+    }
+
+    fn initializer(&mut self, name: &str, dims: &[usize], data: &[f64]) {
+        self.initializers.push(Initializer {
+            name: name.to_string(),
+            dims: dims.to_vec(),
+            data: data.to_vec(),
+        });
+    }
+
+    /// Adds a node and returns the name of its output.
+    fn node(&mut self, op_type: &str, inputs: &[&str], output: &str) -> String {
+        self.nodes.push(Node {
+            op_type: op_type.to_string(),
+            inputs: inputs.iter().map(|s| s.to_string()).collect(),
+            output: output.to_string(),
+        });
+        output.to_string()
+    }
+
+    /// Renames the final tensor to the graph output.
+    fn finish(&mut self, current: &str) {
+        match self.nodes.last_mut() {
+            Some(ref mut node) if node.output == current => node.output = "output".to_string(),
+            _ => {
+                self.node("Identity", &[current], "output");
+            }
+        }
+    }
+
+    /// Encodes the graph as a `ModelProto`.
+    fn encode(&self, name: &str) -> Vec<u8> {
+        let mut graph = Proto::new();
+        for (i, node) in self.nodes.iter().enumerate() {
+            let mut n = Proto::new();
+            for input in &node.inputs {
+                n.string(1, input);
+            }
+            n.string(2, &node.output);
+            n.string(3, &format!("{}_{}", node.op_type, i));
+            n.string(4, &node.op_type);
+            graph.message(1, &n);
+        }
+        graph.string(2, name);
+        for init in &self.initializers {
+            let mut t = Proto::new();
+            for &d in &init.dims {
+                t.uint(1, d as u64);
+            }
+            t.uint(2, DOUBLE);
+            t.string(8, &init.name);
+            let mut raw = Vec::with_capacity(8 * init.data.len());
+            for x in &init.data {
+                raw.extend_from_slice(&x.to_bits().to_le_bytes());
+            }
+            t.bytes(9, &raw);
+            graph.message(5, &t);
+        }
+        graph.message(11, &value_info("input", self.n_inputs));
+        graph.message(12, &value_info("output", self.n_outputs));
+
+        let mut opset = Proto::new();
+        opset.string(1, "");
+        opset.uint(2, OPSET_VERSION);
+
+        let mut model = Proto::new();
+        model.uint(1, IR_VERSION);
+        model.string(2, "rusty-machine");
+        model.string(3, env!("CARGO_PKG_VERSION"));
+        model.message(7, &graph);
+        model.message(8, &opset);
+        model.buf
+    }
+}
+
+/// A `ValueInfoProto` for a double tensor of shape `[N, cols]`.
+fn value_info(name: &str, cols: usize) -> Proto {
+    let mut batch = Proto::new();
+    batch.string(2, "N");
+    let mut width = Proto::new();
+    width.uint(1, cols as u64);
+
+    let mut shape = Proto::new();
+    shape.message(1, &batch);
+    shape.message(1, &width);
+
+    let mut tensor = Proto::new();
+    tensor.uint(1, DOUBLE);
+    tensor.message(2, &shape);
+
+    let mut ty = Proto::new();
+    ty.message(1, &tensor);
+
+    let mut info = Proto::new();
+    info.string(1, name);
+    info.message(2, &ty);
+    info
+}
+
+/// A protocol buffer message writer.
+struct Proto {
+    buf: Vec<u8>,
+}
+
+impl Proto {
+    fn new() -> Proto {
+        Proto { buf: Vec::new() }
+    }
+
+    fn varint(&mut self, mut x: u64) {
+        while x >= 0x80 {
+            self.buf.push((x as u8) | 0x80);
+            x >>= 7;
+        }
+        self.buf.push(x as u8);
+    }
+
+    fn key(&mut self, field: u64, wire_type: u64) {
+        self.varint(field << 3 | wire_type);
+    }
+
+    fn uint(&mut self, field: u64, x: u64) {
+        self.key(field, 0);
+        self.varint(x);
+    }
+
+    fn bytes(&mut self, field: u64, data: &[u8]) {
+        self.key(field, 2);
+        self.varint(data.len() as u64);
+        self.buf.extend_from_slice(data);
+    }
+
+    fn string(&mut self, field: u64, s: &str) {
+        self.bytes(field, s.as_bytes());
+    }
+
+    fn message(&mut self, field: u64, msg: &Proto) {
+        self.bytes(field, &msg.buf);
+    }
+}
@@ -0,0 +1,191 @@
+//! Training callbacks
+//!
+//! Callbacks are called by the gradient descent algorithms at the end
+//! of each epoch with the training log and the current parameters.
+//! They can record or plot the progress, save the parameters, or stop
+//! the training early by returning `Signal::Stop`.
+//!
+//! A callback is either a type implementing `Callback` or a closure
+//! taking the log and parameters. Callbacks must be `Clone`, so that
+//! each clone of an optimizer gets its own copy of their state.
+//!
+//! Adding callbacks to an optimizer requires the `std` feature.
+//!
+//! The mini-batch optimizers can stop on the cost of held out rows
+//! themselves, see the `early_stopping` module. To stop on another
+//! validation score, split the data before training and evaluate the
+//! parameters on the held out rows in the callback. For classification
+//! targets pass `stratify` to `data::split::train_test_split`, so that
+//! an imbalanced dataset does not give a validation set missing whole
+//! classes.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::learning::optim::callback::{Callback, Signal};
+//! use rusty_machine::learning::optim::grad_desc::GradientDesc;
+//! use rusty_machine::learning::optim::logging::TrainingLog;
+//!
+//! /// Stops when the cost has not improved for `patience` epochs.
+//! #[derive(Clone)]
+//! struct EarlyStopping {
+//!     patience: usize,
+//!     best: f64,
+//!     waited: usize,
+//! }
+//!
+//! impl Callback for EarlyStopping {
+//!     fn on_epoch_end(&mut self, log: &TrainingLog, _: &[f64]) -> Signal {
+//!         if log.cost < self.best {
+//!             self.best = log.cost;
+//!             self.waited = 0;
+//!         } else {
+//!             self.waited += 1;
+//!         }
+//!
+//!         if self.waited >= self.patience { Signal::Stop } else { Signal::Continue }
+//!     }
+//! }
+//!
+//! let stopping = EarlyStopping { patience: 5, best: std::f64::INFINITY, waited: 0 };
+//!
+//! let gd = GradientDesc::new(0.3, 1000)
+//!              .callback(stopping)
+//!              .callback(|log: &TrainingLog, params: &[f64]| {
+//!                  println!("Epoch {}: first weight {}", log.iter, params[0]);
+//!                  Signal::Continue
+//!              });
+//! ```
+
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+
+use learning::optim::logging::TrainingLog;
+
+/// Whether the optimizer should continue training.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Signal {
+    /// Continue to the next epoch.
+    Continue,
+    /// Stop training and return the current parameters.
+    Stop,
+}
+
+/// Trait for functions called by optimizers at the end of each epoch.
+pub trait Callback {
+    /// Called at the end of each epoch.
+    ///
+    /// Receives the training log of the epoch and the parameters
+    /// after its updates.
+    fn on_epoch_end(&mut self, log: &TrainingLog, params: &[f64]) -> Signal;
+}
+
+impl<F: FnMut(&TrainingLog, &[f64]) -> Signal> Callback for F {
+    fn on_epoch_end(&mut self, log: &TrainingLog, params: &[f64]) -> Signal {
+        self(log, params)
+    }
+}
+
+/// A callback which can be copied from behind a box.
+#[cfg(feature = "std")]
+trait BoxedCallback: Callback + Send {
+    fn box_clone(&self) -> Box<dyn BoxedCallback>;
+}
+
+#[cfg(feature = "std")]
+impl<C: Callback + Clone + Send + 'static> BoxedCallback for C {
+    fn box_clone(&self) -> Box<dyn BoxedCallback> {
+        Box::new(self.clone())
+    }
+}
+
+/// The callbacks held by an optimizer.
+///
+/// The callbacks are kept behind locks so that they can be called
+/// from `OptimAlgorithm::optimize`, which borrows the optimizer
+/// immutably.
+///
+/// Cloning the list copies the callbacks, so the clones of an
+/// optimizer do not share any state. State which should be shared,
+/// such as a counter, can be held in an `Arc`.
+///
+/// Without the `std` feature the list is always empty.
+#[derive(Default)]
+pub struct CallbackList {
+    #[cfg(feature = "std")]
+    callbacks: Vec<Mutex<Box<dyn BoxedCallback>>>,
+}
+
+impl Clone for CallbackList {
+    fn clone(&self) -> CallbackList {
+        CallbackList {
+            #[cfg(feature = "std")]
+            callbacks: self.callbacks
+                           .iter()
+                           .map(|callback| Mutex::new(callback.lock().unwrap().box_clone()))
+                           .collect(),
+        }
+    }
+}
+
+impl CallbackList {
+    /// Constructs an empty list of callbacks.
+    pub fn new() -> CallbackList {
+        CallbackList::default()
+    }
+}
+
+#[cfg(feature = "std")]
+impl CallbackList {
+    /// Adds a callback to the end of the list.
+    ///
+    /// Requires the `std` feature.
+    pub fn push<C: Callback + Clone + Send + 'static>(&mut self, callback: C) {
+        self.callbacks.push(Mutex::new(Box::new(callback)));
+    }
+
+    /// Returns true if the list holds no callbacks.
+    pub fn is_empty(&self) -> bool {
+        self.callbacks.is_empty()
+    }
+
+    /// Returns the number of callbacks in the list.
+    pub fn len(&self) -> usize {
+        self.callbacks.len()
+    }
+
+    /// Calls every callback in order.
+    ///
+    /// All of the callbacks are called even if an earlier one asks to
+    /// stop. Returns `Signal::Stop` if any of them asked to stop.
+    pub fn on_epoch_end(&self, log: &TrainingLog, params: &[f64]) -> Signal {
+        let mut signal = Signal::Continue;
+
+        for callback in &self.callbacks {
+            let mut callback = callback.lock().unwrap();
+            if callback.on_epoch_end(log, params) == Signal::Stop {
+                signal = Signal::Stop;
+            }
+        }
+
+        signal
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl CallbackList {
+    /// Returns true, the list is always empty without `std`.
+    pub fn is_empty(&self) -> bool {
+        true
+    }
+
+    /// Returns zero, the list is always empty without `std`.
+    pub fn len(&self) -> usize {
+        0
+    }
+
+    /// Returns `Signal::Continue`, there are no callbacks to call.
+    pub fn on_epoch_end(&self, _log: &TrainingLog, _params: &[f64]) -> Signal {
+        Signal::Continue
+    }
+}
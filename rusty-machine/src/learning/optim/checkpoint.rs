@@ -0,0 +1,250 @@
+//! Checkpointing of model parameters
+//!
+//! Long training runs can save the parameters to disk as they train,
+//! so that training can be resumed from the last checkpoint after a
+//! crash. The `Checkpoint` callback saves the parameters every few
+//! epochs or whenever a validation score improves.
+//!
+//! The parameters are saved as text, with a header line followed by
+//! one parameter per line. The values are written so that they are
+//! read back exactly. Each checkpoint is written to a temporary file
+//! which then replaces the previous checkpoint, so a crash while
+//! saving does not lose the previous checkpoint.
+//!
+//! Saving and loading files requires the `io` feature. The
+//! `read_parameters` and `write_parameters` functions work with any
+//! reader or writer, so saved parameters can also be loaded in builds
+//! without file access, such as WebAssembly.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use rusty_machine::learning::SupModel;
+//! use rusty_machine::learning::nnet::NeuralNet;
+//! use rusty_machine::learning::optim::checkpoint::{load_parameters, Checkpoint};
+//! use rusty_machine::learning::optim::grad_desc::StochasticGD;
+//! use rusty_machine::linalg::matrix::Matrix;
+//!
+//! let inputs = Matrix::new(4, 2, vec![0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0]);
+//! let targets = Matrix::new(4, 1, vec![0.0, 1.0, 1.0, 0.0]);
+//!
+//! // Save the weights every 100 epochs.
+//! let sgd = StochasticGD::new(0.1, 0.3, 10000).callback(Checkpoint::every("net.ckpt", 100));
+//! let mut net = NeuralNet::builder().layers(&[2, 3, 1]).optimizer(sgd).build();
+//!
+//! // Resume from the last checkpoint if there is one.
+//! if let Ok(weights) = load_parameters("net.ckpt") {
+//!     net.set_parameters(weights);
+//! }
+//!
+//! net.train(&inputs, &targets);
+//! ```
+
+use std::io::{self, BufRead, Write};
+#[cfg(feature = "io")]
+use std::f64;
+#[cfg(feature = "io")]
+use std::fs::{self, File};
+#[cfg(feature = "io")]
+use std::io::{BufReader, BufWriter};
+#[cfg(feature = "io")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "io")]
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "io")]
+use learning::optim::callback::{Callback, Signal};
+#[cfg(feature = "io")]
+use learning::optim::logging::TrainingLog;
+
+/// The first line of a parameter file.
+const HEADER: &str = "rusty-machine parameters";
+
+/// Writes the parameters in the parameter file format.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::learning::optim::checkpoint::{read_parameters, write_parameters};
+///
+/// let mut buf = Vec::new();
+/// write_parameters(&mut buf, &[0.5, -1.0]).unwrap();
+///
+/// assert_eq!(read_parameters(&buf[..]).unwrap(), vec![0.5, -1.0]);
+/// ```
+pub fn write_parameters<W: Write>(mut writer: W, params: &[f64]) -> io::Result<()> {
+    writeln!(writer, "{}", HEADER)?;
+    writeln!(writer, "{}", params.len())?;
+    for p in params {
+        writeln!(writer, "{:?}", p)?;
+    }
+    writer.flush()
+}
+
+/// Saves the parameters to a file.
+///
+/// The file is first written next to the destination and then renamed
+/// over it, replacing any existing file.
+///
+/// Requires the `io` feature.
+#[cfg(feature = "io")]
+pub fn save_parameters<P: AsRef<Path>>(path: P, params: &[f64]) -> io::Result<()> {
+    let path = path.as_ref();
+    let mut tmp_name = path.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+
+    write_parameters(BufWriter::new(File::create(&tmp_path)?), params)?;
+    fs::rename(&tmp_path, path)
+}
+
+/// Loads parameters saved by `save_parameters`.
+///
+/// Fails with `io::ErrorKind::InvalidData` if the file is not a
+/// parameter file or is incomplete.
+///
+/// Requires the `io` feature.
+#[cfg(feature = "io")]
+pub fn load_parameters<P: AsRef<Path>>(path: P) -> io::Result<Vec<f64>> {
+    read_parameters(BufReader::new(File::open(path)?))
+}
+
+/// Reads parameters written by `write_parameters` or `save_parameters`.
+///
+/// Fails with `io::ErrorKind::InvalidData` if the data is not in the
+/// parameter file format or is incomplete.
+pub fn read_parameters<R: BufRead>(reader: R) -> io::Result<Vec<f64>> {
+    let mut lines = reader.lines();
+
+    match lines.next() {
+        Some(line) => {
+            if line? != HEADER {
+                return Err(invalid_data("Not a parameter file."));
+            }
+        }
+        None => return Err(invalid_data("Not a parameter file.")),
+    }
+
+    let len = match lines.next() {
+        Some(line) => line?.trim().parse::<usize>().map_err(|_| invalid_data("Invalid parameter count."))?,
+        None => return Err(invalid_data("Missing parameter count.")),
+    };
+
+    let mut params = Vec::with_capacity(len);
+    for line in lines {
+        let line = line?;
+        let p = line.trim().parse::<f64>().map_err(|_| invalid_data("Invalid parameter value."))?;
+        params.push(p);
+    }
+
+    if params.len() != len {
+        return Err(invalid_data("Parameter count does not match the number of values."));
+    }
+
+    Ok(params)
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Scores the parameters, higher is better.
+#[cfg(feature = "io")]
+type ScoreFn = Arc<Mutex<dyn FnMut(&[f64]) -> f64 + Send>>;
+
+/// When a `Checkpoint` saves the parameters.
+#[cfg(feature = "io")]
+#[derive(Clone)]
+enum Trigger {
+    /// Every `n` epochs.
+    Every(usize),
+    /// When the score is higher than any before.
+    Best {
+        score: ScoreFn,
+        best: f64,
+    },
+}
+
+/// A callback which saves the parameters during training.
+///
+/// # Panics
+///
+/// The callback panics if saving the parameters fails, rather than
+/// continuing to train without checkpoints.
+///
+/// Requires the `io` feature.
+#[cfg(feature = "io")]
+#[derive(Clone)]
+pub struct Checkpoint {
+    path: PathBuf,
+    trigger: Trigger,
+}
+
+#[cfg(feature = "io")]
+impl Checkpoint {
+    /// Saves the parameters to `path` every `n` epochs.
+    ///
+    /// # Panics
+    ///
+    /// - n is zero.
+    pub fn every<P: AsRef<Path>>(path: P, n: usize) -> Checkpoint {
+        assert!(n > 0, "Must checkpoint at least every epoch.");
+
+        Checkpoint {
+            path: path.as_ref().to_path_buf(),
+            trigger: Trigger::Every(n),
+        }
+    }
+
+    /// Saves the parameters to `path` whenever the score improves.
+    ///
+    /// The score is computed from the parameters at the end of every
+    /// epoch, typically as a metric on validation data. Higher scores
+    /// are better.
+    ///
+    /// Clones of the checkpoint share the score function but keep
+    /// their own best score.
+    pub fn best<P, F>(path: P, score: F) -> Checkpoint
+        where P: AsRef<Path>,
+              F: FnMut(&[f64]) -> f64 + Send + 'static
+    {
+        Checkpoint {
+            path: path.as_ref().to_path_buf(),
+            trigger: Trigger::Best {
+                score: Arc::new(Mutex::new(score)),
+                best: f64::NEG_INFINITY,
+            },
+        }
+    }
+
+    /// The path the parameters are saved to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[cfg(feature = "io")]
+impl Callback for Checkpoint {
+    fn on_epoch_end(&mut self, log: &TrainingLog, params: &[f64]) -> Signal {
+        let save = match self.trigger {
+            Trigger::Every(n) => (log.iter + 1).is_multiple_of(n),
+            Trigger::Best { ref mut score, ref mut best } => {
+                let s = (*score.lock().unwrap())(params);
+                if s > *best {
+                    *best = s;
+                    true
+                } else {
+                    false
+                }
+            }
+        };
+
+        if save {
+            if let Err(e) = save_parameters(&self.path, params) {
+                panic!("Failed to save checkpoint to {}: {}", self.path.display(), e);
+            }
+        }
+
+        Signal::Continue
+    }
+}
@@ -0,0 +1,547 @@
+//! Coordinate descent
+//!
+//! Minimizes the cost one parameter at a time, holding the others
+//! fixed. Problems plug in by implementing `CoordinateModel`, which
+//! gives the exact minimizer along a single coordinate. This is cheap
+//! for problems such as the lasso, where the update is a closed form
+//! soft-threshold, and handles non-smooth penalties which gradient
+//! methods struggle with.
+//!
+//! The module provides the following problems:
+//!
+//! - `LeastSquares`: linear least squares with an elastic net penalty,
+//!   which includes the lasso and ridge regression.
+//! - `Quadratic`: a convex quadratic, such as the dual of a support
+//!   vector machine without a bias.
+//! - `Bounded`: restricts any other problem to a box, such as
+//!   0 <= α<sub>i</sub> <= C for the dual of a support vector machine.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::learning::optim::coord_desc::{CoordinateDescent, LeastSquares};
+//! use rusty_machine::learning::toolkit::regularization::ElasticNet;
+//! use rusty_machine::linalg::matrix::Matrix;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! let inputs = Matrix::new(4, 2, vec![1.0, 0.0,
+//!                                     2.0, 0.1,
+//!                                     3.0, 0.0,
+//!                                     4.0, 0.1]);
+//! let targets = Vector::new(vec![1.0, 2.0, 3.0, 4.0]);
+//!
+//! // The lasso sets the weight of the irrelevant feature to zero.
+//! let lasso = LeastSquares::new(ElasticNet::new(0.1, 1.0));
+//! let params = CoordinateDescent::default().optimize(&lasso, &[0.0, 0.0], &inputs, &targets);
+//!
+//! assert_eq!(params[1], 0.0);
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+use std::vec::Vec;
+
+#[cfg(feature = "std")]
+use learning::optim::callback::Callback;
+use learning::optim::callback::{CallbackList, Signal};
+use learning::optim::logging::{Logger, TrainingLog};
+use learning::toolkit::regularization::{ElasticNet, Penalty};
+#[cfg(feature = "rand")]
+use learning::toolkit::rand_utils;
+use linalg::matrix::{Matrix, BaseMatrix};
+use linalg::vector::Vector;
+#[cfg(feature = "rand")]
+use rand::Rng;
+
+/// Trait for problems which can be minimized one coordinate at a time.
+///
+/// The problem keeps a state alongside the parameters, such as the
+/// residuals of a regression, so that each update does not need to
+/// recompute the cost from scratch.
+pub trait CoordinateModel {
+    /// The input data type to the problem.
+    type Inputs: ?Sized;
+    /// The target data type to the problem.
+    type Targets: ?Sized;
+    /// The state kept up to date alongside the parameters.
+    type State;
+
+    /// Computes the state at the parameters.
+    fn init_state(&self,
+                  params: &[f64],
+                  inputs: &Self::Inputs,
+                  targets: &Self::Targets)
+                  -> Self::State;
+
+    /// The value of the parameter at `idx` minimizing the cost with
+    /// every other parameter held fixed.
+    fn update(&self,
+              idx: usize,
+              params: &[f64],
+              state: &Self::State,
+              inputs: &Self::Inputs,
+              targets: &Self::Targets)
+              -> f64;
+
+    /// Updates the state after the parameter at `idx` changes from
+    /// `old` to `params[idx]`.
+    fn apply(&self,
+             idx: usize,
+             old: f64,
+             params: &[f64],
+             state: &mut Self::State,
+             inputs: &Self::Inputs,
+             targets: &Self::Targets);
+
+    /// The cost at the parameters.
+    fn cost(&self,
+            params: &[f64],
+            state: &Self::State,
+            inputs: &Self::Inputs,
+            targets: &Self::Targets)
+            -> f64;
+}
+
+/// The order the coordinates are updated in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoordinateOrder {
+    /// Each pass updates the coordinates in order.
+    Cyclic,
+    /// Each pass updates the coordinates in a new random order.
+    ///
+    /// Requires the `rand` feature.
+    #[cfg(feature = "rand")]
+    Random,
+}
+
+/// Coordinate descent.
+#[derive(Clone)]
+pub struct CoordinateDescent {
+    /// The maximum number of passes over the coordinates.
+    pub iters: usize,
+    /// Training stops when no coordinate changes by more than this in
+    /// a pass.
+    pub tol: f64,
+    /// The order the coordinates are updated in.
+    pub order: CoordinateOrder,
+    logger: Option<Logger>,
+    callbacks: CallbackList,
+}
+
+/// The default coordinate descent.
+///
+/// The defaults are:
+///
+/// - iters = 1000
+/// - tol = 1e-8
+/// - order = Cyclic
+impl Default for CoordinateDescent {
+    fn default() -> CoordinateDescent {
+        CoordinateDescent::new(1000, 1e-8)
+    }
+}
+
+impl fmt::Debug for CoordinateDescent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CoordinateDescent")
+            .field("iters", &self.iters)
+            .field("tol", &self.tol)
+            .field("order", &self.order)
+            .finish()
+    }
+}
+
+impl CoordinateDescent {
+    /// Constructs coordinate descent with the maximum number of
+    /// passes and the tolerance on the parameter changes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::optim::coord_desc::CoordinateDescent;
+    ///
+    /// let cd = CoordinateDescent::new(100, 1e-6);
+    /// ```
+    pub fn new(iters: usize, tol: f64) -> CoordinateDescent {
+        CoordinateDescent {
+            iters: iters,
+            tol: tol,
+            order: CoordinateOrder::Cyclic,
+            logger: None,
+            callbacks: CallbackList::new(),
+        }
+    }
+
+    /// Sets the order the coordinates are updated in.
+    pub fn order(mut self, order: CoordinateOrder) -> CoordinateDescent {
+        self.order = order;
+        self
+    }
+
+    /// Sets a logger which receives the progress after each pass.
+    ///
+    /// The logged gradient norm is the largest change of a parameter
+    /// in the pass, and the learning rate is always one.
+    pub fn logger<F>(mut self, logger: F) -> CoordinateDescent
+        where F: Fn(&TrainingLog) + Send + Sync + 'static
+    {
+        self.logger = Some(Arc::new(logger));
+        self
+    }
+
+    /// Adds a callback which is called after each pass.
+    ///
+    /// Training stops early if the callback returns `Signal::Stop`.
+    ///
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn callback<C: Callback + Clone + Send + 'static>(mut self, callback: C) -> CoordinateDescent {
+        self.callbacks.push(callback);
+        self
+    }
+
+    /// Minimizes the problem from the starting parameters.
+    pub fn optimize<M: CoordinateModel>(&self,
+                                        model: &M,
+                                        start: &[f64],
+                                        inputs: &M::Inputs,
+                                        targets: &M::Targets)
+                                        -> Vec<f64> {
+        let mut params = start.to_vec();
+        let mut state = model.init_state(&params, inputs, targets);
+        #[cfg_attr(not(feature = "rand"), allow(unused_mut))]
+        let mut idxs: Vec<usize> = (0..params.len()).collect();
+
+        #[cfg(feature = "rand")]
+        let mut rng = rand_utils::rng();
+
+        for i in 0..self.iters {
+            match self.order {
+                CoordinateOrder::Cyclic => {}
+                #[cfg(feature = "rand")]
+                CoordinateOrder::Random => rng.shuffle(&mut idxs),
+            }
+
+            let mut max_change = 0f64;
+            for &j in &idxs {
+                let old = params[j];
+                params[j] = model.update(j, &params, &state, inputs, targets);
+
+                if params[j] != old {
+                    model.apply(j, old, &params, &mut state, inputs, targets);
+                    max_change = max_change.max((params[j] - old).abs());
+                }
+            }
+
+            if self.logger.is_some() || !self.callbacks.is_empty() {
+                let log = TrainingLog {
+                    iter: i,
+                    cost: model.cost(&params, &state, inputs, targets),
+                    grad_norm: max_change,
+                    learning_rate: 1f64,
+                };
+
+                if let Some(ref logger) = self.logger {
+                    logger(&log);
+                }
+                if self.callbacks.on_epoch_end(&log, &params) == Signal::Stop {
+                    break;
+                }
+            }
+
+            if max_change <= self.tol {
+                break;
+            }
+        }
+
+        params
+    }
+}
+
+/// Least squares with an elastic net penalty.
+///
+/// Minimizes ||y - Xw||<sup>2</sup> / 2n + p(w), where p is the
+/// penalty. An L1 ratio of one gives the lasso and a ratio of zero
+/// gives ridge regression.
+///
+/// The inputs are used as given, so add a column of ones for an
+/// intercept and set `intercept` to leave it unpenalized.
+#[derive(Clone, Copy, Debug)]
+pub struct LeastSquares {
+    /// The penalty on the weights.
+    pub penalty: ElasticNet,
+    /// Whether the first column is an intercept, which is not
+    /// penalized.
+    pub intercept: bool,
+}
+
+impl LeastSquares {
+    /// Constructs the problem with the penalty on every weight.
+    pub fn new(penalty: ElasticNet) -> LeastSquares {
+        LeastSquares {
+            penalty: penalty,
+            intercept: false,
+        }
+    }
+
+    /// Sets whether the first column is an unpenalized intercept.
+    pub fn intercept(mut self, intercept: bool) -> LeastSquares {
+        self.intercept = intercept;
+        self
+    }
+
+    fn is_penalized(&self, idx: usize) -> bool {
+        !(self.intercept && idx == 0)
+    }
+}
+
+/// The residuals y - Xw and the squared norms of the columns of X.
+#[derive(Clone, Debug)]
+pub struct LeastSquaresState {
+    residuals: Vec<f64>,
+    col_sq_norms: Vec<f64>,
+}
+
+impl CoordinateModel for LeastSquares {
+    type Inputs = Matrix<f64>;
+    type Targets = Vector<f64>;
+    type State = LeastSquaresState;
+
+    fn init_state(&self,
+                  params: &[f64],
+                  inputs: &Matrix<f64>,
+                  targets: &Vector<f64>)
+                  -> LeastSquaresState {
+        assert!(inputs.rows() == targets.size(),
+                "Inputs and targets have a different number of rows.");
+        assert!(inputs.cols() == params.len(),
+                "Inputs and parameters have a different number of columns.");
+
+        let residuals = (0..inputs.rows())
+            .map(|i| {
+                let row = inputs.row(i);
+                targets[i] - row.iter().zip(params.iter()).map(|(x, w)| x * w).sum::<f64>()
+            })
+            .collect();
+
+        let mut col_sq_norms = vec![0f64; inputs.cols()];
+        for i in 0..inputs.rows() {
+            for (n, x) in col_sq_norms.iter_mut().zip(inputs.row(i).iter()) {
+                *n += x * x;
+            }
+        }
+
+        LeastSquaresState {
+            residuals: residuals,
+            col_sq_norms: col_sq_norms,
+        }
+    }
+
+    fn update(&self,
+              idx: usize,
+              params: &[f64],
+              state: &LeastSquaresState,
+              inputs: &Matrix<f64>,
+              _: &Vector<f64>)
+              -> f64 {
+        let n = inputs.rows() as f64;
+        let curvature = state.col_sq_norms[idx] / n;
+        if curvature == 0f64 {
+            return 0f64;
+        }
+
+        // The correlation of the column with the residuals, adding
+        // back the contribution of the current weight.
+        let rho = (0..inputs.rows())
+            .map(|i| inputs[[i, idx]] * state.residuals[i])
+            .sum::<f64>() / n + curvature * params[idx];
+
+        if self.is_penalized(idx) {
+            // The minimizer of the quadratic plus penalty along the
+            // coordinate is the proximal step from its minimizer.
+            self.penalty.prox(&[rho / curvature], 1f64 / curvature)[0]
+        } else {
+            rho / curvature
+        }
+    }
+
+    fn apply(&self,
+             idx: usize,
+             old: f64,
+             params: &[f64],
+             state: &mut LeastSquaresState,
+             inputs: &Matrix<f64>,
+             _: &Vector<f64>) {
+        let change = params[idx] - old;
+        for (i, r) in state.residuals.iter_mut().enumerate() {
+            *r -= inputs[[i, idx]] * change;
+        }
+    }
+
+    fn cost(&self,
+            params: &[f64],
+            state: &LeastSquaresState,
+            _: &Matrix<f64>,
+            _: &Vector<f64>)
+            -> f64 {
+        let n = state.residuals.len() as f64;
+        let penalized: Vec<f64> = (0..params.len())
+            .filter(|&i| self.is_penalized(i))
+            .map(|i| params[i])
+            .collect();
+
+        state.residuals.iter().map(|r| r * r).sum::<f64>() / (2f64 * n) +
+        self.penalty.cost(&penalized)
+    }
+}
+
+/// The convex quadratic x<sup>T</sup>Qx / 2 + c<sup>T</sup>x.
+///
+/// The matrix Q must be symmetric with a positive diagonal. The
+/// problem takes no inputs or targets, so pass `&()` for both.
+///
+/// Restricted with `Bounded` to 0 <= x<sub>i</sub> <= C, with
+/// Q<sub>ij</sub> = y<sub>i</sub>y<sub>j</sub>K(x<sub>i</sub>, x<sub>j</sub>)
+/// and c<sub>i</sub> = -1, this is the dual of a support vector
+/// machine without a bias.
+#[derive(Clone)]
+pub struct Quadratic {
+    q: Matrix<f64>,
+    c: Vector<f64>,
+}
+
+impl Quadratic {
+    /// Constructs the quadratic from Q and c.
+    ///
+    /// # Panics
+    ///
+    /// - Q is not square.
+    /// - c has a different size to Q.
+    /// - The diagonal of Q is not positive.
+    pub fn new(q: Matrix<f64>, c: Vector<f64>) -> Quadratic {
+        assert!(q.rows() == q.cols(), "Q must be square.");
+        assert!(q.rows() == c.size(), "c must have the same size as Q.");
+        assert!((0..q.rows()).all(|i| q[[i, i]] > 0f64),
+                "The diagonal of Q must be positive.");
+
+        Quadratic { q: q, c: c }
+    }
+}
+
+impl CoordinateModel for Quadratic {
+    type Inputs = ();
+    type Targets = ();
+    /// The gradient Qx + c.
+    type State = Vec<f64>;
+
+    fn init_state(&self, params: &[f64], _: &(), _: &()) -> Vec<f64> {
+        (0..self.q.rows())
+            .map(|i| {
+                let row = self.q.row(i);
+                row.iter().zip(params.iter()).map(|(q, x)| q * x).sum::<f64>() + self.c[i]
+            })
+            .collect()
+    }
+
+    fn update(&self, idx: usize, params: &[f64], state: &Vec<f64>, _: &(), _: &()) -> f64 {
+        params[idx] - state[idx] / self.q[[idx, idx]]
+    }
+
+    fn apply(&self,
+             idx: usize,
+             old: f64,
+             params: &[f64],
+             state: &mut Vec<f64>,
+             _: &(),
+             _: &()) {
+        let change = params[idx] - old;
+        for (i, g) in state.iter_mut().enumerate() {
+            *g += self.q[[i, idx]] * change;
+        }
+    }
+
+    fn cost(&self, params: &[f64], state: &Vec<f64>, _: &(), _: &()) -> f64 {
+        // x^T(Qx + c) / 2 + c^T x / 2
+        params.iter()
+            .zip(state.iter().zip(self.c.data().iter()))
+            .map(|(x, (g, c))| x * (g + c))
+            .sum::<f64>() / 2f64
+    }
+}
+
+/// Restricts a problem to the box lower <= x<sub>i</sub> <= upper.
+///
+/// Each update of the inner problem is clamped to the box, which is
+/// the exact constrained minimizer along the coordinate when the
+/// inner problem is convex. The starting parameters must lie in the
+/// box.
+#[derive(Clone, Debug)]
+pub struct Bounded<M> {
+    /// The unconstrained problem.
+    pub model: M,
+    /// The lower bound on every parameter.
+    pub lower: f64,
+    /// The upper bound on every parameter.
+    pub upper: f64,
+}
+
+impl<M: CoordinateModel> Bounded<M> {
+    /// Restricts the problem to the box.
+    ///
+    /// # Panics
+    ///
+    /// - The lower bound is greater than the upper bound.
+    pub fn new(model: M, lower: f64, upper: f64) -> Bounded<M> {
+        assert!(lower <= upper, "Lower bound must not exceed the upper bound.");
+
+        Bounded {
+            model: model,
+            lower: lower,
+            upper: upper,
+        }
+    }
+}
+
+impl<M: CoordinateModel> CoordinateModel for Bounded<M> {
+    type Inputs = M::Inputs;
+    type Targets = M::Targets;
+    type State = M::State;
+
+    fn init_state(&self,
+                  params: &[f64],
+                  inputs: &M::Inputs,
+                  targets: &M::Targets)
+                  -> M::State {
+        self.model.init_state(params, inputs, targets)
+    }
+
+    fn update(&self,
+              idx: usize,
+              params: &[f64],
+              state: &M::State,
+              inputs: &M::Inputs,
+              targets: &M::Targets)
+              -> f64 {
+        let x = self.model.update(idx, params, state, inputs, targets);
+        x.max(self.lower).min(self.upper)
+    }
+
+    fn apply(&self,
+             idx: usize,
+             old: f64,
+             params: &[f64],
+             state: &mut M::State,
+             inputs: &M::Inputs,
+             targets: &M::Targets) {
+        self.model.apply(idx, old, params, state, inputs, targets)
+    }
+
+    fn cost(&self,
+            params: &[f64],
+            state: &M::State,
+            inputs: &M::Inputs,
+            targets: &M::Targets)
+            -> f64 {
+        self.model.cost(params, state, inputs, targets)
+    }
+}
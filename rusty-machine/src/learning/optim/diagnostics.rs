@@ -0,0 +1,146 @@
+//! Numerical diagnostics
+//!
+//! Checks for NaN and infinite values during training. A diverging
+//! optimization otherwise runs to the end and leaves a model whose
+//! predictions are all NaN.
+//!
+//! The checks are off by default and enabled with `check_finite` on
+//! `GradientDesc`, `StochasticGD`, `AdaGrad`, `AdaDelta`, `Newton` and
+//! `ConjugateGD`. `CoordinateDescent` and the optimizers of the
+//! `global` module do not check. Training then stops at the first
+//! non-finite cost, gradient or parameter, keeping the last finite
+//! parameters, and the
+//! `TrainingReport` holds a `NonFiniteError` describing where it was
+//! found.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::learning::SupModel;
+//! use rusty_machine::learning::lin_reg::LinRegressor;
+//! use rusty_machine::learning::optim::OptimAlgorithm;
+//! use rusty_machine::learning::optim::grad_desc::GradientDesc;
+//! use rusty_machine::linalg::matrix::Matrix;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! let inputs = Matrix::new(2, 2, vec![1.0, 1e200, 1.0, 2e200]);
+//! let targets = Vector::new(vec![1.0, 2.0]);
+//!
+//! let gd = GradientDesc::new(0.1, 100).check_finite(true);
+//! let result = gd.optimize(&LinRegressor::default(), &[0.0, 0.0], &inputs, &targets);
+//!
+//! let error = result.report.error.unwrap();
+//! println!("{}", error);
+//! assert!(result.params.iter().all(|w| w.is_finite()));
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use learning::optim::Optimizable;
+
+/// A value which is NaN or infinite.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NonFiniteValue {
+    /// The cost.
+    Cost,
+    /// The gradient of the parameter at the index.
+    Gradient(usize),
+    /// The parameter at the index.
+    Parameter(usize),
+}
+
+/// A non-finite value found during training.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NonFiniteError {
+    /// The iteration the value was found in, counting from zero.
+    ///
+    /// For the stochastic algorithms this is the pass through the data.
+    pub iter: usize,
+    /// The value which is not finite.
+    pub value: NonFiniteValue,
+    /// The layer of the parameter, for models with layers.
+    pub layer: Option<usize>,
+}
+
+impl fmt::Display for NonFiniteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.value {
+            NonFiniteValue::Cost => write!(f, "Non-finite cost")?,
+            NonFiniteValue::Gradient(idx) => write!(f, "Non-finite gradient of parameter {}", idx)?,
+            NonFiniteValue::Parameter(idx) => write!(f, "Non-finite parameter {}", idx)?,
+        }
+        if let Some(layer) = self.layer {
+            write!(f, " in layer {}", layer)?;
+        }
+        write!(f, " at iteration {}.", self.iter)
+    }
+}
+
+impl Error for NonFiniteError {}
+
+/// Checks that the cost and gradient are finite.
+pub fn check_grad<M: Optimizable + ?Sized>(model: &M,
+                                           iter: usize,
+                                           cost: f64,
+                                           grad: &[f64])
+                                           -> Result<(), NonFiniteError> {
+    if !cost.is_finite() {
+        return Err(NonFiniteError {
+            iter: iter,
+            value: NonFiniteValue::Cost,
+            layer: None,
+        });
+    }
+
+    match grad.iter().position(|g| !g.is_finite()) {
+        Some(idx) => {
+            Err(NonFiniteError {
+                iter: iter,
+                value: NonFiniteValue::Gradient(idx),
+                layer: model.param_layer(idx),
+            })
+        }
+        None => Ok(()),
+    }
+}
+
+/// Checks that the parameters are finite.
+pub fn check_params<M: Optimizable + ?Sized>(model: &M,
+                                             iter: usize,
+                                             params: &[f64])
+                                             -> Result<(), NonFiniteError> {
+    match params.iter().position(|w| !w.is_finite()) {
+        Some(idx) => {
+            Err(NonFiniteError {
+                iter: iter,
+                value: NonFiniteValue::Parameter(idx),
+                layer: model.param_layer(idx),
+            })
+        }
+        None => Ok(()),
+    }
+}
+
+/// Checks that the parameters stay finite after adding `scale` times
+/// the step.
+///
+/// The optimizers check an update before applying it, so they keep the
+/// last finite parameters without copying them.
+pub fn check_step<M: Optimizable + ?Sized>(model: &M,
+                                           iter: usize,
+                                           params: &[f64],
+                                           step: &[f64],
+                                           scale: f64)
+                                           -> Result<(), NonFiniteError> {
+    match params.iter().zip(step.iter()).position(|(w, d)| !(w + scale * d).is_finite()) {
+        Some(idx) => {
+            Err(NonFiniteError {
+                iter: iter,
+                value: NonFiniteValue::Parameter(idx),
+                layer: model.param_layer(idx),
+            })
+        }
+        None => Ok(()),
+    }
+}
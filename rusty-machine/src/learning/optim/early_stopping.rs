@@ -0,0 +1,144 @@
+//! Early stopping
+//!
+//! The mini-batch optimizers, `StochasticGD`, `AdaGrad` and `AdaDelta`,
+//! can hold out part of the training data and stop once the cost on
+//! the held out rows has not improved for a number of passes. The
+//! parameters with the lowest validation cost are returned.
+//!
+//! Targets whose entries are all zero or one, such as binary labels or
+//! one-hot encoded classes, are treated as class labels. The rows of
+//! each class are split separately, so an imbalanced dataset does not
+//! give a validation set missing whole classes. Other targets are split
+//! as a single group.
+//!
+//! The validation rows are spaced evenly through each group rather
+//! than taken from the end, so data sorted by class or by target is
+//! still spread over both sets. The split does not depend on the
+//! random number generator.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::learning::SupModel;
+//! use rusty_machine::learning::nnet::NeuralNet;
+//! use rusty_machine::learning::optim::early_stopping::EarlyStopping;
+//! use rusty_machine::learning::optim::grad_desc::StochasticGD;
+//! use rusty_machine::linalg::matrix::Matrix;
+//!
+//! let inputs = Matrix::new(8, 1, vec![-4.0, -3.0, -2.0, -1.0, 1.0, 2.0, 3.0, 4.0]);
+//! let targets = Matrix::new(8, 1, vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]);
+//!
+//! // Hold out a quarter of the rows and stop after 5 passes without
+//! // improvement.
+//! let sgd = StochasticGD::new(0.1, 0.3, 1000).early_stopping(EarlyStopping::new(0.25, 5));
+//!
+//! let mut net = NeuralNet::builder().layers(&[1, 2, 1]).optimizer(sgd).build();
+//! net.train(&inputs, &targets);
+//! ```
+
+use std::vec::Vec;
+
+use data::split::class_indices;
+use linalg::matrix::{BaseMatrix, Matrix};
+
+#[cfg(not(feature = "std"))]
+use libnum::Float;
+
+/// Settings for early stopping on held out training rows.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EarlyStopping {
+    /// The fraction of the rows held out for validation.
+    pub validation_fraction: f64,
+    /// The number of passes without improvement before training stops.
+    pub patience: usize,
+}
+
+/// The default early stopping.
+///
+/// The defaults are:
+///
+/// - validation_fraction = 0.1
+/// - patience = 10
+impl Default for EarlyStopping {
+    fn default() -> EarlyStopping {
+        EarlyStopping::new(0.1, 10)
+    }
+}
+
+impl EarlyStopping {
+    /// Constructs early stopping with the fraction of rows to hold out
+    /// and the number of passes without improvement to wait.
+    ///
+    /// # Panics
+    ///
+    /// - The validation fraction is not between 0 and 1.
+    /// - The patience is zero.
+    pub fn new(validation_fraction: f64, patience: usize) -> EarlyStopping {
+        assert!(validation_fraction > 0f64 && validation_fraction < 1f64,
+                "Validation fraction must be between 0 and 1.");
+        assert!(patience > 0, "Patience must be at least one pass.");
+
+        EarlyStopping {
+            validation_fraction: validation_fraction,
+            patience: patience,
+        }
+    }
+}
+
+/// Returns true if every entry of the targets is zero or one.
+pub fn is_classification(targets: &Matrix<f64>) -> bool {
+    targets.data().iter().all(|&t| t == 0f64 || t == 1f64)
+}
+
+/// Splits the rows of the targets into training and validation rows.
+///
+/// Classification targets, as decided by `is_classification`, are
+/// split per class. Each group holds out its share of the rows,
+/// rounded to the nearest row and spaced evenly through the group.
+/// The indices are returned as `(train, validation)`, in row order.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::learning::optim::early_stopping::validation_split;
+/// use rusty_machine::linalg::matrix::Matrix;
+///
+/// // Two rows of the second class out of eight.
+/// let targets = Matrix::new(8, 2, vec![1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 1.0,
+///                                      1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 1.0]);
+///
+/// let (train, validation) = validation_split(&targets, 0.5);
+///
+/// // Each class is split in half.
+/// assert_eq!(validation, vec![1, 4, 6, 7]);
+/// assert_eq!(train, vec![0, 2, 3, 5]);
+/// ```
+///
+/// # Panics
+///
+/// - The fraction is not between 0 and 1.
+pub fn validation_split(targets: &Matrix<f64>, fraction: f64) -> (Vec<usize>, Vec<usize>) {
+    assert!(fraction > 0f64 && fraction < 1f64,
+            "Validation fraction must be between 0 and 1.");
+
+    let groups = if is_classification(targets) {
+        let rows = (0..targets.rows()).map(|i| targets.row(i)).collect::<Vec<_>>();
+        class_indices(&rows)
+    } else {
+        vec![(0..targets.rows()).collect()]
+    };
+
+    let mut held_out = vec![false; targets.rows()];
+    for group in groups {
+        let m = group.len();
+        let k = (m as f64 * fraction).round() as usize;
+        // The midpoints of k equal parts of the group.
+        for j in 0..k {
+            held_out[group[(2 * j + 1) * m / (2 * k)]] = true;
+        }
+    }
+
+    let (validation, train): (Vec<usize>, Vec<usize>) = (0..targets.rows())
+        .partition(|&i| held_out[i]);
+    (train, validation)
+}
@@ -27,15 +27,45 @@
 //!
 //! - Conversion to Rust.
 //! - Length hard defaults to the max iterations.
-
-use learning::optim::{Optimizable, OptimAlgorithm};
+//! - Choice of the conjugate direction update and periodic restarts.
+
+use learning::optim::{Optimizable, OptimAlgorithm, OptimResult, TrainingReport};
+#[cfg(feature = "std")]
+use learning::optim::callback::Callback;
+use learning::optim::callback::{CallbackList, Signal};
+use learning::optim::diagnostics::{self, NonFiniteError};
+use learning::optim::logging::{Logger, TrainingLog};
+use linalg::Metric;
 use linalg::vector::Vector;
 
 use std::cmp;
 use std::f64;
+use std::fmt;
+use std::sync::Arc;
+
+#[cfg(not(feature = "std"))]
+use libnum::Float;
 
 
+/// The formula for the conjugate direction update.
+///
+/// Each new search direction is the negative gradient plus beta
+/// times the previous direction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConjugateUpdate {
+    /// Fletcher-Reeves, beta = g<sub>k+1</sub><sup>T</sup>g<sub>k+1</sub> /
+    /// g<sub>k</sub><sup>T</sup>g<sub>k</sub>.
+    FletcherReeves,
+    /// Polak-Ribière, beta = g<sub>k+1</sub><sup>T</sup>(g<sub>k+1</sub> - g<sub>k</sub>) /
+    /// g<sub>k</sub><sup>T</sup>g<sub>k</sub>.
+    PolakRibiere,
+    /// Polak-Ribière with negative beta clamped to zero, which
+    /// restarts along the gradient instead.
+    PolakRibierePlus,
+}
+
 /// Conjugate Gradient Descent algorithm
+#[derive(Clone)]
 pub struct ConjugateGD {
     /// Constant in the Wolfe-Powell conditions.
     pub rho: f64,
@@ -52,6 +82,17 @@ pub struct ConjugateGD {
 
     /// The default number of max iterations.
     pub iters: usize,
+    /// Training stops, and is reported as converged, once the
+    /// gradient norm falls below this.
+    pub tol: f64,
+    /// The conjugate direction update.
+    pub update: ConjugateUpdate,
+    /// Restart along the negative gradient after every `restart`
+    /// successful line searches. Zero never restarts.
+    pub restart: usize,
+    check_finite: bool,
+    logger: Option<Logger>,
+    callbacks: CallbackList,
 }
 
 /// The default Conjugate GD algorithm.
@@ -65,6 +106,10 @@ pub struct ConjugateGD {
 /// - max = 20
 /// - ration = 100
 /// - iters = 100
+/// - tol = 1e-8
+/// - update = Polak-Ribière
+/// - restart = 0
+/// - check_finite = false
 impl Default for ConjugateGD {
     fn default() -> ConjugateGD {
         ConjugateGD {
@@ -75,37 +120,147 @@ impl Default for ConjugateGD {
             max: 20,
             ratio: 100.0,
             iters: 100,
+            tol: 1e-8,
+            update: ConjugateUpdate::PolakRibiere,
+            restart: 0,
+            check_finite: false,
+            logger: None,
+            callbacks: CallbackList::new(),
         }
     }
 }
 
+impl fmt::Debug for ConjugateGD {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ConjugateGD")
+            .field("rho", &self.rho)
+            .field("sig", &self.sig)
+            .field("int", &self.int)
+            .field("ext", &self.ext)
+            .field("max", &self.max)
+            .field("ratio", &self.ratio)
+            .field("iters", &self.iters)
+            .field("tol", &self.tol)
+            .field("update", &self.update)
+            .field("restart", &self.restart)
+            .field("check_finite", &self.check_finite)
+            .finish()
+    }
+}
+
+impl ConjugateGD {
+    /// Sets the conjugate direction update.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::optim::fmincg::{ConjugateGD, ConjugateUpdate};
+    ///
+    /// let cg = ConjugateGD::default().update(ConjugateUpdate::FletcherReeves).restart(50);
+    /// ```
+    pub fn update(mut self, update: ConjugateUpdate) -> ConjugateGD {
+        self.update = update;
+        self
+    }
+
+    /// Restarts along the negative gradient after every `restart`
+    /// successful line searches.
+    ///
+    /// Restarting every n iterations, for n parameters, is common.
+    /// Zero never restarts.
+    pub fn restart(mut self, restart: usize) -> ConjugateGD {
+        self.restart = restart;
+        self
+    }
+
+    /// Stops training at the first non-finite cost, gradient or
+    /// parameter met in a line search, recording it in the training
+    /// report.
+    ///
+    /// The point reached by the last successful line search is
+    /// returned.
+    pub fn check_finite(mut self, check: bool) -> ConjugateGD {
+        self.check_finite = check;
+        self
+    }
+
+    /// Checks a point of the line search if `check_finite` is set.
+    fn check_point<M: Optimizable>(&self,
+                                   model: &M,
+                                   iter: usize,
+                                   x: &Vector<f64>,
+                                   cost: f64,
+                                   grad: &Vector<f64>)
+                                   -> Option<NonFiniteError> {
+        if !self.check_finite {
+            return None;
+        }
+
+        diagnostics::check_params(model, iter, x.data())
+            .and_then(|_| diagnostics::check_grad(model, iter, cost, grad.data()))
+            .err()
+    }
+
+    /// Sets a logger which receives the progress after each
+    /// successful line search.
+    ///
+    /// The reported learning rate is the step size found by the line
+    /// search.
+    pub fn logger<F>(mut self, logger: F) -> ConjugateGD
+        where F: Fn(&TrainingLog) + Send + Sync + 'static
+    {
+        self.logger = Some(Arc::new(logger));
+        self
+    }
+
+    /// Adds a callback which is called after each successful line
+    /// search.
+    ///
+    /// Training stops early if the callback returns `Signal::Stop`.
+    ///
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn callback<C: Callback + Clone + Send + 'static>(mut self, callback: C) -> ConjugateGD {
+        self.callbacks.push(callback);
+        self
+    }
+}
+
 impl<M: Optimizable> OptimAlgorithm<M> for ConjugateGD {
     fn optimize(&self,
                 model: &M,
                 start: &[f64],
                 inputs: &M::Inputs,
                 targets: &M::Targets)
-                -> Vec<f64> {
+                -> OptimResult {
         let mut i = 0usize;
         let mut ls_failed = false;
+        let mut successes = 0usize;
 
         let (mut f1, vec_df1) = model.compute_grad(start, inputs, targets);
         let mut df1 = Vector::new(vec_df1);
 
+        // The cost and gradient at x, reported in the result.
+        let (mut f_x, mut df_x) = (f1, df1.clone());
+
         // The reduction in the function. Can also be specified as part of length
         let red = 1f64;
 
-        let length = self.iters as i32;
+        let mut x = Vector::new(start.to_vec());
+
+        let mut error = self.check_point(model, 0, &x, f1, &df1);
+        let length = if error.is_some() { 0 } else { self.iters as i32 };
 
         let mut s = -df1.clone();
         let mut d1 = -s.dot(&s);
         let mut z1 = red / (1f64 - d1);
 
-        let mut x = Vector::new(start.to_vec());
-
         let (mut f2, mut df2): (f64, Vector<f64>);
 
         while (i as i32) < length.abs() {
+            if df_x.norm() < self.tol {
+                break;
+            }
             if length > 0 {
                 i += 1;
             }
@@ -122,6 +277,12 @@ impl<M: Optimizable> OptimAlgorithm<M> for ConjugateGD {
                 i += 1;
             }
 
+            error = self.check_point(model, i - 1, &x, f2, &df2);
+            if error.is_some() {
+                x = x0;
+                break;
+            }
+
             let mut d2 = df2.dot(&s);
 
             let (mut f3, mut d3, mut z3) = (f1, d1, -z1);
@@ -136,7 +297,7 @@ impl<M: Optimizable> OptimAlgorithm<M> for ConjugateGD {
             let mut success = false;
             let mut limit = -1f64;
 
-            loop {
+            'search: loop {
                 let mut z2: f64;
 
                 while ((f2 > (f1 + z1 * self.rho * d1)) || (d2 > -self.sig * d1)) && (m > 0i32) {
@@ -178,6 +339,11 @@ impl<M: Optimizable> OptimAlgorithm<M> for ConjugateGD {
                         i += 1;
                     }
 
+                    error = self.check_point(model, i - 1, &x, f2, &df2);
+                    if error.is_some() {
+                        break 'search;
+                    }
+
                     d2 = df2.dot(&s);
                     z3 = z3 - z2;
                 }
@@ -226,12 +392,54 @@ impl<M: Optimizable> OptimAlgorithm<M> for ConjugateGD {
                     i += 1;
                 }
 
+                error = self.check_point(model, i - 1, &x, f2, &df2);
+                if error.is_some() {
+                    break 'search;
+                }
+
                 d2 = df2.dot(&s);
             }
 
+            if error.is_some() {
+                x = x0;
+                break;
+            }
+
             if success {
+                f_x = f2;
+                df_x = df2.clone();
+
+                if self.logger.is_some() || !self.callbacks.is_empty() {
+                    let log = TrainingLog {
+                        iter: i - 1,
+                        cost: f2,
+                        grad_norm: df2.norm(),
+                        learning_rate: z1,
+                    };
+
+                    if let Some(ref logger) = self.logger {
+                        logger(&log);
+                    }
+                    if self.callbacks.on_epoch_end(&log, x.data()) == Signal::Stop {
+                        break;
+                    }
+                }
+
                 f1 = f2;
-                s = s * (&df2 - &df1).dot(&df2) / df1.dot(&df1) - &df2;
+                successes += 1;
+
+                if self.restart > 0 && successes.is_multiple_of(self.restart) {
+                    s = -&df2;
+                } else {
+                    let beta = match self.update {
+                        ConjugateUpdate::FletcherReeves => df2.dot(&df2) / df1.dot(&df1),
+                        ConjugateUpdate::PolakRibiere => (&df2 - &df1).dot(&df2) / df1.dot(&df1),
+                        ConjugateUpdate::PolakRibierePlus => {
+                            ((&df2 - &df1).dot(&df2) / df1.dot(&df1)).max(0f64)
+                        }
+                    };
+                    s = s * beta - &df2;
+                }
 
                 df1 = df2;
 
@@ -255,6 +463,8 @@ impl<M: Optimizable> OptimAlgorithm<M> for ConjugateGD {
                 x = x0;
                 f1 = f0;
 
+                // Two line searches in a row failing means no further
+                // progress can be made.
                 if ls_failed || i as i32 > length.abs() {
                     break;
                 }
@@ -269,6 +479,17 @@ impl<M: Optimizable> OptimAlgorithm<M> for ConjugateGD {
             }
 
         }
-        x.into_vec()
+
+        let grad_norm = df_x.norm();
+        OptimResult {
+            params: x.into_vec(),
+            report: TrainingReport {
+                iters: i,
+                cost: f_x,
+                grad_norm: grad_norm,
+                converged: grad_norm < self.tol,
+                error: error,
+            },
+        }
     }
 }
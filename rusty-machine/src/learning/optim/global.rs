@@ -0,0 +1,408 @@
+//! Derivative-free optimization
+//!
+//! Optimizers which only evaluate the cost, so they can minimize
+//! objectives which are not differentiable, such as validation
+//! scores over hyperparameters.
+//!
+//! - `NelderMead` is a deterministic local search over a simplex.
+//! - `SimulatedAnnealing` and `GeneticAlgorithm` are stochastic
+//!   global searches. They require the `rand` feature and draw from
+//!   `rand_utils::rng`, so they are reproducible after
+//!   `rand_utils::set_seed`.
+//!
+//! Each optimizer can minimize a closure with `minimize`, and
+//! implements `OptimAlgorithm` using the cost from
+//! `Optimizable::compute_grad`. The gradient is ignored during the
+//! search and only evaluated for the final report.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::learning::optim::global::NelderMead;
+//!
+//! // The Rosenbrock function has its minimum at (1, 1).
+//! let rosenbrock = |x: &[f64]| {
+//!     (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2)
+//! };
+//!
+//! let min = NelderMead::new(1000).minimize(rosenbrock, &[-1.0, 2.0]);
+//! assert!((min[0] - 1.0).abs() < 1e-3 && (min[1] - 1.0).abs() < 1e-3);
+//! ```
+
+use std::cmp::Ordering;
+use std::vec::Vec;
+
+use learning::optim::{Optimizable, OptimAlgorithm, OptimResult};
+#[cfg(feature = "rand")]
+use learning::toolkit::rand_utils;
+#[cfg(feature = "rand")]
+use rand::Rng;
+
+/// The Nelder-Mead simplex algorithm.
+#[derive(Clone, Copy, Debug)]
+pub struct NelderMead {
+    /// The maximum number of iterations.
+    pub iters: usize,
+    /// Search stops when the costs across the simplex differ by less
+    /// than this.
+    pub tol: f64,
+    /// The distance from the start to the other initial vertices.
+    pub step: f64,
+}
+
+/// The default Nelder-Mead algorithm.
+///
+/// The defaults are:
+///
+/// - iters = 500
+/// - tol = 1e-10
+/// - step = 0.5
+impl Default for NelderMead {
+    fn default() -> NelderMead {
+        NelderMead::new(500)
+    }
+}
+
+impl NelderMead {
+    /// Constructs the Nelder-Mead algorithm with the maximum
+    /// number of iterations.
+    pub fn new(iters: usize) -> NelderMead {
+        NelderMead {
+            iters: iters,
+            tol: 1e-10,
+            step: 0.5,
+        }
+    }
+
+    /// Minimizes the function from the starting point.
+    pub fn minimize<F: FnMut(&[f64]) -> f64>(&self, f: F, start: &[f64]) -> Vec<f64> {
+        self.search(f, start).0
+    }
+
+    /// Minimizes the function, also returning the number of
+    /// iterations and whether the simplex converged.
+    fn search<F: FnMut(&[f64]) -> f64>(&self, mut f: F, start: &[f64]) -> (Vec<f64>, usize, bool) {
+        let n = start.len();
+
+        let mut simplex: Vec<(Vec<f64>, f64)> = Vec::with_capacity(n + 1);
+        simplex.push((start.to_vec(), f(start)));
+        for i in 0..n {
+            let mut vertex = start.to_vec();
+            vertex[i] += self.step;
+            let cost = f(&vertex);
+            simplex.push((vertex, cost));
+        }
+
+        let mut iters = 0;
+        let mut converged = false;
+
+        for i in 0..self.iters {
+            simplex.sort_by(|a, b| cmp_costs(a.1, b.1));
+
+            let (best, worst) = (simplex[0].1, simplex[n].1);
+            if (worst - best).abs() < self.tol {
+                converged = true;
+                break;
+            }
+            iters = i + 1;
+
+            // The centroid of every vertex but the worst.
+            let mut centroid = vec![0f64; n];
+            for (vertex, _) in &simplex[..n] {
+                for (c, v) in centroid.iter_mut().zip(vertex.iter()) {
+                    *c += v / n as f64;
+                }
+            }
+
+            let reflected = towards(&centroid, &simplex[n].0, -1f64);
+            let reflected_cost = f(&reflected);
+
+            if reflected_cost < best {
+                let expanded = towards(&centroid, &simplex[n].0, -2f64);
+                let expanded_cost = f(&expanded);
+                simplex[n] = if expanded_cost < reflected_cost {
+                    (expanded, expanded_cost)
+                } else {
+                    (reflected, reflected_cost)
+                };
+            } else if reflected_cost < simplex[n - 1].1 {
+                simplex[n] = (reflected, reflected_cost);
+            } else {
+                let contracted = if reflected_cost < worst {
+                    towards(&centroid, &reflected, 0.5)
+                } else {
+                    towards(&centroid, &simplex[n].0, 0.5)
+                };
+                let contracted_cost = f(&contracted);
+
+                if contracted_cost < reflected_cost.min(worst) {
+                    simplex[n] = (contracted, contracted_cost);
+                } else {
+                    // Shrink every vertex towards the best.
+                    let best_vertex = simplex[0].0.clone();
+                    for vertex in &mut simplex[1..] {
+                        vertex.0 = towards(&best_vertex, &vertex.0, 0.5);
+                        vertex.1 = f(&vertex.0);
+                    }
+                }
+            }
+        }
+
+        let best = simplex.into_iter().min_by(|a, b| cmp_costs(a.1, b.1)).unwrap().0;
+        (best, iters, converged)
+    }
+}
+
+impl<M: Optimizable> OptimAlgorithm<M> for NelderMead {
+    fn optimize(&self,
+                model: &M,
+                start: &[f64],
+                inputs: &M::Inputs,
+                targets: &M::Targets)
+                -> OptimResult {
+        let (params, iters, converged) =
+            self.search(|params| model.compute_grad(params, inputs, targets).0, start);
+        OptimResult::new(model, params, inputs, targets, iters, converged)
+    }
+}
+
+/// Simulated annealing.
+///
+/// Each iteration proposes a gaussian perturbation of the current
+/// point. Improvements are always accepted, and a worse proposal is
+/// accepted with probability exp(-increase / temperature). The
+/// temperature decays geometrically, so the search settles down over
+/// time. The best point seen is returned.
+///
+/// Requires the `rand` feature.
+#[cfg(feature = "rand")]
+#[derive(Clone, Copy, Debug)]
+pub struct SimulatedAnnealing {
+    /// The number of proposals.
+    pub iters: usize,
+    /// The initial temperature.
+    pub temperature: f64,
+    /// The factor the temperature is multiplied by each iteration.
+    pub cooling: f64,
+    /// The standard deviation of the proposals.
+    pub step: f64,
+}
+
+/// The default simulated annealing.
+///
+/// The defaults are:
+///
+/// - iters = 1000
+/// - temperature = 1
+/// - cooling = 0.995
+/// - step = 0.1
+#[cfg(feature = "rand")]
+impl Default for SimulatedAnnealing {
+    fn default() -> SimulatedAnnealing {
+        SimulatedAnnealing::new(1000, 1f64)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl SimulatedAnnealing {
+    /// Constructs simulated annealing with the number of proposals
+    /// and the initial temperature.
+    ///
+    /// # Panics
+    ///
+    /// - The temperature is not positive.
+    pub fn new(iters: usize, temperature: f64) -> SimulatedAnnealing {
+        assert!(temperature > 0f64, "Temperature must be positive.");
+
+        SimulatedAnnealing {
+            iters: iters,
+            temperature: temperature,
+            cooling: 0.995,
+            step: 0.1,
+        }
+    }
+
+    /// Minimizes the function from the starting point.
+    pub fn minimize<F: FnMut(&[f64]) -> f64>(&self, mut f: F, start: &[f64]) -> Vec<f64> {
+        let mut rng = rand_utils::rng();
+
+        let mut current = start.to_vec();
+        let mut current_cost = f(&current);
+        let mut best = (current.clone(), current_cost);
+        let mut temperature = self.temperature;
+
+        for _ in 0..self.iters {
+            let proposal: Vec<f64> = current.iter()
+                .map(|x| x + self.step * rand_utils::standard_normal(&mut rng))
+                .collect();
+            let cost = f(&proposal);
+
+            let accept = cost <= current_cost ||
+                         rng.gen::<f64>() < (-(cost - current_cost) / temperature).exp();
+            if accept {
+                current = proposal;
+                current_cost = cost;
+                if current_cost < best.1 {
+                    best = (current.clone(), current_cost);
+                }
+            }
+
+            temperature *= self.cooling;
+        }
+
+        best.0
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<M: Optimizable> OptimAlgorithm<M> for SimulatedAnnealing {
+    fn optimize(&self,
+                model: &M,
+                start: &[f64],
+                inputs: &M::Inputs,
+                targets: &M::Targets)
+                -> OptimResult {
+        let params = self.minimize(|params| model.compute_grad(params, inputs, targets).0, start);
+        OptimResult::new(model, params, inputs, targets, self.iters, false)
+    }
+}
+
+/// A simple genetic algorithm.
+///
+/// The initial population is scattered around the starting point.
+/// Each generation keeps the best individual and breeds the rest from
+/// parents chosen by tournaments of two, using uniform crossover and
+/// gaussian mutation.
+///
+/// Requires the `rand` feature.
+#[cfg(feature = "rand")]
+#[derive(Clone, Copy, Debug)]
+pub struct GeneticAlgorithm {
+    /// The number of generations.
+    pub generations: usize,
+    /// The number of individuals in each generation.
+    pub population: usize,
+    /// The probability each parameter of a child is mutated.
+    pub mutation_rate: f64,
+    /// The standard deviation of the mutations and of the initial
+    /// population around the starting point.
+    pub mutation_scale: f64,
+}
+
+/// The default genetic algorithm.
+///
+/// The defaults are:
+///
+/// - generations = 100
+/// - population = 50
+/// - mutation_rate = 0.1
+/// - mutation_scale = 0.5
+#[cfg(feature = "rand")]
+impl Default for GeneticAlgorithm {
+    fn default() -> GeneticAlgorithm {
+        GeneticAlgorithm::new(100, 50)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl GeneticAlgorithm {
+    /// Constructs a genetic algorithm with the number of generations
+    /// and the population size.
+    ///
+    /// # Panics
+    ///
+    /// - The population has fewer than two individuals.
+    pub fn new(generations: usize, population: usize) -> GeneticAlgorithm {
+        assert!(population >= 2, "Population must have at least two individuals.");
+
+        GeneticAlgorithm {
+            generations: generations,
+            population: population,
+            mutation_rate: 0.1,
+            mutation_scale: 0.5,
+        }
+    }
+
+    /// Minimizes the function, searching around the starting point.
+    pub fn minimize<F: FnMut(&[f64]) -> f64>(&self, mut f: F, start: &[f64]) -> Vec<f64> {
+        let mut rng = rand_utils::rng();
+
+        let mut population: Vec<(Vec<f64>, f64)> = Vec::with_capacity(self.population);
+        population.push((start.to_vec(), f(start)));
+        while population.len() < self.population {
+            let individual: Vec<f64> = start.iter()
+                .map(|x| x + self.mutation_scale * rand_utils::standard_normal(&mut rng))
+                .collect();
+            let cost = f(&individual);
+            population.push((individual, cost));
+        }
+
+        for _ in 0..self.generations {
+            let best = population.iter()
+                .min_by(|a, b| cmp_costs(a.1, b.1))
+                .unwrap()
+                .clone();
+
+            let mut next = Vec::with_capacity(self.population);
+            next.push(best);
+            while next.len() < self.population {
+                let (a, b) = (tournament(&population, &mut rng), tournament(&population, &mut rng));
+
+                let child: Vec<f64> = a.iter()
+                    .zip(b.iter())
+                    .map(|(x, y)| {
+                        let gene = if rng.gen() { *x } else { *y };
+                        if rng.gen::<f64>() < self.mutation_rate {
+                            gene + self.mutation_scale * rand_utils::standard_normal(&mut rng)
+                        } else {
+                            gene
+                        }
+                    })
+                    .collect();
+                let cost = f(&child);
+                next.push((child, cost));
+            }
+
+            population = next;
+        }
+
+        population.into_iter().min_by(|a, b| cmp_costs(a.1, b.1)).unwrap().0
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<M: Optimizable> OptimAlgorithm<M> for GeneticAlgorithm {
+    fn optimize(&self,
+                model: &M,
+                start: &[f64],
+                inputs: &M::Inputs,
+                targets: &M::Targets)
+                -> OptimResult {
+        let params = self.minimize(|params| model.compute_grad(params, inputs, targets).0, start);
+        OptimResult::new(model, params, inputs, targets, self.generations, false)
+    }
+}
+
+/// Orders costs with NaN above every number.
+fn cmp_costs(a: f64, b: f64) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => a.partial_cmp(&b).unwrap(),
+    }
+}
+
+/// The point `from + t * (to - from)`.
+fn towards(from: &[f64], to: &[f64], t: f64) -> Vec<f64> {
+    from.iter().zip(to.iter()).map(|(a, b)| a + t * (b - a)).collect()
+}
+
+/// The better of two random individuals.
+#[cfg(feature = "rand")]
+fn tournament<'a, R: Rng>(population: &'a [(Vec<f64>, f64)], rng: &mut R) -> &'a [f64] {
+    let a = &population[rng.gen_range(0, population.len())];
+    let b = &population[rng.gen_range(0, population.len())];
+    if a.1 <= b.1 { &a.0 } else { &b.0 }
+}
+
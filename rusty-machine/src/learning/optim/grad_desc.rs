@@ -4,20 +4,51 @@
 //! the struct GradientDesc which is instantiated within models
 //! implementing the Optimizable trait.
 //!
-//! Currently standard batch gradient descent is the only implemented
-//! optimization algorithm but there is flexibility to introduce new
-//! algorithms and git them into the same scheme easily.
+//! Alongside standard batch gradient descent the module contains
+//! mini-batch stochastic gradient descent and the adaptive AdaGrad
+//! and AdaDelta algorithms.
+//!
+//! Each algorithm can keep the parameters inside a constraint set by
+//! projecting them after every update, see the `projection` module,
+//! and can stop when training diverges, see the `diagnostics` module.
+
+use std::fmt;
+use std::sync::Arc;
+use std::convert::Infallible;
+use std::vec::Vec;
 
-use learning::optim::{Optimizable, OptimAlgorithm};
+use learning::optim::{Optimizable, OptimAlgorithm, OptimResult, TrainingReport};
+#[cfg(feature = "std")]
+use learning::optim::callback::Callback;
+use learning::optim::callback::{CallbackList, Signal};
+use learning::optim::diagnostics::{self, NonFiniteError};
+use learning::optim::early_stopping::{self, EarlyStopping};
+use learning::optim::line_search::{LineSearch, Point};
+use learning::optim::logging::{Logger, TrainingLog};
+use learning::optim::projection::Projection;
+use learning::toolkit::regularization::{self, Penalty};
+use linalg::Metric;
 use linalg::vector::Vector;
 use linalg::matrix::Matrix;
+use data::dataset::RowData;
+use data::source::{DataSource, MemorySource};
+
+#[cfg(not(feature = "std"))]
+use libnum::Float;
 
 /// Batch Gradient Descent algorithm
+#[derive(Clone)]
 pub struct GradientDesc {
     /// The step-size for the gradient descent steps.
     pub alpha: f64,
     /// The number of iterations to run.
     pub iters: usize,
+    penalty: Option<Arc<dyn Penalty + Send + Sync>>,
+    line_search: Option<LineSearch>,
+    projection: Option<Arc<dyn Projection + Send + Sync>>,
+    check_finite: bool,
+    logger: Option<Logger>,
+    callbacks: CallbackList,
 }
 
 /// The default gradient descent algorithm.
@@ -32,10 +63,27 @@ impl Default for GradientDesc {
         GradientDesc {
             alpha: 0.3,
             iters: 100,
+            penalty: None,
+            line_search: None,
+            projection: None,
+            check_finite: false,
+            logger: None,
+            callbacks: CallbackList::new(),
         }
     }
 }
 
+impl fmt::Debug for GradientDesc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GradientDesc")
+            .field("alpha", &self.alpha)
+            .field("iters", &self.iters)
+            .field("line_search", &self.line_search)
+            .field("check_finite", &self.check_finite)
+            .finish()
+    }
+}
+
 impl GradientDesc {
     /// Construct a gradient descent algorithm.
     ///
@@ -53,8 +101,106 @@ impl GradientDesc {
         GradientDesc {
             alpha: alpha,
             iters: iters,
+            penalty: None,
+            line_search: None,
+            projection: None,
+            check_finite: false,
+            logger: None,
+            callbacks: CallbackList::new(),
         }
     }
+
+    /// Sets a regularization penalty on the parameters.
+    ///
+    /// Each step is followed by the proximal step of the penalty on
+    /// the parameters which the model penalizes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::optim::grad_desc::GradientDesc;
+    /// use rusty_machine::learning::toolkit::regularization::L1;
+    ///
+    /// let gd = GradientDesc::new(0.3, 100).penalty(L1::new(0.01));
+    /// ```
+    pub fn penalty<P: Penalty + Send + Sync + 'static>(mut self, penalty: P) -> GradientDesc {
+        self.penalty = Some(Arc::new(penalty));
+        self
+    }
+
+    /// Chooses each step size with a line search.
+    ///
+    /// The step size `alpha` becomes the first step tried in each
+    /// iteration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::optim::grad_desc::GradientDesc;
+    /// use rusty_machine::learning::optim::line_search::LineSearch;
+    ///
+    /// let gd = GradientDesc::new(1.0, 100).line_search(LineSearch::armijo());
+    /// ```
+    pub fn line_search(mut self, line_search: LineSearch) -> GradientDesc {
+        self.line_search = Some(line_search);
+        self
+    }
+
+    /// Projects the parameters onto a constraint set after each step.
+    ///
+    /// The starting parameters are projected too. With a line search
+    /// the projection follows the accepted step.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::optim::grad_desc::GradientDesc;
+    /// use rusty_machine::learning::optim::projection::BoxBounds;
+    ///
+    /// let gd = GradientDesc::new(0.3, 100).projection(BoxBounds::new(-1.0, 1.0));
+    /// ```
+    pub fn projection<P: Projection + Send + Sync + 'static>(mut self,
+                                                            projection: P)
+                                                            -> GradientDesc {
+        self.projection = Some(Arc::new(projection));
+        self
+    }
+
+    /// Stops training at the first non-finite cost, gradient or
+    /// parameter, recording it in the training report.
+    ///
+    /// The parameters from before the failing update are returned.
+    pub fn check_finite(mut self, check: bool) -> GradientDesc {
+        self.check_finite = check;
+        self
+    }
+
+    /// Sets a logger which receives the progress after each iteration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::optim::grad_desc::GradientDesc;
+    ///
+    /// let gd = GradientDesc::new(0.3, 100).logger(|log| println!("{}", log.cost));
+    /// ```
+    pub fn logger<F>(mut self, logger: F) -> GradientDesc
+        where F: Fn(&TrainingLog) + Send + Sync + 'static
+    {
+        self.logger = Some(Arc::new(logger));
+        self
+    }
+
+    /// Adds a callback which is called after each iteration.
+    ///
+    /// Training stops early if the callback returns `Signal::Stop`.
+    ///
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn callback<C: Callback + Clone + Send + 'static>(mut self, callback: C) -> GradientDesc {
+        self.callbacks.push(callback);
+        self
+    }
 }
 
 impl<M: Optimizable> OptimAlgorithm<M> for GradientDesc {
@@ -63,24 +209,115 @@ impl<M: Optimizable> OptimAlgorithm<M> for GradientDesc {
                 start: &[f64],
                 inputs: &M::Inputs,
                 targets: &M::Targets)
-                -> Vec<f64> {
+                -> OptimResult {
+
+        let mut optimizing_val = start.to_vec();
+        if let Some(ref projection) = self.projection {
+            projection.project(&mut optimizing_val);
+        }
+        let penalized = regularization::penalized_indices(model, start.len());
+        let mut iters = 0;
+        let mut error = None;
+
+        for i in 0..self.iters {
+            let (smooth_cost, grad) = model.compute_grad(&optimizing_val[..], inputs, targets);
+            if self.check_finite {
+                if let Err(e) = diagnostics::check_grad(model, i, smooth_cost, &grad) {
+                    error = Some(e);
+                    break;
+                }
+            }
+
+            let mut cost = smooth_cost;
+            if let Some(ref penalty) = self.penalty {
+                cost += regularization::penalized_cost(&**penalty, &penalized, &optimizing_val);
+            }
 
-        let mut optimizing_val = Vector::new(start.to_vec());
+            let step = match self.line_search {
+                Some(ref line_search) => {
+                    let from = Point {
+                        params: optimizing_val,
+                        cost: smooth_cost,
+                        grad: grad.clone(),
+                    };
+                    let dir: Vec<f64> = grad.iter().map(|g| -g).collect();
+
+                    let (step, to) =
+                        line_search.search(model, inputs, targets, &from, &dir, self.alpha);
+                    if self.check_finite {
+                        if let Err(e) = diagnostics::check_params(model, i, &to.params) {
+                            optimizing_val = from.params;
+                            error = Some(e);
+                            break;
+                        }
+                    }
+                    optimizing_val = to.params;
+                    if step == 0f64 {
+                        // The line search failed, so no step makes progress.
+                        break;
+                    }
+                    step
+                }
+                None => {
+                    if self.check_finite {
+                        let checked = diagnostics::check_step(model,
+                                                              i,
+                                                              &optimizing_val,
+                                                              &grad,
+                                                              -self.alpha);
+                        if let Err(e) = checked {
+                            error = Some(e);
+                            break;
+                        }
+                    }
+                    // Update the parameters in place.
+                    for (w, g) in optimizing_val.iter_mut().zip(grad.iter()) {
+                        *w -= self.alpha * g;
+                    }
+                    self.alpha
+                }
+            };
+
+            if let Some(ref penalty) = self.penalty {
+                regularization::apply_prox(&**penalty, &penalized, &mut optimizing_val, step);
+            }
+            if let Some(ref projection) = self.projection {
+                projection.project(&mut optimizing_val);
+            }
+            iters = i + 1;
+
+            if self.logger.is_some() || !self.callbacks.is_empty() {
+                let log = TrainingLog {
+                    iter: i,
+                    cost: cost,
+                    grad_norm: Vector::new(grad).norm(),
+                    learning_rate: step,
+                };
+
+                if let Some(ref logger) = self.logger {
+                    logger(&log);
+                }
+                if self.callbacks.on_epoch_end(&log, &optimizing_val) == Signal::Stop {
+                    break;
+                }
+            }
+        }
 
-        for _ in 0..self.iters {
-            optimizing_val = &optimizing_val -
-                             Vector::new(model.compute_grad(&optimizing_val.data()[..],
-                                                            inputs,
-                                                            targets)
-                                              .1) * self.alpha;
+        let mut result = OptimResult::new(model, optimizing_val, inputs, targets, iters, false);
+        if let Some(ref penalty) = self.penalty {
+            result.report.cost += regularization::penalized_cost(&**penalty, &penalized, &result.params);
         }
-        optimizing_val.into_vec()
+        result.report.error = error;
+        result
     }
 }
 
 /// Stochastic Gradient Descent algorithm.
 ///
 /// Uses basic momentum to control the learning rate.
+///
+/// Each update uses the gradient of a mini-batch of the data.
+#[derive(Clone)]
 pub struct StochasticGD {
     /// Controls the momentum of the descent
     pub alpha: f64,
@@ -88,6 +325,16 @@ pub struct StochasticGD {
     pub mu: f64,
     /// The number of passes through the data.
     pub iters: usize,
+    /// The number of samples in each mini-batch.
+    pub batch_size: usize,
+    #[cfg(feature = "rand")]
+    shuffle: bool,
+    penalty: Option<Arc<dyn Penalty + Send + Sync>>,
+    projection: Option<Arc<dyn Projection + Send + Sync>>,
+    check_finite: bool,
+    early_stopping: Option<EarlyStopping>,
+    logger: Option<Logger>,
+    callbacks: CallbackList,
 }
 
 /// The default Stochastic GD algorithm.
@@ -97,6 +344,8 @@ pub struct StochasticGD {
 /// - alpha = 0.1
 /// - mu = 0.1
 /// - iters = 20
+/// - batch_size = 1
+/// - shuffle = true, with the `rand` feature
 impl Default for StochasticGD {
     
     fn default() -> StochasticGD {
@@ -104,15 +353,39 @@ impl Default for StochasticGD {
             alpha: 0.1,
             mu: 0.1,
             iters: 20,
+            batch_size: 1,
+            #[cfg(feature = "rand")]
+            shuffle: true,
+            penalty: None,
+            projection: None,
+            check_finite: false,
+            early_stopping: None,
+            logger: None,
+            callbacks: CallbackList::new(),
         }
     }
 }
 
+impl fmt::Debug for StochasticGD {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut debug = f.debug_struct("StochasticGD");
+        debug.field("alpha", &self.alpha);
+        debug.field("mu", &self.mu);
+        debug.field("iters", &self.iters);
+        debug.field("batch_size", &self.batch_size);
+        debug.field("check_finite", &self.check_finite);
+        debug.field("early_stopping", &self.early_stopping);
+        #[cfg(feature = "rand")]
+        debug.field("shuffle", &self.shuffle);
+        debug.finish()
+    }
+}
+
 impl StochasticGD {
     /// Construct a stochastic gradient descent algorithm.
     ///
     /// Requires the learning rate, momentum rate and iteration count
-    /// to be specified.
+    /// to be specified. The batch size defaults to 1.
     ///
     /// # Examples
     ///
@@ -126,31 +399,1036 @@ impl StochasticGD {
             alpha: alpha,
             mu: mu,
             iters: iters,
+            batch_size: 1,
+            #[cfg(feature = "rand")]
+            shuffle: true,
+            penalty: None,
+            projection: None,
+            check_finite: false,
+            early_stopping: None,
+            logger: None,
+            callbacks: CallbackList::new(),
+        }
+    }
+
+    /// Construct a stochastic gradient descent algorithm using mini-batches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::optim::grad_desc::StochasticGD;
+    ///
+    /// let sgd = StochasticGD::with_batch_size(0.1, 0.3, 5, 16);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The batch size is zero.
+    pub fn with_batch_size(alpha: f64, mu: f64, iters: usize, batch_size: usize) -> StochasticGD {
+        assert!(batch_size > 0, "Batch size must be greater than zero.");
+
+        StochasticGD {
+            alpha: alpha,
+            mu: mu,
+            iters: iters,
+            batch_size: batch_size,
+            #[cfg(feature = "rand")]
+            shuffle: true,
+            penalty: None,
+            projection: None,
+            check_finite: false,
+            early_stopping: None,
+            logger: None,
+            callbacks: CallbackList::new(),
         }
     }
+
+    /// Sets whether the samples are shuffled before each pass, so the
+    /// mini-batches differ between passes. This is on by default.
+    ///
+    /// Only the order of the samples is shuffled; the mini-batches are
+    /// copied from the inputs in that order. The order is drawn from
+    /// `rand_utils::rng`, so it is reproducible after calling
+    /// `rand_utils::set_seed`.
+    ///
+    /// Requires the `rand` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::optim::grad_desc::StochasticGD;
+    ///
+    /// // Visit the samples in the same order on every pass.
+    /// let sgd = StochasticGD::with_batch_size(0.1, 0.3, 5, 16).shuffle(false);
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn shuffle(mut self, shuffle: bool) -> StochasticGD {
+        self.shuffle = shuffle;
+        self
+    }
+
+    /// Sets a regularization penalty on the parameters.
+    ///
+    /// Each mini-batch update is followed by the proximal step of the
+    /// penalty, with step size `mu * mu`, on the parameters which the
+    /// model penalizes.
+    pub fn penalty<P: Penalty + Send + Sync + 'static>(mut self, penalty: P) -> StochasticGD {
+        self.penalty = Some(Arc::new(penalty));
+        self
+    }
+
+    /// Whether a regularization penalty is set.
+    pub fn has_penalty(&self) -> bool {
+        self.penalty.is_some()
+    }
+
+    /// Projects the parameters onto a constraint set after each
+    /// mini-batch update.
+    pub fn projection<P: Projection + Send + Sync + 'static>(mut self,
+                                                            projection: P)
+                                                            -> StochasticGD {
+        self.projection = Some(Arc::new(projection));
+        self
+    }
+
+    /// Stops training at the first non-finite cost, gradient or
+    /// parameter, recording it in the training report.
+    ///
+    /// The parameters from before the failing update are returned.
+    pub fn check_finite(mut self, check: bool) -> StochasticGD {
+        self.check_finite = check;
+        self
+    }
+
+    /// Holds out part of the data and stops training once the cost on
+    /// it has not improved for a number of passes, returning the
+    /// parameters with the lowest validation cost. See the
+    /// `early_stopping` module.
+    ///
+    /// Only used by `optimize`. Training from a data source uses all of
+    /// its mini-batches.
+    pub fn early_stopping(mut self, early_stopping: EarlyStopping) -> StochasticGD {
+        self.early_stopping = Some(early_stopping);
+        self
+    }
+
+    /// Sets a logger which receives the progress after each pass
+    /// through the data.
+    ///
+    /// The reported learning rate is `mu * mu`, the step size applied
+    /// to the gradient.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::optim::grad_desc::StochasticGD;
+    /// use rusty_machine::learning::optim::logging::print_log;
+    ///
+    /// let sgd = StochasticGD::new(0.1, 0.3, 5).logger(print_log);
+    /// ```
+    pub fn logger<F>(mut self, logger: F) -> StochasticGD
+        where F: Fn(&TrainingLog) + Send + Sync + 'static
+    {
+        self.logger = Some(Arc::new(logger));
+        self
+    }
+
+    /// Adds a callback which is called after each pass through
+    /// the data.
+    ///
+    /// Training stops early if the callback returns `Signal::Stop`.
+    ///
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn callback<C: Callback + Clone + Send + 'static>(mut self, callback: C) -> StochasticGD {
+        self.callbacks.push(callback);
+        self
+    }
+}
+
+impl StochasticGD {
+    /// Trains the model from the mini-batches of a data source.
+    ///
+    /// Each iteration is one pass through the source, and the batch
+    /// size and shuffling are set by the source. As the data may not
+    /// fit in memory, the cost and gradient norm in the training report
+    /// are the means over the mini-batches of the last pass.
+    ///
+    /// The source yields targets of the model's type, a `Matrix` for a
+    /// neural network and a `Vector` for the linear and logistic
+    /// regressors. The regressors take their intercept from a leading
+    /// column of ones in the inputs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::data::csv::{CsvOptions, CsvSource};
+    /// use rusty_machine::learning::nnet::{NeuralNet, MSECriterion};
+    /// use rusty_machine::learning::optim::grad_desc::StochasticGD;
+    /// use std::io::Cursor;
+    ///
+    /// let text = "0,0,0\n0,1,1\n1,0,1\n1,1,2\n";
+    /// let options = CsvOptions::default().target(2);
+    /// let mut source = CsvSource::new(Cursor::new(text), options, 2);
+    ///
+    /// let net = NeuralNet::new(&[2, 1], MSECriterion);
+    /// let sgd = StochasticGD::new(0.1, 0.3, 10);
+    /// let result = sgd.optimize_source(&net, net.parameters(), &mut source).unwrap();
+    ///
+    /// assert_eq!(result.report.iters, 10);
+    /// ```
+    ///
+    /// A linear regression, reading the targets as a vector:
+    ///
+    /// ```
+    /// use rusty_machine::data::csv::{CsvOptions, CsvSource};
+    /// use rusty_machine::learning::lin_reg::LinRegressor;
+    /// use rusty_machine::learning::optim::grad_desc::StochasticGD;
+    /// use rusty_machine::linalg::vector::Vector;
+    /// use std::io::Cursor;
+    ///
+    /// // A column of ones for the intercept, the input and the target.
+    /// let text = "1,0,1\n1,1,3\n1,2,5\n1,3,7\n";
+    /// let options = CsvOptions::default().target(2);
+    /// let mut source = CsvSource::<_, Vector<f64>>::new(Cursor::new(text), options, 2);
+    ///
+    /// let sgd = StochasticGD::new(0.1, 0.1, 500);
+    /// let result = sgd.optimize_source(&LinRegressor::default(), &[0.0, 0.0], &mut source).unwrap();
+    ///
+    /// assert!((result.params[0] - 1.0).abs() < 0.1);
+    /// assert!((result.params[1] - 2.0).abs() < 0.1);
+    /// ```
+    pub fn optimize_source<M, S>(&self,
+                                 model: &M,
+                                 start: &[f64],
+                                 source: &mut S)
+                                 -> Result<OptimResult, S::Error>
+        where M: Optimizable<Inputs = Matrix<f64>>,
+              M::Targets: RowData,
+              S: DataSource<M::Targets>
+    {
+        self.run(model, start, source, None).map(Trained::into_result)
+    }
+
+    /// Passes through the mini-batches of the source.
+    fn run<M, S>(&self,
+                 model: &M,
+                 start: &[f64],
+                 source: &mut S,
+                 validation: Option<&Validation<M::Targets>>)
+                 -> Result<Trained, S::Error>
+        where M: Optimizable<Inputs = Matrix<f64>>,
+              M::Targets: RowData,
+              S: DataSource<M::Targets>
+    {
+        let mut input_batch = Matrix::new(0, 0, vec![]);
+        let mut target_batch = M::Targets::empty();
+
+        let mut delta_w = vec![0f64; start.len()];
+        let mut params = start.to_vec();
+
+        // The first mini-batch starts the momentum.
+        source.reset()?;
+        if source.next_batch(&mut input_batch, &mut target_batch)? {
+            let (cost, grad) = model.compute_grad(start, &input_batch, &target_batch);
+            let mut error = if self.check_finite {
+                diagnostics::check_grad(model, 0, cost, &grad).err()
+            } else {
+                None
+            };
+
+            for ((d, w), g) in delta_w.iter_mut().zip(params.iter_mut()).zip(grad.iter()) {
+                *d = g * self.alpha;
+                *w -= *d * self.mu;
+            }
+            if let Some(ref projection) = self.projection {
+                projection.project(&mut params);
+            }
+            if self.check_finite && error.is_none() {
+                error = diagnostics::check_params(model, 0, &params).err();
+            }
+
+            // Training cannot start from a non-finite first step.
+            if error.is_some() {
+                return Ok(Trained {
+                    params: start.to_vec(),
+                    iters: 0,
+                    cost: cost,
+                    grad_norm: Vector::new(grad).norm(),
+                    error: error,
+                });
+            }
+        }
+
+        let passes = Passes {
+            iters: self.iters,
+            learning_rate: self.mu * self.mu,
+            penalty: &self.penalty,
+            prox_step: Some(self.mu * self.mu),
+            projection: &self.projection,
+            check_finite: self.check_finite,
+            validation: validation,
+            logger: &self.logger,
+            callbacks: &self.callbacks,
+        };
+
+        passes.run(model, &params, source, |grad, step| {
+            // Update the momentum in place.
+            for ((d, s), g) in delta_w.iter_mut().zip(step.iter_mut()).zip(grad.iter()) {
+                *d = g * self.mu + *d * self.alpha;
+                *s = -*d * self.mu;
+            }
+        })
+    }
 }
 
 impl<M: Optimizable<Inputs = Matrix<f64>, Targets = Matrix<f64>>> OptimAlgorithm<M> for StochasticGD {
 
-    fn optimize(&self, model: &M, start: &[f64], inputs: &M::Inputs, targets: &M::Targets) -> Vec<f64> {
+    fn optimize(&self, model: &M, start: &[f64], inputs: &M::Inputs, targets: &M::Targets) -> OptimResult {
+        let held_out = hold_out(self.early_stopping, inputs, targets);
+        let (train_inputs, train_targets, validation) = match held_out {
+            Some((ref inputs, ref targets, ref validation)) => (inputs, targets, Some(validation)),
+            None => (inputs, targets, None),
+        };
+
+        #[cfg_attr(not(feature = "rand"), allow(unused_mut))]
+        let mut source = MemorySource::new(train_inputs, train_targets, self.batch_size);
+        #[cfg(feature = "rand")]
+        {
+            source = source.shuffle(self.shuffle);
+        }
+
+        let mut result = infallible(self.run(model, start, &mut source, validation))
+            .evaluate(model, inputs, targets);
+        if let Some(ref penalty) = self.penalty {
+            let penalized = regularization::penalized_indices(model, start.len());
+            result.report.cost += regularization::penalized_cost(&**penalty, &penalized, &result.params);
+        }
+        result
+    }
+}
+
+/// AdaGrad algorithm.
+///
+/// Scales the step for each parameter by the inverse square root of
+/// the sum of its squared gradients, so rarely updated parameters take
+/// larger steps. This suits sparse gradients, such as those from text
+/// features.
+///
+/// Each update uses the gradient of a mini-batch of the data.
+#[derive(Clone)]
+pub struct AdaGrad {
+    /// The global learning rate.
+    pub eta: f64,
+    /// Added to the denominator for numerical stability.
+    pub epsilon: f64,
+    /// The number of passes through the data.
+    pub iters: usize,
+    /// The number of samples in each mini-batch.
+    pub batch_size: usize,
+    #[cfg(feature = "rand")]
+    shuffle: bool,
+    penalty: Option<Arc<dyn Penalty + Send + Sync>>,
+    projection: Option<Arc<dyn Projection + Send + Sync>>,
+    check_finite: bool,
+    early_stopping: Option<EarlyStopping>,
+    logger: Option<Logger>,
+    callbacks: CallbackList,
+}
+
+/// The default AdaGrad algorithm.
+///
+/// The defaults are:
+///
+/// - eta = 0.1
+/// - epsilon = 1e-8
+/// - iters = 20
+/// - batch_size = 1
+impl Default for AdaGrad {
+    fn default() -> AdaGrad {
+        AdaGrad::new(0.1, 20)
+    }
+}
+
+impl fmt::Debug for AdaGrad {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut debug = f.debug_struct("AdaGrad");
+        debug.field("eta", &self.eta);
+        debug.field("epsilon", &self.epsilon);
+        debug.field("iters", &self.iters);
+        debug.field("batch_size", &self.batch_size);
+        debug.field("check_finite", &self.check_finite);
+        debug.field("early_stopping", &self.early_stopping);
+        #[cfg(feature = "rand")]
+        debug.field("shuffle", &self.shuffle);
+        debug.finish()
+    }
+}
+
+impl AdaGrad {
+    /// Construct an AdaGrad algorithm.
+    ///
+    /// Requires the learning rate and iteration count to be
+    /// specified. The batch size defaults to 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::optim::grad_desc::AdaGrad;
+    ///
+    /// let ada = AdaGrad::new(0.5, 10);
+    /// ```
+    pub fn new(eta: f64, iters: usize) -> AdaGrad {
+        AdaGrad {
+            eta: eta,
+            epsilon: 1e-8,
+            iters: iters,
+            batch_size: 1,
+            #[cfg(feature = "rand")]
+            shuffle: false,
+            penalty: None,
+            projection: None,
+            check_finite: false,
+            early_stopping: None,
+            logger: None,
+            callbacks: CallbackList::new(),
+        }
+    }
+
+    /// Sets the number of samples in each mini-batch.
+    ///
+    /// # Panics
+    ///
+    /// - The batch size is zero.
+    pub fn batch_size(mut self, batch_size: usize) -> AdaGrad {
+        assert!(batch_size > 0, "Batch size must be greater than zero.");
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Shuffles the data before each pass.
+    ///
+    /// Requires the `rand` feature.
+    #[cfg(feature = "rand")]
+    pub fn shuffle(mut self, shuffle: bool) -> AdaGrad {
+        self.shuffle = shuffle;
+        self
+    }
+
+    /// Sets a regularization penalty on the parameters.
+    ///
+    /// The gradient of the penalty on the parameters which the model
+    /// penalizes is added to each mini-batch gradient. The steps
+    /// differ for each parameter, so there is no single step for a
+    /// proximal update.
+    pub fn penalty<P: Penalty + Send + Sync + 'static>(mut self, penalty: P) -> AdaGrad {
+        self.penalty = Some(Arc::new(penalty));
+        self
+    }
+
+    /// Projects the parameters onto a constraint set after each
+    /// mini-batch update.
+    pub fn projection<P: Projection + Send + Sync + 'static>(mut self, projection: P) -> AdaGrad {
+        self.projection = Some(Arc::new(projection));
+        self
+    }
+
+    /// Stops training at the first non-finite cost, gradient or
+    /// parameter, recording it in the training report.
+    ///
+    /// The parameters from before the failing update are returned.
+    pub fn check_finite(mut self, check: bool) -> AdaGrad {
+        self.check_finite = check;
+        self
+    }
+
+    /// Holds out part of the data and stops training once the cost on
+    /// it has not improved for a number of passes, returning the
+    /// parameters with the lowest validation cost. See the
+    /// `early_stopping` module.
+    ///
+    /// Only used by `optimize`. Training from a data source uses all of
+    /// its mini-batches.
+    pub fn early_stopping(mut self, early_stopping: EarlyStopping) -> AdaGrad {
+        self.early_stopping = Some(early_stopping);
+        self
+    }
+
+    /// Sets a logger which receives the progress after each pass
+    /// through the data.
+    ///
+    /// The reported learning rate is `eta`.
+    pub fn logger<F>(mut self, logger: F) -> AdaGrad
+        where F: Fn(&TrainingLog) + Send + Sync + 'static
+    {
+        self.logger = Some(Arc::new(logger));
+        self
+    }
+
+    /// Adds a callback which is called after each pass through
+    /// the data.
+    ///
+    /// Training stops early if the callback returns `Signal::Stop`.
+    ///
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn callback<C: Callback + Clone + Send + 'static>(mut self, callback: C) -> AdaGrad {
+        self.callbacks.push(callback);
+        self
+    }
+}
+
+impl AdaGrad {
+    /// Trains the model from the mini-batches of a data source.
+    ///
+    /// See `StochasticGD::optimize_source` for details.
+    pub fn optimize_source<M, S>(&self,
+                                 model: &M,
+                                 start: &[f64],
+                                 source: &mut S)
+                                 -> Result<OptimResult, S::Error>
+        where M: Optimizable<Inputs = Matrix<f64>>,
+              M::Targets: RowData,
+              S: DataSource<M::Targets>
+    {
+        self.run(model, start, source, None).map(Trained::into_result)
+    }
+
+    /// Passes through the mini-batches of the source.
+    fn run<M, S>(&self,
+                 model: &M,
+                 start: &[f64],
+                 source: &mut S,
+                 validation: Option<&Validation<M::Targets>>)
+                 -> Result<Trained, S::Error>
+        where M: Optimizable<Inputs = Matrix<f64>>,
+              M::Targets: RowData,
+              S: DataSource<M::Targets>
+    {
+        let passes = Passes {
+            iters: self.iters,
+            learning_rate: self.eta,
+            penalty: &self.penalty,
+            prox_step: None,
+            projection: &self.projection,
+            check_finite: self.check_finite,
+            validation: validation,
+            logger: &self.logger,
+            callbacks: &self.callbacks,
+        };
+
+        // The sums of the squared gradients.
+        let mut sq_grads = vec![0f64; start.len()];
+
+        passes.run(model, start, source, |grad, step| {
+            for ((d, s), g) in step.iter_mut().zip(sq_grads.iter_mut()).zip(grad.iter()) {
+                *s += g * g;
+                *d = -self.eta * g / (s.sqrt() + self.epsilon);
+            }
+        })
+    }
+}
+
+impl<M: Optimizable<Inputs = Matrix<f64>, Targets = Matrix<f64>>> OptimAlgorithm<M> for AdaGrad {
+    fn optimize(&self, model: &M, start: &[f64], inputs: &M::Inputs, targets: &M::Targets) -> OptimResult {
+        let held_out = hold_out(self.early_stopping, inputs, targets);
+        let (train_inputs, train_targets, validation) = match held_out {
+            Some((ref inputs, ref targets, ref validation)) => (inputs, targets, Some(validation)),
+            None => (inputs, targets, None),
+        };
+
+        #[cfg_attr(not(feature = "rand"), allow(unused_mut))]
+        let mut source = MemorySource::new(train_inputs, train_targets, self.batch_size);
+        #[cfg(feature = "rand")]
+        {
+            source = source.shuffle(self.shuffle);
+        }
+
+        let mut result = infallible(self.run(model, start, &mut source, validation))
+            .evaluate(model, inputs, targets);
+        if let Some(ref penalty) = self.penalty {
+            let penalized = regularization::penalized_indices(model, start.len());
+            result.report.cost += regularization::penalized_cost(&**penalty, &penalized, &result.params);
+        }
+        result
+    }
+}
+
+/// AdaDelta algorithm.
+///
+/// Adapts the step for each parameter from running averages of its
+/// squared gradients and squared updates, so there is no global
+/// learning rate to tune.
+///
+/// Each update uses the gradient of a mini-batch of the data.
+#[derive(Clone)]
+pub struct AdaDelta {
+    /// The decay rate of the running averages.
+    pub rho: f64,
+    /// Added inside the square roots for numerical stability.
+    pub epsilon: f64,
+    /// The number of passes through the data.
+    pub iters: usize,
+    /// The number of samples in each mini-batch.
+    pub batch_size: usize,
+    #[cfg(feature = "rand")]
+    shuffle: bool,
+    penalty: Option<Arc<dyn Penalty + Send + Sync>>,
+    projection: Option<Arc<dyn Projection + Send + Sync>>,
+    check_finite: bool,
+    early_stopping: Option<EarlyStopping>,
+    logger: Option<Logger>,
+    callbacks: CallbackList,
+}
+
+/// The default AdaDelta algorithm.
+///
+/// The defaults are:
+///
+/// - rho = 0.95
+/// - epsilon = 1e-6
+/// - iters = 20
+/// - batch_size = 1
+impl Default for AdaDelta {
+    fn default() -> AdaDelta {
+        AdaDelta::new(0.95, 20)
+    }
+}
+
+impl fmt::Debug for AdaDelta {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut debug = f.debug_struct("AdaDelta");
+        debug.field("rho", &self.rho);
+        debug.field("epsilon", &self.epsilon);
+        debug.field("iters", &self.iters);
+        debug.field("batch_size", &self.batch_size);
+        debug.field("check_finite", &self.check_finite);
+        debug.field("early_stopping", &self.early_stopping);
+        #[cfg(feature = "rand")]
+        debug.field("shuffle", &self.shuffle);
+        debug.finish()
+    }
+}
+
+impl AdaDelta {
+    /// Construct an AdaDelta algorithm.
+    ///
+    /// Requires the decay rate and iteration count to be specified.
+    /// The batch size defaults to 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::optim::grad_desc::AdaDelta;
+    ///
+    /// let ada = AdaDelta::new(0.9, 10);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The decay rate is not in the interval [0, 1).
+    pub fn new(rho: f64, iters: usize) -> AdaDelta {
+        assert!((0f64..1f64).contains(&rho),
+                "Decay rate must be in the interval [0, 1).");
+
+        AdaDelta {
+            rho: rho,
+            epsilon: 1e-6,
+            iters: iters,
+            batch_size: 1,
+            #[cfg(feature = "rand")]
+            shuffle: false,
+            penalty: None,
+            projection: None,
+            check_finite: false,
+            early_stopping: None,
+            logger: None,
+            callbacks: CallbackList::new(),
+        }
+    }
+
+    /// Sets the number of samples in each mini-batch.
+    ///
+    /// # Panics
+    ///
+    /// - The batch size is zero.
+    pub fn batch_size(mut self, batch_size: usize) -> AdaDelta {
+        assert!(batch_size > 0, "Batch size must be greater than zero.");
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Shuffles the data before each pass.
+    ///
+    /// Requires the `rand` feature.
+    #[cfg(feature = "rand")]
+    pub fn shuffle(mut self, shuffle: bool) -> AdaDelta {
+        self.shuffle = shuffle;
+        self
+    }
+
+    /// Sets a regularization penalty on the parameters.
+    ///
+    /// The gradient of the penalty on the parameters which the model
+    /// penalizes is added to each mini-batch gradient. The steps
+    /// differ for each parameter, so there is no single step for a
+    /// proximal update.
+    pub fn penalty<P: Penalty + Send + Sync + 'static>(mut self, penalty: P) -> AdaDelta {
+        self.penalty = Some(Arc::new(penalty));
+        self
+    }
+
+    /// Projects the parameters onto a constraint set after each
+    /// mini-batch update.
+    pub fn projection<P: Projection + Send + Sync + 'static>(mut self, projection: P) -> AdaDelta {
+        self.projection = Some(Arc::new(projection));
+        self
+    }
+
+    /// Stops training at the first non-finite cost, gradient or
+    /// parameter, recording it in the training report.
+    ///
+    /// The parameters from before the failing update are returned.
+    pub fn check_finite(mut self, check: bool) -> AdaDelta {
+        self.check_finite = check;
+        self
+    }
+
+    /// Holds out part of the data and stops training once the cost on
+    /// it has not improved for a number of passes, returning the
+    /// parameters with the lowest validation cost. See the
+    /// `early_stopping` module.
+    ///
+    /// Only used by `optimize`. Training from a data source uses all of
+    /// its mini-batches.
+    pub fn early_stopping(mut self, early_stopping: EarlyStopping) -> AdaDelta {
+        self.early_stopping = Some(early_stopping);
+        self
+    }
+
+    /// Sets a logger which receives the progress after each pass
+    /// through the data.
+    ///
+    /// AdaDelta has no global learning rate, so the reported
+    /// learning rate is 1.
+    pub fn logger<F>(mut self, logger: F) -> AdaDelta
+        where F: Fn(&TrainingLog) + Send + Sync + 'static
+    {
+        self.logger = Some(Arc::new(logger));
+        self
+    }
+
+    /// Adds a callback which is called after each pass through
+    /// the data.
+    ///
+    /// Training stops early if the callback returns `Signal::Stop`.
+    ///
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn callback<C: Callback + Clone + Send + 'static>(mut self, callback: C) -> AdaDelta {
+        self.callbacks.push(callback);
+        self
+    }
+}
+
+impl AdaDelta {
+    /// Trains the model from the mini-batches of a data source.
+    ///
+    /// See `StochasticGD::optimize_source` for details.
+    pub fn optimize_source<M, S>(&self,
+                                 model: &M,
+                                 start: &[f64],
+                                 source: &mut S)
+                                 -> Result<OptimResult, S::Error>
+        where M: Optimizable<Inputs = Matrix<f64>>,
+              M::Targets: RowData,
+              S: DataSource<M::Targets>
+    {
+        self.run(model, start, source, None).map(Trained::into_result)
+    }
 
-        let (_, vec_data) = model.compute_grad(start,
-                                               &inputs.select_rows(&[0]),
-                                               &targets.select_rows(&[0]));
-        let grad = Vector::new(vec_data);
-        let mut delta_w = grad * self.alpha;
-        let mut optimizing_val = Vector::new(start.to_vec()) - &delta_w * self.mu;
+    /// Passes through the mini-batches of the source.
+    fn run<M, S>(&self,
+                 model: &M,
+                 start: &[f64],
+                 source: &mut S,
+                 validation: Option<&Validation<M::Targets>>)
+                 -> Result<Trained, S::Error>
+        where M: Optimizable<Inputs = Matrix<f64>>,
+              M::Targets: RowData,
+              S: DataSource<M::Targets>
+    {
+        let passes = Passes {
+            iters: self.iters,
+            learning_rate: 1f64,
+            penalty: &self.penalty,
+            prox_step: None,
+            projection: &self.projection,
+            check_finite: self.check_finite,
+            validation: validation,
+            logger: &self.logger,
+            callbacks: &self.callbacks,
+        };
 
-        for _ in 0..self.iters {
-            for i in 1..inputs.rows() {
-                let (_, vec_data) = model.compute_grad(&optimizing_val.data()[..],
-                                                       &inputs.select_rows(&[i]),
-                                                       &targets.select_rows(&[i]));
+        // The running averages of the squared gradients and updates.
+        let mut sq_grads = vec![0f64; start.len()];
+        let mut sq_updates = vec![0f64; start.len()];
+        let rho = self.rho;
 
-                delta_w = Vector::new(vec_data) * self.mu + &delta_w * self.alpha;
-                optimizing_val = &optimizing_val - &delta_w * self.mu;
+        passes.run(model, start, source, |grad, step| {
+            for (i, (d, g)) in step.iter_mut().zip(grad.iter()).enumerate() {
+                sq_grads[i] = rho * sq_grads[i] + (1f64 - rho) * g * g;
+                let update = -(sq_updates[i] + self.epsilon).sqrt() /
+                             (sq_grads[i] + self.epsilon).sqrt() * g;
+                sq_updates[i] = rho * sq_updates[i] + (1f64 - rho) * update * update;
+                *d = update;
             }
+        })
+    }
+}
+
+impl<M: Optimizable<Inputs = Matrix<f64>, Targets = Matrix<f64>>> OptimAlgorithm<M> for AdaDelta {
+    fn optimize(&self, model: &M, start: &[f64], inputs: &M::Inputs, targets: &M::Targets) -> OptimResult {
+        let held_out = hold_out(self.early_stopping, inputs, targets);
+        let (train_inputs, train_targets, validation) = match held_out {
+            Some((ref inputs, ref targets, ref validation)) => (inputs, targets, Some(validation)),
+            None => (inputs, targets, None),
+        };
+
+        #[cfg_attr(not(feature = "rand"), allow(unused_mut))]
+        let mut source = MemorySource::new(train_inputs, train_targets, self.batch_size);
+        #[cfg(feature = "rand")]
+        {
+            source = source.shuffle(self.shuffle);
+        }
+
+        let mut result = infallible(self.run(model, start, &mut source, validation))
+            .evaluate(model, inputs, targets);
+        if let Some(ref penalty) = self.penalty {
+            let penalized = regularization::penalized_indices(model, start.len());
+            result.report.cost += regularization::penalized_cost(&**penalty, &penalized, &result.params);
         }
-        optimizing_val.into_vec()
+        result
+    }
+}
+
+/// Passes through mini-batches of the data, shared by the stochastic
+/// optimizers.
+struct Passes<'a, T: 'a> {
+    iters: usize,
+    /// The learning rate reported in the logs.
+    learning_rate: f64,
+    penalty: &'a Option<Arc<dyn Penalty + Send + Sync>>,
+    /// The step of the proximal update of the penalty after each
+    /// mini-batch, or `None` to add the gradient of the penalty to the
+    /// mini-batch gradients instead.
+    prox_step: Option<f64>,
+    projection: &'a Option<Arc<dyn Projection + Send + Sync>>,
+    check_finite: bool,
+    validation: Option<&'a Validation<T>>,
+    logger: &'a Option<Logger>,
+    callbacks: &'a CallbackList,
+}
+
+impl<'a, T: RowData> Passes<'a, T> {
+    /// Calls `update` with the gradient of each mini-batch of the
+    /// source, which writes the step to add to the parameters.
+    ///
+    /// The step is checked before it is applied. The penalty and
+    /// projection keep finite parameters finite.
+    fn run<M, S, U>(&self,
+                    model: &M,
+                    start: &[f64],
+                    source: &mut S,
+                    mut update: U)
+                    -> Result<Trained, S::Error>
+        where M: Optimizable<Inputs = Matrix<f64>, Targets = T>,
+              S: DataSource<T>,
+              U: FnMut(&[f64], &mut [f64])
+    {
+        let mut input_batch = Matrix::new(0, 0, vec![]);
+        let mut target_batch = T::empty();
+
+        let mut params = start.to_vec();
+        if let Some(ref projection) = *self.projection {
+            projection.project(&mut params);
+        }
+        let mut step = vec![0f64; params.len()];
+        let penalized = regularization::penalized_indices(model, start.len());
+        let mut iters = 0;
+        let mut error = None;
+        let mut last_pass = (0f64, 0f64);
+        // The lowest validation cost, its parameters and the passes since.
+        let mut best = (f64::INFINITY, None, 0usize);
+
+        'passes: for i in 0..self.iters {
+            source.reset()?;
+
+            let mut total_cost = 0f64;
+            let mut total_grad = vec![0f64; params.len()];
+            let mut n_batches = 0usize;
+
+            while source.next_batch(&mut input_batch, &mut target_batch)? {
+                let (mut cost, mut grad) = model.compute_grad(&params, &input_batch, &target_batch);
+                if self.check_finite {
+                    if let Err(e) = diagnostics::check_grad(model, i, cost, &grad) {
+                        error = Some(e);
+                        break 'passes;
+                    }
+                }
+
+                if let Some(ref penalty) = *self.penalty {
+                    cost += regularization::penalized_cost(&**penalty, &penalized, &params);
+                }
+                total_cost += cost;
+                for (t, g) in total_grad.iter_mut().zip(grad.iter()) {
+                    *t += *g;
+                }
+                n_batches += 1;
+
+                if let (Some(penalty), None) = (self.penalty.as_ref(), self.prox_step) {
+                    regularization::add_grad(&**penalty, &penalized, &params, &mut grad);
+                }
+                update(&grad, &mut step);
+                if self.check_finite {
+                    if let Err(e) = diagnostics::check_step(model, i, &params, &step, 1f64) {
+                        error = Some(e);
+                        break 'passes;
+                    }
+                }
+                for (w, d) in params.iter_mut().zip(step.iter()) {
+                    *w += *d;
+                }
+
+                if let (Some(penalty), Some(prox_step)) = (self.penalty.as_ref(), self.prox_step) {
+                    regularization::apply_prox(&**penalty, &penalized, &mut params, prox_step);
+                }
+                if let Some(ref projection) = *self.projection {
+                    projection.project(&mut params);
+                }
+            }
+            iters = i + 1;
+
+            let n = n_batches.max(1) as f64;
+            let mean_grad = Vector::new(total_grad.iter().map(|g| g / n).collect::<Vec<_>>());
+
+            let log = TrainingLog {
+                iter: i,
+                cost: total_cost / n,
+                grad_norm: mean_grad.norm(),
+                learning_rate: self.learning_rate,
+            };
+            last_pass = (log.cost, log.grad_norm);
+
+            if let Some(ref logger) = *self.logger {
+                logger(&log);
+            }
+            if self.callbacks.on_epoch_end(&log, &params) == Signal::Stop {
+                break;
+            }
+
+            if let Some(validation) = self.validation {
+                let (cost, _) = model.compute_grad(&params, &validation.inputs, &validation.targets);
+                if cost < best.0 {
+                    best = (cost, Some(params.clone()), 0);
+                } else {
+                    best.2 += 1;
+                    if best.2 >= validation.patience {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if let Some(best_params) = best.1 {
+            params = best_params;
+        }
+
+        Ok(Trained {
+            params: params,
+            iters: iters,
+            cost: last_pass.0,
+            grad_norm: last_pass.1,
+            error: error,
+        })
+    }
+}
+
+/// Rows held out from training for early stopping.
+struct Validation<T = Matrix<f64>> {
+    inputs: Matrix<f64>,
+    targets: T,
+    patience: usize,
+}
+
+/// Splits the validation rows from the training rows, returning the
+/// training inputs and targets with the validation rows.
+///
+/// # Panics
+///
+/// - There are too few rows to hold out both sets.
+fn hold_out(early_stopping: Option<EarlyStopping>,
+            inputs: &Matrix<f64>,
+            targets: &Matrix<f64>)
+            -> Option<(Matrix<f64>, Matrix<f64>, Validation)> {
+    early_stopping.map(|stopping| {
+        let (train, validation) = early_stopping::validation_split(targets,
+                                                                   stopping.validation_fraction);
+        assert!(!train.is_empty() && !validation.is_empty(),
+                "Too few rows to hold out a validation set.");
+
+        (inputs.select_rows(&train),
+         targets.select_rows(&train),
+         Validation {
+             inputs: inputs.select_rows(&validation),
+             targets: targets.select_rows(&validation),
+             patience: stopping.patience,
+         })
+    })
+}
+
+/// The parameters after passes through a data source.
+struct Trained {
+    params: Vec<f64>,
+    iters: usize,
+    /// The mean cost of the mini-batches in the last pass.
+    cost: f64,
+    /// The norm of the mean gradient in the last pass.
+    grad_norm: f64,
+    error: Option<NonFiniteError>,
+}
+
+impl Trained {
+    /// The result with the cost evaluated on all of the data.
+    fn evaluate<M: Optimizable>(self,
+                                model: &M,
+                                inputs: &M::Inputs,
+                                targets: &M::Targets)
+                                -> OptimResult {
+        let mut result = OptimResult::new(model, self.params, inputs, targets, self.iters, false);
+        result.report.error = self.error;
+        result
+    }
+
+    /// The result with the cost from the last pass.
+    fn into_result(self) -> OptimResult {
+        OptimResult {
+            params: self.params,
+            report: TrainingReport {
+                iters: self.iters,
+                cost: self.cost,
+                grad_norm: self.grad_norm,
+                converged: false,
+                error: self.error,
+            },
+        }
+    }
+}
+
+/// Unwraps the result of training from memory, which cannot fail.
+fn infallible<T>(result: Result<T, Infallible>) -> T {
+    match result {
+        Ok(t) => t,
+        Err(e) => match e {},
     }
 }
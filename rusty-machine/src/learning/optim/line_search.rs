@@ -0,0 +1,181 @@
+//! Line search
+//!
+//! Chooses the step size along a search direction, so optimizers do
+//! not need a hand-tuned learning rate.
+//!
+//! The default search backtracks from the initial step until the
+//! Armijo sufficient decrease condition holds. Setting a curvature
+//! constant also enforces the weak Wolfe conditions, growing the step
+//! when it is too short.
+//!
+//! `GradientDesc` and `Newton` take a line search. `ConjugateGD` keeps
+//! the interpolating line search of fmincg, which reuses the slopes it
+//! computes for the conjugate direction update and is configured by
+//! its own `rho`, `sig`, `int` and `ext` constants.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::learning::logistic_reg::LogisticRegressor;
+//! use rusty_machine::learning::optim::grad_desc::GradientDesc;
+//! use rusty_machine::learning::optim::line_search::LineSearch;
+//!
+//! // The step size of 1 is only the first step tried.
+//! let gd = GradientDesc::new(1.0, 100).line_search(LineSearch::wolfe());
+//! let model = LogisticRegressor::new(gd);
+//! ```
+
+use std::f64;
+use std::vec::Vec;
+
+use learning::optim::Optimizable;
+use linalg::utils;
+
+/// A point evaluated during optimization.
+#[derive(Clone, Debug)]
+pub struct Point {
+    /// The parameters.
+    pub params: Vec<f64>,
+    /// The cost at the parameters.
+    pub cost: f64,
+    /// The gradient at the parameters.
+    pub grad: Vec<f64>,
+}
+
+impl Point {
+    /// Evaluates the model at the parameters.
+    pub fn new<M: Optimizable>(model: &M,
+                               params: Vec<f64>,
+                               inputs: &M::Inputs,
+                               targets: &M::Targets)
+                               -> Point {
+        let (cost, grad) = model.compute_grad(&params, inputs, targets);
+        Point {
+            params: params,
+            cost: cost,
+            grad: grad,
+        }
+    }
+}
+
+/// A backtracking line search.
+#[derive(Clone, Copy, Debug)]
+pub struct LineSearch {
+    /// The sufficient decrease constant of the Armijo condition.
+    pub c1: f64,
+    /// The curvature constant of the Wolfe conditions, if they are
+    /// enforced.
+    pub c2: Option<f64>,
+    /// The factor the step is multiplied by when backtracking.
+    pub shrink: f64,
+    /// The maximum number of steps tried.
+    pub max_iters: usize,
+}
+
+/// The default line search enforces the Armijo condition.
+///
+/// The defaults are:
+///
+/// - c1 = 1e-4
+/// - c2 = None
+/// - shrink = 0.5
+/// - max_iters = 30
+impl Default for LineSearch {
+    fn default() -> LineSearch {
+        LineSearch {
+            c1: 1e-4,
+            c2: None,
+            shrink: 0.5,
+            max_iters: 30,
+        }
+    }
+}
+
+impl LineSearch {
+    /// A line search enforcing the Armijo condition.
+    pub fn armijo() -> LineSearch {
+        LineSearch::default()
+    }
+
+    /// A line search enforcing the weak Wolfe conditions, with
+    /// c2 = 0.9.
+    pub fn wolfe() -> LineSearch {
+        LineSearch { c2: Some(0.9), ..LineSearch::default() }
+    }
+
+    /// Searches along the direction from the point, starting with
+    /// the given step.
+    ///
+    /// Returns the step and the point it reaches. If no step
+    /// satisfies the conditions within `max_iters` tries, the tried
+    /// step with the lowest finite cost is returned if it decreases
+    /// the cost. Otherwise the search has failed and a step of zero
+    /// is returned with the starting point.
+    ///
+    /// # Panics
+    ///
+    /// - The direction has a different length to the parameters.
+    pub fn search<M: Optimizable>(&self,
+                                  model: &M,
+                                  inputs: &M::Inputs,
+                                  targets: &M::Targets,
+                                  from: &Point,
+                                  dir: &[f64],
+                                  step: f64)
+                                  -> (f64, Point) {
+        assert!(dir.len() == from.params.len(),
+                "Direction and parameters have different lengths.");
+
+        let slope = utils::dot(&from.grad, dir);
+        let (mut lo, mut hi) = (0f64, f64::INFINITY);
+        let mut step = step;
+        let mut best: Option<(f64, Point)> = None;
+
+        for _ in 0..self.max_iters {
+            let point = step_to(model, inputs, targets, from, dir, step);
+            let too_long = point.cost > from.cost + self.c1 * step * slope ||
+                           !point.cost.is_finite();
+            let too_short = !too_long &&
+                            self.c2.is_some_and(|c2| utils::dot(&point.grad, dir) < c2 * slope);
+
+            if !too_long && !too_short {
+                return (step, point);
+            }
+            if point.cost < from.cost && best.as_ref().is_none_or(|b| point.cost < b.1.cost) {
+                best = Some((step, point));
+            }
+
+            if too_long {
+                // Backtrack.
+                hi = step;
+                step = if lo == 0f64 {
+                    step * self.shrink
+                } else {
+                    (lo + hi) / 2f64
+                };
+            } else {
+                // Grow the step.
+                lo = step;
+                step = if hi.is_infinite() {
+                    step / self.shrink
+                } else {
+                    (lo + hi) / 2f64
+                };
+            }
+        }
+
+        best.unwrap_or_else(|| (0f64, from.clone()))
+    }
+}
+
+/// The point reached by the step along the direction.
+fn step_to<M: Optimizable>(model: &M,
+                           inputs: &M::Inputs,
+                           targets: &M::Targets,
+                           from: &Point,
+                           dir: &[f64],
+                           step: f64)
+                           -> Point {
+    let params = from.params.iter().zip(dir.iter()).map(|(x, d)| x + step * d).collect();
+    Point::new(model, params, inputs, targets)
+}
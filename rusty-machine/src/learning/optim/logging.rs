@@ -0,0 +1,61 @@
+//! Training progress logs
+//!
+//! The gradient descent algorithms can report their progress after
+//! each iteration (a pass through the data for stochastic gradient
+//! descent) to a logger. This keeps long training runs from being
+//! silent.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::learning::optim::grad_desc::StochasticGD;
+//! use rusty_machine::learning::optim::logging::print_log;
+//!
+//! // Print the progress of each epoch to stderr.
+//! let sgd = StochasticGD::default().logger(print_log);
+//!
+//! // Or handle the logs with a closure.
+//! let sgd = StochasticGD::default().logger(|log| {
+//!     if log.iter % 10 == 0 {
+//!         println!("Epoch {}: cost {}", log.iter, log.cost);
+//!     }
+//! });
+//! ```
+
+use std::sync::Arc;
+
+/// The progress of an optimizer after an iteration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrainingLog {
+    /// The iteration which just finished, counting from zero.
+    pub iter: usize,
+    /// The cost of the model.
+    ///
+    /// For stochastic gradient descent this is the mean cost of the
+    /// mini-batches in the pass.
+    pub cost: f64,
+    /// The euclidean norm of the gradient.
+    ///
+    /// For stochastic gradient descent this is the norm of the mean
+    /// gradient of the mini-batches in the pass.
+    pub grad_norm: f64,
+    /// The step size applied to the gradient.
+    pub learning_rate: f64,
+}
+
+/// A function which receives the training logs.
+///
+/// The logger is shared between the clones of an optimizer.
+pub type Logger = Arc<dyn Fn(&TrainingLog) + Send + Sync>;
+
+/// Prints the training log to stderr.
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+pub fn print_log(log: &TrainingLog) {
+    eprintln!("iter: {:>5}  cost: {:.6e}  grad norm: {:.6e}  learning rate: {:.3e}",
+              log.iter,
+              log.cost,
+              log.grad_norm,
+              log.learning_rate);
+}
@@ -0,0 +1,278 @@
+//! Newton's method
+//!
+//! Second order optimization using the Hessian of the cost. Module
+//! contains the struct Newton which is instantiated within models
+//! implementing `Optimizable::compute_hessian`.
+//!
+//! Each step solves the Newton system, adding damping to the
+//! diagonal of the Hessian when it is singular, and then runs a line
+//! search along the step starting from the full Newton step. For convex models
+//! such as logistic regression this converges in a handful of
+//! iterations.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::learning::logistic_reg::LogisticRegressor;
+//! use rusty_machine::learning::optim::newton::Newton;
+//! use rusty_machine::learning::SupModel;
+//! use rusty_machine::linalg::matrix::Matrix;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! let inputs = Matrix::new(4, 1, vec![1.0, 4.0, 3.0, 7.0]);
+//! let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+//!
+//! let mut model = LogisticRegressor::default();
+//! model.train_with_newton(&inputs, &targets, &Newton::default());
+//!
+//! let output = model.predict(&Matrix::new(1, 1, vec![10.0]));
+//! assert!(output[0] > 0.5);
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+
+use learning::optim::{Optimizable, OptimAlgorithm, OptimResult, TrainingReport};
+#[cfg(feature = "std")]
+use learning::optim::callback::Callback;
+use learning::optim::callback::{CallbackList, Signal};
+use learning::optim::diagnostics;
+use learning::optim::line_search::{LineSearch, Point};
+use learning::optim::logging::{Logger, TrainingLog};
+use linalg::Metric;
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+
+/// The damping added when the Hessian is singular and none is set.
+const MIN_DAMPING: f64 = 1e-10;
+/// The maximum number of times the damping is increased.
+const MAX_DAMPING_STEPS: usize = 20;
+
+/// Damped Newton's method with a line search.
+#[derive(Clone)]
+pub struct Newton {
+    /// The maximum number of iterations to run.
+    pub iters: usize,
+    /// Training stops, and is reported as converged, once the gradient
+    /// norm falls below this.
+    pub tol: f64,
+    /// Added to the diagonal of the Hessian before solving.
+    ///
+    /// Each step increases it further, a bounded number of times,
+    /// while the solution is not finite.
+    pub damping: f64,
+    line_search: LineSearch,
+    check_finite: bool,
+    logger: Option<Logger>,
+    callbacks: CallbackList,
+}
+
+/// The default Newton's method.
+///
+/// The defaults are:
+///
+/// - iters = 100
+/// - tol = 1e-8
+/// - damping = 0
+/// - line search = Armijo backtracking
+impl Default for Newton {
+    fn default() -> Newton {
+        Newton::new(100, 1e-8)
+    }
+}
+
+impl fmt::Debug for Newton {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Newton")
+            .field("iters", &self.iters)
+            .field("tol", &self.tol)
+            .field("damping", &self.damping)
+            .field("line_search", &self.line_search)
+            .field("check_finite", &self.check_finite)
+            .finish()
+    }
+}
+
+impl Newton {
+    /// Construct Newton's method with the maximum number of
+    /// iterations and the gradient tolerance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::optim::newton::Newton;
+    ///
+    /// let newton = Newton::new(20, 1e-6);
+    /// ```
+    pub fn new(iters: usize, tol: f64) -> Newton {
+        Newton {
+            iters: iters,
+            tol: tol,
+            damping: 0f64,
+            line_search: LineSearch::default(),
+            check_finite: false,
+            logger: None,
+            callbacks: CallbackList::new(),
+        }
+    }
+
+    /// Sets the damping added to the diagonal of the Hessian.
+    ///
+    /// Damping shortens the steps towards those of gradient descent,
+    /// which helps when far from the minimum.
+    pub fn damping(mut self, damping: f64) -> Newton {
+        assert!(damping >= 0f64, "Damping must be non-negative.");
+        self.damping = damping;
+        self
+    }
+
+    /// Sets the line search along each Newton step.
+    pub fn line_search(mut self, line_search: LineSearch) -> Newton {
+        self.line_search = line_search;
+        self
+    }
+
+    /// Stops training at the first non-finite cost, gradient or
+    /// parameter, recording it in the training report.
+    ///
+    /// The last finite point is returned.
+    pub fn check_finite(mut self, check: bool) -> Newton {
+        self.check_finite = check;
+        self
+    }
+
+    /// Sets a logger which receives the progress after each iteration.
+    pub fn logger<F>(mut self, logger: F) -> Newton
+        where F: Fn(&TrainingLog) + Send + Sync + 'static
+    {
+        self.logger = Some(Arc::new(logger));
+        self
+    }
+
+    /// Adds a callback which is called after each iteration.
+    ///
+    /// Training stops early if the callback returns `Signal::Stop`.
+    ///
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn callback<C: Callback + Clone + Send + 'static>(mut self, callback: C) -> Newton {
+        self.callbacks.push(callback);
+        self
+    }
+
+    /// Solves the damped Newton system for the step direction.
+    ///
+    /// The damping is increased until the solution is finite. Returns
+    /// `None` if it is still not finite after `MAX_DAMPING_STEPS`
+    /// increases, as it is for a non-finite gradient or Hessian.
+    fn direction(&self, hessian: &Matrix<f64>, grad: &Vector<f64>) -> Option<Vector<f64>> {
+        let n = grad.size();
+        let mut damping = self.damping;
+
+        for _ in 0..MAX_DAMPING_STEPS + 1 {
+            let system = if damping > 0f64 {
+                hessian + Matrix::identity(n) * damping
+            } else {
+                hessian.clone()
+            };
+
+            let dir = -system.solve(grad.clone());
+            if dir.data().iter().all(|x| x.is_finite()) {
+                return Some(dir);
+            }
+
+            damping = (damping * 10f64).max(MIN_DAMPING);
+        }
+
+        None
+    }
+}
+
+impl<M: Optimizable> OptimAlgorithm<M> for Newton {
+    /// Minimizes the cost using Newton's method.
+    ///
+    /// # Panics
+    ///
+    /// - The model does not compute its Hessian.
+    fn optimize(&self,
+                model: &M,
+                start: &[f64],
+                inputs: &M::Inputs,
+                targets: &M::Targets)
+                -> OptimResult {
+        let mut point = Point::new(model, start.to_vec(), inputs, targets);
+        let mut iters = 0;
+        let mut error = None;
+
+        if self.check_finite {
+            error = diagnostics::check_grad(model, 0, point.cost, &point.grad).err();
+        }
+        let max_iters = if error.is_some() { 0 } else { self.iters };
+
+        for i in 0..max_iters {
+            let cost = point.cost;
+            let grad = Vector::new(point.grad.clone());
+            let grad_norm = grad.norm();
+
+            if grad_norm < self.tol {
+                break;
+            }
+
+            let hessian = model.compute_hessian(&point.params, inputs, targets)
+                .expect("Model does not compute its Hessian.");
+
+            let dir = match self.direction(&hessian, &grad) {
+                Some(dir) if grad.dot(&dir) < 0f64 => dir,
+                // The Hessian is not positive definite here, or the
+                // system could not be solved, so fall back to the
+                // steepest descent direction.
+                _ => -grad,
+            };
+
+            let (step, next) = self.line_search
+                .search(model, inputs, targets, &point, dir.data(), 1f64);
+            if step == 0f64 {
+                // The line search failed, so no step makes progress.
+                break;
+            }
+            if self.check_finite {
+                error = diagnostics::check_params(model, i, &next.params)
+                    .and_then(|_| diagnostics::check_grad(model, i, next.cost, &next.grad))
+                    .err();
+                if error.is_some() {
+                    break;
+                }
+            }
+            point = next;
+            iters = i + 1;
+
+            if self.logger.is_some() || !self.callbacks.is_empty() {
+                let log = TrainingLog {
+                    iter: i,
+                    cost: cost,
+                    grad_norm: grad_norm,
+                    learning_rate: step,
+                };
+
+                if let Some(ref logger) = self.logger {
+                    logger(&log);
+                }
+                if self.callbacks.on_epoch_end(&log, &point.params) == Signal::Stop {
+                    break;
+                }
+            }
+        }
+
+        let grad_norm = Vector::new(point.grad).norm();
+        OptimResult {
+            params: point.params,
+            report: TrainingReport {
+                iters: iters,
+                cost: point.cost,
+                grad_norm: grad_norm,
+                converged: grad_norm < self.tol,
+                error: error,
+            },
+        }
+    }
+}
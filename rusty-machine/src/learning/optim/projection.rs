@@ -0,0 +1,134 @@
+//! Projections onto constraint sets
+//!
+//! The gradient descent algorithms can keep the parameters inside a
+//! constraint set by projecting them back onto it after every update.
+//! This is projected gradient descent, used for example for
+//! non-negative matrix factorization or for parameters which are
+//! probabilities.
+//!
+//! Any closure taking `&mut [f64]` is a projection, so constraints on
+//! part of the parameters can be built from the functions here.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::learning::optim::grad_desc::GradientDesc;
+//! use rusty_machine::learning::optim::projection::{self, NonNegative};
+//!
+//! // Keep every parameter non-negative.
+//! let gd = GradientDesc::default().projection(NonNegative);
+//!
+//! // Keep the parameters after the first on the probability simplex.
+//! let gd = GradientDesc::default().projection(|params: &mut [f64]| {
+//!     projection::project_simplex(&mut params[1..]);
+//! });
+//! ```
+
+use std::cmp::Ordering;
+use std::vec::Vec;
+
+/// Trait for projections onto a constraint set.
+pub trait Projection {
+    /// Replaces the parameters with the nearest point in the set.
+    fn project(&self, params: &mut [f64]);
+}
+
+impl<F: Fn(&mut [f64])> Projection for F {
+    fn project(&self, params: &mut [f64]) {
+        self(params)
+    }
+}
+
+/// The non-negative parameters.
+#[derive(Clone, Copy, Debug)]
+pub struct NonNegative;
+
+impl Projection for NonNegative {
+    fn project(&self, params: &mut [f64]) {
+        for w in params {
+            *w = w.max(0f64);
+        }
+    }
+}
+
+/// The parameters between a lower and an upper bound.
+#[derive(Clone, Copy, Debug)]
+pub struct BoxBounds {
+    /// The lower bound on every parameter.
+    pub lower: f64,
+    /// The upper bound on every parameter.
+    pub upper: f64,
+}
+
+impl BoxBounds {
+    /// Constructs the box between the bounds.
+    ///
+    /// # Panics
+    ///
+    /// - The lower bound is greater than the upper bound.
+    pub fn new(lower: f64, upper: f64) -> BoxBounds {
+        assert!(lower <= upper, "Lower bound must not exceed the upper bound.");
+
+        BoxBounds {
+            lower: lower,
+            upper: upper,
+        }
+    }
+}
+
+impl Projection for BoxBounds {
+    fn project(&self, params: &mut [f64]) {
+        for w in params {
+            *w = w.max(self.lower).min(self.upper);
+        }
+    }
+}
+
+/// The probability simplex, the non-negative parameters summing to one.
+#[derive(Clone, Copy, Debug)]
+pub struct Simplex;
+
+impl Projection for Simplex {
+    fn project(&self, params: &mut [f64]) {
+        project_simplex(params)
+    }
+}
+
+/// Projects the parameters onto the probability simplex.
+///
+/// Uses the sorting algorithm of Duchi et al., subtracting the
+/// threshold which leaves a sum of one and clipping at zero.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::learning::optim::projection::project_simplex;
+///
+/// let mut params = vec![0.5, 1.5, -1.0];
+/// project_simplex(&mut params);
+///
+/// assert_eq!(params, vec![0.0, 1.0, 0.0]);
+/// ```
+pub fn project_simplex(params: &mut [f64]) {
+    if params.is_empty() {
+        return;
+    }
+
+    let mut sorted: Vec<f64> = params.to_vec();
+    sorted.sort_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+
+    // The threshold from the largest k values which all stay positive.
+    let mut total = 0f64;
+    let mut threshold = 0f64;
+    for (k, x) in sorted.iter().enumerate() {
+        total += x;
+        let t = (total - 1f64) / (k + 1) as f64;
+        if x - t > 0f64 {
+            threshold = t;
+        }
+    }
+
+    for w in params {
+        *w = (*w - threshold).max(0f64);
+    }
+}
@@ -0,0 +1,166 @@
+//! Pipeline module
+//!
+//! Chains data transforms with a final supervised model.
+//!
+//! Training a pipeline fits each transform in turn to the training
+//! inputs and trains the model on the transformed data. Predictions
+//! then replay the fitted transforms on the new inputs, so the same
+//! preprocessing is always applied at training and prediction time.
+//!
+//! # Usage
+//!
+//! ```
+//! use rusty_machine::data::transforms::standardize::StandardScaler;
+//! use rusty_machine::learning::lin_reg::LinRegressor;
+//! use rusty_machine::learning::pipeline::Pipeline;
+//! use rusty_machine::learning::SupModel;
+//! use rusty_machine::linalg::matrix::Matrix;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! let inputs = Matrix::new(4, 1, vec![100.0, 200.0, 300.0, 400.0]);
+//! let targets = Vector::new(vec![1.0, 2.0, 3.0, 4.0]);
+//!
+//! let mut model = Pipeline::new(LinRegressor::default())
+//!                     .add_transform(StandardScaler::default());
+//!
+//! model.train(&inputs, &targets);
+//!
+//! // The new input is scaled using the training data statistics.
+//! let output = model.predict(&Matrix::new(1, 1, vec![500.0]));
+//! assert!((output[0] - 5.0).abs() < 1e-8);
+//! ```
+
+use std::fmt;
+
+use data::transforms::Transformer;
+use learning::{ApproxEq, ModelParams, ParamGroup, SupModel, WeightedSupModel};
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+
+/// A sequence of transforms followed by a model.
+///
+/// The transforms are boxed trait objects, so unlike the models a
+/// pipeline cannot be cloned.
+pub struct Pipeline<M> {
+    transforms: Vec<Box<dyn Transformer<Matrix<f64>> + Send + Sync>>,
+    model: M,
+}
+
+impl<M> Pipeline<M> {
+    /// Constructs a pipeline with no transforms around the model.
+    pub fn new(model: M) -> Pipeline<M> {
+        Pipeline {
+            transforms: Vec::new(),
+            model: model,
+        }
+    }
+
+    /// Adds a transform to the end of the pipeline.
+    ///
+    /// The transforms are applied in the order they are added.
+    pub fn add_transform<T>(mut self, transform: T) -> Pipeline<M>
+        where T: Transformer<Matrix<f64>> + Send + Sync + 'static
+    {
+        self.transforms.push(Box::new(transform));
+        self
+    }
+
+    /// The number of transforms in the pipeline.
+    pub fn transform_count(&self) -> usize {
+        self.transforms.len()
+    }
+
+    /// Returns a reference to the model.
+    pub fn model(&self) -> &M {
+        &self.model
+    }
+
+    /// Applies the fitted transforms to the inputs.
+    ///
+    /// # Panics
+    ///
+    /// - The pipeline has not been trained.
+    pub fn transform(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        let mut current: Option<Matrix<f64>> = None;
+
+        for t in &self.transforms {
+            let next = t.transform(current.as_ref().unwrap_or(inputs));
+            current = Some(next);
+        }
+
+        current.unwrap_or_else(|| inputs.clone())
+    }
+}
+
+impl<M: fmt::Debug> fmt::Debug for Pipeline<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("transforms", &self.transforms.len())
+            .field("model", &self.model)
+            .finish()
+    }
+}
+
+/// Compares the models, ignoring the fitted transforms.
+impl<M: ApproxEq> ApproxEq for Pipeline<M> {
+    fn approx_eq(&self, other: &Pipeline<M>, tol: f64) -> bool {
+        self.model.approx_eq(&other.model, tol)
+    }
+}
+
+/// The parameters of the model, the fitted transforms are not included.
+impl<M: ModelParams> ModelParams for Pipeline<M> {
+    fn param_groups(&self) -> Vec<ParamGroup> {
+        self.model.param_groups()
+    }
+}
+
+impl<M, U> SupModel<Matrix<f64>, U> for Pipeline<M>
+    where M: SupModel<Matrix<f64>, U>
+{
+    /// Predicts the outputs after applying the fitted transforms.
+    ///
+    /// The pipeline must be trained before prediction can be made.
+    fn predict(&self, inputs: &Matrix<f64>) -> U {
+        if self.transforms.is_empty() {
+            self.model.predict(inputs)
+        } else {
+            self.model.predict(&self.transform(inputs))
+        }
+    }
+
+    /// Fits each transform in order and trains the model on the
+    /// transformed inputs.
+    fn train(&mut self, inputs: &Matrix<f64>, targets: &U) {
+        let current = self.fit_transforms(inputs);
+        self.model.train(current.as_ref().unwrap_or(inputs), targets);
+    }
+}
+
+impl<M, U> WeightedSupModel<Matrix<f64>, U> for Pipeline<M>
+    where M: WeightedSupModel<Matrix<f64>, U>
+{
+    /// Fits each transform in order and trains the model on the
+    /// transformed inputs with the sample weights.
+    ///
+    /// The transforms themselves are fitted without weights.
+    fn train_weighted(&mut self, inputs: &Matrix<f64>, targets: &U, weights: &Vector<f64>) {
+        let current = self.fit_transforms(inputs);
+        self.model.train_weighted(current.as_ref().unwrap_or(inputs), targets, weights);
+    }
+}
+
+impl<M> Pipeline<M> {
+    /// Fits each transform in order, returning the transformed inputs
+    /// if there are any transforms.
+    fn fit_transforms(&mut self, inputs: &Matrix<f64>) -> Option<Matrix<f64>> {
+        let mut current: Option<Matrix<f64>> = None;
+
+        for t in &mut self.transforms {
+            let next = t.fit_transform(current.as_ref().unwrap_or(inputs));
+            current = Some(next);
+        }
+
+        current
+    }
+}
@@ -3,9 +3,13 @@
 //! Contains implementation of Support Vector Machine
 //! using the [Pegasos training algorithm](http://ttic.uchicago.edu/~nati/Publications/PegasosMPB.pdf).
 //!
-//! The SVM models currently only support binary classification.
+//! The `SVM` model supports binary classification.
 //! The model inputs should be a matrix and the training targets are
-//! in the form of a vector of `-1`s and `1`s.
+//! in the form of a vector of `-1`s and `1`s. With `probability` set
+//! it also estimates class probabilities by Platt scaling.
+//!
+//! The `SVR` model is epsilon-insensitive support vector regression,
+//! trained by sequential minimal optimization of its dual problem.
 //!
 //! # Examples
 //!
@@ -31,18 +35,56 @@
 //! // Hopefully we classified our new point correctly!
 //! assert!(output[0] == 1f64, "Our classifier isn't very good!");
 //! ```
+//!
+//! Regression uses the same kernels.
+//!
+//! ```
+//! use rusty_machine::learning::svm::SVR;
+//! use rusty_machine::learning::toolkit::kernel::SquaredExp;
+//! use rusty_machine::learning::SupModel;
+//!
+//! use rusty_machine::linalg::matrix::Matrix;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! let inputs = Matrix::new(6, 1, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+//! let targets = Vector::new(vec![0.0, 0.8, 0.9, 0.1, -0.8, -1.0]);
+//!
+//! // Errors below 0.1 are not penalized.
+//! let mut svr = SVR::new(SquaredExp::new(1.0, 1.0), 10.0, 0.1);
+//! svr.train(&inputs, &targets);
+//!
+//! let output = svr.predict(&Matrix::new(1, 1, vec![2.0]));
+//! assert!((output[0] - 0.9).abs() < 0.11);
+//! ```
+//!
+//! # Inference only builds
+//!
+//! Training an `SVM` draws random samples and needs the `rand`
+//! feature. Without it a model trained elsewhere can be restored with
+//! `SVM::from_parameters`, and `SVR` can be both trained and restored.
+
 
+use std::cmp;
+use std::f64;
+use std::fmt;
+use std::mem;
+use std::rc::Rc;
 
-use linalg::matrix::Matrix;
+use linalg::matrix::{Matrix, BaseMatrix};
 use linalg::vector::Vector;
 
+use analysis::cross_validation::StratifiedKFold;
 use learning::toolkit::kernel::{Kernel, SquaredExp};
-use learning::SupModel;
+#[cfg(feature = "rand")]
+use learning::toolkit::rand_utils;
+use learning::toolkit::stable;
+use learning::{ApproxEq, ModelParams, ParamGroup, SupModel, params_approx_eq};
 
-use rand;
+#[cfg(feature = "rand")]
 use rand::Rng;
 
 /// Support Vector Machine
+#[derive(Clone)]
 pub struct SVM<K: Kernel> {
     ker: K,
     alpha: Option<Vector<f64>>,
@@ -51,6 +93,8 @@ pub struct SVM<K: Kernel> {
     lambda: f64,
     /// Number of iterations for training.
     pub optim_iters: usize,
+    probability: bool,
+    platt: Option<(f64, f64)>,
 }
 
 /// The default Support Vector Machine.
@@ -60,6 +104,7 @@ pub struct SVM<K: Kernel> {
 /// - ker = SquaredExp::default()
 /// - lambda = 0.3
 /// - optim_iters = 100
+/// - probability = false
 impl Default for SVM<SquaredExp> {
     fn default() -> SVM<SquaredExp> {
         SVM {
@@ -69,6 +114,8 @@ impl Default for SVM<SquaredExp> {
             train_targets: None,
             lambda: 0.3f64,
             optim_iters: 100,
+            probability: false,
+            platt: None,
         }
     }
 }
@@ -94,83 +141,1034 @@ impl<K: Kernel> SVM<K> {
 			train_targets: None,
 			lambda: lambda,
 			optim_iters: 100,
+			probability: false,
+			platt: None,
+		}
+	}
+
+	/// Sets the number of iterations for training.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use rusty_machine::learning::svm::SVM;
+	/// use rusty_machine::learning::toolkit::kernel::Linear;
+	///
+	/// let svm = SVM::new(Linear::default(), 0.3).optim_iters(500);
+	///
+	/// assert_eq!(svm.optim_iters, 500);
+	/// ```
+	pub fn optim_iters(mut self, optim_iters: usize) -> SVM<K> {
+		self.optim_iters = optim_iters;
+		self
+	}
+
+	/// Sets whether training also fits probability estimates.
+	///
+	/// The probabilities are a sigmoid of the decision values, fitted
+	/// by Platt scaling to decision values from 5-fold cross
+	/// validation on the training data. Training takes about six
+	/// times as long, and panics if there are fewer than two
+	/// training points.
+	pub fn probability(mut self, probability: bool) -> SVM<K> {
+		self.probability = probability;
+		self
+	}
+
+	/// Constructs a trained SVM from its parameters.
+	///
+	/// The parameters are laid out as returned by `param_groups`, so
+	/// the training inputs start with a column of ones. This restores
+	/// a trained model without the `rand` feature. The other settings
+	/// take their default values and the model has no probabilities.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use rusty_machine::learning::svm::SVM;
+	/// use rusty_machine::learning::toolkit::kernel::Linear;
+	/// use rusty_machine::learning::SupModel;
+	/// use rusty_machine::linalg::matrix::Matrix;
+	/// use rusty_machine::linalg::vector::Vector;
+	///
+	/// // A single training point at x = 1 with a positive weight.
+	/// let svm = SVM::from_parameters(Linear::default(),
+	///                                Vector::new(vec![1.0]),
+	///                                Matrix::new(1, 2, vec![1.0, 1.0]));
+	///
+	/// let output = svm.predict(&Matrix::new(2, 1, vec![-2.0, 2.0]));
+	/// assert_eq!(*output.data(), vec![-1.0, 1.0]);
+	/// ```
+	///
+	/// # Panics
+	///
+	/// - There is not one coefficient for each row of the inputs.
+	pub fn from_parameters(ker: K,
+						   dual_coefficients: Vector<f64>,
+						   train_inputs: Matrix<f64>)
+						   -> SVM<K> {
+		assert!(dual_coefficients.size() == train_inputs.rows(),
+				"Number of coefficients does not match the training inputs.");
+
+		let lambda = 0.3f64;
+		let n = train_inputs.rows();
+		SVM {
+			ker: ker,
+			alpha: Some(dual_coefficients * lambda),
+			train_inputs: Some(train_inputs),
+			train_targets: Some(Vector::ones(n)),
+			lambda: lambda,
+			optim_iters: 100,
+			probability: false,
+			platt: None,
+		}
+	}
+
+	/// The signed distances of the inputs from the separating plane.
+	///
+	/// The predicted classes are the signs of the decision values.
+	///
+	/// # Panics
+	///
+	/// - The model has not been trained.
+	pub fn decision_function<M: BaseMatrix<f64>>(&self, inputs: &M) -> Vector<f64> {
+		if let (Some(alpha),
+				Some(train_inputs),
+				Some(train_targets)) = (&self.alpha, &self.train_inputs, &self.train_targets) {
+			self.decision(alpha, train_inputs, train_targets, inputs)
+		} else {
+			panic!("Model has not been trained.");
+		}
+	}
+
+	/// The probability of the positive class for each input.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use rusty_machine::analysis::curves::calibration_curve;
+	/// use rusty_machine::learning::svm::SVM;
+	/// use rusty_machine::learning::toolkit::kernel::Linear;
+	/// use rusty_machine::learning::SupModel;
+	/// use rusty_machine::linalg::matrix::Matrix;
+	/// use rusty_machine::linalg::vector::Vector;
+	///
+	/// let inputs = Matrix::new(10, 1, vec![-5., -4., -3., -2., -1., 1., 2., 3., 4., 5.]);
+	/// let targets = Vector::new(vec![-1., -1., -1., -1., -1., 1., 1., 1., 1., 1.]);
+	///
+	/// let mut svm = SVM::new(Linear::default(), 0.3).optim_iters(500).probability(true);
+	/// svm.train(&inputs, &targets);
+	///
+	/// let probs = svm.predict_proba(&inputs);
+	/// assert!(probs[0] < 0.5 && probs[9] > 0.5);
+	///
+	/// // The probabilities can be checked against the labels.
+	/// let labels = targets.data().iter().map(|&t| if t > 0. { 1. } else { 0. }).collect::<Vec<_>>();
+	/// let curve = calibration_curve(probs.data(), &labels, 2);
+	/// ```
+	///
+	/// # Panics
+	///
+	/// - The model was not trained with probabilities enabled.
+	pub fn predict_proba<M: BaseMatrix<f64>>(&self, inputs: &M) -> Vector<f64> {
+		match self.platt {
+			Some((a, b)) => {
+				self.decision_function(inputs)
+					.data()
+					.iter()
+					.map(|d| stable::sigmoid(-(a * d + b)))
+					.collect()
+			}
+			None => panic!("Model was not trained with probabilities."),
 		}
 	}
+
+	/// The decision values of the inputs for a trained model.
+	fn decision<M: BaseMatrix<f64>>(&self,
+									alpha: &Vector<f64>,
+									train_inputs: &Matrix<f64>,
+									train_targets: &Vector<f64>,
+									inputs: &M)
+									-> Vector<f64> {
+		let weight_vec = alpha.elemul(train_targets) / self.lambda;
+		let mut full_row = vec![1f64; inputs.cols() + 1];
+
+		(0..inputs.rows())
+			.map(|i| {
+				full_row[1..].copy_from_slice(inputs.row(i));
+				(0..train_inputs.rows())
+					.map(|j| self.ker.kernel(&full_row, train_inputs.row(j)) * weight_vec[j])
+					.sum::<f64>()
+			})
+			.collect()
+	}
+
+	/// The Pegasos weights of the training points, whose inputs
+	/// include the column of ones.
+	#[cfg(feature = "rand")]
+	fn pegasos(&self, full_inputs: &Matrix<f64>, targets: &Vector<f64>) -> Vector<f64> {
+		let n = full_inputs.rows();
+
+		let mut rng = rand_utils::rng();
+
+		let mut alpha = vec![0f64; n];
+
+		let m = full_inputs.cols();
+
+		for t in 0..self.optim_iters {
+			let i = rng.gen_range(0, n);
+			let mut sum = 0f64;
+			for j in 0..n {
+				sum += alpha[j] * targets[j] *
+					   self.ker.kernel(&full_inputs.data()[i * m..(i + 1) * m],
+									   &full_inputs.data()[j * m..(j + 1) * m]);
+			}
+			sum *= targets[i] / (self.lambda * (t as f64));
+
+			if sum < 1f64 {
+				alpha[i] += 1f64;
+			}
+		}
+
+		Vector::new(alpha) / (self.optim_iters as f64)
+	}
+
+	/// Pegasos samples the training points at random.
+	#[cfg(not(feature = "rand"))]
+	fn pegasos(&self, _full_inputs: &Matrix<f64>, _targets: &Vector<f64>) -> Vector<f64> {
+		panic!("Training an SVM requires the `rand` feature.");
+	}
+
+	/// Fits Platt scaling to decision values from cross validation.
+	fn fit_platt(&self, full_inputs: &Matrix<f64>, targets: &Vector<f64>) -> (f64, f64) {
+		let n = full_inputs.rows();
+		assert!(n > 1, "Fitting probabilities needs at least two training points.");
+		let mut decisions = vec![0f64; n];
+
+		for (train, test) in StratifiedKFold::new(targets.data(), cmp::min(5, n)) {
+			let fold_inputs = full_inputs.select_rows(&train);
+			let fold_targets = targets.select(&train);
+			let alpha = self.pegasos(&fold_inputs, &fold_targets);
+
+			// The decision values add the column of ones back.
+			let test_inputs = full_inputs.select(&test, &(1..full_inputs.cols()).collect::<Vec<_>>());
+			let values = self.decision(&alpha, &fold_inputs, &fold_targets, &test_inputs);
+			for (&i, &v) in test.iter().zip(values.data()) {
+				decisions[i] = v;
+			}
+		}
+
+		platt_scaling(&decisions, targets.data())
+	}
 }
 
-impl<K: Kernel> SVM<K> {
+impl<K: Kernel + fmt::Debug> fmt::Debug for SVM<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SVM")
+            .field("ker", &self.ker)
+            .field("lambda", &self.lambda)
+            .field("optim_iters", &self.optim_iters)
+            .field("probability", &self.probability)
+            .field("trained", &self.alpha.is_some())
+            .finish()
+    }
+}
 
-    /// Construct a kernel matrix
-    fn ker_mat(&self, m1: &Matrix<f64>, m2: &Matrix<f64>) -> Matrix<f64> {
-        assert_eq!(m1.cols(), m2.cols());
-        let cols = m1.cols();
+/// Compares the training data and the fitted weights of the data.
+impl<K: Kernel> ApproxEq for SVM<K> {
+    fn approx_eq(&self, other: &SVM<K>, tol: f64) -> bool {
+        params_approx_eq(self.alpha.as_ref().map(|a| a.data().as_slice()),
+                         other.alpha.as_ref().map(|a| a.data().as_slice()),
+                         tol) &&
+        params_approx_eq(self.train_inputs.as_ref().map(|x| x.data().as_slice()),
+                         other.train_inputs.as_ref().map(|x| x.data().as_slice()),
+                         tol) &&
+        params_approx_eq(self.train_targets.as_ref().map(|y| y.data().as_slice()),
+                         other.train_targets.as_ref().map(|y| y.data().as_slice()),
+                         tol)
+    }
+}
 
-        let dim1 = m1.rows();
-        let dim2 = m2.rows();
+/// The `"train_inputs"` and the weight of each, the
+/// `"dual_coefficients"`.
+impl<K: Kernel> ModelParams for SVM<K> {
+    fn param_groups(&self) -> Vec<ParamGroup> {
+        if let (Some(alpha), Some(train_inputs), Some(train_targets)) =
+               (&self.alpha, &self.train_inputs, &self.train_targets) {
+            let weights = alpha.elemul(train_targets) / self.lambda;
+            vec![ParamGroup::new("dual_coefficients",
+                                 Matrix::new(weights.size(), 1, weights.into_vec())),
+                 ParamGroup::new("train_inputs", train_inputs.clone())]
+        } else {
+            Vec::new()
+        }
+    }
+}
 
-        let mut ker_data = Vec::with_capacity(dim1 * dim2);
+/// Train the model using the Pegasos algorithm and
+/// predict the model output from new data.
+impl<K: Kernel, M: BaseMatrix<f64>> SupModel<M, Vector<f64>> for SVM<K> {
+    fn predict(&self, inputs: &M) -> Vector<f64> {
+        Vector::new(self.decision_function(inputs).data().iter().map(|d| d.signum()).collect())
+    }
+
+    fn train(&mut self, inputs: &M, targets: &Vector<f64>) {
+        let ones = Matrix::<f64>::ones(inputs.rows(), 1);
+        let full_inputs = ones.hcat(&inputs.to_matrix());
+
+        self.platt = if self.probability {
+            Some(self.fit_platt(&full_inputs, targets))
+        } else {
+            None
+        };
+        self.alpha = Some(self.pegasos(&full_inputs, targets));
+        self.train_inputs = Some(full_inputs);
+        self.train_targets = Some(targets.clone());
+    }
+}
+
+/// Fits the sigmoid `1 / (1 + exp(a f + b))` of the decision values f
+/// to the targets of -1 or 1.
+///
+/// Uses the Newton method with backtracking of Lin, Lin and Weng (2007)
+/// "A Note on Platt's Probabilistic Outputs for Support Vector
+/// Machines". The targets are smoothed towards the class priors so the
+/// fit does not overfit separable data.
+fn platt_scaling(decisions: &[f64], targets: &[f64]) -> (f64, f64) {
+    let n_pos = targets.iter().filter(|&&t| t > 0f64).count() as f64;
+    let n_neg = targets.len() as f64 - n_pos;
+
+    let hi = (n_pos + 1f64) / (n_pos + 2f64);
+    let lo = 1f64 / (n_neg + 2f64);
+    let probs = targets.iter().map(|&t| if t > 0f64 { hi } else { lo }).collect::<Vec<_>>();
+
+    let objective = |a: f64, b: f64| {
+        decisions.iter()
+            .zip(&probs)
+            .map(|(d, &t)| stable::cross_entropy_with_logits(-(a * d + b), t))
+            .sum::<f64>()
+    };
+
+    let (mut a, mut b) = (0f64, ((n_neg + 1f64) / (n_pos + 1f64)).ln());
+    let mut value = objective(a, b);
+
+    for _ in 0..100 {
+        // The gradient and hessian, with a small ridge on the diagonal.
+        let (mut h11, mut h22, mut h21) = (1e-12, 1e-12, 0f64);
+        let (mut g1, mut g2) = (0f64, 0f64);
+        for (d, t) in decisions.iter().zip(&probs) {
+            let p = stable::sigmoid(-(a * d + b));
+            let d2 = p * (1f64 - p);
+            h11 += d * d * d2;
+            h22 += d2;
+            h21 += d * d2;
+            g1 += d * (t - p);
+            g2 += t - p;
+        }
+
+        if g1.abs() < 1e-5 && g2.abs() < 1e-5 {
+            break;
+        }
+
+        let det = h11 * h22 - h21 * h21;
+        let delta_a = -(h22 * g1 - h21 * g2) / det;
+        let delta_b = -(-h21 * g1 + h11 * g2) / det;
+        let descent = g1 * delta_a + g2 * delta_b;
 
-        for i in 0..dim1 {
-            for j in 0..dim2 {
-                ker_data.push(self.ker.kernel(&m1.data()[i * cols..(i + 1) * cols],
-                                              &m2.data()[j * cols..(j + 1) * cols]));
+        let mut step = 1f64;
+        while step >= 1e-10 {
+            let (new_a, new_b) = (a + step * delta_a, b + step * delta_b);
+            let new_value = objective(new_a, new_b);
+            if new_value < value + 1e-4 * step * descent {
+                a = new_a;
+                b = new_b;
+                value = new_value;
+                break;
             }
+            step /= 2f64;
         }
 
-        Matrix::new(dim1, dim2, ker_data)
+        if step < 1e-10 {
+            break;
+        }
     }
+
+    (a, b)
 }
 
-/// Train the model using the Pegasos algorithm and
-/// predict the model output from new data.
-impl<K: Kernel> SupModel<Matrix<f64>, Vector<f64>> for SVM<K> {
-    fn predict(&self, inputs: &Matrix<f64>) -> Vector<f64> {
-    	let ones = Matrix::<f64>::ones(inputs.rows(), 1);
-        let full_inputs = ones.hcat(inputs);
+/// Support Vector Regression
+///
+/// Epsilon-insensitive regression, in which errors smaller than epsilon
+/// are not penalized and larger errors are penalized linearly with
+/// weight `c`. The predictions are a kernel expansion over the
+/// support vectors, the training points whose errors are at least
+/// epsilon, plus an intercept.
+#[derive(Clone)]
+pub struct SVR<K: Kernel> {
+    ker: K,
+    c: f64,
+    epsilon: f64,
+    /// The tolerance of the optimality conditions for training.
+    pub tol: f64,
+    /// The maximum number of solver iterations for training.
+    pub max_iters: usize,
+    /// Whether the solver shrinks the working set during training.
+    pub shrinking: bool,
+    /// The memory budget of the kernel row cache in megabytes.
+    pub cache_size: usize,
+    coefs: Option<Vector<f64>>,
+    support_vectors: Option<Matrix<f64>>,
+    intercept: f64,
+}
 
-        if let (&Some(ref alpha),
-                &Some(ref train_inputs),
-                &Some(ref train_targets)) = (&self.alpha, &self.train_inputs, &self.train_targets) {
-            let ker_mat = self.ker_mat(&full_inputs, train_inputs);
-            let weight_vec = alpha.elemul(train_targets) / self.lambda;
+/// The default Support Vector Regression.
+///
+/// The defaults are:
+///
+/// - ker = SquaredExp::default()
+/// - c = 1
+/// - epsilon = 0.1
+/// - tol = 1e-3
+/// - max_iters = 100000
+/// - shrinking = true
+/// - cache_size = 100
+impl Default for SVR<SquaredExp> {
+    fn default() -> SVR<SquaredExp> {
+        SVR::new(SquaredExp::default(), 1f64, 0.1)
+    }
+}
 
-            let plane_dist = ker_mat * weight_vec;
+impl<K: Kernel> SVR<K> {
+    /// Constructs an untrained SVR with the kernel, the penalty `c` of
+    /// the errors and the width `epsilon` of the insensitive tube.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::svm::SVR;
+    /// use rusty_machine::learning::toolkit::kernel::Linear;
+    ///
+    /// let _ = SVR::new(Linear::default(), 1.0, 0.1);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - `c` is not positive.
+    /// - `epsilon` is negative.
+    pub fn new(ker: K, c: f64, epsilon: f64) -> SVR<K> {
+        assert!(c > 0f64, "The penalty c must be positive.");
+        assert!(epsilon >= 0f64, "Epsilon must not be negative.");
 
-            plane_dist.apply(&|d| d.signum())
+        SVR {
+            ker: ker,
+            c: c,
+            epsilon: epsilon,
+            tol: 1e-3,
+            max_iters: 100000,
+            shrinking: true,
+            cache_size: 100,
+            coefs: None,
+            support_vectors: None,
+            intercept: 0f64,
         }
-        else {
-        	panic!("Model has not been trained.");
+    }
+
+    /// Sets the tolerance of the optimality conditions for training.
+    pub fn tol(mut self, tol: f64) -> SVR<K> {
+        self.tol = tol;
+        self
+    }
+
+    /// Sets the maximum number of solver iterations for training.
+    pub fn max_iters(mut self, max_iters: usize) -> SVR<K> {
+        self.max_iters = max_iters;
+        self
+    }
+
+    /// Sets whether the solver shrinks the working set.
+    ///
+    /// Shrinking leaves out the variables which are likely to stay at
+    /// their bounds, which speeds up training on large data sets
+    /// without changing the solution.
+    pub fn shrinking(mut self, shrinking: bool) -> SVR<K> {
+        self.shrinking = shrinking;
+        self
+    }
+
+    /// Sets the memory budget of the kernel row cache in megabytes.
+    ///
+    /// The solver reuses the cached rows of the kernel matrix rather
+    /// than recomputing them. Two rows are always kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::svm::SVR;
+    /// use rusty_machine::learning::toolkit::kernel::SquaredExp;
+    ///
+    /// let svr = SVR::new(SquaredExp::default(), 1.0, 0.1).cache_size(500).shrinking(false);
+    ///
+    /// assert_eq!(svr.cache_size, 500);
+    /// ```
+    pub fn cache_size(mut self, cache_size: usize) -> SVR<K> {
+        self.cache_size = cache_size;
+        self
+    }
+
+    /// The support vectors of the trained model.
+    ///
+    /// Returns None if the model has not been trained.
+    pub fn support_vectors(&self) -> Option<&Matrix<f64>> {
+        self.support_vectors.as_ref()
+    }
+
+    /// Constructs a trained SVR from its parameters.
+    ///
+    /// The parameters are those returned by `param_groups`. The other
+    /// settings take their default values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::svm::SVR;
+    /// use rusty_machine::learning::toolkit::kernel::Linear;
+    /// use rusty_machine::learning::SupModel;
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::vector::Vector;
+    ///
+    /// // The line 2x + 1 from a single support vector at x = 1.
+    /// let svr = SVR::from_parameters(Linear::default(),
+    ///                                1.0,
+    ///                                Vector::new(vec![2.0]),
+    ///                                Matrix::new(1, 1, vec![1.0]));
+    ///
+    /// let output = svr.predict(&Matrix::new(1, 1, vec![3.0]));
+    /// assert_eq!(output[0], 7.0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - There is not one coefficient for each support vector.
+    pub fn from_parameters(ker: K,
+                           intercept: f64,
+                           dual_coefficients: Vector<f64>,
+                           support_vectors: Matrix<f64>)
+                           -> SVR<K> {
+        assert!(dual_coefficients.size() == support_vectors.rows(),
+                "Number of coefficients does not match the support vectors.");
+
+        SVR {
+            coefs: Some(dual_coefficients),
+            support_vectors: Some(support_vectors),
+            intercept: intercept,
+            ..SVR::new(ker, 1f64, 0.1)
         }
     }
+}
+
+impl<K: Kernel + fmt::Debug> fmt::Debug for SVR<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SVR")
+            .field("ker", &self.ker)
+            .field("c", &self.c)
+            .field("epsilon", &self.epsilon)
+            .field("tol", &self.tol)
+            .field("max_iters", &self.max_iters)
+            .field("shrinking", &self.shrinking)
+            .field("cache_size", &self.cache_size)
+            .field("trained", &self.coefs.is_some())
+            .finish()
+    }
+}
+
+/// Compares the support vectors, their weights and the intercepts.
+impl<K: Kernel> ApproxEq for SVR<K> {
+    fn approx_eq(&self, other: &SVR<K>, tol: f64) -> bool {
+        params_approx_eq(self.coefs.as_ref().map(|a| a.data().as_slice()),
+                         other.coefs.as_ref().map(|a| a.data().as_slice()),
+                         tol) &&
+        params_approx_eq(self.support_vectors.as_ref().map(|x| x.data().as_slice()),
+                         other.support_vectors.as_ref().map(|x| x.data().as_slice()),
+                         tol) &&
+        (self.intercept - other.intercept).abs() <= tol
+    }
+}
+
+/// The `"intercept"`, the `"support_vectors"` and the weight of each,
+/// the `"dual_coefficients"`.
+impl<K: Kernel> ModelParams for SVR<K> {
+    fn param_groups(&self) -> Vec<ParamGroup> {
+        if let (Some(coefs), Some(support_vectors)) = (&self.coefs, &self.support_vectors) {
+            vec![ParamGroup::new("intercept", Matrix::new(1, 1, vec![self.intercept])),
+                 ParamGroup::new("dual_coefficients",
+                                 Matrix::new(coefs.size(), 1, coefs.data().clone())),
+                 ParamGroup::new("support_vectors", support_vectors.clone())]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Train the model by sequential minimal optimization and predict
+/// the model output from new data.
+impl<K: Kernel, M: BaseMatrix<f64>> SupModel<M, Vector<f64>> for SVR<K> {
+    fn predict(&self, inputs: &M) -> Vector<f64> {
+        if let (Some(coefs), Some(support_vectors)) = (&self.coefs, &self.support_vectors) {
+            (0..inputs.rows())
+                .map(|i| {
+                    (0..support_vectors.rows())
+                        .map(|j| coefs[j] * self.ker.kernel(inputs.row(i), support_vectors.row(j)))
+                        .sum::<f64>() + self.intercept
+                })
+                .collect()
+        } else {
+            panic!("Model has not been trained.");
+        }
+    }
+
+    /// Trains the model on the inputs and targets.
+    ///
+    /// # Panics
+    ///
+    /// - The inputs and targets have different lengths.
+    fn train(&mut self, inputs: &M, targets: &Vector<f64>) {
+        assert!(inputs.rows() == targets.size(),
+                "Inputs and targets have different lengths.");
 
-    fn train(&mut self, inputs: &Matrix<f64>, targets: &Vector<f64>) {
         let n = inputs.rows();
+        let inputs = inputs.to_matrix();
 
-        let mut rng = rand::thread_rng();
+        // The dual has a weight for the points above the tube followed
+        // by a weight for the points below it.
+        let problem = DualProblem {
+            signs: (0..2 * n).map(|t| if t < n { 1f64 } else { -1f64 }).collect(),
+            linear: (0..n)
+                .map(|i| self.epsilon - targets[i])
+                .chain((0..n).map(|i| self.epsilon + targets[i]))
+                .collect(),
+            points: (0..n).chain(0..n).collect(),
+        };
 
-        let mut alpha = vec![0f64; n];
+        let settings = SmoSettings {
+            c: self.c,
+            tol: self.tol,
+            max_iters: self.max_iters,
+            shrinking: self.shrinking,
+        };
+        let rows = KernelRows::new(&self.ker, &inputs, self.cache_size);
+        let (weights, rho) = solve_smo(&problem, rows, &settings);
 
-        let ones = Matrix::<f64>::ones(inputs.rows(), 1);
-        let full_inputs = ones.hcat(inputs);
-        let m = full_inputs.cols();
-
-        for t in 0..self.optim_iters {
-            let i = rng.gen_range(0, n);
-            let mut sum = 0f64;
-            for j in 0..n {
-                sum += alpha[j] * targets[j] *
-                       self.ker.kernel(&full_inputs.data()[i * m..(i + 1) * m],
-                                       &full_inputs.data()[j * m..(j + 1) * m]);
+        let support = (0..n).filter(|&i| weights[i] != weights[i + n]).collect::<Vec<_>>();
+        self.coefs = Some(support.iter().map(|&i| weights[i] - weights[i + n]).collect());
+        self.support_vectors = Some(inputs.select_rows(&support));
+        self.intercept = -rho;
+    }
+}
+
+/// The dual problem of a support vector machine.
+///
+/// Minimize `β'Qβ / 2 + p'β` subject to `0 ≤ β ≤ C` and `y'β` fixed,
+/// where `Q[s][t] = y[s] y[t] K(x[s], x[t])`. Each variable is tied to a
+/// training point, and a point may have more than one variable.
+struct DualProblem {
+    /// The sign y of each variable.
+    signs: Vec<f64>,
+    /// The linear term p.
+    linear: Vec<f64>,
+    /// The training point of each variable.
+    points: Vec<usize>,
+}
+
+/// Computes the rows of the kernel matrix of the training points.
+///
+/// Rows are cached up to a memory budget, evicting the least recently
+/// used row when the cache is full. At least two rows are kept, the
+/// pair being optimized.
+struct KernelRows<'a, K: 'a> {
+    ker: &'a K,
+    inputs: &'a Matrix<f64>,
+    cache: Vec<Option<Rc<Vec<f64>>>>,
+    last_used: Vec<usize>,
+    capacity: usize,
+    cached: usize,
+    clock: usize,
+}
+
+impl<'a, K: Kernel> KernelRows<'a, K> {
+    fn new(ker: &'a K, inputs: &'a Matrix<f64>, cache_size: usize) -> KernelRows<'a, K> {
+        let n = inputs.rows();
+        let row_bytes = cmp::max(n, 1) * mem::size_of::<f64>();
+
+        KernelRows {
+            ker: ker,
+            inputs: inputs,
+            cache: vec![None; n],
+            last_used: vec![0; n],
+            capacity: cmp::max(cache_size * 1024 * 1024 / row_bytes, 2),
+            cached: 0,
+            clock: 0,
+        }
+    }
+
+    /// The kernel between a training point and every training point.
+    fn row(&mut self, i: usize) -> Rc<Vec<f64>> {
+        self.clock += 1;
+        self.last_used[i] = self.clock;
+
+        if let Some(ref row) = self.cache[i] {
+            return row.clone();
+        }
+
+        if self.cached == self.capacity {
+            let oldest = (0..self.cache.len())
+                .filter(|&j| self.cache[j].is_some())
+                .min_by_key(|&j| self.last_used[j])
+                .unwrap();
+            self.cache[oldest] = None;
+            self.cached -= 1;
+        }
+
+        let x = self.inputs.row(i);
+        let row = Rc::new((0..self.inputs.rows())
+            .map(|j| self.ker.kernel(x, self.inputs.row(j)))
+            .collect::<Vec<_>>());
+        self.cache[i] = Some(row.clone());
+        self.cached += 1;
+        row
+    }
+
+    /// The kernel of each training point with itself.
+    fn diag(&self) -> Vec<f64> {
+        (0..self.inputs.rows()).map(|i| self.ker.kernel(self.inputs.row(i), self.inputs.row(i))).collect()
+    }
+}
+
+/// Smallest curvature used along a working pair direction.
+const TAU: f64 = 1e-12;
+
+/// The settings of the SMO solver.
+struct SmoSettings {
+    c: f64,
+    tol: f64,
+    max_iters: usize,
+    shrinking: bool,
+}
+
+/// The state of the SMO solver.
+struct Smo<'a, 'b, K: 'a> {
+    problem: &'b DualProblem,
+    rows: KernelRows<'a, K>,
+    c: f64,
+    q_diag: Vec<f64>,
+    alpha: Vec<f64>,
+    grad: Vec<f64>,
+    /// The variables which may still change. Shrinking removes the
+    /// variables which are likely to stay at their bounds.
+    active: Vec<usize>,
+}
+
+impl<'a, 'b, K: Kernel> Smo<'a, 'b, K> {
+    fn is_upper(&self, t: usize) -> bool {
+        self.alpha[t] >= self.c
+    }
+
+    fn is_lower(&self, t: usize) -> bool {
+        self.alpha[t] <= 0f64
+    }
+
+    /// Whether the variable can move up the direction of its sign.
+    fn can_increase(&self, t: usize) -> bool {
+        if self.problem.signs[t] > 0f64 { !self.is_upper(t) } else { !self.is_lower(t) }
+    }
+
+    /// Whether the variable can move down the direction of its sign.
+    fn can_decrease(&self, t: usize) -> bool {
+        if self.problem.signs[t] > 0f64 { !self.is_lower(t) } else { !self.is_upper(t) }
+    }
+
+    /// The largest violations of the optimality conditions over the
+    /// active variables, by increasing and by decreasing variables.
+    fn max_violations(&self) -> (f64, f64) {
+        let y = &self.problem.signs;
+        let (mut g_max, mut g_max2) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for &t in &self.active {
+            if self.can_increase(t) {
+                g_max = g_max.max(-y[t] * self.grad[t]);
             }
-            sum *= targets[i] / (self.lambda * (t as f64));
+            if self.can_decrease(t) {
+                g_max2 = g_max2.max(y[t] * self.grad[t]);
+            }
+        }
+        (g_max, g_max2)
+    }
+
+    /// Chooses the working pair with second order information.
+    ///
+    /// Returns None if the active variables are optimal within the
+    /// tolerance.
+    fn select_working_set(&mut self, tol: f64) -> Option<(usize, usize)> {
+        let y = &self.problem.signs;
+        let points = &self.problem.points;
 
-            if sum < 1f64 {
-                alpha[i] = alpha[i] + 1f64;
+        // The variable which most violates the optimality conditions.
+        let mut g_max = f64::NEG_INFINITY;
+        let mut i = None;
+        for &t in &self.active {
+            if self.can_increase(t) && -y[t] * self.grad[t] >= g_max {
+                g_max = -y[t] * self.grad[t];
+                i = Some(t);
             }
         }
+        let i = i?;
 
-        self.alpha = Some(Vector::new(alpha) / (self.optim_iters as f64));
-        self.train_inputs = Some(full_inputs);
-        self.train_targets = Some(targets.clone());
+        // The partner which decreases the objective the most.
+        let k_i = self.rows.row(points[i]);
+        let mut g_max2 = f64::NEG_INFINITY;
+        let mut j = None;
+        let mut obj_diff_min = f64::INFINITY;
+        for &t in &self.active {
+            if !self.can_decrease(t) {
+                continue;
+            }
+
+            let y_grad = y[t] * self.grad[t];
+            g_max2 = g_max2.max(y_grad);
+
+            let grad_diff = g_max + y_grad;
+            if grad_diff > 0f64 {
+                let quad = (self.q_diag[i] + self.q_diag[t] - 2f64 * k_i[points[t]]).max(TAU);
+                let obj_diff = -grad_diff * grad_diff / quad;
+                if obj_diff <= obj_diff_min {
+                    obj_diff_min = obj_diff;
+                    j = Some(t);
+                }
+            }
+        }
+
+        match j {
+            Some(j) if g_max + g_max2 >= tol => Some((i, j)),
+            _ => None,
+        }
+    }
+
+    /// Optimizes the pair of variables and updates the active gradients.
+    fn update_pair(&mut self, i: usize, j: usize) {
+        let y = &self.problem.signs;
+        let points = &self.problem.points;
+        let c = self.c;
+        let alpha = &mut self.alpha;
+        let grad = &mut self.grad;
+
+        let k_i = self.rows.row(points[i]);
+        let k_j = self.rows.row(points[j]);
+        let q_ij = y[i] * y[j] * k_i[points[j]];
+        let (old_i, old_j) = (alpha[i], alpha[j]);
+
+        if y[i] != y[j] {
+            let quad = (self.q_diag[i] + self.q_diag[j] + 2f64 * q_ij).max(TAU);
+            let delta = (-grad[i] - grad[j]) / quad;
+            let diff = alpha[i] - alpha[j];
+            alpha[i] += delta;
+            alpha[j] += delta;
+
+            if diff > 0f64 {
+                if alpha[j] < 0f64 {
+                    alpha[j] = 0f64;
+                    alpha[i] = diff;
+                }
+            } else if alpha[i] < 0f64 {
+                alpha[i] = 0f64;
+                alpha[j] = -diff;
+            }
+
+            if diff > 0f64 {
+                if alpha[i] > c {
+                    alpha[i] = c;
+                    alpha[j] = c - diff;
+                }
+            } else if alpha[j] > c {
+                alpha[j] = c;
+                alpha[i] = c + diff;
+            }
+        } else {
+            let quad = (self.q_diag[i] + self.q_diag[j] - 2f64 * q_ij).max(TAU);
+            let delta = (grad[i] - grad[j]) / quad;
+            let sum = alpha[i] + alpha[j];
+            alpha[i] -= delta;
+            alpha[j] += delta;
+
+            if sum > c {
+                if alpha[i] > c {
+                    alpha[i] = c;
+                    alpha[j] = sum - c;
+                }
+            } else if alpha[j] < 0f64 {
+                alpha[j] = 0f64;
+                alpha[i] = sum;
+            }
+
+            if sum > c {
+                if alpha[j] > c {
+                    alpha[j] = c;
+                    alpha[i] = sum - c;
+                }
+            } else if alpha[i] < 0f64 {
+                alpha[i] = 0f64;
+                alpha[j] = sum;
+            }
+        }
+
+        let (delta_i, delta_j) = (y[i] * (alpha[i] - old_i), y[j] * (alpha[j] - old_j));
+        for &t in &self.active {
+            let p = points[t];
+            grad[t] += y[t] * (k_i[p] * delta_i + k_j[p] * delta_j);
+        }
+    }
+
+    /// Removes the variables at a bound whose gradients push them
+    /// further past it from the active set.
+    ///
+    /// Near the solution the gradients are recomputed and every
+    /// variable is reactivated once, in case any were shrunk too early.
+    fn shrink(&mut self, tol: f64, unshrunk: &mut bool) {
+        let (g_max, g_max2) = self.max_violations();
+
+        if !*unshrunk && g_max + g_max2 <= tol * 10f64 {
+            *unshrunk = true;
+            self.unshrink();
+        }
+
+        let y = &self.problem.signs;
+        let mut active = Vec::with_capacity(self.active.len());
+        for &t in &self.active {
+            let shrunk = if self.is_upper(t) {
+                if y[t] > 0f64 { -self.grad[t] > g_max } else { -self.grad[t] > g_max2 }
+            } else if self.is_lower(t) {
+                if y[t] > 0f64 { self.grad[t] > g_max2 } else { self.grad[t] > g_max }
+            } else {
+                false
+            };
+
+            if !shrunk {
+                active.push(t);
+            }
+        }
+        self.active = active;
+    }
+
+    /// Recomputes the gradients of the inactive variables and makes
+    /// every variable active.
+    fn unshrink(&mut self) {
+        let l = self.alpha.len();
+        if self.active.len() == l {
+            return;
+        }
+
+        let y = &self.problem.signs;
+        let points = &self.problem.points;
+        let mut is_active = vec![false; l];
+        for &t in &self.active {
+            is_active[t] = true;
+        }
+        let inactive = (0..l).filter(|&t| !is_active[t]).collect::<Vec<_>>();
+
+        for &t in &inactive {
+            self.grad[t] = self.problem.linear[t];
+        }
+        for s in 0..l {
+            if self.alpha[s] > 0f64 {
+                let k_s = self.rows.row(points[s]);
+                for &t in &inactive {
+                    self.grad[t] += y[t] * y[s] * self.alpha[s] * k_s[points[t]];
+                }
+            }
+        }
+
+        self.active = (0..l).collect();
+    }
+
+    /// The offset ρ, which averages the free variables, or falls
+    /// between the bounds when every variable is at a bound.
+    fn rho(&self) -> f64 {
+        let y = &self.problem.signs;
+        let (mut upper, mut lower) = (f64::INFINITY, f64::NEG_INFINITY);
+        let (mut free_sum, mut n_free) = (0f64, 0usize);
+        for (t, &y_t) in y.iter().enumerate() {
+            let y_grad = y_t * self.grad[t];
+            if self.is_upper(t) {
+                if y_t < 0f64 { upper = upper.min(y_grad) } else { lower = lower.max(y_grad) }
+            } else if self.is_lower(t) {
+                if y_t > 0f64 { upper = upper.min(y_grad) } else { lower = lower.max(y_grad) }
+            } else {
+                free_sum += y_grad;
+                n_free += 1;
+            }
+        }
+
+        if n_free > 0 { free_sum / n_free as f64 } else { (upper + lower) / 2f64 }
+    }
+}
+
+/// Solves the dual problem by sequential minimal optimization.
+///
+/// Each iteration optimizes the pair of variables chosen with second
+/// order information, as in Fan, Chen and Lin (2005) "Working Set
+/// Selection Using Second Order Information for Training Support
+/// Vector Machines", until the optimality conditions hold within the
+/// tolerance or the iterations run out. With shrinking, the variables
+/// which are likely to stay at their bounds are periodically left out
+/// of the working set selection and gradient updates, as in LIBSVM.
+///
+/// Returns the variables and the offset ρ, the negated intercept.
+fn solve_smo<K: Kernel>(problem: &DualProblem,
+                        rows: KernelRows<K>,
+                        settings: &SmoSettings)
+                        -> (Vec<f64>, f64) {
+    let l = problem.signs.len();
+    let kernel_diag = rows.diag();
+
+    let mut smo = Smo {
+        problem: problem,
+        rows: rows,
+        c: settings.c,
+        q_diag: problem.points.iter().map(|&p| kernel_diag[p]).collect(),
+        alpha: vec![0f64; l],
+        grad: problem.linear.clone(),
+        active: (0..l).collect(),
+    };
+
+    let shrink_interval = cmp::min(l, 1000);
+    let mut counter = shrink_interval;
+    let mut unshrunk = false;
+
+    for _ in 0..settings.max_iters {
+        if settings.shrinking {
+            counter -= 1;
+            if counter == 0 {
+                counter = shrink_interval;
+                smo.shrink(settings.tol, &mut unshrunk);
+            }
+        }
+
+        let pair = match smo.select_working_set(settings.tol) {
+            Some(pair) => pair,
+            None if smo.active.len() == l => break,
+            None => {
+                // Check the shrunk variables before stopping.
+                smo.unshrink();
+                match smo.select_working_set(settings.tol) {
+                    Some(pair) => {
+                        counter = 1;
+                        pair
+                    }
+                    None => break,
+                }
+            }
+        };
+        smo.update_pair(pair.0, pair.1);
     }
+
+    smo.unshrink();
+    let rho = smo.rho();
+    (smo.alpha, rho)
 }
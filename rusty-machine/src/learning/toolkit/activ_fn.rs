@@ -8,6 +8,11 @@
 //! You can also create your own custom activation Functions for use in your models.
 //! Just create a unit struct implementing the ActivationFunc trait.
 
+#[cfg(not(feature = "std"))]
+use libnum::Float;
+
+use learning::toolkit::stable;
+
 /// Trait for activation functions in models.
 pub trait ActivationFunc {
     /// The activation function.
@@ -21,6 +26,7 @@ pub trait ActivationFunc {
 }
 
 /// Sigmoid activation function.
+#[derive(Clone, Copy, Debug)]
 pub struct Sigmoid;
 
 impl ActivationFunc for Sigmoid {
@@ -28,7 +34,7 @@ impl ActivationFunc for Sigmoid {
     ///
     /// Returns 1 / ( 1 + e^-t).
     fn func(x: f64) -> f64 {
-        1.0 / (1.0 + (-x).exp())
+        stable::sigmoid(x)
     }
 
     /// Gradient of sigmoid function.
@@ -44,6 +50,7 @@ impl ActivationFunc for Sigmoid {
 }
 
 /// Linear activation function.
+#[derive(Clone, Copy, Debug)]
 pub struct Linear;
 
 impl ActivationFunc for Linear {
@@ -61,6 +68,7 @@ impl ActivationFunc for Linear {
 }
 
 /// Exponential activation function.
+#[derive(Clone, Copy, Debug)]
 pub struct Exp;
 
 impl ActivationFunc for Exp {
@@ -76,3 +84,80 @@ impl ActivationFunc for Exp {
         x.ln()
     }
 }
+
+/// Softplus activation function.
+///
+/// A smooth approximation of the rectifier, ln(1 + e<sup>x</sup>).
+#[derive(Clone, Copy, Debug)]
+pub struct Softplus;
+
+impl ActivationFunc for Softplus {
+    fn func(x: f64) -> f64 {
+        stable::log1p_exp(x)
+    }
+
+    /// Gradient of the softplus function, the sigmoid function.
+    fn func_grad(x: f64) -> f64 {
+        stable::sigmoid(x)
+    }
+
+    /// Returns ln(e<sup>x</sup> - 1), defined for positive x.
+    fn func_inv(x: f64) -> f64 {
+        x + (-(-x).exp_m1()).ln()
+    }
+}
+
+/// Swish activation function, also called SiLU.
+///
+/// Returns x / (1 + e<sup>-x</sup>), the input scaled by its sigmoid.
+#[derive(Clone, Copy, Debug)]
+pub struct Swish;
+
+impl ActivationFunc for Swish {
+    fn func(x: f64) -> f64 {
+        x * stable::sigmoid(x)
+    }
+
+    fn func_grad(x: f64) -> f64 {
+        let s = stable::sigmoid(x);
+        s + x * s * (1f64 - s)
+    }
+
+    /// # Panics
+    ///
+    /// The swish function is not invertible.
+    fn func_inv(_: f64) -> f64 {
+        panic!("The swish function is not invertible.")
+    }
+}
+
+/// GELU activation function.
+///
+/// Uses the tanh approximation of the Gaussian error linear unit,
+/// 0.5x(1 + tanh(√(2/π)(x + 0.044715x<sup>3</sup>))).
+#[derive(Clone, Copy, Debug)]
+pub struct Gelu;
+
+/// The constant √(2/π) of the GELU approximation.
+const GELU_SCALE: f64 = 0.7978845608028654;
+/// The cubic coefficient of the GELU approximation.
+const GELU_CUBIC: f64 = 0.044715;
+
+impl ActivationFunc for Gelu {
+    fn func(x: f64) -> f64 {
+        0.5 * x * (1f64 + (GELU_SCALE * (x + GELU_CUBIC * x * x * x)).tanh())
+    }
+
+    fn func_grad(x: f64) -> f64 {
+        let t = (GELU_SCALE * (x + GELU_CUBIC * x * x * x)).tanh();
+        let dt = GELU_SCALE * (1f64 + 3f64 * GELU_CUBIC * x * x) * (1f64 - t * t);
+        0.5 * (1f64 + t) + 0.5 * x * dt
+    }
+
+    /// # Panics
+    ///
+    /// The GELU function is not invertible.
+    fn func_inv(_: f64) -> f64 {
+        panic!("The GELU function is not invertible.")
+    }
+}
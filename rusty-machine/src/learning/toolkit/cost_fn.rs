@@ -5,12 +5,22 @@
 //! These structs are used within Neural Networks and
 //! Generalized Linear Regression (not yet implemented).
 //!
+//! The `QuantileLoss` and `TweedieLoss` have parameters, so they are
+//! structs with methods for the cost and its gradient instead.
+//!
 //! You can also create your own custom cost functions for use in your models.
 //! Just create a struct implementing the CostFunc trait.
 
+use std::f64;
+use std::vec::Vec;
+
+use learning::toolkit::stable;
 use linalg::matrix::Matrix;
 use linalg::vector::Vector;
 
+#[cfg(not(feature = "std"))]
+use libnum::Float;
+
 /// Trait for cost functions in models.
 pub trait CostFunc<T> {
     /// The cost function.
@@ -20,7 +30,22 @@ pub trait CostFunc<T> {
     fn grad_cost(outputs: &T, targets: &T) -> T;
 }
 
+/// Trait for cost functions which can weight each sample.
+///
+/// The weighted cost is the weighted average of the sample costs, so
+/// unit weights give the same cost as `CostFunc::cost`. Each sample is
+/// a row of a `Matrix` or an element of a `Vector`.
+pub trait WeightedCostFunc<T>: CostFunc<T> {
+    /// The weighted cost function.
+    fn weighted_cost(outputs: &T, targets: &T, weights: &Vector<f64>) -> f64;
+
+    /// The gradient of the cost function with each sample scaled by
+    /// its weight.
+    fn weighted_grad_cost(outputs: &T, targets: &T, weights: &Vector<f64>) -> T;
+}
+
 /// The mean squared error cost function.
+#[derive(Clone, Copy, Debug)]
 pub struct MeanSqError;
 
 // For generics we need a trait for "Hadamard product" here
@@ -55,46 +80,540 @@ impl CostFunc<Vector<f64>> for MeanSqError {
 	}
 }
 
+impl WeightedCostFunc<Matrix<f64>> for MeanSqError {
+    fn weighted_cost(outputs: &Matrix<f64>, targets: &Matrix<f64>, weights: &Vector<f64>) -> f64 {
+        let diff = outputs - targets;
+        let sq_diff = diff.elemul(&diff);
+
+        weighted_mean(&sq_diff.sum_cols(), weights) / 2f64
+    }
+
+    fn weighted_grad_cost(outputs: &Matrix<f64>,
+                          targets: &Matrix<f64>,
+                          weights: &Vector<f64>)
+                          -> Matrix<f64> {
+        scale_rows(outputs - targets, weights)
+    }
+}
+
+impl WeightedCostFunc<Vector<f64>> for MeanSqError {
+    fn weighted_cost(outputs: &Vector<f64>, targets: &Vector<f64>, weights: &Vector<f64>) -> f64 {
+        let diff = outputs - targets;
+
+        weighted_mean(&diff.elemul(&diff), weights) / 2f64
+    }
+
+    fn weighted_grad_cost(outputs: &Vector<f64>,
+                          targets: &Vector<f64>,
+                          weights: &Vector<f64>)
+                          -> Vector<f64> {
+        (outputs - targets).elemul(weights)
+    }
+}
+
 /// The cross entropy error cost function.
+#[derive(Clone, Copy, Debug)]
 pub struct CrossEntropyError;
 
 impl CostFunc<Matrix<f64>> for CrossEntropyError {
     fn cost(outputs: &Matrix<f64>, targets: &Matrix<f64>) -> f64 {
-        // The cost for a single
-        let log_inv_output = (-outputs + 1f64).apply(&ln);
-        let log_output = outputs.clone().apply(&ln);
-
-        let mat_cost = targets.elemul(&log_output) + (-targets + 1f64).elemul(&log_inv_output);
-
         let n = outputs.rows();
 
-        -(mat_cost.sum()) / (n as f64)
+        entropies(outputs.data(), targets.data()).iter().sum::<f64>() / (n as f64)
     }
 
     fn grad_cost(outputs: &Matrix<f64>, targets: &Matrix<f64>) -> Matrix<f64> {
-        (outputs - targets).elediv(&(outputs.elemul(&(-outputs + 1f64))))
+        Matrix::new(outputs.rows(),
+                    outputs.cols(),
+                    entropy_grads(outputs.data(), targets.data()))
     }
 }
 
 impl CostFunc<Vector<f64>> for CrossEntropyError {
     fn cost(outputs: &Vector<f64>, targets: &Vector<f64>) -> f64 {
-        // The cost for a single
-        let log_inv_output = (-outputs + 1f64).apply(&ln);
-        let log_output = outputs.clone().apply(&ln);
-
-        let mat_cost = targets.elemul(&log_output) + (-targets + 1f64).elemul(&log_inv_output);
-
         let n = outputs.size();
 
-        -(mat_cost.sum()) / (n as f64)
+        entropies(outputs.data(), targets.data()).iter().sum::<f64>() / (n as f64)
     }
 
     fn grad_cost(outputs: &Vector<f64>, targets: &Vector<f64>) -> Vector<f64> {
-        (outputs - targets).elediv(&(outputs.elemul(&(-outputs + 1f64))))
+        Vector::new(entropy_grads(outputs.data(), targets.data()))
+    }
+}
+
+impl WeightedCostFunc<Matrix<f64>> for CrossEntropyError {
+    fn weighted_cost(outputs: &Matrix<f64>, targets: &Matrix<f64>, weights: &Vector<f64>) -> f64 {
+        let mat_cost = Matrix::new(outputs.rows(),
+                                   outputs.cols(),
+                                   entropies(outputs.data(), targets.data()));
+
+        weighted_mean(&mat_cost.sum_cols(), weights)
+    }
+
+    fn weighted_grad_cost(outputs: &Matrix<f64>,
+                          targets: &Matrix<f64>,
+                          weights: &Vector<f64>)
+                          -> Matrix<f64> {
+        scale_rows(CrossEntropyError::grad_cost(outputs, targets), weights)
+    }
+}
+
+impl WeightedCostFunc<Vector<f64>> for CrossEntropyError {
+    fn weighted_cost(outputs: &Vector<f64>, targets: &Vector<f64>, weights: &Vector<f64>) -> f64 {
+        let vec_cost = Vector::new(entropies(outputs.data(), targets.data()));
+
+        weighted_mean(&vec_cost, weights)
+    }
+
+    fn weighted_grad_cost(outputs: &Vector<f64>,
+                          targets: &Vector<f64>,
+                          weights: &Vector<f64>)
+                          -> Vector<f64> {
+        CrossEntropyError::grad_cost(outputs, targets).elemul(weights)
+    }
+}
+
+/// The softmax cross entropy cost function on logits.
+///
+/// The outputs are the logits of each class, the inputs to a softmax
+/// over each row, and each row of the targets holds the class
+/// probabilities, usually a one-hot encoding. Fusing the softmax with
+/// the cross entropy gives a cost which is exact for logits of any
+/// size and a gradient of `softmax(outputs) - targets` with respect to
+/// the logits.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::learning::toolkit::cost_fn::{CostFunc, SoftmaxCrossEntropy};
+/// use rusty_machine::linalg::matrix::Matrix;
+///
+/// // The softmax of these logits would round to exactly one and zero.
+/// let logits = Matrix::new(1, 2, vec![1000.0, 0.0]);
+/// let targets = Matrix::new(1, 2, vec![0.0, 1.0]);
+///
+/// assert_eq!(SoftmaxCrossEntropy::cost(&logits, &targets), 1000.0);
+/// assert_eq!(SoftmaxCrossEntropy::grad_cost(&logits, &targets).data(), &[1.0, -1.0]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SoftmaxCrossEntropy;
+
+impl CostFunc<Matrix<f64>> for SoftmaxCrossEntropy {
+    fn cost(outputs: &Matrix<f64>, targets: &Matrix<f64>) -> f64 {
+        softmax_entropies(outputs, targets).sum() / (outputs.rows() as f64)
+    }
+
+    fn grad_cost(outputs: &Matrix<f64>, targets: &Matrix<f64>) -> Matrix<f64> {
+        assert!(outputs.rows() == targets.rows() && outputs.cols() == targets.cols(),
+                "Outputs and targets must have the same size.");
+
+        let cols = outputs.cols();
+        let mut grad = stable::softmax(outputs);
+        for (row, t) in grad.mut_data().chunks_mut(cols).zip(targets.data().chunks(cols)) {
+            let total = t.iter().sum::<f64>();
+            for (g, t) in row.iter_mut().zip(t) {
+                *g = *g * total - t;
+            }
+        }
+        grad
+    }
+}
+
+impl WeightedCostFunc<Matrix<f64>> for SoftmaxCrossEntropy {
+    fn weighted_cost(outputs: &Matrix<f64>, targets: &Matrix<f64>, weights: &Vector<f64>) -> f64 {
+        weighted_mean(&softmax_entropies(outputs, targets), weights)
+    }
+
+    fn weighted_grad_cost(outputs: &Matrix<f64>,
+                          targets: &Matrix<f64>,
+                          weights: &Vector<f64>)
+                          -> Matrix<f64> {
+        scale_rows(SoftmaxCrossEntropy::grad_cost(outputs, targets), weights)
+    }
+}
+
+/// The softmax Kullback-Leibler divergence cost function on logits.
+///
+/// The outputs are the logits of each class and each row of the
+/// targets holds a probability distribution over the classes. The cost
+/// is the divergence of the softmax of the logits from the targets,
+/// `sum(t ln(t / softmax(z)))`, which is zero when the predicted
+/// distribution matches the targets. It differs from the
+/// `SoftmaxCrossEntropy` by the entropy of the targets, so the
+/// gradients are the same.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::learning::toolkit::cost_fn::{CostFunc, SoftmaxKLDivergence};
+/// use rusty_machine::linalg::matrix::Matrix;
+///
+/// let logits = Matrix::new(1, 2, vec![0.0, 0.0]);
+/// let targets = Matrix::new(1, 2, vec![0.5, 0.5]);
+///
+/// assert!(SoftmaxKLDivergence::cost(&logits, &targets).abs() < 1e-12);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SoftmaxKLDivergence;
+
+impl CostFunc<Matrix<f64>> for SoftmaxKLDivergence {
+    fn cost(outputs: &Matrix<f64>, targets: &Matrix<f64>) -> f64 {
+        softmax_divergences(outputs, targets).sum() / (outputs.rows() as f64)
+    }
+
+    fn grad_cost(outputs: &Matrix<f64>, targets: &Matrix<f64>) -> Matrix<f64> {
+        SoftmaxCrossEntropy::grad_cost(outputs, targets)
+    }
+}
+
+impl WeightedCostFunc<Matrix<f64>> for SoftmaxKLDivergence {
+    fn weighted_cost(outputs: &Matrix<f64>, targets: &Matrix<f64>, weights: &Vector<f64>) -> f64 {
+        weighted_mean(&softmax_divergences(outputs, targets), weights)
+    }
+
+    fn weighted_grad_cost(outputs: &Matrix<f64>,
+                          targets: &Matrix<f64>,
+                          weights: &Vector<f64>)
+                          -> Matrix<f64> {
+        scale_rows(SoftmaxCrossEntropy::grad_cost(outputs, targets), weights)
+    }
+}
+
+/// The Gaussian negative log-likelihood cost function.
+///
+/// Models each target as normally distributed with a predicted mean
+/// and variance. For k targets the outputs have 2k columns, the k
+/// means followed by the k log variances. Predicting the log of the
+/// variance keeps the variance positive without an output activation.
+///
+/// The cost of a target y with mean μ and log variance s is
+/// `(s + (y - μ)^2 e^-s + ln(2π)) / 2`.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::learning::toolkit::cost_fn::{CostFunc, GaussianNLL};
+/// use rusty_machine::linalg::matrix::Matrix;
+///
+/// // A mean of 1 and a variance of 1.
+/// let outputs = Matrix::new(1, 2, vec![1.0, 0.0]);
+/// let targets = Matrix::new(1, 1, vec![2.0]);
+///
+/// let nll = (1.0 + (2.0 * std::f64::consts::PI).ln()) / 2.0;
+/// assert!((GaussianNLL::cost(&outputs, &targets) - nll).abs() < 1e-12);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct GaussianNLL;
+
+impl CostFunc<Matrix<f64>> for GaussianNLL {
+    fn cost(outputs: &Matrix<f64>, targets: &Matrix<f64>) -> f64 {
+        gaussian_nlls(outputs, targets).sum() / (outputs.rows() as f64)
+    }
+
+    fn grad_cost(outputs: &Matrix<f64>, targets: &Matrix<f64>) -> Matrix<f64> {
+        check_gaussian_sizes(outputs, targets);
+
+        let k = targets.cols();
+        let mut grad = Vec::with_capacity(outputs.rows() * 2 * k);
+        for (o, y) in outputs.data().chunks(2 * k).zip(targets.data().chunks(k)) {
+            let (means, log_vars) = o.split_at(k);
+            let diffs = means.iter().zip(y).map(|(m, y)| m - y).collect::<Vec<_>>();
+
+            grad.extend(diffs.iter().zip(log_vars).map(|(d, s)| d * (-s).exp()));
+            grad.extend(diffs.iter().zip(log_vars).map(|(d, s)| (1f64 - d * d * (-s).exp()) / 2f64));
+        }
+
+        Matrix::new(outputs.rows(), 2 * k, grad)
     }
 }
 
-/// Logarithm for applying within cost function.
-fn ln(x: f64) -> f64 {
-    x.ln()
+impl WeightedCostFunc<Matrix<f64>> for GaussianNLL {
+    fn weighted_cost(outputs: &Matrix<f64>, targets: &Matrix<f64>, weights: &Vector<f64>) -> f64 {
+        weighted_mean(&gaussian_nlls(outputs, targets), weights)
+    }
+
+    fn weighted_grad_cost(outputs: &Matrix<f64>,
+                          targets: &Matrix<f64>,
+                          weights: &Vector<f64>)
+                          -> Matrix<f64> {
+        scale_rows(GaussianNLL::grad_cost(outputs, targets), weights)
+    }
+}
+
+/// The quantile, or pinball, loss.
+///
+/// The cost of an output f for a target y is `τ(y - f)` when the target
+/// is above the output and `(1 - τ)(f - y)` otherwise. Minimizing the
+/// cost estimates the τ quantile of the targets, so a pair of quantile
+/// models gives a prediction interval. A quantile of 0.5 gives half of
+/// the absolute error.
+///
+/// The quantile is a parameter, so the cost is computed by methods
+/// rather than through the `CostFunc` trait.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::learning::toolkit::cost_fn::QuantileLoss;
+/// use rusty_machine::linalg::vector::Vector;
+///
+/// let loss = QuantileLoss::new(0.75);
+/// let outputs = Vector::new(vec![1.0, 1.0]);
+/// let targets = Vector::new(vec![2.0, 0.0]);
+///
+/// // Under predictions cost three times as much as over predictions.
+/// assert!((loss.cost(&outputs, &targets) - 0.5).abs() < 1e-12);
+/// assert_eq!(loss.grad_cost(&outputs, &targets).data(), &[-0.75, 0.25]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct QuantileLoss {
+    quantile: f64,
+}
+
+impl QuantileLoss {
+    /// Constructs the loss for the quantile.
+    ///
+    /// # Panics
+    ///
+    /// - The quantile is not strictly between 0 and 1.
+    pub fn new(quantile: f64) -> QuantileLoss {
+        assert!(quantile > 0f64 && quantile < 1f64,
+                "The quantile must be between 0 and 1.");
+        QuantileLoss { quantile: quantile }
+    }
+
+    /// The quantile the loss estimates.
+    pub fn quantile(&self) -> f64 {
+        self.quantile
+    }
+
+    /// The mean loss of the outputs.
+    pub fn cost(&self, outputs: &Vector<f64>, targets: &Vector<f64>) -> f64 {
+        check_sizes(outputs, targets);
+
+        let tau = self.quantile;
+        let total = outputs.data()
+            .iter()
+            .zip(targets.data())
+            .map(|(f, y)| if y > f { tau * (y - f) } else { (1f64 - tau) * (f - y) })
+            .sum::<f64>();
+        total / (outputs.size() as f64)
+    }
+
+    /// The gradient of the loss of each output.
+    ///
+    /// The loss is not differentiable where an output equals its
+    /// target, where the gradient from above is used.
+    pub fn grad_cost(&self, outputs: &Vector<f64>, targets: &Vector<f64>) -> Vector<f64> {
+        check_sizes(outputs, targets);
+
+        let tau = self.quantile;
+        outputs.data()
+            .iter()
+            .zip(targets.data())
+            .map(|(f, y)| if y > f { -tau } else { 1f64 - tau })
+            .collect()
+    }
+}
+
+/// The Tweedie negative log-likelihood with a log link.
+///
+/// For powers between 1 and 2 the Tweedie distribution is a compound
+/// of Poisson and gamma distributions, which has a mass at zero and is
+/// continuous above it, like insurance claims. The outputs are the
+/// logarithms of the predicted means, and the targets must not be
+/// negative.
+///
+/// The cost of an output f for a target y is
+/// `-y e^((1-p)f) / (1-p) + e^((2-p)f) / (2-p)`, which leaves out the
+/// terms which do not depend on the outputs.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::learning::toolkit::cost_fn::TweedieLoss;
+/// use rusty_machine::linalg::vector::Vector;
+///
+/// let loss = TweedieLoss::new(1.5);
+/// let targets = Vector::new(vec![0.0, 2.0, 4.0]);
+///
+/// // The gradient vanishes when the predicted mean is the target mean.
+/// let outputs = Vector::new(vec![2f64.ln(); 3]);
+/// let grad = loss.grad_cost(&outputs, &targets);
+/// assert!(grad.sum().abs() < 1e-12);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct TweedieLoss {
+    power: f64,
+}
+
+impl TweedieLoss {
+    /// Constructs the loss with the variance power.
+    ///
+    /// # Panics
+    ///
+    /// - The power is not strictly between 1 and 2.
+    pub fn new(power: f64) -> TweedieLoss {
+        assert!(power > 1f64 && power < 2f64,
+                "The Tweedie power must be between 1 and 2.");
+        TweedieLoss { power: power }
+    }
+
+    /// The variance power of the distribution.
+    pub fn power(&self) -> f64 {
+        self.power
+    }
+
+    /// The mean loss of the outputs.
+    ///
+    /// # Panics
+    ///
+    /// - A target is negative.
+    pub fn cost(&self, outputs: &Vector<f64>, targets: &Vector<f64>) -> f64 {
+        check_tweedie_targets(outputs, targets);
+
+        let (a, b) = (1f64 - self.power, 2f64 - self.power);
+        let total = outputs.data()
+            .iter()
+            .zip(targets.data())
+            .map(|(f, y)| -y * (a * f).exp() / a + (b * f).exp() / b)
+            .sum::<f64>();
+        total / (outputs.size() as f64)
+    }
+
+    /// The gradient of the loss of each output.
+    ///
+    /// # Panics
+    ///
+    /// - A target is negative.
+    pub fn grad_cost(&self, outputs: &Vector<f64>, targets: &Vector<f64>) -> Vector<f64> {
+        check_tweedie_targets(outputs, targets);
+
+        let (a, b) = (1f64 - self.power, 2f64 - self.power);
+        outputs.data()
+            .iter()
+            .zip(targets.data())
+            .map(|(f, y)| -y * (a * f).exp() + (b * f).exp())
+            .collect()
+    }
+
+    /// The second derivative of the loss of each output.
+    ///
+    /// Positive for non-negative targets, for use in Newton steps.
+    ///
+    /// # Panics
+    ///
+    /// - A target is negative.
+    pub fn hessian(&self, outputs: &Vector<f64>, targets: &Vector<f64>) -> Vector<f64> {
+        check_tweedie_targets(outputs, targets);
+
+        let (a, b) = (1f64 - self.power, 2f64 - self.power);
+        outputs.data()
+            .iter()
+            .zip(targets.data())
+            .map(|(f, y)| -a * y * (a * f).exp() + b * (b * f).exp())
+            .collect()
+    }
+}
+
+/// The weighted average of the sample costs.
+fn weighted_mean(costs: &Vector<f64>, weights: &Vector<f64>) -> f64 {
+    assert!(costs.size() == weights.size(),
+            "Weights must have one entry per sample.");
+    costs.dot(weights) / weights.sum()
+}
+
+/// Multiplies each row of the matrix by its weight.
+fn scale_rows(mut mat: Matrix<f64>, weights: &Vector<f64>) -> Matrix<f64> {
+    assert!(mat.rows() == weights.size(),
+            "Weights must have one entry per sample.");
+
+    let cols = mat.cols();
+    for (row, w) in mat.mut_data().chunks_mut(cols).zip(weights.data().iter()) {
+        for x in row {
+            *x *= *w;
+        }
+    }
+    mat
+}
+
+/// The cross entropy of each output and target.
+fn entropies(outputs: &[f64], targets: &[f64]) -> Vec<f64> {
+    assert!(outputs.len() == targets.len(),
+            "Outputs and targets must have the same size.");
+    outputs.iter().zip(targets.iter()).map(|(&o, &t)| stable::cross_entropy(o, t)).collect()
+}
+
+/// The cross entropy gradient of each output and target.
+fn entropy_grads(outputs: &[f64], targets: &[f64]) -> Vec<f64> {
+    assert!(outputs.len() == targets.len(),
+            "Outputs and targets must have the same size.");
+    outputs.iter().zip(targets.iter()).map(|(&o, &t)| stable::cross_entropy_grad(o, t)).collect()
+}
+
+/// The softmax cross entropy of each row of logits and targets.
+fn softmax_entropies(logits: &Matrix<f64>, targets: &Matrix<f64>) -> Vector<f64> {
+    assert!(logits.rows() == targets.rows() && logits.cols() == targets.cols(),
+            "Outputs and targets must have the same size.");
+
+    let cols = logits.cols();
+    logits.data()
+        .chunks(cols)
+        .zip(targets.data().chunks(cols))
+        .map(|(z, t)| {
+            let lse = stable::log_sum_exp(z);
+            z.iter().zip(t).map(|(z, t)| t * (lse - z)).sum::<f64>()
+        })
+        .collect()
+}
+
+/// The softmax divergence of each row of logits from the targets.
+fn softmax_divergences(logits: &Matrix<f64>, targets: &Matrix<f64>) -> Vector<f64> {
+    let cols = logits.cols();
+    softmax_entropies(logits, targets)
+        .data()
+        .iter()
+        .zip(targets.data().chunks(cols))
+        .map(|(h, t)| h + t.iter().filter(|&&t| t > 0f64).map(|t| t * t.ln()).sum::<f64>())
+        .collect()
+}
+
+/// Checks that the outputs hold a mean and log variance for each
+/// target.
+fn check_gaussian_sizes(outputs: &Matrix<f64>, targets: &Matrix<f64>) {
+    assert!(outputs.rows() == targets.rows() && outputs.cols() == 2 * targets.cols(),
+            "Outputs must have a mean and log variance column for each target.");
+}
+
+/// The Gaussian negative log-likelihood of each row of targets.
+fn gaussian_nlls(outputs: &Matrix<f64>, targets: &Matrix<f64>) -> Vector<f64> {
+    check_gaussian_sizes(outputs, targets);
+
+    let k = targets.cols();
+    let log_two_pi = (2f64 * f64::consts::PI).ln();
+    outputs.data()
+        .chunks(2 * k)
+        .zip(targets.data().chunks(k))
+        .map(|(o, y)| {
+            let (means, log_vars) = o.split_at(k);
+            means.iter()
+                .zip(log_vars)
+                .zip(y)
+                .map(|((m, s), y)| (s + (y - m) * (y - m) * (-s).exp() + log_two_pi) / 2f64)
+                .sum::<f64>()
+        })
+        .collect()
+}
+
+/// Checks that there is an output for each target.
+fn check_sizes(outputs: &Vector<f64>, targets: &Vector<f64>) {
+    assert!(outputs.size() == targets.size(),
+            "Outputs and targets must have the same size.");
+}
+
+/// Checks the sizes and that the Tweedie targets are not negative.
+fn check_tweedie_targets(outputs: &Vector<f64>, targets: &Vector<f64>) {
+    check_sizes(outputs, targets);
+    assert!(targets.data().iter().all(|&y| y >= 0f64),
+            "Tweedie targets must not be negative.");
 }
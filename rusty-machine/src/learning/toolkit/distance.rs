@@ -0,0 +1,267 @@
+//! Distance metrics
+//!
+//! Metrics measuring the distance between two points, used for finding
+//! nearest neighbours and cluster centroids and for building kernels.
+//!
+//! You can also create your own metric by implementing the `Metric` trait.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::learning::toolkit::distance::{pairwise, Euclidean, Manhattan, Metric};
+//! use rusty_machine::linalg::matrix::Matrix;
+//!
+//! assert_eq!(Manhattan.distance(&[1.0, 2.0], &[4.0, 6.0]), 7.0);
+//!
+//! let x = Matrix::new(2, 2, vec![0.0, 0.0, 3.0, 4.0]);
+//! let y = Matrix::new(1, 2, vec![0.0, 0.0]);
+//!
+//! // The distances from each row of x to each row of y.
+//! let dists = pairwise(&Euclidean, &x, &y);
+//! assert_eq!(dists.data(), &[0.0, 5.0]);
+//! ```
+
+use std::vec::Vec;
+
+use linalg::matrix::{Matrix, BaseMatrix};
+
+#[cfg(not(feature = "std"))]
+use libnum::Float;
+
+/// Trait for distance metrics.
+pub trait Metric {
+    /// The distance between two points.
+    ///
+    /// Takes two equal length slices and returns a scalar.
+    fn distance(&self, x1: &[f64], x2: &[f64]) -> f64;
+
+    /// The distances between each row of `x` and each row of `y`.
+    ///
+    /// Entry `[i, j]` of the result is the distance between row `i` of
+    /// `x` and row `j` of `y`.
+    ///
+    /// # Panics
+    ///
+    /// - The matrices have a different number of columns.
+    fn pairwise(&self, x: &Matrix<f64>, y: &Matrix<f64>) -> Matrix<f64> {
+        assert!(x.cols() == y.cols(),
+                "Matrices have a different number of columns.");
+
+        let mut data = Vec::with_capacity(x.rows() * y.rows());
+        for i in 0..x.rows() {
+            for j in 0..y.rows() {
+                data.push(self.distance(x.row(i), y.row(j)));
+            }
+        }
+
+        Matrix::new(x.rows(), y.rows(), data)
+    }
+}
+
+/// The distances between each row of `x` and each row of `y`.
+///
+/// Entry `[i, j]` of the result is the distance between row `i` of
+/// `x` and row `j` of `y`.
+///
+/// # Panics
+///
+/// - The matrices have a different number of columns.
+pub fn pairwise<M: Metric>(metric: &M, x: &Matrix<f64>, y: &Matrix<f64>) -> Matrix<f64> {
+    metric.pairwise(x, y)
+}
+
+/// The Euclidean distance.
+///
+/// d(x,y) = ||x-y||
+#[derive(Clone, Copy, Debug)]
+pub struct Euclidean;
+
+impl Metric for Euclidean {
+    fn distance(&self, x1: &[f64], x2: &[f64]) -> f64 {
+        SquaredEuclidean.distance(x1, x2).sqrt()
+    }
+
+    /// Computed from the inner products of the rows, which is much
+    /// faster for large matrices but may differ from `distance` by
+    /// rounding errors.
+    fn pairwise(&self, x: &Matrix<f64>, y: &Matrix<f64>) -> Matrix<f64> {
+        SquaredEuclidean.pairwise(x, y).apply(&|d| d.sqrt())
+    }
+}
+
+/// The squared Euclidean distance.
+///
+/// d(x,y) = ||x-y||<sup>2</sup>
+#[derive(Clone, Copy, Debug)]
+pub struct SquaredEuclidean;
+
+impl Metric for SquaredEuclidean {
+    fn distance(&self, x1: &[f64], x2: &[f64]) -> f64 {
+        assert_eq!(x1.len(), x2.len());
+
+        x1.iter().zip(x2.iter()).map(|(a, b)| (a - b) * (a - b)).sum()
+    }
+
+    /// Computed from the inner products of the rows, which is much
+    /// faster for large matrices but may differ from `distance` by
+    /// rounding errors.
+    fn pairwise(&self, x: &Matrix<f64>, y: &Matrix<f64>) -> Matrix<f64> {
+        assert!(x.cols() == y.cols(),
+                "Matrices have a different number of columns.");
+
+        let x_sq = row_sq_norms(x);
+        let y_sq = row_sq_norms(y);
+        let mut dists = x * &y.transpose();
+
+        let n = y.rows();
+        for (k, d) in dists.mut_data().iter_mut().enumerate() {
+            // Rounding can leave small negative values.
+            *d = (x_sq[k / n] + y_sq[k % n] - 2f64 * *d).max(0f64);
+        }
+
+        dists
+    }
+}
+
+/// The Manhattan, or city block, distance.
+///
+/// d(x,y) = &Sigma;<sub>i</sub> |x<sub>i</sub> - y<sub>i</sub>|
+#[derive(Clone, Copy, Debug)]
+pub struct Manhattan;
+
+impl Metric for Manhattan {
+    fn distance(&self, x1: &[f64], x2: &[f64]) -> f64 {
+        assert_eq!(x1.len(), x2.len());
+
+        x1.iter().zip(x2.iter()).map(|(a, b)| (a - b).abs()).sum()
+    }
+}
+
+/// The Chebyshev distance.
+///
+/// d(x,y) = max<sub>i</sub> |x<sub>i</sub> - y<sub>i</sub>|
+#[derive(Clone, Copy, Debug)]
+pub struct Chebyshev;
+
+impl Metric for Chebyshev {
+    fn distance(&self, x1: &[f64], x2: &[f64]) -> f64 {
+        assert_eq!(x1.len(), x2.len());
+
+        x1.iter().zip(x2.iter()).map(|(a, b)| (a - b).abs()).fold(0f64, f64::max)
+    }
+}
+
+/// The Minkowski distance.
+///
+/// d(x,y) = (&Sigma;<sub>i</sub> |x<sub>i</sub> - y<sub>i</sub>|<sup>p</sup>)<sup>1/p</sup>
+///
+/// Equal to the Manhattan distance when p = 1 and the Euclidean
+/// distance when p = 2.
+#[derive(Clone, Copy, Debug)]
+pub struct Minkowski {
+    /// The order of the distance.
+    pub p: f64,
+}
+
+impl Minkowski {
+    /// Constructs a new Minkowski distance of order p.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::toolkit::distance::{Metric, Minkowski};
+    ///
+    /// let metric = Minkowski::new(3.0);
+    ///
+    /// assert_eq!(metric.distance(&[0.0, 0.0], &[3.0, 0.0]), 3.0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - p is less than one.
+    pub fn new(p: f64) -> Minkowski {
+        assert!(p >= 1f64, "The order must be at least one.");
+
+        Minkowski { p: p }
+    }
+}
+
+impl Metric for Minkowski {
+    fn distance(&self, x1: &[f64], x2: &[f64]) -> f64 {
+        assert_eq!(x1.len(), x2.len());
+
+        let sum: f64 = x1.iter().zip(x2.iter()).map(|(a, b)| (a - b).abs().powf(self.p)).sum();
+        sum.powf(1f64 / self.p)
+    }
+}
+
+/// The cosine distance.
+///
+/// d(x,y) = 1 - x<sup>T</sup>y / (||x|| ||y||)
+///
+/// The distance from a zero vector is one.
+#[derive(Clone, Copy, Debug)]
+pub struct Cosine;
+
+impl Metric for Cosine {
+    fn distance(&self, x1: &[f64], x2: &[f64]) -> f64 {
+        assert_eq!(x1.len(), x2.len());
+
+        let dot: f64 = x1.iter().zip(x2.iter()).map(|(a, b)| a * b).sum();
+        cosine(dot, sq_norm(x1), sq_norm(x2))
+    }
+
+    /// Computed from the inner products of the rows, which is much
+    /// faster for large matrices.
+    fn pairwise(&self, x: &Matrix<f64>, y: &Matrix<f64>) -> Matrix<f64> {
+        assert!(x.cols() == y.cols(),
+                "Matrices have a different number of columns.");
+
+        let x_sq = row_sq_norms(x);
+        let y_sq = row_sq_norms(y);
+        let mut dists = x * &y.transpose();
+
+        let n = y.rows();
+        for (k, d) in dists.mut_data().iter_mut().enumerate() {
+            *d = cosine(*d, x_sq[k / n], y_sq[k % n]);
+        }
+
+        dists
+    }
+}
+
+/// The Hamming distance.
+///
+/// The fraction of the elements which differ.
+#[derive(Clone, Copy, Debug)]
+pub struct Hamming;
+
+impl Metric for Hamming {
+    fn distance(&self, x1: &[f64], x2: &[f64]) -> f64 {
+        assert_eq!(x1.len(), x2.len());
+
+        if x1.is_empty() {
+            return 0f64;
+        }
+
+        let diff = x1.iter().zip(x2.iter()).filter(|&(a, b)| a != b).count();
+        diff as f64 / x1.len() as f64
+    }
+}
+
+fn sq_norm(x: &[f64]) -> f64 {
+    x.iter().map(|a| a * a).sum()
+}
+
+fn row_sq_norms(x: &Matrix<f64>) -> Vec<f64> {
+    (0..x.rows()).map(|i| sq_norm(x.row(i))).collect()
+}
+
+/// The cosine distance from the inner product and squared norms.
+fn cosine(dot: f64, x_sq: f64, y_sq: f64) -> f64 {
+    if x_sq == 0f64 || y_sq == 0f64 {
+        1f64
+    } else {
+        1f64 - dot / (x_sq * y_sq).sqrt()
+    }
+}
@@ -1,11 +1,32 @@
 //! Module for kernels
 //!
 //! Currently used within Gaussian Processes and SVMs.
+//!
+//! Kernels can be combined by wrapping them in `KernelArith`, whose sums
+//! and products are also kernels.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::learning::toolkit::kernel::{self, Kernel, KernelArith};
+//! use rusty_machine::linalg::matrix::Matrix;
+//!
+//! let ker = KernelArith(kernel::Linear::default()) + KernelArith(kernel::RBF::default());
+//!
+//! let x = Matrix::new(3, 2, vec![0.0, 1.0, 1.0, 0.0, 1.0, 1.0]);
+//! let gram = kernel::gram_matrix(&ker, &x, &x);
+//!
+//! assert_eq!(gram[[0, 2]], ker.kernel(&[0.0, 1.0], &[1.0, 1.0]));
+//! ```
 
-use linalg::vector::Vector;
+use std::ops::{Add, Mul};
+use std::vec::Vec;
+
+use linalg::matrix::{Matrix, BaseMatrix};
 use linalg::utils;
-use linalg::Metric;
+use learning::toolkit::distance::{Euclidean, Manhattan, Metric, SquaredEuclidean};
 
+#[cfg(not(feature = "std"))]
 use libnum::Float;
 
 
@@ -19,9 +40,27 @@ pub trait Kernel {
     fn kernel(&self, x1: &[f64], x2: &[f64]) -> f64;
 }
 
+/// Trait for kernels with tunable hyperparameters.
+///
+/// The hyperparameters are positive and exposed on a log scale, which
+/// lets gradient based optimizers search them without constraints.
+/// Used to fit gaussian process kernels to their data.
+pub trait Hyperparameters: Kernel + Sized {
+    /// The logarithms of the hyperparameters.
+    fn log_params(&self) -> Vec<f64>;
+
+    /// A kernel with the hyperparameters set from their logarithms.
+    fn with_log_params(&self, params: &[f64]) -> Self;
+
+    /// The gradient of the kernel function with respect to the
+    /// logarithms of the hyperparameters.
+    fn log_param_grad(&self, x1: &[f64], x2: &[f64]) -> Vec<f64>;
+}
+
 /// The Linear Kernel
 ///
 /// k(x,y) = x<sup>T</sup>y + c
+#[derive(Clone, Copy, Debug)]
 pub struct Linear {
     /// Constant term added to inner product.
     pub c: f64,
@@ -69,6 +108,7 @@ impl Kernel for Linear {
 /// The Polynomial Kernel
 ///
 /// k(x,y) = (αx<sup>T</sup>y + c)<sup>d</sup>
+#[derive(Clone, Copy, Debug)]
 pub struct Polynomial {
     /// Scaling of the inner product.
     pub alpha: f64,
@@ -132,6 +172,7 @@ impl Kernel for Polynomial {
 /// k(x,y) = A _exp_(-||x-y||<sup>2</sup> / 2l<sup>2</sup>)
 ///
 /// Where A is the amplitude and l the length scale.
+#[derive(Clone, Copy, Debug)]
 pub struct SquaredExp {
     /// The length scale of the kernel.
     pub ls: f64,
@@ -181,18 +222,40 @@ impl Kernel for SquaredExp {
     fn kernel(&self, x1: &[f64], x2: &[f64]) -> f64 {
         assert_eq!(x1.len(), x2.len());
 
-        let diff = Vector::new(x1.to_vec()) - Vector::new(x2.to_vec());
-
-        let x = -diff.dot(&diff) / (2f64 * self.ls * self.ls);
+        let x = -SquaredEuclidean.distance(x1, x2) / (2f64 * self.ls * self.ls);
         (self.ampl * x.exp())
     }
 }
 
+/// The hyperparameters are the length scale and the amplitude.
+impl Hyperparameters for SquaredExp {
+    fn log_params(&self) -> Vec<f64> {
+        vec![self.ls.ln(), self.ampl.ln()]
+    }
+
+    fn with_log_params(&self, params: &[f64]) -> SquaredExp {
+        assert_eq!(params.len(), 2);
+        SquaredExp::new(params[0].exp(), params[1].exp())
+    }
+
+    fn log_param_grad(&self, x1: &[f64], x2: &[f64]) -> Vec<f64> {
+        let k = self.kernel(x1, x2);
+        let dist = SquaredEuclidean.distance(x1, x2);
+        vec![k * dist / (self.ls * self.ls), k]
+    }
+}
+
+/// The Radial Basis Function kernel.
+///
+/// Another name for the squared exponential kernel.
+pub type RBF = SquaredExp;
+
 /// The Exponential Kernel
 ///
 /// k(x,y) = A _exp_(-||x-y|| / 2l<sup>2</sup>)
 ///
 /// Where A is the amplitude and l is the length scale.
+#[derive(Clone, Copy, Debug)]
 pub struct Exponential {
     /// The length scale of the kernel.
     pub ls: f64,
@@ -243,16 +306,108 @@ impl Kernel for Exponential {
     fn kernel(&self, x1: &[f64], x2: &[f64]) -> f64 {
         assert_eq!(x1.len(), x2.len());
 
-        let diff = Vector::new(x1.to_vec()) - Vector::new(x2.to_vec());
-
-        let x = -diff.norm() / (2f64 * self.ls * self.ls);
+        let x = -Euclidean.distance(x1, x2) / (2f64 * self.ls * self.ls);
         (self.ampl * x.exp())
     }
 }
 
+/// The hyperparameters are the length scale and the amplitude.
+impl Hyperparameters for Exponential {
+    fn log_params(&self) -> Vec<f64> {
+        vec![self.ls.ln(), self.ampl.ln()]
+    }
+
+    fn with_log_params(&self, params: &[f64]) -> Exponential {
+        assert_eq!(params.len(), 2);
+        Exponential::new(params[0].exp(), params[1].exp())
+    }
+
+    fn log_param_grad(&self, x1: &[f64], x2: &[f64]) -> Vec<f64> {
+        let k = self.kernel(x1, x2);
+        let dist = Euclidean.distance(x1, x2);
+        vec![k * dist / (self.ls * self.ls), k]
+    }
+}
+
+/// The Laplacian Kernel
+///
+/// k(x,y) = A _exp_(-||x-y||<sub>1</sub> / l)
+///
+/// Where ||x-y||<sub>1</sub> is the Manhattan distance, A is the
+/// amplitude and l is the length scale.
+#[derive(Clone, Copy, Debug)]
+pub struct Laplacian {
+    /// The length scale of the kernel.
+    pub ls: f64,
+    /// The amplitude of the kernel.
+    pub ampl: f64,
+}
+
+impl Laplacian {
+    /// Construct a new Laplacian kernel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::toolkit::kernel;
+    /// use rusty_machine::learning::toolkit::kernel::Kernel;
+    ///
+    /// // Construct a kernel with lengthscale 2 and amplitude 1.
+    /// let ker = kernel::Laplacian::new(2f64, 1f64);
+    ///
+    /// println!("{0}", ker.kernel(&[1.,2.,3.], &[3.,4.,5.]));
+    /// ```
+    pub fn new(ls: f64, ampl: f64) -> Laplacian {
+        Laplacian {
+            ls: ls,
+            ampl: ampl,
+        }
+    }
+}
+
+/// Constructs the default Laplacian kernel.
+///
+/// The defaults are:
+///
+/// - ls = 1
+/// - ampl = 1
+impl Default for Laplacian {
+    fn default() -> Laplacian {
+        Laplacian {
+            ls: 1f64,
+            ampl: 1f64,
+        }
+    }
+}
+
+impl Kernel for Laplacian {
+    fn kernel(&self, x1: &[f64], x2: &[f64]) -> f64 {
+        self.ampl * (-Manhattan.distance(x1, x2) / self.ls).exp()
+    }
+}
+
+/// The hyperparameters are the length scale and the amplitude.
+impl Hyperparameters for Laplacian {
+    fn log_params(&self) -> Vec<f64> {
+        vec![self.ls.ln(), self.ampl.ln()]
+    }
+
+    fn with_log_params(&self, params: &[f64]) -> Laplacian {
+        assert_eq!(params.len(), 2);
+        Laplacian::new(params[0].exp(), params[1].exp())
+    }
+
+    fn log_param_grad(&self, x1: &[f64], x2: &[f64]) -> Vec<f64> {
+        let k = self.kernel(x1, x2);
+        let dist = Manhattan.distance(x1, x2);
+        vec![k * dist / self.ls, k]
+    }
+}
+
 /// The Hyperbolic Tangent Kernel.
 ///
 /// ker(x,y) = _tanh_(αx<sup>T</sup>y + c)
+#[derive(Clone, Copy, Debug)]
 pub struct HyperTan {
     /// The scaling of the inner product.
     pub alpha: f64,
@@ -304,9 +459,15 @@ impl Kernel for HyperTan {
     }
 }
 
+/// The Sigmoid kernel.
+///
+/// Another name for the hyperbolic tangent kernel.
+pub type Sigmoid = HyperTan;
+
 /// The Multiquadric Kernel.
 ///
 /// k(x,y) = _sqrt_(||x-y||<sup>2</sup> + c<sup>2</sup>)
+#[derive(Clone, Copy, Debug)]
 pub struct Multiquadric {
     /// Constant added to square of difference.
     pub c: f64,
@@ -351,15 +512,14 @@ impl Kernel for Multiquadric {
     fn kernel(&self, x1: &[f64], x2: &[f64]) -> f64 {
         assert_eq!(x1.len(), x2.len());
 
-        let diff = Vector::new(x1.to_vec()) - Vector::new(x2.to_vec());
-
-        diff.norm().hypot(self.c)
+        Euclidean.distance(x1, x2).hypot(self.c)
     }
 }
 
 /// The Rational Quadratic Kernel.
 ///
 /// k(x,y) = (1 + ||x-y||<sup>2</sup> / (2αl<sup>2</sup>))<sup>-α</sup>
+#[derive(Clone, Copy, Debug)]
 pub struct RationalQuadratic {
     /// Controls inverse power and difference scale.
     pub alpha: f64,
@@ -407,8 +567,128 @@ impl Default for RationalQuadratic {
 
 impl Kernel for RationalQuadratic {
     fn kernel(&self, x1: &[f64], x2: &[f64]) -> f64 {
-        let diff = Vector::new(x1.to_vec()) - Vector::new(x2.to_vec());
+        (1f64 + SquaredEuclidean.distance(x1, x2) / (2f64 * self.alpha * self.ls * self.ls)).powf(-self.alpha)
+    }
+}
+
+/// Wrapper for combining kernels.
+///
+/// The sum or product of two wrapped kernels is a kernel.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::learning::toolkit::kernel::{self, Kernel, KernelArith};
+///
+/// let ker = KernelArith(kernel::Linear::default()) * KernelArith(kernel::Polynomial::default());
+///
+/// println!("{0}", ker.kernel(&[1.,2.,3.], &[3.,4.,5.]));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct KernelArith<K>(pub K);
+
+impl<K: Kernel> Kernel for KernelArith<K> {
+    fn kernel(&self, x1: &[f64], x2: &[f64]) -> f64 {
+        self.0.kernel(x1, x2)
+    }
+}
+
+/// The sum of two kernels.
+///
+/// k(x,y) = k<sub>1</sub>(x,y) + k<sub>2</sub>(x,y)
+#[derive(Clone, Copy, Debug)]
+pub struct KernelSum<T, U> {
+    /// The first kernel.
+    pub k1: T,
+    /// The second kernel.
+    pub k2: U,
+}
+
+impl<T: Kernel, U: Kernel> Kernel for KernelSum<T, U> {
+    fn kernel(&self, x1: &[f64], x2: &[f64]) -> f64 {
+        self.k1.kernel(x1, x2) + self.k2.kernel(x1, x2)
+    }
+}
+
+/// The product of two kernels.
+///
+/// k(x,y) = k<sub>1</sub>(x,y) k<sub>2</sub>(x,y)
+#[derive(Clone, Copy, Debug)]
+pub struct KernelProd<T, U> {
+    /// The first kernel.
+    pub k1: T,
+    /// The second kernel.
+    pub k2: U,
+}
+
+impl<T: Kernel, U: Kernel> Kernel for KernelProd<T, U> {
+    fn kernel(&self, x1: &[f64], x2: &[f64]) -> f64 {
+        self.k1.kernel(x1, x2) * self.k2.kernel(x1, x2)
+    }
+}
+
+impl<T: Kernel, U: Kernel> Add<KernelArith<U>> for KernelArith<T> {
+    type Output = KernelArith<KernelSum<T, U>>;
+
+    fn add(self, ker: KernelArith<U>) -> KernelArith<KernelSum<T, U>> {
+        KernelArith(KernelSum {
+            k1: self.0,
+            k2: ker.0,
+        })
+    }
+}
+
+impl<T: Kernel, U: Kernel> Mul<KernelArith<U>> for KernelArith<T> {
+    type Output = KernelArith<KernelProd<T, U>>;
 
-        (1f64 + diff.dot(&diff) / (2f64 * self.alpha * self.ls * self.ls)).powf(-self.alpha)
+    fn mul(self, ker: KernelArith<U>) -> KernelArith<KernelProd<T, U>> {
+        KernelArith(KernelProd {
+            k1: self.0,
+            k2: ker.0,
+        })
     }
-}
\ No newline at end of file
+}
+
+/// Computes the gram matrix of the kernel.
+///
+/// Entry `[i, j]` of the result is the kernel applied to row `i` of
+/// `x` and row `j` of `y`.
+///
+/// # Panics
+///
+/// - The matrices have a different number of columns.
+pub fn gram_matrix<K: Kernel>(ker: &K, x: &Matrix<f64>, y: &Matrix<f64>) -> Matrix<f64> {
+    assert!(x.cols() == y.cols(),
+            "Matrices have a different number of columns.");
+
+    let mut data = Vec::with_capacity(x.rows() * y.rows());
+    for i in 0..x.rows() {
+        for j in 0..y.rows() {
+            data.push(ker.kernel(x.row(i), y.row(j)));
+        }
+    }
+
+    Matrix::new(x.rows(), y.rows(), data)
+}
+
+/// Computes the gram matrix of the kernel between the rows of `x`.
+///
+/// Equal to `gram_matrix(ker, x, x)` for symmetric kernels, but only
+/// evaluates the kernel for half of the entries.
+pub fn symmetric_gram_matrix<K: Kernel>(ker: &K, x: &Matrix<f64>) -> Matrix<f64> {
+    let n = x.rows();
+    let mut gram = Matrix::zeros(n, n);
+
+    {
+        let data = gram.mut_data();
+        for i in 0..n {
+            for j in i..n {
+                let k = ker.kernel(x.row(i), x.row(j));
+                data[i * n + j] = k;
+                data[j * n + i] = k;
+            }
+        }
+    }
+
+    gram
+}
@@ -0,0 +1,159 @@
+//! Random number generation
+//!
+//! The models draw their random numbers, for weight initialization,
+//! centroid initialization and data shuffling, from generators
+//! returned by `rng`. By default these are seeded from the thread
+//! random number generator.
+//!
+//! After calling `set_seed` the generators are instead drawn in
+//! sequence from the seed, so a program which makes the same calls
+//! gives the same results on every run. Each thread keeps its own
+//! position in the sequence. Setting the seed restarts the sequence
+//! of the calling thread, and of any other thread once it next draws
+//! a generator under a different seed.
+//!
+//! Work which may run on any thread, such as the folds of
+//! `cross_val_score_parallel`, is wrapped in `with_task`. The
+//! generators drawn inside a task depend only on the seed and the
+//! task index, so the results do not depend on how the work is
+//! scheduled.
+//!
+//! Functions which take an explicit seed, such as the data splitting
+//! and bootstrap functions, build their generator with `seeded_rng`.
+//!
+//! Requires the `rand` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::learning::nnet::NeuralNet;
+//! use rusty_machine::learning::toolkit::rand_utils;
+//!
+//! rand_utils::set_seed(42);
+//! let first = NeuralNet::default(&[2, 3, 1]);
+//!
+//! rand_utils::set_seed(42);
+//! let second = NeuralNet::default(&[2, 3, 1]);
+//!
+//! assert_eq!(first.parameters(), second.parameters());
+//! ```
+
+use std::cell::Cell;
+use std::sync::Mutex;
+
+use rand::{Rng, SeedableRng, StdRng, thread_rng};
+use rand::distributions::normal::StandardNormal;
+
+/// The seed shared by all threads.
+static SEED: Mutex<Option<u64>> = Mutex::new(None);
+
+/// The position of a thread in the sequence of generators.
+#[derive(Clone, Copy)]
+struct Stream {
+    seed: u64,
+    task: usize,
+    drawn: usize,
+}
+
+thread_local! {
+    /// The stream this thread is drawing generators from.
+    static STREAM: Cell<Option<Stream>> = const { Cell::new(None) };
+}
+
+/// Seeds the random number generators used by the models.
+///
+/// The calling thread restarts its sequence of generators, including
+/// when the same seed is set again.
+pub fn set_seed(seed: u64) {
+    *SEED.lock().unwrap() = Some(seed);
+    STREAM.with(|stream| {
+        stream.set(Some(Stream {
+            seed: seed,
+            task: 0,
+            drawn: 0,
+        }))
+    });
+}
+
+/// Removes the seed, so the generators are seeded from the thread
+/// random number generator again.
+pub fn clear_seed() {
+    *SEED.lock().unwrap() = None;
+    STREAM.with(|stream| stream.set(None));
+}
+
+/// Returns a new random number generator.
+pub fn rng() -> StdRng {
+    let seed = *SEED.lock().unwrap();
+
+    match seed {
+        Some(seed) => {
+            let current = STREAM.with(|stream| {
+                let current = match stream.get() {
+                    Some(current) if current.seed == seed => current,
+                    current => {
+                        Stream {
+                            seed: seed,
+                            task: current.map_or(0, |s| s.task),
+                            drawn: 0,
+                        }
+                    }
+                };
+                stream.set(Some(Stream { drawn: current.drawn + 1, ..current }));
+                current
+            });
+
+            stream_rng(current)
+        }
+        None => {
+            let mut rng = thread_rng();
+            let seed: &[usize] = &[rng.gen(), rng.gen(), rng.gen(), rng.gen()];
+            SeedableRng::from_seed(seed)
+        }
+    }
+}
+
+/// Runs `f` as the task with the given index.
+///
+/// While the seed is set, the generators drawn by `f` come from a
+/// sequence determined by the seed and the index alone, whichever
+/// thread runs it. The thread returns to its own sequence afterwards.
+pub fn with_task<T, F>(index: usize, f: F) -> T
+    where F: FnOnce() -> T
+{
+    let seed = *SEED.lock().unwrap();
+    let task = seed.map(|seed| {
+        Stream {
+            seed: seed,
+            task: index + 1,
+            drawn: 0,
+        }
+    });
+
+    let previous = STREAM.with(|stream| stream.replace(task));
+    let result = f();
+    STREAM.with(|stream| stream.set(previous));
+    result
+}
+
+/// Returns a random number generator seeded from `seed` alone.
+///
+/// The generator does not depend on `set_seed`.
+pub fn seeded_rng(seed: u64) -> StdRng {
+    let seed: &[usize] = &[seed as usize, (seed >> 32) as usize];
+    SeedableRng::from_seed(seed)
+}
+
+/// Draws a sample from the standard normal distribution.
+pub fn standard_normal<R: Rng>(rng: &mut R) -> f64 {
+    let StandardNormal(z) = rng.gen::<StandardNormal>();
+    z
+}
+
+fn stream_rng(stream: Stream) -> StdRng {
+    let seed: &[usize] = &[stream.seed as usize,
+                           (stream.seed >> 32) as usize,
+                           stream.task,
+                           stream.drawn];
+    SeedableRng::from_seed(seed)
+}
@@ -0,0 +1,230 @@
+//! Regularization penalties
+//!
+//! Penalties on the size of the model parameters which are added to
+//! the training cost to reduce overfitting.
+//!
+//! The gradient descent algorithms take a penalty and apply it to the
+//! parameters which the model marks as penalized with
+//! `Optimizable::is_penalized`, usually every parameter but the
+//! intercepts. The update is a proximal step, so the `L1` penalty sets
+//! small parameters exactly to zero. `AdaGrad` and `AdaDelta` step each
+//! parameter differently, so they add the gradient of the penalty to
+//! the mini-batch gradients instead.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::learning::logistic_reg::LogisticRegressor;
+//! use rusty_machine::learning::optim::grad_desc::GradientDesc;
+//! use rusty_machine::learning::toolkit::regularization::ElasticNet;
+//!
+//! let gd = GradientDesc::new(0.3, 100).penalty(ElasticNet::new(0.1, 0.5));
+//! let model = LogisticRegressor::new(gd);
+//! ```
+
+use std::vec::Vec;
+
+use learning::optim::Optimizable;
+
+/// Trait for regularization penalties.
+pub trait Penalty {
+    /// The penalty on the parameters.
+    fn cost(&self, params: &[f64]) -> f64;
+
+    /// The gradient of the penalty.
+    ///
+    /// Where the penalty is not differentiable a subgradient is returned.
+    fn grad(&self, params: &[f64]) -> Vec<f64>;
+
+    /// The proximal operator of the penalty for the step size.
+    ///
+    /// Returns the parameters `w` minimizing
+    /// `step * cost(w) + ||w - params||^2 / 2`.
+    fn prox(&self, params: &[f64], step: f64) -> Vec<f64>;
+}
+
+/// The L1, or lasso, penalty.
+///
+/// p(w) = λ&Sigma;<sub>i</sub> |w<sub>i</sub>|
+#[derive(Clone, Copy, Debug)]
+pub struct L1 {
+    /// The strength of the penalty.
+    pub lambda: f64,
+}
+
+impl L1 {
+    /// Constructs an L1 penalty with the given strength.
+    ///
+    /// # Panics
+    ///
+    /// - The strength is negative.
+    pub fn new(lambda: f64) -> L1 {
+        assert!(lambda >= 0f64, "Penalty strength must be non-negative.");
+        L1 { lambda: lambda }
+    }
+}
+
+impl Penalty for L1 {
+    fn cost(&self, params: &[f64]) -> f64 {
+        self.lambda * params.iter().map(|w| w.abs()).sum::<f64>()
+    }
+
+    fn grad(&self, params: &[f64]) -> Vec<f64> {
+        params.iter().map(|&w| self.lambda * sign(w)).collect()
+    }
+
+    fn prox(&self, params: &[f64], step: f64) -> Vec<f64> {
+        params.iter().map(|&w| soft_threshold(w, step * self.lambda)).collect()
+    }
+}
+
+/// The L2, or ridge, penalty.
+///
+/// p(w) = λ&Sigma;<sub>i</sub> w<sub>i</sub><sup>2</sup> / 2
+#[derive(Clone, Copy, Debug)]
+pub struct L2 {
+    /// The strength of the penalty.
+    pub lambda: f64,
+}
+
+impl L2 {
+    /// Constructs an L2 penalty with the given strength.
+    ///
+    /// # Panics
+    ///
+    /// - The strength is negative.
+    pub fn new(lambda: f64) -> L2 {
+        assert!(lambda >= 0f64, "Penalty strength must be non-negative.");
+        L2 { lambda: lambda }
+    }
+}
+
+impl Penalty for L2 {
+    fn cost(&self, params: &[f64]) -> f64 {
+        self.lambda * params.iter().map(|w| w * w).sum::<f64>() / 2f64
+    }
+
+    fn grad(&self, params: &[f64]) -> Vec<f64> {
+        params.iter().map(|w| self.lambda * w).collect()
+    }
+
+    fn prox(&self, params: &[f64], step: f64) -> Vec<f64> {
+        let shrink = 1f64 + step * self.lambda;
+        params.iter().map(|w| w / shrink).collect()
+    }
+}
+
+/// The elastic net penalty, mixing the L1 and L2 penalties.
+///
+/// p(w) = λ(r&Sigma;<sub>i</sub> |w<sub>i</sub>| +
+/// (1 - r)&Sigma;<sub>i</sub> w<sub>i</sub><sup>2</sup> / 2)
+///
+/// Where r is the L1 ratio.
+#[derive(Clone, Copy, Debug)]
+pub struct ElasticNet {
+    /// The strength of the penalty.
+    pub lambda: f64,
+    /// The fraction of the penalty which is L1.
+    pub l1_ratio: f64,
+}
+
+impl ElasticNet {
+    /// Constructs an elastic net penalty with the given strength and
+    /// L1 ratio.
+    ///
+    /// # Panics
+    ///
+    /// - The strength is negative.
+    /// - The L1 ratio is not in the interval [0, 1].
+    pub fn new(lambda: f64, l1_ratio: f64) -> ElasticNet {
+        assert!(lambda >= 0f64, "Penalty strength must be non-negative.");
+        assert!((0f64..=1f64).contains(&l1_ratio),
+                "L1 ratio must be in the interval [0, 1].");
+        ElasticNet {
+            lambda: lambda,
+            l1_ratio: l1_ratio,
+        }
+    }
+
+    fn l1(&self) -> L1 {
+        L1 { lambda: self.lambda * self.l1_ratio }
+    }
+
+    fn l2(&self) -> L2 {
+        L2 { lambda: self.lambda * (1f64 - self.l1_ratio) }
+    }
+}
+
+impl Penalty for ElasticNet {
+    fn cost(&self, params: &[f64]) -> f64 {
+        self.l1().cost(params) + self.l2().cost(params)
+    }
+
+    fn grad(&self, params: &[f64]) -> Vec<f64> {
+        self.l1()
+            .grad(params)
+            .into_iter()
+            .zip(self.l2().grad(params))
+            .map(|(a, b)| a + b)
+            .collect()
+    }
+
+    fn prox(&self, params: &[f64], step: f64) -> Vec<f64> {
+        self.l2().prox(&self.l1().prox(params, step), step)
+    }
+}
+
+/// The indices of the parameters which the model penalizes.
+///
+/// The optimizers compute these once for each optimization and pass
+/// them to `penalized_cost` and `apply_prox`.
+pub fn penalized_indices<M: Optimizable + ?Sized>(model: &M, n_params: usize) -> Vec<usize> {
+    (0..n_params).filter(|&i| model.is_penalized(i)).collect()
+}
+
+/// The penalty on the parameters at the given indices.
+pub fn penalized_cost<P: Penalty + ?Sized>(penalty: &P, idxs: &[usize], params: &[f64]) -> f64 {
+    penalty.cost(&select(idxs, params))
+}
+
+/// Adds the gradient of the penalty on the parameters at the given
+/// indices to the gradient.
+pub fn add_grad<P: Penalty + ?Sized>(penalty: &P, idxs: &[usize], params: &[f64], grad: &mut [f64]) {
+    for (&i, g) in idxs.iter().zip(penalty.grad(&select(idxs, params))) {
+        grad[i] += g;
+    }
+}
+
+/// Applies the proximal operator of the penalty to the parameters at
+/// the given indices.
+pub fn apply_prox<P: Penalty + ?Sized>(penalty: &P, idxs: &[usize], params: &mut [f64], step: f64) {
+    let selected = select(idxs, params);
+    for (&i, w) in idxs.iter().zip(penalty.prox(&selected, step)) {
+        params[i] = w;
+    }
+}
+
+/// The values of the parameters at the given indices.
+fn select(idxs: &[usize], params: &[f64]) -> Vec<f64> {
+    idxs.iter().map(|&i| params[i]).collect()
+}
+
+fn sign(x: f64) -> f64 {
+    if x > 0f64 {
+        1f64
+    } else if x < 0f64 {
+        -1f64
+    } else {
+        0f64
+    }
+}
+
+fn soft_threshold(x: f64, t: f64) -> f64 {
+    if x > t {
+        x - t
+    } else if x < -t {
+        x + t
+    } else {
+        0f64
+    }
+}
@@ -0,0 +1,172 @@
+//! Running statistics
+//!
+//! Column means and variances computed in a single pass over the data.
+//!
+//! The `RunningStats` accumulator is updated one row or one batch at a
+//! time using Welford's algorithm, so streaming data can be normalized
+//! without holding it in memory or making a second pass. Accumulators
+//! of separate streams can be merged.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::learning::toolkit::running_stats::RunningStats;
+//! use rusty_machine::linalg::matrix::Matrix;
+//!
+//! let mut stats = RunningStats::new();
+//!
+//! // The rows can come from any stream, for example a file reader.
+//! for x in 0..5 {
+//!     stats.update(&[x as f64, 10.0]);
+//! }
+//!
+//! assert_eq!(stats.count(), 5);
+//! assert_eq!(stats.mean(), &[2.0, 10.0]);
+//! assert_eq!(stats.variance(), vec![2.5, 0.0]);
+//!
+//! let scaled = stats.standardize(&Matrix::new(1, 2, vec![4.0, 12.0]));
+//! assert!((scaled[[0, 0]] - 2.0 / 2.5f64.sqrt()).abs() < 1e-12);
+//! assert_eq!(scaled[[0, 1]], 2.0);
+//! ```
+
+use std::vec::Vec;
+
+use linalg::matrix::{Matrix, BaseMatrix};
+
+#[cfg(not(feature = "std"))]
+use libnum::Float;
+
+/// Running means and variances of each column.
+#[derive(Clone, Debug, Default)]
+pub struct RunningStats {
+    count: usize,
+    mean: Vec<f64>,
+    m2: Vec<f64>,
+}
+
+impl RunningStats {
+    /// Constructs an empty accumulator.
+    ///
+    /// The number of columns is set by the first row.
+    pub fn new() -> RunningStats {
+        RunningStats::default()
+    }
+
+    /// The number of rows seen.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The column means.
+    ///
+    /// Empty if no rows have been seen.
+    pub fn mean(&self) -> &[f64] {
+        &self.mean
+    }
+
+    /// The column (sample) variances.
+    ///
+    /// # Panics
+    ///
+    /// - Fewer than two rows have been seen.
+    pub fn variance(&self) -> Vec<f64> {
+        assert!(self.count > 1, "At least two rows are needed for the variance.");
+
+        let n = (self.count - 1) as f64;
+        self.m2.iter().map(|m| m / n).collect()
+    }
+
+    /// The column (sample) standard deviations.
+    ///
+    /// # Panics
+    ///
+    /// - Fewer than two rows have been seen.
+    pub fn std_dev(&self) -> Vec<f64> {
+        self.variance().iter().map(|v| v.sqrt()).collect()
+    }
+
+    /// Adds a row to the statistics.
+    ///
+    /// # Panics
+    ///
+    /// - The row has a different length to the previous rows.
+    pub fn update(&mut self, x: &[f64]) {
+        if self.count == 0 {
+            self.mean = vec![0f64; x.len()];
+            self.m2 = vec![0f64; x.len()];
+        }
+        assert!(x.len() == self.mean.len(), "Row length does not match the statistics.");
+
+        self.count += 1;
+        let n = self.count as f64;
+        for (j, &x_j) in x.iter().enumerate() {
+            let delta = x_j - self.mean[j];
+            self.mean[j] += delta / n;
+            self.m2[j] += delta * (x_j - self.mean[j]);
+        }
+    }
+
+    /// Adds each row of the inputs to the statistics.
+    ///
+    /// # Panics
+    ///
+    /// - The inputs have a different number of columns to the previous rows.
+    pub fn update_rows(&mut self, inputs: &Matrix<f64>) {
+        for i in 0..inputs.rows() {
+            self.update(inputs.row(i));
+        }
+    }
+
+    /// Combines the statistics of another accumulator into these.
+    ///
+    /// The result is the same as updating with the rows of both.
+    ///
+    /// # Panics
+    ///
+    /// - Both accumulators have seen rows of different lengths.
+    pub fn merge(&mut self, other: &RunningStats) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = other.clone();
+            return;
+        }
+        assert!(self.mean.len() == other.mean.len(),
+                "Statistics have a different number of columns.");
+
+        let (n_a, n_b) = (self.count as f64, other.count as f64);
+        let n = n_a + n_b;
+        for j in 0..self.mean.len() {
+            let delta = other.mean[j] - self.mean[j];
+            self.mean[j] += delta * n_b / n;
+            self.m2[j] += other.m2[j] + delta * delta * n_a * n_b / n;
+        }
+        self.count += other.count;
+    }
+
+    /// Standardizes the inputs with the current statistics.
+    ///
+    /// Subtracts the column means and divides by the column standard
+    /// deviations, as the `StandardScaler` does. Columns with zero
+    /// variance are only centered.
+    ///
+    /// # Panics
+    ///
+    /// - Fewer than two rows have been seen.
+    /// - The inputs have a different number of columns to the statistics.
+    pub fn standardize(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        let std_devs = self.std_dev()
+                           .into_iter()
+                           .map(|s| if s > 0f64 { s } else { 1f64 })
+                           .collect::<Vec<_>>();
+        assert!(inputs.cols() == self.mean.len(),
+                "Input columns do not match the statistics.");
+
+        let data = inputs.data();
+        let cols = inputs.cols();
+        Matrix::from_fn(inputs.rows(), cols, |i, j| {
+            (data[i * cols + j] - self.mean[j]) / std_devs[j]
+        })
+    }
+}
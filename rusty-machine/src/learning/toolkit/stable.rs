@@ -0,0 +1,131 @@
+//! Numerically stable functions
+//!
+//! Implementations of common functions which avoid overflow and give
+//! finite results where the naive formulas produce `NaN`.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::learning::toolkit::stable;
+//!
+//! // The naive formula overflows to infinity.
+//! assert_eq!(stable::log_sum_exp(&[1000.0, 1000.0]), 1000.0 + 2f64.ln());
+//!
+//! // The naive formula gives 0 * ln(0) = NaN.
+//! assert_eq!(stable::cross_entropy(1.0, 1.0), 0.0);
+//! ```
+
+use std::f64;
+use std::vec::Vec;
+
+use linalg::matrix::{Matrix, BaseMatrix};
+
+#[cfg(not(feature = "std"))]
+use libnum::Float;
+
+/// The logarithm of the sum of the exponentials of the values.
+///
+/// Returns negative infinity for an empty slice.
+pub fn log_sum_exp(xs: &[f64]) -> f64 {
+    let max = xs.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+
+    if max.is_infinite() {
+        return max;
+    }
+
+    max + xs.iter().map(|x| (x - max).exp()).sum::<f64>().ln()
+}
+
+/// The function ln(1 + e<sup>x</sup>), also called softplus.
+pub fn log1p_exp(x: f64) -> f64 {
+    if x > 0f64 {
+        x + (-x).exp().ln_1p()
+    } else {
+        x.exp().ln_1p()
+    }
+}
+
+/// The sigmoid function 1 / (1 + e<sup>-x</sup>).
+pub fn sigmoid(x: f64) -> f64 {
+    if x >= 0f64 {
+        1f64 / (1f64 + (-x).exp())
+    } else {
+        let e = x.exp();
+        e / (1f64 + e)
+    }
+}
+
+/// The logarithm of the sigmoid function.
+pub fn log_sigmoid(x: f64) -> f64 {
+    -log1p_exp(-x)
+}
+
+/// The cross entropy of a predicted probability and a target.
+///
+/// -(t ln(p) + (1 - t) ln(1 - p))
+///
+/// Terms with a zero target weight contribute zero, so outputs of
+/// exactly zero or one give a finite cost when they match the target.
+pub fn cross_entropy(output: f64, target: f64) -> f64 {
+    -(xlogy(target, output) + xlogy(1f64 - target, 1f64 - output))
+}
+
+/// The gradient of the cross entropy with respect to the output.
+///
+/// The denominator is bounded away from zero so that saturated
+/// outputs give a finite gradient.
+pub fn cross_entropy_grad(output: f64, target: f64) -> f64 {
+    (output - target) / (output * (1f64 - output)).max(f64::EPSILON)
+}
+
+/// The cross entropy of the sigmoid of a logit and a target.
+///
+/// Equal to `cross_entropy(sigmoid(logit), target)` but exact for
+/// logits of any size.
+pub fn cross_entropy_with_logits(logit: f64, target: f64) -> f64 {
+    log1p_exp(logit) - target * logit
+}
+
+/// The softmax of each row of the matrix.
+///
+/// The row maximum is subtracted before taking exponentials, so large
+/// values do not overflow.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::learning::toolkit::stable::softmax;
+/// use rusty_machine::linalg::matrix::Matrix;
+///
+/// let x = Matrix::new(2, 2, vec![0.0, 0.0, 1000.0, 1000.0]);
+/// let probs = softmax(&x);
+///
+/// assert_eq!(probs.data(), &[0.5, 0.5, 0.5, 0.5]);
+/// ```
+pub fn softmax(mat: &Matrix<f64>) -> Matrix<f64> {
+    let cols = mat.cols();
+    let mut data = Vec::with_capacity(mat.rows() * cols);
+
+    for i in 0..mat.rows() {
+        let row = mat.row(i);
+        let max = row.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let start = data.len();
+
+        data.extend(row.iter().map(|x| (x - max).exp()));
+        let sum: f64 = data[start..].iter().sum();
+        for x in &mut data[start..] {
+            *x /= sum;
+        }
+    }
+
+    Matrix::new(mat.rows(), cols, data)
+}
+
+/// x ln(y), taken to be zero when x is zero.
+fn xlogy(x: f64, y: f64) -> f64 {
+    if x == 0f64 {
+        0f64
+    } else {
+        x * y.ln()
+    }
+}
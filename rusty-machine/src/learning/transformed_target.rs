@@ -0,0 +1,128 @@
+//! Transformed target regression module
+//!
+//! Trains a regressor on transformed targets and inverts the transform
+//! on its predictions.
+//!
+//! Regression targets which are skewed, such as prices, are often
+//! modelled better on a log scale. The `TransformedTargetRegressor`
+//! fits a target transform, such as those in the
+//! `data::transforms::target` module, trains the model on the
+//! transformed targets and maps the predictions back to the original
+//! scale.
+//!
+//! Note that the predictions are the inverse transform of the predicted
+//! transformed targets. For the log transform these estimate the median
+//! of the targets rather than the mean.
+//!
+//! # Usage
+//!
+//! ```
+//! use rusty_machine::data::transforms::target::LogTransform;
+//! use rusty_machine::learning::lin_reg::LinRegressor;
+//! use rusty_machine::learning::transformed_target::TransformedTargetRegressor;
+//! use rusty_machine::learning::SupModel;
+//! use rusty_machine::linalg::matrix::Matrix;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! // The targets grow exponentially with the input.
+//! let inputs = Matrix::new(4, 1, vec![0.0, 1.0, 2.0, 3.0]);
+//! let targets = Vector::new(vec![1.0, 10.0, 100.0, 1000.0]);
+//!
+//! let mut model = TransformedTargetRegressor::new(LinRegressor::default(),
+//!                                                 LogTransform::default());
+//! model.train(&inputs, &targets);
+//!
+//! let output = model.predict(&Matrix::new(1, 1, vec![4.0]));
+//! assert!((output[0] - 10000.0).abs() < 1e-6);
+//! ```
+
+use std::fmt;
+use std::vec::Vec;
+
+use data::transforms::Transformer;
+use learning::{ApproxEq, ModelParams, ParamGroup, SupModel, WeightedSupModel};
+use linalg::vector::Vector;
+
+/// A regressor trained on transformed targets.
+#[derive(Clone)]
+pub struct TransformedTargetRegressor<M, T> {
+    model: M,
+    transform: T,
+}
+
+impl<M, T> TransformedTargetRegressor<M, T> {
+    /// Constructs an untrained regressor from a model and a target
+    /// transform.
+    pub fn new(model: M, transform: T) -> TransformedTargetRegressor<M, T> {
+        TransformedTargetRegressor {
+            model: model,
+            transform: transform,
+        }
+    }
+
+    /// Returns a reference to the model.
+    pub fn model(&self) -> &M {
+        &self.model
+    }
+
+    /// Returns a reference to the target transform.
+    pub fn transform(&self) -> &T {
+        &self.transform
+    }
+}
+
+impl<M: fmt::Debug, T: fmt::Debug> fmt::Debug for TransformedTargetRegressor<M, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TransformedTargetRegressor")
+            .field("model", &self.model)
+            .field("transform", &self.transform)
+            .finish()
+    }
+}
+
+/// Compares the models, ignoring the fitted transforms.
+impl<M: ApproxEq, T> ApproxEq for TransformedTargetRegressor<M, T> {
+    fn approx_eq(&self, other: &TransformedTargetRegressor<M, T>, tol: f64) -> bool {
+        self.model.approx_eq(&other.model, tol)
+    }
+}
+
+/// The parameters of the model, the fitted transform is not included.
+impl<M: ModelParams, T> ModelParams for TransformedTargetRegressor<M, T> {
+    fn param_groups(&self) -> Vec<ParamGroup> {
+        self.model.param_groups()
+    }
+}
+
+impl<I, M, T> SupModel<I, Vector<f64>> for TransformedTargetRegressor<M, T>
+    where M: SupModel<I, Vector<f64>>,
+          T: Transformer<Vector<f64>>
+{
+    /// Predicts the transformed targets and inverts the transform.
+    ///
+    /// The model must be trained before prediction can be made.
+    fn predict(&self, inputs: &I) -> Vector<f64> {
+        self.transform.inverse_transform(&self.model.predict(inputs))
+    }
+
+    /// Fits the transform to the targets and trains the model on the
+    /// transformed targets.
+    fn train(&mut self, inputs: &I, targets: &Vector<f64>) {
+        let transformed = self.transform.fit_transform(targets);
+        self.model.train(inputs, &transformed);
+    }
+}
+
+impl<I, M, T> WeightedSupModel<I, Vector<f64>> for TransformedTargetRegressor<M, T>
+    where M: WeightedSupModel<I, Vector<f64>>,
+          T: Transformer<Vector<f64>>
+{
+    /// Fits the transform to the targets and trains the model on the
+    /// transformed targets with the sample weights.
+    ///
+    /// The transform itself is fitted without weights.
+    fn train_weighted(&mut self, inputs: &I, targets: &Vector<f64>, weights: &Vector<f64>) {
+        let transformed = self.transform.fit_transform(targets);
+        self.model.train_weighted(inputs, &transformed, weights);
+    }
+}
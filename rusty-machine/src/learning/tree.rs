@@ -0,0 +1,1482 @@
+//! Decision Tree module
+//!
+//! Contains classification and regression trees grown by recursive
+//! binary splitting. Each split compares one feature with a threshold,
+//! chosen to most reduce the Gini impurity of the classes for the
+//! `DecisionTreeClassifier` or the squared error of the targets for
+//! the `DecisionTreeRegressor`.
+//!
+//! The classes are labelled `0..k`, like the outputs of the
+//! `KMeansClassifier`.
+//!
+//! Columns listed with `TreeParams::categorical` hold category codes
+//! rather than ordered values, and are split into two subsets of
+//! categories instead of at a threshold. At each node the categories
+//! are ordered by their mean target, or for classification by their
+//! proportion of the most frequent class at the node, and the best
+//! split of that order is taken. This finds the best subset for
+//! regression and binary classification without one-hot encoding the
+//! column. Categories not seen at a node in training go right.
+//!
+//! Missing values, given as NaN, need no imputation. Each split learns
+//! which branch the rows missing its feature go down, the one giving
+//! the larger decrease in impurity. Where no training rows at a node
+//! were missing the feature, they go down the branch with more rows.
+//!
+//! Both trees can be trained with a weight for each row through
+//! `WeightedSupModel`. The weights scale each row's share of the class
+//! proportions, mean targets and impurities, so a row of weight two
+//! counts as two copies of it. `min_samples_split` and
+//! `min_samples_leaf` still count rows.
+//!
+//! A tree grown until its leaves are pure memorizes the training set.
+//! Setting `ccp_alpha` prunes it by minimal cost-complexity: subtrees
+//! are collapsed, weakest link first, while the increase in the
+//! impurity of the leaves per leaf removed is at most `ccp_alpha`. The
+//! values of `ccp_alpha` at which the tree changes are given by
+//! `Tree::pruning_path`, and can be searched by cross validation.
+//!
+//! With the `rand` feature, each split can consider a random subset
+//! of the features with `TreeParams::max_features`, and draw a random
+//! threshold for each feature rather than searching with
+//! `Splitter::Random`. These are the building blocks of the forests in
+//! the `forest` module. The random numbers are drawn from
+//! `rand_utils::rng`.
+//!
+//! Interpretability is much of the reason to choose a tree, so a
+//! trained tree can be exported as a Graphviz DOT graph with
+//! `Tree::to_dot`, or as a plain text listing of its rules with
+//! `Tree::to_text`. Both show the feature thresholds and the class
+//! distribution or mean target at each node.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::learning::SupModel;
+//! use rusty_machine::learning::tree::{DecisionTreeClassifier, TreeParams};
+//! use rusty_machine::linalg::matrix::Matrix;
+//! use rusty_machine::linalg::vector::Vector;
+//!
+//! let inputs = Matrix::new(6, 2, vec![1.0, 5.0, 2.0, 4.0, 3.0, 6.0,
+//!                                     7.0, 5.0, 8.0, 4.0, 9.0, 6.0]);
+//! let targets = Vector::new(vec![0, 0, 0, 1, 1, 1]);
+//!
+//! let mut model = DecisionTreeClassifier::new(TreeParams::default().max_depth(3));
+//! model.train(&inputs, &targets);
+//!
+//! let outputs = model.predict(&Matrix::new(2, 2, vec![2.5, 5.0, 8.5, 5.0]));
+//! assert_eq!(outputs.data(), &[0, 1]);
+//!
+//! // Print the rules of the trained tree.
+//! println!("{}", model.tree().unwrap().to_text(Some(&["width", "height"])));
+//! ```
+
+use std::fmt;
+use std::fmt::Write;
+
+use learning::{SupModel, WeightedSupModel};
+use linalg::matrix::{BaseMatrix, Matrix};
+use linalg::vector::Vector;
+use linalg::utils;
+
+#[cfg(feature = "rand")]
+use learning::toolkit::rand_utils;
+#[cfg(feature = "rand")]
+use rand::{Rng, StdRng};
+
+/// The number of features considered at each split.
+///
+/// Requires the `rand` feature.
+#[cfg(feature = "rand")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MaxFeatures {
+    /// Every feature.
+    All,
+    /// The square root of the number of features, rounded down.
+    Sqrt,
+    /// A fixed number of features, or all of them if there are fewer.
+    Count(usize),
+}
+
+#[cfg(feature = "rand")]
+impl MaxFeatures {
+    /// The number of features considered out of `n_features`.
+    pub fn count(&self, n_features: usize) -> usize {
+        match *self {
+            MaxFeatures::All => n_features,
+            MaxFeatures::Sqrt => ((n_features as f64).sqrt() as usize).max(1),
+            MaxFeatures::Count(k) => k.min(n_features),
+        }
+    }
+}
+
+/// How the threshold of a split on a feature is chosen.
+///
+/// Categorical columns are always searched for their best split.
+///
+/// Requires the `rand` feature.
+#[cfg(feature = "rand")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Splitter {
+    /// The threshold which most reduces the impurity.
+    Best,
+    /// A threshold drawn uniformly between the smallest and largest
+    /// values of the feature at the node, as in extremely randomized
+    /// trees.
+    Random,
+}
+
+/// The settings for growing a tree.
+///
+/// The settings are chained from the defaults.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::learning::tree::TreeParams;
+///
+/// let params = TreeParams::default().max_depth(4).min_samples_leaf(5);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeParams {
+    /// The greatest depth of a leaf, or `None` to split until the
+    /// leaves are pure.
+    pub max_depth: Option<usize>,
+    /// The fewest rows a node needs to be split.
+    pub min_samples_split: usize,
+    /// The fewest rows in each child of a split.
+    pub min_samples_leaf: usize,
+    /// The cost-complexity of a leaf, zero for no pruning.
+    pub ccp_alpha: f64,
+    /// The columns which hold categories.
+    pub categorical: Vec<usize>,
+    /// The number of features considered at each split.
+    #[cfg(feature = "rand")]
+    pub max_features: MaxFeatures,
+    /// How the threshold of each split is chosen.
+    #[cfg(feature = "rand")]
+    pub splitter: Splitter,
+}
+
+/// The default tree settings.
+///
+/// The defaults are:
+///
+/// - max_depth = None
+/// - min_samples_split = 2
+/// - min_samples_leaf = 1
+/// - ccp_alpha = 0
+/// - categorical = [] (no categorical columns)
+/// - max_features = All
+/// - splitter = Best
+impl Default for TreeParams {
+    fn default() -> TreeParams {
+        TreeParams {
+            max_depth: None,
+            min_samples_split: 2,
+            min_samples_leaf: 1,
+            ccp_alpha: 0f64,
+            categorical: Vec::new(),
+            #[cfg(feature = "rand")]
+            max_features: MaxFeatures::All,
+            #[cfg(feature = "rand")]
+            splitter: Splitter::Best,
+        }
+    }
+}
+
+impl TreeParams {
+    /// Sets the greatest depth of a leaf.
+    pub fn max_depth(mut self, max_depth: usize) -> TreeParams {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Sets the fewest rows a node needs to be split.
+    ///
+    /// # Panics
+    ///
+    /// - The number is less than two.
+    pub fn min_samples_split(mut self, min_samples_split: usize) -> TreeParams {
+        assert!(min_samples_split >= 2, "A split needs at least two rows.");
+        self.min_samples_split = min_samples_split;
+        self
+    }
+
+    /// Sets the fewest rows in each child of a split.
+    ///
+    /// # Panics
+    ///
+    /// - The number is zero.
+    pub fn min_samples_leaf(mut self, min_samples_leaf: usize) -> TreeParams {
+        assert!(min_samples_leaf > 0, "A leaf needs at least one row.");
+        self.min_samples_leaf = min_samples_leaf;
+        self
+    }
+
+    /// Sets the cost-complexity of a leaf used to prune the tree.
+    ///
+    /// # Panics
+    ///
+    /// - The alpha is negative.
+    pub fn ccp_alpha(mut self, ccp_alpha: f64) -> TreeParams {
+        assert!(ccp_alpha >= 0f64, "The pruning alpha must be non-negative.");
+        self.ccp_alpha = ccp_alpha;
+        self
+    }
+
+    /// Sets the columns which hold categories.
+    ///
+    /// The values of a categorical column are codes, such as those
+    /// given by a `LabelEncoder`, and only their equality is used.
+    pub fn categorical(mut self, columns: &[usize]) -> TreeParams {
+        self.categorical = columns.to_vec();
+        self
+    }
+
+    /// Sets the number of features considered at each split.
+    ///
+    /// Requires the `rand` feature.
+    ///
+    /// # Panics
+    ///
+    /// - The number is a count of zero.
+    #[cfg(feature = "rand")]
+    pub fn max_features(mut self, max_features: MaxFeatures) -> TreeParams {
+        assert!(max_features != MaxFeatures::Count(0),
+                "At least one feature must be considered.");
+        self.max_features = max_features;
+        self
+    }
+
+    /// Sets how the threshold of each split is chosen.
+    ///
+    /// Requires the `rand` feature.
+    #[cfg(feature = "rand")]
+    pub fn splitter(mut self, splitter: Splitter) -> TreeParams {
+        self.splitter = splitter;
+        self
+    }
+}
+
+/// The test of a split on a feature.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Split {
+    /// Rows whose feature is at most the threshold go left.
+    Threshold(f64),
+    /// Rows whose feature is one of the categories go left.
+    Categories(Vec<f64>),
+}
+
+impl Split {
+    /// Returns true if a row with the feature value goes left.
+    pub fn goes_left(&self, x: f64) -> bool {
+        match *self {
+            Split::Threshold(threshold) => x <= threshold,
+            Split::Categories(ref categories) => categories.contains(&x),
+        }
+    }
+
+    /// The conditions of the left and right branches, for the exports.
+    fn conditions(&self, name: &str) -> (String, String) {
+        match *self {
+            Split::Threshold(threshold) => {
+                (format!("{} <= {}", name, threshold), format!("{} > {}", name, threshold))
+            }
+            Split::Categories(ref categories) => {
+                let set = categories.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", ");
+                (format!("{} in {{{}}}", name, set), format!("{} not in {{{}}}", name, set))
+            }
+        }
+    }
+}
+
+/// The split of an internal node.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Branch {
+    /// The column of the inputs which is tested.
+    pub feature: usize,
+    /// The test on the feature.
+    pub split: Split,
+    /// The index of the left child.
+    pub left: usize,
+    /// The index of the right child.
+    pub right: usize,
+    /// Whether rows missing the feature go left.
+    pub missing_left: bool,
+}
+
+impl Branch {
+    /// Returns true if a row with the feature value goes left.
+    ///
+    /// NaN values go down the default branch.
+    pub fn goes_left(&self, x: f64) -> bool {
+        if x.is_nan() {
+            self.missing_left
+        } else {
+            self.split.goes_left(x)
+        }
+    }
+
+    /// The index of the child a row goes to.
+    pub fn child(&self, row: &[f64]) -> usize {
+        if self.goes_left(row[self.feature]) {
+            self.left
+        } else {
+            self.right
+        }
+    }
+}
+
+/// A node of a tree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Node {
+    /// The prediction of the node.
+    ///
+    /// For a classification tree this holds the proportion of each
+    /// class among the training rows at the node, and for a regression
+    /// tree their mean target.
+    pub value: Vec<f64>,
+    /// The number of training rows at the node.
+    pub samples: usize,
+    /// The total weight of the training rows at the node, which is the
+    /// number of rows for unweighted training.
+    pub weight: f64,
+    /// The impurity of the training rows at the node, the Gini
+    /// impurity for classes and the variance for targets.
+    pub impurity: f64,
+    /// The split of the node, or `None` for a leaf.
+    pub branch: Option<Branch>,
+}
+
+/// The sequence of trees given by weakest-link pruning.
+///
+/// Entry `i` holds the smallest `ccp_alpha` which prunes the tree to
+/// the `i`th tree of the sequence, and the total impurity of its
+/// leaves. The impurity of each leaf is weighted by its share of the
+/// weight of the training rows. The first tree is the unpruned tree, at alpha zero,
+/// and the last is the root alone.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PruningPath {
+    /// The effective alphas, in increasing order.
+    pub alphas: Vec<f64>,
+    /// The total leaf impurity of the tree pruned at each alpha.
+    pub impurities: Vec<f64>,
+}
+
+/// A trained decision tree.
+///
+/// The nodes are stored in a vector with the root first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tree {
+    nodes: Vec<Node>,
+    /// The number of classes, or `None` for a regression tree.
+    n_classes: Option<usize>,
+    n_features: usize,
+    /// Whether any training input was missing.
+    has_missing: bool,
+}
+
+impl Tree {
+    /// Grows a classification tree on the given rows of the inputs.
+    ///
+    /// Rows may be repeated, as in a bootstrap sample. Each row has the
+    /// weight given for it, or a weight of one if there are no weights.
+    ///
+    /// # Panics
+    ///
+    /// - There are no rows.
+    /// - A class is not less than `n_classes`.
+    /// - The weights are invalid, as for `check_weights`.
+    pub fn grow_classes(inputs: &Matrix<f64>,
+                        classes: &[usize],
+                        n_classes: usize,
+                        rows: &[usize],
+                        weights: Option<&[f64]>,
+                        params: &TreeParams)
+                        -> Tree {
+        assert!(classes.iter().all(|&c| c < n_classes),
+                "Classes must be less than the number of classes.");
+        Builder::new(inputs, Targets::Classes(classes, n_classes), weights, params).build(rows)
+    }
+
+    /// Grows a regression tree on the given rows of the inputs.
+    ///
+    /// Rows may be repeated, as in a bootstrap sample. Each row has the
+    /// weight given for it, or a weight of one if there are no weights.
+    ///
+    /// # Panics
+    ///
+    /// - There are no rows.
+    /// - The weights are invalid, as for `check_weights`.
+    pub fn grow_values(inputs: &Matrix<f64>,
+                       values: &[f64],
+                       rows: &[usize],
+                       weights: Option<&[f64]>,
+                       params: &TreeParams)
+                       -> Tree {
+        Builder::new(inputs, Targets::Values(values), weights, params).build(rows)
+    }
+
+    /// Constructs a regression tree from its nodes, with the root first.
+    ///
+    /// This is used by models which grow their own trees, such as the
+    /// `GradientBoostingRegressor`, so they share the exports. The
+    /// exports mark the default branch of each split for missing
+    /// values if `has_missing` is set.
+    ///
+    /// # Panics
+    ///
+    /// - There are no nodes.
+    /// - A child index is not after its parent or is out of range.
+    /// - A node value does not have one entry.
+    pub fn from_nodes(nodes: Vec<Node>, n_features: usize, has_missing: bool) -> Tree {
+        assert!(!nodes.is_empty(), "A tree needs at least one node.");
+        for (idx, node) in nodes.iter().enumerate() {
+            assert!(node.value.len() == 1, "Regression nodes must have one value.");
+            if let Some(ref branch) = node.branch {
+                assert!(branch.left > idx && branch.left < nodes.len() && branch.right > idx &&
+                        branch.right < nodes.len(),
+                        "Children must come after their parent.");
+            }
+        }
+
+        Tree {
+            nodes: nodes,
+            n_classes: None,
+            n_features: n_features,
+            has_missing: has_missing,
+        }
+    }
+
+    /// The nodes of the tree, with the root first.
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// The number of classes, or `None` for a regression tree.
+    pub fn n_classes(&self) -> Option<usize> {
+        self.n_classes
+    }
+
+    /// The number of input columns the tree was grown on.
+    pub fn n_features(&self) -> usize {
+        self.n_features
+    }
+
+    /// The number of leaves.
+    pub fn n_leaves(&self) -> usize {
+        self.nodes.iter().filter(|n| n.branch.is_none()).count()
+    }
+
+    /// The greatest depth of a leaf, with the root at depth zero.
+    pub fn depth(&self) -> usize {
+        self.node_depth(0)
+    }
+
+    fn node_depth(&self, idx: usize) -> usize {
+        match self.nodes[idx].branch {
+            Some(ref b) => 1 + self.node_depth(b.left).max(self.node_depth(b.right)),
+            None => 0,
+        }
+    }
+
+    /// The index of the leaf an input row reaches.
+    ///
+    /// # Panics
+    ///
+    /// - The row is shorter than a feature used by the tree.
+    pub fn leaf(&self, row: &[f64]) -> usize {
+        let mut idx = 0;
+        while let Some(ref branch) = self.nodes[idx].branch {
+            idx = branch.child(row);
+        }
+        idx
+    }
+
+    /// The prediction of the leaf an input row reaches.
+    pub fn value(&self, row: &[f64]) -> &[f64] {
+        &self.nodes[self.leaf(row)].value
+    }
+
+    /// Returns the tree pruned by minimal cost-complexity.
+    ///
+    /// The weakest link, the subtree whose collapse increases the leaf
+    /// impurity least per leaf removed, is collapsed into a leaf until
+    /// that increase exceeds `ccp_alpha`.
+    ///
+    /// # Panics
+    ///
+    /// - The alpha is negative.
+    pub fn prune(&self, ccp_alpha: f64) -> Tree {
+        assert!(ccp_alpha >= 0f64, "The pruning alpha must be non-negative.");
+
+        let mut tree = self.clone();
+        while let (_, Some((idx, alpha))) = tree.weakest_link() {
+            if alpha > ccp_alpha {
+                break;
+            }
+            tree.nodes[idx].branch = None;
+        }
+        tree.compact()
+    }
+
+    /// Computes the pruning path of the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::SupModel;
+    /// use rusty_machine::learning::tree::DecisionTreeRegressor;
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::vector::Vector;
+    ///
+    /// let inputs = Matrix::new(4, 1, vec![1.0, 2.0, 3.0, 4.0]);
+    /// let targets = Vector::new(vec![0.0, 1.0, 4.0, 5.0]);
+    ///
+    /// let mut model = DecisionTreeRegressor::default();
+    /// model.train(&inputs, &targets);
+    ///
+    /// let tree = model.tree().unwrap();
+    /// let path = tree.pruning_path();
+    /// assert_eq!(path.alphas, vec![0.0, 0.125, 4.0]);
+    /// assert_eq!(path.impurities, vec![0.0, 0.25, 4.25]);
+    ///
+    /// // Any alpha from the last entry prunes the tree to its root.
+    /// assert_eq!(tree.prune(4.0).n_leaves(), 1);
+    /// ```
+    pub fn pruning_path(&self) -> PruningPath {
+        let mut tree = self.clone();
+        let (impurity, mut weakest) = tree.weakest_link();
+        let mut path = PruningPath {
+            alphas: vec![0f64],
+            impurities: vec![impurity],
+        };
+
+        while let Some((idx, alpha)) = weakest {
+            tree.nodes[idx].branch = None;
+            let (impurity, next) = tree.weakest_link();
+
+            // Links as weak as the last collapse at the same alpha.
+            if alpha <= *path.alphas.last().unwrap() {
+                *path.impurities.last_mut().unwrap() = impurity;
+            } else {
+                path.alphas.push(alpha);
+                path.impurities.push(impurity);
+            }
+            weakest = next;
+        }
+
+        path
+    }
+
+    /// The total weighted impurity of the leaves, and the internal node
+    /// with the smallest effective alpha with its alpha.
+    fn weakest_link(&self) -> (f64, Option<(usize, f64)>) {
+        let mut weakest = None;
+        let (impurity, _) = self.find_weakest(0, &mut weakest);
+        (impurity, weakest)
+    }
+
+    /// The weighted leaf impurity and number of leaves of the subtree.
+    fn find_weakest(&self, idx: usize, weakest: &mut Option<(usize, f64)>) -> (f64, usize) {
+        let node = &self.nodes[idx];
+        let node_impurity = node.impurity * node.weight / self.nodes[0].weight;
+
+        match node.branch {
+            Some(ref branch) => {
+                let (left, left_leaves) = self.find_weakest(branch.left, weakest);
+                let (right, right_leaves) = self.find_weakest(branch.right, weakest);
+                let (impurity, leaves) = (left + right, left_leaves + right_leaves);
+
+                let alpha = (node_impurity - impurity) / (leaves - 1) as f64;
+                if weakest.is_none_or(|w| alpha < w.1) {
+                    *weakest = Some((idx, alpha));
+                }
+                (impurity, leaves)
+            }
+            None => (node_impurity, 1),
+        }
+    }
+
+    /// Drops the nodes which are no longer reachable from the root.
+    fn compact(self) -> Tree {
+        let mut nodes = Vec::new();
+        self.copy_subtree(0, &mut nodes);
+        Tree { nodes: nodes, ..self }
+    }
+
+    fn copy_subtree(&self, idx: usize, nodes: &mut Vec<Node>) -> usize {
+        let new_idx = nodes.len();
+        nodes.push(Node { branch: None, ..self.nodes[idx].clone() });
+
+        if let Some(ref branch) = self.nodes[idx].branch {
+            let left = self.copy_subtree(branch.left, nodes);
+            let right = self.copy_subtree(branch.right, nodes);
+            nodes[new_idx].branch = Some(Branch {
+                left: left,
+                right: right,
+                ..branch.clone()
+            });
+        }
+
+        new_idx
+    }
+
+    /// Exports the tree as a Graphviz DOT graph.
+    ///
+    /// Each node shows its split, impurity, number of samples and
+    /// prediction. The features are named `x[j]` unless names are
+    /// given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::SupModel;
+    /// use rusty_machine::learning::tree::DecisionTreeRegressor;
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::vector::Vector;
+    ///
+    /// let inputs = Matrix::new(4, 1, vec![1.0, 2.0, 3.0, 4.0]);
+    /// let targets = Vector::new(vec![1.0, 1.0, 3.0, 3.0]);
+    ///
+    /// let mut model = DecisionTreeRegressor::default();
+    /// model.train(&inputs, &targets);
+    ///
+    /// let dot = model.tree().unwrap().to_dot(Some(&["size"]));
+    /// assert!(dot.starts_with("digraph Tree {"));
+    /// assert!(dot.contains("size <= 2.5"));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - There are fewer names than features.
+    pub fn to_dot(&self, feature_names: Option<&[&str]>) -> String {
+        let mut dot = String::from("digraph Tree {\nnode [shape=box];\n");
+
+        for (idx, node) in self.nodes.iter().enumerate() {
+            let mut label = String::new();
+            if let Some(ref branch) = node.branch {
+                let name = self.feature_name(feature_names, branch.feature);
+                label.push_str(&self.conditions(branch, &name).0);
+                label.push_str("\\n");
+            }
+            write!(label,
+                   "impurity = {:.4}\\nsamples = {}\\n{}",
+                   node.impurity,
+                   node.samples,
+                   self.describe_value(&node.value))
+                .unwrap();
+            writeln!(dot, "{} [label=\"{}\"];", idx, label).unwrap();
+
+            if let Some(ref branch) = node.branch {
+                writeln!(dot, "{} -> {} [label=\"True\"];", idx, branch.left).unwrap();
+                writeln!(dot, "{} -> {} [label=\"False\"];", idx, branch.right).unwrap();
+            }
+        }
+
+        dot.push('}');
+        dot
+    }
+
+    /// Exports the rules of the tree as indented plain text.
+    ///
+    /// Each line holds the condition of a branch, and each leaf its
+    /// prediction and number of samples. The features are named `x[j]`
+    /// unless names are given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::SupModel;
+    /// use rusty_machine::learning::tree::DecisionTreeClassifier;
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::vector::Vector;
+    ///
+    /// let inputs = Matrix::new(4, 1, vec![1.0, 2.0, 3.0, 4.0]);
+    /// let targets = Vector::new(vec![0, 0, 1, 1]);
+    ///
+    /// let mut model = DecisionTreeClassifier::default();
+    /// model.train(&inputs, &targets);
+    ///
+    /// let text = model.tree().unwrap().to_text(None);
+    /// assert_eq!(text,
+    ///            "|--- x[0] <= 2.5\n\
+    ///             |   |--- class = 0, proportions = [1.00, 0.00], samples = 2\n\
+    ///             |--- x[0] > 2.5\n\
+    ///             |   |--- class = 1, proportions = [0.00, 1.00], samples = 2\n");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - There are fewer names than features.
+    pub fn to_text(&self, feature_names: Option<&[&str]>) -> String {
+        let mut text = String::new();
+        self.write_rules(&mut text, feature_names, 0, 0);
+        text
+    }
+
+    fn write_rules(&self, text: &mut String, names: Option<&[&str]>, idx: usize, depth: usize) {
+        let indent = "|   ".repeat(depth);
+        let node = &self.nodes[idx];
+
+        match node.branch {
+            Some(ref branch) => {
+                let name = self.feature_name(names, branch.feature);
+                let (left, right) = self.conditions(branch, &name);
+                writeln!(text, "{}|--- {}", indent, left).unwrap();
+                self.write_rules(text, names, branch.left, depth + 1);
+                writeln!(text, "{}|--- {}", indent, right).unwrap();
+                self.write_rules(text, names, branch.right, depth + 1);
+            }
+            None => {
+                writeln!(text,
+                         "{}|--- {}, samples = {}",
+                         indent,
+                         self.describe_value(&node.value),
+                         node.samples)
+                    .unwrap();
+            }
+        }
+    }
+
+    /// The conditions of the branches, marking the default branch if
+    /// the training inputs had missing values.
+    fn conditions(&self, branch: &Branch, name: &str) -> (String, String) {
+        let (mut left, mut right) = branch.split.conditions(name);
+        if self.has_missing {
+            if branch.missing_left {
+                left.push_str(" or missing");
+            } else {
+                right.push_str(" or missing");
+            }
+        }
+        (left, right)
+    }
+
+    fn feature_name(&self, names: Option<&[&str]>, feature: usize) -> String {
+        match names {
+            Some(names) => {
+                assert!(names.len() >= self.n_features,
+                        "There must be a name for each feature.");
+                names[feature].to_string()
+            }
+            None => format!("x[{}]", feature),
+        }
+    }
+
+    /// The prediction of a node, for the exports.
+    fn describe_value(&self, value: &[f64]) -> String {
+        match self.n_classes {
+            Some(_) => {
+                let proportions = value.iter().map(|p| format!("{:.2}", p)).collect::<Vec<_>>();
+                format!("class = {}, proportions = [{}]",
+                        utils::argmax(value).0,
+                        proportions.join(", "))
+            }
+            None => format!("value = {:.4}", value[0]),
+        }
+    }
+}
+
+/// The targets a tree is grown on.
+#[derive(Clone, Copy)]
+enum Targets<'a> {
+    /// The class of each row and the number of classes.
+    Classes(&'a [usize], usize),
+    /// The target of each row.
+    Values(&'a [f64]),
+}
+
+/// Weighted sums over a set of rows, from which their impurity and
+/// prediction follow.
+#[derive(Clone)]
+struct Stats {
+    /// The number of rows.
+    n: f64,
+    /// The total weight of the rows.
+    weight: f64,
+    /// The weight of the rows of each class, empty for regression.
+    counts: Vec<f64>,
+    sum: f64,
+    sum_sq: f64,
+}
+
+impl Stats {
+    fn new(targets: Targets) -> Stats {
+        Stats {
+            n: 0f64,
+            weight: 0f64,
+            counts: match targets {
+                Targets::Classes(_, k) => vec![0f64; k],
+                Targets::Values(_) => Vec::new(),
+            },
+            sum: 0f64,
+            sum_sq: 0f64,
+        }
+    }
+
+    /// Adds the row with its weight or, with a negative sign, removes it.
+    fn add(&mut self, targets: Targets, row: usize, weight: f64, sign: f64) {
+        let w = sign * weight;
+        self.n += sign;
+        self.weight += w;
+        match targets {
+            Targets::Classes(classes, _) => self.counts[classes[row]] += w,
+            Targets::Values(values) => {
+                self.sum += w * values[row];
+                self.sum_sq += w * values[row] * values[row];
+            }
+        }
+    }
+
+    /// Adds or, with a negative sign, removes the rows of other stats.
+    fn combine(&mut self, other: &Stats, sign: f64) {
+        self.n += sign * other.n;
+        self.weight += sign * other.weight;
+        for (c, o) in self.counts.iter_mut().zip(&other.counts) {
+            *c += sign * o;
+        }
+        self.sum += sign * other.sum;
+        self.sum_sq += sign * other.sum_sq;
+    }
+
+    fn impurity(&self) -> f64 {
+        if self.weight <= 0f64 {
+            return 0f64;
+        }
+
+        if self.counts.is_empty() {
+            let mean = self.sum / self.weight;
+            (self.sum_sq / self.weight - mean * mean).max(0f64)
+        } else {
+            1f64 - self.counts.iter().map(|c| (c / self.weight) * (c / self.weight)).sum::<f64>()
+        }
+    }
+
+    fn value(&self) -> Vec<f64> {
+        if self.counts.is_empty() {
+            vec![self.sum / self.weight]
+        } else {
+            self.counts.iter().map(|c| c / self.weight).collect()
+        }
+    }
+}
+
+/// The best split found for a node.
+struct Candidate {
+    feature: usize,
+    split: Split,
+    missing_left: bool,
+    /// The decrease in the total impurity of the rows.
+    gain: f64,
+}
+
+/// Grows a tree node by node.
+struct Builder<'a> {
+    inputs: &'a Matrix<f64>,
+    targets: Targets<'a>,
+    /// The weight of each row, or `None` for unit weights.
+    weights: Option<&'a [f64]>,
+    params: &'a TreeParams,
+    nodes: Vec<Node>,
+    /// The generator for random splits, if the params ask for them.
+    #[cfg(feature = "rand")]
+    rng: Option<StdRng>,
+}
+
+impl<'a> Builder<'a> {
+    fn new(inputs: &'a Matrix<f64>,
+           targets: Targets<'a>,
+           weights: Option<&'a [f64]>,
+           params: &'a TreeParams)
+           -> Builder<'a> {
+        if let Some(weights) = weights {
+            check_weights(inputs, weights);
+        }
+
+        Builder {
+            inputs: inputs,
+            targets: targets,
+            weights: weights,
+            params: params,
+            nodes: Vec::new(),
+            #[cfg(feature = "rand")]
+            rng: if params.max_features != MaxFeatures::All || params.splitter == Splitter::Random {
+                Some(rand_utils::rng())
+            } else {
+                None
+            },
+        }
+    }
+
+    fn build(mut self, rows: &[usize]) -> Tree {
+        assert!(!rows.is_empty(), "A tree needs at least one row.");
+        assert!(self.params.categorical.iter().all(|&c| c < self.inputs.cols()),
+                "Categorical columns must be columns of the inputs.");
+
+        let inputs = self.inputs;
+        let has_missing = rows.iter().any(|&r| inputs.row(r).iter().any(|x| x.is_nan()));
+
+        self.grow(rows.to_vec(), 0);
+        let tree = Tree {
+            nodes: self.nodes,
+            n_classes: match self.targets {
+                Targets::Classes(_, k) => Some(k),
+                Targets::Values(_) => None,
+            },
+            n_features: self.inputs.cols(),
+            has_missing: has_missing,
+        };
+
+        if self.params.ccp_alpha > 0f64 {
+            tree.prune(self.params.ccp_alpha)
+        } else {
+            tree
+        }
+    }
+
+    /// Adds the row to the stats or, with a negative sign, removes it.
+    fn add(&self, stats: &mut Stats, row: usize, sign: f64) {
+        let weight = self.weights.map_or(1f64, |w| w[row]);
+        stats.add(self.targets, row, weight, sign);
+    }
+
+    /// Adds the node for the rows and its subtree, returning its index.
+    fn grow(&mut self, rows: Vec<usize>, depth: usize) -> usize {
+        let mut stats = Stats::new(self.targets);
+        for &r in &rows {
+            self.add(&mut stats, r, 1f64);
+        }
+
+        let idx = self.nodes.len();
+        self.nodes.push(Node {
+            value: stats.value(),
+            samples: rows.len(),
+            weight: stats.weight,
+            impurity: stats.impurity(),
+            branch: None,
+        });
+
+        let splittable = rows.len() >= self.params.min_samples_split &&
+                         self.params.max_depth.is_none_or(|d| depth < d) &&
+                         stats.impurity() > 0f64;
+        if !splittable {
+            return idx;
+        }
+
+        if let Some(candidate) = self.best_split(&rows, &stats) {
+            let mut branch = Branch {
+                feature: candidate.feature,
+                split: candidate.split,
+                left: 0,
+                right: 0,
+                missing_left: candidate.missing_left,
+            };
+            let (left_rows, right_rows): (Vec<usize>, Vec<usize>) =
+                rows.iter().partition(|&&r| branch.goes_left(self.inputs[[r, branch.feature]]));
+
+            branch.left = self.grow(left_rows, depth + 1);
+            branch.right = self.grow(right_rows, depth + 1);
+            self.nodes[idx].branch = Some(branch);
+        }
+
+        idx
+    }
+
+    /// The split of the rows which most reduces their impurity.
+    fn best_split(&mut self, rows: &[usize], stats: &Stats) -> Option<Candidate> {
+        let mut best: Option<Candidate> = None;
+
+        for feature in self.split_features() {
+            let candidate = if self.params.categorical.contains(&feature) {
+                self.best_categories(rows, stats, feature)
+            } else {
+                self.threshold_split(rows, stats, feature)
+            };
+
+            if let Some(candidate) = candidate {
+                if best.as_ref().is_none_or(|b| candidate.gain > b.gain) {
+                    best = Some(candidate);
+                }
+            }
+        }
+
+        best
+    }
+
+    /// The features considered for the next split.
+    fn split_features(&mut self) -> Vec<usize> {
+        let n = self.inputs.cols();
+
+        #[cfg(feature = "rand")]
+        {
+            if let Some(ref mut rng) = self.rng {
+                // The first k features of a random permutation.
+                let k = self.params.max_features.count(n);
+                let mut features = (0..n).collect::<Vec<_>>();
+                for i in 0..k {
+                    let j = rng.gen_range(i, n);
+                    features.swap(i, j);
+                }
+                features.truncate(k);
+                return features;
+            }
+        }
+
+        (0..n).collect()
+    }
+
+    /// The threshold split of the rows on a feature chosen by the
+    /// splitter.
+    fn threshold_split(&mut self, rows: &[usize], stats: &Stats, feature: usize) -> Option<Candidate> {
+        #[cfg(feature = "rand")]
+        {
+            if self.params.splitter == Splitter::Random {
+                return self.random_threshold(rows, stats, feature);
+            }
+        }
+
+        self.best_threshold(rows, stats, feature)
+    }
+
+    /// A split of the rows on a feature at a random threshold.
+    #[cfg(feature = "rand")]
+    fn random_threshold(&mut self, rows: &[usize], stats: &Stats, feature: usize) -> Option<Candidate> {
+        let (mut lo, mut hi) = (f64::INFINITY, f64::NEG_INFINITY);
+        for &r in rows {
+            let x = self.inputs[[r, feature]];
+            if !x.is_nan() {
+                lo = lo.min(x);
+                hi = hi.max(x);
+            }
+        }
+        if lo >= hi {
+            return None;
+        }
+
+        let u: f64 = self.rng.as_mut().unwrap().gen();
+        let threshold = lo + u * (hi - lo);
+
+        let mut left = Stats::new(self.targets);
+        let mut right = Stats::new(self.targets);
+        let mut missing = Stats::new(self.targets);
+        for &r in rows {
+            let x = self.inputs[[r, feature]];
+            if x.is_nan() {
+                self.add(&mut missing, r, 1f64);
+            } else if x <= threshold {
+                self.add(&mut left, r, 1f64);
+            } else {
+                self.add(&mut right, r, 1f64);
+            }
+        }
+
+        let total = stats.weight * stats.impurity();
+        self.split_gain(total, &left, &right, &missing).map(|(gain, missing_left)| {
+            Candidate {
+                feature: feature,
+                split: Split::Threshold(threshold),
+                missing_left: missing_left,
+                gain: gain,
+            }
+        })
+    }
+
+    /// The best threshold split of the rows on a feature.
+    fn best_threshold(&self, rows: &[usize], stats: &Stats, feature: usize) -> Option<Candidate> {
+        let (sorted, missing) = self.sorted_values(rows, feature);
+
+        let total = stats.weight * stats.impurity();
+        let mut left = Stats::new(self.targets);
+        let mut right = stats.clone();
+        right.combine(&missing, -1f64);
+        let mut best: Option<Candidate> = None;
+
+        for i in 0..sorted.len().saturating_sub(1) {
+            self.add(&mut left, sorted[i].1, 1f64);
+            self.add(&mut right, sorted[i].1, -1f64);
+
+            let (x, next) = (sorted[i].0, sorted[i + 1].0);
+            if x == next {
+                continue;
+            }
+
+            if let Some((gain, missing_left)) = self.split_gain(total, &left, &right, &missing) {
+                if best.as_ref().is_none_or(|b| gain > b.gain) {
+                    best = Some(Candidate {
+                        feature: feature,
+                        split: Split::Threshold(midpoint(x, next)),
+                        missing_left: missing_left,
+                        gain: gain,
+                    });
+                }
+            }
+        }
+
+        best
+    }
+
+    /// The rows with the feature present, sorted by the feature, and
+    /// the stats of the rows missing it.
+    fn sorted_values(&self, rows: &[usize], feature: usize) -> (Vec<(f64, usize)>, Stats) {
+        let mut missing = Stats::new(self.targets);
+        let mut sorted = Vec::with_capacity(rows.len());
+        for &r in rows {
+            let x = self.inputs[[r, feature]];
+            if x.is_nan() {
+                self.add(&mut missing, r, 1f64);
+            } else {
+                sorted.push((x, r));
+            }
+        }
+
+        sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        (sorted, missing)
+    }
+
+    /// The gain of splitting the present rows into left and right, with
+    /// the missing rows sent the way which gives more gain, and whether
+    /// that is left.
+    ///
+    /// Returns `None` if either side would have too few rows or no
+    /// weight.
+    fn split_gain(&self,
+                  total: f64,
+                  left: &Stats,
+                  right: &Stats,
+                  missing: &Stats)
+                  -> Option<(f64, bool)> {
+        let min_leaf = self.params.min_samples_leaf as f64;
+        let gain = |l: &Stats, r: &Stats| if l.n < min_leaf || r.n < min_leaf ||
+                                             l.weight <= 0f64 ||
+                                             r.weight <= 0f64 {
+            None
+        } else {
+            Some(total - l.weight * l.impurity() - r.weight * r.impurity())
+        };
+
+        if missing.n == 0f64 {
+            return gain(left, right).map(|g| (g, left.weight >= right.weight));
+        }
+
+        let mut with_left = left.clone();
+        with_left.combine(missing, 1f64);
+        let mut with_right = right.clone();
+        with_right.combine(missing, 1f64);
+
+        match (gain(&with_left, right), gain(left, &with_right)) {
+            (Some(l), Some(r)) => Some(if l >= r { (l, true) } else { (r, false) }),
+            (Some(l), None) => Some((l, true)),
+            (None, Some(r)) => Some((r, false)),
+            (None, None) => None,
+        }
+    }
+
+    /// The best split of the rows into two sets of categories of a
+    /// feature.
+    fn best_categories(&self, rows: &[usize], stats: &Stats, feature: usize) -> Option<Candidate> {
+        let (sorted, missing) = self.sorted_values(rows, feature);
+
+        // The stats of the rows of each category.
+        let mut groups: Vec<(f64, Stats)> = Vec::new();
+        for &(x, r) in &sorted {
+            if groups.last().is_none_or(|g| g.0 != x) {
+                groups.push((x, Stats::new(self.targets)));
+            }
+            self.add(&mut groups.last_mut().unwrap().1, r, 1f64);
+        }
+
+        let majority = match self.targets {
+            Targets::Classes(..) => utils::argmax(&stats.counts).0,
+            Targets::Values(_) => 0,
+        };
+        // Categories of zero weight go first.
+        let key = |g: &Stats| if g.weight <= 0f64 {
+            f64::NEG_INFINITY
+        } else if g.counts.is_empty() {
+            g.sum / g.weight
+        } else {
+            g.counts[majority] / g.weight
+        };
+        groups.sort_by(|a, b| key(&a.1).partial_cmp(&key(&b.1)).unwrap());
+
+        let total = stats.weight * stats.impurity();
+        let mut left = Stats::new(self.targets);
+        let mut right = stats.clone();
+        right.combine(&missing, -1f64);
+        let mut best: Option<(usize, f64, bool)> = None;
+
+        for (i, group) in groups[..groups.len().saturating_sub(1)].iter().enumerate() {
+            left.combine(&group.1, 1f64);
+            right.combine(&group.1, -1f64);
+
+            if let Some((gain, missing_left)) = self.split_gain(total, &left, &right, &missing) {
+                if best.is_none_or(|b| gain > b.1) {
+                    best = Some((i, gain, missing_left));
+                }
+            }
+        }
+
+        best.map(|(i, gain, missing_left)| {
+            let mut categories = groups[..i + 1].iter().map(|g| g.0).collect::<Vec<_>>();
+            categories.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            Candidate {
+                feature: feature,
+                split: Split::Categories(categories),
+                missing_left: missing_left,
+                gain: gain,
+            }
+        })
+    }
+}
+
+/// A threshold between two values which keeps the lower value on the
+/// left.
+fn midpoint(x: f64, next: f64) -> f64 {
+    let mid = x + (next - x) / 2f64;
+    if mid < next { mid } else { x }
+}
+
+/// Checks the inputs and targets before training.
+fn check_training_data(inputs: &Matrix<f64>, n_targets: usize) {
+    assert!(inputs.rows() == n_targets,
+            "Inputs and targets have different lengths.");
+    assert!(inputs.rows() > 0, "Cannot train on no data.");
+}
+
+/// Checks the weights of the training rows.
+///
+/// # Panics
+///
+/// - There is not one weight per input row.
+/// - A weight is negative or not finite.
+/// - The weights sum to zero.
+pub fn check_weights(inputs: &Matrix<f64>, weights: &[f64]) {
+    assert!(inputs.rows() == weights.len(),
+            "Weights must have one entry per sample.");
+    assert!(weights.iter().all(|&w| w >= 0f64 && w.is_finite()),
+            "Weights must be non-negative and finite.");
+    assert!(weights.iter().sum::<f64>() > 0f64,
+            "Weights must not all be zero.");
+}
+
+/// Checks the inputs match the tree before prediction.
+fn trained_tree<'a>(tree: &'a Option<Tree>, inputs: &Matrix<f64>) -> &'a Tree {
+    match *tree {
+        Some(ref tree) => {
+            assert!(inputs.cols() == tree.n_features,
+                    "Input columns do not match the trained tree.");
+            tree
+        }
+        None => panic!("The model has not been trained."),
+    }
+}
+
+/// A decision tree for classification.
+///
+/// The targets are class labels `0..k`, and the tree predicts the most
+/// frequent class in each leaf.
+#[derive(Clone, Default)]
+pub struct DecisionTreeClassifier {
+    params: TreeParams,
+    tree: Option<Tree>,
+}
+
+impl fmt::Debug for DecisionTreeClassifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DecisionTreeClassifier")
+            .field("params", &self.params)
+            .field("trained", &self.tree.is_some())
+            .finish()
+    }
+}
+
+impl DecisionTreeClassifier {
+    /// Constructs an untrained classifier with the tree settings.
+    pub fn new(params: TreeParams) -> DecisionTreeClassifier {
+        DecisionTreeClassifier {
+            params: params,
+            tree: None,
+        }
+    }
+
+    /// The trained tree.
+    ///
+    /// Returns None if the model has not been trained.
+    pub fn tree(&self) -> Option<&Tree> {
+        self.tree.as_ref()
+    }
+
+    /// Predicts the probability of each class, one row per input row.
+    ///
+    /// The probabilities are the class proportions of the training rows
+    /// in the leaf each input reaches.
+    ///
+    /// # Panics
+    ///
+    /// - The model has not been trained.
+    /// - The inputs do not have the columns the model was trained on.
+    pub fn predict_proba(&self, inputs: &Matrix<f64>) -> Matrix<f64> {
+        let tree = trained_tree(&self.tree, inputs);
+        let n_classes = tree.n_classes.unwrap();
+
+        let mut data = Vec::with_capacity(inputs.rows() * n_classes);
+        for i in 0..inputs.rows() {
+            data.extend_from_slice(tree.value(inputs.row(i)));
+        }
+        Matrix::new(inputs.rows(), n_classes, data)
+    }
+}
+
+impl SupModel<Matrix<f64>, Vector<usize>> for DecisionTreeClassifier {
+    /// Grows the tree on the inputs and class labels.
+    ///
+    /// # Panics
+    ///
+    /// - The inputs and targets have different lengths.
+    /// - There are no rows.
+    fn train(&mut self, inputs: &Matrix<f64>, targets: &Vector<usize>) {
+        check_training_data(inputs, targets.size());
+
+        let n_classes = targets.data().iter().max().unwrap() + 1;
+        let rows = (0..inputs.rows()).collect::<Vec<_>>();
+        self.tree = Some(Tree::grow_classes(inputs, targets.data(), n_classes, &rows, None, &self.params));
+    }
+
+    /// Predicts the most probable class of each input row.
+    ///
+    /// # Panics
+    ///
+    /// - The model has not been trained.
+    /// - The inputs do not have the columns the model was trained on.
+    fn predict(&self, inputs: &Matrix<f64>) -> Vector<usize> {
+        let tree = trained_tree(&self.tree, inputs);
+        Vector::new((0..inputs.rows())
+                        .map(|i| utils::argmax(tree.value(inputs.row(i))).0)
+                        .collect::<Vec<_>>())
+    }
+}
+
+impl WeightedSupModel<Matrix<f64>, Vector<usize>> for DecisionTreeClassifier {
+    /// Grows the tree on the inputs and class labels, with the class
+    /// proportions and impurities weighted by the sample weights.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::{SupModel, WeightedSupModel};
+    /// use rusty_machine::learning::tree::{DecisionTreeClassifier, TreeParams};
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::vector::Vector;
+    ///
+    /// let inputs = Matrix::new(3, 1, vec![1.0, 1.0, 1.0]);
+    /// let targets = Vector::new(vec![0, 1, 1]);
+    ///
+    /// // The single row of the first class outweighs the other two.
+    /// let mut model = DecisionTreeClassifier::new(TreeParams::default());
+    /// model.train_weighted(&inputs, &targets, &Vector::new(vec![3.0, 1.0, 1.0]));
+    ///
+    /// assert_eq!(model.predict(&Matrix::new(1, 1, vec![1.0])).data(), &[0]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The inputs and targets have different lengths.
+    /// - There are no rows.
+    /// - The weights are invalid, as for `check_weights`.
+    fn train_weighted(&mut self, inputs: &Matrix<f64>, targets: &Vector<usize>, weights: &Vector<f64>) {
+        check_training_data(inputs, targets.size());
+
+        let n_classes = targets.data().iter().max().unwrap() + 1;
+        let rows = (0..inputs.rows()).collect::<Vec<_>>();
+        self.tree = Some(Tree::grow_classes(inputs,
+                                            targets.data(),
+                                            n_classes,
+                                            &rows,
+                                            Some(weights.data()),
+                                            &self.params));
+    }
+}
+
+/// A decision tree for regression.
+///
+/// The tree predicts the mean target of the training rows in each leaf.
+#[derive(Clone, Default)]
+pub struct DecisionTreeRegressor {
+    params: TreeParams,
+    tree: Option<Tree>,
+}
+
+impl fmt::Debug for DecisionTreeRegressor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DecisionTreeRegressor")
+            .field("params", &self.params)
+            .field("trained", &self.tree.is_some())
+            .finish()
+    }
+}
+
+impl DecisionTreeRegressor {
+    /// Constructs an untrained regressor with the tree settings.
+    pub fn new(params: TreeParams) -> DecisionTreeRegressor {
+        DecisionTreeRegressor {
+            params: params,
+            tree: None,
+        }
+    }
+
+    /// The trained tree.
+    ///
+    /// Returns None if the model has not been trained.
+    pub fn tree(&self) -> Option<&Tree> {
+        self.tree.as_ref()
+    }
+}
+
+impl SupModel<Matrix<f64>, Vector<f64>> for DecisionTreeRegressor {
+    /// Grows the tree on the inputs and targets.
+    ///
+    /// # Panics
+    ///
+    /// - The inputs and targets have different lengths.
+    /// - There are no rows.
+    fn train(&mut self, inputs: &Matrix<f64>, targets: &Vector<f64>) {
+        check_training_data(inputs, targets.size());
+
+        let rows = (0..inputs.rows()).collect::<Vec<_>>();
+        self.tree = Some(Tree::grow_values(inputs, targets.data(), &rows, None, &self.params));
+    }
+
+    /// Predicts the target of each input row.
+    ///
+    /// # Panics
+    ///
+    /// - The model has not been trained.
+    /// - The inputs do not have the columns the model was trained on.
+    fn predict(&self, inputs: &Matrix<f64>) -> Vector<f64> {
+        let tree = trained_tree(&self.tree, inputs);
+        Vector::new((0..inputs.rows()).map(|i| tree.value(inputs.row(i))[0]).collect::<Vec<_>>())
+    }
+}
+
+impl WeightedSupModel<Matrix<f64>, Vector<f64>> for DecisionTreeRegressor {
+    /// Grows the tree on the inputs and targets, with the leaf means
+    /// and impurities weighted by the sample weights.
+    ///
+    /// # Panics
+    ///
+    /// - The inputs and targets have different lengths.
+    /// - There are no rows.
+    /// - The weights are invalid, as for `check_weights`.
+    fn train_weighted(&mut self, inputs: &Matrix<f64>, targets: &Vector<f64>, weights: &Vector<f64>) {
+        check_training_data(inputs, targets.size());
+
+        let rows = (0..inputs.rows()).collect::<Vec<_>>();
+        self.tree = Some(Tree::grow_values(inputs,
+                                           targets.data(),
+                                           &rows,
+                                           Some(weights.data()),
+                                           &self.params));
+    }
+}
@@ -6,7 +6,7 @@
 //!
 //! ## Structure
 //!
-//! The crate is made up of two primary modules: learning and linalg.
+//! The crate is made up of four primary modules: learning, linalg, data and analysis.
 //!
 //! ### learning
 //!
@@ -21,6 +21,11 @@
 //! - Logistic Regression
 //! - Neural Networks (simple feed forward)
 //! - Support Vector Machines
+//! - Decision Trees
+//! - Gradient Boosting
+//! - Random Forests
+//! - Pipelines of data transforms and a model
+//! - Multi-output regression with any single target regressor
 //!
 //! ### linalg
 //!
@@ -28,6 +33,16 @@
 //! This module is efficient but not state of the art. Development of this module
 //! is not a key focus as I'm waiting for a clear community winner.
 //!
+//! ### data
+//!
+//! The data module contains tools for preparing data for the learning
+//! modules, such as transforms which scale the input features.
+//!
+//! ### analysis
+//!
+//! The analysis module contains tools for evaluating trained models,
+//! such as cross validation and hyperparameter search.
+//!
 //! ---
 //!
 //! # Usage
@@ -79,13 +94,54 @@
 //! `let mut gp = GaussianProcess::default();`. Conversely, you could also implement
 //! your own kernels and mean functions by using the appropriate traits.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+#[macro_use]
+extern crate alloc;
 
-extern crate num as libnum;
+extern crate num_traits as libnum;
+#[cfg(feature = "rand")]
 extern crate rand;
+#[cfg(feature = "std")]
+extern crate num_complex;
+
+#[cfg(feature = "ndarray")]
+extern crate ndarray;
+#[cfg(feature = "nalgebra")]
+extern crate nalgebra;
+#[cfg(feature = "parallel")]
+extern crate rayon;
+#[cfg(feature = "arrow")]
+extern crate arrow_array;
+#[cfg(feature = "arrow")]
+extern crate arrow_schema;
+#[cfg(feature = "parquet")]
+extern crate parquet;
+#[cfg(feature = "polars")]
+extern crate polars;
+#[cfg(feature = "gpu")]
+extern crate wgpu;
+#[cfg(feature = "gpu")]
+extern crate pollster;
+
+/// Stand-in for the parts of `std` used by the crate when built
+/// without the `std` feature.
+#[cfg(not(feature = "std"))]
+mod std {
+    pub use core::*;
+    pub use alloc::{sync, vec};
+}
 
 /// Module for linear algebra.
 pub mod linalg {
 
+    /// Complex number type, usable as a `Matrix` element.
+    ///
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub use num_complex::Complex;
+
     /// Trait for linear algebra metrics.
     ///
     /// Currently only implements basic euclidean norm.
@@ -98,20 +154,55 @@ pub mod linalg {
     pub mod matrix;
     pub mod vector;
     pub mod utils;
+    pub mod expr;
     pub mod macros;
+
+    #[cfg(feature = "ndarray")]
+    pub mod ndarray_convert;
+    #[cfg(feature = "nalgebra")]
+    pub mod nalgebra_convert;
+    #[cfg(feature = "gpu")]
+    pub mod gpu;
 }
 
 /// Module for machine learning.
 pub mod learning {
+    use std::vec::Vec;
+
+    use linalg::matrix::Matrix;
+    use linalg::vector::Vector;
+
+    #[cfg(feature = "std")]
     pub mod glm;
     pub mod lin_reg;
     pub mod logistic_reg;
     pub mod k_means;
     pub mod nnet;
     pub mod gp;
+    #[cfg(feature = "std")]
     pub mod svm;
+    #[cfg(feature = "std")]
+    pub mod tree;
+    #[cfg(feature = "std")]
+    pub mod gbm;
+    #[cfg(feature = "rand")]
+    pub mod forest;
+    #[cfg(feature = "std")]
+    pub mod pipeline;
+    pub mod multi_output;
+    #[cfg(feature = "std")]
+    pub mod transformed_target;
+    pub mod chunked;
+    #[cfg(feature = "onnx")]
+    pub mod onnx;
 
     /// Trait for supervised model.
+    ///
+    /// Prediction only borrows the model, so a trained model can be
+    /// shared between threads with an `Arc`. The models in this crate
+    /// are `Send + Sync`. Their only interior mutability is the training
+    /// callbacks held by some optimizers, which are behind a `Mutex` and
+    /// are not used by prediction.
     pub trait SupModel<T,U> {
 
         /// Predict output from inputs.
@@ -121,6 +212,16 @@ pub mod learning {
         fn train(&mut self, inputs: &T, targets: &U);
     }
 
+    /// Trait for supervised models which can weight each training sample.
+    ///
+    /// Samples with larger weights contribute more to the training cost.
+    /// Training with unit weights is equivalent to `SupModel::train`.
+    pub trait WeightedSupModel<T, U>: SupModel<T, U> {
+
+        /// Train the model using inputs, targets and a weight for each sample.
+        fn train_weighted(&mut self, inputs: &T, targets: &U, weights: &Vector<f64>);
+    }
+
     /// Trait for unsupervised model.
     pub trait UnSupModel<T, U> {
 
@@ -131,8 +232,138 @@ pub mod learning {
         fn train(&mut self, inputs: &T);
     }
 
+    /// Trait for supervised models which can learn from batches of data.
+    ///
+    /// Each batch updates the model from its current state, so data
+    /// which does not fit in memory can be streamed through the model.
+    pub trait IncrementalModel<T, U>: SupModel<T, U> {
+
+        /// Update the model using a batch of inputs and targets.
+        fn train_incremental(&mut self, inputs: &T, targets: &U);
+    }
+
+    /// Trait for unsupervised models which can learn from batches of data.
+    pub trait IncrementalUnSupModel<T, U>: UnSupModel<T, U> {
+
+        /// Update the model using a batch of inputs.
+        fn train_incremental(&mut self, inputs: &T);
+    }
+
+    /// Trait for comparing the learned parameters of models.
+    ///
+    /// Training is rarely exactly reproducible in floating point, so
+    /// models are compared up to a tolerance rather than with
+    /// `PartialEq`. Only the learned parameters are compared, not the
+    /// hyperparameters.
+    pub trait ApproxEq {
+
+        /// Returns true if both models are untrained, or if both are
+        /// trained and each parameter differs by at most `tol`.
+        fn approx_eq(&self, other: &Self, tol: f64) -> bool;
+    }
+
+    /// Compares optional parameters for `ApproxEq`.
+    fn params_approx_eq(a: Option<&[f64]>, b: Option<&[f64]>, tol: f64) -> bool {
+        match (a, b) {
+            (None, None) => true,
+            (Some(a), Some(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(x, y)| (x - y).abs() <= tol)
+            }
+            _ => false,
+        }
+    }
+
+    /// A named group of the learned parameters of a model.
+    pub struct ParamGroup {
+        /// The name of the group, such as `"weights"` or `"intercept"`.
+        pub name: &'static str,
+        /// The layer the group belongs to, for models with layers.
+        pub layer: Option<usize>,
+        /// The parameters of the group.
+        ///
+        /// Vectors of parameters are stored as a single column.
+        pub values: Matrix<f64>,
+    }
+
+    impl ParamGroup {
+        /// Constructs a group which belongs to no layer.
+        pub fn new(name: &'static str, values: Matrix<f64>) -> ParamGroup {
+            ParamGroup {
+                name: name,
+                layer: None,
+                values: values,
+            }
+        }
+
+        /// Constructs a group belonging to a layer.
+        pub fn in_layer(name: &'static str, layer: usize, values: Matrix<f64>) -> ParamGroup {
+            ParamGroup {
+                name: name,
+                layer: Some(layer),
+                values: values,
+            }
+        }
+    }
+
+    /// Trait for models which expose their learned parameters.
+    ///
+    /// The parameters are split into named groups, so that tools for
+    /// serialization, regularization or inspection can handle any
+    /// model without knowing its type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::learning::{ModelParams, SupModel};
+    /// use rusty_machine::learning::lin_reg::LinRegressor;
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::vector::Vector;
+    ///
+    /// let mut model = LinRegressor::default();
+    /// assert!(model.param_groups().is_empty());
+    ///
+    /// let inputs = Matrix::new(3, 1, vec![1.0, 2.0, 3.0]);
+    /// model.train(&inputs, &Vector::new(vec![3.0, 5.0, 7.0]));
+    ///
+    /// for group in model.param_groups() {
+    ///     println!("{}: {:?}", group.name, group.values.data());
+    /// }
+    /// assert_eq!(model.num_params(), 2);
+    /// ```
+    pub trait ModelParams {
+
+        /// The groups of learned parameters.
+        ///
+        /// Returns an empty list if the model has not been trained.
+        fn param_groups(&self) -> Vec<ParamGroup>;
+
+        /// The total number of learned parameters.
+        fn num_params(&self) -> usize {
+            self.param_groups().iter().map(|g| g.values.data().len()).sum()
+        }
+    }
+
+    /// The groups of a linear model whose parameters hold the
+    /// intercept followed by the coefficients.
+    fn linear_param_groups(params: Option<&Vector<f64>>) -> Vec<ParamGroup> {
+        match params {
+            Some(params) => {
+                let (intercept, coefs) = params.data().split_at(1);
+                vec![ParamGroup::new("intercept", Matrix::new(1, 1, intercept.to_vec())),
+                     ParamGroup::new("coefficients",
+                                     Matrix::new(coefs.len(), 1, coefs.to_vec()))]
+            }
+            None => Vec::new(),
+        }
+    }
+
     /// Module for optimization in machine learning setting.
     pub mod optim {
+        use std::vec::Vec;
+
+        use linalg::Metric;
+        use linalg::matrix::Matrix;
+        use linalg::vector::Vector;
 
         /// Trait for models which can be gradient-optimized.
         pub trait Optimizable {
@@ -147,6 +378,35 @@ pub mod learning {
                             inputs: &Self::Inputs,
                             targets: &Self::Targets)
                             -> (f64, Vec<f64>);
+
+            /// Whether regularization penalties apply to the parameter
+            /// at the given index.
+            ///
+            /// Defaults to every parameter. Models usually leave out
+            /// their intercepts.
+            fn is_penalized(&self, _idx: usize) -> bool {
+                true
+            }
+
+            /// The layer the parameter at the given index belongs to.
+            ///
+            /// Defaults to `None`, for models without layers. Used to
+            /// describe where training diverged.
+            fn param_layer(&self, _idx: usize) -> Option<usize> {
+                None
+            }
+
+            /// Compute the Hessian of the cost for the model.
+            ///
+            /// Returns `None` by default, for models which do not
+            /// support second order optimization.
+            fn compute_hessian(&self,
+                               _params: &[f64],
+                               _inputs: &Self::Inputs,
+                               _targets: &Self::Targets)
+                               -> Option<Matrix<f64>> {
+                None
+            }
         }
 
         /// Trait for optimization algorithms.
@@ -155,26 +415,145 @@ pub mod learning {
             /// Return the optimized parameter using gradient optimization.
             ///
             /// Takes in a set of starting parameters and related model data.
+            /// The result holds the parameters alongside a report on the
+            /// optimization.
             fn optimize(&self,
                         model: &M,
                         start: &[f64],
                         inputs: &M::Inputs,
                         targets: &M::Targets)
-                        -> Vec<f64>;
+                        -> OptimResult;
+        }
+
+        /// The outcome of an optimization.
+        #[derive(Clone, Debug, PartialEq)]
+        pub struct OptimResult {
+            /// The optimized parameters.
+            pub params: Vec<f64>,
+            /// Diagnostics from the optimization.
+            pub report: TrainingReport,
+        }
+
+        impl OptimResult {
+            /// Evaluates the model at the optimized parameters to
+            /// complete the report.
+            pub fn new<M: Optimizable + ?Sized>(model: &M,
+                                                params: Vec<f64>,
+                                                inputs: &M::Inputs,
+                                                targets: &M::Targets,
+                                                iters: usize,
+                                                converged: bool)
+                                                -> OptimResult {
+                let (cost, grad) = model.compute_grad(&params, inputs, targets);
+
+                OptimResult {
+                    params: params,
+                    report: TrainingReport {
+                        iters: iters,
+                        cost: cost,
+                        grad_norm: Vector::new(grad).norm(),
+                        converged: converged,
+                        error: None,
+                    },
+                }
+            }
+        }
+
+        /// Diagnostics from an optimization.
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub struct TrainingReport {
+            /// The number of iterations run.
+            ///
+            /// For the stochastic algorithms this is the number of
+            /// passes through the data.
+            pub iters: usize,
+            /// The cost at the optimized parameters, over all of the
+            /// data and including any penalty.
+            pub cost: f64,
+            /// The euclidean norm of the gradient of the cost, without
+            /// any penalty, at the optimized parameters.
+            pub grad_norm: f64,
+            /// Whether the algorithm met its convergence criterion.
+            ///
+            /// Algorithms which always run a fixed number of
+            /// iterations, such as gradient descent, report `false`.
+            pub converged: bool,
+            /// The non-finite value which stopped training, when the
+            /// optimizer checks for them.
+            pub error: Option<diagnostics::NonFiniteError>,
         }
 
         pub mod grad_desc;
         pub mod fmincg;
+        pub mod newton;
+        pub mod line_search;
+        pub mod projection;
+        pub mod coord_desc;
+        pub mod global;
+        pub mod logging;
+        pub mod callback;
+        pub mod diagnostics;
+        pub mod early_stopping;
+        #[cfg(feature = "std")]
+        pub mod checkpoint;
     }
 
     /// Module for learning tools.
     pub mod toolkit {
         pub mod activ_fn;
         pub mod kernel;
+        pub mod distance;
+        pub mod regularization;
+        pub mod stable;
         pub mod cost_fn;
+        pub mod running_stats;
+        #[cfg(feature = "rand")]
+        pub mod rand_utils;
     }
 }
 
+/// Module for evaluating models.
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+pub mod analysis {
+    pub mod cross_validation;
+    pub mod model_selection;
+    pub mod confusion_matrix;
+    pub mod curves;
+    pub mod regression;
+    pub mod learning_curve;
+    pub mod report;
+    #[cfg(feature = "rand")]
+    pub mod bootstrap;
+}
+
+/// Module for data handling.
+pub mod data {
+    #[cfg(feature = "std")]
+    pub mod transforms;
+    pub mod split;
+    #[cfg(feature = "rand")]
+    pub mod resample;
+    #[cfg(feature = "std")]
+    pub mod csv;
+    #[cfg(feature = "std")]
+    pub mod libsvm;
+    #[cfg(feature = "std")]
+    pub mod npy;
+    #[cfg(feature = "arrow")]
+    pub mod arrow;
+    #[cfg(feature = "polars")]
+    pub mod polars;
+    #[cfg(feature = "rand")]
+    pub mod generators;
+    pub mod dataset;
+    pub mod labels;
+    pub mod source;
+    #[cfg(feature = "std")]
+    pub mod text;
+}
+
 #[cfg(feature = "stats")]
 /// Module for computational statistics
 pub mod stats {
@@ -0,0 +1,366 @@
+//! Lazy matrix expressions.
+//!
+//! Chains of matrix arithmetic using the standard operators allocate a new
+//! matrix for every intermediate result. This module provides expression
+//! templates which build up the computation without evaluating it. The
+//! whole expression is then evaluated in a single pass into one output
+//! matrix.
+//!
+//! Expressions are created from matrices using `Matrix::lazy` and combined
+//! using `+`, `-`, `*` (matrix product of two matrices) and the
+//! `elemul`/`scale` methods.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::linalg::matrix::Matrix;
+//!
+//! let a = Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+//! let b = Matrix::new(2, 2, vec![1.0, 0.0, 0.0, 1.0]);
+//! let c = Matrix::new(2, 2, vec![2.0; 4]);
+//! let d = Matrix::new(2, 2, vec![0.5; 4]);
+//!
+//! // Computes a * b + c.elemul(d) with a single allocation.
+//! let e = (a.lazy() * b.lazy() + c.lazy().elemul(d.lazy())).eval();
+//!
+//! assert_eq!(*e.data(), vec![2.0, 3.0, 4.0, 5.0]);
+//! ```
+//!
+//! Note that the matrix product is computed element by element when the
+//! expression is evaluated. Products are therefore only supported between
+//! two matrices, so that no part of the expression is recomputed.
+
+use std::ops::{Add, Mul, Sub};
+
+use libnum::Zero;
+
+use linalg::matrix::Matrix;
+
+/// Trait for lazily evaluated matrix expressions.
+pub trait MatrixExpr {
+    /// The element type of the expression.
+    type Elem;
+
+    /// The number of rows in the evaluated matrix.
+    fn rows(&self) -> usize;
+
+    /// The number of columns in the evaluated matrix.
+    fn cols(&self) -> usize;
+
+    /// Computes the element at the given row and column.
+    ///
+    /// # Panics
+    ///
+    /// - The indices are outside the dimensions.
+    fn get(&self, i: usize, j: usize) -> Self::Elem;
+}
+
+/// A lazy matrix expression.
+///
+/// Wraps an expression tree and provides the operators for
+/// combining it with other expressions.
+pub struct Expr<E>(E);
+
+impl<E> Expr<E> {
+    /// Returns the wrapped expression tree.
+    pub fn into_inner(self) -> E {
+        self.0
+    }
+}
+
+impl<T: Copy + Zero, E: MatrixExpr<Elem = T>> Expr<E> {
+    /// The number of rows in the evaluated matrix.
+    pub fn rows(&self) -> usize {
+        self.0.rows()
+    }
+
+    /// The number of columns in the evaluated matrix.
+    pub fn cols(&self) -> usize {
+        self.0.cols()
+    }
+
+    /// Evaluates the expression into a new matrix.
+    pub fn eval(&self) -> Matrix<T> {
+        Matrix::from_fn(self.0.rows(), self.0.cols(), |i, j| self.0.get(i, j))
+    }
+
+    /// Evaluates the expression into an existing matrix.
+    ///
+    /// This reuses the allocation of the output matrix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::matrix::Matrix;
+    ///
+    /// let a = Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+    /// let mut out = Matrix::zeros(2, 2);
+    ///
+    /// (a.lazy() - a.lazy().scale(2.0)).eval_into(&mut out);
+    ///
+    /// assert_eq!(*out.data(), vec![-1.0, -2.0, -3.0, -4.0]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The output matrix has different dimensions to the expression.
+    pub fn eval_into(&self, out: &mut Matrix<T>) {
+        let (rows, cols) = (self.0.rows(), self.0.cols());
+        assert!(out.rows() == rows && out.cols() == cols,
+                "Output matrix dimensions do not agree.");
+
+        let data = out.mut_data();
+        for i in 0..rows {
+            for j in 0..cols {
+                data[i * cols + j] = self.0.get(i, j);
+            }
+        }
+    }
+
+    /// The elementwise product of two expressions.
+    ///
+    /// # Panics
+    ///
+    /// - The expressions have different dimensions.
+    pub fn elemul<F: MatrixExpr<Elem = T>>(self, rhs: Expr<F>) -> Expr<ElemMul<E, F>>
+        where T: Mul<T, Output = T>
+    {
+        assert_same_dims(&self.0, &rhs.0);
+        Expr(ElemMul(self.0, rhs.0))
+    }
+
+    /// Multiplies the expression by a scalar.
+    pub fn scale(self, scalar: T) -> Expr<Scale<E, T>>
+        where T: Mul<T, Output = T>
+    {
+        Expr(Scale(self.0, scalar))
+    }
+
+    /// Applies a function to each element of the expression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::matrix::Matrix;
+    ///
+    /// let a = Matrix::new(1, 3, vec![1.0, 4.0, 9.0]);
+    /// let b = a.lazy().map(|x: f64| x.sqrt()).eval();
+    ///
+    /// assert_eq!(*b.data(), vec![1.0, 2.0, 3.0]);
+    /// ```
+    pub fn map<F: Fn(T) -> T>(self, f: F) -> Expr<Map<E, F>> {
+        Expr(Map(self.0, f))
+    }
+}
+
+impl<T: Copy> Matrix<T> {
+    /// Creates a lazy expression referencing this matrix.
+    ///
+    /// See the `linalg::expr` module for details.
+    pub fn lazy(&self) -> Expr<MatRef<'_, T>> {
+        Expr(MatRef(self))
+    }
+}
+
+fn assert_same_dims<E: MatrixExpr, F: MatrixExpr>(lhs: &E, rhs: &F) {
+    assert!(lhs.rows() == rhs.rows() && lhs.cols() == rhs.cols(),
+            "Expression dimensions do not agree.");
+}
+
+/// Leaf expression referencing a matrix.
+pub struct MatRef<'a, T: 'a>(&'a Matrix<T>);
+
+impl<'a, T: Copy> MatrixExpr for MatRef<'a, T> {
+    type Elem = T;
+
+    fn rows(&self) -> usize {
+        self.0.rows()
+    }
+
+    fn cols(&self) -> usize {
+        self.0.cols()
+    }
+
+    fn get(&self, i: usize, j: usize) -> T {
+        let cols = self.0.cols();
+        assert!(j < cols, "Column index is out of bounds.");
+        self.0.data()[i * cols + j]
+    }
+}
+
+/// Elementwise sum of two expressions.
+pub struct Sum<E, F>(E, F);
+
+impl<T, E, F> MatrixExpr for Sum<E, F>
+    where T: Add<T, Output = T>,
+          E: MatrixExpr<Elem = T>,
+          F: MatrixExpr<Elem = T>
+{
+    type Elem = T;
+
+    fn rows(&self) -> usize {
+        self.0.rows()
+    }
+
+    fn cols(&self) -> usize {
+        self.0.cols()
+    }
+
+    fn get(&self, i: usize, j: usize) -> T {
+        self.0.get(i, j) + self.1.get(i, j)
+    }
+}
+
+/// Elementwise difference of two expressions.
+pub struct Diff<E, F>(E, F);
+
+impl<T, E, F> MatrixExpr for Diff<E, F>
+    where T: Sub<T, Output = T>,
+          E: MatrixExpr<Elem = T>,
+          F: MatrixExpr<Elem = T>
+{
+    type Elem = T;
+
+    fn rows(&self) -> usize {
+        self.0.rows()
+    }
+
+    fn cols(&self) -> usize {
+        self.0.cols()
+    }
+
+    fn get(&self, i: usize, j: usize) -> T {
+        self.0.get(i, j) - self.1.get(i, j)
+    }
+}
+
+/// Elementwise product of two expressions.
+pub struct ElemMul<E, F>(E, F);
+
+impl<T, E, F> MatrixExpr for ElemMul<E, F>
+    where T: Mul<T, Output = T>,
+          E: MatrixExpr<Elem = T>,
+          F: MatrixExpr<Elem = T>
+{
+    type Elem = T;
+
+    fn rows(&self) -> usize {
+        self.0.rows()
+    }
+
+    fn cols(&self) -> usize {
+        self.0.cols()
+    }
+
+    fn get(&self, i: usize, j: usize) -> T {
+        self.0.get(i, j) * self.1.get(i, j)
+    }
+}
+
+/// Expression multiplied by a scalar.
+pub struct Scale<E, T>(E, T);
+
+impl<T: Copy + Mul<T, Output = T>, E: MatrixExpr<Elem = T>> MatrixExpr for Scale<E, T> {
+    type Elem = T;
+
+    fn rows(&self) -> usize {
+        self.0.rows()
+    }
+
+    fn cols(&self) -> usize {
+        self.0.cols()
+    }
+
+    fn get(&self, i: usize, j: usize) -> T {
+        self.0.get(i, j) * self.1
+    }
+}
+
+/// Function applied to each element of an expression.
+pub struct Map<E, F>(E, F);
+
+impl<T, E: MatrixExpr<Elem = T>, F: Fn(T) -> T> MatrixExpr for Map<E, F> {
+    type Elem = T;
+
+    fn rows(&self) -> usize {
+        self.0.rows()
+    }
+
+    fn cols(&self) -> usize {
+        self.0.cols()
+    }
+
+    fn get(&self, i: usize, j: usize) -> T {
+        (self.1)(self.0.get(i, j))
+    }
+}
+
+/// Matrix product of two matrices.
+pub struct Product<'a, 'b, T: 'a + 'b>(&'a Matrix<T>, &'b Matrix<T>);
+
+impl<'a, 'b, T> MatrixExpr for Product<'a, 'b, T>
+    where T: Copy + Zero + Add<T, Output = T> + Mul<T, Output = T>
+{
+    type Elem = T;
+
+    fn rows(&self) -> usize {
+        self.0.rows()
+    }
+
+    fn cols(&self) -> usize {
+        self.1.cols()
+    }
+
+    fn get(&self, i: usize, j: usize) -> T {
+        let n = self.0.cols();
+        let p = self.1.cols();
+        assert!(j < p, "Column index is out of bounds.");
+
+        // Row i of the left matrix against column j of the right.
+        let lhs = &self.0.data()[i * n..(i + 1) * n];
+        let rhs = self.1.data()[j..].iter().step_by(p);
+
+        lhs.iter().zip(rhs).fold(T::zero(), |sum, (&a, &b)| sum + a * b)
+    }
+}
+
+/// Adds two expressions.
+impl<T, E, F> Add<Expr<F>> for Expr<E>
+    where T: Add<T, Output = T>,
+          E: MatrixExpr<Elem = T>,
+          F: MatrixExpr<Elem = T>
+{
+    type Output = Expr<Sum<E, F>>;
+
+    fn add(self, rhs: Expr<F>) -> Expr<Sum<E, F>> {
+        assert_same_dims(&self.0, &rhs.0);
+        Expr(Sum(self.0, rhs.0))
+    }
+}
+
+/// Subtracts two expressions.
+impl<T, E, F> Sub<Expr<F>> for Expr<E>
+    where T: Sub<T, Output = T>,
+          E: MatrixExpr<Elem = T>,
+          F: MatrixExpr<Elem = T>
+{
+    type Output = Expr<Diff<E, F>>;
+
+    fn sub(self, rhs: Expr<F>) -> Expr<Diff<E, F>> {
+        assert_same_dims(&self.0, &rhs.0);
+        Expr(Diff(self.0, rhs.0))
+    }
+}
+
+/// Matrix product of two matrix expressions.
+impl<'a, 'b, T> Mul<Expr<MatRef<'b, T>>> for Expr<MatRef<'a, T>>
+    where T: Copy + Zero + Add<T, Output = T> + Mul<T, Output = T>
+{
+    type Output = Expr<Product<'a, 'b, T>>;
+
+    fn mul(self, rhs: Expr<MatRef<'b, T>>) -> Expr<Product<'a, 'b, T>> {
+        assert!(self.0.cols() == rhs.0.rows(),
+                "Matrix dimensions do not agree.");
+        Expr(Product((self.0).0, (rhs.0).0))
+    }
+}
@@ -0,0 +1,332 @@
+//! GPU compute backend
+//!
+//! Runs large matrix products on the GPU using [wgpu](https://wgpu.rs),
+//! which supports Vulkan, Metal and DirectX 12 devices.
+//!
+//! The computations are done in double precision, so the results match
+//! the CPU up to rounding. Double precision shaders are not supported by
+//! every device. When no suitable device is found, the matrices are too
+//! small to benefit from the GPU, or the device reports an error, the
+//! functions in this module fall back to the CPU.
+//!
+//! Only matrix products are offloaded. Element-wise operations are
+//! limited by memory bandwidth, so copying them to the device is slower
+//! than computing them on the CPU.
+//!
+//! `NeuralNet` uses this backend for training and prediction when the
+//! `gpu` feature is enabled.
+//!
+//! Requires the `gpu` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use rusty_machine::linalg::gpu;
+//! use rusty_machine::linalg::matrix::Matrix;
+//!
+//! let a = Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+//! let b = Matrix::new(2, 1, vec![1.0, 1.0]);
+//!
+//! // Computed on the GPU if one is available and the product is large.
+//! let mut c = Matrix::new(0, 0, vec![]);
+//! gpu::mul_into(&a, &b, &mut c);
+//!
+//! assert_eq!(*c.data(), vec![3.0, 7.0]);
+//! ```
+
+use std::sync::{Mutex, OnceLock};
+use std::sync::mpsc;
+
+use pollster;
+use wgpu;
+use wgpu::util::DeviceExt;
+
+use linalg::matrix::Matrix;
+
+/// The fewest multiply-adds for which a product is computed on the GPU.
+///
+/// Smaller products are faster on the CPU than the cost of copying
+/// the matrices to and from the device.
+pub const MIN_GPU_WORK: usize = 1 << 20;
+
+/// The size of the square workgroups of the matrix product.
+const MATMUL_WORKGROUP: u32 = 16;
+
+const SHADER: &str = r#"
+struct Dims {
+    m: u32,
+    k: u32,
+    n: u32,
+    pad: u32,
+}
+
+@group(0) @binding(0) var<storage, read> a: array<f64>;
+@group(0) @binding(1) var<storage, read> b: array<f64>;
+@group(0) @binding(2) var<storage, read_write> out: array<f64>;
+@group(0) @binding(3) var<uniform> dims: Dims;
+
+@compute @workgroup_size(16, 16)
+fn matmul(@builtin(global_invocation_id) id: vec3<u32>) {
+    let row = id.y;
+    let col = id.x;
+    if (row >= dims.m || col >= dims.n) {
+        return;
+    }
+
+    var sum = f64(0);
+    for (var i = 0u; i < dims.k; i = i + 1u) {
+        sum = sum + a[row * dims.k + i] * b[i * dims.n + col];
+    }
+    out[row * dims.n + col] = sum;
+}
+"#;
+
+static GPU: OnceLock<Option<Gpu>> = OnceLock::new();
+
+/// A GPU device which can run double precision compute shaders.
+pub struct Gpu {
+    name: String,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    max_buffer_len: usize,
+    max_workgroups: u32,
+    matmul: wgpu::ComputePipeline,
+    // Error scopes belong to the device, so runs must not interleave.
+    lock: Mutex<()>,
+}
+
+/// The GPU used by this module.
+///
+/// The device is found the first time this is called. Returns None
+/// if there is no device supporting double precision shaders.
+pub fn device() -> Option<&'static Gpu> {
+    GPU.get_or_init(Gpu::new).as_ref()
+}
+
+/// Whether a GPU is available.
+pub fn is_available() -> bool {
+    device().is_some()
+}
+
+/// Computes the matrix product `a * b` and stores it in `out`.
+///
+/// Uses the GPU when one is available and the product has at least
+/// `MIN_GPU_WORK` multiply-adds.
+///
+/// # Panics
+///
+/// - The columns of `a` do not match the rows of `b`.
+pub fn mul_into(a: &Matrix<f64>, b: &Matrix<f64>, out: &mut Matrix<f64>) {
+    assert!(a.cols() == b.rows(), "Matrix dimensions do not agree.");
+
+    if a.rows() * a.cols() * b.cols() >= MIN_GPU_WORK {
+        if let Some(gpu) = device() {
+            if let Some(c) = gpu.mul(a, b) {
+                *out = c;
+                return;
+            }
+        }
+    }
+
+    a.mul_into(b, out);
+}
+
+impl Gpu {
+    fn new() -> Option<Gpu> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            force_fallback_adapter: false,
+            compatible_surface: None,
+        }))?;
+
+        if !adapter.features().contains(wgpu::Features::SHADER_F64) {
+            return None;
+        }
+
+        let limits = adapter.limits();
+        let descriptor = wgpu::DeviceDescriptor {
+            label: Some("rusty-machine"),
+            required_features: wgpu::Features::SHADER_F64,
+            required_limits: limits.clone(),
+            memory_hints: wgpu::MemoryHints::Performance,
+        };
+        let (device, queue) = pollster::block_on(adapter.request_device(&descriptor, None)).ok()?;
+
+        // Errors are caught by the error scopes in `run`. Any others must
+        // not panic, the computation falls back to the CPU instead.
+        device.on_uncaptured_error(Box::new(|_| {}));
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("rusty-machine kernels"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+        let matmul = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("matmul"),
+            layout: None,
+            module: &module,
+            entry_point: Some("matmul"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        let max_binding = (limits.max_storage_buffer_binding_size as u64).min(limits.max_buffer_size);
+
+        Some(Gpu {
+            name: adapter.get_info().name,
+            device: device,
+            queue: queue,
+            max_buffer_len: max_binding as usize / 8,
+            max_workgroups: limits.max_compute_workgroups_per_dimension,
+            matmul: matmul,
+            lock: Mutex::new(()),
+        })
+    }
+
+    /// The name of the device.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Computes the matrix product on the GPU.
+    ///
+    /// Returns None if the matrices are empty or too large for the
+    /// device, or if the device reports an error.
+    pub fn mul(&self, a: &Matrix<f64>, b: &Matrix<f64>) -> Option<Matrix<f64>> {
+        assert!(a.cols() == b.rows(), "Matrix dimensions do not agree.");
+
+        let (m, k, n) = (a.rows(), a.cols(), b.cols());
+        if m * k * n == 0 || [m * k, k * n, m * n].iter().any(|&len| len > self.max_buffer_len) {
+            return None;
+        }
+
+        let groups_x = (n as u32).div_ceil(MATMUL_WORKGROUP);
+        let groups_y = (m as u32).div_ceil(MATMUL_WORKGROUP);
+        if groups_x > self.max_workgroups || groups_y > self.max_workgroups {
+            return None;
+        }
+
+        let data = self.run(a.data(),
+                            b.data(),
+                            m * n,
+                            [m as u32, k as u32, n as u32, 0],
+                            (groups_x, groups_y))?;
+        Some(Matrix::new(m, n, data))
+    }
+
+    /// Runs the product kernel on two inputs and reads back the output.
+    ///
+    /// Returns None if the device reports an error.
+    fn run(&self,
+           a: &[f64],
+           b: &[f64],
+           out_len: usize,
+           dims: [u32; 4],
+           groups: (u32, u32))
+           -> Option<Vec<f64>> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        self.device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+        self.device.push_error_scope(wgpu::ErrorFilter::Validation);
+
+        let storage = |data: &[f64]| {
+            self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: &to_bytes(data),
+                usage: wgpu::BufferUsages::STORAGE,
+            })
+        };
+        let a_buf = storage(a);
+        let b_buf = storage(b);
+
+        let out_size = (out_len * 8) as wgpu::BufferAddress;
+        let out_buf = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: out_size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let read_buf = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: out_size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let dims_bytes: Vec<u8> = dims.iter().flat_map(|d| d.to_le_bytes().to_vec()).collect();
+        let dims_buf = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: None,
+            contents: &dims_bytes,
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &self.matmul.get_bind_group_layout(0),
+            entries: &[wgpu::BindGroupEntry {
+                           binding: 0,
+                           resource: a_buf.as_entire_binding(),
+                       },
+                       wgpu::BindGroupEntry {
+                           binding: 1,
+                           resource: b_buf.as_entire_binding(),
+                       },
+                       wgpu::BindGroupEntry {
+                           binding: 2,
+                           resource: out_buf.as_entire_binding(),
+                       },
+                       wgpu::BindGroupEntry {
+                           binding: 3,
+                           resource: dims_buf.as_entire_binding(),
+                       }],
+        });
+
+        let mut encoder = self.device
+                              .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: None,
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.matmul);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(groups.0, groups.1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&out_buf, 0, &read_buf, 0, out_size);
+        self.queue.submit(Some(encoder.finish()));
+
+        let validation = pollster::block_on(self.device.pop_error_scope());
+        let out_of_memory = pollster::block_on(self.device.pop_error_scope());
+        if validation.is_some() || out_of_memory.is_some() {
+            return None;
+        }
+
+        let slice = read_buf.slice(..);
+        let (sender, receiver) = mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        match receiver.recv() {
+            Ok(Ok(())) => {}
+            _ => return None,
+        }
+
+        let data = from_bytes(&slice.get_mapped_range());
+        read_buf.unmap();
+        Some(data)
+    }
+}
+
+fn to_bytes(data: &[f64]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(data.len() * 8);
+    for x in data {
+        bytes.extend_from_slice(&x.to_le_bytes());
+    }
+    bytes
+}
+
+fn from_bytes(bytes: &[u8]) -> Vec<f64> {
+    bytes.chunks(8)
+         .map(|c| f64::from_le_bytes([c[0], c[1], c[2], c[3], c[4], c[5], c[6], c[7]]))
+         .collect()
+}
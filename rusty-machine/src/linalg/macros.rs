@@ -1,5 +1,6 @@
 //! Macros for the linear algebra modules.
 
+#[allow(unused_macros)]
 macro_rules! count {
     () => (0usize);
     ( $x:tt $($xs:tt)* ) => (1usize + count!($($xs)*));
@@ -23,6 +24,7 @@ macro_rules! count {
 ///
 /// This macro currently supports the use cases described
 /// by a,b,c in the specification above. 
+#[allow(unused_macros)]
 macro_rules! mat {
     ( $( $x:expr ),* ) => { {
         let vec = vec![$($x),*];
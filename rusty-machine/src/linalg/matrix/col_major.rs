@@ -0,0 +1,311 @@
+//! Column-major matrices.
+//!
+//! `Matrix` stores its data in row-major order. Some computations access
+//! the data a column at a time, for example computing per-feature statistics
+//! or multiplying on the right of a row-major matrix. The `ColMajorMatrix`
+//! stores its data in column-major order so that these accesses are
+//! contiguous. It is also the layout used by BLAS and LAPACK routines.
+
+use std::ops::{Add, Index, Mul};
+use std::vec::Vec;
+
+use libnum::{One, Zero};
+
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+use linalg::utils;
+
+/// A matrix stored in column-major order.
+pub struct ColMajorMatrix<T> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+impl<T> ColMajorMatrix<T> {
+    /// Constructor for ColMajorMatrix struct.
+    ///
+    /// The data is given in column-major order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::matrix::ColMajorMatrix;
+    ///
+    /// // The matrix [[1, 2, 3], [4, 5, 6]].
+    /// let mat = ColMajorMatrix::new(2, 3, vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+    ///
+    /// assert_eq!(mat[[0, 2]], 3.0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The input data does not match the given dimensions.
+    pub fn new(rows: usize, cols: usize, data: Vec<T>) -> ColMajorMatrix<T> {
+        assert!(cols * rows == data.len(),
+                "Data does not match given dimensions.");
+
+        ColMajorMatrix {
+            rows: rows,
+            cols: cols,
+            data: data,
+        }
+    }
+
+    /// Returns the number of rows in the matrix.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of columns in the matrix.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns a non-mutable reference to the underlying column-major data.
+    pub fn data(&self) -> &Vec<T> {
+        &self.data
+    }
+
+    /// Consumes the matrix and returns the Vec of column-major data.
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    /// Returns a contiguous slice of the given column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::matrix::{Matrix, ColMajorMatrix};
+    ///
+    /// let mat = ColMajorMatrix::from(&Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0]));
+    ///
+    /// assert_eq!(mat.col(1), &[2.0, 4.0]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The column index exceeds the number of columns.
+    pub fn col(&self, idx: usize) -> &[T] {
+        assert!(idx < self.cols,
+                "Column index is greater than number of columns.");
+        &self.data[idx * self.rows..(idx + 1) * self.rows]
+    }
+
+    /// Consumes the matrix and returns its transpose as a row-major Matrix.
+    ///
+    /// This does not copy any data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::matrix::ColMajorMatrix;
+    ///
+    /// let mat = ColMajorMatrix::new(2, 3, vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+    /// let mat_t = mat.into_transpose();
+    ///
+    /// assert_eq!(mat_t.rows(), 3);
+    /// assert_eq!(mat_t[[2, 1]], 6.0);
+    /// ```
+    pub fn into_transpose(self) -> Matrix<T> {
+        Matrix::new(self.cols, self.rows, self.data)
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Consumes the matrix and returns its transpose in column-major order.
+    ///
+    /// This does not copy any data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::matrix::Matrix;
+    ///
+    /// let mat = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    /// let mat_t = mat.into_col_major_transpose();
+    ///
+    /// assert_eq!(mat_t.rows(), 3);
+    /// assert_eq!(mat_t[[2, 1]], 6.0);
+    /// ```
+    pub fn into_col_major_transpose(self) -> ColMajorMatrix<T> {
+        let (rows, cols) = (self.rows(), self.cols());
+        ColMajorMatrix::new(cols, rows, self.into_vec())
+    }
+}
+
+impl<T: Clone> Clone for ColMajorMatrix<T> {
+    fn clone(&self) -> ColMajorMatrix<T> {
+        ColMajorMatrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.data.clone(),
+        }
+    }
+}
+
+/// Converts a row-major matrix into column-major order.
+impl<T: Copy> From<&Matrix<T>> for ColMajorMatrix<T> {
+    fn from(mat: &Matrix<T>) -> ColMajorMatrix<T> {
+        let (rows, cols) = (mat.rows(), mat.cols());
+        let row_data = mat.data();
+        let mut data = Vec::with_capacity(rows * cols);
+
+        for j in 0..cols {
+            for i in 0..rows {
+                data.push(row_data[i * cols + j]);
+            }
+        }
+
+        ColMajorMatrix::new(rows, cols, data)
+    }
+}
+
+/// Converts a column-major matrix into row-major order.
+impl<T: Copy> From<&ColMajorMatrix<T>> for Matrix<T> {
+    fn from(mat: &ColMajorMatrix<T>) -> Matrix<T> {
+        Matrix::from_fn(mat.rows, mat.cols, |i, j| mat.data[j * mat.rows + i])
+    }
+}
+
+/// Indexes the matrix.
+///
+/// Takes row index first then column.
+impl<T> Index<[usize; 2]> for ColMajorMatrix<T> {
+    type Output = T;
+
+    fn index(&self, idx: [usize; 2]) -> &T {
+        assert!(idx[0] < self.rows,
+                "Row index is greater than row dimension.");
+        assert!(idx[1] < self.cols,
+                "Column index is greater than column dimension.");
+        &self.data[idx[1] * self.rows + idx[0]]
+    }
+}
+
+/// Multiplies a row-major matrix by a column-major matrix.
+///
+/// Each element of the product is the dot product of a contiguous
+/// row and a contiguous column.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::linalg::matrix::{Matrix, ColMajorMatrix};
+///
+/// let a = Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+/// let b = ColMajorMatrix::from(&a);
+///
+/// let c = &a * &b;
+/// assert_eq!(*c.data(), vec![7.0, 10.0, 15.0, 22.0]);
+/// ```
+impl<T> Mul<&ColMajorMatrix<T>> for &Matrix<T>
+    where T: Copy + Zero + One + Mul<T, Output = T> + Add<T, Output = T>
+{
+    type Output = Matrix<T>;
+
+    fn mul(self, m: &ColMajorMatrix<T>) -> Matrix<T> {
+        assert!(self.cols() == m.rows, "Matrix dimensions do not agree.");
+
+        let n = self.cols();
+        let lhs = self.data();
+
+        Matrix::from_fn(self.rows(), m.cols, |i, j| {
+            utils::dot(&lhs[i * n..(i + 1) * n], m.col(j))
+        })
+    }
+}
+
+/// Multiplies two column-major matrices.
+///
+/// Each column of the product is accumulated from contiguous
+/// columns of the left hand side.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::linalg::matrix::{Matrix, ColMajorMatrix};
+///
+/// let a = ColMajorMatrix::from(&Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0]));
+///
+/// let c = Matrix::from(&(&a * &a));
+/// assert_eq!(*c.data(), vec![7.0, 10.0, 15.0, 22.0]);
+/// ```
+impl<T> Mul<&ColMajorMatrix<T>> for &ColMajorMatrix<T>
+    where T: Copy + Zero + One + Mul<T, Output = T> + Add<T, Output = T>
+{
+    type Output = ColMajorMatrix<T>;
+
+    fn mul(self, m: &ColMajorMatrix<T>) -> ColMajorMatrix<T> {
+        assert!(self.cols == m.rows, "Matrix dimensions do not agree.");
+
+        let mut data = vec![T::zero(); self.rows * m.cols];
+
+        for j in 0..m.cols {
+            let out_col = &mut data[j * self.rows..(j + 1) * self.rows];
+
+            for (k, b) in m.col(j).iter().enumerate() {
+                for (c, a) in out_col.iter_mut().zip(self.col(k).iter()) {
+                    *c = *c + *a * *b;
+                }
+            }
+        }
+
+        ColMajorMatrix::new(self.rows, m.cols, data)
+    }
+}
+
+/// Multiplies a column-major matrix by a vector.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::linalg::matrix::ColMajorMatrix;
+/// use rusty_machine::linalg::vector::Vector;
+///
+/// let a = ColMajorMatrix::new(2, 2, vec![1.0, 3.0, 2.0, 4.0]);
+/// let v = Vector::new(vec![1.0, 1.0]);
+///
+/// assert_eq!(*(&a * &v).data(), vec![3.0, 7.0]);
+/// ```
+impl<T> Mul<&Vector<T>> for &ColMajorMatrix<T>
+    where T: Copy + Zero + One + Mul<T, Output = T> + Add<T, Output = T>
+{
+    type Output = Vector<T>;
+
+    fn mul(self, v: &Vector<T>) -> Vector<T> {
+        assert!(v.size() == self.cols,
+                "Matrix and Vector dimensions do not agree.");
+
+        let mut data = vec![T::zero(); self.rows];
+
+        for (k, b) in v.data().iter().enumerate() {
+            for (c, a) in data.iter_mut().zip(self.col(k).iter()) {
+                *c = *c + *a * *b;
+            }
+        }
+
+        Vector::new(data)
+    }
+}
+
+impl<T: Copy + Zero + Add<T, Output = T>> ColMajorMatrix<T> {
+    /// The sum of the rows of the matrix.
+    ///
+    /// Returns a Vector containing the sum of each column, computed
+    /// over contiguous memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::matrix::{Matrix, ColMajorMatrix};
+    ///
+    /// let a = ColMajorMatrix::from(&Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0]));
+    ///
+    /// assert_eq!(*a.sum_rows().data(), vec![4.0, 6.0]);
+    /// ```
+    pub fn sum_rows(&self) -> Vector<T> {
+        (0..self.cols).map(|j| utils::unrolled_sum(self.col(j))).collect()
+    }
+}
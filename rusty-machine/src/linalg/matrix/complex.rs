@@ -0,0 +1,198 @@
+//! Complex Matrices
+//!
+//! Methods specific to matrices with complex elements.
+//!
+//! The arithmetic, concatenation and selection methods on `Matrix<T>`
+//! already work for `Complex<T>` elements. This module adds the
+//! operations which make use of the complex conjugate.
+
+use linalg::matrix::Matrix;
+use linalg::Complex;
+
+use libnum::{Num, Zero, Float};
+use std::ops::Neg;
+
+impl<T: Copy + Num> Matrix<Complex<T>> {
+    /// Constructs a complex matrix from its real and imaginary parts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::Complex;
+    ///
+    /// let re = Matrix::new(1, 2, vec![1.0, 2.0]);
+    /// let im = Matrix::new(1, 2, vec![3.0, 4.0]);
+    ///
+    /// let c = Matrix::from_parts(&re, &im);
+    ///
+    /// assert_eq!(c[[0, 1]], Complex::new(2.0, 4.0));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The real and imaginary parts have different dimensions.
+    pub fn from_parts(re: &Matrix<T>, im: &Matrix<T>) -> Matrix<Complex<T>> {
+        assert!(re.rows == im.rows && re.cols == im.cols,
+                "Real and imaginary parts have different dimensions.");
+
+        let data = re.data
+                     .iter()
+                     .zip(im.data.iter())
+                     .map(|(a, b)| Complex::new(*a, *b))
+                     .collect();
+
+        Matrix {
+            cols: re.cols,
+            rows: re.rows,
+            data: data,
+        }
+    }
+
+    /// Returns the real part of the matrix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::Complex;
+    ///
+    /// let c = Matrix::new(1, 2, vec![Complex::new(1.0, 3.0), Complex::new(2.0, 4.0)]);
+    ///
+    /// assert_eq!(*c.re().data(), vec![1.0, 2.0]);
+    /// ```
+    pub fn re(&self) -> Matrix<T> {
+        Matrix {
+            cols: self.cols,
+            rows: self.rows,
+            data: self.data.iter().map(|c| c.re).collect(),
+        }
+    }
+
+    /// Returns the imaginary part of the matrix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::Complex;
+    ///
+    /// let c = Matrix::new(1, 2, vec![Complex::new(1.0, 3.0), Complex::new(2.0, 4.0)]);
+    ///
+    /// assert_eq!(*c.im().data(), vec![3.0, 4.0]);
+    /// ```
+    pub fn im(&self) -> Matrix<T> {
+        Matrix {
+            cols: self.cols,
+            rows: self.rows,
+            data: self.data.iter().map(|c| c.im).collect(),
+        }
+    }
+}
+
+impl<T: Copy + Num + Neg<Output = T>> Matrix<Complex<T>> {
+    /// The elementwise complex conjugate of the matrix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::Complex;
+    ///
+    /// let c = Matrix::new(1, 2, vec![Complex::new(1.0, 3.0), Complex::new(2.0, -4.0)]);
+    ///
+    /// let c_conj = c.conj();
+    /// assert_eq!(*c_conj.data(), vec![Complex::new(1.0, -3.0), Complex::new(2.0, 4.0)]);
+    /// ```
+    pub fn conj(&self) -> Matrix<Complex<T>> {
+        Matrix {
+            cols: self.cols,
+            rows: self.rows,
+            data: self.data.iter().map(|c| c.conj()).collect(),
+        }
+    }
+
+    /// The conjugate transpose of the matrix.
+    ///
+    /// Also known as the hermitian transpose.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::Complex;
+    ///
+    /// let c = Matrix::new(1, 2, vec![Complex::new(1.0, 3.0), Complex::new(2.0, -4.0)]);
+    ///
+    /// let c_h = c.conj_transpose();
+    ///
+    /// assert_eq!(c_h.rows(), 2);
+    /// assert_eq!(c_h[[1, 0]], Complex::new(2.0, 4.0));
+    /// ```
+    pub fn conj_transpose(&self) -> Matrix<Complex<T>> {
+        let mut new_data = vec![Complex::zero(); self.cols * self.rows];
+
+        for i in 0..self.cols {
+            for j in 0..self.rows {
+                new_data[i * self.rows + j] = self.data[j * self.cols + i].conj();
+            }
+        }
+
+        Matrix {
+            cols: self.rows,
+            rows: self.cols,
+            data: new_data,
+        }
+    }
+
+    /// Checks if the matrix is hermitian.
+    ///
+    /// A hermitian matrix is equal to its own conjugate transpose.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::Complex;
+    ///
+    /// let a = Matrix::new(2, 2, vec![Complex::new(2.0, 0.0), Complex::new(1.0, 1.0),
+    ///                                Complex::new(1.0, -1.0), Complex::new(3.0, 0.0)]);
+    /// assert!(a.is_hermitian());
+    ///
+    /// let b = Matrix::new(2, 2, vec![Complex::new(2.0, 0.0), Complex::new(1.0, 1.0),
+    ///                                Complex::new(1.0, 1.0), Complex::new(3.0, 0.0)]);
+    /// assert!(!b.is_hermitian());
+    /// ```
+    pub fn is_hermitian(&self) -> bool {
+        if self.rows != self.cols {
+            return false;
+        }
+
+        for i in 0..self.rows {
+            for j in i..self.cols {
+                if self.data[i * self.cols + j] != self.data[j * self.cols + i].conj() {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+impl<T: Copy + Float> Matrix<Complex<T>> {
+    /// Computes the frobenius norm of the complex matrix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::Complex;
+    ///
+    /// let c = Matrix::new(1, 2, vec![Complex::new(3.0, 4.0), Complex::new(0.0, 0.0)]);
+    ///
+    /// assert_eq!(c.norm(), 5.0);
+    /// ```
+    pub fn norm(&self) -> T {
+        self.data.iter().fold(T::zero(), |acc, c| acc + c.norm_sqr()).sqrt()
+    }
+}
@@ -5,6 +5,7 @@
 
 use std::ops::{Mul, Add, Div, Sub, Neg};
 use std::cmp;
+use std::vec::Vec;
 
 use linalg::matrix::Matrix;
 use linalg::vector::Vector;
@@ -4,13 +4,26 @@
 //! relating to the matrix linear algebra struct.
 
 use std::ops::{Mul, Add, Div, Sub, Index, Neg};
+use std::vec::Vec;
 use libnum::{One, Zero, Float, FromPrimitive};
 use std::cmp::{PartialEq, min};
+use std::iter::FromIterator;
 use linalg::Metric;
 use linalg::vector::Vector;
 use linalg::utils;
+#[cfg(feature = "rand")]
+use rand::Rng;
+#[cfg(feature = "rand")]
+use rand::distributions::normal::StandardNormal;
 
 mod decomposition;
+#[cfg(feature = "std")]
+mod complex;
+mod col_major;
+mod slice;
+
+pub use self::col_major::ColMajorMatrix;
+pub use self::slice::{BaseMatrix, MatrixSlice};
 
 /// The Matrix struct.
 ///
@@ -51,6 +64,68 @@ impl<T> Matrix<T> {
         }
     }
 
+    /// Constructor for Matrix struct using a function of the indices.
+    ///
+    /// The function is called with the row and column index of each
+    /// element, in row-major order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::matrix::Matrix;
+    ///
+    /// let mat = Matrix::from_fn(2, 3, |i, j| i * 3 + j);
+    ///
+    /// assert_eq!(*mat.data(), vec![0, 1, 2, 3, 4, 5]);
+    /// ```
+    pub fn from_fn<F>(rows: usize, cols: usize, mut f: F) -> Matrix<T>
+        where F: FnMut(usize, usize) -> T
+    {
+        let mut data = Vec::with_capacity(rows * cols);
+
+        for i in 0..rows {
+            for j in 0..cols {
+                data.push(f(i, j));
+            }
+        }
+
+        Matrix {
+            cols: cols,
+            rows: rows,
+            data: data,
+        }
+    }
+
+    /// Constructor for Matrix struct from an iterator of the elements
+    /// in row-major order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::matrix::Matrix;
+    ///
+    /// let mat = Matrix::from_iter_shape(2, 3, (0..6).map(|x| x as f64));
+    ///
+    /// assert_eq!(mat[[1, 0]], 3.0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The iterator does not yield `rows * cols` elements.
+    pub fn from_iter_shape<I>(rows: usize, cols: usize, iter: I) -> Matrix<T>
+        where I: IntoIterator<Item = T>
+    {
+        let data = iter.into_iter().collect::<Vec<_>>();
+
+        assert!(cols * rows == data.len(),
+                "Data does not match given dimensions.");
+        Matrix {
+            cols: cols,
+            rows: rows,
+            data: data,
+        }
+    }
+
     /// Returns the number of rows in the Matrix.
     pub fn rows(&self) -> usize {
         self.rows
@@ -66,6 +141,11 @@ impl<T> Matrix<T> {
         &self.data
     }
 
+    /// Returns a mutable slice of the underlying data.
+    pub fn mut_data(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
     /// Consumes the Matrix and returns the Vec of data.
     pub fn into_vec(self) -> Vec<T> {
         self.data
@@ -83,6 +163,60 @@ impl<T: Clone> Clone for Matrix<T> {
     }
 }
 
+/// Collects an iterator of rows into a Matrix.
+///
+/// The number of columns is taken from the first row.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::linalg::matrix::Matrix;
+///
+/// let rows = vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]];
+/// let mat = rows.into_iter().collect::<Matrix<f64>>();
+///
+/// assert_eq!(mat.rows(), 3);
+/// assert_eq!(mat.cols(), 2);
+/// ```
+///
+/// # Panics
+///
+/// - The rows do not all have the same length.
+impl<T> FromIterator<Vec<T>> for Matrix<T> {
+    fn from_iter<I: IntoIterator<Item = Vec<T>>>(iter: I) -> Matrix<T> {
+        let mut data = Vec::new();
+        let mut rows = 0usize;
+        let mut cols = 0usize;
+
+        for row in iter {
+            if rows == 0 {
+                cols = row.len();
+            }
+
+            assert!(row.len() == cols, "Row lengths are not equal.");
+            data.extend(row);
+            rows += 1;
+        }
+
+        Matrix {
+            cols: cols,
+            rows: rows,
+            data: data,
+        }
+    }
+}
+
+/// Collects an iterator of Vector rows into a Matrix.
+///
+/// # Panics
+///
+/// - The rows do not all have the same size.
+impl<T> FromIterator<Vector<T>> for Matrix<T> {
+    fn from_iter<I: IntoIterator<Item = Vector<T>>>(iter: I) -> Matrix<T> {
+        iter.into_iter().map(|v| v.into_vec()).collect()
+    }
+}
+
 impl<T: Copy> Matrix<T> {
     /// Select rows from matrix
     ///
@@ -128,6 +262,39 @@ impl<T: Copy> Matrix<T> {
         }
     }
 
+    /// Select rows from matrix, writing them into an existing matrix.
+    ///
+    /// The output matrix is resized as needed, reusing its allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::matrix::Matrix;
+    ///
+    /// let a = Matrix::new(3, 2, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    /// let mut b = Matrix::new(0, 0, vec![]);
+    ///
+    /// a.select_rows_into(&[2, 0], &mut b);
+    /// assert_eq!(*b.data(), vec![5.0, 6.0, 1.0, 2.0]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - Panics if row indices exceed the matrix dimensions.
+    pub fn select_rows_into(&self, rows: &[usize], out: &mut Matrix<T>) {
+        for row in rows {
+            assert!(*row < self.rows,
+                    "Row index is greater than number of rows.");
+        }
+
+        out.data.clear();
+        for row in rows {
+            out.data.extend_from_slice(&self.data[*row * self.cols..(*row + 1) * self.cols]);
+        }
+        out.rows = rows.len();
+        out.cols = self.cols;
+    }
+
     /// Select columns from matrix
     ///
     /// # Examples
@@ -356,7 +523,7 @@ impl<T: Copy> Matrix<T> {
     ///
     /// assert_eq!(*b.data(), vec![2.0; 4]);
     /// ```
-    pub fn apply(self, f: &Fn(T) -> T) -> Matrix<T> {
+    pub fn apply(self, f: &dyn Fn(T) -> T) -> Matrix<T> {
         let new_data = self.data.into_iter().map(f).collect();
 
         Matrix {
@@ -421,7 +588,7 @@ impl<T: Zero + One + Copy> Matrix<T> {
         let mut data = vec![T::zero(); size * size];
 
         for i in 0..size {
-            data[(i * (size + 1)) as usize] = T::one();
+            data[i * (size + 1)] = T::one();
         }
 
         Matrix {
@@ -482,6 +649,138 @@ impl<T: Zero + One + Copy> Matrix<T> {
             data: new_data,
         }
     }
+
+    /// Tranposes the matrix, writing the result into an existing matrix.
+    ///
+    /// The output matrix is resized as needed, reusing its allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::matrix::Matrix;
+    ///
+    /// let mat = Matrix::new(2,3, vec![1.0,2.0,3.0,4.0,5.0,6.0]);
+    /// let mut mt = Matrix::new(0, 0, vec![]);
+    ///
+    /// mat.transpose_into(&mut mt);
+    ///
+    /// assert_eq!(mt.rows(), 3);
+    /// assert_eq!(*mt.data(), vec![1.0,4.0,2.0,5.0,3.0,6.0]);
+    /// ```
+    pub fn transpose_into(&self, out: &mut Matrix<T>) {
+        out.resize_zeroed(self.cols, self.rows);
+
+        unsafe {
+            for i in 0..self.cols {
+                for j in 0..self.rows {
+                    *out.data.get_unchecked_mut(i * self.rows + j) =
+                        *self.data.get_unchecked(j * self.cols + i);
+                }
+            }
+        }
+    }
+
+    /// Resizes the matrix and fills it with zeros.
+    ///
+    /// Reuses the existing allocation where possible.
+    fn resize_zeroed(&mut self, rows: usize, cols: usize) {
+        self.data.clear();
+        self.data.resize(rows * cols, T::zero());
+        self.rows = rows;
+        self.cols = cols;
+    }
+}
+
+// Random matrices require the `rand` feature.
+#[cfg(feature = "rand")]
+impl Matrix<f64> {
+    /// Constructs matrix with entries drawn uniformly from [0, 1).
+    ///
+    /// Requires the row and column dimensions and a random number generator.
+    /// Passing a seeded generator gives reproducible matrices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate rand;
+    /// # extern crate rusty_machine;
+    ///
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rand::{StdRng, SeedableRng};
+    ///
+    /// # fn main() {
+    /// let seed: &[usize] = &[1, 2, 3];
+    /// let mut rng: StdRng = SeedableRng::from_seed(seed);
+    ///
+    /// let mat = Matrix::rand_uniform(3, 2, &mut rng);
+    ///
+    /// assert_eq!(mat.rows(), 3);
+    /// assert!(mat.data().iter().all(|x| *x >= 0f64 && *x < 1f64));
+    /// # }
+    /// ```
+    pub fn rand_uniform<R: Rng>(rows: usize, cols: usize, rng: &mut R) -> Matrix<f64> {
+        let mut data = Vec::with_capacity(rows * cols);
+
+        for _ in 0..rows * cols {
+            data.push(rng.gen::<f64>());
+        }
+
+        Matrix {
+            cols: cols,
+            rows: rows,
+            data: data,
+        }
+    }
+
+    /// Constructs matrix with entries drawn from a normal distribution.
+    ///
+    /// Requires the row and column dimensions, the mean and standard
+    /// deviation of the distribution and a random number generator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate rand;
+    /// # extern crate rusty_machine;
+    ///
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rand::{StdRng, SeedableRng};
+    ///
+    /// # fn main() {
+    /// let seed: &[usize] = &[1, 2, 3];
+    /// let mut rng: StdRng = SeedableRng::from_seed(seed);
+    ///
+    /// // 100x100 matrix of samples from N(5, 0.5^2).
+    /// let mat = Matrix::rand_normal(100, 100, 5f64, 0.5, &mut rng);
+    ///
+    /// assert!((mat.sum() / 10000f64 - 5f64).abs() < 0.1);
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The standard deviation is negative.
+    pub fn rand_normal<R: Rng>(rows: usize,
+                               cols: usize,
+                               mean: f64,
+                               std_dev: f64,
+                               rng: &mut R)
+                               -> Matrix<f64> {
+        assert!(std_dev >= 0f64, "Standard deviation must be non-negative.");
+
+        let mut data = Vec::with_capacity(rows * cols);
+
+        for _ in 0..rows * cols {
+            let StandardNormal(z) = rng.gen::<StandardNormal>();
+            data.push(mean + std_dev * z);
+        }
+
+        Matrix {
+            cols: cols,
+            rows: rows,
+            data: data,
+        }
+    }
 }
 
 impl<T: Copy + Zero + One + PartialEq> Matrix<T> {
@@ -1029,9 +1328,47 @@ impl<'a, 'b, T: Copy + Zero + One + Mul<T, Output=T> + Add<T, Output=T>> Mul<&'b
     type Output = Matrix<T>;
 
     fn mul(self, m: &Matrix<T>) -> Matrix<T> {
+        let mut out = Matrix {
+            rows: 0,
+            cols: 0,
+            data: Vec::with_capacity(self.rows * m.cols),
+        };
+
+        self.mul_into(m, &mut out);
+        out
+    }
+}
+
+impl<T: Copy + Zero + One + Mul<T, Output=T> + Add<T, Output=T>> Matrix<T> {
+    /// Multiplies two matrices, writing the product into an existing matrix.
+    ///
+    /// The output matrix is resized as needed, reusing its allocation.
+    /// This avoids allocating a new matrix for each product when the same
+    /// computation is repeated, for example within a training loop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::matrix::Matrix;
+    ///
+    /// let a = Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+    /// let mut out = Matrix::new(0, 0, vec![]);
+    ///
+    /// for _ in 0..10 {
+    ///     a.mul_into(&a, &mut out);
+    /// }
+    ///
+    /// assert_eq!(*out.data(), vec![7.0, 10.0, 15.0, 22.0]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The matrix dimensions do not agree.
+    pub fn mul_into(&self, m: &Matrix<T>, out: &mut Matrix<T>) {
         assert!(self.cols == m.rows, "Matrix dimensions do not agree.");
 
-        let mut new_data = vec![T::zero(); self.rows * m.cols];
+        out.resize_zeroed(self.rows, m.cols);
+        let new_data = &mut out.data;
 
         unsafe {
             for i in 0..self.rows
@@ -1045,12 +1382,6 @@ impl<'a, 'b, T: Copy + Zero + One + Mul<T, Output=T> + Add<T, Output=T>> Mul<&'b
                 }
             }
         }
-
-        Matrix {
-            rows: self.rows,
-            cols: m.cols,
-            data: new_data
-        }
     }
 }
 
@@ -0,0 +1,179 @@
+//! Borrowed matrices.
+//!
+//! The `BaseMatrix` trait describes row-major matrix data which can be
+//! read a row at a time. It is implemented by the owned `Matrix` and by
+//! `MatrixSlice`, a matrix view onto borrowed data. Models which accept
+//! any `BaseMatrix` can predict from a block of rows of a larger matrix,
+//! or from a buffer owned elsewhere, without first copying the data into
+//! a new `Matrix`.
+
+use std::ops::Index;
+use std::vec::Vec;
+
+use linalg::matrix::Matrix;
+
+/// Trait for row-major matrix data.
+pub trait BaseMatrix<T> {
+    /// Returns the number of rows.
+    fn rows(&self) -> usize;
+
+    /// Returns the number of columns.
+    fn cols(&self) -> usize;
+
+    /// Returns a contiguous slice of the given row.
+    ///
+    /// # Panics
+    ///
+    /// - The row index is out of bounds.
+    fn row(&self, idx: usize) -> &[T];
+
+    /// Copies the data into an owned `Matrix`.
+    fn to_matrix(&self) -> Matrix<T>
+        where T: Copy
+    {
+        let mut data = Vec::with_capacity(self.rows() * self.cols());
+        for i in 0..self.rows() {
+            data.extend_from_slice(self.row(i));
+        }
+
+        Matrix::new(self.rows(), self.cols(), data)
+    }
+}
+
+impl<T> BaseMatrix<T> for Matrix<T> {
+    fn rows(&self) -> usize {
+        Matrix::rows(self)
+    }
+
+    fn cols(&self) -> usize {
+        Matrix::cols(self)
+    }
+
+    fn row(&self, idx: usize) -> &[T] {
+        assert!(idx < Matrix::rows(self),
+                "Row index is greater than row dimension.");
+        let cols = Matrix::cols(self);
+        &self.data()[idx * cols..(idx + 1) * cols]
+    }
+
+    fn to_matrix(&self) -> Matrix<T>
+        where T: Copy
+    {
+        Matrix::new(Matrix::rows(self), Matrix::cols(self), self.data().clone())
+    }
+}
+
+/// A matrix view onto borrowed row-major data.
+#[derive(Clone, Copy)]
+pub struct MatrixSlice<'a, T: 'a> {
+    rows: usize,
+    cols: usize,
+    data: &'a [T],
+}
+
+impl<'a, T> MatrixSlice<'a, T> {
+    /// Constructs a matrix view onto row-major data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::matrix::MatrixSlice;
+    ///
+    /// let data = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+    /// let mat = MatrixSlice::from_slice(&data, 3, 2);
+    ///
+    /// assert_eq!(mat[[2, 0]], 5.0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The data does not match the given dimensions.
+    pub fn from_slice(data: &'a [T], rows: usize, cols: usize) -> MatrixSlice<'a, T> {
+        assert!(cols * rows == data.len(),
+                "Data does not match given dimensions.");
+
+        MatrixSlice {
+            rows: rows,
+            cols: cols,
+            data: data,
+        }
+    }
+
+    /// Returns the number of rows in the view.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of columns in the view.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns the borrowed row-major data.
+    pub fn data(&self) -> &'a [T] {
+        self.data
+    }
+}
+
+impl<'a, T> BaseMatrix<T> for MatrixSlice<'a, T> {
+    fn rows(&self) -> usize {
+        self.rows
+    }
+
+    fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn row(&self, idx: usize) -> &[T] {
+        assert!(idx < self.rows, "Row index is greater than row dimension.");
+        &self.data[idx * self.cols..(idx + 1) * self.cols]
+    }
+}
+
+impl<'a, T> Index<[usize; 2]> for MatrixSlice<'a, T> {
+    type Output = T;
+
+    fn index(&self, idx: [usize; 2]) -> &T {
+        assert!(idx[0] < self.rows,
+                "Row index is greater than row dimension.");
+        assert!(idx[1] < self.cols,
+                "Column index is greater than column dimension.");
+        &self.data[idx[0] * self.cols + idx[1]]
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Returns a view of the rows in `[start, end)` without copying.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::matrix::Matrix;
+    ///
+    /// let mat = Matrix::new(3, 2, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    /// let view = mat.slice_rows(1, 3);
+    ///
+    /// assert_eq!(view.rows(), 2);
+    /// assert_eq!(view[[0, 1]], 4.0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The range is decreasing or past the last row.
+    pub fn slice_rows(&self, start: usize, end: usize) -> MatrixSlice<'_, T> {
+        assert!(start <= end && end <= self.rows(),
+                "Row range is out of bounds.");
+        let cols = self.cols();
+
+        MatrixSlice {
+            rows: end - start,
+            cols: cols,
+            data: &self.data()[start * cols..end * cols],
+        }
+    }
+
+    /// Returns a view of the whole matrix.
+    pub fn as_slice(&self) -> MatrixSlice<'_, T> {
+        self.slice_rows(0, self.rows())
+    }
+}
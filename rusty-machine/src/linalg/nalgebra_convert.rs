@@ -0,0 +1,87 @@
+//! Conversions between rusty-machine and nalgebra types.
+//!
+//! This module is only available with the `nalgebra` feature enabled.
+//!
+//! nalgebra stores dense matrices in column-major order whereas `Matrix`
+//! is row-major, so matrix conversions copy the data in the new order.
+//! Vectors have the same layout in both crates and are converted without
+//! copying.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "nalgebra")]
+//! extern crate nalgebra;
+//! # extern crate rusty_machine;
+//!
+//! # #[cfg(feature = "nalgebra")]
+//! # fn main() {
+//! use nalgebra::DMatrix;
+//! use rusty_machine::linalg::matrix::Matrix;
+//!
+//! let mat = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+//! let dmat = DMatrix::from(&mat);
+//!
+//! assert_eq!(dmat[(1, 0)], 4.0);
+//!
+//! let mat_back = Matrix::from(dmat);
+//! assert_eq!(mat_back.data(), mat.data());
+//! # }
+//! # #[cfg(not(feature = "nalgebra"))]
+//! # fn main() {}
+//! ```
+
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+
+use nalgebra::{DMatrix, DVector, Scalar};
+
+impl<T: Scalar> From<Matrix<T>> for DMatrix<T> {
+    fn from(mat: Matrix<T>) -> DMatrix<T> {
+        DMatrix::from_row_slice(mat.rows(), mat.cols(), mat.data())
+    }
+}
+
+impl<T: Scalar> From<&Matrix<T>> for DMatrix<T> {
+    fn from(mat: &Matrix<T>) -> DMatrix<T> {
+        DMatrix::from_row_slice(mat.rows(), mat.cols(), mat.data())
+    }
+}
+
+impl<T: Scalar> From<DMatrix<T>> for Matrix<T> {
+    fn from(mat: DMatrix<T>) -> Matrix<T> {
+        Matrix::from(&mat)
+    }
+}
+
+impl<T: Scalar> From<&DMatrix<T>> for Matrix<T> {
+    fn from(mat: &DMatrix<T>) -> Matrix<T> {
+        Matrix::from_fn(mat.nrows(), mat.ncols(), |i, j| mat[(i, j)].clone())
+    }
+}
+
+impl<T: Scalar> From<Vector<T>> for DVector<T> {
+    /// Converts the Vector without copying the data.
+    fn from(vec: Vector<T>) -> DVector<T> {
+        DVector::from_vec(vec.into_vec())
+    }
+}
+
+impl<T: Scalar> From<&Vector<T>> for DVector<T> {
+    fn from(vec: &Vector<T>) -> DVector<T> {
+        DVector::from_column_slice(vec.data())
+    }
+}
+
+impl<T: Scalar> From<DVector<T>> for Vector<T> {
+    /// Converts the DVector without copying the data.
+    fn from(vec: DVector<T>) -> Vector<T> {
+        Vector::new(vec.data.into())
+    }
+}
+
+impl<T: Scalar> From<&DVector<T>> for Vector<T> {
+    fn from(vec: &DVector<T>) -> Vector<T> {
+        Vector::new(vec.as_slice().to_vec())
+    }
+}
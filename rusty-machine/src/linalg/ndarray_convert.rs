@@ -0,0 +1,116 @@
+//! Conversions between rusty-machine and ndarray types.
+//!
+//! This module is only available with the `ndarray` feature enabled.
+//!
+//! Matrices are stored in row-major order so converting an owned `Matrix`
+//! into an `Array2` (and back, when the array has standard layout) reuses
+//! the existing allocation. Arrays with any other memory layout are copied.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "ndarray")]
+//! extern crate ndarray;
+//! # extern crate rusty_machine;
+//!
+//! # #[cfg(feature = "ndarray")]
+//! # fn main() {
+//! use ndarray::Array2;
+//! use rusty_machine::linalg::matrix::Matrix;
+//!
+//! let mat = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+//! let arr: Array2<f64> = mat.into();
+//!
+//! assert_eq!(arr[[1, 0]], 4.0);
+//!
+//! // Transposing the array gives a column-major layout which is copied.
+//! let mat_t = Matrix::from(arr.reversed_axes());
+//!
+//! assert_eq!(mat_t.rows(), 3);
+//! assert_eq!(mat_t[[0, 1]], 4.0);
+//! # }
+//! # #[cfg(not(feature = "ndarray"))]
+//! # fn main() {}
+//! ```
+
+use linalg::matrix::Matrix;
+use linalg::vector::Vector;
+
+use ndarray::{Array, Array1, Array2, ArrayView1, ArrayView2, Dimension};
+
+impl<T> From<Matrix<T>> for Array2<T> {
+    /// Converts the Matrix without copying the data.
+    fn from(mat: Matrix<T>) -> Array2<T> {
+        let shape = (mat.rows(), mat.cols());
+        Array2::from_shape_vec(shape, mat.into_vec()).unwrap()
+    }
+}
+
+impl<T: Clone> From<&Matrix<T>> for Array2<T> {
+    fn from(mat: &Matrix<T>) -> Array2<T> {
+        let shape = (mat.rows(), mat.cols());
+        Array2::from_shape_vec(shape, mat.data().clone()).unwrap()
+    }
+}
+
+impl<T: Clone> From<Array2<T>> for Matrix<T> {
+    /// Converts the array, reusing its buffer if it is in standard layout.
+    fn from(arr: Array2<T>) -> Matrix<T> {
+        let (rows, cols) = arr.dim();
+
+        if arr.is_standard_layout() {
+            Matrix::new(rows, cols, into_contiguous_vec(arr))
+        } else {
+            Matrix::new(rows, cols, arr.iter().cloned().collect())
+        }
+    }
+}
+
+impl<'a, T: Clone> From<ArrayView2<'a, T>> for Matrix<T> {
+    fn from(arr: ArrayView2<'a, T>) -> Matrix<T> {
+        let (rows, cols) = arr.dim();
+        Matrix::new(rows, cols, arr.iter().cloned().collect())
+    }
+}
+
+impl<T> From<Vector<T>> for Array1<T> {
+    /// Converts the Vector without copying the data.
+    fn from(vec: Vector<T>) -> Array1<T> {
+        Array1::from(vec.into_vec())
+    }
+}
+
+impl<T: Clone> From<&Vector<T>> for Array1<T> {
+    fn from(vec: &Vector<T>) -> Array1<T> {
+        Array1::from(vec.data().clone())
+    }
+}
+
+impl<T: Clone> From<Array1<T>> for Vector<T> {
+    /// Converts the array, reusing its buffer if it is contiguous.
+    fn from(arr: Array1<T>) -> Vector<T> {
+        if arr.is_standard_layout() {
+            Vector::new(into_contiguous_vec(arr))
+        } else {
+            Vector::new(arr.iter().cloned().collect())
+        }
+    }
+}
+
+impl<'a, T: Clone> From<ArrayView1<'a, T>> for Vector<T> {
+    fn from(arr: ArrayView1<'a, T>) -> Vector<T> {
+        Vector::new(arr.iter().cloned().collect())
+    }
+}
+
+/// Takes the buffer of a standard layout array, dropping any elements
+/// outside of the array view (left over from slicing).
+fn into_contiguous_vec<T, D: Dimension>(arr: Array<T, D>) -> Vec<T> {
+    let len = arr.len();
+    let (mut data, offset) = arr.into_raw_vec_and_offset();
+    let offset = offset.unwrap_or(0);
+
+    data.truncate(offset + len);
+    data.drain(..offset);
+    data
+}
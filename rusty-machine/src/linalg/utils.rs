@@ -3,6 +3,7 @@
 //! Contains support methods for linear algebra structs.
 
 use std::cmp;
+use std::vec::Vec;
 use libnum::Zero;
 use std::ops::{Add, Mul, Sub, Div};
 
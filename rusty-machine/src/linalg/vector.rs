@@ -4,8 +4,10 @@
 //! relating to the vector linear algebra struct.
 
 use std::ops::{Mul, Add, Div, Sub, Index, Neg};
+use std::vec::Vec;
 use libnum::{One, Zero, Float, FromPrimitive};
 use std::cmp::PartialEq;
+use std::iter::FromIterator;
 use linalg::Metric;
 use linalg::utils;
 
@@ -49,6 +51,11 @@ impl<T> Vector<T> {
         &self.data
     }
 
+    /// Returns a mutable slice of the underlying data.
+    pub fn mut_data(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
     /// Consumes the Vector and returns the Vec of data.
     pub fn into_vec(self) -> Vec<T> {
         self.data
@@ -65,6 +72,23 @@ impl<T: Clone> Clone for Vector<T> {
     }
 }
 
+/// Collects an iterator into a Vector.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::linalg::vector::Vector;
+///
+/// let vec = (0..4).map(|x| x as f64 * 0.5).collect::<Vector<f64>>();
+///
+/// assert_eq!(*vec.data(), vec![0.0, 0.5, 1.0, 1.5]);
+/// ```
+impl<T> FromIterator<T> for Vector<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Vector<T> {
+        Vector::new(iter.into_iter().collect())
+    }
+}
+
 impl<T: Copy> Vector<T> {
     /// Applies a function to each element in the vector.
     ///
@@ -82,7 +106,7 @@ impl<T: Copy> Vector<T> {
     ///
     /// assert_eq!(b.into_vec(), vec![2.0; 4]);
     /// ```
-    pub fn apply(self, f: &Fn(T) -> T) -> Vector<T> {
+    pub fn apply(self, f: &dyn Fn(T) -> T) -> Vector<T> {
         let new_data = self.data.into_iter().map(f).collect();
 
         Vector {
@@ -90,6 +114,57 @@ impl<T: Copy> Vector<T> {
             data: new_data,
         }
     }
+
+    /// Select elements from the Vector and form a new Vector from them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::vector::Vector;
+    ///
+    /// let a = Vector::new(vec![1.0,2.0,3.0,4.0,5.0]);
+    ///
+    /// let a_lower = a.select(&[2,3,4]);
+    ///
+    /// // Prints [3,4,5]
+    /// println!("{:?}", a_lower.data());
+    /// ```
+    pub fn select(&self, idxs: &[usize]) -> Vector<T> {
+        let mut new_data = Vec::with_capacity(idxs.len());
+
+        for idx in idxs.into_iter() {
+            new_data.push(self[*idx]);
+        }
+
+        Vector::new(new_data)
+    }
+
+    /// Select elements from the Vector into an existing Vector.
+    ///
+    /// This reuses the allocation of the output vector.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::linalg::vector::Vector;
+    ///
+    /// let a = Vector::new(vec![1.0, 2.0, 3.0]);
+    /// let mut b = Vector::new(vec![]);
+    ///
+    /// a.select_into(&[2, 0], &mut b);
+    /// assert_eq!(*b.data(), vec![3.0, 1.0]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - Panics if an index exceeds the vector size.
+    pub fn select_into(&self, idxs: &[usize], out: &mut Vector<T>) {
+        out.data.clear();
+        for idx in idxs {
+            out.data.push(self[*idx]);
+        }
+        out.size = idxs.len();
+    }
 }
 
 impl<T: Copy + PartialOrd> Vector<T> {
@@ -128,30 +203,6 @@ impl<T: Copy + PartialOrd> Vector<T> {
     pub fn argmin(&self) -> (usize, T) {
         utils::argmin(&self.data)
     }
-
-    /// Select elements from the Vector and form a new Vector from them.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use rusty_machine::linalg::vector::Vector;
-    ///
-    /// let a = Vector::new(vec![1.0,2.0,3.0,4.0,5.0]);
-    ///
-    /// let a_lower = a.select(&[2,3,4]);
-    ///
-    /// // Prints [3,4,5]
-    /// println!("{:?}", a_lower.data());
-    /// ```
-    pub fn select(&self, idxs: &[usize]) -> Vector<T> {
-        let mut new_data = Vec::with_capacity(idxs.len());
-
-        for idx in idxs.into_iter() {
-            new_data.push(self[*idx]);
-        }
-
-        Vector::new(new_data)
-    }
 }
 
 impl<T: Zero + One + Copy> Vector<T> {
@@ -0,0 +1,98 @@
+//! Categorical distribution module.
+//!
+//! Contains the Categorical struct, a distribution over
+//! a finite number of categories.
+
+use stats::dist::Distribution;
+#[cfg(feature = "rand")]
+use rand::Rng;
+#[cfg(feature = "rand")]
+use rand::distributions::{Sample, IndependentSample};
+
+/// A Categorical random variable.
+///
+/// Takes the values `0..k` with the given probabilities.
+pub struct Categorical {
+    probs: Vec<f64>,
+}
+
+impl Categorical {
+    /// Constructs a new Categorical random variable from
+    /// the weight of each category.
+    ///
+    /// The weights are normalized to sum to one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::stats::dist::Categorical;
+    /// use rusty_machine::stats::dist::Distribution;
+    ///
+    /// let cat = Categorical::new(&[1f64, 3f64]);
+    ///
+    /// assert_eq!(cat.pdf(1), 0.75);
+    /// assert_eq!(cat.cdf(1), 1f64);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - There are no categories.
+    /// - A weight is negative or the weights sum to zero.
+    pub fn new(weights: &[f64]) -> Categorical {
+        assert!(!weights.is_empty(), "There must be at least one category.");
+        assert!(weights.iter().all(|&w| w >= 0f64),
+                "Category weights must be non-negative.");
+
+        let total: f64 = weights.iter().sum();
+        assert!(total > 0f64, "Category weights must not sum to zero.");
+
+        Categorical { probs: weights.iter().map(|w| w / total).collect() }
+    }
+
+    /// Returns the probability of each category.
+    pub fn probs(&self) -> &[f64] {
+        &self.probs
+    }
+}
+
+impl Distribution<usize> for Categorical {
+    /// The probability of the category.
+    ///
+    /// Categories out of range have probability zero.
+    fn pdf(&self, x: usize) -> f64 {
+        self.probs.get(x).cloned().unwrap_or(0f64)
+    }
+
+    /// The probability of the category or any lower category.
+    fn cdf(&self, x: usize) -> f64 {
+        if x + 1 >= self.probs.len() {
+            1f64
+        } else {
+            self.probs[..x + 1].iter().sum()
+        }
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Sample<usize> for Categorical {
+    fn sample<R: Rng>(&mut self, rng: &mut R) -> usize {
+        self.ind_sample(rng)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl IndependentSample<usize> for Categorical {
+    fn ind_sample<R: Rng>(&self, rng: &mut R) -> usize {
+        let mut u = rng.gen::<f64>();
+
+        for (i, p) in self.probs.iter().enumerate() {
+            if u < *p {
+                return i;
+            }
+            u -= *p;
+        }
+
+        // Rounding can leave a little probability at the end.
+        self.probs.iter().rposition(|&p| p > 0f64).unwrap()
+    }
+}
@@ -0,0 +1,106 @@
+//! Dirichlet distribution module.
+//!
+//! Contains the Dirichlet struct, a distribution over
+//! probability vectors.
+
+use linalg::vector::Vector;
+use stats::dist::gamma::{ln_gamma, xlogy};
+#[cfg(feature = "rand")]
+use rand::Rng;
+#[cfg(feature = "rand")]
+use rand::distributions::{Sample, IndependentSample};
+#[cfg(feature = "rand")]
+use rand::distributions::gamma::Gamma;
+
+/// A Dirichlet random variable.
+///
+/// Samples are vectors of non-negative values which sum to one.
+pub struct Dirichlet {
+    alpha: Vec<f64>,
+}
+
+impl Dirichlet {
+    /// Constructs a new Dirichlet random variable with the given
+    /// concentration parameters.
+    ///
+    /// # Panics
+    ///
+    /// - There are fewer than two parameters.
+    /// - A parameter is not positive.
+    pub fn new(alpha: Vec<f64>) -> Dirichlet {
+        assert!(alpha.len() >= 2, "There must be at least two parameters.");
+        assert!(alpha.iter().all(|&a| a > 0f64),
+                "Concentration parameters must be positive.");
+
+        Dirichlet { alpha: alpha }
+    }
+
+    /// Constructs a symmetric Dirichlet random variable of
+    /// the given dimension.
+    pub fn symmetric(alpha: f64, dim: usize) -> Dirichlet {
+        Dirichlet::new(vec![alpha; dim])
+    }
+
+    /// Returns the concentration parameters.
+    pub fn alpha(&self) -> &[f64] {
+        &self.alpha
+    }
+
+    /// The pdf of the Dirichlet distribution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::stats::dist::Dirichlet;
+    /// use rusty_machine::linalg::vector::Vector;
+    ///
+    /// // The uniform distribution over the 2-simplex.
+    /// let dir = Dirichlet::symmetric(1f64, 3);
+    ///
+    /// let x = Vector::new(vec![0.2, 0.3, 0.5]);
+    /// assert!((dir.pdf(&x) - 2f64).abs() < 1e-12);
+    /// ```
+    pub fn pdf(&self, x: &Vector<f64>) -> f64 {
+        self.logpdf(x).exp()
+    }
+
+    /// The log pdf of the Dirichlet distribution.
+    ///
+    /// # Panics
+    ///
+    /// - The input has the wrong dimension.
+    pub fn logpdf(&self, x: &Vector<f64>) -> f64 {
+        assert!(x.size() == self.alpha.len(),
+                "Input has the wrong dimension for the distribution.");
+
+        let total: f64 = self.alpha.iter().sum();
+        let norm = ln_gamma(total) - self.alpha.iter().map(|&a| ln_gamma(a)).sum::<f64>();
+
+        norm +
+        self.alpha
+            .iter()
+            .zip(x.data().iter())
+            .map(|(&a, &x)| xlogy(a - 1f64, x))
+            .sum::<f64>()
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Sample<Vector<f64>> for Dirichlet {
+    fn sample<R: Rng>(&mut self, rng: &mut R) -> Vector<f64> {
+        self.ind_sample(rng)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl IndependentSample<Vector<f64>> for Dirichlet {
+    fn ind_sample<R: Rng>(&self, rng: &mut R) -> Vector<f64> {
+        let draws = Vector::new(self.alpha
+            .iter()
+            .map(|&a| Gamma::new(a, 1f64).ind_sample(rng))
+            .collect::<Vec<_>>());
+        let total = draws.sum();
+
+        draws / total
+    }
+}
@@ -5,8 +5,11 @@
 //! traits added within the containing stats module.
 
 use stats::dist::Distribution;
+#[cfg(feature = "rand")]
 use rand::Rng;
+#[cfg(feature = "rand")]
 use rand::distributions::{Sample, IndependentSample};
+#[cfg(feature = "rand")]
 use rand::distributions::exponential::Exp1;
 
 /// An Exponential random variable.
@@ -102,12 +105,14 @@ impl Distribution<f64> for Exponential {
     }
 }
 
+#[cfg(feature = "rand")]
 impl Sample<f64> for Exponential {
     fn sample<R: Rng>(&mut self, rng: &mut R) -> f64 {
         self.ind_sample(rng)
     }
 }
 
+#[cfg(feature = "rand")]
 impl IndependentSample<f64> for Exponential {
     fn ind_sample<R: Rng>(&self, rng: &mut R) -> f64 {
         let Exp1(n) = rng.gen::<Exp1>();
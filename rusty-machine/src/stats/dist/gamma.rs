@@ -0,0 +1,258 @@
+//! Gamma distribution module.
+//!
+//! Contains extension methods for the Gamma struct
+//! found in the rand crate. This is provided through
+//! traits added within the containing stats module.
+
+use stats::dist::Distribution;
+#[cfg(feature = "rand")]
+use rand::Rng;
+#[cfg(feature = "rand")]
+use rand::distributions::{Sample, IndependentSample};
+#[cfg(feature = "rand")]
+use rand::distributions::gamma::Gamma as RandGamma;
+use std::f64;
+use std::f64::consts::PI;
+
+/// Coefficients of the Lanczos approximation with g = 7.
+const LANCZOS: [f64; 9] = [0.99999999999980993,
+                           676.5203681218851,
+                           -1259.1392167224028,
+                           771.32342877765313,
+                           -176.61502916214059,
+                           12.507343278686905,
+                           -0.13857109526572012,
+                           9.9843695780195716e-6,
+                           1.5056327351493116e-7];
+
+/// The most terms summed by the incomplete gamma series and continued
+/// fraction.
+const MAX_ITERS: usize = 10000;
+
+/// A Gamma random variable.
+///
+/// Parameterized by the shape k and the scale θ, with mean kθ.
+pub struct Gamma {
+    shape: f64,
+    scale: f64,
+}
+
+/// The default Gamma random variable.
+///
+/// The defaults are:
+///
+/// - shape = 1
+/// - scale = 1
+impl Default for Gamma {
+    fn default() -> Gamma {
+        Gamma {
+            shape: 1f64,
+            scale: 1f64,
+        }
+    }
+}
+
+impl Gamma {
+    /// Constructs a new Gamma random variable with given
+    /// shape and scale.
+    ///
+    /// # Panics
+    ///
+    /// - The shape or scale is not positive.
+    pub fn new(shape: f64, scale: f64) -> Gamma {
+        assert!(shape > 0f64, "Shape must be positive.");
+        assert!(scale > 0f64, "Scale must be positive.");
+
+        Gamma {
+            shape: shape,
+            scale: scale,
+        }
+    }
+
+    /// Returns the shape parameter.
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    /// Returns the scale parameter.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+}
+
+impl Distribution<f64> for Gamma {
+    /// The pdf of the gamma distribution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::stats::dist::Gamma;
+    /// use rusty_machine::stats::dist::Distribution;
+    ///
+    /// // With shape one this is an exponential distribution.
+    /// let gamma = Gamma::new(1f64, 0.5);
+    ///
+    /// assert!((gamma.pdf(1f64) - 2f64 * (-2f64).exp()).abs() < 1e-12);
+    /// ```
+    fn pdf(&self, x: f64) -> f64 {
+        self.logpdf(x).exp()
+    }
+
+    /// The log pdf of the gamma distribution.
+    fn logpdf(&self, x: f64) -> f64 {
+        assert!(x >= 0., "Input to log pdf must be non-negative for gamma.");
+        xlogy(self.shape - 1f64, x) - x / self.scale - ln_gamma(self.shape) -
+        self.shape * self.scale.ln()
+    }
+
+    /// The cdf of the gamma distribution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::stats::dist::Gamma;
+    /// use rusty_machine::stats::dist::Distribution;
+    ///
+    /// let gamma = Gamma::new(1f64, 1f64);
+    ///
+    /// assert!((gamma.cdf(2f64) - (1f64 - (-2f64).exp())).abs() < 1e-12);
+    /// assert_eq!(gamma.cdf(f64::INFINITY), 1f64);
+    /// assert!(gamma.cdf(f64::NAN).is_nan());
+    /// ```
+    fn cdf(&self, x: f64) -> f64 {
+        assert!(x >= 0. || x.is_nan(), "Input to cdf must be non-negative for gamma.");
+        lower_regularized_gamma(self.shape, x / self.scale)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Sample<f64> for Gamma {
+    fn sample<R: Rng>(&mut self, rng: &mut R) -> f64 {
+        self.ind_sample(rng)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl IndependentSample<f64> for Gamma {
+    fn ind_sample<R: Rng>(&self, rng: &mut R) -> f64 {
+        RandGamma::new(self.shape, self.scale).ind_sample(rng)
+    }
+}
+
+/// The natural logarithm of the gamma function.
+///
+/// Uses the Lanczos approximation, accurate to around 15 significant
+/// digits.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::stats::dist::gamma::ln_gamma;
+///
+/// // Gamma(5) = 4! = 24
+/// assert!((ln_gamma(5f64) - 24f64.ln()).abs() < 1e-12);
+/// ```
+///
+/// # Panics
+///
+/// - The input is not positive.
+pub fn ln_gamma(x: f64) -> f64 {
+    assert!(x > 0f64, "Input to ln_gamma must be positive.");
+
+    if x < 0.5 {
+        // The reflection formula.
+        return (PI / (PI * x).sin()).ln() - ln_gamma(1f64 - x);
+    }
+
+    let x = x - 1f64;
+    let t = x + 7.5;
+    let sum = LANCZOS[1..]
+        .iter()
+        .enumerate()
+        .fold(LANCZOS[0], |acc, (i, c)| acc + c / (x + (i + 1) as f64));
+
+    0.5 * (2f64 * PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Computes `x * ln(y)`, which is zero when `x` is zero.
+///
+/// This is the limit used by densities at the edge of their support,
+/// where `x * ln(y)` would otherwise be `0 * -inf`.
+///
+/// # Examples
+///
+/// ```
+/// use rusty_machine::stats::dist::gamma::xlogy;
+///
+/// assert_eq!(xlogy(0f64, 0f64), 0f64);
+/// assert_eq!(xlogy(2f64, 1f64), 0f64);
+/// assert!(xlogy(0f64, f64::NAN).is_nan());
+/// ```
+pub fn xlogy(x: f64, y: f64) -> f64 {
+    if x == 0f64 && !y.is_nan() {
+        0f64
+    } else {
+        x * y.ln()
+    }
+}
+
+/// The regularized lower incomplete gamma function P(a, x).
+///
+/// Returns NaN for a NaN input. The series and continued fraction
+/// stop after `MAX_ITERS` terms.
+fn lower_regularized_gamma(a: f64, x: f64) -> f64 {
+    if x.is_nan() {
+        return f64::NAN;
+    }
+    if x <= 0f64 {
+        return 0f64;
+    }
+    if x.is_infinite() {
+        return 1f64;
+    }
+
+    let scale = (-x + a * x.ln() - ln_gamma(a)).exp();
+
+    if x < a + 1f64 {
+        // The series expansion converges quickly here.
+        let mut term = 1f64 / a;
+        let mut sum = term;
+        let mut n = a;
+        for _ in 0..MAX_ITERS {
+            if term.abs() <= sum.abs() * f64::EPSILON {
+                break;
+            }
+            n += 1f64;
+            term *= x / n;
+            sum += term;
+        }
+        sum * scale
+    } else {
+        // The continued fraction for the upper function, by Lentz's method.
+        let tiny = f64::MIN_POSITIVE / f64::EPSILON;
+        let mut b = x + 1f64 - a;
+        let mut c = 1f64 / tiny;
+        let mut d = 1f64 / b;
+        let mut h = d;
+        for i in 1..MAX_ITERS {
+            let i = i as f64;
+            let an = -i * (i - a);
+            b += 2f64;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1f64 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1f64).abs() <= f64::EPSILON {
+                break;
+            }
+        }
+        1f64 - scale * h
+    }
+}
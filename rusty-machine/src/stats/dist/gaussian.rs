@@ -5,8 +5,11 @@
 //! traits added within the containing stats module.
 
 use stats::dist::Distribution;
+#[cfg(feature = "rand")]
 use rand::Rng;
+#[cfg(feature = "rand")]
 use rand::distributions::{Sample, IndependentSample};
+#[cfg(feature = "rand")]
 use rand::distributions::normal::StandardNormal;
 use super::consts as stat_consts;
 use std::f64::consts as float_consts;
@@ -148,12 +151,14 @@ impl Distribution<f64> for Gaussian {
     }
 }
 
+#[cfg(feature = "rand")]
 impl Sample<f64> for Gaussian {
     fn sample<R: Rng>(&mut self, rng: &mut R) -> f64 {
         self.ind_sample(rng)
     }
 }
 
+#[cfg(feature = "rand")]
 impl IndependentSample<f64> for Gaussian {
     fn ind_sample<R: Rng>(&self, rng: &mut R) -> f64 {
         let StandardNormal(n) = rng.gen::<StandardNormal>();
@@ -1,8 +1,16 @@
 pub mod exponential;
 pub mod gaussian;
+pub mod gamma;
+pub mod categorical;
+pub mod dirichlet;
+pub mod multivariate_normal;
 
 pub use self::gaussian::Gaussian;
 pub use self::exponential::Exponential;
+pub use self::gamma::Gamma;
+pub use self::categorical::Categorical;
+pub use self::dirichlet::Dirichlet;
+pub use self::multivariate_normal::MultivariateNormal;
 
 /// Statistical constants
 ///
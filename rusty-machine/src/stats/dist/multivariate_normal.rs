@@ -0,0 +1,124 @@
+//! Multivariate normal distribution module.
+//!
+//! Contains the MultivariateNormal struct. The Cholesky
+//! decomposition of the covariance is computed once and used
+//! for the density and sampling.
+
+use linalg::matrix::{Matrix, BaseMatrix};
+use linalg::vector::Vector;
+use stats::dist::consts as stat_consts;
+#[cfg(feature = "rand")]
+use rand::Rng;
+#[cfg(feature = "rand")]
+use rand::distributions::{Sample, IndependentSample};
+#[cfg(feature = "rand")]
+use rand::distributions::normal::StandardNormal;
+
+/// A multivariate normal random variable.
+pub struct MultivariateNormal {
+    mean: Vector<f64>,
+    cov: Matrix<f64>,
+    /// The lower triangular Cholesky factor of the covariance.
+    chol: Matrix<f64>,
+}
+
+impl MultivariateNormal {
+    /// Constructs a new multivariate normal random variable from
+    /// a mean and a covariance matrix.
+    ///
+    /// # Panics
+    ///
+    /// - The covariance is not square or does not match the mean.
+    /// - The covariance is not positive definite.
+    pub fn new(mean: Vector<f64>, cov: Matrix<f64>) -> MultivariateNormal {
+        assert!(cov.rows() == mean.size() && cov.cols() == mean.size(),
+                "Covariance dimensions do not match the mean.");
+
+        let chol = cov.cholesky();
+        assert!((0..chol.rows()).all(|i| chol[[i, i]] > 0f64),
+                "Covariance is not positive definite.");
+
+        MultivariateNormal {
+            mean: mean,
+            cov: cov,
+            chol: chol,
+        }
+    }
+
+    /// Returns the mean.
+    pub fn mean(&self) -> &Vector<f64> {
+        &self.mean
+    }
+
+    /// Returns the covariance matrix.
+    pub fn cov(&self) -> &Matrix<f64> {
+        &self.cov
+    }
+
+    /// The pdf of the multivariate normal distribution.
+    pub fn pdf(&self, x: &Vector<f64>) -> f64 {
+        self.logpdf(x).exp()
+    }
+
+    /// The log pdf of the multivariate normal distribution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rusty_machine::stats::dist::MultivariateNormal;
+    /// use rusty_machine::stats::dist::consts;
+    /// use rusty_machine::linalg::matrix::Matrix;
+    /// use rusty_machine::linalg::vector::Vector;
+    ///
+    /// let mvn = MultivariateNormal::new(Vector::zeros(2), Matrix::identity(2));
+    ///
+    /// let lpdf_zero = mvn.logpdf(&Vector::zeros(2));
+    /// assert!((lpdf_zero + consts::LN_2_PI).abs() < 1e-12);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// - The input has the wrong dimension.
+    pub fn logpdf(&self, x: &Vector<f64>) -> f64 {
+        assert!(x.size() == self.mean.size(),
+                "Input has the wrong dimension for the distribution.");
+
+        let z = forward_substitution(&self.chol, x - &self.mean);
+        let log_det: f64 = (0..self.chol.rows()).map(|i| self.chol[[i, i]].ln()).sum();
+
+        -0.5 * (self.mean.size() as f64 * stat_consts::LN_2_PI + z.dot(&z)) - log_det
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Sample<Vector<f64>> for MultivariateNormal {
+    fn sample<R: Rng>(&mut self, rng: &mut R) -> Vector<f64> {
+        self.ind_sample(rng)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl IndependentSample<Vector<f64>> for MultivariateNormal {
+    fn ind_sample<R: Rng>(&self, rng: &mut R) -> Vector<f64> {
+        let z = Vector::new((0..self.mean.size())
+            .map(|_| {
+                let StandardNormal(n) = rng.gen::<StandardNormal>();
+                n
+            })
+            .collect::<Vec<_>>());
+
+        &self.mean + &self.chol * z
+    }
+}
+
+/// Solves Lx = y for lower triangular L.
+fn forward_substitution(l: &Matrix<f64>, y: Vector<f64>) -> Vector<f64> {
+    let mut x = y.into_vec();
+
+    for i in 0..x.len() {
+        let sum: f64 = l.row(i)[..i].iter().zip(x.iter()).map(|(a, b)| a * b).sum();
+        x[i] = (x[i] - sum) / l[[i, i]];
+    }
+
+    Vector::new(x)
+}
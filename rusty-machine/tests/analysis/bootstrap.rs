@@ -0,0 +1,62 @@
+use rm::analysis::bootstrap::Bootstrap;
+use rm::analysis::regression::mean_absolute_error;
+use rm::linalg::vector::Vector;
+
+fn data() -> (Vector<f64>, Vector<f64>) {
+    let outputs = Vector::new((0..50).map(|i| i as f64).collect());
+    let targets = Vector::new((0..50).map(|i| i as f64 + (i % 5) as f64).collect());
+    (outputs, targets)
+}
+
+#[test]
+fn interval_contains_estimate() {
+    let (outputs, targets) = data();
+
+    let ci = Bootstrap::new(500, 3).interval(&outputs, &targets, mean_absolute_error);
+
+    assert_eq!(ci.estimate, 2.0);
+    assert_eq!(ci.scores.len(), 500);
+    assert!(ci.lower < ci.estimate && ci.estimate < ci.upper);
+    assert!(ci.lower >= 0.0 && ci.upper <= 4.0);
+}
+
+#[test]
+fn interval_is_reproducible_and_narrows() {
+    let (outputs, targets) = data();
+
+    let wide = Bootstrap::new(500, 3).interval(&outputs, &targets, mean_absolute_error);
+    let again = Bootstrap::new(500, 3).interval(&outputs, &targets, mean_absolute_error);
+    let narrow = Bootstrap::new(500, 3)
+        .confidence(0.5)
+        .interval(&outputs, &targets, mean_absolute_error);
+
+    assert_eq!(wide.scores, again.scores);
+    assert!(narrow.upper - narrow.lower < wide.upper - wide.lower);
+}
+
+#[test]
+fn constant_metric_has_no_width() {
+    let (outputs, targets) = data();
+
+    let ci = Bootstrap::new(20, 1).interval(&outputs, &targets, |_, _| 1.0);
+
+    assert_eq!((ci.lower, ci.upper), (1.0, 1.0));
+}
+
+#[test]
+fn nan_scores_are_dropped() {
+    let (outputs, targets) = data();
+
+    let ci = Bootstrap::new(50, 1).interval(&outputs, &targets, |o: &Vector<f64>, _: &Vector<f64>| {
+        if o[0] < 25.0 { 0.0 } else { f64::NAN }
+    });
+
+    assert!(ci.scores.len() < 50);
+    assert!(ci.scores.iter().all(|&s| s == 0.0));
+}
+
+#[test]
+#[should_panic]
+fn invalid_confidence() {
+    let _ = Bootstrap::new(10, 1).confidence(1.0);
+}
@@ -0,0 +1,58 @@
+use rm::analysis::confusion_matrix::{confusion_matrix, ConfusionMatrix};
+
+#[test]
+fn binary_counts_and_rates() {
+    let predicted = vec![1, 1, 0, 0, 1, 0];
+    let actual = vec![1, 0, 0, 1, 1, 0];
+
+    let cm = confusion_matrix(&predicted, &actual);
+    let pos = cm.label_index(&1).unwrap();
+
+    assert_eq!(cm.true_positives(pos), 2);
+    assert_eq!(cm.false_positives(pos), 1);
+    assert_eq!(cm.false_negatives(pos), 1);
+    assert_eq!(cm.true_negatives(pos), 2);
+    assert_eq!(cm.total(), 6);
+
+    assert_eq!(cm.recalls()[pos], 2.0 / 3.0);
+    assert_eq!(cm.precisions()[pos], 2.0 / 3.0);
+    assert_eq!(cm.specificities()[pos], 2.0 / 3.0);
+    assert_eq!(cm.false_positive_rates()[pos], 1.0 / 3.0);
+    assert!((cm.f1_scores()[pos] - 2.0 / 3.0).abs() < 1e-12);
+}
+
+#[test]
+fn labels_include_unpredicted_classes() {
+    let predicted = vec!["a", "a", "b"];
+    let actual = vec!["a", "c", "b"];
+
+    let cm = confusion_matrix(&predicted, &actual);
+
+    assert_eq!(cm.labels(), &["a", "b", "c"]);
+    assert_eq!(cm.counts()[[2, 0]], 1);
+
+    // Class "c" is never predicted.
+    assert_eq!(cm.precisions()[2], 0.0);
+    assert_eq!(cm.recalls()[2], 0.0);
+}
+
+#[test]
+fn with_labels_keeps_empty_classes() {
+    let cm = ConfusionMatrix::with_labels(&[0.0, 1.0], &[0.0, 1.0], vec![0.0, 1.0, 2.0]);
+
+    assert_eq!(cm.counts().rows(), 3);
+    assert_eq!(cm.support(2), 0);
+    assert_eq!(cm.accuracy(), 1.0);
+}
+
+#[test]
+#[should_panic]
+fn with_labels_unknown_class() {
+    let _ = ConfusionMatrix::with_labels(&[0, 3], &[0, 1], vec![0, 1]);
+}
+
+#[test]
+#[should_panic]
+fn mismatched_lengths() {
+    let _ = confusion_matrix(&[0, 1], &[0]);
+}
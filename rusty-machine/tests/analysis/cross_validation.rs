@@ -0,0 +1,90 @@
+use rm::analysis::cross_validation::{KFold, StratifiedKFold, cross_val_score};
+#[cfg(feature = "parallel")]
+use rm::analysis::cross_validation::{cross_val_score_folds, cross_val_score_parallel};
+use rm::learning::lin_reg::LinRegressor;
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+fn assert_partition(train: &[usize], test: &[usize], n: usize) {
+    let mut all: Vec<usize> = train.iter().chain(test.iter()).cloned().collect();
+    all.sort();
+    assert_eq!(all, (0..n).collect::<Vec<_>>());
+}
+
+#[test]
+fn kfold_sizes() {
+    let folds: Vec<_> = KFold::new(10, 3).collect();
+
+    assert_eq!(folds.len(), 3);
+    let sizes: Vec<usize> = folds.iter().map(|f| f.1.len()).collect();
+    assert_eq!(sizes, vec![4, 3, 3]);
+
+    for (train, test) in &folds {
+        assert_partition(train, test, 10);
+    }
+}
+
+#[test]
+fn kfold_shuffled_covers_all() {
+    let mut tested: Vec<usize> = KFold::shuffled(9, 3, 4).flat_map(|(_, t)| t).collect();
+    tested.sort();
+
+    assert_eq!(tested, (0..9).collect::<Vec<_>>());
+}
+
+#[test]
+#[should_panic]
+fn kfold_too_many_folds() {
+    let _ = KFold::new(3, 4);
+}
+
+#[test]
+fn stratified_kfold_preserves_classes() {
+    let targets = vec![0, 0, 0, 0, 0, 0, 1, 1, 1];
+
+    for (train, test) in StratifiedKFold::shuffled(&targets, 3, 8) {
+        assert_partition(&train, &test, 9);
+        assert_eq!(test.len(), 3);
+        assert_eq!(test.iter().filter(|&&i| targets[i] == 1).count(), 1);
+    }
+}
+
+#[test]
+fn cross_val_score_per_fold() {
+    let inputs = Matrix::new(8, 1, (0..8).map(|x| x as f64).collect());
+    let targets = Vector::new((0..8).map(|x| 2.0 * x as f64 - 1.0).collect());
+
+    let mse = |outputs: &Vector<f64>, targets: &Vector<f64>| {
+        let diff = outputs - targets;
+        diff.dot(&diff) / diff.size() as f64
+    };
+
+    let scores = cross_val_score(LinRegressor::default, &inputs, &targets, 4, mse);
+
+    assert_eq!(scores.len(), 4);
+    for s in scores {
+        assert!(s < 1e-8);
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn cross_val_score_parallel_matches_serial() {
+    let inputs = Matrix::new(10, 1, (0..10).map(|x| x as f64).collect());
+    let targets = Vector::new((0..10).map(|x| (x * x) as f64).collect());
+
+    let mse = |outputs: &Vector<f64>, targets: &Vector<f64>| {
+        let diff = outputs - targets;
+        diff.dot(&diff) / diff.size() as f64
+    };
+
+    let folds: Vec<_> = KFold::shuffled(inputs.rows(), 5, 3).collect();
+    let serial = cross_val_score_folds(LinRegressor::default,
+                                       &inputs,
+                                       &targets,
+                                       folds.iter().cloned(),
+                                       &mse);
+    let parallel = cross_val_score_parallel(LinRegressor::default, &inputs, &targets, &folds, &mse);
+
+    assert_eq!(serial, parallel);
+}
@@ -0,0 +1,127 @@
+use rm::analysis::curves::{roc_curve, roc_auc_score, precision_recall_curve, average_precision,
+                           auc, calibration_curve, brier_score, tune_threshold,
+                           ThresholdObjective};
+
+#[test]
+fn roc_perfect_and_reversed() {
+    let targets = vec![0.0, 0.0, 1.0, 1.0];
+
+    assert_eq!(roc_auc_score(&[0.1, 0.2, 0.8, 0.9], &targets), 1.0);
+    assert_eq!(roc_auc_score(&[0.9, 0.8, 0.2, 0.1], &targets), 0.0);
+}
+
+#[test]
+fn roc_ties_share_a_point() {
+    let scores = vec![0.5, 0.5, 0.5, 0.5];
+    let targets = vec![0.0, 1.0, 0.0, 1.0];
+
+    let roc = roc_curve(&scores, &targets);
+
+    assert_eq!(roc.thresholds.len(), 2);
+    assert_eq!(roc.true_positive_rates, vec![0.0, 1.0]);
+    assert_eq!(roc_auc_score(&scores, &targets), 0.5);
+}
+
+#[test]
+fn roc_auc_matches_pair_ranking() {
+    let scores = vec![0.3, 0.7, 0.2, 0.9, 0.6, 0.4];
+    let targets = vec![0.0, 1.0, 1.0, 1.0, 0.0, 0.0];
+
+    // Count the correctly ordered positive-negative pairs.
+    let mut correct = 0.0;
+    for (s_p, _) in scores.iter().zip(targets.iter()).filter(|&(_, t)| *t == 1.0) {
+        for (s_n, _) in scores.iter().zip(targets.iter()).filter(|&(_, t)| *t == 0.0) {
+            if s_p > s_n {
+                correct += 1.0;
+            }
+        }
+    }
+
+    assert!((roc_auc_score(&scores, &targets) - correct / 9.0).abs() < 1e-12);
+}
+
+#[test]
+fn average_precision_values() {
+    let targets = vec![0.0, 0.0, 1.0, 1.0];
+
+    assert_eq!(average_precision(&[0.1, 0.2, 0.8, 0.9], &targets), 1.0);
+
+    // Recall steps of 0.5 at precisions 1 and 2/3.
+    let ap = average_precision(&[0.1, 0.4, 0.35, 0.8], &targets);
+    assert!((ap - (0.5 + 1.0 / 3.0)).abs() < 1e-12);
+
+    let pr = precision_recall_curve(&[0.1, 0.4, 0.35, 0.8], &targets);
+    assert_eq!(*pr.recalls.last().unwrap(), 1.0);
+}
+
+#[test]
+fn auc_decreasing_x() {
+    assert_eq!(auc(&[2.0, 1.0, 0.0], &[1.0, 1.0, 0.0]), 1.5);
+}
+
+#[test]
+#[should_panic]
+fn roc_single_class_panics() {
+    let _ = roc_curve(&[0.1, 0.2], &[1.0, 1.0]);
+}
+
+#[test]
+#[should_panic]
+fn invalid_targets_panic() {
+    let _ = precision_recall_curve(&[0.1, 0.2], &[1.0, 2.0]);
+}
+
+#[test]
+fn calibration_skips_empty_bins() {
+    let probs = vec![0.05, 0.15, 0.95, 1.0];
+    let targets = vec![0.0, 1.0, 1.0, 1.0];
+
+    let curve = calibration_curve(&probs, &targets, 10);
+
+    assert_eq!(curve.counts, vec![1, 1, 2]);
+    assert_eq!(curve.observed_frequency, vec![0.0, 1.0, 1.0]);
+    assert!((curve.mean_predicted[2] - 0.975).abs() < 1e-12);
+}
+
+#[test]
+fn brier_score_perfect() {
+    assert_eq!(brier_score(&[0.0, 1.0, 1.0], &[0.0, 1.0, 1.0]), 0.0);
+    assert_eq!(brier_score(&[1.0, 0.0], &[0.0, 1.0]), 1.0);
+}
+
+#[test]
+#[should_panic]
+fn calibration_invalid_probability() {
+    let _ = calibration_curve(&[1.5], &[1.0], 5);
+}
+
+#[test]
+fn tune_threshold_objectives() {
+    let scores = vec![0.9, 0.8, 0.7, 0.6, 0.5, 0.4, 0.3, 0.2];
+    let targets = vec![1.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 0.0];
+
+    // Predicting the top six finds every positive with two false
+    // positives.
+    let f1 = tune_threshold(&scores, &targets, ThresholdObjective::F1);
+    assert_eq!(f1.threshold, 0.4);
+    assert!((f1.score - 0.8).abs() < 1e-12);
+
+    let j = tune_threshold(&scores, &targets, ThresholdObjective::YoudenJ);
+    assert_eq!(j.threshold, 0.8);
+    assert!((j.score - 0.5).abs() < 1e-12);
+
+    // Full recall needs the threshold at 0.4, with a precision of 4/6.
+    let p = tune_threshold(&scores, &targets, ThresholdObjective::PrecisionAtRecall(1.0));
+    assert_eq!(p.threshold, 0.4);
+    assert!((p.score - 4.0 / 6.0).abs() < 1e-12);
+
+    let p = tune_threshold(&scores, &targets, ThresholdObjective::PrecisionAtRecall(0.5));
+    assert_eq!(p.threshold, 0.8);
+    assert_eq!(p.score, 1.0);
+}
+
+#[test]
+#[should_panic]
+fn tune_threshold_youden_needs_negatives() {
+    tune_threshold(&[0.2, 0.4], &[1.0, 1.0], ThresholdObjective::YoudenJ);
+}
@@ -0,0 +1,65 @@
+use rm::analysis::cross_validation::KFold;
+use rm::analysis::learning_curve::{learning_curve, validation_curve};
+use rm::learning::SupModel;
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+/// Predicts the number of samples it was trained on, plus an offset.
+struct CountModel {
+    offset: f64,
+    count: usize,
+}
+
+impl SupModel<Matrix<f64>, Vector<f64>> for CountModel {
+    fn predict(&self, inputs: &Matrix<f64>) -> Vector<f64> {
+        Vector::new(vec![self.count as f64 + self.offset; inputs.rows()])
+    }
+
+    fn train(&mut self, inputs: &Matrix<f64>, _: &Vector<f64>) {
+        self.count = inputs.rows();
+    }
+}
+
+fn first_output(outputs: &Vector<f64>, _: &Vector<f64>) -> f64 {
+    outputs[0]
+}
+
+#[test]
+fn learning_curve_uses_fractions() {
+    let inputs = Matrix::new(10, 1, vec![0.0; 10]);
+    let targets = Vector::new(vec![0.0; 10]);
+    let folds: Vec<_> = KFold::new(10, 5).collect();
+
+    let builder = || CountModel { offset: 0.0, count: 0 };
+    let curve = learning_curve(builder, &inputs, &targets, &folds, &[0.1, 0.5, 1.0], first_output);
+
+    assert_eq!(curve.train_sizes, vec![1, 4, 8]);
+    assert_eq!(curve.mean_train_scores(), vec![1.0, 4.0, 8.0]);
+    assert_eq!(curve.mean_test_scores(), vec![1.0, 4.0, 8.0]);
+    assert_eq!(curve.test_scores[0].len(), 5);
+}
+
+#[test]
+#[should_panic]
+fn learning_curve_invalid_fraction() {
+    let inputs = Matrix::new(4, 1, vec![0.0; 4]);
+    let targets = Vector::new(vec![0.0; 4]);
+    let folds: Vec<_> = KFold::new(4, 2).collect();
+
+    let builder = || CountModel { offset: 0.0, count: 0 };
+    let _ = learning_curve(builder, &inputs, &targets, &folds, &[0.0], first_output);
+}
+
+#[test]
+fn validation_curve_sweeps_values() {
+    let inputs = Matrix::new(6, 1, vec![0.0; 6]);
+    let targets = Vector::new(vec![0.0; 6]);
+    let folds: Vec<_> = KFold::new(6, 3).collect();
+
+    let builder = |v: f64| CountModel { offset: v, count: 0 };
+    let curve = validation_curve(builder, &inputs, &targets, &folds, &[0.0, 10.0], first_output);
+
+    assert_eq!(curve.values, vec![0.0, 10.0]);
+    assert_eq!(curve.train_scores, vec![vec![4.0; 3], vec![14.0; 3]]);
+    assert_eq!(curve.mean_test_scores(), vec![4.0, 14.0]);
+}
@@ -0,0 +1,134 @@
+use rm::analysis::cross_validation::KFold;
+use rm::analysis::model_selection::{BayesSearch, GridSearch, RandomSearch, ParamGrid, ParamSpace,
+                                    ParamDist, Params, SearchResult};
+use rm::learning::SupModel;
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+/// Predicts a constant value given by the parameters.
+struct ConstModel {
+    value: f64,
+}
+
+impl SupModel<Matrix<f64>, Vector<f64>> for ConstModel {
+    fn predict(&self, inputs: &Matrix<f64>) -> Vector<f64> {
+        Vector::new(vec![self.value; inputs.rows()])
+    }
+
+    fn train(&mut self, _: &Matrix<f64>, _: &Vector<f64>) {}
+}
+
+fn neg_mse(outputs: &Vector<f64>, targets: &Vector<f64>) -> f64 {
+    let diff = outputs - targets;
+    -diff.dot(&diff) / diff.size() as f64
+}
+
+type Folds = Vec<(Vec<usize>, Vec<usize>)>;
+
+fn data() -> (Matrix<f64>, Vector<f64>, Folds) {
+    let inputs = Matrix::new(6, 1, vec![0.0; 6]);
+    let targets = Vector::new(vec![2.0; 6]);
+    let folds = KFold::new(6, 3).collect();
+    (inputs, targets, folds)
+}
+
+#[test]
+fn grid_search_finds_best() {
+    let (inputs, targets, folds) = data();
+    let grid = ParamGrid::new().add("a", vec![0.0, 1.0, 2.0]).add("b", vec![0.0, 1.0]);
+
+    let builder = |p: &Params| ConstModel { value: p.get("a") + p.get("b") };
+    let report = GridSearch::new(grid).fit(builder, &inputs, &targets, &folds, neg_mse);
+
+    assert_eq!(report.results().len(), 6);
+    assert_eq!(report.best().mean_score, 0.0);
+    assert_eq!(report.best().fold_scores.len(), 3);
+    assert_eq!(report.best().params.get("a") + report.best().params.get("b"), 2.0);
+}
+
+#[test]
+fn grid_search_parallel_matches_serial() {
+    let (inputs, targets, folds) = data();
+    let grid = || ParamGrid::new().add("a", vec![-1.0, 0.0, 1.0, 2.0, 3.0]);
+
+    let builder = |p: &Params| ConstModel { value: p.get("a") };
+    let serial = GridSearch::new(grid()).fit(builder, &inputs, &targets, &folds, neg_mse);
+    let parallel = GridSearch::new(grid()).fit_parallel(builder, &inputs, &targets, &folds, neg_mse);
+
+    for (s, p) in serial.results().iter().zip(parallel.results().iter()) {
+        assert_eq!(s.params, p.params);
+        assert_eq!(s.fold_scores, p.fold_scores);
+    }
+    assert_eq!(parallel.best().params.get("a"), 2.0);
+}
+
+#[test]
+fn random_search_samples_in_space() {
+    let (inputs, targets, folds) = data();
+    let space = ParamSpace::new().add("a", ParamDist::Uniform(1.0, 3.0))
+                                 .add("b", ParamDist::LogUniform(0.01, 1.0))
+                                 .add("c", ParamDist::Choice(vec![5.0, 7.0]));
+
+    let search = RandomSearch::new(space, 10, 3);
+    let candidates = search.candidates();
+
+    assert_eq!(candidates, search.candidates());
+    for p in &candidates {
+        assert!(p.get("a") >= 1.0 && p.get("a") < 3.0);
+        assert!(p.get("b") >= 0.01 && p.get("b") < 1.0);
+        assert!(p.get("c") == 5.0 || p.get("c") == 7.0);
+    }
+
+    let builder = |p: &Params| ConstModel { value: p.get("a") };
+    let report = search.fit(builder, &inputs, &targets, &folds, neg_mse);
+
+    let best_err = (report.best().params.get("a") - 2.0).abs();
+    assert!(candidates.iter().all(|p| (p.get("a") - 2.0).abs() >= best_err));
+}
+
+#[test]
+fn bayes_search_finds_optimum() {
+    let (inputs, targets, folds) = data();
+    let space = || {
+        ParamSpace::new().add("a", ParamDist::Uniform(-5.0, 5.0))
+                         .add("b", ParamDist::LogUniform(0.01, 1.0))
+    };
+
+    let builder = |p: &Params| ConstModel { value: p.get("a") + p.get("b") };
+    let search = BayesSearch::new(space(), 20, 7).init_iters(5);
+    let report = search.fit(builder, &inputs, &targets, &folds, neg_mse);
+
+    assert_eq!(report.results().len(), 20);
+    for r in report.results() {
+        assert!(r.params.get("a") >= -5.0 && r.params.get("a") < 5.0);
+        assert!(r.params.get("b") >= 0.01 && r.params.get("b") < 1.0);
+    }
+
+    let best = report.best().params.get("a") + report.best().params.get("b");
+    assert!((best - 2.0).abs() < 0.1);
+
+    // The guided candidates do better than the random ones on average.
+    let mean_score = |results: &[SearchResult]| {
+        results.iter().map(|r| r.mean_score).sum::<f64>() / results.len() as f64
+    };
+    assert!(mean_score(&report.results()[5..]) > mean_score(&report.results()[..5]));
+
+    let again = BayesSearch::new(space(), 20, 7).fit(builder, &inputs, &targets, &folds, neg_mse);
+    for (r, s) in report.results().iter().zip(again.results()) {
+        assert_eq!(r.params, s.params);
+    }
+}
+
+#[test]
+#[should_panic]
+fn bayes_search_zero_init_iters() {
+    let space = ParamSpace::new().add("a", ParamDist::Uniform(0.0, 1.0));
+    let _ = BayesSearch::new(space, 10, 1).init_iters(0);
+}
+
+#[test]
+#[should_panic]
+fn params_missing_name() {
+    let grid = ParamGrid::new().add("a", vec![1.0]);
+    let _ = grid.candidates()[0].get("b");
+}
@@ -0,0 +1,69 @@
+use rm::analysis::cross_validation::cross_val_score;
+use rm::analysis::regression::{mean_squared_error, root_mean_squared_error, mean_absolute_error,
+                               mean_absolute_percentage_error, r2_score, explained_variance};
+use rm::learning::lin_reg::LinRegressor;
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+#[test]
+fn error_metrics() {
+    let outputs = Vector::new(vec![2.0, 2.0, 5.0, 4.0]);
+    let targets = Vector::new(vec![1.0, 2.0, 4.0, 8.0]);
+
+    assert_eq!(mean_squared_error(&outputs, &targets), 4.5);
+    assert!((root_mean_squared_error(&outputs, &targets) - 4.5f64.sqrt()).abs() < 1e-12);
+    assert_eq!(mean_absolute_error(&outputs, &targets), 1.5);
+    assert_eq!(mean_absolute_percentage_error(&outputs, &targets), (1.0 + 0.25 + 0.5) / 4.0);
+}
+
+#[test]
+fn r2_and_explained_variance() {
+    let targets = Vector::new(vec![1.0, 2.0, 3.0, 4.0]);
+
+    assert_eq!(r2_score(&targets, &targets), 1.0);
+
+    let means = Vector::new(vec![2.5; 4]);
+    assert_eq!(r2_score(&means, &targets), 0.0);
+
+    // A constant bias is ignored by explained variance only.
+    let shifted = Vector::new(vec![2.0, 3.0, 4.0, 5.0]);
+    assert_eq!(explained_variance(&shifted, &targets), 1.0);
+    assert!((r2_score(&shifted, &targets) - 0.2).abs() < 1e-12);
+}
+
+#[test]
+fn constant_targets() {
+    let targets = Vector::new(vec![3.0, 3.0]);
+
+    assert_eq!(r2_score(&targets, &targets), 1.0);
+    assert_eq!(r2_score(&Vector::new(vec![3.0, 4.0]), &targets), 0.0);
+}
+
+#[test]
+fn multi_output_averages_columns() {
+    let outputs = Matrix::new(3, 2, vec![1.0, 0.0, 2.0, 0.0, 3.0, 0.0]);
+    let targets = Matrix::new(3, 2, vec![1.0, 1.0, 2.0, 2.0, 3.0, 3.0]);
+
+    let second_r2 = r2_score(&Vector::new(vec![0.0; 3]), &Vector::new(vec![1.0, 2.0, 3.0]));
+
+    assert_eq!(r2_score(&outputs, &targets), (1.0 + second_r2) / 2.0);
+    assert_eq!(mean_absolute_error(&outputs, &targets), 1.0);
+}
+
+#[test]
+fn metrics_work_with_cross_validation() {
+    let inputs = Matrix::new(6, 1, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+    let targets = Vector::new(vec![1.0, 3.0, 5.0, 7.0, 9.0, 11.0]);
+
+    let scores = cross_val_score(LinRegressor::default, &inputs, &targets, 3, r2_score);
+
+    for s in scores {
+        assert!((s - 1.0).abs() < 1e-8);
+    }
+}
+
+#[test]
+#[should_panic]
+fn mismatched_lengths_panic() {
+    let _ = mean_squared_error(&Vector::new(vec![1.0]), &Vector::new(vec![1.0, 2.0]));
+}
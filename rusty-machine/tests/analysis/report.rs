@@ -0,0 +1,56 @@
+use rm::analysis::confusion_matrix::ConfusionMatrix;
+use rm::analysis::report::{classification_report, ClassificationReport};
+
+#[test]
+fn report_per_class_metrics() {
+    let predicted = vec![0, 1, 1, 2, 2, 0];
+    let actual = vec![0, 1, 2, 2, 2, 1];
+
+    let report = classification_report(&predicted, &actual);
+    let classes = report.classes();
+
+    assert_eq!(classes.len(), 3);
+    assert_eq!(classes[0].label, 0);
+    assert_eq!(classes[0].precision, 0.5);
+    assert_eq!(classes[0].recall, 1.0);
+    assert_eq!(classes[1].support, 2);
+    assert_eq!(classes[2].f1_score, 0.8);
+    assert_eq!(report.total(), 6);
+}
+
+#[test]
+fn report_averages() {
+    let predicted = vec![0, 0, 0, 1];
+    let actual = vec![0, 0, 1, 1];
+
+    let report = classification_report(&predicted, &actual);
+
+    let macro_avg = report.macro_average();
+    assert_eq!(macro_avg.precision, (2.0 / 3.0 + 1.0) / 2.0);
+    assert_eq!(macro_avg.recall, 0.75);
+
+    // Equal support, so the weighted average matches the macro average.
+    assert_eq!(report.weighted_average(), macro_avg);
+}
+
+#[test]
+fn report_keeps_unseen_labels() {
+    let cm = ConfusionMatrix::with_labels(&[1, 1], &[1, 1], vec![0, 1]);
+    let report = ClassificationReport::from_confusion_matrix(&cm);
+
+    assert_eq!(report.classes()[0].support, 0);
+    assert_eq!(report.classes()[0].precision, 0.0);
+    assert_eq!(report.weighted_average().recall, 1.0);
+}
+
+#[test]
+fn report_display() {
+    let report = classification_report(&[0, 1, 1], &[0, 1, 0]);
+    let text = report.to_string();
+    let lines: Vec<&str> = text.lines().collect();
+
+    assert!(lines[0].ends_with("precision    recall  f1-score   support"));
+    assert_eq!(lines[2], "           0      1.00      0.50      0.67         2");
+    assert!(lines[5].starts_with("    accuracy"));
+    assert!(lines[7].starts_with("weighted avg"));
+}
@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, BooleanArray, Float32Array, Float64Array, Int64Array, RecordBatch,
+                  StringArray, UInt8Array};
+use rm::data::arrow::{read_record_batches, ArrowDataError, ArrowOptions};
+
+fn batch(x: Vec<f64>, z: Vec<i64>, y: Vec<bool>) -> RecordBatch {
+    RecordBatch::try_from_iter(vec![("x", Arc::new(Float64Array::from(x)) as ArrayRef),
+                                    ("z", Arc::new(Int64Array::from(z)) as ArrayRef),
+                                    ("y", Arc::new(BooleanArray::from(y)) as ArrayRef)])
+        .unwrap()
+}
+
+#[test]
+fn batches_are_concatenated() {
+    let batches = vec![batch(vec![1.0, 2.0], vec![10, 20], vec![true, false]),
+                       batch(vec![3.0], vec![30], vec![true])];
+
+    let options = ArrowOptions::default().columns(vec!["z", "x"]).target("y");
+    let data = read_record_batches(&batches, &options).unwrap();
+
+    assert_eq!((data.inputs.rows(), data.inputs.cols()), (3, 2));
+    assert_eq!(*data.inputs.data(), vec![10.0, 1.0, 20.0, 2.0, 30.0, 3.0]);
+    assert_eq!(*data.targets.unwrap().data(), vec![1.0, 0.0, 1.0]);
+    assert_eq!(data.columns, vec!["z", "x"]);
+}
+
+#[test]
+fn default_columns_skip_target() {
+    let batch = RecordBatch::try_from_iter(vec![
+        ("a", Arc::new(Float32Array::from(vec![0.5f32])) as ArrayRef),
+        ("t", Arc::new(UInt8Array::from(vec![3u8])) as ArrayRef),
+        ("b", Arc::new(UInt8Array::from(vec![7u8])) as ArrayRef),
+    ]).unwrap();
+
+    let data = read_record_batches(&[batch], &ArrowOptions::default().target("t")).unwrap();
+
+    assert_eq!(*data.inputs.data(), vec![0.5, 7.0]);
+    assert_eq!(data.columns, vec!["a", "b"]);
+}
+
+#[test]
+fn column_errors() {
+    let batches = vec![batch(vec![1.0], vec![1], vec![true])];
+
+    match read_record_batches(&batches, &ArrowOptions::default().columns(vec!["w"])) {
+        Err(ArrowDataError::MissingColumn(ref c)) if c == "w" => {}
+        _ => panic!("Expected a missing column error."),
+    }
+
+    let strings = RecordBatch::try_from_iter(vec![
+        ("s", Arc::new(StringArray::from(vec!["a"])) as ArrayRef),
+    ]).unwrap();
+    match read_record_batches(&[strings], &ArrowOptions::default()) {
+        Err(ArrowDataError::UnsupportedType { ref column, .. }) if column == "s" => {}
+        _ => panic!("Expected an unsupported type error."),
+    }
+
+    let nulls = RecordBatch::try_from_iter(vec![
+        ("n", Arc::new(Float64Array::from(vec![Some(1.0), None])) as ArrayRef),
+    ]).unwrap();
+    match read_record_batches(&[nulls], &ArrowOptions::default()) {
+        Err(ArrowDataError::Nulls(ref c)) if c == "n" => {}
+        _ => panic!("Expected a nulls error."),
+    }
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn parquet_selected_columns() {
+    use std::env;
+    use std::fs::File;
+
+    use parquet::arrow::ArrowWriter;
+    use rm::data::arrow::load_parquet;
+
+    let path = env::temp_dir().join("rusty_machine_parquet_test.parquet");
+    let batch = batch(vec![1.0, 2.0, 3.0], vec![4, 5, 6], vec![false, true, true]);
+
+    let mut writer = ArrowWriter::try_new(File::create(&path).unwrap(), batch.schema(), None)
+                         .unwrap();
+    writer.write(&batch).unwrap();
+    writer.close().unwrap();
+
+    let options = ArrowOptions::default().columns(vec!["x"]).target("y");
+    let data = load_parquet(&path, &options).unwrap();
+
+    assert_eq!(*data.inputs.data(), vec![1.0, 2.0, 3.0]);
+    assert_eq!(*data.targets.unwrap().data(), vec![0.0, 1.0, 1.0]);
+}
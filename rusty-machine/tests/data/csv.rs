@@ -0,0 +1,110 @@
+use std::env;
+use std::fs::File;
+use std::io::{Cursor, Write};
+
+use rm::data::csv::{load_matrix, read_matrix, CsvOptions, CsvError, CsvSource};
+use rm::data::source::DataSource;
+use rm::linalg::matrix::Matrix;
+
+#[test]
+fn csv_read_with_header_and_target() {
+    let text = "a,b,label\n1,2,0\n\n3,4,1\n5,6,0\n";
+    let options = CsvOptions::default().header(true).target(2);
+
+    let data = read_matrix(text.as_bytes(), &options).unwrap();
+
+    assert_eq!(data.inputs.rows(), 3);
+    assert_eq!(*data.inputs.data(), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    assert_eq!(*data.targets.unwrap().data(), vec![0.0, 1.0, 0.0]);
+    assert_eq!(data.header.unwrap(), vec!["a".to_string(), "b".to_string()]);
+}
+
+#[test]
+fn csv_read_quoted_fields() {
+    let text = "\"1.5\"\t\" 2 \"\r\n3\t4\r\n";
+    let options = CsvOptions::default().delimiter('\t');
+
+    let data = read_matrix(text.as_bytes(), &options).unwrap();
+
+    assert_eq!(*data.inputs.data(), vec![1.5, 2.0, 3.0, 4.0]);
+}
+
+#[test]
+fn csv_read_errors() {
+    let options = CsvOptions::default();
+
+    match read_matrix("1,2\n3,x\n".as_bytes(), &options) {
+        Err(CsvError::Parse { line, column, value }) => {
+            assert_eq!((line, column, value), (2, 1, "x".to_string()));
+        }
+        _ => panic!("Expected a parse error."),
+    }
+
+    match read_matrix("1,2\n3\n".as_bytes(), &options) {
+        Err(CsvError::FieldCount { line: 2, expected: 2, found: 1 }) => {}
+        _ => panic!("Expected a field count error."),
+    }
+
+    match read_matrix("1,2\n".as_bytes(), &options.clone().target(2)) {
+        Err(CsvError::MissingColumn(2)) => {}
+        _ => panic!("Expected a missing column error."),
+    }
+
+    match read_matrix("\n".as_bytes(), &options) {
+        Err(CsvError::Empty) => {}
+        _ => panic!("Expected an empty error."),
+    }
+}
+
+#[test]
+fn csv_load_file() {
+    let path = env::temp_dir().join("rusty_machine_csv_load_test.csv");
+    {
+        let mut f = File::create(&path).unwrap();
+        f.write_all(b"1,2,3\n4,5,6\n").unwrap();
+    }
+
+    let data = load_matrix(&path, &CsvOptions::default().columns(vec![1])).unwrap();
+    assert_eq!(*data.inputs.data(), vec![2.0, 5.0]);
+
+    assert!(load_matrix(path.with_extension("missing"), &CsvOptions::default()).is_err());
+}
+
+#[test]
+fn csv_source_reads_batches_each_pass() {
+    let text = "a,b,label\n1,2,0\n\n3,4,1\r\n5,6,0\n";
+    let options = CsvOptions::default().header(true).target(2);
+    let mut source = CsvSource::new(Cursor::new(text), options, 2);
+
+    let mut inputs = Matrix::new(0, 0, vec![]);
+    let mut targets = Matrix::new(0, 0, vec![]);
+
+    for _ in 0..2 {
+        source.reset().unwrap();
+
+        assert!(source.next_batch(&mut inputs, &mut targets).unwrap());
+        assert_eq!(*inputs.data(), vec![1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(*targets.data(), vec![0.0, 1.0]);
+
+        assert!(source.next_batch(&mut inputs, &mut targets).unwrap());
+        assert_eq!((inputs.rows(), inputs.cols()), (1, 2));
+        assert_eq!(*targets.data(), vec![0.0]);
+
+        assert!(!source.next_batch(&mut inputs, &mut targets).unwrap());
+    }
+}
+
+#[test]
+fn csv_source_reports_bad_lines() {
+    let text = "1,2\n3\n";
+    let mut source = CsvSource::new(Cursor::new(text), CsvOptions::default(), 4);
+
+    let mut inputs = Matrix::new(0, 0, vec![]);
+    let mut targets = Matrix::new(0, 0, vec![]);
+
+    source.reset().unwrap();
+    match source.next_batch(&mut inputs, &mut targets) {
+        Err(CsvError::FieldCount { line: 2, expected: 2, found: 1 }) => {}
+        _ => panic!("Expected a field count error."),
+    }
+}
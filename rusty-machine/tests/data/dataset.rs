@@ -0,0 +1,95 @@
+use rm::data::dataset::{Dataset, RowData};
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+fn sample_data(n: usize) -> Dataset<Matrix<f64>, Vector<f64>> {
+    let inputs = Matrix::new(n, 2, (0..2 * n).map(|x| (x / 2) as f64).collect());
+    let targets = Vector::new((0..n).map(|x| x as f64).collect());
+    Dataset::new(inputs, targets)
+}
+
+#[test]
+fn batches_cover_all_rows() {
+    let data = sample_data(7);
+
+    let batches: Vec<(Matrix<f64>, Vector<f64>)> = data.batches(3).collect();
+
+    assert_eq!(batches.len(), 3);
+    assert_eq!(batches[0].0.rows(), 3);
+    assert_eq!(batches[2].0.rows(), 1);
+    assert_eq!(*batches[2].1.data(), vec![6.0]);
+}
+
+#[test]
+fn shuffle_keeps_rows_paired() {
+    let mut data = sample_data(10);
+    data.shuffle(3);
+
+    let mut seen = Vec::new();
+    for (inputs, targets) in data.batches(4) {
+        for (i, t) in targets.data().iter().enumerate() {
+            assert_eq!(inputs[[i, 0]], *t);
+            assert_eq!(inputs[[i, 1]], *t);
+            seen.push(*t);
+        }
+    }
+
+    assert!(seen != (0..10).map(|x| x as f64).collect::<Vec<_>>());
+    seen.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(seen, (0..10).map(|x| x as f64).collect::<Vec<_>>());
+}
+
+#[test]
+fn shuffle_is_reproducible() {
+    let mut a = sample_data(12);
+    let mut b = sample_data(12);
+
+    a.shuffle(7);
+    b.shuffle(7);
+
+    assert_eq!(a.order(), b.order());
+}
+
+#[test]
+fn split_uses_current_order() {
+    let mut data = sample_data(10);
+    data.shuffle(1);
+    let order = data.order().to_vec();
+
+    let (train, test) = data.split(0.8);
+
+    assert_eq!(train.len(), 8);
+    assert_eq!(test.len(), 2);
+    assert_eq!(*test.targets().data(), vec![order[8] as f64, order[9] as f64]);
+}
+
+#[test]
+fn next_into_reuses_buffers() {
+    let data = sample_data(5);
+    let mut inputs = Matrix::empty();
+    let mut targets = Vector::empty();
+
+    let mut batches = data.batches(2);
+    let mut count = 0;
+    while batches.next_into(&mut inputs, &mut targets) {
+        assert_eq!(inputs.rows(), targets.size());
+        count += 1;
+    }
+
+    assert_eq!(count, 3);
+    assert_eq!(*targets.data(), vec![4.0]);
+}
+
+#[test]
+#[should_panic]
+fn mismatched_lengths_panic() {
+    let _ = Dataset::new(Matrix::new(3, 1, vec![1.0, 2.0, 3.0]),
+                         Vector::new(vec![1.0, 2.0]));
+}
+
+#[test]
+#[should_panic]
+fn zero_batch_size_panics() {
+    let data = sample_data(3);
+    let _ = data.batches(0);
+}
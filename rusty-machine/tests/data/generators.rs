@@ -0,0 +1,58 @@
+use rm::data::generators::{make_blobs, make_circles, make_moons, make_regression};
+use rm::learning::SupModel;
+use rm::learning::lin_reg::LinRegressor;
+use rm::linalg::matrix::Matrix;
+
+#[test]
+fn blobs_are_reproducible_and_centered() {
+    let centers = Matrix::new(3, 2, vec![0.0, 0.0, 5.0, 5.0, -5.0, 5.0]);
+
+    let (a, labels) = make_blobs(300, &centers, 0.1, 7);
+    let (b, _) = make_blobs(300, &centers, 0.1, 7);
+    assert_eq!(a.data(), b.data());
+
+    for i in 0..300 {
+        let c = labels[i] as usize;
+        assert!((a[[i, 0]] - centers[[c, 0]]).abs() < 1.0);
+        assert!((a[[i, 1]] - centers[[c, 1]]).abs() < 1.0);
+    }
+}
+
+#[test]
+fn moons_without_noise() {
+    let (inputs, labels) = make_moons(10, 0.0, 1);
+
+    assert_eq!(labels.sum(), 5.0);
+    for i in 0..5 {
+        let (x, y) = (inputs[[i, 0]], inputs[[i, 1]]);
+        assert!((x * x + y * y - 1.0).abs() < 1e-12);
+    }
+    for i in 5..10 {
+        let (x, y) = (inputs[[i, 0]] - 1.0, inputs[[i, 1]] - 0.5);
+        assert!((x * x + y * y - 1.0).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn circles_without_noise() {
+    let (inputs, labels) = make_circles(11, 0.3, 0.0, 1);
+
+    for i in 0..11 {
+        let r = (inputs[[i, 0]].powi(2) + inputs[[i, 1]].powi(2)).sqrt();
+        let expected = if labels[i] == 0.0 { 1.0 } else { 0.3 };
+        assert!((r - expected).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn regression_recovers_coefficients() {
+    let (inputs, targets, coefs) = make_regression(50, 2, 0.01, 4);
+
+    let mut model = LinRegressor::default();
+    model.train(&inputs, &targets);
+
+    let params = model.parameters().unwrap();
+    assert!(params[0].abs() < 0.1);
+    assert!((params[1] - coefs[0]).abs() < 0.1);
+    assert!((params[2] - coefs[1]).abs() < 0.1);
+}
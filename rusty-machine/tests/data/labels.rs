@@ -0,0 +1,54 @@
+use rm::data::labels::{argmax_decode, one_hot, LabelEncoder};
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+#[test]
+fn one_hot_round_trip() {
+    let labels = Vector::new(vec![1.0, 0.0, 3.0, 1.0]);
+    let encoded = one_hot(&labels, 4);
+
+    assert_eq!(encoded.rows(), 4);
+    assert_eq!(encoded.cols(), 4);
+    assert_eq!(encoded.data().iter().sum::<f64>(), 4.0);
+    assert_eq!(*argmax_decode(&encoded).data(), *labels.data());
+}
+
+#[test]
+fn argmax_decode_scores() {
+    let scores = Matrix::new(3, 3, vec![0.1, 0.7, 0.2, 0.5, 0.5, 0.0, -1.0, -3.0, -0.5]);
+
+    assert_eq!(*argmax_decode(&scores).data(), vec![1.0, 0.0, 2.0]);
+}
+
+#[test]
+#[should_panic]
+fn one_hot_out_of_range() {
+    one_hot(&Vector::new(vec![0.0, 3.0]), 3);
+}
+
+#[test]
+#[should_panic]
+fn one_hot_non_integer() {
+    one_hot(&Vector::new(vec![0.5]), 3);
+}
+
+#[test]
+fn label_encoder_strings() {
+    let labels = vec!["versicolor".to_string(), "setosa".to_string(), "versicolor".to_string()];
+
+    let mut encoder = LabelEncoder::default();
+    assert!(encoder.classes().is_none());
+
+    let encoded = encoder.fit_transform(&labels);
+    assert_eq!(encoder.classes().unwrap(), &["setosa".to_string(), "versicolor".to_string()]);
+    assert_eq!(*encoded.data(), vec![1.0, 0.0, 1.0]);
+    assert_eq!(encoder.inverse_transform(&encoded), labels);
+}
+
+#[test]
+#[should_panic]
+fn label_encoder_unknown_label() {
+    let mut encoder = LabelEncoder::default();
+    encoder.fit(&[1, 2]);
+    encoder.transform(&[3]);
+}
@@ -0,0 +1,48 @@
+use std::env;
+
+use rm::data::libsvm::{load_libsvm, read_libsvm, save_libsvm, LibSvmError};
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+#[test]
+fn libsvm_read_infers_features() {
+    let text = "\n+1 4:1.0 1:-2\n0\n";
+    let (inputs, targets) = read_libsvm(text.as_bytes(), None).unwrap();
+
+    assert_eq!(inputs.rows(), 2);
+    assert_eq!(inputs.cols(), 4);
+    assert_eq!(*inputs.data(), vec![-2.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0]);
+    assert_eq!(*targets.data(), vec![1.0, 0.0]);
+}
+
+#[test]
+fn libsvm_read_errors() {
+    match read_libsvm("1 0:2\n".as_bytes(), None) {
+        Err(LibSvmError::InvalidIndex { line: 1, index: 0 }) => {}
+        _ => panic!("Expected an invalid index error."),
+    }
+
+    match read_libsvm("1 1:2\n1 3:1\n".as_bytes(), Some(2)) {
+        Err(LibSvmError::InvalidIndex { line: 2, index: 3 }) => {}
+        _ => panic!("Expected an invalid index error."),
+    }
+
+    match read_libsvm("1 1=2\n".as_bytes(), None) {
+        Err(LibSvmError::Parse { line: 1, .. }) => {}
+        _ => panic!("Expected a parse error."),
+    }
+}
+
+#[test]
+fn libsvm_save_and_load() {
+    let path = env::temp_dir().join("rusty_machine_libsvm_test.txt");
+
+    let inputs = Matrix::new(2, 3, vec![0.25, 0.0, -1.0, 0.0, 0.0, 3.0]);
+    let targets = Vector::new(vec![2.0, -1.0]);
+
+    save_libsvm(&path, &inputs, &targets).unwrap();
+    let (loaded_inputs, loaded_targets) = load_libsvm(&path, Some(3)).unwrap();
+
+    assert_eq!(loaded_inputs.data(), inputs.data());
+    assert_eq!(loaded_targets.data(), targets.data());
+}
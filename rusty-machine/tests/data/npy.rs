@@ -0,0 +1,168 @@
+use std::env;
+
+use rm::data::npy::{load_npy, read_npy, read_npz, save_npy, write_npy, write_npz, NpyChunks,
+                    NpyError};
+use rm::data::source::DataSource;
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+/// A version 1.0 `.npy` file with the given header values.
+fn npy_bytes(descr: &str, fortran_order: bool, shape: &str, data: &[u8]) -> Vec<u8> {
+    let header = format!("{{'descr': '{}', 'fortran_order': {}, 'shape': {}, }}\n",
+                         descr,
+                         if fortran_order { "True" } else { "False" },
+                         shape);
+
+    let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    bytes.extend_from_slice(data);
+    bytes
+}
+
+#[test]
+fn npy_vector_round_trip() {
+    let vec = Vector::new(vec![1.5, -2.0, 1e-300]);
+
+    let mut bytes = Vec::new();
+    write_npy(&mut bytes, &vec).unwrap();
+
+    // The data starts on a 64 byte boundary.
+    assert_eq!((bytes.len() - 24) % 64, 0);
+
+    let loaded: Vector<f64> = read_npy(&bytes[..]).unwrap();
+    assert_eq!(*loaded.data(), *vec.data());
+
+    // A one dimensional array is read as a column.
+    let column: Matrix<f64> = read_npy(&bytes[..]).unwrap();
+    assert_eq!((column.rows(), column.cols()), (3, 1));
+}
+
+#[test]
+fn npy_file_round_trip() {
+    let path = env::temp_dir().join("rusty_machine_npy_test.npy");
+    let mat = Matrix::new(3, 2, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+    save_npy(&path, &mat).unwrap();
+    let loaded: Matrix<f64> = load_npy(&path).unwrap();
+
+    assert_eq!((loaded.rows(), loaded.cols()), (3, 2));
+    assert_eq!(*loaded.data(), *mat.data());
+}
+
+#[test]
+fn npy_fortran_order() {
+    // The column-major data of [[1, 2, 3], [4, 5, 6]].
+    let data: Vec<u8> = [1.0f64, 4.0, 2.0, 5.0, 3.0, 6.0].iter()
+                                                          .flat_map(|x| x.to_le_bytes().to_vec())
+                                                          .collect();
+    let bytes = npy_bytes("<f8", true, "(2, 3)", &data);
+
+    let mat: Matrix<f64> = read_npy(&bytes[..]).unwrap();
+    assert_eq!(*mat.data(), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+}
+
+#[test]
+fn npy_other_dtypes() {
+    let ints: Vec<u8> = [-3i32, 7].iter().flat_map(|x| x.to_be_bytes().to_vec()).collect();
+    let vec: Vector<f64> = read_npy(&npy_bytes(">i4", false, "(2,)", &ints)[..]).unwrap();
+    assert_eq!(*vec.data(), vec![-3.0, 7.0]);
+
+    let floats: Vec<u8> = [0.5f32, 2.0].iter().flat_map(|x| x.to_le_bytes().to_vec()).collect();
+    let vec: Vector<f64> = read_npy(&npy_bytes("<f4", false, "(1, 2)", &floats)[..]).unwrap();
+    assert_eq!(*vec.data(), vec![0.5, 2.0]);
+
+    let bools = [1u8, 0, 1];
+    let vec: Vector<f64> = read_npy(&npy_bytes("|b1", false, "(3,)", &bools)[..]).unwrap();
+    assert_eq!(*vec.data(), vec![1.0, 0.0, 1.0]);
+}
+
+#[test]
+fn npy_errors() {
+    match read_npy::<Vector<f64>, _>(&b"not a numpy file"[..]) {
+        Err(NpyError::Format(_)) => {}
+        _ => panic!("Expected a format error."),
+    }
+
+    match read_npy::<Vector<f64>, _>(&npy_bytes("<c16", false, "(1,)", &[0; 16])[..]) {
+        Err(NpyError::UnsupportedDtype(ref d)) if d == "<c16" => {}
+        _ => panic!("Expected an unsupported dtype error."),
+    }
+
+    match read_npy::<Vector<f64>, _>(&npy_bytes("<f8", false, "(2, 2)", &[0; 32])[..]) {
+        Err(NpyError::Shape(ref s)) if *s == vec![2, 2] => {}
+        _ => panic!("Expected a shape error."),
+    }
+
+    match read_npy::<Vector<f64>, _>(&npy_bytes("<f8", false, "(4,)", &[0; 16])[..]) {
+        Err(NpyError::Format(_)) => {}
+        _ => panic!("Expected truncated data to fail."),
+    }
+}
+
+#[test]
+fn npz_round_trip() {
+    let inputs = Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+    let targets = Vector::new(vec![0.0, 1.0]);
+
+    let mut bytes = Vec::new();
+    write_npz(&mut bytes, &[("x", &inputs), ("y", &targets)]).unwrap();
+
+    let archive = read_npz(&bytes[..]).unwrap();
+    assert_eq!(archive.names(), vec!["x", "y"]);
+
+    let (x, y) = archive.inputs_targets("x", "y").unwrap();
+    assert_eq!(*x.data(), *inputs.data());
+    assert_eq!(*y.data(), *targets.data());
+
+    match archive.array::<Matrix<f64>>("z") {
+        Err(NpyError::MissingArray(ref name)) if name == "z" => {}
+        _ => panic!("Expected a missing array error."),
+    }
+}
+
+#[test]
+fn npz_compressed_entry() {
+    let mut bytes = Vec::new();
+    write_npz(&mut bytes, &[("x", &Vector::new(vec![1.0]))]).unwrap();
+
+    // Mark the entry as deflated in the central directory.
+    let dir = (0..bytes.len()).find(|&i| bytes[i..i + 4] == [0x50, 0x4b, 0x01, 0x02]).unwrap();
+    bytes[dir + 10] = 8;
+
+    match read_npz(&bytes[..]) {
+        Err(NpyError::Compressed(ref name)) if name == "x.npy" => {}
+        _ => panic!("Expected a compressed entry error."),
+    }
+}
+
+#[test]
+fn npy_chunks_read_batches_within_chunks() {
+    let dir = env::temp_dir();
+    let chunks = [(dir.join("rusty_machine_chunk_x0.npy"), dir.join("rusty_machine_chunk_y0.npy")),
+                  (dir.join("rusty_machine_chunk_x1.npy"), dir.join("rusty_machine_chunk_y1.npy"))];
+
+    save_npy(&chunks[0].0, &Matrix::new(3, 1, vec![1.0, 2.0, 3.0])).unwrap();
+    save_npy(&chunks[0].1, &Vector::new(vec![10.0, 20.0, 30.0])).unwrap();
+    save_npy(&chunks[1].0, &Matrix::new(1, 1, vec![4.0])).unwrap();
+    save_npy(&chunks[1].1, &Vector::new(vec![40.0])).unwrap();
+
+    let mut source = NpyChunks::new(&chunks, 2);
+    let mut inputs = Matrix::new(0, 0, vec![]);
+    let mut targets = Matrix::new(0, 0, vec![]);
+
+    for _ in 0..2 {
+        source.reset().unwrap();
+
+        let mut batches = Vec::new();
+        while source.next_batch(&mut inputs, &mut targets).unwrap() {
+            assert_eq!(targets.cols(), 1);
+            batches.push((inputs.data().clone(), targets.data().clone()));
+        }
+
+        assert_eq!(batches,
+                   vec![(vec![1.0, 2.0], vec![10.0, 20.0]),
+                        (vec![3.0], vec![30.0]),
+                        (vec![4.0], vec![40.0])]);
+    }
+}
@@ -0,0 +1,77 @@
+use polars::prelude::*;
+use rm::data::polars::{target_vector, DataFrameEncoder, PolarsDataError};
+use rm::data::transforms::encode::UnknownCategory;
+
+#[test]
+fn encodes_all_columns() {
+    let df = df!("a" => [1i32, 2, 3],
+                 "b" => ["x", "y", "x"],
+                 "c" => [true, false, true]).unwrap();
+
+    let mut encoder = DataFrameEncoder::new();
+    let inputs = encoder.fit_transform(&df).unwrap();
+
+    assert_eq!(encoder.feature_names().unwrap(), vec!["a", "b=x", "b=y", "c"]);
+    assert_eq!((inputs.rows(), inputs.cols()), (3, 4));
+    assert_eq!(*inputs.data(), vec![1.0, 1.0, 0.0, 1.0,
+                                    2.0, 0.0, 1.0, 0.0,
+                                    3.0, 1.0, 0.0, 1.0]);
+}
+
+#[test]
+fn categorical_columns_are_encoded() {
+    let mut df = df!("c" => ["b", "a", "b"]).unwrap();
+    df.apply("c", |s| s.cast(&DataType::Categorical(None, Default::default())).unwrap())
+      .unwrap();
+
+    let mut encoder = DataFrameEncoder::new();
+    let inputs = encoder.fit_transform(&df).unwrap();
+
+    assert_eq!(*inputs.data(), vec![0.0, 1.0, 1.0, 0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn test_frames_use_fitted_categories() {
+    let train = df!("c" => ["a", "b"], "x" => [1.0, 2.0]).unwrap();
+    let test = df!("x" => [3.0, 4.0], "c" => ["b", "z"]).unwrap();
+
+    let mut encoder = DataFrameEncoder::new().columns(vec!["c", "x"])
+                                             .unknown(UnknownCategory::Ignore);
+    encoder.fit(&train).unwrap();
+    let inputs = encoder.transform(&test).unwrap();
+
+    assert_eq!(*inputs.data(), vec![0.0, 1.0, 3.0, 0.0, 0.0, 4.0]);
+}
+
+#[test]
+#[should_panic]
+fn transform_unfitted() {
+    let df = df!("x" => [1.0]).unwrap();
+    let _ = DataFrameEncoder::new().transform(&df);
+}
+
+#[test]
+fn targets() {
+    let df = df!("y" => [1i64, 0, 1], "s" => ["a", "b", "c"]).unwrap();
+
+    assert_eq!(*target_vector(&df, "y").unwrap().data(), vec![1.0, 0.0, 1.0]);
+
+    match target_vector(&df, "s") {
+        Err(PolarsDataError::UnsupportedType { ref column, .. }) if column == "s" => {}
+        _ => panic!("Expected an unsupported type error."),
+    }
+    match target_vector(&df, "z") {
+        Err(PolarsDataError::MissingColumn(ref c)) if c == "z" => {}
+        _ => panic!("Expected a missing column error."),
+    }
+}
+
+#[test]
+fn nulls_are_rejected() {
+    let df = df!("x" => [Some(1.0), None]).unwrap();
+
+    match DataFrameEncoder::new().fit(&df) {
+        Err(PolarsDataError::Nulls(ref c)) if c == "x" => {}
+        _ => panic!("Expected a nulls error."),
+    }
+}
@@ -0,0 +1,87 @@
+use rm::data::resample::{random_oversample, random_undersample, smote};
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+fn class_count(targets: &Vector<f64>, class: f64) -> usize {
+    targets.data().iter().filter(|&&t| t == class).count()
+}
+
+#[test]
+fn oversample_balances_classes() {
+    let inputs = Matrix::new(6, 2, vec![0.0, 0.0, 1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0, 5.0, 5.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 0.0, 0.0, 1.0, 2.0]);
+
+    let (new_inputs, new_targets) = random_oversample(&inputs, &targets, 3);
+
+    assert_eq!(new_inputs.rows(), 12);
+    assert_eq!(class_count(&new_targets, 1.0), 4);
+    assert_eq!(class_count(&new_targets, 2.0), 4);
+
+    // The originals come first and the copies keep their rows.
+    assert_eq!(&new_inputs.data()[..12], &inputs.data()[..]);
+    for i in 6..12 {
+        assert_eq!(new_inputs[[i, 0]], if new_targets[i] == 1.0 { 4.0 } else { 5.0 });
+    }
+}
+
+#[test]
+fn undersample_balances_classes() {
+    let inputs = Matrix::new(5, 1, vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+    let targets = Vector::new(vec![1.0, 0.0, 0.0, 1.0, 0.0]);
+
+    let (new_inputs, new_targets) = random_undersample(&inputs, &targets, 7);
+
+    assert_eq!(new_inputs.rows(), 4);
+    assert_eq!(class_count(&new_targets, 0.0), 2);
+    assert_eq!(class_count(&new_targets, 1.0), 2);
+
+    // The kept samples stay in order.
+    let kept = new_inputs.data();
+    assert!(kept.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[test]
+fn smote_interpolates_within_class() {
+    let inputs = Matrix::new(6, 2, vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0, 10.0, 10.0, 11.0, 12.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0]);
+
+    let (new_inputs, new_targets) = smote(&inputs, &targets, 3, 5);
+
+    assert_eq!(new_inputs.rows(), 8);
+    assert_eq!(class_count(&new_targets, 1.0), 4);
+
+    for i in 6..8 {
+        let (x, y) = (new_inputs[[i, 0]], new_inputs[[i, 1]]);
+        assert!((10.0..=11.0).contains(&x));
+        assert!((y - (10.0 + 2.0 * (x - 10.0))).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn resampling_is_reproducible() {
+    let inputs = Matrix::new(5, 1, vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 0.0, 1.0, 1.0]);
+
+    let (a, _) = smote(&inputs, &targets, 1, 11);
+    let (b, _) = smote(&inputs, &targets, 1, 11);
+
+    assert_eq!(a.data(), b.data());
+}
+
+#[test]
+#[should_panic]
+fn smote_single_sample_class_panics() {
+    let inputs = Matrix::new(3, 1, vec![0.0, 1.0, 2.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 1.0]);
+
+    let _ = smote(&inputs, &targets, 1, 1);
+}
+
+#[test]
+#[should_panic(expected = "Inputs must not contain NaN.")]
+fn smote_nan_inputs_panics() {
+    let inputs = Matrix::new(4, 1, vec![0.0, f64::NAN, 2.0, 10.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 0.0, 1.0]);
+
+    let _ = smote(&inputs, &targets, 1, 1);
+}
@@ -0,0 +1,66 @@
+use rm::data::split::{shuffle, train_test_split};
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+#[test]
+fn train_test_split_partitions_rows() {
+    let inputs = Matrix::new(10, 1, (0..10).map(|x| x as f64).collect());
+    let targets = Vector::new((0..10).map(|x| x as f64).collect());
+
+    let (train_in, test_in, train_t, test_t) =
+        train_test_split(&inputs, &targets, 0.3, 5, false);
+
+    assert_eq!(train_in.rows(), 7);
+    assert_eq!(test_in.rows(), 3);
+    assert_eq!(train_in.data(), train_t.data());
+    assert_eq!(test_in.data(), test_t.data());
+
+    let mut all: Vec<f64> = train_t.data().iter().chain(test_t.data().iter()).cloned().collect();
+    all.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(all, *targets.data());
+}
+
+#[test]
+fn train_test_split_is_reproducible() {
+    let inputs = Matrix::new(8, 1, (0..8).map(|x| x as f64).collect());
+    let targets = Vector::new(vec![0.0; 8]);
+
+    let (a, _, _, _) = train_test_split(&inputs, &targets, 0.5, 11, false);
+    let (b, _, _, _) = train_test_split(&inputs, &targets, 0.5, 11, false);
+
+    assert_eq!(a.data(), b.data());
+}
+
+#[test]
+fn train_test_split_stratified() {
+    let inputs = Matrix::new(12, 1, (0..12).map(|x| x as f64).collect());
+    let targets = Vector::new(vec![0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]);
+
+    let (_, _, train_t, test_t) = train_test_split(&inputs, &targets, 0.25, 2, true);
+
+    assert_eq!(train_t.size(), 9);
+    assert_eq!(test_t.size(), 3);
+    assert_eq!(train_t.sum(), 3.0);
+    assert_eq!(test_t.sum(), 1.0);
+}
+
+#[test]
+#[should_panic]
+fn train_test_split_bad_fraction() {
+    let inputs = Matrix::new(2, 1, vec![1.0, 2.0]);
+    let targets = Vector::new(vec![1.0, 2.0]);
+
+    let _ = train_test_split(&inputs, &targets, 1.0, 0, false);
+}
+
+#[test]
+fn shuffle_keeps_pairs() {
+    let inputs = Matrix::new(5, 2, (0..10).map(|x| x as f64).collect());
+    let targets = Vector::new((0..5).map(|x| 2.0 * x as f64).collect());
+
+    let (inputs, targets) = shuffle(&inputs, &targets, 9);
+
+    for i in 0..5 {
+        assert_eq!(inputs[[i, 0]], targets[i]);
+    }
+}
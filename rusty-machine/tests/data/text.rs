@@ -0,0 +1,115 @@
+use rm::data::text::{CountVectorizer, HashingVectorizer, TfIdfVectorizer};
+
+#[test]
+fn count_vectorizer_counts_terms() {
+    let docs = ["Apple banana apple", "banana cherry"];
+
+    let mut vectorizer = CountVectorizer::default();
+    let counts = vectorizer.fit_transform(&docs);
+
+    assert_eq!(vectorizer.vocabulary(), ["apple", "banana", "cherry"]);
+    assert_eq!(*counts.data(), vec![2.0, 1.0, 0.0, 0.0, 1.0, 1.0]);
+}
+
+#[test]
+fn count_vectorizer_ignores_unknown_terms() {
+    let mut vectorizer = CountVectorizer::default();
+    vectorizer.fit(&["one two"]);
+
+    let counts = vectorizer.transform(&["two three"]);
+    assert_eq!(*counts.data(), vec![0.0, 1.0]);
+}
+
+#[test]
+fn count_vectorizer_ngrams() {
+    let mut vectorizer = CountVectorizer::default().ngram_range(2, 3);
+    vectorizer.fit(&["a b c"]);
+
+    assert_eq!(vectorizer.vocabulary(), ["a b", "a b c", "b c"]);
+}
+
+#[test]
+fn count_vectorizer_document_frequency_cutoffs() {
+    let docs = ["common rare", "common shared", "common shared"];
+
+    let mut vectorizer = CountVectorizer::default().min_df(2).max_df(0.9);
+    vectorizer.fit(&docs);
+
+    assert_eq!(vectorizer.vocabulary(), ["shared"]);
+}
+
+#[test]
+fn count_vectorizer_case_sensitive() {
+    let mut vectorizer = CountVectorizer::default().lowercase(false);
+    vectorizer.fit(&["Word word"]);
+
+    assert_eq!(vectorizer.vocabulary(), ["Word", "word"]);
+}
+
+#[test]
+#[should_panic]
+fn count_vectorizer_unfitted_panics() {
+    let vectorizer = CountVectorizer::default();
+    let _ = vectorizer.transform(&["text"]);
+}
+
+#[test]
+fn tfidf_rows_have_unit_norm() {
+    let docs = ["x y z", "x x", "y"];
+
+    let mut vectorizer = TfIdfVectorizer::default();
+    let features = vectorizer.fit_transform(&docs);
+
+    for i in 0..features.rows() {
+        let norm: f64 = features.data()[i * 3..(i + 1) * 3].iter().map(|x| x * x).sum();
+        assert!((norm - 1.0).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn tfidf_idf_values() {
+    let docs = ["x y", "x"];
+
+    let mut vectorizer = TfIdfVectorizer::default().normalize(false);
+    let features = vectorizer.fit_transform(&docs);
+
+    let idf = vectorizer.idf();
+    assert!((idf[0] - 1.0).abs() < 1e-12);
+    assert!((idf[1] - ((3.0f64 / 2.0).ln() + 1.0)).abs() < 1e-12);
+    assert_eq!(features[[0, 1]], idf[1]);
+}
+
+#[test]
+fn hashing_vectorizer_is_deterministic() {
+    let docs = ["some words here", "more words"];
+
+    let a = HashingVectorizer::new(32).transform(&docs);
+    let b = HashingVectorizer::new(32).transform(&docs);
+
+    assert_eq!(a.data(), b.data());
+}
+
+#[test]
+fn hashing_vectorizer_counts_repeated_terms() {
+    let vectorizer = HashingVectorizer::new(64).alternate_sign(false);
+    let features = vectorizer.transform(&["echo Echo echo"]);
+
+    assert_eq!(features.data().iter().sum::<f64>(), 3.0);
+    assert_eq!(features.data().iter().filter(|&&x| x != 0.0).count(), 1);
+}
+
+#[test]
+fn hashing_vectorizer_matches_pre_tokenized_features() {
+    let vectorizer = HashingVectorizer::new(16).ngram_range(1, 2);
+
+    let from_text = vectorizer.transform(&["a b"]);
+    let from_terms = vectorizer.transform_features(&[vec!["a", "b", "a b"]]);
+
+    assert_eq!(from_text.data(), from_terms.data());
+}
+
+#[test]
+#[should_panic]
+fn hashing_vectorizer_zero_features_panics() {
+    let _ = HashingVectorizer::new(0);
+}
@@ -0,0 +1,489 @@
+use rm::data::transforms::Transformer;
+use rm::data::transforms::standardize::StandardScaler;
+use rm::data::transforms::minmax::MinMaxScaler;
+use rm::data::transforms::encode::{OneHotEncoder, OrdinalEncoder, UnknownCategory};
+use rm::data::transforms::column::ColumnTransformer;
+use rm::data::transforms::poly::PolynomialFeatures;
+use rm::data::transforms::select::{VarianceThreshold, SelectKBest, ScoreFunc, Rfe, chi2, f_classif,
+                                   mutual_info};
+use rm::data::transforms::discretize::{KBinsDiscretizer, BinStrategy, BinEncoding};
+use rm::data::transforms::whiten::{Whitening, WhitenMethod};
+use rm::data::transforms::target::{BoxCox, LogTransform};
+use rm::learning::lin_reg::LinRegressor;
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+#[test]
+fn standard_scaler_fit_transform() {
+    let inputs = Matrix::new(4, 2, vec![1.0f64, 5.0, 2.0, 5.0, 3.0, 5.0, 4.0, 5.0]);
+
+    let mut scaler = StandardScaler::default();
+    let scaled = scaler.fit_transform(&inputs);
+
+    let means = scaled.mean(0);
+    let vars = scaled.variance(0);
+
+    assert!(means[0].abs() < 1e-12);
+    assert!((vars[0] - 1.0).abs() < 1e-12);
+
+    // The constant column is only centered.
+    assert_eq!(scaler.std_devs().unwrap()[1], 1.0);
+    assert_eq!(means[1], 0.0);
+
+    let original = scaler.inverse_transform(&scaled);
+    for (a, b) in original.data().iter().zip(inputs.data().iter()) {
+        assert!((a - b).abs() < 1e-12);
+    }
+}
+
+#[test]
+#[should_panic]
+fn standard_scaler_not_fitted() {
+    let scaler = StandardScaler::<f64>::default();
+    let _ = scaler.transform(&Matrix::new(1, 1, vec![1.0]));
+}
+
+#[test]
+#[should_panic]
+fn standard_scaler_wrong_cols() {
+    let mut scaler = StandardScaler::default();
+    scaler.fit(&Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0]));
+
+    let _ = scaler.transform(&Matrix::new(1, 3, vec![1.0, 2.0, 3.0]));
+}
+
+#[test]
+fn minmax_scaler_range() {
+    let inputs = Matrix::new(3, 2, vec![-2.0f64, 1.0, 0.0, 1.0, 2.0, 1.0]);
+
+    let mut scaler = MinMaxScaler::new(-1.0, 1.0);
+    let scaled = scaler.fit_transform(&inputs);
+
+    assert_eq!(*scaled.data(), vec![-1.0, -1.0, 0.0, -1.0, 1.0, -1.0]);
+
+    let original = scaler.inverse_transform(&scaled);
+    assert_eq!(original.data(), inputs.data());
+
+    // New data is scaled using the fitted range.
+    let outside = scaler.transform(&Matrix::new(1, 2, vec![4.0, 1.0]));
+    assert_eq!(*outside.data(), vec![2.0, -1.0]);
+}
+
+#[test]
+#[should_panic(expected = "Cannot scale inputs with no columns.")]
+fn minmax_scaler_no_columns() {
+    let mut scaler = MinMaxScaler::default();
+    scaler.fit(&Matrix::<f64>::new(3, 0, vec![]));
+}
+
+#[test]
+fn one_hot_encoder_strings() {
+    let inputs = Matrix::new(3, 1, vec!["b".to_string(), "a".to_string(), "c".to_string()]);
+
+    let mut encoder = OneHotEncoder::default();
+    encoder.fit(&inputs);
+
+    assert_eq!(encoder.encoded_cols(), Some(3));
+
+    let encoded = encoder.transform(&inputs);
+    assert_eq!(*encoded.data(), vec![0.0, 1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+
+    let decoded = encoder.inverse_transform(&encoded);
+    assert_eq!(decoded.data(), inputs.data());
+}
+
+#[test]
+#[should_panic]
+fn one_hot_encoder_unknown_panics() {
+    let mut encoder = OneHotEncoder::default();
+    encoder.fit(&Matrix::new(2, 1, vec![1, 2]));
+
+    let _ = encoder.transform(&Matrix::new(1, 1, vec![3]));
+}
+
+#[test]
+fn one_hot_encoder_as_transformer() {
+    let inputs = Matrix::new(3, 2, vec![1.0, 0.0, 2.0, 0.0, 1.0, 5.0]);
+
+    let mut encoder = OneHotEncoder::new(UnknownCategory::Ignore);
+    let encoded = Transformer::fit_transform(&mut encoder, &inputs);
+
+    assert_eq!(encoded.cols(), 4);
+    assert_eq!(*encoded.data(),
+               vec![1.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.0, 1.0]);
+
+    let unknown = encoder.transform(&Matrix::new(1, 2, vec![3.0, 5.0]));
+    assert_eq!(*unknown.data(), vec![0.0, 0.0, 0.0, 1.0]);
+}
+
+#[test]
+fn one_hot_encoder_no_columns() {
+    let inputs = Matrix::<u32>::new(3, 0, vec![]);
+
+    let mut encoder = OneHotEncoder::default();
+    encoder.fit(&inputs);
+    assert_eq!(encoder.encoded_cols(), Some(0));
+
+    let encoded = encoder.transform(&inputs);
+    assert_eq!((encoded.rows(), encoded.cols()), (3, 0));
+
+    let decoded = encoder.inverse_transform(&encoded);
+    assert_eq!((decoded.rows(), decoded.cols()), (3, 0));
+}
+
+#[test]
+fn ordinal_encoder_round_trip() {
+    let inputs = Matrix::new(3, 2, vec![10, 3, 20, 1, 10, 2]);
+
+    let mut encoder = OrdinalEncoder::default();
+    encoder.fit(&inputs);
+
+    let encoded = encoder.transform(&inputs);
+    assert_eq!(*encoded.data(), vec![0.0, 2.0, 1.0, 0.0, 0.0, 1.0]);
+
+    let decoded = encoder.inverse_transform(&encoded);
+    assert_eq!(decoded.data(), inputs.data());
+}
+
+#[test]
+#[should_panic]
+fn ordinal_encoder_invalid_inverse() {
+    let mut encoder = OrdinalEncoder::default();
+    encoder.fit(&Matrix::new(2, 1, vec![1, 2]));
+
+    let _ = encoder.inverse_transform(&Matrix::new(1, 1, vec![2.0]));
+}
+
+#[test]
+fn column_transformer_round_trip() {
+    let inputs = Matrix::new(3, 3, vec![1.0, 10.0, 0.0, 2.0, 20.0, 1.0, 3.0, 40.0, 0.0]);
+
+    let mut transformer = ColumnTransformer::new()
+                              .add(&[2], OneHotEncoder::default())
+                              .add(&[0, 1], MinMaxScaler::default());
+
+    let outputs = transformer.fit_transform(&inputs);
+
+    assert_eq!(outputs.cols(), 4);
+    assert_eq!(outputs.select_cols(&[0, 1]).data(), &vec![1.0, 0.0, 0.0, 1.0, 1.0, 0.0]);
+    assert_eq!(outputs[[2, 3]], 1.0);
+
+    let original = transformer.inverse_transform(&outputs);
+    for (a, b) in original.data().iter().zip(inputs.data().iter()) {
+        assert!((a - b).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn column_transformer_drops_unused() {
+    let inputs = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+    let mut transformer = ColumnTransformer::new().add(&[1], MinMaxScaler::default());
+    let outputs = transformer.fit_transform(&inputs);
+
+    assert_eq!(*outputs.data(), vec![0.0, 1.0]);
+}
+
+#[test]
+#[should_panic]
+fn column_transformer_cannot_invert_dropped() {
+    let inputs = Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+
+    let mut transformer = ColumnTransformer::new().add(&[1], MinMaxScaler::default());
+    let outputs = transformer.fit_transform(&inputs);
+
+    let _ = transformer.inverse_transform(&outputs);
+}
+
+#[test]
+fn polynomial_features_terms() {
+    let inputs = Matrix::new(1, 2, vec![2.0, 3.0]);
+
+    let mut poly = PolynomialFeatures::new(3).include_bias(false);
+    let outputs = poly.fit_transform(&inputs);
+
+    assert_eq!(poly.terms().len(), 9);
+    assert_eq!(*outputs.data(), vec![2.0, 3.0, 4.0, 6.0, 9.0, 8.0, 12.0, 18.0, 27.0]);
+}
+
+#[test]
+fn polynomial_features_round_trip() {
+    let inputs = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+    let mut poly = PolynomialFeatures::new(2);
+    let outputs = poly.fit_transform(&inputs);
+
+    assert_eq!(outputs.cols(), 10);
+    assert_eq!(poly.inverse_transform(&outputs).data(), inputs.data());
+}
+
+#[test]
+#[should_panic]
+fn polynomial_features_wrong_cols_panics() {
+    let mut poly = PolynomialFeatures::new(2);
+    poly.fit(&Matrix::new(1, 2, vec![1.0, 2.0]));
+
+    let _ = poly.transform(&Matrix::new(1, 3, vec![1.0, 2.0, 3.0]));
+}
+
+#[test]
+fn variance_threshold_removes_constant() {
+    let inputs = Matrix::new(3, 3, vec![1.0, 5.0, 0.0, 2.0, 5.0, 4.0, 3.0, 5.0, 8.0]);
+
+    let mut select = VarianceThreshold::default();
+    let outputs = select.fit_transform(&inputs);
+
+    assert_eq!(select.selected(), &[0, 2]);
+    assert_eq!(*outputs.data(), vec![1.0, 0.0, 2.0, 4.0, 3.0, 8.0]);
+
+    let restored = select.inverse_transform(&outputs);
+    assert_eq!(*restored.data(), vec![1.0, 0.0, 0.0, 2.0, 0.0, 4.0, 3.0, 0.0, 8.0]);
+}
+
+#[test]
+fn select_k_best_chi2() {
+    // Word counts where the first word only occurs in the first class.
+    let inputs = Matrix::new(4, 3, vec![3.0, 1.0, 1.0, 2.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 0.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+
+    let scores = chi2(&inputs, &targets);
+    assert!(scores[0] > scores[2]);
+    assert_eq!(scores[1], 0.0);
+
+    let mut select = SelectKBest::new(1, ScoreFunc::ChiSquared);
+    let outputs = select.fit_transform(&inputs, &targets);
+
+    assert_eq!(select.selected(), &[0]);
+    assert_eq!(*outputs.data(), vec![3.0, 2.0, 0.0, 0.0]);
+}
+
+#[test]
+fn select_k_best_mutual_info() {
+    let inputs = Matrix::new(4, 2, vec![0.0, 1.0, 0.1, 0.0, 1.0, 1.0, 0.9, 0.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+
+    let scores = mutual_info(&inputs, &targets, 2);
+    assert!((scores[0] - 2f64.ln()).abs() < 1e-12);
+    assert!(scores[1].abs() < 1e-12);
+
+    let mut select = SelectKBest::new(1, ScoreFunc::MutualInfo(2));
+    select.fit(&inputs, &targets);
+    assert_eq!(select.selected(), &[0]);
+}
+
+#[test]
+fn f_classif_scores() {
+    let inputs = Matrix::new(4, 2, vec![1.0, 0.0, 1.0, 1.0, 3.0, 0.0, 3.0, 1.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+
+    let scores = f_classif(&inputs, &targets);
+    assert!(scores[0].is_infinite());
+    assert_eq!(scores[1], 0.0);
+}
+
+#[test]
+#[should_panic]
+fn select_k_best_too_many_cols() {
+    let mut select = SelectKBest::new(3, ScoreFunc::FClassif);
+    select.fit(&Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0]),
+               &Vector::new(vec![0.0, 1.0]));
+}
+
+#[test]
+fn rfe_ranks_columns() {
+    let inputs = Matrix::new(8, 3, vec![1.0, 0.3, 5.0, 2.0, -0.2, 1.0, 3.0, 0.8, 2.0,
+                                        4.0, 0.1, 7.0, 5.0, -0.5, 3.0, 6.0, 0.4, 1.0,
+                                        7.0, 0.2, 4.0, 8.0, -0.1, 2.0]);
+    let targets = Vector::new((0..8).map(|i| 2.0 * inputs[[i, 0]] + inputs[[i, 2]]).collect());
+
+    let mut rfe = Rfe::new(2);
+    rfe.fit(LinRegressor::default, &inputs, &targets);
+
+    assert_eq!(rfe.selected(), &[0, 2]);
+    assert_eq!(rfe.ranking().unwrap(), &vec![1, 2, 1]);
+    assert_eq!(rfe.transform(&inputs).cols(), 2);
+}
+
+#[test]
+fn rfe_removes_step_columns() {
+    let inputs = Matrix::new(8, 4, vec![1.0, 0.3, 5.0, 0.2, 2.0, -0.2, 1.0, -0.1,
+                                        3.0, 0.8, 2.0, 0.4, 4.0, 0.1, 7.0, 0.3,
+                                        5.0, -0.5, 3.0, 0.1, 6.0, 0.4, 1.0, -0.3,
+                                        7.0, 0.2, 4.0, 0.2, 8.0, -0.1, 2.0, 0.5]);
+    let targets = Vector::new((0..8).map(|i| 3.0 * inputs[[i, 0]] + inputs[[i, 2]]).collect());
+
+    let mut rfe = Rfe::new(1).step(2);
+    rfe.fit(LinRegressor::default, &inputs, &targets);
+
+    // Two columns go in the first elimination, then one in the last.
+    assert_eq!(rfe.selected(), &[0]);
+    assert_eq!(rfe.ranking().unwrap(), &vec![1, 3, 2, 3]);
+}
+
+#[test]
+#[should_panic(expected = "Must remove at least one column per step.")]
+fn rfe_zero_step() {
+    let _ = Rfe::new(1).step(0);
+}
+
+#[test]
+fn kbins_quantile_ordinal() {
+    let inputs = Matrix::new(6, 1, vec![1.0, 2.0, 3.0, 4.0, 5.0, 100.0]);
+
+    let mut bins = KBinsDiscretizer::new(3);
+    let outputs = bins.fit_transform(&inputs);
+
+    assert_eq!(*outputs.data(), vec![0.0, 0.0, 1.0, 1.0, 2.0, 2.0]);
+    assert_eq!(bins.edges().unwrap()[0].len(), 4);
+}
+
+#[test]
+fn kbins_kmeans_separates_clusters() {
+    let inputs = Matrix::new(6, 1, vec![0.0, 0.1, 0.2, 10.0, 10.1, 10.2]);
+
+    let mut bins = KBinsDiscretizer::new(2).strategy(BinStrategy::KMeans);
+    let outputs = bins.fit_transform(&inputs);
+
+    assert_eq!(*outputs.data(), vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+    assert!((bins.edges().unwrap()[0][1] - 5.1).abs() < 1e-12);
+}
+
+#[test]
+fn kbins_one_hot_round_trip() {
+    let inputs = Matrix::new(3, 2, vec![0.0, 5.0, 1.0, 5.0, 2.0, 5.0]);
+
+    let mut bins = KBinsDiscretizer::new(2)
+                       .strategy(BinStrategy::Uniform)
+                       .encoding(BinEncoding::OneHot);
+    let outputs = bins.fit_transform(&inputs);
+
+    // The constant column has a single bin.
+    assert_eq!(outputs.cols(), 3);
+    assert_eq!(*outputs.data(), vec![1.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0]);
+
+    // Values outside the fitted range use the end bins.
+    let outside = bins.transform(&Matrix::new(1, 2, vec![-3.0, 7.0]));
+    assert_eq!(*outside.data(), vec![1.0, 0.0, 1.0]);
+
+    let centers = bins.inverse_transform(&outputs);
+    assert_eq!(*centers.data(), vec![0.5, 5.0, 1.5, 5.0, 1.5, 5.0]);
+}
+
+fn covariance(m: &Matrix<f64>) -> Matrix<f64> {
+    let means = m.mean(0);
+    let centered = Matrix::from_fn(m.rows(), m.cols(), |i, j| m[[i, j]] - means[j]);
+    (centered.transpose() * centered) / (m.rows() - 1) as f64
+}
+
+#[test]
+fn whitening_pca_and_zca() {
+    let inputs = Matrix::new(6, 3, vec![1.0, 2.0, 0.5, 2.0, 3.5, 1.0, 3.0, 6.5, 0.0,
+                                        4.0, 8.0, 2.5, 5.0, 9.0, 1.5, 6.5, 13.0, 3.0]);
+
+    for method in &[WhitenMethod::Pca, WhitenMethod::Zca] {
+        let mut whiten = Whitening::new(*method).epsilon(0.0);
+        let outputs = whiten.fit_transform(&inputs);
+
+        let cov = covariance(&outputs);
+        for i in 0..3 {
+            for j in 0..3 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((cov[[i, j]] - expected).abs() < 1e-8);
+            }
+        }
+
+        let original = whiten.inverse_transform(&outputs);
+        for (a, b) in original.data().iter().zip(inputs.data().iter()) {
+            assert!((a - b).abs() < 1e-8);
+        }
+    }
+}
+
+#[test]
+fn whitening_pca_orders_components() {
+    let inputs = Matrix::new(4, 2, vec![0.0, 0.0, 10.0, 0.1, 20.0, -0.1, 30.0, 0.0]);
+
+    let mut whiten = Whitening::new(WhitenMethod::Pca);
+    whiten.fit(&inputs);
+
+    let values = whiten.eigenvalues().unwrap();
+    assert!(values[0] > values[1]);
+
+    // The first component is (almost) the first axis.
+    let w = whiten.whitening_matrix().unwrap();
+    assert!(w[[0, 0]].abs() > 100.0 * w[[1, 0]].abs());
+}
+
+#[test]
+fn whitening_zca_is_symmetric() {
+    let inputs = Matrix::new(4, 2, vec![1.0, 2.0, 2.0, 3.5, 3.0, 6.5, 4.0, 8.0]);
+
+    let mut whiten = Whitening::new(WhitenMethod::Zca);
+    whiten.fit(&inputs);
+
+    let w = whiten.whitening_matrix().unwrap();
+    assert!((w[[0, 1]] - w[[1, 0]]).abs() < 1e-10);
+}
+
+#[test]
+#[should_panic]
+fn whitening_singular_without_epsilon() {
+    let inputs = Matrix::new(3, 2, vec![1.0, 2.0, 2.0, 4.0, 3.0, 6.0]);
+
+    let mut whiten = Whitening::new(WhitenMethod::Pca).epsilon(0.0);
+    whiten.fit(&inputs);
+}
+
+#[test]
+fn log_transform_round_trip() {
+    let targets = Vector::new(vec![0.0, 1.0, 9.0]);
+    let mut log = LogTransform::new(1.0);
+
+    let transformed = log.fit_transform(&targets);
+    assert_eq!(transformed[1], 2f64.ln());
+
+    let original = log.inverse_transform(&transformed);
+    for (x, y) in original.data().iter().zip(targets.data()) {
+        assert!((x - y).abs() < 1e-12);
+    }
+}
+
+#[test]
+#[should_panic]
+fn log_transform_non_positive() {
+    let _ = LogTransform::default().transform(&Vector::new(vec![1.0, 0.0]));
+}
+
+#[test]
+fn box_cox_fits_logarithm() {
+    // Targets whose logarithms are symmetric about zero.
+    let logs = [-2.0, -1.5, -1.0, -0.5, -0.25, 0.0, 0.25, 0.5, 1.0, 1.5, 2.0];
+    let targets = Vector::new(logs.iter().map(|x: &f64| x.exp()).collect::<Vec<_>>());
+    let mut box_cox = BoxCox::default();
+    let transformed = box_cox.fit_transform(&targets);
+
+    assert!(box_cox.lambda().unwrap().abs() < 0.01);
+
+    let original = box_cox.inverse_transform(&transformed);
+    for (x, y) in original.data().iter().zip(targets.data()) {
+        assert!((x - y).abs() < 1e-10);
+    }
+}
+
+#[test]
+fn box_cox_fixed_lambda() {
+    let targets = Vector::new(vec![1.0, 4.0, 9.0]);
+    let mut box_cox = BoxCox::with_lambda(0.5);
+    let transformed = box_cox.fit_transform(&targets);
+
+    assert_eq!(box_cox.lambda(), Some(0.5));
+    for (x, y) in transformed.data().iter().zip(&[0.0, 2.0, 4.0]) {
+        assert!((x - y).abs() < 1e-12);
+    }
+}
+
+#[test]
+#[should_panic]
+fn box_cox_not_fitted() {
+    let _ = BoxCox::default().transform(&Vector::new(vec![1.0, 2.0]));
+}
@@ -0,0 +1,45 @@
+use rm::learning::toolkit::activ_fn::{ActivationFunc, Gelu, Softplus, Swish};
+
+/// Checks the gradient against central finite differences.
+fn check_grad<F: ActivationFunc>() {
+    for &x in &[-6.0, -2.5, -0.7, 0.0, 0.3, 1.9, 5.0] {
+        let diff = (F::func(x + 1e-6) - F::func(x - 1e-6)) / 2e-6;
+        assert!((F::func_grad(x) - diff).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn smooth_activation_gradients() {
+    check_grad::<Softplus>();
+    check_grad::<Swish>();
+    check_grad::<Gelu>();
+}
+
+#[test]
+fn softplus_values() {
+    assert!((Softplus::func(0.0) - 2f64.ln()).abs() < 1e-12);
+    assert_eq!(Softplus::func(1000.0), 1000.0);
+    assert_eq!(Softplus::func_grad(-1000.0), 0.0);
+
+    for &x in &[1e-3, 0.5, 2.0, 40.0] {
+        assert!((Softplus::func(Softplus::func_inv(x)) - x).abs() < 1e-9 * x.max(1.0));
+    }
+}
+
+#[test]
+fn swish_and_gelu_values() {
+    assert_eq!(Swish::func(0.0), 0.0);
+    assert_eq!(Swish::func(1000.0), 1000.0);
+    assert_eq!(Swish::func(-1000.0), 0.0);
+
+    assert_eq!(Gelu::func(0.0), 0.0);
+    assert!((Gelu::func(1.0) - 0.8411920).abs() < 1e-6);
+    assert_eq!(Gelu::func(-100.0), 0.0);
+    assert_eq!(Gelu::func(100.0), 100.0);
+}
+
+#[test]
+#[should_panic]
+fn gelu_not_invertible() {
+    Gelu::func_inv(0.5);
+}
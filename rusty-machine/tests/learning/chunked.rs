@@ -0,0 +1,60 @@
+use rm::learning::SupModel;
+use rm::learning::chunked::ChunkedPredict;
+use rm::learning::lin_reg::LinRegressor;
+use rm::learning::nnet::NeuralNet;
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+#[test]
+fn chunks_match_full_prediction() {
+    let inputs = Matrix::new(7, 2, (0..14).map(|x| ((x * x) % 5) as f64).collect());
+    let targets = Vector::new(vec![1.0, 3.0, 2.0, 5.0, 4.0, 7.0, 6.0]);
+
+    let mut model = LinRegressor::default();
+    model.train(&inputs, &targets);
+    let expected = model.predict(&inputs);
+
+    let rows = inputs.data().chunks(2).collect::<Vec<_>>();
+    let chunks: Vec<Vector<f64>> = model.predict_chunked(rows, 3).collect();
+
+    assert_eq!(chunks.iter().map(|c| c.size()).collect::<Vec<_>>(), vec![3, 3, 1]);
+
+    let outputs: Vec<f64> = chunks.into_iter().flat_map(|c| c.into_vec()).collect();
+    assert_eq!(outputs, expected.into_vec());
+}
+
+#[test]
+fn chunks_of_matrix_outputs() {
+    let net = NeuralNet::builder().layers(&[2, 3, 2]).build();
+    let rows = vec![vec![0.0, 1.0], vec![1.0, 0.0], vec![1.0, 1.0]];
+
+    let chunks: Vec<Matrix<f64>> = net.predict_chunked(rows.clone(), 2).collect();
+
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[0].rows(), 2);
+    assert_eq!(chunks[1].cols(), 2);
+
+    let full = net.predict(&Matrix::new(3, 2, rows.concat()));
+    assert_eq!(chunks[1].data()[..], full.data()[4..]);
+}
+
+#[test]
+fn chunks_of_empty_rows() {
+    let model = LinRegressor::default();
+    let rows: Vec<Vec<f64>> = vec![];
+
+    assert_eq!(model.predict_chunked(rows, 4).count(), 0);
+}
+
+#[test]
+#[should_panic]
+fn chunks_with_uneven_rows() {
+    let inputs = Matrix::new(2, 1, vec![1.0, 2.0]);
+    let targets = Vector::new(vec![1.0, 2.0]);
+
+    let mut model = LinRegressor::default();
+    model.train(&inputs, &targets);
+
+    let rows = vec![vec![1.0], vec![1.0, 2.0]];
+    let _ = model.predict_chunked(rows, 2).count();
+}
@@ -0,0 +1,125 @@
+use rm::learning::SupModel;
+use rm::learning::lin_reg::LinRegressor;
+use rm::learning::optim::coord_desc::{Bounded, CoordinateDescent, LeastSquares, Quadratic};
+#[cfg(feature = "rand")]
+use rm::learning::optim::coord_desc::CoordinateOrder;
+use rm::learning::toolkit::regularization::ElasticNet;
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+fn regression_data() -> (Matrix<f64>, Vector<f64>) {
+    let inputs = Matrix::new(5, 2, vec![1.0, 0.0, 2.0, 1.0, 3.0, 5.0, 4.0, 2.0, 5.0, 3.0]);
+    let targets = Vector::new(vec![1.0, 3.0, 10.0, 6.0, 8.5]);
+    (inputs, targets)
+}
+
+#[test]
+fn unpenalized_least_squares_matches_lin_reg() {
+    let (inputs, targets) = regression_data();
+
+    let mut exact = LinRegressor::default();
+    exact.train(&inputs, &targets);
+
+    let full_inputs = Matrix::ones(5, 1).hcat(&inputs);
+    let problem = LeastSquares::new(ElasticNet::new(0.0, 1.0)).intercept(true);
+    let params = CoordinateDescent::new(10000, 1e-12)
+        .optimize(&problem, &[0.0; 3], &full_inputs, &targets);
+
+    for (p, e) in params.iter().zip(exact.parameters().unwrap().data().iter()) {
+        assert!((p - e).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn strong_lasso_keeps_only_the_intercept() {
+    let (inputs, targets) = regression_data();
+    let full_inputs = Matrix::ones(5, 1).hcat(&inputs);
+
+    let problem = LeastSquares::new(ElasticNet::new(100.0, 1.0)).intercept(true);
+    let params = CoordinateDescent::default().optimize(&problem, &[0.0; 3], &full_inputs, &targets);
+
+    assert!((params[0] - targets.mean()).abs() < 1e-6);
+    assert_eq!(params[1], 0.0);
+    assert_eq!(params[2], 0.0);
+}
+
+#[test]
+fn ridge_matches_closed_form() {
+    // A single feature without intercept has the ridge solution
+    // x^T y / (x^T x + n * lambda).
+    let inputs = Matrix::new(3, 1, vec![1.0, 2.0, 3.0]);
+    let targets = Vector::new(vec![2.0, 3.0, 7.0]);
+
+    let problem = LeastSquares::new(ElasticNet::new(0.5, 0.0));
+    let params = CoordinateDescent::default().optimize(&problem, &[0.0], &inputs, &targets);
+
+    assert!((params[0] - 29.0 / 15.5).abs() < 1e-10);
+}
+
+#[test]
+fn bounded_quadratic_solves_svm_dual() {
+    // Two points x = 1 (label 1) and x = -1 (label -1) with a linear
+    // kernel give Q = [[1, 1], [1, 1]]. The unconstrained dual pushes
+    // the multipliers up to the bound C.
+    let q = Matrix::new(2, 2, vec![1.0, 1.0, 1.0, 1.0]);
+    let c = Vector::new(vec![-1.0, -1.0]);
+
+    let dual = Bounded::new(Quadratic::new(q.clone(), c.clone()), 0.0, 0.25);
+    let params = CoordinateDescent::default().optimize(&dual, &[0.0, 0.0], &(), &());
+    assert_eq!(params, vec![0.25, 0.25]);
+
+    // A loose bound leaves the multipliers summing to one, which
+    // minimizes (a + b)^2 / 2 - (a + b).
+    let dual = Bounded::new(Quadratic::new(q, c), 0.0, 10.0);
+    let params = CoordinateDescent::default().optimize(&dual, &[0.0, 0.0], &(), &());
+    assert!((params[0] + params[1] - 1.0).abs() < 1e-10);
+    assert!(params.iter().all(|&a| (0.0..=10.0).contains(&a)));
+}
+
+#[test]
+fn quadratic_minimum() {
+    let q = Matrix::new(2, 2, vec![2.0, 0.5, 0.5, 1.0]);
+    let c = Vector::new(vec![-1.0, 1.0]);
+    let problem = Quadratic::new(q.clone(), c.clone());
+
+    let params = CoordinateDescent::default().optimize(&problem, &[0.0, 0.0], &(), &());
+    let grad = &q * Vector::new(params) + c;
+
+    assert!(grad.data().iter().all(|g| g.abs() < 1e-6));
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn random_order_converges_to_the_same_solution() {
+    let (inputs, targets) = regression_data();
+    let full_inputs = Matrix::ones(5, 1).hcat(&inputs);
+    let problem = LeastSquares::new(ElasticNet::new(0.3, 0.5)).intercept(true);
+
+    let cyclic = CoordinateDescent::new(10000, 1e-12)
+        .optimize(&problem, &[0.0; 3], &full_inputs, &targets);
+    let random = CoordinateDescent::new(10000, 1e-12)
+        .order(CoordinateOrder::Random)
+        .optimize(&problem, &[0.0; 3], &full_inputs, &targets);
+
+    for (a, b) in cyclic.iter().zip(random.iter()) {
+        assert!((a - b).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn logger_sees_decreasing_cost() {
+    use std::sync::{Arc, Mutex};
+
+    let (inputs, targets) = regression_data();
+    let costs = Arc::new(Mutex::new(Vec::new()));
+    let log_costs = costs.clone();
+
+    let problem = LeastSquares::new(ElasticNet::new(0.1, 1.0));
+    CoordinateDescent::new(20, 0.0)
+        .logger(move |log| log_costs.lock().unwrap().push(log.cost))
+        .optimize(&problem, &[0.0; 2], &inputs, &targets);
+
+    let costs = costs.lock().unwrap();
+    assert!(!costs.is_empty());
+    assert!(costs.windows(2).all(|w| w[1] <= w[0] + 1e-12));
+}
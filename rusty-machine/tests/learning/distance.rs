@@ -0,0 +1,63 @@
+use rm::learning::toolkit::distance::{pairwise, Chebyshev, Cosine, Euclidean, Hamming, Manhattan,
+                                      Metric, Minkowski, SquaredEuclidean};
+use rm::linalg::matrix::{BaseMatrix, Matrix};
+
+#[test]
+fn metric_values() {
+    let x = [1.0, 2.0, 3.0];
+    let y = [4.0, 6.0, 3.0];
+
+    assert_eq!(Euclidean.distance(&x, &y), 5.0);
+    assert_eq!(SquaredEuclidean.distance(&x, &y), 25.0);
+    assert_eq!(Manhattan.distance(&x, &y), 7.0);
+    assert_eq!(Chebyshev.distance(&x, &y), 4.0);
+    assert_eq!(Minkowski::new(1.0).distance(&x, &y), 7.0);
+    assert!((Minkowski::new(2.0).distance(&x, &y) - 5.0).abs() < 1e-12);
+    assert_eq!(Hamming.distance(&x, &y), 2.0 / 3.0);
+
+    assert!((Cosine.distance(&[1.0, 0.0], &[0.0, 2.0]) - 1.0).abs() < 1e-12);
+    assert!(Cosine.distance(&[1.0, 1.0], &[3.0, 3.0]).abs() < 1e-12);
+    assert_eq!(Cosine.distance(&[0.0, 0.0], &[1.0, 2.0]), 1.0);
+}
+
+#[test]
+#[should_panic]
+fn minkowski_order_below_one() {
+    let _ = Minkowski::new(0.5);
+}
+
+fn check_pairwise<M: Metric>(metric: &M) {
+    let x = Matrix::new(3, 2, vec![0.0, 0.0, 1.0, 2.0, -3.0, 0.5]);
+    let y = Matrix::new(2, 2, vec![1.0, 1.0, 0.0, 0.0]);
+
+    let dists = pairwise(metric, &x, &y);
+    assert_eq!(dists.rows(), 3);
+    assert_eq!(dists.cols(), 2);
+
+    for i in 0..3 {
+        for j in 0..2 {
+            let expected = metric.distance(x.row(i), y.row(j));
+            assert!((dists[[i, j]] - expected).abs() < 1e-10);
+        }
+    }
+}
+
+#[test]
+fn pairwise_matches_distance() {
+    check_pairwise(&Euclidean);
+    check_pairwise(&SquaredEuclidean);
+    check_pairwise(&Manhattan);
+    check_pairwise(&Chebyshev);
+    check_pairwise(&Minkowski::new(3.0));
+    check_pairwise(&Cosine);
+    check_pairwise(&Hamming);
+}
+
+#[test]
+#[should_panic]
+fn pairwise_column_mismatch() {
+    let x = Matrix::new(1, 2, vec![0.0, 0.0]);
+    let y = Matrix::new(1, 3, vec![0.0, 0.0, 0.0]);
+
+    let _ = pairwise(&Euclidean, &x, &y);
+}
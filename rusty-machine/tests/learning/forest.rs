@@ -0,0 +1,191 @@
+use rm::learning::{SupModel, WeightedSupModel};
+use rm::learning::forest::{RandomForestClassifier, RandomForestRegressor};
+use rm::learning::toolkit::rand_utils;
+use rm::learning::tree::{MaxFeatures, Split, TreeParams};
+use rm::linalg::matrix::{BaseMatrix, Matrix};
+use rm::linalg::vector::Vector;
+
+fn two_blobs() -> (Matrix<f64>, Vector<usize>) {
+    let mut data = Vec::new();
+    let mut targets = Vec::new();
+    for i in 0..20 {
+        let offset = if i < 10 { 0.0 } else { 10.0 };
+        data.push(offset + (i % 5) as f64);
+        data.push(offset + (i % 3) as f64);
+        targets.push(i / 10);
+    }
+    (Matrix::new(20, 2, data), Vector::new(targets))
+}
+
+#[test]
+fn classifier_separates_blobs() {
+    let (inputs, targets) = two_blobs();
+
+    let mut forest = RandomForestClassifier::new(20, TreeParams::default());
+    forest.train(&inputs, &targets);
+
+    assert_eq!(forest.trees().len(), 20);
+    assert_eq!(forest.predict(&inputs).data(), targets.data());
+
+    let proba = forest.predict_proba(&inputs);
+    for i in 0..proba.rows() {
+        assert!((proba.row(i).iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn regressor_averages_trees() {
+    let inputs = Matrix::new(8, 1, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+    let targets = Vector::new(vec![1.0, 1.0, 1.0, 1.0, 5.0, 5.0, 5.0, 5.0]);
+
+    let mut forest = RandomForestRegressor::new(10, TreeParams::default()).bootstrap(false);
+    forest.train(&inputs, &targets);
+
+    // Without bootstrapping or random splits every tree is the same.
+    assert!(forest.trees().iter().all(|t| *t == forest.trees()[0]));
+    assert_eq!(forest.predict(&inputs).data(), targets.data());
+}
+
+#[test]
+fn extra_trees_draw_random_thresholds() {
+    let inputs = Matrix::new(4, 1, vec![0.0, 1.0, 9.0, 10.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+
+    let mut forest = RandomForestRegressor::extra_trees(20, TreeParams::default().max_depth(1));
+    forest.train(&inputs, &targets);
+
+    let thresholds = forest.trees()
+        .iter()
+        .map(|t| match t.nodes()[0].branch.as_ref().unwrap().split {
+            Split::Threshold(x) => x,
+            _ => panic!("Expected a threshold split."),
+        })
+        .collect::<Vec<_>>();
+
+    assert!(thresholds.iter().all(|x| (0.0..10.0).contains(x)));
+    assert!(thresholds.iter().any(|&x| x != thresholds[0]));
+    assert!(forest.trees().iter().all(|t| t.nodes()[0].samples == 4));
+}
+
+#[test]
+fn max_features_limits_each_split() {
+    let (inputs, targets) = two_blobs();
+
+    // The second column is constant, so trees limited to it cannot split.
+    let mut data = inputs.into_vec();
+    for i in 0..20 {
+        data[2 * i + 1] = 0.0;
+    }
+    let inputs = Matrix::new(20, 2, data);
+
+    let params = TreeParams::default().max_features(MaxFeatures::Count(1));
+    let mut forest = RandomForestClassifier::new(30, params).bootstrap(false);
+    forest.train(&inputs, &targets);
+
+    let leaves = forest.trees().iter().map(|t| t.n_leaves()).collect::<Vec<_>>();
+    assert!(leaves.iter().all(|&n| n <= 2));
+    assert!(leaves.contains(&1) && leaves.contains(&2));
+}
+
+#[test]
+fn set_seed_reproduces_forest() {
+    let (inputs, targets) = two_blobs();
+
+    rand_utils::set_seed(11);
+    let mut first = RandomForestClassifier::extra_trees(5, TreeParams::default());
+    first.train(&inputs, &targets);
+
+    rand_utils::set_seed(11);
+    let mut second = RandomForestClassifier::extra_trees(5, TreeParams::default());
+    second.train(&inputs, &targets);
+
+    assert_eq!(first.trees(), second.trees());
+}
+
+#[test]
+fn successive_forests_draw_different_trees() {
+    let (inputs, targets) = two_blobs();
+
+    // Without bootstrap samples the trees differ only by the random
+    // thresholds, which each tree draws in its own task.
+    rand_utils::set_seed(12);
+    let mut first = RandomForestClassifier::extra_trees(5, TreeParams::default());
+    first.train(&inputs, &targets);
+    let mut second = RandomForestClassifier::extra_trees(5, TreeParams::default());
+    second.train(&inputs, &targets);
+
+    assert!(first.trees() != second.trees());
+}
+
+#[test]
+#[should_panic]
+fn untrained_forest_panics() {
+    let forest = RandomForestRegressor::default();
+    forest.predict(&Matrix::new(1, 1, vec![0.0]));
+}
+
+#[test]
+fn classifier_oob_estimates_accuracy() {
+    let (inputs, targets) = two_blobs();
+
+    let mut forest = RandomForestClassifier::new(50, TreeParams::default());
+    forest.train(&inputs, &targets);
+
+    let proba = forest.oob_proba().unwrap();
+    assert_eq!((proba.rows(), proba.cols()), (20, 2));
+    for i in 0..20 {
+        let row = proba.row(i);
+        // With 50 trees every row is almost surely out of some sample.
+        assert!((row.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!(row[targets[i]] > 0.5);
+    }
+    assert_eq!(forest.oob_score(), Some(1.0));
+}
+
+#[test]
+fn regressor_oob_estimates_r2() {
+    let inputs = Matrix::new(30, 1, (0..30).map(|i| i as f64).collect::<Vec<_>>());
+    let targets = Vector::new((0..30).map(|i| 2.0 * i as f64).collect::<Vec<_>>());
+
+    let mut forest = RandomForestRegressor::new(50, TreeParams::default());
+    forest.train(&inputs, &targets);
+
+    let prediction = forest.oob_prediction().unwrap();
+    assert_eq!(prediction.size(), 30);
+    let score = forest.oob_score().unwrap();
+    assert!(score > 0.9 && score < 1.0);
+
+    // A row is never predicted by a tree which saw it.
+    let training = forest.predict(&inputs);
+    assert!(prediction.data() != training.data());
+}
+
+#[test]
+fn no_oob_without_bootstrap() {
+    let (inputs, targets) = two_blobs();
+
+    let mut forest = RandomForestClassifier::extra_trees(5, TreeParams::default());
+    forest.train(&inputs, &targets);
+
+    assert!(forest.oob_proba().is_none());
+    assert!(forest.oob_score().is_none());
+}
+
+#[test]
+fn zero_weight_rows_are_ignored() {
+    let (inputs, mut targets) = two_blobs();
+    // Mislabel a row of each blob and give it no weight.
+    targets.mut_data()[0] = 1;
+    targets.mut_data()[19] = 0;
+    let mut weights = Vector::ones(20);
+    weights.mut_data()[0] = 0.0;
+    weights.mut_data()[19] = 0.0;
+
+    let mut forest = RandomForestClassifier::new(20, TreeParams::default());
+    forest.train_weighted(&inputs, &targets, &weights);
+
+    // No bootstrap sample holds the rows, so every tree leaves them out.
+    let predictions = forest.predict(&inputs);
+    assert_eq!((predictions[0], predictions[19]), (0, 1));
+    assert!(forest.trees().iter().all(|t| t.nodes()[0].weight == 18.0));
+}
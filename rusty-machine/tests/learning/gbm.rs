@@ -0,0 +1,228 @@
+use rm::learning::{SupModel, WeightedSupModel};
+use rm::learning::gbm::{GradientBoostingRegressor, Monotone, Quantile, Tweedie};
+use rm::learning::tree::Split;
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+fn mse(outputs: &Vector<f64>, targets: &Vector<f64>) -> f64 {
+    let diff = outputs - targets;
+    diff.dot(&diff) / outputs.size() as f64
+}
+
+#[test]
+fn boosting_reduces_training_error() {
+    let inputs = Matrix::new(20, 1, (0..20).map(|i| i as f64).collect::<Vec<_>>());
+    let targets = Vector::new((0..20).map(|i| ((i as f64) / 3.0).sin()).collect::<Vec<_>>());
+
+    let mut few = GradientBoostingRegressor::new(5, 0.1);
+    few.train(&inputs, &targets);
+    let mut many = GradientBoostingRegressor::new(100, 0.1);
+    many.train(&inputs, &targets);
+
+    assert_eq!(many.trees().len(), 100);
+    assert!(mse(&many.predict(&inputs), &targets) < mse(&few.predict(&inputs), &targets));
+    assert!(mse(&many.predict(&inputs), &targets) < 0.01);
+}
+
+#[test]
+fn distinct_values_get_exact_thresholds() {
+    let inputs = Matrix::new(4, 1, vec![1.0, 2.0, 3.0, 4.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+
+    let mut model = GradientBoostingRegressor::new(1, 1.0).max_depth(1);
+    model.train(&inputs, &targets);
+
+    let root = &model.trees()[0].nodes()[0];
+    assert_eq!(root.branch.as_ref().unwrap().split, Split::Threshold(2.5));
+}
+
+#[test]
+fn bins_cut_at_quantiles() {
+    let inputs = Matrix::new(100, 1, (0..100).map(|i| i as f64).collect::<Vec<_>>());
+    let targets = Vector::new((0..100).map(|i| i as f64).collect::<Vec<_>>());
+
+    let mut model = GradientBoostingRegressor::new(3, 0.5).max_depth(1).max_bins(2);
+    model.train(&inputs, &targets);
+
+    // With two bins every tree splits at the median.
+    for tree in model.trees() {
+        let branch = tree.nodes()[0].branch.as_ref().unwrap();
+        assert_eq!(branch.split, Split::Threshold(49.0));
+    }
+}
+
+#[test]
+fn missing_values_learn_default_branch() {
+    let inputs = Matrix::new(6, 1, vec![1.0, 2.0, 3.0, 4.0, f64::NAN, f64::NAN]);
+    let targets = Vector::new(vec![0.0, 0.0, 10.0, 10.0, 10.0, 10.0]);
+
+    let mut model = GradientBoostingRegressor::new(50, 0.3).max_depth(1).lambda(0.0);
+    model.train(&inputs, &targets);
+
+    let branch = model.trees()[0].nodes()[0].branch.clone().unwrap();
+    assert_eq!(branch.split, Split::Threshold(2.5));
+    assert!(!branch.missing_left);
+
+    let outputs = model.predict(&Matrix::new(2, 1, vec![f64::NAN, 1.0]));
+    assert!((outputs[0] - 10.0).abs() < 1e-6);
+    assert!(outputs[1].abs() < 1e-6);
+    assert!(model.trees()[0].to_text(None).contains("x[0] > 2.5 or missing"));
+}
+
+#[test]
+fn debug_shows_trained() {
+    let model = GradientBoostingRegressor::default();
+    assert!(format!("{:?}", model).contains("trained: false"));
+}
+
+#[test]
+#[should_panic]
+fn untrained_model_panics() {
+    let model = GradientBoostingRegressor::default();
+    model.predict(&Matrix::new(1, 1, vec![0.0]));
+}
+
+#[test]
+fn monotone_constraints_order_predictions() {
+    // The targets rise with the first column but for a dip, and the
+    // second column is noise.
+    let x = (0..40).map(|i| i as f64).collect::<Vec<_>>();
+    let mut data = Vec::new();
+    let mut targets = Vec::new();
+    for &x_i in &x {
+        data.push(x_i);
+        data.push((x_i * 7.0) % 5.0);
+        let dip = if (20.0..25.0).contains(&x_i) { -8.0 } else { 0.0 };
+        targets.push(x_i / 4.0 + dip);
+    }
+    let inputs = Matrix::new(40, 2, data);
+    let targets = Vector::new(targets);
+
+    let grid = |z: f64| {
+        Matrix::new(80, 2, (0..80).flat_map(|i| vec![i as f64 / 2.0, z]).collect::<Vec<_>>())
+    };
+
+    let constraints = [Monotone::Increasing, Monotone::Unconstrained];
+    let mut model = GradientBoostingRegressor::new(50, 0.3).monotone_constraints(&constraints);
+    model.train(&inputs, &targets);
+
+    for z in 0..5 {
+        let outputs = model.predict(&grid(z as f64));
+        assert!(outputs.data().windows(2).all(|w| w[0] <= w[1] + 1e-12));
+    }
+
+    let constraints = [Monotone::Decreasing, Monotone::Unconstrained];
+    let mut model = GradientBoostingRegressor::new(50, 0.3).monotone_constraints(&constraints);
+    model.train(&inputs, &targets);
+
+    for z in 0..5 {
+        let outputs = model.predict(&grid(z as f64));
+        assert!(outputs.data().windows(2).all(|w| w[0] >= w[1] - 1e-12));
+    }
+}
+
+#[test]
+#[should_panic]
+fn monotone_constraints_need_each_column() {
+    let inputs = Matrix::new(2, 2, vec![0.0, 1.0, 1.0, 0.0]);
+    let targets = Vector::new(vec![0.0, 1.0]);
+
+    let mut model = GradientBoostingRegressor::default()
+        .monotone_constraints(&[Monotone::Increasing]);
+    model.train(&inputs, &targets);
+}
+
+#[test]
+fn quantile_objective_brackets_targets() {
+    // Two groups, each with targets spread evenly over ten values.
+    let inputs = Matrix::new(40, 1, (0..40).map(|i| (i / 20) as f64).collect::<Vec<_>>());
+    let targets = Vector::new((0..40).map(|i| (i % 20 / 2) as f64 + 10.0 * (i / 20) as f64)
+        .collect::<Vec<_>>());
+
+    let mut lower = GradientBoostingRegressor::new(300, 0.1).objective(Quantile::new(0.1));
+    lower.train(&inputs, &targets);
+    let mut upper = GradientBoostingRegressor::new(300, 0.1).objective(Quantile::new(0.9));
+    upper.train(&inputs, &targets);
+
+    let probe = Matrix::new(2, 1, vec![0.0, 1.0]);
+    let (low, high) = (lower.predict(&probe), upper.predict(&probe));
+    for (k, base) in [0.0, 10.0].iter().enumerate() {
+        assert!((low[k] - base).abs() < 1e-6);
+        assert!((high[k] - (base + 8.0)).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn tweedie_objective_predicts_means() {
+    let inputs = Matrix::new(8, 1, vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 1.0, 3.0, 0.0, 4.0, 8.0, 12.0]);
+
+    let mut model = GradientBoostingRegressor::new(200, 0.1).objective(Tweedie::new(1.5));
+    model.train(&inputs, &targets);
+
+    assert!((model.base_score() - 3.5f64.ln()).abs() < 1e-12);
+    let outputs = model.predict(&Matrix::new(2, 1, vec![0.0, 1.0]));
+    assert!((outputs[0] - 1.0).abs() < 0.05);
+    assert!((outputs[1] - 6.0).abs() < 0.05);
+}
+
+#[test]
+fn closure_objective_matches_squared_error() {
+    let inputs = Matrix::new(6, 1, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let targets = Vector::new(vec![1.0, 3.0, 2.0, 5.0, 4.0, 6.0]);
+
+    let mut squared = GradientBoostingRegressor::new(10, 0.3);
+    squared.train(&inputs, &targets);
+    let mut closure = GradientBoostingRegressor::new(10, 0.3)
+        .objective(|output: f64, target: f64| (output - target, 1.0));
+    closure.train(&inputs, &targets);
+
+    assert_eq!(squared.predict(&inputs).data(), closure.predict(&inputs).data());
+}
+
+#[test]
+fn unit_weights_match_unweighted() {
+    let inputs = Matrix::new(6, 1, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let targets = Vector::new(vec![1.0, 3.0, 2.0, 5.0, 4.0, 6.0]);
+
+    let mut unweighted = GradientBoostingRegressor::new(10, 0.3);
+    unweighted.train(&inputs, &targets);
+    let mut weighted = GradientBoostingRegressor::new(10, 0.3);
+    weighted.train_weighted(&inputs, &targets, &Vector::ones(6));
+
+    assert_eq!(unweighted.predict(&inputs).data(), weighted.predict(&inputs).data());
+}
+
+#[test]
+fn zero_weight_rows_are_ignored() {
+    // The last two rows repeat inputs with other targets.
+    let inputs = Matrix::new(6, 1, vec![1.0, 2.0, 3.0, 4.0, 1.0, 4.0]);
+    let targets = Vector::new(vec![1.0, 1.0, 5.0, 5.0, 9.0, -9.0]);
+    let weights = Vector::new(vec![1.0, 1.0, 1.0, 1.0, 0.0, 0.0]);
+
+    let mut weighted = GradientBoostingRegressor::new(20, 0.3);
+    weighted.train_weighted(&inputs, &targets, &weights);
+
+    let mut subset = GradientBoostingRegressor::new(20, 0.3);
+    subset.train(&Matrix::new(4, 1, vec![1.0, 2.0, 3.0, 4.0]),
+                 &Vector::new(vec![1.0, 1.0, 5.0, 5.0]));
+
+    assert_eq!(weighted.base_score(), 3.0);
+    let (w, s) = (weighted.predict(&inputs), subset.predict(&inputs));
+    for i in 0..6 {
+        assert!((w[i] - s[i]).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn quantile_objective_weights_targets() {
+    let inputs = Matrix::new(4, 1, vec![1.0, 1.0, 1.0, 1.0]);
+    let targets = Vector::new(vec![1.0, 2.0, 3.0, 4.0]);
+    let weights = Vector::new(vec![6.0, 1.0, 1.0, 2.0]);
+
+    let mut model = GradientBoostingRegressor::default().objective(Quantile::new(0.5));
+    model.train_weighted(&inputs, &targets, &weights);
+
+    // The first target holds more than half of the weight.
+    assert_eq!(model.base_score(), 1.0);
+}
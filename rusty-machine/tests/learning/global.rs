@@ -0,0 +1,51 @@
+use rm::learning::SupModel;
+use rm::learning::lin_reg::LinRegressor;
+use rm::learning::optim::OptimAlgorithm;
+use rm::learning::optim::global::NelderMead;
+#[cfg(feature = "rand")]
+use rm::learning::optim::global::{GeneticAlgorithm, SimulatedAnnealing};
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+/// A bowl with its minimum at (1, -2) and a non-differentiable kink.
+fn bowl(x: &[f64]) -> f64 {
+    (x[0] - 1.0).abs() + (x[1] + 2.0) * (x[1] + 2.0)
+}
+
+#[test]
+fn nelder_mead_fits_linear_regression() {
+    let inputs = Matrix::new(4, 2, vec![1.0, 0.0, 2.0, 1.0, 3.0, 5.0, 4.0, 2.0]);
+    let targets = Vector::new(vec![1.0, 3.0, 10.0, 6.0]);
+
+    let mut exact = LinRegressor::default();
+    exact.train(&inputs, &targets);
+
+    let full_inputs = Matrix::ones(4, 1).hcat(&inputs);
+    let params = NelderMead::new(2000).optimize(&exact, &[0.0; 3], &full_inputs, &targets).params;
+
+    for (p, e) in params.iter().zip(exact.parameters().unwrap().data().iter()) {
+        assert!((p - e).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn nelder_mead_handles_kinks() {
+    let min = NelderMead::default().minimize(bowl, &[4.0, 4.0]);
+    assert!(bowl(&min) < 1e-6);
+}
+
+#[test]
+#[cfg(feature = "rand")]
+fn stochastic_searches_find_minimum() {
+    let mut evals = 0;
+    let counted = |x: &[f64]| {
+        evals += 1;
+        bowl(x)
+    };
+    let annealed = SimulatedAnnealing::new(3000, 1.0).minimize(counted, &[4.0, 4.0]);
+    assert_eq!(evals, 3001);
+    assert!(bowl(&annealed) < 0.05);
+
+    let evolved = GeneticAlgorithm::new(100, 40).minimize(bowl, &[4.0, 4.0]);
+    assert!(bowl(&evolved) < 0.05);
+}
@@ -1,7 +1,10 @@
 use rm::linalg::matrix::Matrix;
 use rm::linalg::vector::Vector;
 use rm::learning::SupModel;
-use rm::learning::gp::GaussianProcess;
+use rm::learning::gp::{ConstMean, GaussianProcess, GaussianProcessClassifier, MarginalLikelihood};
+use rm::learning::optim::Optimizable;
+use rm::learning::optim::fmincg::ConjugateGD;
+use rm::learning::toolkit::kernel::{Hyperparameters, Laplacian, SquaredExp};
 
 #[test]
 fn test_default_gp() {
@@ -17,3 +20,98 @@ fn test_default_gp() {
 
 	let outputs = gp.predict(&test_inputs);
 }
+
+#[test]
+fn gp_classifier_probabilities() {
+	let inputs = Matrix::new(8, 1, vec![-4., -3., -2., -1., 1., 2., 3., 4.]);
+	let targets = Vector::new(vec![0., 0., 0., 0., 1., 1., 1., 1.]);
+
+	let mut gpc = GaussianProcessClassifier::new(SquaredExp::new(2., 4.));
+	gpc.train(&inputs, &targets);
+
+	let test_inputs = Matrix::new(3, 1, vec![-2.5, 0., 2.5]);
+	let probs = gpc.predict(&test_inputs);
+
+	assert!(probs[0] < 0.2);
+	assert!((probs[1] - 0.5).abs() < 1e-6);
+	assert!(probs[2] > 0.8);
+	assert!((probs[0] + probs[2] - 1.0).abs() < 1e-6);
+	assert!(gpc.log_marginal_likelihood().unwrap() < 0.0);
+}
+
+#[test]
+fn gp_classifier_uncertainty_grows_away_from_data() {
+	let inputs = Matrix::new(4, 1, vec![-2., -1., 1., 2.]);
+	let targets = Vector::new(vec![0., 0., 1., 1.]);
+
+	let mut gpc = GaussianProcessClassifier::default();
+	gpc.train(&inputs, &targets);
+
+	let (mean, var) = gpc.predict_latent(&Matrix::new(2, 1, vec![1.5, 30.]));
+	assert!(mean[0] > 0.0);
+	assert!(var[0] < var[1]);
+	assert!((var[1] - 1.0).abs() < 1e-6);
+
+	// The prediction far from the data reverts to the prior.
+	let probs = gpc.predict(&Matrix::new(1, 1, vec![30.]));
+	assert!((probs[0] - 0.5).abs() < 1e-6);
+}
+
+#[test]
+#[should_panic]
+fn gp_classifier_rejects_non_binary_targets() {
+	let mut gpc = GaussianProcessClassifier::default();
+	gpc.train(&Matrix::new(2, 1, vec![0., 1.]), &Vector::new(vec![0., 2.]));
+}
+
+fn check_marginal_likelihood_grad<T: Hyperparameters>(objective: MarginalLikelihood<T>) {
+	let inputs = Matrix::new(5, 2, vec![0., 1., 1., 0.5, 2., 2., 3., 1.5, 4., 0.]);
+	let targets = Vector::new(vec![0.5, -0.2, 1.0, 0.3, -0.7]);
+
+	for params in &[vec![0.3, -0.2, -1.5], vec![-0.5, 0.4, -0.8]] {
+		let (_, grad) = objective.compute_grad(params, &inputs, &targets);
+
+		let eps = 1e-6;
+		for i in 0..params.len() {
+			let mut plus = params.clone();
+			let mut minus = params.clone();
+			plus[i] += eps;
+			minus[i] -= eps;
+
+			let numeric = (objective.compute_grad(&plus, &inputs, &targets).0 -
+			               objective.compute_grad(&minus, &inputs, &targets).0) / (2. * eps);
+			assert!((grad[i] - numeric).abs() < 1e-5);
+		}
+	}
+}
+
+#[test]
+fn marginal_likelihood_gradient_matches_finite_differences() {
+	check_marginal_likelihood_grad(MarginalLikelihood::new(SquaredExp::default()));
+	check_marginal_likelihood_grad(MarginalLikelihood::new(Laplacian::default()));
+}
+
+#[test]
+fn gp_optimize_hyperparams_increases_likelihood() {
+	let inputs = Matrix::new(12, 1, (0..12).map(|i| i as f64 * 0.5).collect::<Vec<_>>());
+	let targets = inputs.data().iter().map(|x| (x * 1.3).sin()).collect::<Vector<f64>>();
+
+	let ker = SquaredExp::new(0.1, 5.);
+	let mut gp = GaussianProcess::new(ker, ConstMean::default(), 0.5);
+
+	let mut start = ker.log_params();
+	start.push(0.5f64.ln());
+	let objective = MarginalLikelihood::new(ker);
+	let (start_cost, _) = objective.compute_grad(&start, &inputs, &targets);
+
+	let report = gp.optimize_hyperparams(&inputs, &targets, &ConjugateGD::default());
+	assert!(report.cost < start_cost);
+
+	// The targets are smooth with little noise.
+	assert!(gp.kernel().ls > 0.5);
+	assert!(gp.noise < 0.1);
+
+	let outputs = gp.predict(&Matrix::new(2, 1, vec![1.25, 3.75]));
+	assert!((outputs[0] - (1.25f64 * 1.3).sin()).abs() < 0.1);
+	assert!((outputs[1] - (3.75f64 * 1.3).sin()).abs() < 0.1);
+}
@@ -0,0 +1,82 @@
+use rm::learning::{SupModel, UnSupModel, IncrementalModel, IncrementalUnSupModel};
+use rm::learning::k_means::KMeansClassifier;
+use rm::learning::logistic_reg::LogisticRegressor;
+use rm::learning::nnet::NeuralNet;
+use rm::learning::optim::grad_desc::GradientDesc;
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+#[test]
+fn logistic_batches_continue_training() {
+    let inputs = Matrix::new(4, 1, vec![-2.0, -1.0, 1.0, 2.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+
+    let mut model = LogisticRegressor::new(GradientDesc::new(0.5, 10));
+    model.train_incremental(&inputs, &targets);
+    let first = model.predict(&inputs);
+
+    model.train_incremental(&inputs, &targets);
+    let second = model.predict(&inputs);
+
+    // More batches move the predictions further towards the targets.
+    assert!(second[3] > first[3]);
+    assert!(second[0] < first[0]);
+}
+
+#[test]
+fn logistic_batches_match_longer_training() {
+    let inputs = Matrix::new(4, 1, vec![-2.0, -1.0, 1.0, 2.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+
+    let mut batched = LogisticRegressor::new(GradientDesc::new(0.5, 10));
+    batched.train_incremental(&inputs, &targets);
+    batched.train_incremental(&inputs, &targets);
+
+    let mut full = LogisticRegressor::new(GradientDesc::new(0.5, 20));
+    full.train(&inputs, &targets);
+
+    let diff = batched.parameters().unwrap() - full.parameters().unwrap();
+    assert!(diff.dot(&diff) < 1e-20);
+}
+
+#[test]
+#[should_panic]
+fn logistic_batch_wrong_columns() {
+    let mut model = LogisticRegressor::default();
+    model.train_incremental(&Matrix::new(2, 1, vec![0.0, 1.0]), &Vector::new(vec![0.0, 1.0]));
+    model.train_incremental(&Matrix::new(1, 2, vec![0.0, 1.0]), &Vector::new(vec![1.0]));
+}
+
+#[test]
+fn neural_net_batches() {
+    let inputs = Matrix::new(2, 1, vec![0.0, 1.0]);
+    let targets = Matrix::new(2, 1, vec![0.0, 1.0]);
+
+    let layers = &[1, 2, 1];
+    let mut model = NeuralNet::default(layers);
+
+    for _ in 0..3 {
+        model.train_incremental(&inputs, &targets);
+    }
+
+    assert_eq!(model.predict(&inputs).rows(), 2);
+}
+
+#[test]
+fn k_means_batches_converge_to_cluster_means() {
+    let mut model = KMeansClassifier::new(2);
+    model.train_incremental(&Matrix::new(2, 1, vec![0.0, 10.0]));
+
+    for _ in 0..50 {
+        model.train_incremental(&Matrix::new(4, 1, vec![-1.0, 1.0, 9.0, 11.0]));
+    }
+
+    let mut centroids = model.centroids.as_ref().unwrap().data().clone();
+    centroids.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    assert!((centroids[0] - 0.0).abs() < 0.1);
+    assert!((centroids[1] - 10.0).abs() < 0.1);
+
+    let classes = model.predict(&Matrix::new(2, 1, vec![0.5, 9.5]));
+    assert!(classes[0] != classes[1]);
+}
@@ -80,4 +80,38 @@ fn test_no_train_predict() {
 
     model.predict(&inputs);
 
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_predict_from_slice() {
+    let mut model = KMeansClassifier::new(2);
+    model.centroids = Some(Matrix::new(2, 1, vec![0.0, 10.0]));
+
+    let inputs = Matrix::new(4, 1, vec![0.0, 0.5, 10.0, 10.5]);
+    let classes = model.predict(&inputs.slice_rows(1, 3));
+
+    assert_eq!(*classes.data(), vec![0, 1]);
+}
+
+#[test]
+fn test_online_update_means() {
+    let mut model = KMeansClassifier::new(2);
+
+    let samples = [0.0, 0.0, 10.0, 2.0, 12.0, 1.0, 11.0];
+    let classes: Vec<usize> = samples.iter().map(|x| model.update(&[*x])).collect();
+
+    // The repeated first sample does not become a second centroid.
+    assert_eq!(classes, vec![0, 0, 1, 0, 1, 0, 1]);
+
+    let centroids = model.centroids.as_ref().unwrap();
+    assert!((centroids[[0, 0]] - 0.75).abs() < 1e-12);
+    assert!((centroids[[1, 0]] - 11.0).abs() < 1e-12);
+}
+
+#[test]
+#[should_panic]
+fn test_online_update_wrong_length() {
+    let mut model = KMeansClassifier::new(2);
+    model.update(&[0.0, 1.0]);
+    model.update(&[0.0]);
+}
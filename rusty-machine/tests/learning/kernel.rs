@@ -0,0 +1,54 @@
+use rm::learning::toolkit::kernel::{self, Kernel, KernelArith, Laplacian, Linear, Polynomial, RBF,
+                                    Sigmoid};
+use rm::linalg::matrix::Matrix;
+
+#[test]
+fn laplacian_kernel() {
+    let ker = Laplacian::new(2.0, 3.0);
+
+    assert_eq!(ker.kernel(&[1.0, 2.0], &[1.0, 2.0]), 3.0);
+    assert!((ker.kernel(&[1.0, 2.0], &[2.0, 0.0]) - 3.0 * (-1.5f64).exp()).abs() < 1e-12);
+}
+
+#[test]
+fn kernel_sum_and_product() {
+    let x1 = [1.0, 2.0];
+    let x2 = [0.5, -1.0];
+
+    let lin = Linear::new(1.0).kernel(&x1, &x2);
+    let poly = Polynomial::new(1.0, 2.0, 1.0).kernel(&x1, &x2);
+
+    let sum = KernelArith(Linear::new(1.0)) + KernelArith(Polynomial::new(1.0, 2.0, 1.0));
+    assert_eq!(sum.kernel(&x1, &x2), lin + poly);
+
+    let prod = KernelArith(Linear::new(1.0)) * KernelArith(Polynomial::new(1.0, 2.0, 1.0));
+    assert_eq!(prod.kernel(&x1, &x2), lin * poly);
+
+    let nested = sum * KernelArith(Linear::new(1.0));
+    assert_eq!(nested.kernel(&x1, &x2), (lin + poly) * lin);
+}
+
+#[test]
+fn gram_matrices() {
+    let x = Matrix::new(3, 2, vec![0.0, 1.0, 1.0, 0.0, 2.0, 2.0]);
+    let y = Matrix::new(2, 2, vec![1.0, 1.0, -1.0, 0.5]);
+    let ker = RBF::new(1.5, 2.0);
+
+    let gram = kernel::gram_matrix(&ker, &x, &y);
+    assert_eq!(gram.rows(), 3);
+    assert_eq!(gram.cols(), 2);
+    assert_eq!(gram[[2, 1]], ker.kernel(&[2.0, 2.0], &[-1.0, 0.5]));
+
+    let sym = kernel::symmetric_gram_matrix(&Sigmoid::new(0.5, 0.1), &x);
+    let full = kernel::gram_matrix(&Sigmoid::new(0.5, 0.1), &x, &x);
+    assert_eq!(sym.data(), full.data());
+}
+
+#[test]
+#[should_panic]
+fn gram_matrix_column_mismatch() {
+    let x = Matrix::new(1, 2, vec![0.0, 1.0]);
+    let y = Matrix::new(1, 3, vec![0.0, 1.0, 2.0]);
+
+    let _ = kernel::gram_matrix(&Linear::default(), &x, &y);
+}
@@ -1,6 +1,6 @@
-use rm::linalg::matrix::Matrix;
+use rm::linalg::matrix::{Matrix, MatrixSlice, BaseMatrix};
 use rm::linalg::vector::Vector;
-use rm::learning::SupModel;
+use rm::learning::{ApproxEq, ModelParams, SupModel, WeightedSupModel};
 use rm::learning::lin_reg::LinRegressor;
 use libnum::abs;
 
@@ -47,4 +47,122 @@ fn test_no_train_predict() {
     let inputs = Matrix::new(3, 2, vec![1.0, 2.0, 1.0, 3.0, 1.0, 4.0]);
 
     let _ = lin_mod.predict(&inputs);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_weighted_regression() {
+    let inputs = Matrix::new(4, 1, vec![1.0, 2.0, 3.0, 4.0]);
+    let targets = Vector::new(vec![2.0, 4.0, 6.0, 20.0]);
+
+    // Unit weights match ordinary least squares.
+    let mut unweighted = LinRegressor::default();
+    unweighted.train(&inputs, &targets);
+
+    let mut lin_mod = LinRegressor::default();
+    lin_mod.train_weighted(&inputs, &targets, &Vector::ones(4));
+
+    let (a, b) = (unweighted.parameters().unwrap(), lin_mod.parameters().unwrap());
+    assert!(abs(a[0] - b[0]) < 1e-8);
+    assert!(abs(a[1] - b[1]) < 1e-8);
+
+    // A zero weight removes the outlier.
+    lin_mod.train_weighted(&inputs, &targets, &Vector::new(vec![1.0, 1.0, 1.0, 0.0]));
+
+    let parameters = lin_mod.parameters().unwrap();
+    assert!(abs(parameters[0]) < 1e-8);
+    assert!(abs(parameters[1] - 2.0) < 1e-8);
+}
+
+#[test]
+#[should_panic]
+fn test_weighted_regression_wrong_weights() {
+    let mut lin_mod = LinRegressor::default();
+    let inputs = Matrix::new(3, 1, vec![2.0, 3.0, 4.0]);
+    let targets = Vector::new(vec![5.0, 6.0, 7.0]);
+
+    lin_mod.train_weighted(&inputs, &targets, &Vector::ones(2));
+}
+
+#[test]
+fn test_predict_from_slices() {
+    let mut lin_mod = LinRegressor::default();
+    let inputs = Matrix::new(4, 1, vec![1.0, 3.0, 5.0, 7.0]);
+    let targets = Vector::new(vec![1.0, 5.0, 9.0, 13.0]);
+
+    lin_mod.train(&inputs, &targets);
+
+    let view = lin_mod.predict(&inputs.slice_rows(2, 4));
+    assert_eq!(view.size(), 2);
+    assert!(abs(view[1] - 13.0) < 1e-8);
+
+    let data = [10.0];
+    let outputs = lin_mod.predict(&MatrixSlice::from_slice(&data, 1, 1));
+    assert!(abs(outputs[0] - 19.0) < 1e-8);
+}
+
+#[test]
+fn test_predict_row() {
+    let mut lin_mod = LinRegressor::default();
+    let inputs = Matrix::new(4, 2, vec![1.0, 0.0, 3.0, 1.0, 5.0, 0.0, 7.0, 2.0]);
+    let targets = Vector::new(vec![1.0, 6.0, 9.0, 15.0]);
+
+    lin_mod.train(&inputs, &targets);
+
+    let outputs = lin_mod.predict(&inputs);
+    for i in 0..inputs.rows() {
+        assert!(abs(lin_mod.predict_row(inputs.row(i)) - outputs[i]) < 1e-12);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_predict_row_wrong_length() {
+    let mut lin_mod = LinRegressor::default();
+    let inputs = Matrix::new(3, 1, vec![2.0, 3.0, 4.0]);
+    let targets = Vector::new(vec![5.0, 6.0, 7.0]);
+
+    lin_mod.train(&inputs, &targets);
+    lin_mod.predict_row(&[1.0, 2.0]);
+}
+
+#[test]
+fn test_clone_and_approx_eq() {
+    let inputs = Matrix::new(3, 1, vec![2.0, 3.0, 4.0]);
+    let targets = Vector::new(vec![5.0, 6.0, 7.0]);
+
+    let untrained = LinRegressor::default();
+    let mut lin_mod = untrained.clone();
+    assert!(lin_mod.approx_eq(&untrained, 0.0));
+    assert!(format!("{:?}", lin_mod).contains("trained: false"));
+
+    lin_mod.train(&inputs, &targets);
+    assert!(!lin_mod.approx_eq(&untrained, 1e10));
+    assert!(format!("{:?}", lin_mod).contains("trained: true"));
+
+    let mut copy = lin_mod.clone();
+    assert!(copy.approx_eq(&lin_mod, 0.0));
+
+    copy.train(&inputs, &Vector::new(vec![5.0, 6.0, 7.5]));
+    assert!(!copy.approx_eq(&lin_mod, 1e-3));
+    assert!(copy.approx_eq(&lin_mod, 1.0));
+}
+
+#[test]
+fn test_param_groups() {
+    let mut lin_mod = LinRegressor::default();
+    assert!(lin_mod.param_groups().is_empty());
+    assert_eq!(lin_mod.num_params(), 0);
+
+    let inputs = Matrix::new(4, 2, vec![1.0, 0.0, 0.0, 1.0, 1.0, 1.0, 2.0, 1.0]);
+    let targets = Vector::new(vec![3.0, 0.0, 2.0, 5.0]);
+    lin_mod.train(&inputs, &targets);
+
+    let params = lin_mod.parameters().unwrap();
+    let groups = lin_mod.param_groups();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].name, "intercept");
+    assert_eq!(groups[0].values.data(), &vec![params[0]]);
+    assert_eq!(groups[1].name, "coefficients");
+    assert_eq!((groups[1].values.rows(), groups[1].values.cols()), (2, 1));
+    assert_eq!(groups[1].values.data(), &vec![params[1], params[2]]);
+}
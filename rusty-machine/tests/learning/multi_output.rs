@@ -0,0 +1,54 @@
+use rm::learning::{ApproxEq, SupModel, WeightedSupModel};
+use rm::learning::gp::GaussianProcess;
+use rm::learning::lin_reg::LinRegressor;
+use rm::learning::multi_output::MultiOutputRegressor;
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+#[test]
+fn each_output_matches_single_model() {
+    let inputs = Matrix::new(5, 1, vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+    let targets = Matrix::new(5, 2, vec![0.5, 1.0, 0.9, 2.0, 1.1, 0.0, 0.7, 1.5, 0.2, 3.0]);
+
+    let mut model = MultiOutputRegressor::new(GaussianProcess::default());
+    model.train(&inputs, &targets);
+    assert_eq!(model.models().len(), 2);
+
+    let test = Matrix::new(3, 1, vec![0.5, 1.5, 5.0]);
+    let outputs = model.predict(&test);
+    assert_eq!((outputs.rows(), outputs.cols()), (3, 2));
+
+    for j in 0..2 {
+        let column = Vector::new((0..5).map(|i| targets[[i, j]]).collect());
+        let mut single = GaussianProcess::default();
+        single.train(&inputs, &column);
+        let expected = single.predict(&test);
+
+        for i in 0..3 {
+            assert!((outputs[[i, j]] - expected[i]).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn weighted_training_applies_to_each_output() {
+    let inputs = Matrix::new(4, 1, vec![1.0, 2.0, 3.0, 4.0]);
+    let targets = Matrix::new(4, 2, vec![1.0, 2.0, 2.0, 1.0, 3.0, 5.0, 5.0, 0.0]);
+    let weights = Vector::new(vec![1.0, 2.0, 1.0, 0.5]);
+
+    let mut model = MultiOutputRegressor::new(LinRegressor::default());
+    model.train_weighted(&inputs, &targets, &weights);
+
+    let mut second = LinRegressor::default();
+    second.train_weighted(&inputs, &Vector::new(vec![2.0, 1.0, 5.0, 0.0]), &weights);
+
+    assert!(model.models()[1].approx_eq(&second, 1e-12));
+    assert!(!model.approx_eq(&MultiOutputRegressor::new(LinRegressor::default()), 1e10));
+}
+
+#[test]
+#[should_panic]
+fn predict_untrained() {
+    let model = MultiOutputRegressor::new(LinRegressor::default());
+    let _ = model.predict(&Matrix::new(1, 1, vec![1.0]));
+}
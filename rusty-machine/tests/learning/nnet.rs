@@ -0,0 +1,407 @@
+use rm::learning::{ApproxEq, ModelParams, SupModel};
+use rm::learning::nnet::{NeuralNet, MSECriterion, SoftmaxCriterion, KLDivCriterion,
+                        GaussianNLLCriterion};
+use rm::learning::toolkit::cost_fn::{CostFunc, SoftmaxCrossEntropy, SoftmaxKLDivergence};
+use rm::learning::optim::Optimizable;
+use rm::learning::optim::grad_desc::StochasticGD;
+use rm::learning::toolkit::regularization::L2;
+use rm::linalg::matrix::{BaseMatrix, Matrix};
+
+#[test]
+fn builder_sets_options() {
+    let layers = &[2, 3, 1];
+    let mut net = NeuralNet::builder().layers(layers).criterion(MSECriterion).l2(0.5).build();
+
+    assert_eq!(net.layer_sizes(), layers);
+    assert_eq!(net.l2(), 0.5);
+
+    let inputs = Matrix::new(2, 2, vec![0.0, 1.0, 1.0, 0.0]);
+    let targets = Matrix::new(2, 1, vec![1.0, 0.0]);
+    net.train(&inputs, &targets);
+
+    assert_eq!(net.predict(&inputs).rows(), 2);
+}
+
+#[test]
+#[should_panic]
+fn builder_without_layers() {
+    let _ = NeuralNet::builder().build();
+}
+
+#[test]
+#[should_panic(expected = "Set the penalty on either the network or its optimizer, not both.")]
+fn builder_rejects_double_l2() {
+    let sgd = StochasticGD::default().penalty(L2::new(0.1));
+    let _ = NeuralNet::builder().layers(&[1, 2, 1]).optimizer(sgd).l2(0.1).build();
+}
+
+#[test]
+fn l2_penalizes_non_bias_weights() {
+    let layers = &[1, 2, 1];
+    let plain = NeuralNet::builder().layers(layers).criterion(MSECriterion).build();
+    let reg = NeuralNet::builder().layers(layers).criterion(MSECriterion).l2(0.1).build();
+
+    // Layer blocks are (inputs + 1) x outputs, with the bias weights first.
+    let params = vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7];
+    let inputs = Matrix::new(2, 1, vec![0.5, -0.5]);
+    let targets = Matrix::new(2, 1, vec![1.0, 0.0]);
+
+    let (cost, grad) = plain.compute_grad(&params, &inputs, &targets);
+    let (reg_cost, reg_grad) = reg.compute_grad(&params, &inputs, &targets);
+
+    let penalized = [2, 3, 5, 6];
+    let expected_penalty: f64 = penalized.iter().map(|&i| params[i] * params[i]).sum::<f64>() * 0.05;
+    assert!((reg_cost - cost - expected_penalty).abs() < 1e-12);
+
+    for i in 0..params.len() {
+        let extra = if penalized.contains(&i) { 0.1 * params[i] } else { 0.0 };
+        assert!((reg_grad[i] - grad[i] - extra).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn from_parameters_matches_trained_net() {
+    let inputs = Matrix::new(4, 2, vec![0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0]);
+    let targets = Matrix::new(4, 1, vec![0.0, 1.0, 1.0, 2.0]);
+
+    let layers = &[2, 3, 1];
+    let mut net = NeuralNet::new(layers, MSECriterion);
+    net.train(&inputs, &targets);
+
+    let restored = NeuralNet::from_parameters(layers, MSECriterion, net.parameters().to_vec());
+
+    assert_eq!(*restored.predict(&inputs).data(), *net.predict(&inputs).data());
+}
+
+#[test]
+#[should_panic]
+fn from_parameters_wrong_length() {
+    let _ = NeuralNet::from_parameters(&[2, 3, 1], MSECriterion, vec![0.0; 12]);
+}
+
+#[test]
+fn gradient_matches_finite_differences() {
+    let layers = &[2, 3, 1];
+    let params: Vec<f64> = (0..13).map(|i| (i as f64 * 0.37).sin()).collect();
+    let net = NeuralNet::from_parameters(layers, MSECriterion, params.clone());
+
+    let inputs = Matrix::new(2, 2, vec![0.3, -0.2, 0.5, 0.9]);
+    let targets = Matrix::new(2, 1, vec![1.0, 0.0]);
+
+    let (_, grad) = net.compute_grad(&params, &inputs, &targets);
+
+    for i in 0..params.len() {
+        let mut up = params.clone();
+        let mut down = params.clone();
+        up[i] += 1e-6;
+        down[i] -= 1e-6;
+
+        let diff = (net.compute_grad(&up, &inputs, &targets).0 -
+                    net.compute_grad(&down, &inputs, &targets).0) / 2e-6;
+        assert!((grad[i] - diff).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn softmax_gradient_matches_finite_differences() {
+    let layers = &[2, 3, 3];
+    let params: Vec<f64> = (0..21).map(|i| (i as f64 * 0.37).sin()).collect();
+    let net = NeuralNet::from_parameters(layers, SoftmaxCriterion, params.clone());
+
+    let inputs = Matrix::new(2, 2, vec![0.3, -0.2, 0.5, 0.9]);
+    let targets = Matrix::new(2, 3, vec![1.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+
+    let (_, grad) = net.compute_grad(&params, &inputs, &targets);
+
+    for i in 0..params.len() {
+        let mut up = params.clone();
+        let mut down = params.clone();
+        up[i] += 1e-6;
+        down[i] -= 1e-6;
+
+        let diff = (net.compute_grad(&up, &inputs, &targets).0 -
+                    net.compute_grad(&down, &inputs, &targets).0) / 2e-6;
+        assert!((grad[i] - diff).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn softmax_predictions_are_probabilities() {
+    let layers = &[2, 3, 3];
+    let params: Vec<f64> = (0..21).map(|i| (i as f64 * 0.37).sin()).collect();
+    let net = NeuralNet::from_parameters(layers, SoftmaxCriterion, params);
+
+    let inputs = Matrix::new(2, 2, vec![0.3, -0.2, 50.0, -80.0]);
+    let outputs = net.predict(&inputs);
+    assert_eq!(*net.predictor().predict(&inputs).data(), *outputs.data());
+
+    for row in outputs.data().chunks(3) {
+        assert!(row.iter().all(|&p| (0.0..=1.0).contains(&p)));
+        assert!((row.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn gaussian_nll_gradient_matches_finite_differences() {
+    let layers = &[2, 3, 2];
+    let params: Vec<f64> = (0..17).map(|i| (i as f64 * 0.37).sin()).collect();
+    let net = NeuralNet::from_parameters(layers, GaussianNLLCriterion, params.clone());
+
+    let inputs = Matrix::new(2, 2, vec![0.3, -0.2, 0.5, 0.9]);
+    let targets = Matrix::new(2, 1, vec![1.5, -0.5]);
+
+    let (_, grad) = net.compute_grad(&params, &inputs, &targets);
+
+    for i in 0..params.len() {
+        let mut up = params.clone();
+        let mut down = params.clone();
+        up[i] += 1e-6;
+        down[i] -= 1e-6;
+
+        let diff = (net.compute_grad(&up, &inputs, &targets).0 -
+                    net.compute_grad(&down, &inputs, &targets).0) / 2e-6;
+        assert!((grad[i] - diff).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn kl_divergence_is_offset_cross_entropy() {
+    let logits = Matrix::new(2, 3, vec![0.5, -1.0, 2.0, 0.0, 3.0, 1.0]);
+    let targets = Matrix::new(2, 3, vec![0.2, 0.3, 0.5, 0.0, 1.0, 0.0]);
+
+    let target_entropy = -(0.2f64 * 0.2f64.ln() + 0.3 * 0.3f64.ln() + 0.5 * 0.5f64.ln()) / 2.0;
+    let ce = SoftmaxCrossEntropy::cost(&logits, &targets);
+    let kl = SoftmaxKLDivergence::cost(&logits, &targets);
+    assert!((ce - kl - target_entropy).abs() < 1e-12);
+
+    // The divergence is zero when the softmax matches the targets.
+    let matched = Matrix::new(1, 3, vec![0.2f64.ln(), 0.3f64.ln(), 0.5f64.ln()]);
+    let soft = Matrix::new(1, 3, vec![0.2, 0.3, 0.5]);
+    assert!(SoftmaxKLDivergence::cost(&matched, &soft).abs() < 1e-12);
+
+    let net = NeuralNet::from_parameters(&[2, 3],
+                                         KLDivCriterion,
+                                         vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9]);
+    let probs = net.predict(&Matrix::new(1, 2, vec![1.0, -1.0]));
+    assert!((probs.data().iter().sum::<f64>() - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn residual_gradient_matches_finite_differences() {
+    let layers = &[2, 2, 2, 2, 1];
+    let params: Vec<f64> = (0..21).map(|i| (i as f64 * 0.37).sin()).collect();
+    let net = NeuralNet::from_parameters(layers, MSECriterion, params.clone())
+        .residual(0, 2)
+        .residual(1, 3)
+        .residual(2, 3);
+
+    let inputs = Matrix::new(2, 2, vec![0.3, -0.2, 0.5, 0.9]);
+    let targets = Matrix::new(2, 1, vec![1.0, 0.0]);
+
+    let (_, grad) = net.compute_grad(&params, &inputs, &targets);
+
+    for i in 0..params.len() {
+        let mut up = params.clone();
+        let mut down = params.clone();
+        up[i] += 1e-6;
+        down[i] -= 1e-6;
+
+        let diff = (net.compute_grad(&up, &inputs, &targets).0 -
+                    net.compute_grad(&down, &inputs, &targets).0) / 2e-6;
+        assert!((grad[i] - diff).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn residual_adds_block_input() {
+    // The first hidden layer is all ones and the second all zeros,
+    // before the residual. The output sums the second hidden layer.
+    let layers = &[2, 2, 2, 1];
+    let mut params = vec![1.0, 1.0];
+    params.extend(vec![0.0; 10]);
+    params.extend(vec![0.0, 1.0, 1.0]);
+
+    let plain = NeuralNet::from_parameters(layers, MSECriterion, params.clone());
+    let net = NeuralNet::from_parameters(layers, MSECriterion, params).residual(1, 2);
+    let inputs = Matrix::new(2, 2, vec![0.3, -0.2, 4.0, 1.0]);
+
+    assert_eq!(*plain.predict(&inputs).data(), vec![0.0, 0.0]);
+    assert_eq!(*net.predict(&inputs).data(), vec![2.0, 2.0]);
+    assert_eq!(*net.predictor().predict(&inputs).data(), vec![2.0, 2.0]);
+    assert_eq!(net.predict_row(&[0.3, -0.2]), vec![2.0]);
+}
+
+#[test]
+#[should_panic]
+fn residual_requires_same_sizes() {
+    NeuralNet::from_parameters(&[2, 3, 1], MSECriterion, vec![0.0; 13]).residual(0, 1);
+}
+
+#[test]
+#[should_panic]
+fn residual_must_end_on_hidden_layer() {
+    NeuralNet::from_parameters(&[1, 1, 1], MSECriterion, vec![0.0; 4]).residual(1, 2);
+}
+
+#[test]
+fn summary_describes_layers() {
+    let layers = &[4, 3, 3, 2];
+    let net = NeuralNet::from_parameters(layers, SoftmaxCriterion, vec![0.0; 35]).residual(1, 2);
+    let summary = net.summary();
+
+    let shapes: Vec<_> = summary.layers.iter().map(|l| (l.inputs, l.outputs, l.params)).collect();
+    assert_eq!(shapes, vec![(4, 3, 15), (3, 3, 12), (3, 2, 8)]);
+
+    let activations: Vec<_> = summary.layers.iter().map(|l| l.activation).collect();
+    assert_eq!(activations, vec!["Sigmoid", "Sigmoid", "SoftmaxCrossEntropy"]);
+    assert_eq!(summary.layers[1].residuals, vec![1]);
+    assert!(summary.layers[0].residuals.is_empty());
+
+    assert_eq!(summary.total_params, 35);
+    assert_eq!(summary.total_params, net.num_params());
+    assert_eq!(summary.param_bytes, 280);
+    assert_eq!(summary.training_bytes(10), 560 + 2 * 10 * 8 * 8);
+
+    let printed = summary.to_string();
+    assert!(printed.contains("4 -> 3"));
+    assert!(printed.contains("Total parameters: 35"));
+}
+
+#[test]
+fn predict_classes_threshold() {
+    // A single output equal to the input.
+    let mut net = NeuralNet::from_parameters(&[1, 1], MSECriterion, vec![0.0, 1.0]);
+    let inputs = Matrix::new(4, 1, vec![0.2, 0.5, 0.7, 1.5]);
+
+    assert_eq!(net.threshold(), 0.5);
+    assert_eq!(*net.predict_classes(&inputs).data(), vec![0, 1, 1, 1]);
+
+    net.set_threshold(1.0);
+    assert_eq!(*net.predict_classes(&inputs).data(), vec![0, 0, 0, 1]);
+
+    let built = NeuralNet::builder().layers(&[1, 1]).threshold(0.25).build();
+    assert_eq!(built.threshold(), 0.25);
+}
+
+#[test]
+fn predict_classes_argmax() {
+    let layers = &[2, 3, 3];
+    let params: Vec<f64> = (0..21).map(|i| (i as f64 * 0.37).sin()).collect();
+    let net = NeuralNet::from_parameters(layers, SoftmaxCriterion, params);
+
+    let inputs = Matrix::new(3, 2, vec![0.3, -0.2, 5.0, 1.0, -4.0, 2.0]);
+    let probs = net.predict(&inputs);
+    let classes = net.predict_classes(&inputs);
+
+    for (i, &c) in classes.data().iter().enumerate() {
+        assert!(probs.row(i).iter().all(|&p| p <= probs[[i, c]]));
+    }
+}
+
+#[test]
+fn predictor_matches_predict() {
+    let layers = &[2, 3, 2];
+    let params: Vec<f64> = (0..17).map(|i| (i as f64 * 0.37).sin()).collect();
+    let net = NeuralNet::from_parameters(layers, MSECriterion, params);
+    let mut predictor = net.predictor();
+
+    let batches = [Matrix::new(1, 2, vec![0.3, -0.2]),
+                   Matrix::new(3, 2, vec![0.5, 0.9, -1.0, 0.0, 2.0, 1.5]),
+                   Matrix::new(1, 2, vec![0.1, 0.7])];
+
+    for inputs in batches.iter() {
+        assert_eq!(*predictor.predict(inputs).data(), *net.predict(inputs).data());
+    }
+}
+
+#[test]
+fn predict_row_matches_predict() {
+    let layers = &[2, 3, 2];
+    let params: Vec<f64> = (0..17).map(|i| (i as f64 * 0.37).sin()).collect();
+    let net = NeuralNet::from_parameters(layers, MSECriterion, params);
+
+    let inputs = Matrix::new(1, 2, vec![0.5, -0.9]);
+    assert_eq!(net.predict_row(&[0.5, -0.9]), *net.predict(&inputs).data());
+}
+
+#[test]
+fn clone_trains_independently() {
+    let layers = &[2, 3, 1];
+    let net = NeuralNet::builder().layers(layers).criterion(MSECriterion).build();
+    let mut copy = net.clone();
+    assert!(copy.approx_eq(&net, 0.0));
+
+    let debug = format!("{:?}", net);
+    assert!(debug.contains("layer_sizes: [2, 3, 1]"));
+    // The initialized weights can already be used to predict.
+    assert!(debug.contains("trained: true"));
+
+    let inputs = Matrix::new(2, 2, vec![0.0, 1.0, 1.0, 0.0]);
+    let targets = Matrix::new(2, 1, vec![1.0, 0.0]);
+    copy.train(&inputs, &targets);
+
+    assert!(!copy.approx_eq(&net, 1e-12));
+    assert!(format!("{:?}", copy).contains("trained: true"));
+}
+
+#[test]
+fn param_groups_split_layers() {
+    let weights = (0..17).map(|x| x as f64).collect::<Vec<_>>();
+    let net = NeuralNet::from_parameters(&[2, 3, 2], MSECriterion, weights);
+    let groups = net.param_groups();
+
+    assert_eq!(groups.len(), 4);
+    assert_eq!(net.num_params(), 17);
+
+    assert_eq!(groups[0].name, "weights");
+    assert_eq!(groups[0].layer, Some(0));
+    assert_eq!((groups[0].values.rows(), groups[0].values.cols()), (2, 3));
+    assert_eq!(groups[0].values.data(), &vec![3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+
+    assert_eq!(groups[1].name, "biases");
+    assert_eq!(groups[1].values.data(), &vec![0.0, 1.0, 2.0]);
+
+    assert_eq!(groups[2].layer, Some(1));
+    assert_eq!((groups[2].values.rows(), groups[2].values.cols()), (3, 2));
+    assert_eq!(groups[3].values.data(), &vec![9.0, 10.0]);
+}
+
+#[test]
+fn layer_weights_reproduce_predictions() {
+    let weights = (0..17).map(|x| x as f64 / 10.0 - 0.8).collect::<Vec<_>>();
+    let net = NeuralNet::from_parameters(&[2, 3, 2], MSECriterion, weights);
+    let inputs = Matrix::new(2, 2, vec![0.5, -1.0, 2.0, 0.0]);
+
+    let mut outputs = inputs.clone();
+    for (w, b) in net.weights_per_layer() {
+        let linear = &outputs * &w;
+        let data = linear.data().iter().enumerate().map(|(k, x)| x + b[k % w.cols()]).collect();
+        outputs = Matrix::new(linear.rows(), w.cols(), data);
+    }
+
+    let expected = net.predict(&inputs);
+    for (x, y) in outputs.data().iter().zip(expected.data()) {
+        assert!((x - y).abs() < 1e-12);
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn get_net_weights_stacks_biases_and_weights() {
+    let weights = (0..17).map(|x| x as f64).collect::<Vec<_>>();
+    let net = NeuralNet::from_parameters(&[2, 3, 2], MSECriterion, weights);
+
+    let stacked = net.get_net_weights(1);
+    let (w, b) = net.layer_weights(1);
+
+    assert_eq!((stacked.rows(), stacked.cols()), (4, 2));
+    assert_eq!(&stacked.data()[..2], b.data().as_slice());
+    assert_eq!(&stacked.data()[2..], w.data().as_slice());
+}
+
+#[test]
+#[should_panic]
+fn layer_weights_out_of_range() {
+    let net = NeuralNet::from_parameters(&[1, 1], MSECriterion, vec![1.0, 2.0]);
+    let _ = net.layer_weights(1);
+}
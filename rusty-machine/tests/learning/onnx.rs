@@ -0,0 +1,193 @@
+use rm::learning::SupModel;
+use rm::learning::lin_reg::LinRegressor;
+use rm::learning::logistic_reg::LogisticRegressor;
+use rm::learning::nnet::{GaussianNLLCriterion, MSECriterion, NeuralNet, SoftmaxCriterion};
+use rm::learning::onnx::{OnnxError, ToOnnx};
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+enum Field {
+    Varint(u64),
+    Bytes(Vec<u8>),
+}
+
+/// Decodes the top level fields of a protocol buffer message.
+fn decode(mut buf: &[u8]) -> Vec<(u64, Field)> {
+    fn varint(buf: &mut &[u8]) -> u64 {
+        let mut x = 0;
+        let mut shift = 0;
+        loop {
+            let b = buf[0];
+            *buf = &buf[1..];
+            x |= ((b & 0x7f) as u64) << shift;
+            if b < 0x80 {
+                return x;
+            }
+            shift += 7;
+        }
+    }
+
+    let mut fields = Vec::new();
+    while !buf.is_empty() {
+        let key = varint(&mut buf);
+        let field = match key & 7 {
+            0 => Field::Varint(varint(&mut buf)),
+            2 => {
+                let len = varint(&mut buf) as usize;
+                let data = buf[..len].to_vec();
+                buf = &buf[len..];
+                Field::Bytes(data)
+            }
+            w => panic!("Unexpected wire type {}", w),
+        };
+        fields.push((key >> 3, field));
+    }
+    fields
+}
+
+fn bytes(fields: &[(u64, Field)], field: u64) -> Vec<Vec<u8>> {
+    fields.iter()
+          .filter_map(|f| match *f {
+              (n, Field::Bytes(ref b)) if n == field => Some(b.clone()),
+              _ => None,
+          })
+          .collect()
+}
+
+fn varints(fields: &[(u64, Field)], field: u64) -> Vec<u64> {
+    fields.iter()
+          .filter_map(|f| match *f {
+              (n, Field::Varint(x)) if n == field => Some(x),
+              _ => None,
+          })
+          .collect()
+}
+
+fn string(b: &[u8]) -> String {
+    String::from_utf8(b.to_vec()).unwrap()
+}
+
+/// The graph of a model proto.
+fn graph(model: &[u8]) -> Vec<(u64, Field)> {
+    let model = decode(model);
+    assert_eq!(varints(&model, 1), vec![7]);
+    decode(&bytes(&model, 7)[0])
+}
+
+/// The op types of the graph nodes and the output of the last node.
+fn ops(graph: &[(u64, Field)]) -> (Vec<String>, String) {
+    let nodes: Vec<_> = bytes(graph, 1).iter().map(|n| decode(n)).collect();
+    let ops = nodes.iter().map(|n| string(&bytes(n, 4)[0])).collect();
+    let last = string(&bytes(nodes.last().unwrap(), 2)[0]);
+    (ops, last)
+}
+
+/// The name, dims and values of each initializer.
+fn initializers(graph: &[(u64, Field)]) -> Vec<(String, Vec<u64>, Vec<f64>)> {
+    bytes(graph, 5).iter()
+                   .map(|t| {
+                       let t = decode(t);
+                       let raw = &bytes(&t, 9)[0];
+                       let values = raw.chunks(8)
+                                       .map(|c| {
+                                           let mut b = [0u8; 8];
+                                           b.copy_from_slice(c);
+                                           f64::from_bits(u64::from_le_bytes(b))
+                                       })
+                                       .collect();
+                       (string(&bytes(&t, 8)[0]), varints(&t, 1), values)
+                   })
+                   .collect()
+}
+
+#[test]
+fn lin_reg_graph() {
+    let inputs = Matrix::new(4, 2, vec![1.0, 0.0, 0.0, 1.0, 1.0, 1.0, 2.0, 1.0]);
+    let targets = Vector::new(vec![3.0, 4.0, 6.0, 8.0]);
+
+    let mut model = LinRegressor::default();
+    model.train(&inputs, &targets);
+    let params = model.parameters().unwrap().into_vec();
+
+    let graph = graph(&model.to_onnx().unwrap());
+    assert_eq!(ops(&graph), (vec!["MatMul".to_string(), "Add".to_string()], "output".to_string()));
+
+    let inits = initializers(&graph);
+    assert_eq!(inits[0], ("weights".to_string(), vec![2, 1], params[1..].to_vec()));
+    assert_eq!(inits[1], ("intercept".to_string(), vec![1], params[..1].to_vec()));
+
+    let io: Vec<String> = bytes(&graph, 11).iter()
+                                           .chain(bytes(&graph, 12).iter())
+                                           .map(|v| string(&bytes(&decode(v), 1)[0]))
+                                           .collect();
+    assert_eq!(io, vec!["input", "output"]);
+}
+
+#[test]
+fn logistic_reg_graph() {
+    let inputs = Matrix::new(4, 1, vec![-2.0, -1.0, 1.0, 2.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+
+    let mut model = LogisticRegressor::default();
+    model.train(&inputs, &targets);
+
+    let graph = graph(&model.to_onnx().unwrap());
+    let (ops, last) = ops(&graph);
+    assert_eq!(ops, vec!["MatMul", "Add", "Sigmoid"]);
+    assert_eq!(last, "output");
+}
+
+#[test]
+fn nnet_graph() {
+    let net = NeuralNet::builder().layers(&[2, 3, 1]).build();
+
+    let graph = graph(&net.to_onnx().unwrap());
+    let (ops, last) = ops(&graph);
+    assert_eq!(ops, vec!["MatMul", "Add", "Sigmoid", "MatMul", "Add", "Sigmoid"]);
+    assert_eq!(last, "output");
+
+    let inits = initializers(&graph);
+    let (weights, bias) = net.layer_weights(1);
+    assert_eq!(inits[2], ("layer1_weights".to_string(), vec![3, 1], weights.into_vec()));
+    assert_eq!(inits[3], ("layer1_bias".to_string(), vec![1], bias.into_vec()));
+}
+
+#[test]
+fn nnet_linear_activation_graph() {
+    let net = NeuralNet::builder().layers(&[2, 2]).criterion(MSECriterion).build();
+
+    let (ops, last) = ops(&graph(&net.to_onnx().unwrap()));
+    assert_eq!(ops, vec!["MatMul", "Add"]);
+    assert_eq!(last, "output");
+}
+
+#[test]
+fn nnet_fused_output_graph() {
+    let net = NeuralNet::builder().layers(&[2, 3]).criterion(SoftmaxCriterion).build();
+    let (softmax_ops, _) = ops(&graph(&net.to_onnx().unwrap()));
+    assert_eq!(softmax_ops, vec!["MatMul", "Add", "Softmax"]);
+
+    // The criterion names its own output operator.
+    let net = NeuralNet::builder().layers(&[2, 2]).criterion(GaussianNLLCriterion).build();
+    let (ops, _) = ops(&graph(&net.to_onnx().unwrap()));
+    assert_eq!(ops, vec!["MatMul", "Add", "Identity"]);
+}
+
+#[test]
+fn nnet_residual_graph() {
+    let net = NeuralNet::builder().layers(&[2, 2, 2, 1]).residual(0, 2).build();
+
+    let (ops, last) = ops(&graph(&net.to_onnx().unwrap()));
+    assert_eq!(ops,
+               vec!["MatMul", "Add", "Sigmoid", "MatMul", "Add", "Sigmoid", "Add", "MatMul",
+                    "Add", "Sigmoid"]);
+    assert_eq!(last, "output");
+}
+
+#[test]
+fn untrained_model() {
+    match LinRegressor::default().to_onnx() {
+        Err(OnnxError::Untrained) => {}
+        _ => panic!("Expected an untrained model error."),
+    }
+}
@@ -0,0 +1,794 @@
+use std::env;
+use std::fs;
+use std::io::{Cursor, ErrorKind};
+use std::sync::{Arc, Mutex};
+
+use rm::data::csv::{CsvOptions, CsvSource};
+use rm::data::source::MemorySource;
+use rm::learning::SupModel;
+use rm::learning::lin_reg::LinRegressor;
+use rm::learning::logistic_reg::LogisticRegressor;
+use rm::learning::nnet::{MSECriterion, NeuralNet};
+use rm::learning::optim::{OptimAlgorithm, Optimizable};
+use rm::learning::optim::callback::{Callback, Signal};
+use rm::learning::optim::diagnostics::{NonFiniteError, NonFiniteValue};
+use rm::learning::optim::early_stopping::{validation_split, EarlyStopping};
+use rm::learning::optim::checkpoint::{load_parameters, read_parameters, save_parameters,
+                                      write_parameters, Checkpoint};
+use rm::learning::optim::fmincg::{ConjugateGD, ConjugateUpdate};
+use rm::learning::optim::grad_desc::{AdaDelta, AdaGrad, GradientDesc, StochasticGD};
+use rm::learning::optim::line_search::{LineSearch, Point};
+use rm::learning::optim::logging::TrainingLog;
+use rm::learning::optim::newton::Newton;
+use rm::learning::toolkit::regularization::L2;
+use rm::linalg::Metric;
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+#[test]
+fn gradient_desc_logs_each_iteration() {
+    let inputs = Matrix::new(4, 1, vec![-2.0, -1.0, 1.0, 2.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+
+    let logs: Arc<Mutex<Vec<TrainingLog>>> = Arc::new(Mutex::new(Vec::new()));
+    let sink = logs.clone();
+    let gd = GradientDesc::new(0.5, 10).logger(move |log| sink.lock().unwrap().push(*log));
+
+    let mut model = LogisticRegressor::new(gd);
+    model.train(&inputs, &targets);
+
+    let logs = logs.lock().unwrap();
+    assert_eq!(logs.len(), 10);
+    for (i, log) in logs.iter().enumerate() {
+        assert_eq!(log.iter, i);
+        assert_eq!(log.learning_rate, 0.5);
+        assert!(log.grad_norm > 0f64);
+    }
+    assert!(logs[9].cost < logs[0].cost);
+}
+
+#[test]
+fn stochastic_gd_logs_each_pass() {
+    let inputs = Matrix::new(4, 2, vec![0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0]);
+    let targets = Matrix::new(4, 1, vec![0.0, 1.0, 1.0, 0.0]);
+
+    let logs: Arc<Mutex<Vec<TrainingLog>>> = Arc::new(Mutex::new(Vec::new()));
+    let sink = logs.clone();
+    let sgd = StochasticGD::new(0.1, 0.3, 5).logger(move |log| sink.lock().unwrap().push(*log));
+
+    let mut model = NeuralNet::builder().layers(&[2, 3, 1]).optimizer(sgd).build();
+    model.train(&inputs, &targets);
+
+    let logs = logs.lock().unwrap();
+    assert_eq!(logs.len(), 5);
+    assert_eq!(logs[4].iter, 4);
+    assert!((logs[0].learning_rate - 0.09).abs() < 1e-12);
+    assert!(logs.iter().all(|log| log.cost.is_finite() && log.grad_norm.is_finite()));
+}
+
+#[derive(Clone)]
+struct StopAfter {
+    epochs: usize,
+}
+
+impl Callback for StopAfter {
+    fn on_epoch_end(&mut self, log: &TrainingLog, _: &[f64]) -> Signal {
+        if log.iter + 1 >= self.epochs {
+            Signal::Stop
+        } else {
+            Signal::Continue
+        }
+    }
+}
+
+#[test]
+fn callback_receives_current_params() {
+    let inputs = Matrix::new(4, 1, vec![-2.0, -1.0, 1.0, 2.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+
+    let last: Arc<Mutex<Vec<f64>>> = Arc::new(Mutex::new(Vec::new()));
+    let sink = last.clone();
+    let gd = GradientDesc::new(0.5, 10).callback(move |_: &TrainingLog, params: &[f64]| {
+        *sink.lock().unwrap() = params.to_vec();
+        Signal::Continue
+    });
+
+    let mut model = LogisticRegressor::new(gd);
+    model.train(&inputs, &targets);
+
+    assert_eq!(*last.lock().unwrap(), model.parameters().unwrap().into_vec());
+}
+
+#[test]
+fn callback_stops_gradient_desc() {
+    let inputs = Matrix::new(4, 1, vec![-2.0, -1.0, 1.0, 2.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+
+    let mut stopped = LogisticRegressor::new(GradientDesc::new(0.5, 100)
+                                                 .callback(StopAfter { epochs: 3 }));
+    stopped.train(&inputs, &targets);
+
+    let mut short = LogisticRegressor::new(GradientDesc::new(0.5, 3));
+    short.train(&inputs, &targets);
+
+    assert_eq!(stopped.parameters().unwrap().into_vec(),
+               short.parameters().unwrap().into_vec());
+}
+
+#[test]
+fn cloned_optimizer_copies_callback_state() {
+    // The inputs include the bias column.
+    let inputs = Matrix::new(4, 2, vec![1.0, -2.0, 1.0, -1.0, 1.0, 1.0, 1.0, 2.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+    let model = LogisticRegressor::default();
+
+    let mut seen = 0;
+    let gd = GradientDesc::new(0.5, 100).callback(move |_: &TrainingLog, _: &[f64]| {
+        seen += 1;
+        if seen >= 3 { Signal::Stop } else { Signal::Continue }
+    });
+    let copy = gd.clone();
+
+    let first = gd.optimize(&model, &[0.0, 0.0], &inputs, &targets);
+    let second = copy.optimize(&model, &[0.0, 0.0], &inputs, &targets);
+
+    assert_eq!(first.report.iters, 3);
+    assert_eq!(second.report.iters, 3);
+}
+
+#[test]
+fn callback_stops_stochastic_gd() {
+    let inputs = Matrix::new(4, 2, vec![0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0]);
+    let targets = Matrix::new(4, 1, vec![0.0, 1.0, 1.0, 0.0]);
+
+    let epochs = Arc::new(Mutex::new(0));
+    let counter = epochs.clone();
+    let sgd = StochasticGD::new(0.1, 0.3, 50)
+                  .callback(StopAfter { epochs: 2 })
+                  .callback(move |_: &TrainingLog, _: &[f64]| {
+                      *counter.lock().unwrap() += 1;
+                      Signal::Continue
+                  });
+
+    let mut model = NeuralNet::builder().layers(&[2, 3, 1]).optimizer(sgd).build();
+    model.train(&inputs, &targets);
+
+    // Every callback runs on the final epoch, even after one asks to stop.
+    assert_eq!(*epochs.lock().unwrap(), 2);
+}
+
+#[test]
+fn callback_stops_conjugate_gd() {
+    // The inputs include the bias column.
+    let inputs = Matrix::new(4, 2, vec![1.0, -2.0, 1.0, -1.0, 1.0, 1.0, 1.0, 2.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+    let model = LogisticRegressor::default();
+
+    let epochs = Arc::new(Mutex::new(0));
+    let counter = epochs.clone();
+    let cgd = ConjugateGD::default()
+                  .callback(StopAfter { epochs: 1 })
+                  .callback(move |_: &TrainingLog, _: &[f64]| {
+                      *counter.lock().unwrap() += 1;
+                      Signal::Continue
+                  });
+
+    cgd.optimize(&model, &[0.5, 0.5], &inputs, &targets);
+
+    assert_eq!(*epochs.lock().unwrap(), 1);
+}
+
+#[test]
+fn conjugate_gd_updates_converge() {
+    let inputs = Matrix::new(5, 2, vec![1.0, 0.0, 2.0, 1.0, 3.0, 5.0, 4.0, 2.0, 0.5, 3.0]);
+    let targets = Vector::new(vec![1.0, 3.0, 10.0, 6.0, 4.0]);
+
+    let mut exact = LinRegressor::default();
+    exact.train(&inputs, &targets);
+    let expected = exact.parameters().unwrap();
+    let full_inputs = Matrix::ones(5, 1).hcat(&inputs);
+
+    let updates = [ConjugateUpdate::FletcherReeves,
+                   ConjugateUpdate::PolakRibiere,
+                   ConjugateUpdate::PolakRibierePlus];
+    for &update in &updates {
+        for &restart in &[0, 3] {
+            let cgd = ConjugateGD::default().update(update).restart(restart);
+            let params = cgd.optimize(&exact, &[0.0; 3], &full_inputs, &targets).params;
+
+            for (p, e) in params.iter().zip(expected.data().iter()) {
+                assert!((p - e).abs() < 1e-4, "{:?} with restart {}", update, restart);
+            }
+        }
+    }
+}
+
+#[test]
+fn line_search_decreases_cost_with_large_steps() {
+    let inputs = Matrix::new(6, 1, vec![1.0, 2.0, 4.0, 3.0, 5.0, 6.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+
+    let logs: Arc<Mutex<Vec<TrainingLog>>> = Arc::new(Mutex::new(Vec::new()));
+    let sink = logs.clone();
+    let gd = GradientDesc::new(100.0, 50)
+        .line_search(LineSearch::armijo())
+        .logger(move |log| sink.lock().unwrap().push(*log));
+
+    let mut model = LogisticRegressor::new(gd);
+    model.train(&inputs, &targets);
+
+    let logs = logs.lock().unwrap();
+    for pair in logs.windows(2) {
+        assert!(pair[1].cost <= pair[0].cost);
+    }
+    assert!(logs.iter().all(|log| log.learning_rate <= 100.0));
+    assert!(logs[49].cost < logs[0].cost);
+}
+
+#[test]
+fn wolfe_line_search_grows_short_steps() {
+    let inputs = Matrix::new(3, 2, vec![1.0, 1.0, 1.0, 2.0, 1.0, 3.0]);
+    let targets = Vector::new(vec![2.0, 4.0, 6.0]);
+    let model = LinRegressor::default();
+
+    let from = Point::new(&model, vec![0.0, 0.0], &inputs, &targets);
+    let dir: Vec<f64> = from.grad.iter().map(|g| -g).collect();
+
+    let (armijo_step, _) = LineSearch::armijo().search(&model, &inputs, &targets, &from, &dir, 1e-3);
+    assert_eq!(armijo_step, 1e-3);
+
+    let (wolfe_step, to) = LineSearch::wolfe().search(&model, &inputs, &targets, &from, &dir, 1e-3);
+    assert!(wolfe_step > 1e-3);
+    assert!(to.cost < from.cost);
+}
+
+#[test]
+fn line_search_fails_along_ascent_direction() {
+    let inputs = Matrix::new(3, 2, vec![1.0, 1.0, 1.0, 2.0, 1.0, 3.0]);
+    let targets = Vector::new(vec![2.0, 4.0, 6.0]);
+    let model = LinRegressor::default();
+
+    let from = Point::new(&model, vec![0.0, 0.0], &inputs, &targets);
+    let (step, to) = LineSearch::armijo().search(&model, &inputs, &targets, &from, &from.grad, 1.0);
+    assert_eq!(step, 0.0);
+    assert_eq!(to.params, from.params);
+}
+
+/// A linear problem where the second feature is rarely non-zero.
+fn sparse_problem() -> (Matrix<f64>, Matrix<f64>) {
+    let mut inputs = Vec::new();
+    let mut targets = Vec::new();
+    for i in 0..40 {
+        let x1 = (i % 5) as f64 / 4.0;
+        let x2 = if i % 10 == 0 { 1.0 } else { 0.0 };
+        inputs.push(x1);
+        inputs.push(x2);
+        targets.push(2.0 * x1 + 3.0 * x2);
+    }
+    (Matrix::new(40, 2, inputs), Matrix::new(40, 1, targets))
+}
+
+#[test]
+fn adaptive_optimizers_fit_sparse_features() {
+    let (inputs, targets) = sparse_problem();
+    let net = NeuralNet::builder().layers(&[2, 1]).criterion(MSECriterion).build();
+    let start = vec![0.0; 3];
+
+    let adagrad = AdaGrad::new(0.5, 100).batch_size(4);
+    let adadelta = AdaDelta::default().batch_size(4);
+    let sgd = StochasticGD::with_batch_size(0.0, 0.2, 100, 4);
+
+    let ada_params = adagrad.optimize(&net, &start, &inputs, &targets).params;
+    let delta_params = adadelta.optimize(&net, &start, &inputs, &targets).params;
+    let sgd_params = sgd.optimize(&net, &start, &inputs, &targets).params;
+
+    let (start_cost, _) = net.compute_grad(&start, &inputs, &targets);
+    let (ada_cost, _) = net.compute_grad(&ada_params, &inputs, &targets);
+    let (delta_cost, _) = net.compute_grad(&delta_params, &inputs, &targets);
+    let (sgd_cost, _) = net.compute_grad(&sgd_params, &inputs, &targets);
+
+    assert!(ada_cost < 1e-3 * start_cost);
+    assert!(delta_cost < 0.5 * start_cost);
+    assert!(ada_cost < sgd_cost);
+}
+
+#[test]
+fn adaptive_optimizers_apply_penalty() {
+    let (inputs, targets) = sparse_problem();
+    let net = NeuralNet::builder().layers(&[2, 1]).criterion(MSECriterion).build();
+    let start = vec![0.0; 3];
+
+    let weight_norm = |params: &[f64]| params[1..].iter().map(|w| w * w).sum::<f64>();
+
+    let plain = AdaGrad::new(0.5, 100).batch_size(4).optimize(&net, &start, &inputs, &targets);
+    let penalized = AdaGrad::new(0.5, 100)
+        .batch_size(4)
+        .penalty(L2::new(1.0))
+        .optimize(&net, &start, &inputs, &targets);
+    assert!(weight_norm(&penalized.params) < weight_norm(&plain.params));
+    assert!(penalized.report.cost > net.compute_grad(&penalized.params, &inputs, &targets).0);
+
+    let plain = AdaDelta::default().batch_size(4).optimize(&net, &start, &inputs, &targets);
+    let penalized = AdaDelta::default()
+        .batch_size(4)
+        .penalty(L2::new(1.0))
+        .optimize(&net, &start, &inputs, &targets);
+    assert!(weight_norm(&penalized.params) < weight_norm(&plain.params));
+}
+
+#[test]
+fn adaptive_optimizers_log_each_pass() {
+    let (inputs, targets) = sparse_problem();
+    let net = NeuralNet::builder().layers(&[2, 1]).criterion(MSECriterion).build();
+
+    let logs: Arc<Mutex<Vec<TrainingLog>>> = Arc::new(Mutex::new(Vec::new()));
+    let sink = logs.clone();
+    let adagrad = AdaGrad::new(0.5, 5).logger(move |log| sink.lock().unwrap().push(*log));
+    adagrad.optimize(&net, &[0.0; 3], &inputs, &targets);
+    assert_eq!(logs.lock().unwrap().len(), 5);
+    assert!(logs.lock().unwrap().iter().all(|log| log.learning_rate == 0.5));
+
+    let adadelta = AdaDelta::new(0.9, 10).callback(StopAfter { epochs: 3 });
+    let params = adadelta.optimize(&net, &[0.0; 3], &inputs, &targets).params;
+    assert!(params.iter().all(|p| p.is_finite()));
+}
+
+#[test]
+#[should_panic]
+fn adadelta_invalid_decay() {
+    let _ = AdaDelta::new(1.0, 10);
+}
+
+#[test]
+fn parameters_round_trip() {
+    let path = env::temp_dir().join("rusty_machine_params_round_trip.ckpt");
+    let params = vec![0.1, -2.5e-8, 1.0 / 3.0, 123456.789];
+
+    save_parameters(&path, &params).unwrap();
+    let loaded = load_parameters(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, params);
+}
+
+#[test]
+fn parameters_round_trip_in_memory() {
+    let params = vec![0.25, -1e300, 7.0];
+
+    let mut buf = Vec::new();
+    write_parameters(&mut buf, &params).unwrap();
+
+    assert_eq!(read_parameters(&buf[..]).unwrap(), params);
+    assert_eq!(read_parameters(&buf[..buf.len() - 4]).unwrap_err().kind(),
+               ErrorKind::InvalidData);
+}
+
+#[test]
+fn load_parameters_rejects_other_files() {
+    let path = env::temp_dir().join("rusty_machine_params_invalid.ckpt");
+    fs::write(&path, "1.0\n2.0\n").unwrap();
+
+    let err = load_parameters(&path).unwrap_err();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn checkpoint_resumes_neural_net() {
+    let path = env::temp_dir().join("rusty_machine_nnet_checkpoint.ckpt");
+    let inputs = Matrix::new(4, 2, vec![0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0]);
+    let targets = Matrix::new(4, 1, vec![0.0, 1.0, 1.0, 0.0]);
+
+    let sgd = StochasticGD::new(0.1, 0.3, 10).callback(Checkpoint::every(&path, 5));
+    let mut model = NeuralNet::builder().layers(&[2, 3, 1]).optimizer(sgd).build();
+    model.train(&inputs, &targets);
+
+    // The last epoch is a multiple of 5, so the checkpoint holds the final weights.
+    let saved = load_parameters(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(saved, model.parameters().to_vec());
+
+    let mut resumed = NeuralNet::builder().layers(&[2, 3, 1]).build();
+    resumed.set_parameters(saved);
+    assert_eq!(resumed.predict(&inputs).data(), model.predict(&inputs).data());
+}
+
+#[test]
+fn checkpoint_saves_best_score() {
+    let path = env::temp_dir().join("rusty_machine_best_checkpoint.ckpt");
+    let inputs = Matrix::new(4, 1, vec![-2.0, -1.0, 1.0, 2.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+
+    // The score peaks when the first parameter is closest to 0.2.
+    let best: Arc<Mutex<(f64, Vec<f64>)>> = Arc::new(Mutex::new((f64::NEG_INFINITY, vec![])));
+    let sink = best.clone();
+    let gd = GradientDesc::new(0.5, 20)
+                 .callback(Checkpoint::best(&path, |params: &[f64]| -(params[0] - 0.2).abs()))
+                 .callback(move |_: &TrainingLog, params: &[f64]| {
+                     let score = -(params[0] - 0.2).abs();
+                     let mut best = sink.lock().unwrap();
+                     if score > best.0 {
+                         *best = (score, params.to_vec());
+                     }
+                     Signal::Continue
+                 });
+
+    let mut model = LogisticRegressor::new(gd);
+    model.train(&inputs, &targets);
+
+    let saved = load_parameters(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(saved, best.lock().unwrap().1);
+}
+
+#[test]
+fn newton_solves_linear_regression_in_one_step() {
+    let inputs = Matrix::new(4, 2, vec![1.0, 0.0, 2.0, 1.0, 3.0, 5.0, 4.0, 2.0]);
+    let targets = Vector::new(vec![1.0, 3.0, 10.0, 6.0]);
+
+    let mut exact = LinRegressor::default();
+    exact.train(&inputs, &targets);
+
+    let logs: Arc<Mutex<Vec<TrainingLog>>> = Arc::new(Mutex::new(Vec::new()));
+    let sink = logs.clone();
+    let newton = Newton::default().logger(move |log| sink.lock().unwrap().push(*log));
+
+    let full_inputs = Matrix::ones(4, 1).hcat(&inputs);
+    let params = newton.optimize(&exact, &[0.0; 3], &full_inputs, &targets).params;
+
+    for (p, e) in params.iter().zip(exact.parameters().unwrap().data().iter()) {
+        assert!((p - e).abs() < 1e-8);
+    }
+    assert_eq!(logs.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn newton_matches_gradient_desc_for_logistic() {
+    let inputs = Matrix::new(6, 1, vec![1.0, 2.0, 4.0, 3.0, 5.0, 6.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+
+    let logs: Arc<Mutex<Vec<TrainingLog>>> = Arc::new(Mutex::new(Vec::new()));
+    let sink = logs.clone();
+    let newton = Newton::new(50, 1e-10).logger(move |log| sink.lock().unwrap().push(*log));
+
+    let mut model = LogisticRegressor::default();
+    model.train_with_newton(&inputs, &targets, &newton);
+    let newton_params = model.parameters().unwrap();
+    assert!(logs.lock().unwrap().len() < 15);
+
+    let mut gd_model = LogisticRegressor::new(GradientDesc::new(0.5, 20000));
+    gd_model.train(&inputs, &targets);
+    let gd_params = gd_model.parameters().unwrap();
+
+    for (a, b) in newton_params.data().iter().zip(gd_params.data().iter()) {
+        assert!((a - b).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn newton_stops_on_non_finite_inputs() {
+    let inputs = Matrix::new(2, 1, vec![f64::NAN, 1.0]);
+    let targets = Vector::new(vec![0.0, 1.0]);
+
+    let mut model = LogisticRegressor::default();
+    model.train_with_newton(&inputs, &targets, &Newton::new(5, 1e-8));
+
+    // The line search finds no finite step.
+    let report = model.training_report().unwrap();
+    assert_eq!(report.iters, 0);
+    assert!(!report.converged);
+}
+
+#[test]
+#[should_panic]
+fn newton_requires_hessian() {
+    let inputs = Matrix::new(2, 2, vec![0.0, 1.0, 1.0, 0.0]);
+    let targets = Matrix::new(2, 1, vec![1.0, 0.0]);
+
+    let model = NeuralNet::default(&[2, 1]);
+    let _ = Newton::default().optimize(&model, &[0.0; 3], &inputs, &targets);
+}
+
+#[test]
+fn optimizers_report_iterations_and_convergence() {
+    let inputs = Matrix::new(4, 2, vec![1.0, 0.0, 2.0, 1.0, 3.0, 5.0, 4.0, 2.0]);
+    let targets = Vector::new(vec![1.0, 3.0, 10.0, 6.0]);
+    let full_inputs = Matrix::ones(4, 1).hcat(&inputs);
+    let model = LinRegressor::default();
+
+    let newton = Newton::new(20, 1e-8).optimize(&model, &[0.0; 3], &full_inputs, &targets);
+    assert!(newton.report.converged);
+    assert!(newton.report.iters < 20);
+    assert!(newton.report.grad_norm < 1e-8);
+
+    let gd = GradientDesc::new(0.01, 30).optimize(&model, &[0.0; 3], &full_inputs, &targets);
+    assert!(!gd.report.converged);
+    assert_eq!(gd.report.iters, 30);
+    let (cost, _) = model.compute_grad(&gd.params, &full_inputs, &targets);
+    assert_eq!(gd.report.cost, cost);
+
+    let cg = ConjugateGD::default().optimize(&model, &[0.0; 3], &full_inputs, &targets);
+    assert!(cg.report.converged);
+    assert!(cg.report.grad_norm < 1e-8);
+    let (cost, grad) = model.compute_grad(&cg.params, &full_inputs, &targets);
+    assert_eq!(cg.report.cost, cost);
+    assert_eq!(cg.report.grad_norm, Vector::new(grad).norm());
+
+    let mut loose = ConjugateGD::default();
+    loose.tol = 0.0;
+    let failed = loose.optimize(&model, &[0.0; 3], &full_inputs, &targets);
+    assert!(!failed.report.converged);
+
+    let stopped = GradientDesc::new(0.01, 30)
+        .callback(StopAfter { epochs: 4 })
+        .optimize(&model, &[0.0; 3], &full_inputs, &targets);
+    assert_eq!(stopped.report.iters, 4);
+}
+
+#[test]
+fn models_expose_training_report() {
+    let inputs = Matrix::new(4, 1, vec![-2.0, -1.0, 1.0, 2.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+
+    let mut model = LogisticRegressor::new(GradientDesc::new(0.5, 25));
+    assert!(model.training_report().is_none());
+    model.train(&inputs, &targets);
+    let report = model.training_report().unwrap();
+    assert_eq!(report.iters, 25);
+    assert!(report.cost.is_finite() && report.grad_norm > 0.0);
+
+    let mut lin = LinRegressor::default();
+    lin.train_with_optimization(&inputs, &targets);
+    assert_eq!(lin.training_report().unwrap().iters, 100);
+    lin.train(&inputs, &targets);
+    assert!(lin.training_report().is_none());
+
+    let net_targets = Matrix::new(4, 1, vec![0.0, 0.0, 1.0, 1.0]);
+    let mut net = NeuralNet::builder()
+        .layers(&[1, 2, 1])
+        .optimizer(StochasticGD::new(0.1, 0.3, 7))
+        .build();
+    assert!(net.training_report().is_none());
+    net.train(&inputs, &net_targets);
+    assert_eq!(net.training_report().unwrap().iters, 7);
+}
+
+#[test]
+fn check_finite_stops_diverging_training() {
+    let inputs = Matrix::new(2, 2, vec![1.0, 1e200, 1.0, 2e200]);
+    let targets = Vector::new(vec![1.0, 2.0]);
+    let model = LinRegressor::default();
+
+    let unchecked = GradientDesc::new(0.1, 50).optimize(&model, &[0.0, 0.0], &inputs, &targets);
+    assert!(unchecked.report.error.is_none());
+    assert!(unchecked.params.iter().any(|w| !w.is_finite()));
+
+    let checked = GradientDesc::new(0.1, 50)
+        .check_finite(true)
+        .optimize(&model, &[0.0, 0.0], &inputs, &targets);
+    let error = checked.report.error.unwrap();
+    assert!(checked.params.iter().all(|w| w.is_finite()));
+    assert!(checked.report.iters < 50);
+    assert_eq!(error.iter, checked.report.iters);
+    assert_eq!(error.layer, None);
+
+    let net_inputs = Matrix::new(2, 1, vec![1e200, 2e200]);
+    let net_targets = Matrix::new(2, 1, vec![1.0, 2.0]);
+    let net = NeuralNet::builder().layers(&[1, 1]).criterion(MSECriterion).build();
+    let sgd = StochasticGD::new(0.1, 0.3, 50).check_finite(true);
+    let result = sgd.optimize(&net, net.parameters(), &net_inputs, &net_targets);
+    assert!(result.report.error.is_some());
+    assert!(result.params.iter().all(|w| w.is_finite()));
+}
+
+/// A quadratic with its minimum past a cliff where the cost is NaN.
+struct Cliff;
+
+impl Optimizable for Cliff {
+    type Inputs = Matrix<f64>;
+    type Targets = Matrix<f64>;
+
+    fn compute_grad(&self, params: &[f64], _: &Matrix<f64>, _: &Matrix<f64>) -> (f64, Vec<f64>) {
+        let x = params[0];
+        if x > 2.0 {
+            (f64::NAN, vec![f64::NAN])
+        } else {
+            ((x - 5.0) * (x - 5.0), vec![2.0 * (x - 5.0)])
+        }
+    }
+}
+
+#[test]
+fn conjugate_gd_check_finite_stops_at_cliff() {
+    let data = Matrix::zeros(1, 1);
+
+    // Unchecked, the line search fails at the cliff without a report.
+    let unchecked = ConjugateGD::default().optimize(&Cliff, &[0.0], &data, &data);
+    assert!(unchecked.report.error.is_none());
+
+    // The first line search extrapolates past the cliff.
+    let checked = ConjugateGD::default()
+        .check_finite(true)
+        .optimize(&Cliff, &[0.0], &data, &data);
+    assert_eq!(checked.report.error,
+               Some(NonFiniteError {
+                   iter: 0,
+                   value: NonFiniteValue::Cost,
+                   layer: None,
+               }));
+    assert_eq!(checked.params, vec![0.0]);
+    assert_eq!(checked.report.cost, 25.0);
+}
+
+#[test]
+fn non_finite_errors_name_the_layer() {
+    let net = NeuralNet::builder().layers(&[2, 3, 1]).build();
+
+    // The first layer has (2 + 1) * 3 weights and the second (3 + 1) * 1.
+    assert_eq!(net.param_layer(0), Some(0));
+    assert_eq!(net.param_layer(8), Some(0));
+    assert_eq!(net.param_layer(9), Some(1));
+    assert_eq!(net.param_layer(12), Some(1));
+    assert_eq!(net.param_layer(13), None);
+
+    let error = NonFiniteError {
+        iter: 3,
+        value: NonFiniteValue::Gradient(9),
+        layer: Some(1),
+    };
+    assert_eq!(error.to_string(),
+               "Non-finite gradient of parameter 9 in layer 1 at iteration 3.");
+}
+
+/// Records the samples in each mini-batch, identified by their input.
+struct BatchRecorder {
+    batches: Mutex<Vec<Vec<f64>>>,
+}
+
+impl Optimizable for BatchRecorder {
+    type Inputs = Matrix<f64>;
+    type Targets = Matrix<f64>;
+
+    fn compute_grad(&self, params: &[f64], inputs: &Matrix<f64>, _: &Matrix<f64>) -> (f64, Vec<f64>) {
+        self.batches.lock().unwrap().push(inputs.data().clone());
+        (0f64, vec![0f64; params.len()])
+    }
+}
+
+fn recorded_passes(sgd: StochasticGD, n: usize) -> Vec<Vec<f64>> {
+    let model = BatchRecorder { batches: Mutex::new(Vec::new()) };
+    let inputs = Matrix::new(n, 1, (0..n).map(|i| i as f64).collect::<Vec<_>>());
+    let targets = Matrix::zeros(n, 1);
+
+    sgd.optimize(&model, &[0.0], &inputs, &targets);
+
+    // Skip the initial step and the final evaluation on all the data.
+    let batches = model.batches.into_inner().unwrap();
+    let batches = &batches[1..batches.len() - 1];
+    batches.chunks(batches.len() / 3).map(|pass| pass.concat()).collect()
+}
+
+#[test]
+fn stochastic_gd_shuffles_each_pass() {
+    let passes = recorded_passes(StochasticGD::with_batch_size(0.1, 0.1, 3, 4), 10);
+
+    for pass in &passes {
+        let mut sorted = pass.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(sorted, (0..10).map(|i| i as f64).collect::<Vec<_>>());
+    }
+    assert!(passes[0] != passes[1] || passes[1] != passes[2]);
+
+    let ordered = recorded_passes(StochasticGD::with_batch_size(0.1, 0.1, 3, 4).shuffle(false), 10);
+    for pass in &ordered {
+        assert_eq!(*pass, (0..10).map(|i| i as f64).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn optimizers_train_from_data_sources() {
+    let text = "0,0,0\n0,1,1\n1,0,1\n1,1,2\n2,1,3\n";
+    let inputs = Matrix::new(5, 2, vec![0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0, 2.0, 1.0]);
+    let targets = Matrix::new(5, 1, vec![0.0, 1.0, 1.0, 2.0, 3.0]);
+
+    let net = NeuralNet::from_parameters(&[2, 1], MSECriterion, vec![0.1, 0.2, 0.3]);
+    let start = net.parameters().to_vec();
+
+    let sgd = StochasticGD::with_batch_size(0.1, 0.3, 10, 2).shuffle(false);
+    let in_memory = sgd.optimize(&net, &start, &inputs, &targets);
+
+    let mut csv = CsvSource::new(Cursor::new(text), CsvOptions::default().target(2), 2);
+    let from_csv = sgd.optimize_source(&net, &start, &mut csv).unwrap();
+    assert_eq!(from_csv.params, in_memory.params);
+    assert_eq!(from_csv.report.iters, 10);
+
+    let adagrad = AdaGrad::new(0.5, 10).shuffle(false);
+    let mut memory = MemorySource::new(&inputs, &targets, 1);
+    let from_memory = adagrad.optimize_source(&net, &start, &mut memory).unwrap();
+    assert_eq!(from_memory.params, adagrad.optimize(&net, &start, &inputs, &targets).params);
+    assert!(from_memory.report.cost < net.compute_grad(&start, &inputs, &targets).0);
+}
+
+#[test]
+fn regressors_train_from_vector_sources() {
+    // A column of ones for the intercept, then the input.
+    let inputs = Matrix::new(6, 2, vec![1.0, 0.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0, 1.0, 4.0, 1.0, 5.0]);
+    let targets = Vector::new(vec![1.0, 3.0, 5.0, 7.0, 9.0, 11.0]);
+    let text = "1,0,1\n1,1,3\n1,2,5\n1,3,7\n1,4,9\n1,5,11\n";
+
+    let lin_reg = LinRegressor::default();
+    let sgd = StochasticGD::with_batch_size(0.5, 0.05, 500, 2).shuffle(false);
+
+    let mut memory = MemorySource::new(&inputs, &targets, 2);
+    let from_memory = sgd.optimize_source(&lin_reg, &[0.0, 0.0], &mut memory).unwrap();
+    let mut csv: CsvSource<_, Vector<f64>> =
+        CsvSource::new(Cursor::new(text), CsvOptions::default().target(2), 2);
+    let from_csv = sgd.optimize_source(&lin_reg, &[0.0, 0.0], &mut csv).unwrap();
+
+    assert_eq!(from_csv.params, from_memory.params);
+    assert!((from_csv.params[0] - 1.0).abs() < 0.1);
+    assert!((from_csv.params[1] - 2.0).abs() < 0.1);
+
+    let classes = Vector::new(vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+    let log_reg = LogisticRegressor::default();
+    let start = [0.0, 0.0];
+    let mut memory = MemorySource::new(&inputs, &classes, 3);
+    let result = AdaGrad::new(0.5, 100).optimize_source(&log_reg, &start, &mut memory).unwrap();
+    assert!(result.report.cost < log_reg.compute_grad(&start, &inputs, &classes).0);
+    assert!(result.params[1] > 0.0);
+}
+
+#[test]
+fn validation_split_keeps_every_class() {
+    // Sorted one-hot targets, where a tail split would hold out only
+    // the second class.
+    let mut data = Vec::new();
+    for i in 0..20 {
+        data.extend_from_slice(if i < 17 { &[1.0, 0.0] } else { &[0.0, 1.0] });
+    }
+    let targets = Matrix::new(20, 2, data);
+
+    let (train, validation) = validation_split(&targets, 0.2);
+    assert_eq!(train.len() + validation.len(), 20);
+    assert!(validation.iter().any(|&i| i < 17));
+    assert!(validation.iter().any(|&i| i >= 17));
+    assert!(train.iter().any(|&i| i >= 17));
+
+    // Regression targets are spread over the rows.
+    let targets = Matrix::new(10, 1, (0..10).map(|x| x as f64 * 0.5).collect::<Vec<_>>());
+    let (_, validation) = validation_split(&targets, 0.2);
+    assert_eq!(validation, vec![2, 7]);
+}
+
+#[test]
+fn early_stopping_waits_for_patience() {
+    let inputs = Matrix::new(8, 1, vec![-4.0, -3.0, -2.0, -1.0, 1.0, 2.0, 3.0, 4.0]);
+    let targets = Matrix::new(8, 1, vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]);
+    let net = NeuralNet::from_parameters(&[1, 1], MSECriterion, vec![0.1, 0.2]);
+    let stopping = EarlyStopping::new(0.25, 3);
+
+    // Without a learning rate the validation cost never improves after
+    // the first pass.
+    let sgd = StochasticGD::new(0.1, 0.0, 100).early_stopping(stopping);
+    assert_eq!(sgd.optimize(&net, &[0.1, 0.2], &inputs, &targets).report.iters, 4);
+
+    let adagrad = AdaGrad::new(0.0, 100).early_stopping(stopping);
+    assert_eq!(adagrad.optimize(&net, &[0.1, 0.2], &inputs, &targets).report.iters, 4);
+
+    let trained = AdaGrad::new(0.5, 100).early_stopping(stopping);
+    let result = trained.optimize(&net, &[0.1, 0.2], &inputs, &targets);
+    assert!(result.report.cost < net.compute_grad(&[0.1, 0.2], &inputs, &targets).0);
+}
+
+#[test]
+#[should_panic]
+fn early_stopping_needs_validation_rows() {
+    let inputs = Matrix::new(2, 1, vec![0.0, 1.0]);
+    let targets = Matrix::new(2, 1, vec![0.0, 1.0]);
+    let net = NeuralNet::from_parameters(&[1, 1], MSECriterion, vec![0.1, 0.2]);
+
+    // Neither class has enough rows to hold one out.
+    let sgd = StochasticGD::new(0.1, 0.1, 10).early_stopping(EarlyStopping::new(0.1, 3));
+    sgd.optimize(&net, &[0.1, 0.2], &inputs, &targets);
+}
@@ -0,0 +1,58 @@
+use rm::data::transforms::Transformer;
+use rm::data::transforms::minmax::MinMaxScaler;
+use rm::data::transforms::standardize::StandardScaler;
+use rm::learning::SupModel;
+use rm::learning::lin_reg::LinRegressor;
+use rm::learning::pipeline::Pipeline;
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+#[test]
+fn pipeline_replays_transforms() {
+    let inputs = Matrix::new(4, 2, vec![1.0, 100.0, 2.0, 300.0, 3.0, 200.0, 4.0, 400.0]);
+    let targets = Vector::new(vec![1.0, 2.0, 3.0, 4.0]);
+
+    let mut pipeline = Pipeline::new(LinRegressor::default())
+                           .add_transform(StandardScaler::default())
+                           .add_transform(MinMaxScaler::default());
+    assert_eq!(pipeline.transform_count(), 2);
+
+    pipeline.train(&inputs, &targets);
+
+    // Manually apply the same transforms.
+    let mut scaler = StandardScaler::default();
+    let mut minmax = MinMaxScaler::default();
+    let scaled = minmax.fit_transform(&scaler.fit_transform(&inputs));
+
+    let mut lin_mod = LinRegressor::default();
+    lin_mod.train(&scaled, &targets);
+
+    let test = Matrix::new(2, 2, vec![0.5, 50.0, 5.0, 500.0]);
+    let expected = lin_mod.predict(&minmax.transform(&scaler.transform(&test)));
+    let outputs = pipeline.predict(&test);
+
+    for (a, b) in outputs.data().iter().zip(expected.data().iter()) {
+        assert!((a - b).abs() < 1e-8);
+    }
+}
+
+#[test]
+fn pipeline_without_transforms() {
+    let inputs = Matrix::new(3, 1, vec![1.0, 2.0, 3.0]);
+    let targets = Vector::new(vec![2.0, 4.0, 6.0]);
+
+    let mut pipeline = Pipeline::new(LinRegressor::default());
+    pipeline.train(&inputs, &targets);
+
+    assert!(pipeline.model().parameters().is_some());
+    assert!((pipeline.predict(&Matrix::new(1, 1, vec![4.0]))[0] - 8.0).abs() < 1e-8);
+}
+
+#[test]
+#[should_panic]
+fn pipeline_predict_untrained() {
+    let pipeline = Pipeline::new(LinRegressor::default())
+                       .add_transform(StandardScaler::default());
+
+    let _ = pipeline.predict(&Matrix::new(1, 1, vec![1.0]));
+}
@@ -0,0 +1,108 @@
+use rm::learning::optim::{OptimAlgorithm, Optimizable};
+use rm::learning::optim::grad_desc::{AdaGrad, GradientDesc, StochasticGD};
+use rm::learning::optim::line_search::LineSearch;
+use rm::learning::optim::projection::{project_simplex, BoxBounds, NonNegative, Projection,
+                                      Simplex};
+use rm::linalg::matrix::Matrix;
+
+/// The cost ||w - center||^2 / 2, ignoring the data.
+struct Bowl {
+    center: Vec<f64>,
+}
+
+impl Optimizable for Bowl {
+    type Inputs = Matrix<f64>;
+    type Targets = Matrix<f64>;
+
+    fn compute_grad(&self, params: &[f64], _: &Matrix<f64>, _: &Matrix<f64>) -> (f64, Vec<f64>) {
+        let grad: Vec<f64> = params.iter().zip(self.center.iter()).map(|(w, c)| w - c).collect();
+        let cost = grad.iter().map(|g| g * g).sum::<f64>() / 2.0;
+        (cost, grad)
+    }
+}
+
+fn data() -> (Matrix<f64>, Matrix<f64>) {
+    (Matrix::zeros(4, 1), Matrix::zeros(4, 1))
+}
+
+#[test]
+fn simplex_projection_sums_to_one() {
+    let mut params = vec![0.3, -0.2, 2.0, 0.9];
+    project_simplex(&mut params);
+
+    assert!((params.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    assert!(params.iter().all(|&p| p >= 0.0));
+    // The order of the values is kept.
+    assert!(params[2] >= params[3] && params[3] >= params[0] && params[0] >= params[1]);
+
+    // Points on the simplex are unchanged.
+    let mut on = vec![0.25, 0.5, 0.25];
+    Simplex.project(&mut on);
+    assert_eq!(on, vec![0.25, 0.5, 0.25]);
+}
+
+#[test]
+fn box_and_non_negative_projections_clip() {
+    let mut params = vec![-2.0, 0.5, 3.0];
+    BoxBounds::new(-1.0, 1.0).project(&mut params);
+    assert_eq!(params, vec![-1.0, 0.5, 1.0]);
+
+    NonNegative.project(&mut params);
+    assert_eq!(params, vec![0.0, 0.5, 1.0]);
+}
+
+#[test]
+#[should_panic]
+fn box_bounds_reject_empty_box() {
+    BoxBounds::new(1.0, -1.0);
+}
+
+#[test]
+fn projected_gradient_desc_finds_constrained_minimum() {
+    let (inputs, targets) = data();
+    let model = Bowl { center: vec![-1.0, 2.0] };
+
+    let gd = GradientDesc::new(0.5, 100).projection(NonNegative);
+    let params = gd.optimize(&model, &[1.0, 1.0], &inputs, &targets).params;
+    assert!(params[0].abs() < 1e-12);
+    assert!((params[1] - 2.0).abs() < 1e-8);
+
+    let gd = GradientDesc::new(1.0, 100)
+        .line_search(LineSearch::armijo())
+        .projection(BoxBounds::new(-0.5, 0.5));
+    let params = gd.optimize(&model, &[0.0, 0.0], &inputs, &targets).params;
+    assert_eq!(params, vec![-0.5, 0.5]);
+}
+
+#[test]
+fn closure_projection_constrains_part_of_the_parameters() {
+    let (inputs, targets) = data();
+    let model = Bowl { center: vec![5.0, 0.6, 0.6, -0.2] };
+
+    let gd = GradientDesc::new(0.5, 200).projection(|params: &mut [f64]| {
+        project_simplex(&mut params[1..]);
+    });
+    let params = gd.optimize(&model, &[0.0; 4], &inputs, &targets).params;
+
+    // The first parameter is free and the rest are the projection of
+    // the center onto the simplex.
+    assert!((params[0] - 5.0).abs() < 1e-8);
+    assert!((params[1] - 0.5).abs() < 1e-8);
+    assert!((params[2] - 0.5).abs() < 1e-8);
+    assert!(params[3].abs() < 1e-8);
+}
+
+#[test]
+fn stochastic_optimizers_stay_feasible() {
+    let (inputs, targets) = data();
+    let model = Bowl { center: vec![-1.0, 3.0] };
+
+    let sgd = StochasticGD::new(0.1, 0.3, 50).projection(BoxBounds::new(0.0, 1.0));
+    let params = sgd.optimize(&model, &[0.5, 0.5], &inputs, &targets).params;
+    assert!(params[0].abs() < 1e-6 && (params[1] - 1.0).abs() < 1e-6);
+
+    let ada = AdaGrad::new(0.5, 50).projection(NonNegative);
+    let params = ada.optimize(&model, &[0.5, 0.5], &inputs, &targets).params;
+    assert_eq!(params[0], 0.0);
+    assert!((params[1] - 3.0).abs() < 1e-2);
+}
@@ -0,0 +1,106 @@
+use std::thread;
+
+use rm::analysis::cross_validation::{cross_val_score_folds, KFold};
+use rm::learning::{SupModel, UnSupModel};
+use rm::learning::k_means::{InitAlgorithm, KMeansClassifier};
+use rm::learning::nnet::{NeuralNet, BCECriterion};
+use rm::learning::optim::grad_desc::StochasticGD;
+use rm::learning::svm::SVM;
+use rm::learning::toolkit::kernel::Linear;
+use rm::learning::toolkit::rand_utils;
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+fn train_net(inputs: &Matrix<f64>, targets: &Matrix<f64>) -> Vec<f64> {
+    let sgd = StochasticGD::with_batch_size(0.1, 0.3, 10, 2).shuffle(true);
+    let mut net = NeuralNet::builder()
+                      .layers(&[2, 4, 1])
+                      .criterion(BCECriterion)
+                      .optimizer(sgd)
+                      .build();
+    net.train(inputs, targets);
+    net.parameters().to_vec()
+}
+
+fn cluster(inputs: &Matrix<f64>, init: InitAlgorithm) -> Vec<usize> {
+    let mut model = KMeansClassifier::new(3).iters(1).init_algorithm(init);
+    model.train(inputs);
+    model.predict(inputs).into_vec()
+}
+
+#[cfg(feature = "parallel")]
+fn assert_parallel_scores(inputs: &Matrix<f64>,
+                          labels: &Vector<f64>,
+                          folds: &[(Vec<usize>, Vec<usize>)],
+                          scores: &[f64]) {
+    use rm::analysis::cross_validation::cross_val_score_parallel;
+
+    let parallel = cross_val_score_parallel(|| SVM::new(Linear::default(), 0.3),
+                                            inputs,
+                                            labels,
+                                            folds,
+                                            |o: &Vector<f64>, t: &Vector<f64>| o.dot(t));
+    assert_eq!(parallel, scores);
+}
+
+#[cfg(not(feature = "parallel"))]
+fn assert_parallel_scores(_: &Matrix<f64>, _: &Vector<f64>, _: &[(Vec<usize>, Vec<usize>)], _: &[f64]) {}
+
+// The seed is shared between threads, so every check which sets it is
+// kept in one test.
+#[test]
+fn set_seed_reproduces_training() {
+    let inputs = Matrix::new(8, 2, vec![0.0, 0.1, 0.9, 1.0, 0.2, 0.8, 0.7, 0.3,
+                                        0.4, 0.4, 0.1, 0.9, 0.6, 0.5, 0.8, 0.2]);
+    let targets = Matrix::new(8, 1, vec![0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0]);
+
+    rand_utils::set_seed(7);
+    let first = train_net(&inputs, &targets);
+    rand_utils::set_seed(7);
+    let second = train_net(&inputs, &targets);
+    assert_eq!(first, second);
+
+    rand_utils::set_seed(8);
+    assert!(train_net(&inputs, &targets) != first);
+
+    for i in 0..3 {
+        let init = || match i {
+            0 => InitAlgorithm::Forgy,
+            1 => InitAlgorithm::RandomPartition,
+            _ => InitAlgorithm::KPlusPlus,
+        };
+
+        rand_utils::set_seed(3);
+        let first = cluster(&inputs, init());
+        rand_utils::set_seed(3);
+        assert_eq!(cluster(&inputs, init()), first);
+    }
+
+    // A task draws the same generators whichever thread runs it.
+    rand_utils::set_seed(5);
+    let first = rand_utils::with_task(2, || train_net(&inputs, &targets));
+    let (task_inputs, task_targets) = (inputs.clone(), targets.clone());
+    let second = thread::spawn(move || {
+                     rand_utils::with_task(2, || train_net(&task_inputs, &task_targets))
+                 })
+                 .join()
+                 .unwrap();
+    assert_eq!(first, second);
+
+    // The folds are tasks, so the scores are reproducible.
+    let labels = Vector::new(targets.data().iter().map(|t| 2.0 * t - 1.0).collect::<Vec<_>>());
+    let folds: Vec<_> = KFold::new(inputs.rows(), 2).collect();
+    let score = || {
+        cross_val_score_folds(|| SVM::new(Linear::default(), 0.3),
+                              &inputs,
+                              &labels,
+                              folds.clone(),
+                              |o: &Vector<f64>, t: &Vector<f64>| o.dot(t))
+    };
+    rand_utils::set_seed(6);
+    let first = score();
+    assert_eq!(score(), first);
+    assert_parallel_scores(&inputs, &labels, &folds, &first);
+
+    rand_utils::clear_seed();
+}
@@ -0,0 +1,70 @@
+use rm::learning::SupModel;
+use rm::learning::logistic_reg::LogisticRegressor;
+use rm::learning::optim::grad_desc::GradientDesc;
+use rm::learning::toolkit::regularization::{ElasticNet, Penalty, L1, L2};
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+fn assert_close(a: &[f64], b: &[f64]) {
+    assert_eq!(a.len(), b.len());
+    for (x, y) in a.iter().zip(b.iter()) {
+        assert!((x - y).abs() < 1e-12, "{:?} != {:?}", a, b);
+    }
+}
+
+#[test]
+fn l1_penalty() {
+    let l1 = L1::new(0.5);
+    let w = [2.0, -1.0, 0.0, 0.2];
+
+    assert_eq!(l1.cost(&w), 1.6);
+    assert_close(&l1.grad(&w), &[0.5, -0.5, 0.0, 0.5]);
+    assert_close(&l1.prox(&w, 1.0), &[1.5, -0.5, 0.0, 0.0]);
+}
+
+#[test]
+fn l2_penalty() {
+    let l2 = L2::new(0.5);
+    let w = [2.0, -1.0];
+
+    assert_eq!(l2.cost(&w), 1.25);
+    assert_close(&l2.grad(&w), &[1.0, -0.5]);
+    assert_close(&l2.prox(&w, 2.0), &[1.0, -0.5]);
+}
+
+#[test]
+fn elastic_net_mixes_penalties() {
+    let w = [2.0, -1.0, 0.1];
+    let net = ElasticNet::new(2.0, 0.25);
+    let l1 = L1::new(0.5);
+    let l2 = L2::new(1.5);
+
+    assert!((net.cost(&w) - (l1.cost(&w) + l2.cost(&w))).abs() < 1e-12);
+    assert_close(&net.grad(&w),
+                 &l1.grad(&w).iter().zip(l2.grad(&w)).map(|(a, b)| a + b).collect::<Vec<_>>());
+    assert_close(&net.prox(&w, 1.0), &l2.prox(&l1.prox(&w, 1.0), 1.0));
+
+    assert_close(&ElasticNet::new(2.0, 1.0).prox(&w, 0.5), &L1::new(2.0).prox(&w, 0.5));
+    assert_close(&ElasticNet::new(2.0, 0.0).prox(&w, 0.5), &L2::new(2.0).prox(&w, 0.5));
+}
+
+#[test]
+#[should_panic]
+fn elastic_net_invalid_ratio() {
+    let _ = ElasticNet::new(1.0, 1.5);
+}
+
+#[test]
+fn l1_penalty_zeroes_unused_weights() {
+    // Only the first feature is related to the targets.
+    let inputs = Matrix::new(8, 2, vec![-2.0, 0.3, -1.5, -0.2, -1.0, 0.1, -0.5, -0.3,
+                                        0.5, 0.2, 1.0, -0.1, 1.5, 0.3, 2.0, -0.2]);
+    let targets = Vector::new(vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]);
+
+    let mut model = LogisticRegressor::new(GradientDesc::new(0.5, 500).penalty(L1::new(0.05)));
+    model.train(&inputs, &targets);
+
+    let params = model.parameters().unwrap();
+    assert!(params[1] > 0.5);
+    assert_eq!(params[2], 0.0);
+}
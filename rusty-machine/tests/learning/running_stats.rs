@@ -0,0 +1,71 @@
+use rm::learning::toolkit::running_stats::RunningStats;
+use rm::linalg::matrix::Matrix;
+
+#[test]
+fn running_stats_match_matrix() {
+    let inputs = Matrix::new(5, 2, vec![1.0, 1e9, 2.0, 1e9 + 1.0, 4.0, 1e9 + 3.0,
+                                        8.0, 1e9 + 2.0, 3.0, 1e9 + 4.0]);
+
+    let mut stats = RunningStats::new();
+    stats.update_rows(&inputs);
+
+    assert_eq!(stats.count(), 5);
+    for (a, b) in stats.mean().iter().zip(inputs.mean(0).data()) {
+        assert!((a - b).abs() < 1e-6);
+    }
+    for (a, b) in stats.variance().iter().zip(inputs.variance(0).data()) {
+        assert!((a - b).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn running_stats_merge() {
+    let inputs = Matrix::new(6, 1, vec![1.0, 5.0, 2.0, 8.0, 3.0, 7.0]);
+
+    let mut all = RunningStats::new();
+    all.update_rows(&inputs);
+
+    let mut first = RunningStats::new();
+    first.update_rows(&inputs.select_rows(&[0, 1]));
+    let mut second = RunningStats::new();
+    second.update_rows(&inputs.select_rows(&[2, 3, 4, 5]));
+    first.merge(&second);
+
+    assert_eq!(first.count(), 6);
+    assert!((first.mean()[0] - all.mean()[0]).abs() < 1e-12);
+    assert!((first.variance()[0] - all.variance()[0]).abs() < 1e-12);
+
+    let mut empty = RunningStats::new();
+    empty.merge(&all);
+    assert_eq!(empty.mean(), all.mean());
+}
+
+#[test]
+fn running_stats_standardize() {
+    let inputs = Matrix::new(4, 2, vec![1.0, 3.0, 2.0, 3.0, 3.0, 3.0, 4.0, 3.0]);
+
+    let mut stats = RunningStats::new();
+    stats.update_rows(&inputs);
+    let scaled = stats.standardize(&inputs);
+
+    let means = scaled.mean(0);
+    assert!(means[0].abs() < 1e-12);
+    assert_eq!(means[1], 0.0);
+    assert!((scaled.variance(0)[0] - 1.0).abs() < 1e-12);
+}
+
+#[test]
+#[should_panic]
+fn running_stats_variance_needs_two_rows() {
+    let mut stats = RunningStats::new();
+    stats.update(&[1.0]);
+    stats.variance();
+}
+
+#[test]
+#[should_panic]
+fn running_stats_row_length() {
+    let mut stats = RunningStats::new();
+    stats.update(&[1.0, 2.0]);
+    stats.update(&[1.0]);
+}
@@ -0,0 +1,120 @@
+use rm::learning::toolkit::activ_fn::{ActivationFunc, Sigmoid};
+use rm::learning::toolkit::cost_fn::{CostFunc, CrossEntropyError, QuantileLoss, SoftmaxCrossEntropy,
+                                     TweedieLoss};
+use rm::learning::toolkit::stable;
+use rm::linalg::matrix::{BaseMatrix, Matrix};
+use rm::linalg::vector::Vector;
+
+#[test]
+fn log_sum_exp_large_values() {
+    assert!((stable::log_sum_exp(&[1.0, 2.0]) - (1f64.exp() + 2f64.exp()).ln()).abs() < 1e-12);
+    assert_eq!(stable::log_sum_exp(&[1000.0, 1000.0]), 1000.0 + 2f64.ln());
+    assert_eq!(stable::log_sum_exp(&[-1000.0, -1000.0]), -1000.0 + 2f64.ln());
+    assert_eq!(stable::log_sum_exp(&[]), -f64::INFINITY);
+}
+
+#[test]
+fn logistic_functions_saturate() {
+    assert_eq!(stable::sigmoid(0.0), 0.5);
+    assert_eq!(stable::sigmoid(1000.0), 1.0);
+    assert_eq!(stable::sigmoid(-1000.0), 0.0);
+    assert_eq!(Sigmoid::func(-1000.0), 0.0);
+
+    assert_eq!(stable::log1p_exp(1000.0), 1000.0);
+    assert_eq!(stable::log1p_exp(0.0), 2f64.ln());
+    assert_eq!(stable::log_sigmoid(-1000.0), -1000.0);
+
+    let logit = 3.0;
+    let ce = stable::cross_entropy(stable::sigmoid(logit), 1.0);
+    assert!((stable::cross_entropy_with_logits(logit, 1.0) - ce).abs() < 1e-12);
+    assert_eq!(stable::cross_entropy_with_logits(-1000.0, 0.0), 0.0);
+}
+
+#[test]
+fn cross_entropy_saturated_outputs() {
+    let outputs = Vector::new(vec![0.0, 1.0, 0.5]);
+    let targets = Vector::new(vec![0.0, 1.0, 1.0]);
+
+    let cost = CrossEntropyError::cost(&outputs, &targets);
+    assert!((cost - 2f64.ln() / 3.0).abs() < 1e-12);
+
+    let grad = CrossEntropyError::grad_cost(&outputs, &targets);
+    assert!(grad.data().iter().all(|x| x.is_finite()));
+    assert_eq!(grad[0], 0.0);
+    assert_eq!(grad[1], 0.0);
+
+    let outputs = Matrix::new(1, 2, vec![1.0, 0.0]);
+    let targets = Matrix::new(1, 2, vec![1.0, 0.0]);
+    assert_eq!(CrossEntropyError::cost(&outputs, &targets), 0.0);
+}
+
+#[test]
+fn softmax_rows() {
+    let x = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 1000.0, 1000.0, -1000.0]);
+    let probs = stable::softmax(&x);
+
+    let total = 1f64.exp() + 2f64.exp() + 3f64.exp();
+    assert!((probs[[0, 2]] - 3f64.exp() / total).abs() < 1e-12);
+    assert_eq!(probs.row(1), &[0.5, 0.5, 0.0]);
+    assert!(probs.data().iter().all(|x| x.is_finite()));
+}
+
+#[test]
+fn softmax_cross_entropy_large_logits() {
+    let logits = Matrix::new(2, 2, vec![1000.0, -1000.0, 0.0, 0.0]);
+    let targets = Matrix::new(2, 2, vec![0.0, 1.0, 1.0, 0.0]);
+
+    let cost = SoftmaxCrossEntropy::cost(&logits, &targets);
+    assert!((cost - (2000.0 + 2f64.ln()) / 2.0).abs() < 1e-9);
+
+    let grad = SoftmaxCrossEntropy::grad_cost(&logits, &targets);
+    assert_eq!(*grad.data(), vec![1.0, -1.0, -0.5, 0.5]);
+}
+
+#[test]
+fn quantile_loss_minimized_at_quantile() {
+    let targets = Vector::new((1..11).map(|x| x as f64).collect::<Vec<_>>());
+    let loss = QuantileLoss::new(0.8);
+
+    let cost_at = |c: f64| loss.cost(&Vector::new(vec![c; 10]), &targets);
+    let best = (0..101).map(|i| i as f64 / 10.0)
+                       .fold(0.0, |best, c| if cost_at(c) < cost_at(best) { c } else { best });
+    assert!((8.0..=9.0).contains(&best));
+
+    let grad = loss.grad_cost(&Vector::new(vec![8.5; 10]), &targets);
+    assert!(grad.sum().abs() < 1e-12);
+}
+
+#[test]
+#[should_panic]
+fn quantile_loss_invalid_quantile() {
+    let _ = QuantileLoss::new(1.0);
+}
+
+#[test]
+fn tweedie_loss_derivatives() {
+    let loss = TweedieLoss::new(1.3);
+    let outputs = Vector::new(vec![-0.5, 0.2, 1.4]);
+    let targets = Vector::new(vec![0.0, 1.5, 3.0]);
+    let eps = 1e-6;
+
+    let grad = loss.grad_cost(&outputs, &targets);
+    let hess = loss.hessian(&outputs, &targets);
+    for i in 0..3 {
+        let shift = |d: f64| {
+            let mut data = outputs.data().clone();
+            data[i] += d;
+            Vector::new(data)
+        };
+
+        // The cost is a mean, so each gradient is scaled by the size.
+        let numeric = (loss.cost(&shift(eps), &targets) - loss.cost(&shift(-eps), &targets)) /
+                      (2.0 * eps) * 3.0;
+        assert!((grad[i] - numeric).abs() < 1e-6);
+
+        let numeric = (loss.grad_cost(&shift(eps), &targets)[i] -
+                       loss.grad_cost(&shift(-eps), &targets)[i]) / (2.0 * eps);
+        assert!((hess[i] - numeric).abs() < 1e-6);
+        assert!(hess[i] > 0.0);
+    }
+}
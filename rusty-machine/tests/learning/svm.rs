@@ -0,0 +1,188 @@
+use rm::learning::svm::{SVM, SVR};
+use rm::learning::toolkit::kernel::{Linear, SquaredExp};
+use rm::learning::{ModelParams, SupModel};
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+#[test]
+fn svr_fits_linear_function() {
+    let inputs = Matrix::new(10, 1, (0..10).map(|x| x as f64).collect::<Vec<_>>());
+    let targets = inputs.data().iter().map(|x| 2.0 * x + 1.0).collect::<Vector<f64>>();
+
+    let mut svr = SVR::new(Linear::default(), 100.0, 0.5);
+    svr.train(&inputs, &targets);
+
+    let outputs = svr.predict(&inputs);
+    for (o, t) in outputs.data().iter().zip(targets.data()) {
+        assert!((o - t).abs() <= 0.5 + 1e-2);
+    }
+
+    // The flattest line within the tube has slope 2 - 1/9.
+    let output = svr.predict(&Matrix::new(1, 1, vec![20.0]));
+    assert!((output[0] - (20.0 * 17.0 / 9.0 + 1.5)).abs() < 1e-2);
+}
+
+#[test]
+fn svr_support_vectors_outside_tube() {
+    let inputs = Matrix::new(20, 1, (0..20).map(|x| x as f64 * 0.3).collect::<Vec<_>>());
+    let targets = inputs.data().iter().map(|x| x.sin()).collect::<Vector<f64>>();
+
+    let mut svr = SVR::new(SquaredExp::new(1.0, 1.0), 10.0, 0.2);
+    svr.train(&inputs, &targets);
+
+    let outputs = svr.predict(&inputs);
+    let n_outside = outputs.data()
+                           .iter()
+                           .zip(targets.data())
+                           .filter(|&(o, t)| (o - t).abs() > 0.2 - 1e-2)
+                           .count();
+    let n_support = svr.support_vectors().unwrap().rows();
+    assert!(n_support < 20);
+    assert!(n_support >= n_outside);
+
+    // The weights of the support vectors sum to zero.
+    let groups = svr.param_groups();
+    let coefs = groups.iter().find(|g| g.name == "dual_coefficients").unwrap();
+    assert!(coefs.values.sum().abs() < 1e-8);
+    assert!(coefs.values.data().iter().all(|c| c.abs() <= 10.0 + 1e-8));
+}
+
+#[test]
+fn svr_zero_epsilon_interpolates() {
+    let inputs = Matrix::new(5, 1, vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+    let targets = Vector::new(vec![1.0, -1.0, 0.5, 2.0, 0.0]);
+
+    let mut svr = SVR::new(SquaredExp::new(0.5, 1.0), 1000.0, 0.0).tol(1e-6);
+    svr.train(&inputs, &targets);
+
+    let outputs = svr.predict(&inputs);
+    for (o, t) in outputs.data().iter().zip(targets.data()) {
+        assert!((o - t).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn svr_shrinking_and_cache_keep_solution() {
+    let inputs = Matrix::new(200, 2, (0..400).map(|i| ((i * 37) % 101) as f64 / 20.0).collect::<Vec<_>>());
+    // Noisy targets leave many points outside the tube.
+    let targets = (0..200)
+        .map(|i| (inputs[[i, 0]] - inputs[[i, 1]]).sin() + ((i * 53) % 17) as f64 / 40.0)
+        .collect::<Vector<f64>>();
+
+    let svr = || SVR::new(SquaredExp::new(0.5, 1.0), 20.0, 0.01).tol(1e-6);
+    let mut plain = svr().shrinking(false);
+    let mut shrunk = svr().shrinking(true);
+    // A cache too small for more than a few rows.
+    let mut small_cache = svr().shrinking(true).cache_size(0);
+
+    plain.train(&inputs, &targets);
+    shrunk.train(&inputs, &targets);
+    small_cache.train(&inputs, &targets);
+
+    let test_inputs = Matrix::new(3, 2, vec![0.3, 1.2, 2.5, 0.4, 4.0, 3.1]);
+    let expected = plain.predict(&test_inputs);
+    for model in &[shrunk, small_cache] {
+        let outputs = model.predict(&test_inputs);
+        for (o, e) in outputs.data().iter().zip(expected.data()) {
+            assert!((o - e).abs() < 1e-4);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn svr_untrained_predict() {
+    let svr = SVR::default();
+    let _ = svr.predict(&Matrix::new(1, 1, vec![0.0]));
+}
+
+fn separable_data() -> (Matrix<f64>, Vector<f64>) {
+    let inputs = Matrix::new(20, 1, (0..20).map(|x| x as f64 - 9.5).collect::<Vec<_>>());
+    let targets = inputs.data().iter().map(|&x| if x > 0.0 { 1.0 } else { -1.0 }).collect();
+    (inputs, targets)
+}
+
+#[test]
+fn svm_decision_function_signs_match_predictions() {
+    let (inputs, targets) = separable_data();
+
+    let mut svm = SVM::new(Linear::default(), 0.3).optim_iters(500);
+    svm.train(&inputs, &targets);
+
+    let decisions = svm.decision_function(&inputs);
+    let outputs = svm.predict(&inputs);
+    for (d, o) in decisions.data().iter().zip(outputs.data()) {
+        assert_eq!(d.signum(), *o);
+    }
+}
+
+#[test]
+fn svm_platt_probabilities() {
+    let (inputs, targets) = separable_data();
+
+    let mut svm = SVM::new(Linear::default(), 0.3).optim_iters(500).probability(true);
+    svm.train(&inputs, &targets);
+
+    let probs = svm.predict_proba(&inputs);
+    for (p, t) in probs.data().iter().zip(targets.data()) {
+        assert!(*p > 0.0 && *p < 1.0);
+        assert_eq!(*p > 0.5, *t > 0.0);
+    }
+
+    // The probabilities increase with the decision values.
+    let decisions = svm.decision_function(&inputs);
+    for i in 1..20 {
+        assert_eq!(probs[i] >= probs[i - 1], decisions[i] >= decisions[i - 1]);
+    }
+}
+
+#[test]
+#[should_panic]
+fn svm_predict_proba_without_probability() {
+    let (inputs, targets) = separable_data();
+
+    let mut svm = SVM::new(Linear::default(), 0.3);
+    svm.train(&inputs, &targets);
+    let _ = svm.predict_proba(&inputs);
+}
+
+#[test]
+#[should_panic(expected = "at least two training points")]
+fn svm_probability_needs_two_points() {
+    let mut svm = SVM::new(Linear::default(), 0.3).probability(true);
+    svm.train(&Matrix::new(1, 1, vec![1.0]), &Vector::new(vec![1.0]));
+}
+
+#[test]
+fn svm_restored_from_parameters() {
+    let (inputs, targets) = separable_data();
+
+    let mut svm = SVM::new(Linear::default(), 0.3).optim_iters(500);
+    svm.train(&inputs, &targets);
+
+    let params = svm.param_groups();
+    let coefs = Vector::new(params[0].values.data().clone());
+    let restored = SVM::from_parameters(Linear::default(), coefs, params[1].values.clone());
+
+    let expected = svm.decision_function(&inputs);
+    for (a, b) in restored.decision_function(&inputs).data().iter().zip(expected.data()) {
+        assert!((a - b).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn svr_restored_from_parameters() {
+    let inputs = Matrix::new(6, 1, vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+    let targets = Vector::new(vec![0.0, 0.8, 0.9, 0.1, -0.8, -1.0]);
+
+    let mut svr = SVR::new(SquaredExp::new(1.0, 1.0), 10.0, 0.1);
+    svr.train(&inputs, &targets);
+
+    let params = svr.param_groups();
+    let restored = SVR::from_parameters(SquaredExp::new(1.0, 1.0),
+                                        params[0].values[[0, 0]],
+                                        Vector::new(params[1].values.data().clone()),
+                                        params[2].values.clone());
+
+    assert_eq!(restored.predict(&inputs).data(), svr.predict(&inputs).data());
+}
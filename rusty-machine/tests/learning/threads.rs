@@ -0,0 +1,66 @@
+use std::sync::Arc;
+use std::thread;
+
+use rm::data::transforms::column::ColumnTransformer;
+use rm::data::transforms::standardize::StandardScaler;
+use rm::learning::SupModel;
+use rm::learning::forest::{RandomForestClassifier, RandomForestRegressor};
+use rm::learning::gbm::GradientBoostingRegressor;
+use rm::learning::glm::{Bernoulli, GenLinearModel};
+use rm::learning::gp::{ConstMean, GaussianProcess};
+use rm::learning::k_means::KMeansClassifier;
+use rm::learning::lin_reg::LinRegressor;
+use rm::learning::logistic_reg::LogisticRegressor;
+use rm::learning::nnet::{BCECriterion, MSECriterion, NeuralNet};
+use rm::learning::pipeline::Pipeline;
+use rm::learning::svm::SVM;
+use rm::learning::tree::{DecisionTreeClassifier, DecisionTreeRegressor};
+use rm::learning::toolkit::kernel::{HyperTan, SquaredExp};
+use rm::linalg::matrix::Matrix;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn models_are_send_sync() {
+    assert_send_sync::<LinRegressor>();
+    assert_send_sync::<LogisticRegressor>();
+    assert_send_sync::<GenLinearModel<Bernoulli>>();
+    assert_send_sync::<KMeansClassifier>();
+    assert_send_sync::<GaussianProcess<SquaredExp, ConstMean>>();
+    assert_send_sync::<SVM<HyperTan>>();
+    assert_send_sync::<DecisionTreeClassifier>();
+    assert_send_sync::<DecisionTreeRegressor>();
+    assert_send_sync::<GradientBoostingRegressor>();
+    assert_send_sync::<RandomForestClassifier>();
+    assert_send_sync::<RandomForestRegressor>();
+    assert_send_sync::<NeuralNet<'static, BCECriterion>>();
+    assert_send_sync::<NeuralNet<'static, MSECriterion>>();
+    assert_send_sync::<Pipeline<LinRegressor>>();
+    assert_send_sync::<ColumnTransformer>();
+    assert_send_sync::<StandardScaler<f64>>();
+}
+
+#[test]
+fn neural_net_shared_between_threads() {
+    let inputs = Matrix::new(4, 2, vec![0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0]);
+    let targets = Matrix::new(4, 1, vec![0.0, 1.0, 1.0, 0.0]);
+
+    let mut model = NeuralNet::builder().layers(&[2, 3, 1]).build();
+    model.train(&inputs, &targets);
+    let expected = model.predict(&inputs).into_vec();
+
+    let model = Arc::new(model);
+    let inputs = Arc::new(inputs);
+
+    let handles: Vec<_> = (0..4)
+                              .map(|_| {
+                                  let model = model.clone();
+                                  let inputs = inputs.clone();
+                                  thread::spawn(move || model.predict(&inputs).into_vec())
+                              })
+                              .collect();
+
+    for h in handles {
+        assert_eq!(h.join().unwrap(), expected);
+    }
+}
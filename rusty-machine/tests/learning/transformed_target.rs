@@ -0,0 +1,42 @@
+use rm::data::transforms::Transformer;
+use rm::data::transforms::target::{BoxCox, LogTransform};
+use rm::learning::{SupModel, WeightedSupModel};
+use rm::learning::lin_reg::LinRegressor;
+use rm::learning::transformed_target::TransformedTargetRegressor;
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+#[test]
+fn predictions_are_inverse_transformed() {
+    let inputs = Matrix::new(5, 1, vec![0.0, 1.0, 2.0, 3.0, 4.0]);
+    let targets = Vector::new(vec![1.0, 3.0, 8.0, 21.0, 55.0]);
+
+    let mut model = TransformedTargetRegressor::new(LinRegressor::default(), BoxCox::default());
+    model.train(&inputs, &targets);
+
+    // The model is trained on the fitted transform of the targets.
+    let mut box_cox = BoxCox::default();
+    let mut lin_mod = LinRegressor::default();
+    lin_mod.train(&inputs, &box_cox.fit_transform(&targets));
+
+    let test = Matrix::new(2, 1, vec![0.5, 2.5]);
+    let expected = box_cox.inverse_transform(&lin_mod.predict(&test));
+    assert_eq!(model.predict(&test).data(), expected.data());
+    assert_eq!(model.transform().lambda(), box_cox.lambda());
+}
+
+#[test]
+fn weighted_training_uses_transformed_targets() {
+    let inputs = Matrix::new(4, 1, vec![0.0, 1.0, 2.0, 3.0]);
+    let targets = Vector::new(vec![1.0, 2.0, 5.0, 7.0]);
+    let weights = Vector::new(vec![1.0, 3.0, 1.0, 2.0]);
+
+    let mut model = TransformedTargetRegressor::new(LinRegressor::default(), LogTransform::default());
+    model.train_weighted(&inputs, &targets, &weights);
+
+    let mut lin_mod = LinRegressor::default();
+    lin_mod.train_weighted(&inputs, &LogTransform::default().transform(&targets), &weights);
+
+    assert_eq!(model.model().parameters().unwrap().data(),
+               lin_mod.parameters().unwrap().data());
+}
@@ -0,0 +1,290 @@
+use rm::learning::{SupModel, WeightedSupModel};
+use rm::learning::tree::{DecisionTreeClassifier, DecisionTreeRegressor, Split, TreeParams};
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+
+#[test]
+fn classifier_fits_xor() {
+    let inputs = Matrix::new(4, 2, vec![0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0]);
+    let targets = Vector::new(vec![0, 1, 1, 0]);
+
+    let mut model = DecisionTreeClassifier::default();
+    model.train(&inputs, &targets);
+
+    assert_eq!(model.predict(&inputs).data(), targets.data());
+
+    let tree = model.tree().unwrap();
+    assert_eq!(tree.n_leaves(), 4);
+    assert_eq!(tree.depth(), 2);
+}
+
+#[test]
+fn classifier_probabilities_are_leaf_proportions() {
+    let inputs = Matrix::new(5, 1, vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+    let targets = Vector::new(vec![0, 0, 1, 1, 0]);
+
+    let mut model = DecisionTreeClassifier::new(TreeParams::default().max_depth(1));
+    model.train(&inputs, &targets);
+
+    let proba = model.predict_proba(&Matrix::new(2, 1, vec![1.5, 4.5]));
+    assert_eq!(proba.data(), &[1.0, 0.0, 1.0 / 3.0, 2.0 / 3.0]);
+}
+
+#[test]
+fn min_samples_leaf_limits_splits() {
+    let inputs = Matrix::new(4, 1, vec![1.0, 2.0, 3.0, 4.0]);
+    let targets = Vector::new(vec![0, 1, 1, 1]);
+
+    let mut model = DecisionTreeClassifier::new(TreeParams::default().min_samples_leaf(2));
+    model.train(&inputs, &targets);
+
+    let tree = model.tree().unwrap();
+    assert_eq!(tree.nodes()[0].branch.as_ref().unwrap().split, Split::Threshold(2.5));
+    assert_eq!(tree.n_leaves(), 2);
+}
+
+#[test]
+fn regressor_predicts_leaf_means() {
+    let inputs = Matrix::new(6, 1, vec![1.0, 2.0, 3.0, 10.0, 11.0, 12.0]);
+    let targets = Vector::new(vec![1.0, 2.0, 3.0, 10.0, 11.0, 12.0]);
+
+    let mut model = DecisionTreeRegressor::new(TreeParams::default().max_depth(1));
+    model.train(&inputs, &targets);
+
+    let outputs = model.predict(&Matrix::new(2, 1, vec![0.0, 20.0]));
+    assert_eq!(outputs.data(), &[2.0, 11.0]);
+
+    let root = &model.tree().unwrap().nodes()[0];
+    assert_eq!(root.samples, 6);
+    assert_eq!(root.value, vec![6.5]);
+}
+
+#[test]
+fn text_export_names_features() {
+    let inputs = Matrix::new(4, 2, vec![1.0, 0.0, 2.0, 0.0, 3.0, 1.0, 4.0, 1.0]);
+    let targets = Vector::new(vec![1.0, 1.0, 5.0, 5.0]);
+
+    let mut model = DecisionTreeRegressor::default();
+    model.train(&inputs, &targets);
+
+    let text = model.tree().unwrap().to_text(Some(&["size", "flag"]));
+    assert_eq!(text,
+               "|--- size <= 2.5\n\
+                |   |--- value = 1.0000, samples = 2\n\
+                |--- size > 2.5\n\
+                |   |--- value = 5.0000, samples = 2\n");
+}
+
+#[test]
+fn dot_export_has_node_per_tree_node() {
+    let inputs = Matrix::new(4, 2, vec![0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0, 1.0]);
+    let targets = Vector::new(vec![0, 1, 1, 0]);
+
+    let mut model = DecisionTreeClassifier::default();
+    model.train(&inputs, &targets);
+
+    let tree = model.tree().unwrap();
+    let dot = tree.to_dot(None);
+    assert!(dot.starts_with("digraph Tree {"));
+    assert!(dot.ends_with('}'));
+    assert_eq!(dot.matches("[label=\"True\"]").count(), tree.nodes().len() / 2);
+    assert!(dot.contains("x[0] <= 0.5"));
+    assert!(dot.contains("class = 1, proportions = [0.00, 1.00]"));
+}
+
+#[test]
+fn debug_shows_trained() {
+    let model = DecisionTreeClassifier::default();
+    assert!(format!("{:?}", model).contains("trained: false"));
+}
+
+#[test]
+#[should_panic]
+fn untrained_tree_panics() {
+    let model = DecisionTreeRegressor::default();
+    model.predict(&Matrix::new(1, 1, vec![0.0]));
+}
+
+#[test]
+fn pruning_path_ends_at_root() {
+    let inputs = Matrix::new(8, 1, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+    let targets = Vector::new(vec![0, 1, 0, 0, 1, 1, 0, 1]);
+
+    let mut model = DecisionTreeClassifier::default();
+    model.train(&inputs, &targets);
+
+    let tree = model.tree().unwrap();
+    let path = tree.pruning_path();
+
+    assert_eq!(path.alphas[0], 0.0);
+    assert_eq!(path.impurities[0], 0.0);
+    assert!(path.alphas.windows(2).all(|w| w[0] < w[1]));
+    assert!(path.impurities.windows(2).all(|w| w[0] <= w[1]));
+    assert_eq!(*path.impurities.last().unwrap(), tree.nodes()[0].impurity);
+
+    // Each alpha of the path prunes more of the tree.
+    let leaves = path.alphas.iter().map(|&a| tree.prune(a).n_leaves()).collect::<Vec<_>>();
+    assert!(leaves.windows(2).all(|w| w[0] > w[1]));
+    assert_eq!(*leaves.last().unwrap(), 1);
+}
+
+#[test]
+fn ccp_alpha_prunes_trained_tree() {
+    let inputs = Matrix::new(6, 1, vec![1.0, 2.0, 3.0, 10.0, 11.0, 12.0]);
+    let targets = Vector::new(vec![1.0, 2.0, 3.0, 10.0, 11.0, 12.0]);
+
+    let mut full = DecisionTreeRegressor::default();
+    full.train(&inputs, &targets);
+    assert_eq!(full.tree().unwrap().n_leaves(), 6);
+
+    let mut pruned = DecisionTreeRegressor::new(TreeParams::default().ccp_alpha(1.0));
+    pruned.train(&inputs, &targets);
+
+    let tree = pruned.tree().unwrap();
+    assert_eq!(tree.n_leaves(), 2);
+    assert_eq!(tree.nodes().len(), 3);
+    assert_eq!(*tree, full.tree().unwrap().prune(1.0));
+    assert_eq!(pruned.predict(&inputs).data(), &[2.0, 2.0, 2.0, 11.0, 11.0, 11.0]);
+}
+
+#[test]
+fn categorical_split_groups_categories() {
+    // The targets depend on whether the category is odd, which no
+    // threshold on the codes can separate.
+    let inputs = Matrix::new(8, 1, vec![0.0, 1.0, 2.0, 3.0, 0.0, 1.0, 2.0, 3.0]);
+    let targets = Vector::new(vec![0.0, 10.0, 0.0, 10.0, 1.0, 11.0, 1.0, 11.0]);
+
+    let params = TreeParams::default().max_depth(1).categorical(&[0]);
+    let mut model = DecisionTreeRegressor::new(params);
+    model.train(&inputs, &targets);
+
+    let root = &model.tree().unwrap().nodes()[0];
+    assert_eq!(root.branch.as_ref().unwrap().split, Split::Categories(vec![0.0, 2.0]));
+    assert_eq!(model.predict(&inputs).data(), &[0.5, 10.5, 0.5, 10.5, 0.5, 10.5, 0.5, 10.5]);
+
+    // An unseen category goes right.
+    assert_eq!(model.predict(&Matrix::new(1, 1, vec![7.0])).data(), &[10.5]);
+
+    let text = model.tree().unwrap().to_text(Some(&["colour"]));
+    assert!(text.starts_with("|--- colour in {0, 2}\n"));
+    assert!(text.contains("|--- colour not in {0, 2}\n"));
+}
+
+#[test]
+fn categorical_classification_orders_by_class_proportion() {
+    let inputs = Matrix::new(6, 2, vec![5.0, 0.0, 3.0, 0.0, 5.0, 1.0,
+                                        3.0, 1.0, 4.0, 0.0, 4.0, 1.0]);
+    let targets = Vector::new(vec![1, 1, 1, 1, 0, 0]);
+
+    let mut model = DecisionTreeClassifier::new(TreeParams::default().categorical(&[0, 1]));
+    model.train(&inputs, &targets);
+
+    let tree = model.tree().unwrap();
+    assert_eq!(tree.n_leaves(), 2);
+    assert_eq!(tree.nodes()[0].branch.as_ref().unwrap().split, Split::Categories(vec![4.0]));
+    assert_eq!(model.predict(&inputs).data(), targets.data());
+}
+
+#[test]
+#[should_panic]
+fn categorical_column_out_of_range() {
+    let inputs = Matrix::new(2, 1, vec![0.0, 1.0]);
+    let targets = Vector::new(vec![0, 1]);
+
+    let mut model = DecisionTreeClassifier::new(TreeParams::default().categorical(&[1]));
+    model.train(&inputs, &targets);
+}
+
+#[test]
+fn missing_values_learn_default_branch() {
+    let inputs = Matrix::new(7, 1, vec![1.0, 2.0, 3.0, 10.0, 11.0, f64::NAN, f64::NAN]);
+    let targets = Vector::new(vec![0.0, 0.0, 0.0, 5.0, 5.0, 5.0, 5.0]);
+
+    let mut model = DecisionTreeRegressor::new(TreeParams::default().max_depth(1));
+    model.train(&inputs, &targets);
+
+    let root = &model.tree().unwrap().nodes()[0];
+    let branch = root.branch.as_ref().unwrap();
+    assert_eq!(branch.split, Split::Threshold(6.5));
+    assert!(!branch.missing_left);
+    assert_eq!(model.predict(&Matrix::new(2, 1, vec![f64::NAN, 2.0])).data(), &[5.0, 0.0]);
+
+    let text = model.tree().unwrap().to_text(None);
+    assert!(text.contains("|--- x[0] > 6.5 or missing\n"));
+}
+
+#[test]
+fn unseen_missing_values_go_to_larger_child() {
+    let inputs = Matrix::new(5, 1, vec![1.0, 2.0, 3.0, 10.0, 11.0]);
+    let targets = Vector::new(vec![0, 0, 0, 1, 1]);
+
+    let mut model = DecisionTreeClassifier::default();
+    model.train(&inputs, &targets);
+
+    let tree = model.tree().unwrap();
+    assert!(tree.nodes()[0].branch.as_ref().unwrap().missing_left);
+    assert_eq!(model.predict(&Matrix::new(1, 1, vec![f64::NAN])).data(), &[0]);
+    assert!(!tree.to_text(None).contains("missing"));
+}
+
+#[test]
+fn missing_categories_learn_default_branch() {
+    let inputs = Matrix::new(6, 1, vec![0.0, 1.0, 2.0, 0.0, f64::NAN, f64::NAN]);
+    let targets = Vector::new(vec![1, 0, 1, 1, 0, 0]);
+
+    let mut model = DecisionTreeClassifier::new(TreeParams::default().categorical(&[0]));
+    model.train(&inputs, &targets);
+
+    assert_eq!(model.predict(&inputs).data(), targets.data());
+}
+
+#[test]
+fn integer_weights_match_repeated_rows() {
+    let inputs = Matrix::new(4, 1, vec![1.0, 2.0, 3.0, 4.0]);
+    let targets = Vector::new(vec![1.0, 2.0, 6.0, 9.0]);
+    let weights = Vector::new(vec![2.0, 1.0, 1.0, 3.0]);
+
+    let mut weighted = DecisionTreeRegressor::new(TreeParams::default().max_depth(1));
+    weighted.train_weighted(&inputs, &targets, &weights);
+
+    let repeated_inputs = Matrix::new(7, 1, vec![1.0, 1.0, 2.0, 3.0, 4.0, 4.0, 4.0]);
+    let repeated_targets = Vector::new(vec![1.0, 1.0, 2.0, 6.0, 9.0, 9.0, 9.0]);
+    let mut repeated = DecisionTreeRegressor::new(TreeParams::default().max_depth(1));
+    repeated.train(&repeated_inputs, &repeated_targets);
+
+    let (w, r) = (weighted.tree().unwrap(), repeated.tree().unwrap());
+    assert_eq!(w.nodes()[0].weight, 7.0);
+    assert_eq!(w.nodes()[0].samples, 4);
+    assert_eq!(w.nodes().len(), r.nodes().len());
+    for (a, b) in w.nodes().iter().zip(r.nodes()) {
+        assert!((a.value[0] - b.value[0]).abs() < 1e-12);
+        assert!((a.impurity - b.impurity).abs() < 1e-12);
+        assert_eq!(a.weight, b.weight);
+    }
+}
+
+#[test]
+fn zero_weight_rows_are_ignored() {
+    let inputs = Matrix::new(6, 1, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    // The middle rows are mislabelled.
+    let targets = Vector::new(vec![0, 0, 1, 0, 1, 1]);
+    let weights = Vector::new(vec![1.0, 1.0, 0.0, 0.0, 1.0, 1.0]);
+
+    let mut model = DecisionTreeClassifier::default();
+    model.train_weighted(&inputs, &targets, &weights);
+
+    // One split separates the weighted rows, and the first threshold
+    // between them is taken.
+    assert_eq!(model.tree().unwrap().nodes().len(), 3);
+    assert_eq!(model.predict(&inputs).data(), &[0, 0, 1, 1, 1, 1]);
+}
+
+#[test]
+#[should_panic]
+fn negative_weights_panic() {
+    let inputs = Matrix::new(2, 1, vec![1.0, 2.0]);
+    let targets = Vector::new(vec![0, 1]);
+
+    let mut model = DecisionTreeClassifier::default();
+    model.train_weighted(&inputs, &targets, &Vector::new(vec![1.0, -1.0]));
+}
@@ -0,0 +1,101 @@
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+use rm::learning::{SupModel, WeightedSupModel};
+use rm::learning::logistic_reg::LogisticRegressor;
+use rm::learning::nnet::{NeuralNet, MSECriterion};
+use rm::learning::toolkit::cost_fn::{CostFunc, WeightedCostFunc, MeanSqError, CrossEntropyError};
+use rm::learning::optim::grad_desc::{GradientDesc, StochasticGD};
+
+#[test]
+fn weighted_cost_unit_weights() {
+    let outputs = Matrix::new(2, 2, vec![0.2, 0.7, 0.6, 0.1]);
+    let targets = Matrix::new(2, 2, vec![0.0, 1.0, 1.0, 0.0]);
+    let ones = Vector::ones(2);
+
+    let mse = MeanSqError::cost(&outputs, &targets);
+    let weighted_mse = MeanSqError::weighted_cost(&outputs, &targets, &ones);
+    assert!((mse - weighted_mse).abs() < 1e-12);
+
+    let ce = CrossEntropyError::cost(&outputs, &targets);
+    let weighted_ce = CrossEntropyError::weighted_cost(&outputs, &targets, &ones);
+    assert!((ce - weighted_ce).abs() < 1e-12);
+}
+
+#[test]
+fn weighted_cost_scales_samples() {
+    let outputs = Vector::new(vec![1.0, 3.0]);
+    let targets = Vector::new(vec![0.0, 0.0]);
+    let weights = Vector::new(vec![3.0, 1.0]);
+
+    // (3 * 1 + 1 * 9) / (2 * 4)
+    assert_eq!(MeanSqError::weighted_cost(&outputs, &targets, &weights), 1.5);
+    assert_eq!(*MeanSqError::weighted_grad_cost(&outputs, &targets, &weights).data(),
+               vec![3.0, 3.0]);
+}
+
+#[test]
+fn logistic_unit_weights_match_unweighted() {
+    let inputs = Matrix::new(4, 1, vec![1.0, 3.0, 5.0, 7.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+
+    let mut model = LogisticRegressor::default();
+    model.train(&inputs, &targets);
+
+    let mut weighted = LogisticRegressor::default();
+    weighted.train_weighted(&inputs, &targets, &Vector::ones(4));
+
+    let (a, b) = (model.parameters().unwrap(), weighted.parameters().unwrap());
+    for (x, y) in a.data().iter().zip(b.data().iter()) {
+        assert!((x - y).abs() < 1e-10);
+    }
+}
+
+#[test]
+fn logistic_weights_shift_boundary() {
+    // Overlapping classes at x = 2.
+    let inputs = Matrix::new(4, 1, vec![0.0, 2.0, 2.0, 4.0]);
+    let targets = Vector::new(vec![0.0, 0.0, 1.0, 1.0]);
+
+    let mut model = LogisticRegressor::new(GradientDesc::new(0.5, 2000));
+    model.train_weighted(&inputs, &targets, &Vector::new(vec![1.0, 1.0, 5.0, 1.0]));
+
+    let outputs = model.predict(&Matrix::new(1, 1, vec![2.0]));
+    assert!(outputs[0] > 0.5);
+
+    model.train_weighted(&inputs, &targets, &Vector::new(vec![1.0, 5.0, 1.0, 1.0]));
+
+    let outputs = model.predict(&Matrix::new(1, 1, vec![2.0]));
+    assert!(outputs[0] < 0.5);
+}
+
+#[test]
+fn nnet_train_weighted() {
+    // Conflicting targets at zero, with the second far more trusted.
+    let n = 100;
+    let inputs = Matrix::zeros(n, 1);
+    let targets = Matrix::new(n, 1, (0..n).map(|i| (i % 2) as f64).collect());
+    let weights = Vector::new((0..n).map(|i| if i % 2 == 0 { 0.01 } else { 10.0 }).collect());
+
+    let layers = &[1, 1];
+    let mut model = NeuralNet::new(layers, MSECriterion);
+    model.train_weighted(&inputs, &targets, &weights);
+
+    let outputs = model.predict(&Matrix::new(1, 1, vec![0.0]));
+    assert!(outputs[[0, 0]] > 0.8);
+}
+
+#[test]
+fn nnet_zero_weight_sample_has_finite_cost() {
+    let inputs = Matrix::new(3, 1, vec![0.0, 1.0, 2.0]);
+    let targets = Matrix::new(3, 1, vec![0.0, 1.0, 1.0]);
+    let weights = Vector::new(vec![0.0, 1.0, 1.0]);
+
+    // Single sample batches, so one batch has no weight at all.
+    let gd = StochasticGD::default().check_finite(true);
+    let mut model = NeuralNet::builder().layers(&[1, 2, 1]).optimizer(gd).build();
+    model.train_weighted(&inputs, &targets, &weights);
+
+    let report = model.training_report().unwrap();
+    assert!(report.error.is_none());
+    assert!(report.cost.is_finite());
+}
@@ -1,13 +1,95 @@
 extern crate rusty_machine as rm;
-extern crate num as libnum;
+extern crate num_traits as libnum;
+extern crate rand;
+
+#[cfg(feature = "ndarray")]
+#[macro_use]
+extern crate ndarray;
+#[cfg(feature = "nalgebra")]
+extern crate nalgebra;
+#[cfg(feature = "arrow")]
+extern crate arrow_array;
+#[cfg(feature = "parquet")]
+extern crate parquet;
+#[cfg(feature = "polars")]
+extern crate polars;
 
 pub mod linalg {
     mod mat;
     mod vector;
+    mod col_major;
+
+    #[cfg(feature = "ndarray")]
+    mod ndarray_convert;
+    #[cfg(feature = "nalgebra")]
+    mod nalgebra_convert;
+    #[cfg(feature = "gpu")]
+    mod gpu;
 }
 
 pub mod learning {
     mod lin_reg;
     mod k_means;
     mod gp;
-}
\ No newline at end of file
+    mod pipeline;
+    mod multi_output;
+    mod transformed_target;
+    mod weighted;
+    mod incremental;
+    mod nnet;
+    mod optim;
+    mod threads;
+    mod chunked;
+    mod distance;
+    mod kernel;
+    mod activ_fn;
+    mod regularization;
+    mod global;
+    mod coord_desc;
+    mod projection;
+    mod stable;
+    mod running_stats;
+    mod tree;
+    mod gbm;
+    #[cfg(feature = "rand")]
+    mod rand_utils;
+    #[cfg(feature = "rand")]
+    mod svm;
+    #[cfg(feature = "rand")]
+    mod forest;
+    #[cfg(feature = "onnx")]
+    mod onnx;
+}
+
+pub mod data {
+    mod transforms;
+    mod split;
+    mod resample;
+    mod csv;
+    mod libsvm;
+    mod npy;
+    #[cfg(feature = "arrow")]
+    mod arrow;
+    #[cfg(feature = "polars")]
+    mod polars;
+    mod generators;
+    mod dataset;
+    mod labels;
+    mod text;
+}
+
+pub mod analysis {
+    mod cross_validation;
+    mod model_selection;
+    mod confusion_matrix;
+    mod curves;
+    mod regression;
+    mod learning_curve;
+    mod report;
+    mod bootstrap;
+}
+
+#[cfg(feature = "stats")]
+pub mod stats {
+    mod dist;
+}
@@ -0,0 +1,55 @@
+use rm::linalg::matrix::{Matrix, ColMajorMatrix};
+use rm::linalg::vector::Vector;
+
+#[test]
+fn col_major_round_trip() {
+    let a = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let b = ColMajorMatrix::from(&a);
+
+    assert_eq!(*b.data(), vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+    assert_eq!(b.col(2), &[3.0, 6.0]);
+    assert_eq!(b[[1, 0]], 4.0);
+
+    let c = Matrix::from(&b);
+    assert_eq!(c.data(), a.data());
+}
+
+#[test]
+fn col_major_mul_matches_row_major() {
+    let a = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let b = Matrix::new(3, 2, vec![1.0, -1.0, 0.5, 2.0, 0.0, 3.0]);
+    let expected = &a * &b;
+
+    let b_col = ColMajorMatrix::from(&b);
+    let c = &a * &b_col;
+    assert_eq!(c.data(), expected.data());
+
+    let a_col = ColMajorMatrix::from(&a);
+    let d = Matrix::from(&(&a_col * &b_col));
+    assert_eq!(d.data(), expected.data());
+
+    let v = Vector::new(vec![1.0, 1.0, 1.0]);
+    assert_eq!(*(&a_col * &v).data(), *(&a * &v).data());
+}
+
+#[test]
+#[should_panic]
+fn col_major_mul_dim_mismatch() {
+    let a = Matrix::new(2, 3, vec![0.0; 6]);
+    let b = ColMajorMatrix::new(2, 2, vec![0.0; 4]);
+
+    let _ = &a * &b;
+}
+
+#[test]
+fn col_major_transpose_no_copy() {
+    let a = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let a_t = a.clone().into_col_major_transpose();
+
+    assert_eq!(a_t.rows(), 3);
+    assert_eq!(a_t.cols(), 2);
+    assert_eq!(a_t[[1, 0]], 2.0);
+
+    let a_back = a_t.into_transpose();
+    assert_eq!(a_back.data(), a.data());
+}
@@ -0,0 +1,53 @@
+use rm::linalg::gpu;
+use rm::linalg::matrix::Matrix;
+
+fn assert_close(a: &Matrix<f64>, b: &Matrix<f64>) {
+    assert_eq!((a.rows(), a.cols()), (b.rows(), b.cols()));
+    for (x, y) in a.data().iter().zip(b.data().iter()) {
+        assert!((x - y).abs() <= 1e-9 * (1.0 + y.abs()));
+    }
+}
+
+#[test]
+fn small_product_matches_cpu() {
+    let a = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let b = Matrix::new(3, 2, vec![1.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+
+    let mut c = Matrix::new(0, 0, vec![]);
+    gpu::mul_into(&a, &b, &mut c);
+
+    assert_eq!(*c.data(), *(&a * &b).data());
+}
+
+#[test]
+fn large_product_matches_cpu() {
+    let a = Matrix::from_fn(128, 96, |i, j| ((i * 7 + j * 3) % 11) as f64 - 5.0);
+    let b = Matrix::from_fn(96, 128, |i, j| ((i * 5 + j) % 13) as f64 / 4.0);
+    assert!(a.rows() * a.cols() * b.cols() >= gpu::MIN_GPU_WORK);
+
+    let mut c = Matrix::new(0, 0, vec![]);
+    gpu::mul_into(&a, &b, &mut c);
+    assert_close(&c, &(&a * &b));
+}
+
+#[test]
+fn device_product() {
+    // Only runs where a double precision device is available.
+    if let Some(device) = gpu::device() {
+        let a = Matrix::from_fn(17, 33, |i, j| (i as f64) - (j as f64) / 3.0);
+        let b = Matrix::from_fn(33, 9, |i, j| (i * j) as f64 / 7.0);
+
+        assert_close(&device.mul(&a, &b).unwrap(), &(&a * &b));
+        assert!(device.mul(&Matrix::new(0, 3, vec![]), &Matrix::new(3, 2, vec![0.0; 6])).is_none());
+    } else {
+        assert!(!gpu::is_available());
+    }
+}
+
+#[test]
+#[should_panic]
+fn mismatched_dimensions() {
+    let a = Matrix::new(2, 3, vec![0.0; 6]);
+    let mut c = Matrix::new(0, 0, vec![]);
+    gpu::mul_into(&a, &a, &mut c);
+}
@@ -1,6 +1,8 @@
-use rm::linalg::matrix::Matrix;
+use rm::linalg::matrix::{Matrix, MatrixSlice, BaseMatrix};
 use rm::linalg::vector::Vector;
+use rm::linalg::Complex;
 use libnum::abs;
+use rand::{StdRng, SeedableRng};
 
 #[test]
 fn create_mat_zeros() {
@@ -50,6 +52,77 @@ fn create_mat_diag() {
     assert_eq!(a[[3, 0]], 0.0);
 }
 
+#[test]
+fn create_mat_rand_uniform() {
+    let seed: &[usize] = &[42];
+    let mut rng: StdRng = SeedableRng::from_seed(seed);
+    let a = Matrix::rand_uniform(4, 5, &mut rng);
+
+    assert_eq!(a.rows(), 4);
+    assert_eq!(a.cols(), 5);
+
+    for x in a.data() {
+        assert!(*x >= 0.0 && *x < 1.0);
+    }
+}
+
+#[test]
+fn create_mat_rand_seeded() {
+    let seed: &[usize] = &[42];
+
+    let mut rng: StdRng = SeedableRng::from_seed(seed);
+    let a = Matrix::rand_normal(3, 3, 0.0, 1.0, &mut rng);
+
+    let mut rng: StdRng = SeedableRng::from_seed(seed);
+    let b = Matrix::rand_normal(3, 3, 0.0, 1.0, &mut rng);
+
+    assert_eq!(a.data(), b.data());
+}
+
+#[test]
+fn create_mat_from_fn() {
+    let a = Matrix::from_fn(3, 2, |i, j| (i * 10 + j) as f64);
+
+    assert_eq!(a.rows(), 3);
+    assert_eq!(a.cols(), 2);
+    assert_eq!(a[[2, 1]], 21.0);
+    assert_eq!(a[[1, 0]], 10.0);
+}
+
+#[test]
+fn create_mat_from_iter() {
+    let a = (0..3).map(|i| vec![i as f64; 4]).collect::<Matrix<f64>>();
+
+    assert_eq!(a.rows(), 3);
+    assert_eq!(a.cols(), 4);
+    assert_eq!(a[[2, 3]], 2.0);
+
+    let b = (0..2).map(|i| Vector::new(vec![i; 2])).collect::<Matrix<usize>>();
+
+    assert_eq!(*b.data(), vec![0, 0, 1, 1]);
+}
+
+#[test]
+fn create_mat_from_iter_shape() {
+    let a = Matrix::from_iter_shape(2, 3, (0..6).map(|i| i as f64));
+
+    assert_eq!(a.rows(), 2);
+    assert_eq!(a.cols(), 3);
+    assert_eq!(a[[1, 2]], 5.0);
+}
+
+#[test]
+#[should_panic]
+fn create_mat_from_short_iter_shape() {
+    let _ = Matrix::from_iter_shape(2, 3, 0..5);
+}
+
+#[test]
+#[should_panic]
+fn create_mat_from_ragged_iter() {
+    let _ = vec![vec![1.0, 2.0], vec![3.0]].into_iter().collect::<Matrix<f64>>();
+}
+
 #[test]
 fn transpose_mat() {
     let a = Matrix::new(5, 2, vec![1., 2., 3., 4., 5., 6., 7., 8., 9., 10.]);
@@ -322,3 +395,92 @@ fn qr() {
         assert!(val < tol, format!("val is {0}", val));
     }
 }
+
+#[test]
+fn complex_mat_mul_conj_transpose() {
+    let a = Matrix::new(2, 2, vec![Complex::new(1.0, 2.0), Complex::new(0.0, -1.0),
+                                   Complex::new(3.0, 0.0), Complex::new(1.0, 1.0)]);
+
+    let b = &a * a.conj_transpose();
+
+    assert!(b.is_hermitian());
+    assert_eq!(b[[0, 0]], Complex::new(6.0, 0.0));
+    assert_eq!(b[[0, 1]], Complex::new(2.0, 5.0));
+}
+
+#[test]
+fn lazy_expression_eval() {
+    let a = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let b = Matrix::new(3, 2, vec![1.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+    let c = Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+
+    let expected = (&a * &b) + c.elemul(&c) * 2.0 - &c;
+    let lazy = (a.lazy() * b.lazy() + c.lazy().elemul(c.lazy()).scale(2.0) - c.lazy()).eval();
+
+    assert_eq!(lazy.data(), expected.data());
+}
+
+#[test]
+#[should_panic]
+fn lazy_expression_dim_mismatch() {
+    let a = Matrix::new(2, 2, vec![1.0; 4]);
+    let b = Matrix::new(2, 1, vec![1.0; 2]);
+
+    let _ = a.lazy() + b.lazy();
+}
+
+#[test]
+#[should_panic]
+fn lazy_expression_get_out_of_bounds() {
+    use rm::linalg::expr::MatrixExpr;
+
+    let a = Matrix::new(2, 2, vec![1.0; 4]);
+    let b = Matrix::new(2, 2, vec![1.0; 4]);
+
+    let _ = (a.lazy() * b.lazy()).into_inner().get(0, 2);
+}
+
+#[test]
+fn matrix_mul_into_reuses_output() {
+    let a = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let b = Matrix::new(3, 1, vec![1.0, 1.0, 1.0]);
+
+    // Start with an output of the wrong shape.
+    let mut out = Matrix::new(3, 3, vec![9.0; 9]);
+
+    a.mul_into(&b, &mut out);
+    assert_eq!(out.rows(), 2);
+    assert_eq!(out.cols(), 1);
+    assert_eq!(*out.data(), vec![6.0, 15.0]);
+
+    a.transpose_into(&mut out);
+    assert_eq!(out.data(), a.transpose().data());
+}
+
+#[test]
+fn slice_rows_view() {
+    let a = Matrix::new(3, 2, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let view = a.slice_rows(1, 3);
+
+    assert_eq!(view.rows(), 2);
+    assert_eq!(view.cols(), 2);
+    assert_eq!(view.row(1), &[5.0, 6.0]);
+    assert_eq!(*view.to_matrix().data(), vec![3.0, 4.0, 5.0, 6.0]);
+    assert_eq!(a.as_slice().data(), &a.data()[..]);
+}
+
+#[test]
+fn matrix_slice_from_slice() {
+    let data = [1, 2, 3, 4, 5, 6];
+    let view = MatrixSlice::from_slice(&data[2..], 2, 2);
+
+    assert_eq!(view[[1, 0]], 5);
+    assert_eq!(BaseMatrix::row(&view, 0), &[3, 4]);
+}
+
+#[test]
+#[should_panic]
+fn slice_rows_out_of_bounds() {
+    let a = Matrix::new(2, 1, vec![1.0, 2.0]);
+    let _ = a.slice_rows(1, 3);
+}
@@ -0,0 +1,28 @@
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+use nalgebra::{DMatrix, DVector};
+
+#[test]
+fn matrix_to_dmatrix_and_back() {
+    let a = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+    let b = DMatrix::from(&a);
+    assert_eq!(b.nrows(), 2);
+    assert_eq!(b.ncols(), 3);
+    assert_eq!(b[(0, 2)], 3.0);
+
+    let c = Matrix::from(b.transpose());
+    assert_eq!(c.rows(), 3);
+    assert_eq!(*c.data(), vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+}
+
+#[test]
+fn vector_to_dvector_and_back() {
+    let a = Vector::new(vec![1.0, 2.0, 3.0]);
+
+    let b = DVector::from(a);
+    assert_eq!(b[1], 2.0);
+
+    let c = Vector::from(b * 2.0);
+    assert_eq!(*c.data(), vec![2.0, 4.0, 6.0]);
+}
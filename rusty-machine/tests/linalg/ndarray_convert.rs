@@ -0,0 +1,36 @@
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+use ndarray::{Array1, Array2};
+
+#[test]
+fn matrix_to_array_and_back() {
+    let a = Matrix::new(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+    let arr = Array2::from(&a);
+    assert_eq!(arr.dim(), (2, 3));
+    assert_eq!(arr[[1, 2]], 6.0);
+
+    let b = Matrix::from(arr);
+    assert_eq!(b.data(), a.data());
+}
+
+#[test]
+fn sliced_array_to_matrix() {
+    let arr = Array2::from_shape_vec((3, 2), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    let sliced = arr.slice_move(s![1.., ..]);
+
+    let a = Matrix::from(sliced);
+    assert_eq!(a.rows(), 2);
+    assert_eq!(*a.data(), vec![3.0, 4.0, 5.0, 6.0]);
+}
+
+#[test]
+fn vector_to_array_and_back() {
+    let a = Vector::new(vec![1.0, 2.0, 3.0]);
+
+    let arr = Array1::from(a);
+    assert_eq!(arr.len(), 3);
+
+    let b = Vector::from(arr.slice_move(s![..;-1]));
+    assert_eq!(*b.data(), vec![3.0, 2.0, 1.0]);
+}
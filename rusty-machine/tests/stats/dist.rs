@@ -0,0 +1,124 @@
+use rm::linalg::matrix::Matrix;
+use rm::linalg::vector::Vector;
+use rm::stats::dist::{Categorical, Dirichlet, Distribution, Gamma, MultivariateNormal};
+use rm::stats::dist::gamma::ln_gamma;
+
+use std::f64;
+use std::f64::consts::PI;
+
+use rand::{SeedableRng, StdRng};
+use rand::distributions::IndependentSample;
+
+fn rng() -> StdRng {
+    let seed: &[usize] = &[7];
+    SeedableRng::from_seed(seed)
+}
+
+#[test]
+fn ln_gamma_values() {
+    assert!(ln_gamma(1.0).abs() < 1e-12);
+    assert!((ln_gamma(0.5) - PI.sqrt().ln()).abs() < 1e-12);
+    assert!((ln_gamma(10.0) - 362880f64.ln()).abs() < 1e-10);
+}
+
+#[test]
+fn gamma_cdf_and_samples() {
+    // Shape 2 with unit scale has cdf 1 - (1 + x)e^-x.
+    let gamma = Gamma::new(2.0, 1.0);
+    for &x in &[0.5f64, 3.0, 10.0] {
+        let expected = 1.0 - (1.0 + x) * (-x).exp();
+        assert!((gamma.cdf(x) - expected).abs() < 1e-12);
+    }
+
+    let gamma = Gamma::new(3.0, 2.0);
+    let mut rng = rng();
+    let mean = (0..5000).map(|_| gamma.ind_sample(&mut rng)).sum::<f64>() / 5000.0;
+    assert!((mean - 6.0).abs() < 0.3);
+}
+
+#[test]
+fn gamma_edge_of_support() {
+    // With shape one the density at zero is the rate.
+    assert!((Gamma::new(1.0, 2.0).pdf(0.0) - 0.5).abs() < 1e-12);
+    assert_eq!(Gamma::new(2.0, 1.0).pdf(0.0), 0.0);
+
+    let gamma = Gamma::new(3.0, 2.0);
+    assert_eq!(gamma.cdf(0.0), 0.0);
+    assert_eq!(gamma.cdf(f64::INFINITY), 1.0);
+    assert!(gamma.cdf(f64::NAN).is_nan());
+    assert!((Gamma::new(1e6, 1.0).cdf(1e6) - 0.5).abs() < 1e-2);
+}
+
+#[test]
+fn categorical_samples() {
+    let cat = Categorical::new(&[0.0, 1.0, 3.0]);
+    assert_eq!(cat.pdf(0), 0.0);
+    assert_eq!(cat.cdf(1), 0.25);
+    assert_eq!(cat.pdf(5), 0.0);
+
+    let mut rng = rng();
+    let mut counts = [0usize; 3];
+    for _ in 0..4000 {
+        counts[cat.ind_sample(&mut rng)] += 1;
+    }
+    assert_eq!(counts[0], 0);
+    assert!((counts[2] as f64 / 4000.0 - 0.75).abs() < 0.05);
+}
+
+#[test]
+#[should_panic]
+fn categorical_zero_weights() {
+    let _ = Categorical::new(&[0.0, 0.0]);
+}
+
+#[test]
+fn dirichlet_samples_on_simplex() {
+    let dir = Dirichlet::new(vec![1.0, 2.0, 7.0]);
+    let mut rng = rng();
+
+    let mut total = Vector::zeros(3);
+    for _ in 0..2000 {
+        let x = dir.ind_sample(&mut rng);
+        assert!((x.sum() - 1.0).abs() < 1e-12);
+        assert!(x.data().iter().all(|&p| p >= 0.0));
+        total = total + x;
+    }
+
+    let mean = total / 2000.0;
+    assert!((mean[2] - 0.7).abs() < 0.03);
+
+    // Beta(2, 2) has density 6x(1-x).
+    let beta = Dirichlet::new(vec![2.0, 2.0]);
+    assert!((beta.pdf(&Vector::new(vec![0.3, 0.7])) - 6.0 * 0.3 * 0.7).abs() < 1e-12);
+
+    // A unit concentration gives a finite density on the boundary.
+    let dir = Dirichlet::new(vec![1.0, 2.0]);
+    assert!((dir.pdf(&Vector::new(vec![0.0, 1.0])) - 2.0).abs() < 1e-12);
+}
+
+#[test]
+fn multivariate_normal_density_and_samples() {
+    let mean = Vector::new(vec![1.0, -1.0]);
+    let cov = Matrix::new(2, 2, vec![2.0, 0.6, 0.6, 1.0]);
+    let mvn = MultivariateNormal::new(mean, cov);
+
+    // Compare with the explicit inverse and determinant.
+    let x = Vector::new(vec![0.5, 0.0]);
+    let det: f64 = 2.0 * 1.0 - 0.6 * 0.6;
+    let inv = Matrix::new(2, 2, vec![1.0, -0.6, -0.6, 2.0]) / det;
+    let d = Vector::new(vec![-0.5, 1.0]);
+    let expected = -(2.0 * PI).ln() - 0.5 * det.ln() - 0.5 * d.dot(&(&inv * &d));
+    assert!((mvn.logpdf(&x) - expected).abs() < 1e-10);
+
+    let mut rng = rng();
+    let n = 5000;
+    let samples: Vec<Vector<f64>> = (0..n).map(|_| mvn.ind_sample(&mut rng)).collect();
+    let cov_01 = samples.iter().map(|s| (s[0] - 1.0) * (s[1] + 1.0)).sum::<f64>() / n as f64;
+    assert!((cov_01 - 0.6).abs() < 0.1);
+}
+
+#[test]
+#[should_panic]
+fn multivariate_normal_not_positive_definite() {
+    let _ = MultivariateNormal::new(Vector::zeros(2), Matrix::new(2, 2, vec![1.0, 2.0, 2.0, 1.0]));
+}